@@ -9,3 +9,4 @@
 pub mod list;
 pub mod resolve;
 pub mod show;
+pub mod tap;
@@ -0,0 +1,136 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use std::time::UNIX_EPOCH;
+
+use hyperactor::introspect::IntrospectMessage;
+use hyperactor::mailbox::TapSpec;
+use hyperactor::mailbox::TappedMessage;
+use hyperactor::reference;
+use hyperactor_mesh::config::MESH_ADMIN_SINGLE_HOST_TIMEOUT;
+use hyperactor_mesh::context;
+
+#[derive(clap::Args, Debug)]
+pub struct TapCommand {
+    /// The actor to tap, or one of its ports to tap only that port.
+    reference: reference::Reference,
+
+    /// Capture every n-th message.
+    #[arg(long, default_value_t = 1)]
+    sample_every: u64,
+
+    /// Number of captured messages the tapped actor buffers before
+    /// dropping new ones.
+    #[arg(long, default_value_t = 256)]
+    buffer: usize,
+
+    /// Stop after this many messages.
+    #[arg(long)]
+    limit: Option<u64>,
+}
+
+impl TapCommand {
+    pub async fn run(self) -> anyhow::Result<()> {
+        let (actor_id, port) = match self.reference {
+            reference::Reference::Actor(actor_id) => (actor_id, None),
+            reference::Reference::Port(port_id) => {
+                let index = port_id.index();
+                (port_id.into_actor_id(), Some(index))
+            }
+            ref_ => {
+                anyhow::bail!(
+                    "cannot tap reference {}: unsupported reference kind '{}'",
+                    ref_,
+                    ref_.kind()
+                );
+            }
+        };
+
+        let cx = context().await;
+        let client = cx.actor_instance;
+        let reply_timeout = hyperactor_config::global::get(MESH_ADMIN_SINGLE_HOST_TIMEOUT);
+
+        let introspect = reference::PortRef::<IntrospectMessage>::attest_message_port(&actor_id);
+        let (capture_port, mut capture_rx) = client.open_port::<TappedMessage>();
+        let (reply_port, reply_rx) = client.open_once_port::<Result<u64, String>>();
+        introspect.send(
+            &client,
+            IntrospectMessage::Tap {
+                spec: TapSpec {
+                    port,
+                    sample_every: self.sample_every,
+                    buffer: self.buffer,
+                    limit: self.limit,
+                },
+                port: capture_port.bind(),
+                reply: reply_port.bind(),
+            },
+        )?;
+        let tap_id = tokio::time::timeout(reply_timeout, reply_rx.recv())
+            .await
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "timed out after {:?} waiting for {} to install the tap",
+                    reply_timeout,
+                    actor_id
+                )
+            })??
+            .map_err(|e| anyhow::anyhow!("cannot tap {}: {}", actor_id, e))?;
+        eprintln!("tapping {} (tap {})", actor_id, tap_id);
+
+        let mut received = 0;
+        loop {
+            let message = tokio::select! {
+                message = capture_rx.recv() => message?,
+                _ = tokio::signal::ctrl_c() => break,
+            };
+            println!("{}", serde_json::to_string(&render(message))?);
+            received += 1;
+            if self.limit.is_some_and(|limit| received >= limit) {
+                break;
+            }
+        }
+
+        let (reply_port, reply_rx) = client.open_once_port::<bool>();
+        introspect.send(
+            &client,
+            IntrospectMessage::Untap {
+                tap_id,
+                reply: reply_port.bind(),
+            },
+        )?;
+        // The tap may already have removed itself after reaching its
+        // limit, and it removes itself once its capture port goes away,
+        // so neither the reply nor its absence is interesting.
+        let _ = tokio::time::timeout(reply_timeout, reply_rx.recv()).await;
+
+        Ok(())
+    }
+}
+
+/// Render a captured message as a JSON object, embedding its payload
+/// as JSON rather than as a string.
+fn render(message: TappedMessage) -> serde_json::Value {
+    let timestamp_ms = message
+        .timestamp
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64);
+    let payload = message.payload.map(|payload| {
+        serde_json::from_str(&payload).unwrap_or(serde_json::Value::String(payload))
+    });
+    serde_json::json!({
+        "seq": message.seq,
+        "dropped": message.dropped,
+        "timestamp_ms": timestamp_ms,
+        "sender": message.sender.to_string(),
+        "dest": message.dest.to_string(),
+        "headers": message.headers,
+        "typename": message.typename,
+        "payload": payload,
+    })
+}
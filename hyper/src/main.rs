@@ -20,6 +20,7 @@ use tokio as _;
 use crate::commands::list::ListCommand;
 use crate::commands::resolve::ResolveCommand;
 use crate::commands::show::ShowCommand;
+use crate::commands::tap::TapCommand;
 
 #[derive(Parser)]
 #[command()]
@@ -38,6 +39,9 @@ enum Command {
 
     #[clap(about = "Resolve a MAST job handle to a mesh admin URL")]
     Resolve(ResolveCommand),
+
+    #[clap(about = "Stream the messages delivered to an actor")]
+    Tap(TapCommand),
 }
 
 #[cfg(fbcode_build)]
@@ -60,6 +64,7 @@ async fn run() -> Result<(), anyhow::Error> {
         Command::Show(command) => command.run().await,
         Command::List(command) => command.run().await,
         Command::Resolve(command) => command.run().await,
+        Command::Tap(command) => command.run().await,
     };
 
     // Allow the channel layer to flush pending acks before exit.
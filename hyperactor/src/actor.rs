@@ -820,6 +820,8 @@ mod tests {
     use crate::mailbox::BoxableMailboxSender as _;
     use crate::mailbox::MailboxSender;
     use crate::mailbox::PortLocation;
+    use crate::mailbox::TapSpec;
+    use crate::mailbox::TappedMessage;
     use crate::mailbox::monitored_return_handle;
     use crate::ordering::SEQ_INFO;
     use crate::ordering::SeqInfo;
@@ -1621,6 +1623,62 @@ mod tests {
         handle.await;
     }
 
+    /// `IntrospectMessage::Tap` is refused unless message taps are
+    /// enabled, and otherwise captures the actor's incoming messages.
+    #[tokio::test]
+    async fn test_introspect_tap_requires_opt_in() {
+        let proc = Proc::local();
+        let (client, _) = proc.instance("client").unwrap();
+        let (tx, _rx) = client.open_port::<u64>();
+        let handle = proc
+            .spawn::<EchoActor>("echo_tap", EchoActor(tx.bind()))
+            .unwrap();
+        let introspect =
+            reference::PortRef::<IntrospectMessage>::attest_message_port(handle.actor_id());
+        let (capture_port, mut capture_rx) = client.open_port::<TappedMessage>();
+        let tap = |reply| IntrospectMessage::Tap {
+            spec: TapSpec::default(),
+            port: capture_port.bind(),
+            reply,
+        };
+
+        let (reply_port, reply_rx) = client.open_once_port::<Result<u64, String>>();
+        introspect.send(&client, tap(reply_port.bind())).unwrap();
+        let err = reply_rx.recv().await.unwrap().unwrap_err();
+        assert!(err.contains("HYPERACTOR_ENABLE_MESSAGE_TAPS"), "{err}");
+
+        let config = hyperactor_config::global::lock();
+        let _guard = config.override_key(config::ENABLE_MESSAGE_TAPS, true);
+        let (reply_port, reply_rx) = client.open_once_port::<Result<u64, String>>();
+        introspect.send(&client, tap(reply_port.bind())).unwrap();
+        reply_rx.recv().await.unwrap().unwrap();
+
+        // Introspection requests are posted to the actor's mailbox like
+        // any other message, so the tap captures them.
+        let (reply_port, _reply_rx) = client.open_once_port::<IntrospectResult>();
+        introspect
+            .send(
+                &client,
+                IntrospectMessage::Query {
+                    view: IntrospectView::Actor,
+                    reply: reply_port.bind(),
+                },
+            )
+            .unwrap();
+        let tapped = timeout(Duration::from_secs(5), capture_rx.recv())
+            .await
+            .expect("timed out waiting for tapped message")
+            .unwrap();
+        assert_eq!(tapped.dest, *introspect.port_id());
+        assert_eq!(
+            tapped.typename.as_deref(),
+            Some(IntrospectMessage::typename())
+        );
+
+        handle.drain_and_stop("test").unwrap();
+        handle.await;
+    }
+
     /// Helper: look up an attr in the attrs JSON by short name.
     fn attrs_get(attrs_json: &str, short_name: &str) -> Option<serde_json::Value> {
         use hyperactor_config::INTROSPECT;
@@ -19,6 +19,7 @@ use hyperactor_config::AttrValue;
 use hyperactor_config::CONFIG;
 use hyperactor_config::ConfigAttr;
 use hyperactor_config::attrs::declare_attrs;
+use hyperactor_config::validate::Validator;
 use serde::Deserialize;
 use serde::Serialize;
 use typeuri::Named;
@@ -106,6 +107,7 @@ declare_attrs! {
         Some("HYPERACTOR_CODEC_MAX_FRAME_LENGTH".to_string()),
         Some("codec_max_frame_length".to_string()),
     ))
+    @validate(Validator::range(1..))
     pub attr CODEC_MAX_FRAME_LENGTH: usize = 10 * 1024 * 1024 * 1024; // 10 GiB
 
     /// Message delivery timeout
@@ -143,6 +145,7 @@ declare_attrs! {
         Some("HYPERACTOR_MESSAGE_ACK_EVERY_N_MESSAGES".to_string()),
         Some("message_ack_every_n_messages".to_string()),
     ))
+    @validate(Validator::range(1..))
     pub attr MESSAGE_ACK_EVERY_N_MESSAGES: u64 = 1000;
 
     /// Default hop Time-To-Live for message envelopes.
@@ -203,6 +206,7 @@ declare_attrs! {
         Some("HYPERACTOR_MESSAGE_LATENCY_SAMPLING_RATE".to_string()),
         Some("message_latency_sampling_rate".to_string()),
     ))
+    @validate(Validator::range(0.0..=1.0))
     pub attr MESSAGE_LATENCY_SAMPLING_RATE: f32 = 0.01;
 
     /// Whether to enable dest actor reordering buffer.
@@ -240,6 +244,25 @@ declare_attrs! {
     ))
     pub attr FORWARDER_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
 
+    /// How long a remote watch replica waits for an event before it
+    /// re-subscribes to its publisher. This lets replicas recover from
+    /// a restarted publisher or a dropped subscription without an
+    /// explicit resync. Zero disables re-subscription.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_WATCH_REPLICA_RESYNC_INTERVAL".to_string()),
+        Some("watch_replica_resync_interval".to_string()),
+    ))
+    pub attr WATCH_REPLICA_RESYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+    /// Whether actors accept `IntrospectMessage::Tap` requests. Taps
+    /// expose message payloads to anyone who can reach the actor, so
+    /// they are disabled unless explicitly enabled.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_ENABLE_MESSAGE_TAPS".to_string()),
+        Some("enable_message_taps".to_string()),
+    ))
+    pub attr ENABLE_MESSAGE_TAPS: bool = false;
+
     /// Path to TLS certificate file for the 'tls' transport.
     @meta(CONFIG = ConfigAttr::new(
         Some("HYPERACTOR_TLS_CERT".to_string()),
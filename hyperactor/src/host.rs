@@ -286,6 +286,20 @@ impl<M: ProcManager> Host<M> {
         Ok((proc_id, ready.agent_ref().clone()))
     }
 
+    /// Forget a proc previously spawned with `name`, unbinding it
+    /// from the router so that a replacement can be spawned under the
+    /// same name (and thus the same ProcId). The caller must ensure
+    /// that the proc has already terminated. Returns false if no such
+    /// proc is known.
+    pub fn forget_proc(&mut self, name: &str) -> bool {
+        if !self.procs.remove(name) {
+            return false;
+        }
+        let proc_id = reference::ProcId::with_name(self.frontend_addr.clone(), name);
+        self.router.unbind(&proc_id.into());
+        true
+    }
+
     fn forwarder(&self) -> ProcOrDial {
         ProcOrDial {
             service_proc: self.service_proc.clone(),
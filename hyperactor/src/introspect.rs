@@ -24,6 +24,12 @@
 //! Callers navigate topology by fetching an [`IntrospectResult`] and
 //! following its `children` references.
 //!
+//! The introspect task also installs message taps
+//! ([`IntrospectMessage::Tap`]), which mirror the actor's incoming
+//! traffic to a capture port; see [`crate::mailbox::tap`]. Taps are
+//! refused unless [`ENABLE_MESSAGE_TAPS`](crate::config::ENABLE_MESSAGE_TAPS)
+//! is set.
+//!
 //! # Design Invariants
 //!
 //! The introspection subsystem maintains twelve invariants (S1--S12).
@@ -132,6 +138,8 @@ use serde::Serialize;
 use typeuri::Named;
 
 use crate::InstanceCell;
+use crate::mailbox::TapSpec;
+use crate::mailbox::TappedMessage;
 use crate::reference;
 
 /// Typed reference to an introspectable entity.
@@ -638,6 +646,24 @@ pub enum IntrospectMessage {
         /// Reply port receiving the child's description.
         reply: reference::OncePortRef<IntrospectResult>,
     },
+    /// "Mirror the messages delivered to you onto this port." See
+    /// [`crate::mailbox::tap`].
+    Tap {
+        /// What to capture.
+        spec: TapSpec,
+        /// Port receiving the captured messages.
+        port: reference::PortRef<TappedMessage>,
+        /// Reply port receiving the tap's id, or why the tap was
+        /// refused.
+        reply: reference::OncePortRef<Result<u64, String>>,
+    },
+    /// "Remove a tap."
+    Untap {
+        /// The id returned for the tap.
+        tap_id: u64,
+        /// Reply port receiving whether the tap was still installed.
+        reply: reference::OncePortRef<bool>,
+    },
 }
 wirevalue::register_type!(IntrospectMessage);
 
@@ -935,6 +961,26 @@ pub(crate) async fn serve_introspect(
                     crate::mailbox::monitored_return_handle(),
                 )
             }
+            IntrospectMessage::Tap { spec, port, reply } => {
+                let tap_id = if hyperactor_config::global::get(crate::config::ENABLE_MESSAGE_TAPS) {
+                    mailbox.tap(spec, port).map_err(|e| e.to_string())
+                } else {
+                    Err(
+                        "message taps are disabled; set HYPERACTOR_ENABLE_MESSAGE_TAPS to enable them"
+                            .to_string(),
+                    )
+                };
+                mailbox.serialize_and_send_once(
+                    reply,
+                    tap_id,
+                    crate::mailbox::monitored_return_handle(),
+                )
+            }
+            IntrospectMessage::Untap { tap_id, reply } => mailbox.serialize_and_send_once(
+                reply,
+                mailbox.untap(tap_id),
+                crate::mailbox::monitored_return_handle(),
+            ),
         };
         if let Err(e) = result {
             tracing::debug!("introspect reply failed: {e}");
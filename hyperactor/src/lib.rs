@@ -86,6 +86,7 @@ pub mod sync;
 /// Test utilities.
 pub mod testing;
 pub mod time;
+pub mod watch;
 
 #[cfg(fbcode_build)]
 mod meta;
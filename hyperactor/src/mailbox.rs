@@ -136,6 +136,10 @@ pub use mailbox_admin_message::MailboxAdminMessage;
 pub use mailbox_admin_message::MailboxAdminMessageHandler;
 /// For message headers and latency tracking.
 pub mod headers;
+/// For [`TapSpec`] and [`TappedMessage`], live capture of delivered messages.
+pub mod tap;
+pub use tap::TapSpec;
+pub use tap::TappedMessage;
 
 /// Message collects the necessary requirements for messages that are deposited
 /// into mailboxes.
@@ -184,6 +188,40 @@ pub enum DeliveryError {
     /// The message went through too many hops and has expired.
     #[error("ttl expired")]
     TtlExpired,
+
+    /// The message was serialized with a different schema of its type
+    /// than the receiver's, and the receiver has no migration for it.
+    #[error(
+        "schema mismatch for {typename}: receiver expects fingerprint {expected:#018x}, \
+         message has {actual:#018x}"
+    )]
+    SchemaMismatch {
+        /// The message type.
+        typename: String,
+        /// The receiver's schema fingerprint.
+        expected: u64,
+        /// The sender's schema fingerprint.
+        actual: u64,
+    },
+}
+
+impl From<&MailboxSenderError> for DeliveryError {
+    fn from(error: &MailboxSenderError) -> Self {
+        if let MailboxSenderErrorKind::Deserialize(_, err) = error.kind()
+            && let Some(wirevalue::Error::SchemaMismatch {
+                typename,
+                expected,
+                actual,
+            }) = err.downcast_ref::<wirevalue::Error>()
+        {
+            return DeliveryError::SchemaMismatch {
+                typename: typename.to_string(),
+                expected: *expected,
+                actual: *actual,
+            };
+        }
+        DeliveryError::Mailbox(format!("{}", error))
+    }
 }
 
 /// An envelope that carries a message destined to a remote actor.
@@ -546,6 +584,10 @@ pub enum MailboxErrorKind {
     /// The owning actor terminated (either stopped or failed).
     #[error("owner terminated: {0}")]
     OwnerTerminated(ActorStatus),
+
+    /// The operation must be called from within a Tokio runtime.
+    #[error("no tokio runtime: {0}")]
+    NoRuntime(#[source] tokio::runtime::TryCurrentError),
 }
 
 impl MailboxError {
@@ -1585,6 +1627,35 @@ impl Mailbox {
             panic!("mailbox with owner {} already closed", self.actor_id());
         }
         let _ = closed.insert(status);
+        self.inner.update_taps(|taps| taps.clear());
+    }
+
+    /// Mirror the envelopes delivered to this mailbox onto `port`, as
+    /// described by `spec`. Returns an id that can be passed to
+    /// [`Mailbox::untap`]. See [`tap`] for the guarantees. Fails if
+    /// called outside a Tokio runtime, which forwards the captures.
+    pub fn tap(
+        &self,
+        spec: TapSpec,
+        port: reference::PortRef<TappedMessage>,
+    ) -> Result<u64, MailboxError> {
+        let runtime = tokio::runtime::Handle::try_current().map_err(|err| {
+            MailboxError::new(self.actor_id().clone(), MailboxErrorKind::NoRuntime(err))
+        })?;
+        let id = self.inner.next_tap.fetch_add(1, Ordering::Relaxed);
+        let tap = tap::ActiveTap::spawn(&runtime, &self.inner, id, spec, port);
+        self.inner.update_taps(|taps| taps.push(tap));
+        Ok(id)
+    }
+
+    /// Remove a tap installed by [`Mailbox::tap`]. Returns whether the
+    /// tap was still installed.
+    pub fn untap(&self, tap_id: u64) -> bool {
+        self.inner.update_taps(|taps| {
+            let before = taps.len();
+            taps.retain(|tap| tap.id != tap_id);
+            taps.len() != before
+        })
     }
 }
 
@@ -1682,6 +1753,13 @@ impl MailboxSender for Mailbox {
                     }
                 }
 
+                // Untapped mailboxes, the common case, skip the lock.
+                if self.inner.num_taps.load(Ordering::Acquire) > 0 {
+                    for tap in self.inner.taps.read().unwrap().iter() {
+                        tap.offer(&envelope);
+                    }
+                }
+
                 let (metadata, data) = envelope.open();
                 let MessageMetadata {
                     mut headers,
@@ -1740,7 +1818,7 @@ impl MailboxSender for Mailbox {
                         headers,
                     }) => {
                         entry.remove();
-                        let err = DeliveryError::Mailbox(format!("{}", sender_error));
+                        let err = DeliveryError::from(&sender_error);
 
                         MessageEnvelope::seal(
                             MessageMetadata {
@@ -2424,6 +2502,16 @@ struct State {
     /// If a value is present, the mailbox has been closed with the provided
     /// status, and any subsequent `Mailbox::post_unchecked` calls will fail.
     closed: RwLock<Option<ActorStatus>>,
+
+    /// Taps installed on this mailbox. Modified only through
+    /// [`State::update_taps`].
+    taps: RwLock<Vec<tap::ActiveTap>>,
+
+    /// The number of entries in `taps`, readable without the lock.
+    num_taps: AtomicUsize,
+
+    /// The next tap ID to allocate.
+    next_tap: AtomicU64,
 }
 
 impl State {
@@ -2437,9 +2525,20 @@ impl State {
             next_port: AtomicU64::new(USER_PORT_OFFSET),
             forwarder,
             closed: RwLock::new(None),
+            taps: RwLock::new(Vec::new()),
+            num_taps: AtomicUsize::new(0),
+            next_tap: AtomicU64::new(0),
         }
     }
 
+    /// Apply `f` to the installed taps, keeping `num_taps` in sync.
+    fn update_taps<R>(&self, f: impl FnOnce(&mut Vec<tap::ActiveTap>) -> R) -> R {
+        let mut taps = self.taps.write().unwrap();
+        let result = f(&mut taps);
+        self.num_taps.store(taps.len(), Ordering::Release);
+        result
+    }
+
     /// Allocate a fresh port.
     fn allocate_port(&self) -> u64 {
         self.next_port.fetch_add(1, Ordering::SeqCst)
@@ -4289,6 +4388,110 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_mailbox_post_schema_mismatch() {
+        mod old {
+            #[derive(Debug, serde::Serialize, serde::Deserialize, typeuri::Named)]
+            #[named(schema, name = "hyperactor::mailbox::tests::Versioned")]
+            pub struct Versioned {
+                pub id: u32,
+            }
+        }
+        #[derive(Debug, serde::Serialize, serde::Deserialize, typeuri::Named)]
+        #[named(schema, name = "hyperactor::mailbox::tests::Versioned")]
+        struct Versioned {
+            id: u64,
+            label: String,
+        }
+
+        let actor_id = test_actor_id("0", "versioned_actor");
+        let mailbox = Mailbox::new_detached(actor_id.clone());
+        let (user_port, _user_rx) = mailbox.open_port::<Versioned>();
+        let (return_handle, mut return_rx) = undeliverable::new_undeliverable_port();
+
+        let envelope = MessageEnvelope::serialize(
+            actor_id,
+            user_port.bind().port_id().clone(),
+            &old::Versioned { id: 1 },
+            Flattrs::new(),
+        )
+        .expect("serialize");
+        mailbox.post(envelope, return_handle);
+
+        let undeliverable = tokio::time::timeout(Duration::from_secs(1), return_rx.recv())
+            .await
+            .expect("timed out waiting for undeliverable")
+            .expect("return port closed");
+        assert_eq!(
+            undeliverable.0.errors().last(),
+            Some(&DeliveryError::SchemaMismatch {
+                typename: "hyperactor::mailbox::tests::Versioned".to_string(),
+                expected: Versioned::schema_fingerprint().unwrap(),
+                actual: old::Versioned::schema_fingerprint().unwrap(),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mailbox_tap() {
+        let actor_id = test_actor_id("0", "tapped_actor");
+        let mailbox = Mailbox::new_detached(actor_id.clone());
+        let (user_port, mut user_rx) = mailbox.open_port::<TapSpec>();
+        let (other_port, _other_rx) = mailbox.open_port::<TapSpec>();
+        let (capture_port, mut capture_rx) = mailbox.open_port::<TappedMessage>();
+
+        let spec = TapSpec {
+            port: Some(user_port.bind().port_id().index()),
+            sample_every: 2,
+            limit: Some(2),
+            ..Default::default()
+        };
+        let tap_id = mailbox.tap(spec, capture_port.bind()).unwrap();
+
+        let post = |port: &PortHandle<TapSpec>, limit: u64| {
+            let envelope = MessageEnvelope::serialize(
+                actor_id.clone(),
+                port.bind().port_id().clone(),
+                &TapSpec {
+                    limit: Some(limit),
+                    ..Default::default()
+                },
+                Flattrs::new(),
+            )
+            .unwrap();
+            mailbox.post(envelope, monitored_return_handle());
+        };
+        post(&other_port, 100);
+        for limit in 0..6 {
+            post(&user_port, limit);
+        }
+        for _ in 0..6 {
+            user_rx.recv().await.unwrap();
+        }
+
+        // Every other envelope to the tapped port, up to the limit.
+        for expected in [0, 2] {
+            let tapped = tokio::time::timeout(Duration::from_secs(1), capture_rx.recv())
+                .await
+                .expect("timed out waiting for tapped message")
+                .unwrap();
+            assert_eq!(tapped.seq, expected);
+            assert_eq!(tapped.dropped, 0);
+            assert_eq!(&tapped.dest, user_port.bind().port_id());
+            assert_eq!(tapped.typename.as_deref(), Some(TapSpec::typename()));
+            let payload: serde_json::Value =
+                serde_json::from_str(tapped.payload.as_deref().unwrap()).unwrap();
+            assert_eq!(payload["limit"], expected);
+        }
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), capture_rx.recv())
+                .await
+                .is_err()
+        );
+        // The tap removed itself when it reached its limit.
+        assert!(!mailbox.untap(tap_id));
+    }
+
     #[tokio::test]
     async fn test_mailbox_post_fails_when_actor_failed() {
         use crate::actor::ActorErrorKind;
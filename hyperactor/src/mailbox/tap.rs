@@ -0,0 +1,244 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Message taps: live capture of the traffic delivered to a mailbox.
+//!
+//! A tap mirrors each [`MessageEnvelope`] delivered to a mailbox (or
+//! only those for one of its ports) onto a capture port, as a
+//! [`TappedMessage`] whose payload has been transcoded to JSON through
+//! the `wirevalue` type registry. Taps are installed through
+//! [`IntrospectMessage::Tap`](crate::introspect::IntrospectMessage::Tap),
+//! which is served outside the actor loop, so any actor can be tapped
+//! without cooperation from its handlers. Such requests are refused
+//! unless [`ENABLE_MESSAGE_TAPS`](crate::config::ENABLE_MESSAGE_TAPS)
+//! is set in the tapped proc.
+//!
+//! ## Tap invariants (TAP-*)
+//!
+//! - **TAP-1 (non-blocking):** A tap never delays delivery. Envelopes
+//!   are queued in a bounded buffer of [`TapSpec::buffer`] entries;
+//!   envelopes arriving while it is full are dropped and counted in
+//!   the next captured message's `dropped`.
+//! - **TAP-2 (sampling):** Only every [`TapSpec::sample_every`]-th
+//!   matching envelope is captured. Sequence numbers count all
+//!   matching envelopes, so gaps show what was skipped.
+//! - **TAP-3 (self-limiting):** A tap is removed when its capture port
+//!   becomes unreachable, after [`TapSpec::limit`] captures, when it is
+//!   explicitly removed, or when the mailbox closes.
+//! - **TAP-4 (no feedback):** Envelopes addressed to a tap's own
+//!   capture port are never captured by that tap.
+//! - **TAP-5 (serialized traffic only):** Only envelopes posted to the
+//!   mailbox are seen. Messages sent through a local
+//!   [`PortHandle`](crate::mailbox::PortHandle) bypass serialization
+//!   and are not captured.
+
+use std::sync::Arc;
+use std::sync::Weak;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::SystemTime;
+
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::mpsc;
+use typeuri::Named;
+
+use crate::mailbox::Mailbox;
+use crate::mailbox::MessageEnvelope;
+use crate::mailbox::PortSender as _;
+use crate::mailbox::State;
+use crate::mailbox::undeliverable;
+use crate::reference;
+
+/// What a tap captures, and how much.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Named)]
+pub struct TapSpec {
+    /// Only capture envelopes for this port index; all ports if `None`.
+    pub port: Option<u64>,
+    /// Capture every n-th matching envelope. Values below 1 are
+    /// treated as 1.
+    pub sample_every: u64,
+    /// Maximum number of captured envelopes queued for the capture
+    /// port.
+    pub buffer: usize,
+    /// Remove the tap after this many captures.
+    pub limit: Option<u64>,
+}
+wirevalue::register_type!(TapSpec);
+
+impl Default for TapSpec {
+    fn default() -> Self {
+        Self {
+            port: None,
+            sample_every: 1,
+            buffer: 256,
+            limit: None,
+        }
+    }
+}
+
+/// A captured envelope, decoded for display.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Named)]
+pub struct TappedMessage {
+    /// The envelope's position among all envelopes matching the tap.
+    pub seq: u64,
+    /// Matching envelopes dropped since the previous capture because
+    /// the tap's buffer was full.
+    pub dropped: u64,
+    /// When the envelope was delivered.
+    pub timestamp: SystemTime,
+    /// The envelope's sender.
+    pub sender: reference::ActorId,
+    /// The envelope's destination.
+    pub dest: reference::PortId,
+    /// The envelope's headers, as `key=value` pairs.
+    pub headers: String,
+    /// The message type, if it is registered in the tapped binary.
+    pub typename: Option<String>,
+    /// The message as JSON, if it could be transcoded.
+    pub payload: Option<String>,
+}
+wirevalue::register_type!(TappedMessage);
+
+impl TappedMessage {
+    fn decode(entry: TapEntry, dropped: u64) -> Self {
+        let TapEntry {
+            seq,
+            timestamp,
+            envelope,
+        } = entry;
+        let payload = envelope.data().dump().ok().map(|value| value.to_string());
+        Self {
+            seq,
+            dropped,
+            timestamp,
+            sender: envelope.sender().clone(),
+            dest: envelope.dest().clone(),
+            headers: envelope.headers().to_string(),
+            typename: envelope.data().typename().map(str::to_string),
+            payload,
+        }
+    }
+}
+
+struct TapEntry {
+    seq: u64,
+    timestamp: SystemTime,
+    envelope: MessageEnvelope,
+}
+
+/// A tap installed on a mailbox.
+pub(crate) struct ActiveTap {
+    pub(crate) id: u64,
+    spec: TapSpec,
+    capture: reference::PortId,
+    matched: AtomicU64,
+    dropped: Arc<AtomicU64>,
+    tx: mpsc::Sender<TapEntry>,
+}
+
+impl ActiveTap {
+    /// Install a tap on the mailbox owning `state`, spawning the task
+    /// that forwards its captures to `port` on `runtime`.
+    pub(super) fn spawn(
+        runtime: &tokio::runtime::Handle,
+        state: &Arc<State>,
+        id: u64,
+        spec: TapSpec,
+        port: reference::PortRef<TappedMessage>,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel(spec.buffer.max(1));
+        let dropped = Arc::new(AtomicU64::new(0));
+        runtime.spawn(forward(
+            Arc::downgrade(state),
+            id,
+            spec.limit,
+            port.clone(),
+            rx,
+            dropped.clone(),
+        ));
+        Self {
+            id,
+            spec,
+            capture: port.port_id().clone(),
+            matched: AtomicU64::new(0),
+            dropped,
+            tx,
+        }
+    }
+
+    /// Offer a delivered envelope to the tap.
+    pub(crate) fn offer(&self, envelope: &MessageEnvelope) {
+        // TAP-4: never capture our own captures.
+        if envelope.dest() == &self.capture {
+            return;
+        }
+        if let Some(port) = self.spec.port
+            && envelope.dest().index() != port
+        {
+            return;
+        }
+        // TAP-2
+        let seq = self.matched.fetch_add(1, Ordering::Relaxed);
+        if seq % self.spec.sample_every.max(1) != 0 {
+            return;
+        }
+        let entry = TapEntry {
+            seq,
+            timestamp: SystemTime::now(),
+            envelope: envelope.clone(),
+        };
+        // TAP-1
+        if let Err(mpsc::error::TrySendError::Full(_)) = self.tx.try_send(entry) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Forward captures to `port` until the tap is removed (TAP-3).
+async fn forward(
+    state: Weak<State>,
+    id: u64,
+    limit: Option<u64>,
+    port: reference::PortRef<TappedMessage>,
+    mut rx: mpsc::Receiver<TapEntry>,
+    dropped: Arc<AtomicU64>,
+) {
+    let (return_handle, mut returned) = undeliverable::new_undeliverable_port();
+    let mut captured = 0;
+    loop {
+        let entry = tokio::select! {
+            entry = rx.recv() => match entry {
+                Some(entry) => entry,
+                None => break,
+            },
+            _ = returned.recv() => {
+                tracing::info!(tap_id = id, "tap capture port {} unreachable", port.port_id());
+                break;
+            }
+        };
+        // Hold only a weak reference between captures, so that the tap
+        // does not keep an otherwise-dropped mailbox alive.
+        let Some(inner) = state.upgrade() else {
+            return;
+        };
+        let mailbox = Mailbox { inner };
+        let message = TappedMessage::decode(entry, dropped.swap(0, Ordering::Relaxed));
+        if let Err(e) = mailbox.serialize_and_send(&port, message, return_handle.clone()) {
+            tracing::info!(tap_id = id, "failed to forward tapped message: {e}");
+            break;
+        }
+        captured += 1;
+        if limit.is_some_and(|limit| captured >= limit) {
+            break;
+        }
+    }
+    if let Some(inner) = state.upgrade() {
+        inner.update_taps(|taps| taps.retain(|tap| tap.id != id));
+    }
+}
@@ -216,6 +216,12 @@ struct ProcState {
     /// [`config::TERMINATED_SNAPSHOT_RETENTION`].
     terminated_snapshots: DashMap<reference::ActorId, crate::introspect::IntrospectResult>,
 
+    /// Introspection generation of the proc. Bumped after any change
+    /// to the introspectable state of the proc's actors (status,
+    /// message counters, published attrs, membership, terminated
+    /// snapshots). See [`Proc::introspect_generation`].
+    introspect_generation: AtomicU64,
+
     /// Used by root actors to send events to the actor coordinating
     /// supervision of root actors in this proc.
     supervision_coordinator_port: OnceLock<PortHandle<ActorSupervisionEvent>>,
@@ -278,6 +284,7 @@ impl Proc {
                 roots: DashMap::new(),
                 instances: DashMap::new(),
                 terminated_snapshots: DashMap::new(),
+                introspect_generation: AtomicU64::new(0),
                 supervision_coordinator_port: OnceLock::new(),
                 supervision_coordinator_actor_id: OnceLock::new(),
                 mailbox_server_handle: std::sync::Mutex::new(None),
@@ -634,6 +641,25 @@ impl Proc {
             .collect()
     }
 
+    /// The proc's introspection generation.
+    ///
+    /// The generation is bumped after every change to the
+    /// introspectable state of the proc's actors, so if two reads
+    /// return the same value, an actor payload resolved after the
+    /// first read is still current. Snapshot capture uses this to
+    /// skip resolving the actors of unchanged procs.
+    pub fn introspect_generation(&self) -> u64 {
+        self.state().introspect_generation.load(Ordering::SeqCst)
+    }
+
+    /// Record a change to the introspectable state of the proc's
+    /// actors. Must be called after the change is visible.
+    pub(crate) fn bump_introspect_generation(&self) {
+        self.state()
+            .introspect_generation
+            .fetch_add(1, Ordering::SeqCst);
+    }
+
     /// Create a child instance. Called from `Instance`.
     fn child_instance(
         &self,
@@ -1233,6 +1259,7 @@ impl<A: Actor> Drop for InstanceState<A> {
                 true
             }
         });
+        self.proc.bump_introspect_generation();
     }
 }
 
@@ -1339,6 +1366,9 @@ impl<A: Actor> Instance<A> {
     #[track_caller]
     pub fn change_status(&self, new: ActorStatus) {
         let old = self.inner.status_tx.send_replace(new.clone());
+        if old != new {
+            self.inner.proc.bump_introspect_generation();
+        }
         // 2 cases are allowed:
         // * non-terminal -> non-terminal
         // * non-terminal -> terminal
@@ -1918,6 +1948,7 @@ impl<A: Actor> Instance<A> {
                 .inner
                 .num_processed_messages
                 .fetch_add(1, Ordering::SeqCst);
+            self.inner.proc.bump_introspect_generation();
         }
 
         if need_drain {
@@ -2072,6 +2103,7 @@ impl<A: Actor> Instance<A> {
             .inner
             .total_processing_time_us
             .fetch_add(elapsed_us, Ordering::SeqCst);
+        self.inner.proc.bump_introspect_generation();
 
         if let Some(message_id) = message_id {
             notify_message_status(hyperactor_telemetry::MessageStatusEvent {
@@ -2469,6 +2501,7 @@ impl InstanceCell {
         proc.inner
             .instances
             .insert(actor_id.clone(), cell.downgrade());
+        proc.bump_introspect_generation();
         cell
     }
 
@@ -2684,6 +2717,7 @@ impl InstanceCell {
     /// Replace the published introspection attrs with a new bag.
     pub fn set_published_attrs(&self, attrs: hyperactor_config::Attrs) {
         *self.inner.published_attrs.write().unwrap() = Some(attrs);
+        self.inner.proc.bump_introspect_generation();
     }
 
     /// Set a single introspection attr, merging into the existing bag
@@ -2699,6 +2733,7 @@ impl InstanceCell {
             .unwrap()
             .get_or_insert_with(hyperactor_config::Attrs::new)
             .set(key, value);
+        self.inner.proc.bump_introspect_generation();
     }
 
     /// Read the published introspection attrs, if any.
@@ -2771,6 +2806,7 @@ impl InstanceCell {
                 snapshots.remove(&key);
             }
         }
+        self.inner.proc.bump_introspect_generation();
     }
 
     /// This is temporary so that we can share binding code between handle and instance.
@@ -2841,6 +2877,7 @@ impl Drop for InstanceCellState {
         if self.proc.inner.instances.remove(&self.actor_id).is_none() {
             tracing::error!("instance {} was dropped but not in proc", self.actor_id);
         }
+        self.proc.bump_introspect_generation();
     }
 }
 
@@ -3964,6 +4001,48 @@ mod tests {
         );
     }
 
+    // The introspection generation moves on spawn, on every handled
+    // message, on publish and on termination, and stays put while
+    // nothing happens.
+    #[async_timed_test(timeout_secs = 30)]
+    async fn test_introspect_generation_tracks_changes() {
+        let proc = Proc::local();
+        let (client, _client_handle) = proc.instance("client").unwrap();
+
+        let before_spawn = proc.introspect_generation();
+        let handle = proc.spawn::<TestActor>("actor", TestActor).unwrap();
+        let mut status = handle.status().clone();
+        status
+            .wait_for(|s| matches!(*s, ActorStatus::Idle))
+            .await
+            .unwrap();
+        let idle = proc.introspect_generation();
+        assert!(idle > before_spawn);
+        assert_eq!(proc.introspect_generation(), idle);
+
+        let (tx, rx) = oneshot::channel::<()>();
+        handle.send(&client, TestActorMessage::Reply(tx)).unwrap();
+        rx.await.unwrap();
+        status
+            .wait_for(|s| matches!(*s, ActorStatus::Idle))
+            .await
+            .unwrap();
+        let handled = proc.introspect_generation();
+        assert!(handled > idle);
+
+        handle
+            .cell()
+            .set_published_attrs(hyperactor_config::Attrs::new());
+        let published = proc.introspect_generation();
+        assert!(published > handled);
+
+        let actor_id = handle.actor_id().clone();
+        handle.drain_and_stop("test").unwrap();
+        handle.await;
+        wait_for_terminated_snapshot(&proc, &actor_id).await;
+        assert!(proc.introspect_generation() > published);
+    }
+
     // Verifies that an actor failure results in a terminated snapshot
     // being stored. The test installs a ProcSupervisionCoordinator
     // (required for failure handling), spawns an actor, triggers a
@@ -0,0 +1,607 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Remote watches: replicate a [`struct_diff_patch`] cell across actors.
+//!
+//! An actor owns a [`RemoteWatch`], which wraps a
+//! [`struct_diff_patch::watch::Watch`], and handles [`WatchSubscribe`]
+//! by calling [`RemoteWatch::subscribe`]. Other actors, on any proc,
+//! keep a [`WatchReplica`], which receives a snapshot of the value
+//! followed by the patches produced by each [`RemoteWatch::update`].
+//!
+//! ## Remote watch invariants (RW-*)
+//!
+//! - **RW-1 (snapshot first):** Every subscription, and every resync,
+//!   starts with a [`WatchEvent::Snapshot`] of the current value.
+//! - **RW-2 (sequenced patches):** Each update is published as a
+//!   [`WatchEvent::Patch`] with the next sequence number of the
+//!   watch's epoch. A patch with sequence number `n` applies to the
+//!   value at `n - 1`.
+//! - **RW-3 (resync):** A replica that observes a sequence gap, a
+//!   patch from another epoch (e.g., a restarted publisher), or a
+//!   patch that fails to apply, discards further patches and requests
+//!   a fresh snapshot. It is consistent again once the snapshot
+//!   arrives.
+//! - **RW-4 (best effort):** Publishing never blocks or fails the
+//!   publishing actor. Subscribers whose events are returned as
+//!   undeliverable are dropped (see
+//!   [`RemoteWatch::handle_undeliverable`]); such a replica
+//!   subscribes again under RW-5, or by calling
+//!   [`WatchReplica::resync`].
+//! - **RW-5 (liveness):** A replica waiting in
+//!   [`WatchReplica::changed`] that receives no event for
+//!   [`WATCH_REPLICA_RESYNC_INTERVAL`] re-subscribes to its publisher.
+//!   A dropped subscription, or a publisher restarted at the same
+//!   address, is thus picked up without an explicit resync. The
+//!   snapshot that answers such a re-subscription is reported as a
+//!   change only if its epoch or sequence number differs from the
+//!   replicated value's.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use hyperactor_config::Flattrs;
+use hyperactor_config::attrs::declare_attrs;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use struct_diff_patch::Diff;
+use struct_diff_patch::Patch as _;
+use struct_diff_patch::watch::Watch;
+use tokio::sync::broadcast;
+use typeuri::Named;
+
+use crate::RemoteMessage;
+use crate::config::WATCH_REPLICA_RESYNC_INTERVAL;
+use crate::context;
+use crate::mailbox::MailboxSenderError;
+use crate::mailbox::MessageEnvelope;
+use crate::mailbox::PortReceiver;
+use crate::mailbox::Undeliverable;
+use crate::reference;
+
+declare_attrs! {
+    /// If present in a message header, the message is a [`WatchEvent`]
+    /// sent by a [`RemoteWatch`] to one of its subscribers.
+    pub attr WATCH_EVENT: bool;
+}
+
+/// Values that can be replicated by a [`RemoteWatch`]: the value and
+/// its patches must both be serializable.
+pub trait WatchValue:
+    Diff<Patch: Clone + Debug + Serialize + DeserializeOwned + Send + Sync + 'static>
+    + Clone
+    + Debug
+    + RemoteMessage
+{
+}
+
+impl<T> WatchValue for T where
+    T: Diff<Patch: Clone + Debug + Serialize + DeserializeOwned + Send + Sync + 'static>
+        + Clone
+        + Debug
+        + RemoteMessage
+{
+}
+
+/// An event sent by a [`RemoteWatch`] to its subscribers.
+#[derive(Debug, Clone, Serialize, Deserialize, Named)]
+#[serde(bound = "")]
+pub enum WatchEvent<T: WatchValue> {
+    /// The full value at `seq` (RW-1).
+    Snapshot {
+        /// The publishing watch's epoch.
+        epoch: u64,
+        /// The sequence number of `value`.
+        seq: u64,
+        /// The current value.
+        value: T,
+    },
+    /// The patch from `seq - 1` to `seq` (RW-2).
+    Patch {
+        /// The publishing watch's epoch.
+        epoch: u64,
+        /// The sequence number of the patched value.
+        seq: u64,
+        /// The patch to apply.
+        patch: T::Patch,
+    },
+}
+
+/// Subscribe the given port to a [`RemoteWatch`], or request a fresh
+/// snapshot if it is already subscribed. Actors that own a remote
+/// watch handle this message with [`RemoteWatch::subscribe`].
+#[derive(Debug, Clone, Serialize, Deserialize, Named)]
+#[serde(bound = "")]
+pub struct WatchSubscribe<T: WatchValue>(pub reference::PortRef<WatchEvent<T>>);
+
+/// A [`Watch`] whose updates are published to remote subscribers.
+pub struct RemoteWatch<T: WatchValue> {
+    watch: Watch<T>,
+    patches: broadcast::Receiver<T::Patch>,
+    epoch: u64,
+    seq: u64,
+    subscribers: HashMap<reference::PortId, reference::PortRef<WatchEvent<T>>>,
+}
+
+impl<T: WatchValue> RemoteWatch<T> {
+    /// Create a new remote watch holding the provided initial value.
+    /// Each remote watch starts a new epoch, so that replicas of a
+    /// previous instance resync rather than apply its patches.
+    pub fn new(value: T) -> Self {
+        let watch = Watch::new(value);
+        let patches = watch.subscribe();
+        Self {
+            watch,
+            patches,
+            epoch: fastrand::u64(..),
+            seq: 0,
+            subscribers: HashMap::new(),
+        }
+    }
+
+    /// The current value of the watch.
+    pub fn value(&self) -> &T {
+        self.watch.value()
+    }
+
+    /// The underlying watch, for in-process subscribers.
+    pub fn watch(&self) -> &Watch<T> {
+        &self.watch
+    }
+
+    /// The sequence number of the current value.
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// The number of remote subscribers.
+    pub fn num_subscribers(&self) -> usize {
+        self.subscribers.len()
+    }
+
+    /// Add `port` as a subscriber, sending it a snapshot of the
+    /// current value (RW-1). Subscribing an existing subscriber again
+    /// resyncs it.
+    pub fn subscribe(&mut self, cx: &impl context::Actor, port: reference::PortRef<WatchEvent<T>>) {
+        let snapshot = WatchEvent::Snapshot {
+            epoch: self.epoch,
+            seq: self.seq,
+            value: self.watch.value().clone(),
+        };
+        self.publish(cx, std::iter::once(&port), snapshot);
+        self.subscribers.insert(port.port_id().clone(), port);
+    }
+
+    /// Remove the subscriber bound to `port_id`, returning whether it
+    /// was subscribed.
+    pub fn unsubscribe(&mut self, port_id: &reference::PortId) -> bool {
+        self.subscribers.remove(port_id).is_some()
+    }
+
+    /// Mutate the value with `f`, publishing the resulting patch to
+    /// local and remote subscribers (RW-2). If the update produced no
+    /// patch, nothing is published and the sequence number is
+    /// unchanged.
+    pub fn update(&mut self, cx: &impl context::Actor, f: impl FnOnce(&mut T)) {
+        f(&mut self.watch.update());
+        let patch = match self.patches.try_recv() {
+            Ok(patch) => Some(patch),
+            Err(broadcast::error::TryRecvError::Empty) => return,
+            // We drain the receiver on every update, so this is not
+            // expected; fall back to a snapshot rather than lose the
+            // update.
+            Err(err) => {
+                tracing::warn!(seq = self.seq, "remote watch lost its patch: {err}");
+                self.patches = self.watch.subscribe();
+                None
+            }
+        };
+        self.seq += 1;
+        let event = match patch {
+            Some(patch) => WatchEvent::Patch {
+                epoch: self.epoch,
+                seq: self.seq,
+                patch,
+            },
+            None => WatchEvent::Snapshot {
+                epoch: self.epoch,
+                seq: self.seq,
+                value: self.watch.value().clone(),
+            },
+        };
+        self.publish(cx, self.subscribers.values(), event);
+    }
+
+    /// Remove the subscriber to which `envelope` was addressed, if it
+    /// is a [`WatchEvent`] sent by this watch (RW-4). Returns whether
+    /// the envelope was handled. Actors that own a remote watch call
+    /// this from their
+    /// [`handle_undeliverable_message`](crate::Actor::handle_undeliverable_message),
+    /// falling back to the default behavior for other envelopes.
+    pub fn handle_undeliverable(&mut self, envelope: &Undeliverable<MessageEnvelope>) -> bool {
+        let Undeliverable(envelope) = envelope;
+        if envelope.headers().get(WATCH_EVENT) != Some(true) || !self.unsubscribe(envelope.dest()) {
+            return false;
+        }
+        tracing::debug!(
+            num_subscribers = self.subscribers.len(),
+            "removed unreachable remote watch subscriber {}",
+            envelope.dest()
+        );
+        true
+    }
+
+    /// Send `event` to each of `ports`, serializing it once.
+    fn publish<'a>(
+        &self,
+        cx: &impl context::Actor,
+        ports: impl IntoIterator<Item = &'a reference::PortRef<WatchEvent<T>>>,
+        event: WatchEvent<T>,
+    ) {
+        let serialized = match wirevalue::Any::serialize(&event) {
+            Ok(serialized) => serialized,
+            Err(err) => {
+                tracing::error!(
+                    seq = self.seq,
+                    "failed to serialize remote watch event: {err}"
+                );
+                return;
+            }
+        };
+        let mut headers = Flattrs::new();
+        headers.set(WATCH_EVENT, true);
+        for port in ports {
+            port.send_serialized(cx, headers.clone(), serialized.clone());
+        }
+    }
+}
+
+/// The replicated value, as of `seq` in `epoch`.
+struct Replicated<T> {
+    epoch: u64,
+    seq: u64,
+    value: T,
+}
+
+/// A replica of a [`RemoteWatch`] in another actor.
+pub struct WatchReplica<T: WatchValue> {
+    publisher: reference::PortRef<WatchSubscribe<T>>,
+    port: reference::PortRef<WatchEvent<T>>,
+    receiver: PortReceiver<WatchEvent<T>>,
+    replicated: Option<Replicated<T>>,
+    resyncing: bool,
+}
+
+impl<T: WatchValue> WatchReplica<T> {
+    /// Subscribe to the remote watch served by `publisher`. The
+    /// replica has no value until [`WatchReplica::changed`] has
+    /// received the initial snapshot.
+    pub fn subscribe(
+        cx: &impl context::Actor,
+        publisher: reference::PortRef<WatchSubscribe<T>>,
+    ) -> Result<Self, MailboxSenderError> {
+        let (handle, receiver) = crate::mailbox::open_port(cx);
+        let mut replica = Self {
+            publisher,
+            port: handle.bind(),
+            receiver,
+            replicated: None,
+            resyncing: false,
+        };
+        replica.resync(cx)?;
+        Ok(replica)
+    }
+
+    /// The replicated value, if a snapshot has been received. While
+    /// the replica is resyncing, this is the last consistent value, or
+    /// `None` if a patch failed to apply.
+    pub fn value(&self) -> Option<&T> {
+        self.replicated.as_ref().map(|replicated| &replicated.value)
+    }
+
+    /// The sequence number of the replicated value.
+    pub fn seq(&self) -> Option<u64> {
+        self.replicated.as_ref().map(|replicated| replicated.seq)
+    }
+
+    /// Whether the replica is waiting for a snapshot.
+    pub fn is_resyncing(&self) -> bool {
+        self.resyncing
+    }
+
+    /// Request a fresh snapshot from the publisher, discarding patches
+    /// until it arrives (RW-3). This also re-subscribes a replica that
+    /// the publisher has dropped (RW-4).
+    pub fn resync(&mut self, cx: &impl context::Actor) -> Result<(), MailboxSenderError> {
+        self.resyncing = true;
+        self.resubscribe(cx)
+    }
+
+    /// Wait for the next change to the replicated value, and return it.
+    /// While waiting, the replica re-subscribes whenever it has not
+    /// received an event for [`WATCH_REPLICA_RESYNC_INTERVAL`] (RW-5).
+    pub async fn changed(&mut self, cx: &impl context::Actor) -> anyhow::Result<&T> {
+        let interval = hyperactor_config::global::get(WATCH_REPLICA_RESYNC_INTERVAL);
+        loop {
+            let event = if interval.is_zero() {
+                self.receiver.recv().await?
+            } else {
+                match tokio::time::timeout(interval, self.receiver.recv()).await {
+                    Ok(event) => event?,
+                    Err(_) => {
+                        tracing::debug!(
+                            "remote watch replica received no event for {:?}, re-subscribing",
+                            interval
+                        );
+                        self.resubscribe(cx)?;
+                        continue;
+                    }
+                }
+            };
+            if self.apply(cx, event)? {
+                break;
+            }
+        }
+        Ok(self.value().expect("applied events leave a value"))
+    }
+
+    /// Ask the publisher for a snapshot, subscribing this replica if
+    /// it is not subscribed. Unlike [`WatchReplica::resync`], patches
+    /// continue to apply while the snapshot is in flight.
+    fn resubscribe(&self, cx: &impl context::Actor) -> Result<(), MailboxSenderError> {
+        self.publisher.send(cx, WatchSubscribe(self.port.clone()))
+    }
+
+    /// Apply `event`, returning whether the value changed.
+    fn apply(
+        &mut self,
+        cx: &impl context::Actor,
+        event: WatchEvent<T>,
+    ) -> Result<bool, MailboxSenderError> {
+        let (epoch, seq, patch) = match event {
+            WatchEvent::Snapshot { epoch, seq, value } => {
+                // A re-subscription (RW-5) to an unchanged publisher
+                // returns the value we already hold.
+                if !self.resyncing
+                    && let Some(replicated) = &self.replicated
+                    && replicated.epoch == epoch
+                    && replicated.seq == seq
+                {
+                    return Ok(false);
+                }
+                self.replicated = Some(Replicated { epoch, seq, value });
+                self.resyncing = false;
+                return Ok(true);
+            }
+            WatchEvent::Patch { epoch, seq, patch } => (epoch, seq, patch),
+        };
+        if self.resyncing {
+            return Ok(false);
+        }
+        let Some(replicated) = &mut self.replicated else {
+            return Ok(false);
+        };
+        if epoch == replicated.epoch && seq <= replicated.seq {
+            // Already reflected in a snapshot.
+            return Ok(false);
+        }
+        if epoch != replicated.epoch || seq != replicated.seq + 1 {
+            tracing::info!(
+                epoch,
+                seq,
+                expected_seq = replicated.seq + 1,
+                "remote watch replica out of sync, resyncing"
+            );
+            self.resync(cx)?;
+            return Ok(false);
+        }
+        if let Err(err) = patch.apply(&mut replicated.value) {
+            tracing::warn!(seq, "failed to apply remote watch patch, resyncing: {err}");
+            // The value may have been partially patched.
+            self.replicated = None;
+            self.resync(cx)?;
+            return Ok(false);
+        }
+        replicated.seq = seq;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use timed_test::async_timed_test;
+
+    use super::*;
+    use crate as hyperactor; // for macros
+    use crate::Actor;
+    use crate::Context;
+    use crate::Handler;
+    use crate::Instance;
+    use crate::Proc;
+    use crate::actor::handle_undeliverable_message;
+
+    type Table = HashMap<String, u64>;
+
+    #[hyperactor::export(handlers = [
+        WatchSubscribe<Table>,
+        (String, u64),
+        reference::OncePortRef<usize>,
+    ])]
+    struct TableActor(RemoteWatch<Table>);
+
+    #[async_trait]
+    impl Actor for TableActor {
+        async fn handle_undeliverable_message(
+            &mut self,
+            cx: &Instance<Self>,
+            envelope: Undeliverable<MessageEnvelope>,
+        ) -> Result<(), anyhow::Error> {
+            if self.0.handle_undeliverable(&envelope) {
+                Ok(())
+            } else {
+                handle_undeliverable_message(cx, envelope)
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Handler<WatchSubscribe<Table>> for TableActor {
+        async fn handle(
+            &mut self,
+            cx: &Context<Self>,
+            WatchSubscribe(port): WatchSubscribe<Table>,
+        ) -> Result<(), anyhow::Error> {
+            self.0.subscribe(cx, port);
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl Handler<(String, u64)> for TableActor {
+        async fn handle(
+            &mut self,
+            cx: &Context<Self>,
+            (key, value): (String, u64),
+        ) -> Result<(), anyhow::Error> {
+            self.0.update(cx, |table| {
+                table.insert(key, value);
+            });
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl Handler<reference::OncePortRef<usize>> for TableActor {
+        async fn handle(
+            &mut self,
+            cx: &Context<Self>,
+            reply: reference::OncePortRef<usize>,
+        ) -> Result<(), anyhow::Error> {
+            reply.send(cx, self.0.num_subscribers())?;
+            Ok(())
+        }
+    }
+
+    #[async_timed_test(timeout_secs = 30)]
+    async fn test_remote_watch_replica() {
+        let proc = Proc::local();
+        let (client, _) = proc.instance("client").unwrap();
+        let actor_handle = proc
+            .spawn("table", TableActor(RemoteWatch::new(Table::new())))
+            .unwrap();
+        let actor_ref: reference::ActorRef<TableActor> = actor_handle.bind();
+
+        // RW-1
+        let mut replica = WatchReplica::<Table>::subscribe(&client, actor_ref.port()).unwrap();
+        assert_eq!(replica.changed(&client).await.unwrap(), &Table::new());
+        assert_eq!(replica.seq(), Some(0));
+
+        // RW-2
+        actor_ref.send(&client, ("a".to_string(), 1)).unwrap();
+        actor_ref.send(&client, ("b".to_string(), 2)).unwrap();
+        replica.changed(&client).await.unwrap();
+        let table = replica.changed(&client).await.unwrap();
+        assert_eq!(table.get("a"), Some(&1));
+        assert_eq!(table.get("b"), Some(&2));
+        assert_eq!(replica.seq(), Some(2));
+
+        // RW-3: a gap triggers a resync from a fresh snapshot.
+        let epoch = replica.replicated.as_ref().unwrap().epoch;
+        replica
+            .port
+            .send(
+                &client,
+                WatchEvent::Patch {
+                    epoch,
+                    seq: 10,
+                    patch: Table::new().diff(&Table::from([("c".to_string(), 3)])),
+                },
+            )
+            .unwrap();
+        let table = replica.changed(&client).await.unwrap();
+        assert_eq!(table.len(), 2);
+        assert!(!replica.is_resyncing());
+        assert_eq!(replica.seq(), Some(2));
+
+        actor_ref.send(&client, ("a".to_string(), 10)).unwrap();
+        let table = replica.changed(&client).await.unwrap();
+        assert_eq!(table.get("a"), Some(&10));
+        assert_eq!(replica.seq(), Some(3));
+
+        // Resyncing does not add a second subscription.
+        let (reply, rx) = client.open_once_port::<usize>();
+        actor_ref.send(&client, reply.bind()).unwrap();
+        assert_eq!(rx.recv().await.unwrap(), 1);
+
+        // RW-4: a dropped replica is removed once its events bounce.
+        drop(replica);
+        actor_ref.send(&client, ("b".to_string(), 20)).unwrap();
+        loop {
+            let (reply, rx) = client.open_once_port::<usize>();
+            actor_ref.send(&client, reply.bind()).unwrap();
+            if rx.recv().await.unwrap() == 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    }
+
+    #[async_timed_test(timeout_secs = 30)]
+    async fn test_remote_watch_replica_publisher_restart() {
+        let interval = std::time::Duration::from_millis(100);
+        let config = hyperactor_config::global::lock();
+        let _guard = config.override_key(WATCH_REPLICA_RESYNC_INTERVAL, interval);
+
+        let proc = Proc::local();
+        let (client, _) = proc.instance("client").unwrap();
+        let old_handle = proc
+            .spawn("table", TableActor(RemoteWatch::new(Table::new())))
+            .unwrap();
+        let old_ref: reference::ActorRef<TableActor> = old_handle.bind();
+
+        let mut replica = WatchReplica::<Table>::subscribe(&client, old_ref.port()).unwrap();
+        replica.changed(&client).await.unwrap();
+        old_ref.send(&client, ("a".to_string(), 1)).unwrap();
+        assert_eq!(replica.changed(&client).await.unwrap().get("a"), Some(&1));
+        let old_epoch = replica.replicated.as_ref().unwrap().epoch;
+
+        // RW-5: re-subscribing to an idle publisher is not a change.
+        assert!(
+            tokio::time::timeout(3 * interval, replica.changed(&client))
+                .await
+                .is_err()
+        );
+        assert_eq!(replica.seq(), Some(1));
+
+        // Restart the publisher. Actor names are not reused within a
+        // proc, so point the replica at the new instance directly, as
+        // if it had been restarted at the same address.
+        old_handle.drain_and_stop("restart").unwrap();
+        old_handle.await;
+        let new_handle = proc
+            .spawn(
+                "table2",
+                TableActor(RemoteWatch::new(Table::from([("b".to_string(), 2)]))),
+            )
+            .unwrap();
+        let new_ref: reference::ActorRef<TableActor> = new_handle.bind();
+        replica.publisher = new_ref.port();
+
+        // The replica picks up the new epoch without an explicit resync.
+        let table = replica.changed(&client).await.unwrap();
+        assert_eq!(table, &Table::from([("b".to_string(), 2)]));
+        assert_ne!(replica.replicated.as_ref().unwrap().epoch, old_epoch);
+        assert_eq!(replica.seq(), Some(0));
+
+        new_ref.send(&client, ("c".to_string(), 3)).unwrap();
+        assert_eq!(replica.changed(&client).await.unwrap().get("c"), Some(&3));
+        assert_eq!(replica.seq(), Some(1));
+    }
+}
@@ -141,6 +141,9 @@ pub struct AttrKeyInfo {
     pub parse: fn(&str) -> Result<Box<dyn SerializableValue>, anyhow::Error>,
     /// Default value for the attribute, if any.
     pub default: Option<&'static dyn SerializableValue>,
+    /// Check a value against the key's `@validate(...)` validator, if
+    /// it declares one.
+    pub validate: Option<fn(&dyn SerializableValue) -> Result<(), String>>,
     /// A reference to the relevant key object with the associated
     /// type parameter erased. Can be downcast to a concrete Key<T>.
     pub erased: &'static dyn ErasedKey,
@@ -656,6 +659,14 @@ impl Attrs {
         self.values.get(name).map(|b| b.as_ref())
     }
 
+    /// Take a value by key name, returning the boxed value if present
+    pub fn remove_value_by_name(
+        &mut self,
+        name: &'static str,
+    ) -> Option<Box<dyn SerializableValue>> {
+        self.values.remove(name)
+    }
+
     /// Merge all attributes from `other` into this set, consuming
     /// `other`.
     ///
@@ -895,11 +906,19 @@ macro_rules! assert_impl {
 ///
 ///     /// Another key (default value is optional)
 ///     pub attr ANOTHER_KEY: AnotherType;
+///
+///     /// A key with meta-attributes and a validator
+///     @meta(META_KEY = meta_value)
+///     @validate(Validator::range(1..))
+///     pub attr VALIDATED_KEY: u64 = 1;
 /// }
 /// ```
 ///
 /// # Arguments
 ///
+/// * Optional meta-attributes (`@meta(KEY = value, ...)`)
+/// * Optional validator (`@validate(...)`), a
+///   [`Validator`](crate::validate::Validator) for the key's type
 /// * Optional visibility modifier (`pub`, `pub(crate)`, etc.)
 /// * `attr` keyword (required)
 /// * Key name (identifier)
@@ -932,12 +951,14 @@ macro_rules! declare_attrs {
     ($(
         $(#[$attr:meta])*
         $(@meta($($meta_key:ident = $meta_value:expr),* $(,)?))*
+        $(@validate($validator:expr))?
         $vis:vis attr $name:ident: $type:ty $(= $default:expr)?;
     )*) => {
         $(
             $crate::declare_attrs! {
                 @single
                 $(@meta($($meta_key = $meta_value),*))*
+                $(@validate($validator))?
                 $(#[$attr])* ;
                 $vis attr $name: $type $(= $default)?;
             }
@@ -945,7 +966,7 @@ macro_rules! declare_attrs {
     };
 
     // Handle single attribute key with default value and meta attributes
-    (@single $(@meta($($meta_key:ident = $meta_value:expr),* $(,)?))* $(#[$attr:meta])* ; $vis:vis attr $name:ident: $type:ty = $default:expr;) => {
+    (@single $(@meta($($meta_key:ident = $meta_value:expr),* $(,)?))* $(@validate($validator:expr))? $(#[$attr:meta])* ; $vis:vis attr $name:ident: $type:ty = $default:expr;) => {
         $crate::assert_impl!($type, $crate::attrs::AttrValue);
 
         // Create a static default value
@@ -1008,13 +1029,14 @@ macro_rules! declare_attrs {
                     Ok(Box::new(value) as Box<dyn $crate::attrs::SerializableValue>)
                 },
                 default: Some($crate::paste! { &[<$name _DEFAULT>] }),
+                validate: $crate::__attr_validator!($type $(, $validator)?),
                 erased: &$name,
             }
         }
     };
 
     // Handle single attribute key without default value but with meta attributes
-    (@single $(@meta($($meta_key:ident = $meta_value:expr),* $(,)?))* $(#[$attr:meta])* ; $vis:vis attr $name:ident: $type:ty;) => {
+    (@single $(@meta($($meta_key:ident = $meta_value:expr),* $(,)?))* $(@validate($validator:expr))? $(#[$attr:meta])* ; $vis:vis attr $name:ident: $type:ty;) => {
         $crate::assert_impl!($type, $crate::attrs::AttrValue);
 
         $crate::paste! {
@@ -1070,6 +1092,7 @@ macro_rules! declare_attrs {
                     Ok(Box::new(value) as Box<dyn $crate::attrs::SerializableValue>)
                 },
                 default: None,
+                validate: $crate::__attr_validator!($type $(, $validator)?),
                 erased: &$name,
             }
         }
@@ -26,6 +26,19 @@
 //!   Note that Env and Runtime layers will take precedence over this
 //!   inherited configuration.
 //!
+//! - Layers installed through `set`, `create_or_merge`,
+//!   `init_from_env` and the file loaders are validated (see
+//!   [`crate::validate`]). Invalid values never take effect; they are
+//!   logged and reported by `config_entries()` and
+//!   `validation_errors()`.
+//! - The File layer can be loaded from a YAML file, a TOML file, or
+//!   an ordered list of `conf.d`-style directories of TOML files
+//!   (`init_from_config_dirs`), where later files take precedence.
+//!   `config_entries()` reports the file that set each File value.
+//! - `subscribe()` returns a watch receiver for a key's effective
+//!   value, so that long-lived components can react to runtime
+//!   updates instead of reading the value once at startup.
+//!
 //! This design provides flexibility (easy test overrides, runtime
 //! updates, YAML/Env baselines) while ensuring type safety and
 //! predictable resolution order.
@@ -45,10 +58,13 @@
 //! }
 //! ```
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::marker::PhantomData;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::LazyLock;
+use std::sync::Mutex;
 use std::sync::RwLock;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
@@ -56,14 +72,18 @@ use std::sync::atomic::Ordering;
 use arc_swap::ArcSwap;
 use serde::Deserialize;
 use serde::Serialize;
+use tokio::sync::watch;
 
 use crate::CONFIG;
 use crate::attrs::AttrKeyInfo;
 use crate::attrs::AttrValue;
 use crate::attrs::Attrs;
 use crate::attrs::Key;
-use crate::from_env;
+use crate::from_env_checked;
+use crate::from_toml_checked;
 use crate::from_yaml;
+use crate::validate;
+use crate::validate::ValidationError;
 
 /// Configuration source layers in priority order.
 ///
@@ -123,6 +143,12 @@ struct Layers {
     /// Kept sorted by `priority` (lowest number first = highest
     /// priority).
     ordered: Vec<Layer>,
+    /// Values and layers rejected by validation, for each source
+    /// since that source's layer was last replaced.
+    rejected: Vec<ValidationError>,
+    /// The configuration file that set each value in the File layer,
+    /// by key name. Values set programmatically have no entry.
+    file_origins: HashMap<String, String>,
 }
 
 /// A single configuration layer in the global configuration model.
@@ -340,6 +366,10 @@ impl Layers {
     /// priority order.
     fn clear(&mut self, source: Source) {
         self.ordered.retain(|l| layer_source(l) != source);
+        self.rejected.retain(|e| e.layer() != source);
+        if source == Source::File {
+            self.file_origins.clear();
+        }
     }
 
     /// Reset the global configuration to only Defaults (for testing).
@@ -349,6 +379,8 @@ impl Layers {
     /// resolve keys entirely from their declared defaults.
     fn reset(&mut self) {
         self.ordered.clear();
+        self.rejected.clear();
+        self.file_origins.clear();
     }
 
     // Read methods:
@@ -500,8 +532,14 @@ struct GlobalConfig {
     layers: RwLock<Layers>,
     /// Pre-materialized snapshot for lock-free reads.
     materialized: ArcSwap<Attrs>,
+    /// Subscribers notified of every new snapshot; see [`subscribe`].
+    subscribers: Mutex<Vec<Subscriber>>,
 }
 
+/// Notifies a subscriber of a new materialized snapshot. Returns
+/// `false` once the subscriber has gone away.
+type Subscriber = Box<dyn Fn(&Attrs) -> bool + Send + Sync>;
+
 /// Global layered configuration store.
 ///
 /// This is the single authoritative store for configuration in the
@@ -523,23 +561,38 @@ struct GlobalConfig {
 /// installs this snapshot as its [`Source::ClientOverride`] layer,
 /// which has the lowest precedence among explicit layers.
 static GLOBAL: LazyLock<GlobalConfig> = LazyLock::new(|| {
-    let env = from_env();
+    let (mut env, mut rejected) = from_env_checked();
+    for error in validate::strip_invalid(Source::Env, &mut env) {
+        tracing::error!("{}", error);
+        rejected.push(error);
+    }
     let layers = Layers {
         ordered: vec![Layer::Env(env)],
+        rejected,
+        file_origins: HashMap::new(),
     };
     let materialized = ArcSwap::new(Arc::new(layers.materialize()));
     GlobalConfig {
         layers: RwLock::new(layers),
         materialized,
+        subscribers: Mutex::new(Vec::new()),
     }
 });
 
-/// Update the materialized snapshot from the current layers.
+/// Update the materialized snapshot from the current layers, and
+/// notify subscribers.
 ///
 /// Must be called while holding `GLOBAL.layers.write()` to ensure the
-/// snapshot is consistent with the layers.
+/// snapshot is consistent with the layers, and that subscribers
+/// observe snapshots in order.
 fn rematerialize(layers: &Layers) {
-    GLOBAL.materialized.store(Arc::new(layers.materialize()));
+    let snapshot = Arc::new(layers.materialize());
+    GLOBAL.materialized.store(snapshot.clone());
+    GLOBAL
+        .subscribers
+        .lock()
+        .unwrap()
+        .retain(|notify| notify(&snapshot));
 }
 
 /// Monotonically increasing sequence used to assign unique tokens to
@@ -590,7 +643,14 @@ pub fn lock() -> ConfigLock {
 /// from the environment. Repeated calls replace the existing Env
 /// layer.
 pub fn init_from_env() {
-    set(Source::Env, from_env());
+    let (env, unparsable) = from_env_checked();
+    let _ = install(
+        Source::Env,
+        env,
+        unparsable,
+        HashMap::new(),
+        Install::Replace,
+    );
 }
 
 /// Initialize the global configuration from a YAML file.
@@ -602,10 +662,133 @@ pub fn init_from_env() {
 ///
 /// Typically invoked once at process startup to provide a baseline
 /// configuration. Repeated calls replace the existing File layer.
+///
+/// Fails without installing anything if any value in the file is
+/// invalid, or if the file would violate a cross-key constraint.
 pub fn init_from_yaml<P: AsRef<Path>>(path: P) -> Result<(), anyhow::Error> {
-    let file = from_yaml(path)?;
-    set(Source::File, file);
-    Ok(())
+    let file = from_yaml(&path)?;
+    let origins = file
+        .iter()
+        .map(|(name, _)| (name.to_string(), path.as_ref().display().to_string()))
+        .collect();
+    install(
+        Source::File,
+        file,
+        Vec::new(),
+        origins,
+        Install::ReplaceStrict,
+    )
+    .map_err(|errors| {
+        let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        anyhow::anyhow!("invalid configuration file: {}", errors.join("; "))
+    })
+}
+
+/// Initialize the global configuration from a TOML file (see
+/// [`crate::from_toml`] for the format).
+///
+/// Like [`init_from_yaml`], the file replaces the [`Source::File`]
+/// layer, and nothing is installed if any entry is invalid.
+pub fn init_from_toml<P: AsRef<Path>>(path: P) -> Result<(), anyhow::Error> {
+    init_from_files(&[path.as_ref().to_path_buf()])
+}
+
+/// Initialize the global configuration from the `*.toml` files in
+/// `dirs`, returning the files that were loaded.
+///
+/// Directories are read in the given order, and the files within a
+/// directory in lexicographic order of their names. All files are
+/// merged into a single [`Source::File`] layer in which later files
+/// take precedence, and [`config_entries`] reports the file that set
+/// each value. Missing directories are skipped.
+///
+/// Like [`init_from_yaml`], the files replace the File layer, and
+/// nothing is installed if any entry in any file is invalid. If no
+/// files are found, the File layer is left as it is.
+///
+/// Typically invoked once at process startup with
+/// [`default_config_dirs`].
+pub fn init_from_config_dirs<P: AsRef<Path>>(
+    dirs: impl IntoIterator<Item = P>,
+) -> Result<Vec<PathBuf>, anyhow::Error> {
+    let mut files = Vec::new();
+    for dir in dirs {
+        let dir = dir.as_ref();
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => anyhow::bail!("failed to read config directory {}: {}", dir.display(), e),
+        };
+        let mut dir_files = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "toml") && path.is_file() {
+                dir_files.push(path);
+            }
+        }
+        dir_files.sort();
+        files.extend(dir_files);
+    }
+    if !files.is_empty() {
+        init_from_files(&files)?;
+    }
+    Ok(files)
+}
+
+/// The standard configuration directories, in increasing order of
+/// precedence:
+///
+/// 1. `/etc/hyperactor/conf.d` (site-wide),
+/// 2. `$XDG_CONFIG_HOME/hyperactor/conf.d`, or
+///    `$HOME/.config/hyperactor/conf.d` (user),
+/// 3. `.hyperactor/conf.d` in the current directory (project).
+pub fn default_config_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![PathBuf::from("/etc/hyperactor/conf.d")];
+    let user = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+    if let Some(user) = user {
+        dirs.push(user.join("hyperactor").join("conf.d"));
+    }
+    dirs.push(PathBuf::from(".hyperactor").join("conf.d"));
+    dirs
+}
+
+/// Merge TOML `files` in order, and install the result as the File
+/// layer, all or nothing.
+fn init_from_files(files: &[PathBuf]) -> Result<(), anyhow::Error> {
+    let mut layer = Attrs::new();
+    let mut origins = HashMap::new();
+    let mut unparsable = Vec::new();
+    for file in files {
+        let (attrs, errors) = from_toml_checked(file)?;
+        for (name, _) in attrs.iter() {
+            origins.insert(name.to_string(), file.display().to_string());
+        }
+        layer.merge(attrs);
+        unparsable.extend(errors);
+    }
+    install(
+        Source::File,
+        layer,
+        unparsable,
+        origins.clone(),
+        Install::ReplaceStrict,
+    )
+    .map_err(|errors| {
+        let errors: Vec<_> = errors
+            .iter()
+            .map(|error| match error.key().and_then(|key| origins.get(key)) {
+                // Unparsable entries already name their file.
+                Some(file) if !matches!(error, ValidationError::Unparsable { .. }) => {
+                    format!("{error} (in {file})")
+                }
+                _ => error.to_string(),
+            })
+            .collect();
+        anyhow::anyhow!("invalid configuration file: {}", errors.join("; "))
+    })
 }
 
 /// Get a key from the global configuration (Copy types).
@@ -656,6 +839,52 @@ pub fn try_get_cloned<T: AttrValue>(key: Key<T>) -> Option<T> {
     snapshot.get(key).cloned()
 }
 
+/// Subscribe to the effective value of `key`.
+///
+/// The returned receiver starts out holding the current value, and is
+/// marked changed whenever a layer update (from any source, including
+/// test overrides) changes the value. Updates that leave the value
+/// unchanged are not signalled. The subscription ends when the
+/// receiver is dropped.
+///
+/// Panics if the key has no default and is not set in any layer.
+///
+/// # Example
+/// ```rust,ignore
+/// let mut timeout = hyperactor_config::global::subscribe(CLEANUP_TIMEOUT);
+/// loop {
+///     tokio::select! {
+///         _ = timeout.changed() => { /* reschedule with the new value */ }
+///         _ = tokio::time::sleep(*timeout.borrow()) => { /* ... */ }
+///     }
+/// }
+/// ```
+pub fn subscribe<T: AttrValue + PartialEq>(key: Key<T>) -> watch::Receiver<T> {
+    // Hold the layers while registering, so that no update can land
+    // between reading the initial value and registering for updates.
+    let layers = GLOBAL.layers.read().unwrap();
+    let (tx, rx) = watch::channel(get_cloned(key));
+    GLOBAL
+        .subscribers
+        .lock()
+        .unwrap()
+        .push(Box::new(move |snapshot: &Attrs| {
+            if let Some(value) = snapshot.get(key) {
+                tx.send_if_modified(|current| {
+                    if current == value {
+                        false
+                    } else {
+                        *current = value.clone();
+                        true
+                    }
+                });
+            }
+            !tx.is_closed()
+        }));
+    drop(layers);
+    rx
+}
+
 /// Construct a [`Layer`] for the given [`Source`] using the provided
 /// `attrs`.
 ///
@@ -691,10 +920,17 @@ fn make_layer(source: Source, attrs: Attrs) -> Layer {
 /// This function is used by initialization routines (e.g.
 /// `init_from_env`, `init_from_yaml`) and by tests when overriding
 /// configuration values.
+///
+/// Values that fail their key's validator are dropped, and the layer
+/// is left unchanged if it would violate a cross-key constraint; see
+/// [`validation_errors`].
 pub fn set(source: Source, attrs: Attrs) {
-    let mut g = GLOBAL.layers.write().unwrap();
-    g.set(source, attrs);
-    rematerialize(&g);
+    let _ = try_set(source, attrs);
+}
+
+/// Like [`set`], but returns the rejections, if any.
+pub fn try_set(source: Source, attrs: Attrs) -> Result<(), Vec<ValidationError>> {
+    install(source, attrs, Vec::new(), HashMap::new(), Install::Replace)
 }
 
 /// Insert or update a configuration layer for the given [`Source`].
@@ -714,10 +950,118 @@ pub fn set(source: Source, attrs: Attrs) {
 ///
 /// By contrast, [`set`] replaces the entire layer for `source` with
 /// `attrs`, discarding any existing values in that layer.
+///
+/// Like [`set`], this drops invalid values and leaves the layer
+/// unchanged if the update would violate a cross-key constraint.
 pub fn create_or_merge(source: Source, attrs: Attrs) {
+    let _ = try_create_or_merge(source, attrs);
+}
+
+/// Like [`create_or_merge`], but returns the rejections, if any.
+pub fn try_create_or_merge(source: Source, attrs: Attrs) -> Result<(), Vec<ValidationError>> {
+    install(source, attrs, Vec::new(), HashMap::new(), Install::Merge)
+}
+
+/// How [`install`] applies a layer.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Install {
+    /// Replace the layer, dropping invalid values.
+    Replace,
+    /// Replace the layer, unless any value is invalid.
+    ReplaceStrict,
+    /// Merge into the layer, dropping invalid values.
+    Merge,
+}
+
+/// Validate `attrs` and install them as (or into) the `source`
+/// layer.
+///
+/// Values that fail their key's validator are dropped (or, for
+/// [`Install::ReplaceStrict`], the whole layer is rejected). If the
+/// resulting configuration violates a cross-key constraint that the
+/// current configuration satisfies, the layer is left unchanged.
+///
+/// `unparsable` carries values that were already rejected while
+/// loading `attrs`, and `origins` the files that set them, by key
+/// name. All rejections are recorded for [`validation_errors`] and
+/// [`config_entries`], and returned.
+fn install(
+    source: Source,
+    mut attrs: Attrs,
+    unparsable: Vec<ValidationError>,
+    mut origins: HashMap<String, String>,
+    mode: Install,
+) -> Result<(), Vec<ValidationError>> {
+    let mut errors = validate::strip_invalid(source, &mut attrs);
+
     let mut g = GLOBAL.layers.write().unwrap();
-    g.merge(source, attrs);
-    rematerialize(&g);
+    if (errors.is_empty() && unparsable.is_empty()) || mode != Install::ReplaceStrict {
+        // Forget earlier rejections of the values being replaced.
+        if mode == Install::Merge {
+            let keys: Vec<_> = attrs.iter().map(|(name, _)| name).collect();
+            g.rejected.retain(|e| {
+                e.layer() != source || e.key().is_some_and(|key| !keys.iter().any(|k| *k == key))
+            });
+        } else {
+            g.rejected.retain(|e| e.layer() != source);
+        }
+
+        let violated_before = validate::violated_constraints(&g.materialize());
+        let previous = g
+            .ordered
+            .iter()
+            .any(|l| layer_source(l) == source)
+            .then(|| g.layer_attrs_for(source));
+        let installed: HashSet<String> = attrs.iter().map(|(name, _)| name.to_string()).collect();
+        match mode {
+            Install::Merge => g.merge(source, attrs),
+            Install::Replace | Install::ReplaceStrict => g.set(source, attrs),
+        }
+        let violations: Vec<_> = validate::violated_constraints(&g.materialize())
+            .into_iter()
+            .filter(|(name, _)| !violated_before.iter().any(|(before, _)| before == name))
+            .collect();
+        if !violations.is_empty() {
+            match previous {
+                Some(previous) => g.set(source, previous),
+                None => g.ordered.retain(|l| layer_source(l) != source),
+            }
+            errors.extend(violations.into_iter().map(|(constraint, reason)| {
+                ValidationError::Constraint {
+                    constraint: constraint.to_string(),
+                    layer: source,
+                    reason,
+                }
+            }));
+        } else if source == Source::File {
+            origins.retain(|name, _| installed.contains(name));
+            if mode == Install::Merge {
+                g.file_origins.retain(|name, _| !installed.contains(name));
+                g.file_origins.extend(origins);
+            } else {
+                g.file_origins = origins;
+            }
+        }
+        rematerialize(&g);
+    }
+
+    for error in &errors {
+        tracing::error!("{}", error);
+    }
+    let errors = [unparsable, errors].concat();
+    g.rejected.extend(errors.iter().cloned());
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Return the values and layers currently rejected by validation,
+/// oldest first. Rejections for a source are forgotten when its layer
+/// is replaced or cleared.
+pub fn validation_errors() -> Vec<ValidationError> {
+    GLOBAL.layers.read().unwrap().rejected.clone()
 }
 
 /// Remove the configuration layer for the given [`Source`], if
@@ -789,6 +1133,14 @@ pub struct ConfigEntry {
     pub changed_from_default: bool,
     /// Environment variable name, if the key declares one.
     pub env_var: Option<String>,
+    /// Why the most recent value offered for this key by some layer
+    /// was rejected, if it was. A rejected value does not take effect.
+    #[serde(default)]
+    pub validation_error: Option<String>,
+    /// The configuration file that set the value, when it came from
+    /// the File layer.
+    #[serde(default)]
+    pub file: Option<String>,
 }
 
 /// Snapshot all CONFIG-marked keys with their resolved values and
@@ -845,6 +1197,15 @@ pub fn config_entries() -> Vec<ConfigEntry> {
             source,
             changed_from_default,
             env_var: cfg_meta.env_name.clone(),
+            validation_error: g
+                .rejected
+                .iter()
+                .rev()
+                .find(|e| e.key() == Some(name))
+                .map(ToString::to_string),
+            file: (source == Source::File)
+                .then(|| g.file_origins.get(name).cloned())
+                .flatten(),
         });
     }
 
@@ -1115,6 +1476,7 @@ mod tests {
     use super::*;
     use crate::ConfigAttr;
     use crate::attrs::declare_attrs;
+    use crate::validate::Validator;
 
     // Test configuration keys used to exercise the layered config
     // infrastructure. These mirror hyperactor's config keys but are
@@ -1169,8 +1531,32 @@ mod tests {
             None,
         ))
         pub attr CONFIG_KEY_NO_ENV: u32 = 100;
+
+        /// A test key with a validator
+        @meta(CONFIG = ConfigAttr::new(
+            Some("TEST_VALIDATED_ACK_EVERY".to_string()),
+            None,
+        ))
+        @validate(Validator::range(1..))
+        pub attr VALIDATED_ACK_EVERY: u64 = 10;
+
+        /// Lower bound constrained by `validated_bounds`
+        @meta(CONFIG = ConfigAttr::new(None, None))
+        pub attr VALIDATED_LOW: u32 = 1;
+
+        /// Upper bound constrained by `validated_bounds`
+        @meta(CONFIG = ConfigAttr::new(None, None))
+        pub attr VALIDATED_HIGH: u32 = 10;
     }
 
+    crate::config_constraint!(validated_bounds, |config| {
+        if config[VALIDATED_LOW] <= config[VALIDATED_HIGH] {
+            Ok(())
+        } else {
+            Err("validated_low must not exceed validated_high".to_string())
+        }
+    });
+
     #[test]
     fn test_global_config() {
         let config = lock();
@@ -1997,4 +2383,268 @@ mod tests {
             .expect("CONFIG_KEY_NO_ENV should appear");
         assert_eq!(no_env.env_var, None);
     }
+
+    #[test]
+    fn test_set_drops_invalid_values() {
+        let _lock = lock();
+        reset_to_defaults();
+
+        let mut attrs = Attrs::new();
+        attrs.set(VALIDATED_ACK_EVERY, 0);
+        attrs.set(MESSAGE_TTL_DEFAULT, 8);
+        let errors = try_set(Source::Runtime, attrs).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![ValidationError::Invalid {
+                key: VALIDATED_ACK_EVERY.name().to_string(),
+                layer: Source::Runtime,
+                value: "0".to_string(),
+                reason: "must be at least 1".to_string(),
+            }]
+        );
+        // The valid value took effect; the invalid one did not.
+        assert_eq!(get(MESSAGE_TTL_DEFAULT), 8);
+        assert_eq!(get(VALIDATED_ACK_EVERY), 10);
+        assert!(
+            errors[0]
+                .to_string()
+                .contains("from Runtime layer: must be at least 1")
+        );
+
+        let entry = config_entries()
+            .into_iter()
+            .find(|e| e.name == VALIDATED_ACK_EVERY.name())
+            .unwrap();
+        assert_eq!(entry.source, Source::Default);
+        assert_eq!(entry.validation_error, Some(errors[0].to_string()));
+
+        // A valid value replaces the rejection.
+        let mut attrs = Attrs::new();
+        attrs.set(VALIDATED_ACK_EVERY, 5);
+        create_or_merge(Source::Runtime, attrs);
+        assert_eq!(get(VALIDATED_ACK_EVERY), 5);
+        assert_eq!(get(MESSAGE_TTL_DEFAULT), 8);
+        assert!(validation_errors().is_empty());
+
+        reset_to_defaults();
+    }
+
+    #[test]
+    fn test_set_rejects_constraint_violation() {
+        let _lock = lock();
+        reset_to_defaults();
+
+        let mut attrs = Attrs::new();
+        attrs.set(VALIDATED_HIGH, 4);
+        set(Source::File, attrs);
+        assert_eq!(get(VALIDATED_HIGH), 4);
+
+        // Raising the lower bound above the upper bound is rejected as
+        // a whole, leaving the previous layer in place.
+        let mut attrs = Attrs::new();
+        attrs.set(VALIDATED_LOW, 5);
+        attrs.set(MESSAGE_TTL_DEFAULT, 8);
+        let errors = try_create_or_merge(Source::File, attrs).unwrap_err();
+        assert!(matches!(
+            &errors[..],
+            [ValidationError::Constraint { constraint, layer: Source::File, .. }]
+                if constraint.ends_with("::validated_bounds")
+        ));
+        assert_eq!(get(VALIDATED_LOW), 1);
+        assert_eq!(get(VALIDATED_HIGH), 4);
+        assert_eq!(get(MESSAGE_TTL_DEFAULT), 64);
+
+        // Updating both keys together satisfies the constraint.
+        let mut attrs = Attrs::new();
+        attrs.set(VALIDATED_LOW, 5);
+        attrs.set(VALIDATED_HIGH, 6);
+        try_create_or_merge(Source::File, attrs).unwrap();
+        assert_eq!(get(VALIDATED_LOW), 5);
+        assert!(validation_errors().is_empty());
+
+        reset_to_defaults();
+    }
+
+    #[test]
+    fn test_init_from_yaml_rejects_invalid_file() {
+        let _lock = lock();
+        reset_to_defaults();
+
+        let path = std::env::temp_dir().join("test_validated_config.yaml");
+        let mut attrs = Attrs::new();
+        attrs.set(VALIDATED_ACK_EVERY, 0);
+        attrs.set(MESSAGE_TTL_DEFAULT, 8);
+        crate::to_yaml(&attrs, &path).unwrap();
+
+        let err = init_from_yaml(&path).unwrap_err().to_string();
+        assert!(err.contains(VALIDATED_ACK_EVERY.name()), "{err}");
+        assert!(err.contains("from File layer"), "{err}");
+        // Nothing from the file was installed.
+        assert_eq!(get(MESSAGE_TTL_DEFAULT), 64);
+        assert_eq!(validation_errors().len(), 1);
+
+        let _ = std::fs::remove_file(&path);
+        reset_to_defaults();
+    }
+
+    #[test]
+    fn test_init_from_env_records_rejections() {
+        let _lock = lock();
+        reset_to_defaults();
+
+        // SAFETY: Under global ConfigLock during tests.
+        unsafe {
+            std::env::set_var("TEST_VALIDATED_ACK_EVERY", "0");
+            std::env::set_var("HYPERACTOR_MESSAGE_TTL_DEFAULT", "lots");
+        }
+        init_from_env();
+        // SAFETY: Under global ConfigLock during tests.
+        unsafe {
+            std::env::remove_var("TEST_VALIDATED_ACK_EVERY");
+            std::env::remove_var("HYPERACTOR_MESSAGE_TTL_DEFAULT");
+        }
+
+        assert_eq!(get(VALIDATED_ACK_EVERY), 10);
+        assert_eq!(get(MESSAGE_TTL_DEFAULT), 64);
+        let errors = validation_errors();
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            ValidationError::Unparsable { key, layer: Source::Env, value, .. }
+                if key == MESSAGE_TTL_DEFAULT.name() && value == "lots"
+        )));
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            ValidationError::Invalid { key, layer: Source::Env, .. }
+                if key == VALIDATED_ACK_EVERY.name()
+        )));
+
+        reset_to_defaults();
+    }
+
+    #[test]
+    fn test_subscribe() {
+        let lock = lock();
+        reset_to_defaults();
+
+        let mut rx = subscribe(MESSAGE_TTL_DEFAULT);
+        assert_eq!(*rx.borrow_and_update(), 64);
+
+        let mut attrs = Attrs::new();
+        attrs.set(MESSAGE_TTL_DEFAULT, 8);
+        create_or_merge(Source::Runtime, attrs);
+        assert!(rx.has_changed().unwrap());
+        assert_eq!(*rx.borrow_and_update(), 8);
+
+        // Updates that leave the value unchanged are not signalled.
+        let mut attrs = Attrs::new();
+        attrs.set(MESSAGE_DELIVERY_TIMEOUT, Duration::from_secs(1));
+        create_or_merge(Source::Runtime, attrs);
+        assert!(!rx.has_changed().unwrap());
+
+        // Higher-priority layers are reflected too.
+        {
+            let _guard = lock.override_key(MESSAGE_TTL_DEFAULT, 2);
+            assert_eq!(*rx.borrow_and_update(), 2);
+        }
+        assert_eq!(*rx.borrow_and_update(), 8);
+
+        clear(Source::Runtime);
+        assert_eq!(*rx.borrow_and_update(), 64);
+
+        // Dropped receivers are unsubscribed on the next update.
+        drop(rx);
+        reset_to_defaults();
+        assert!(GLOBAL.subscribers.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_init_from_config_dirs() {
+        let _lock = lock();
+        reset_to_defaults();
+
+        let root = std::env::temp_dir().join(format!("test_config_dirs_{}", std::process::id()));
+        let site = root.join("site");
+        let user = root.join("user");
+        std::fs::create_dir_all(&site).unwrap();
+        std::fs::create_dir_all(&user).unwrap();
+        let ttl = MESSAGE_TTL_DEFAULT.name();
+        let timeout = MESSAGE_DELIVERY_TIMEOUT.name();
+        std::fs::write(
+            site.join("10-base.toml"),
+            format!("\"{ttl}\" = 8\n\"{timeout}\" = \"5s\"\n"),
+        )
+        .unwrap();
+        std::fs::write(site.join("20-override.toml"), format!("\"{ttl}\" = 16\n")).unwrap();
+        std::fs::write(user.join("job.toml"), format!("\"{ttl}\" = 32\n")).unwrap();
+        std::fs::write(user.join("notes.txt"), "not configuration").unwrap();
+
+        let loaded = init_from_config_dirs([&site, &user, &root.join("missing")]).unwrap();
+        assert_eq!(
+            loaded,
+            vec![
+                site.join("10-base.toml"),
+                site.join("20-override.toml"),
+                user.join("job.toml"),
+            ]
+        );
+        // Later files take precedence.
+        assert_eq!(get(MESSAGE_TTL_DEFAULT), 32);
+        assert_eq!(get(MESSAGE_DELIVERY_TIMEOUT), Duration::from_secs(5));
+
+        let entries = config_entries();
+        let file_of = |name: &str| {
+            entries
+                .iter()
+                .find(|e| e.name == name)
+                .unwrap()
+                .file
+                .clone()
+        };
+        assert_eq!(
+            file_of(ttl),
+            Some(user.join("job.toml").display().to_string())
+        );
+        assert_eq!(
+            file_of(timeout),
+            Some(site.join("10-base.toml").display().to_string())
+        );
+        assert_eq!(file_of(CODEC_MAX_FRAME_LENGTH.name()), None);
+
+        // Finding no files leaves the File layer alone.
+        assert!(
+            init_from_config_dirs([root.join("missing")])
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(get(MESSAGE_TTL_DEFAULT), 32);
+
+        // An invalid entry in any file rejects all of them.
+        std::fs::write(
+            user.join("job.toml"),
+            format!("\"{ttl}\" = \"lots\"\nno_such_key = 1\n"),
+        )
+        .unwrap();
+        let err = init_from_config_dirs([&site, &user])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("job.toml"), "{err}");
+        assert!(err.contains("unknown configuration key"), "{err}");
+        assert_eq!(get(MESSAGE_TTL_DEFAULT), 32);
+
+        // Replacing the File layer programmatically forgets the files.
+        let mut attrs = Attrs::new();
+        attrs.set(MESSAGE_TTL_DEFAULT, 2);
+        set(Source::File, attrs);
+        assert_eq!(
+            config_entries()
+                .into_iter()
+                .find(|e| e.name == ttl)
+                .unwrap()
+                .file,
+            None
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+        reset_to_defaults();
+    }
 }
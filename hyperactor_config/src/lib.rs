@@ -12,8 +12,11 @@
 //! management including:
 //! - `ConfigAttr`: Metadata for configuration keys
 //! - Helper functions to load/save `Attrs` (from env via `from_env`,
-//!   from YAML via `from_yaml`, and `to_yaml`)
+//!   from YAML via `from_yaml`, from TOML via `from_toml`, and
+//!   `to_yaml`)
 //! - Global layered configuration store under [`crate::global`]
+//! - Declarative validation of configuration values under
+//!   [`crate::validate`]
 //!
 //! Individual crates should declare their own config keys using `declare_attrs!`
 //! and import `ConfigAttr`, `CONFIG`, and other infrastructure from this crate.
@@ -31,6 +34,7 @@ use typeuri::Named;
 pub mod attrs;
 pub mod flattrs;
 pub mod global;
+pub mod validate;
 
 // Re-export commonly used items
 pub use attrs::AttrKeyInfo;
@@ -185,7 +189,14 @@ declare_attrs! {
 
 /// Load configuration from environment variables
 pub fn from_env() -> Attrs {
+    from_env_checked().0
+}
+
+/// Load configuration from environment variables, also returning the
+/// values that could not be parsed.
+pub(crate) fn from_env_checked() -> (Attrs, Vec<validate::ValidationError>) {
     let mut config = Attrs::new();
+    let mut errors = Vec::new();
     let mut output = String::new();
 
     fn export(env_var: &str, value: Option<&dyn SerializableValue>) -> String {
@@ -226,6 +237,12 @@ pub fn from_env() -> Attrs {
                 );
                 output.push_str("# ");
                 output.push_str(&export(env_var, key.default));
+                errors.push(validate::ValidationError::Unparsable {
+                    key: key.name.to_string(),
+                    layer: global::Source::Env,
+                    value: val,
+                    reason: e.to_string(),
+                });
             }
             Ok(parsed) => {
                 output.push_str("# ");
@@ -241,7 +258,7 @@ pub fn from_env() -> Attrs {
         output.trim_end()
     );
 
-    config
+    (config, errors)
 }
 
 /// Load configuration from a YAML file
@@ -252,6 +269,85 @@ pub fn from_yaml<P: AsRef<Path>>(path: P) -> Result<Attrs, anyhow::Error> {
     Ok(serde_yaml::from_str(&contents)?)
 }
 
+/// Load configuration from a TOML file.
+///
+/// Each top-level entry names a CONFIG key, either by its `py_name`
+/// or by its fully qualified name (quoted, since it contains `::`).
+/// Values are written as they would be in the key's environment
+/// variable; TOML integers, floats and booleans are also accepted:
+///
+/// ```toml
+/// message_delivery_timeout = "1m"
+/// "hyperactor::config::message_ack_every_n_messages" = 500
+/// ```
+///
+/// Fails if the file cannot be read or parsed, or if any entry
+/// names an unknown key or has an unparsable value.
+pub fn from_toml<P: AsRef<Path>>(path: P) -> Result<Attrs, anyhow::Error> {
+    let (attrs, errors) = from_toml_checked(path.as_ref())?;
+    if !errors.is_empty() {
+        let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        anyhow::bail!("invalid configuration file: {}", errors.join("; "));
+    }
+    Ok(attrs)
+}
+
+/// Load configuration from a TOML file, also returning the entries
+/// that name unknown keys or could not be parsed. Fails only if the
+/// file cannot be read or is not valid TOML.
+pub(crate) fn from_toml_checked(
+    path: &Path,
+) -> Result<(Attrs, Vec<validate::ValidationError>), anyhow::Error> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("failed to read {}: {}", path.display(), e))?;
+    let table: toml::Table = contents
+        .parse()
+        .map_err(|e| anyhow::anyhow!("failed to parse {}: {}", path.display(), e))?;
+
+    let unparsable =
+        |key: &str, value: String, reason: &str| validate::ValidationError::Unparsable {
+            key: key.to_string(),
+            layer: global::Source::File,
+            value,
+            reason: format!("{} (in {})", reason, path.display()),
+        };
+    let mut config = Attrs::new();
+    let mut errors = Vec::new();
+    for (name, value) in table {
+        let Some(key) = inventory::iter::<AttrKeyInfo>().find(|key| {
+            key.meta.get(CONFIG).is_some_and(|cfg| {
+                key.name == name || cfg.py_name.as_deref() == Some(name.as_str())
+            })
+        }) else {
+            errors.push(unparsable(
+                &name,
+                value.to_string(),
+                "unknown configuration key",
+            ));
+            continue;
+        };
+        let value = match value {
+            toml::Value::String(value) => value,
+            toml::Value::Integer(value) => value.to_string(),
+            toml::Value::Float(value) => value.to_string(),
+            toml::Value::Boolean(value) => value.to_string(),
+            other => {
+                errors.push(unparsable(
+                    key.name,
+                    other.to_string(),
+                    "expected a string, number or boolean",
+                ));
+                continue;
+            }
+        };
+        match (key.parse)(&value) {
+            Ok(parsed) => config.insert_value_by_name_unchecked(key.name, parsed),
+            Err(e) => errors.push(unparsable(key.name, value, &e.to_string())),
+        }
+    }
+    Ok((config, errors))
+}
+
 /// Save configuration to a YAML file
 pub fn to_yaml<P: AsRef<Path>>(attrs: &Attrs, path: P) -> Result<(), anyhow::Error> {
     let yaml = serde_yaml::to_string(attrs)?;
@@ -264,12 +360,14 @@ mod tests {
     use std::collections::HashSet;
     use std::net::Ipv4Addr;
 
+    use indoc::formatdoc;
     use indoc::indoc;
 
     use crate::CONFIG;
     use crate::ConfigAttr;
     use crate::attrs::declare_attrs;
     use crate::from_env;
+    use crate::from_toml;
     use crate::from_yaml;
     use crate::to_yaml;
 
@@ -569,6 +667,50 @@ mod tests {
         let _ = std::fs::remove_file(&temp_path);
     }
 
+    #[test]
+    fn test_from_toml() {
+        let temp_path = std::env::temp_dir().join("test_config.toml");
+
+        // Keys may be given by py_name or by full name; values use
+        // the env var syntax, or native TOML scalars.
+        let toml_content = formatdoc!(
+            r#"
+                test_u32_key = 777
+                "{}" = 2048
+                "{}" = "hello_toml"
+                "{}" = true
+                "{}" = 1.5
+                "{}" = "2m"
+            "#,
+            USIZE_KEY.name(),
+            STRING_KEY.name(),
+            BOOL_KEY.name(),
+            F64_KEY.name(),
+            DURATION_KEY.name(),
+        );
+        std::fs::write(&temp_path, toml_content).unwrap();
+
+        let loaded_config = from_toml(&temp_path).unwrap();
+        assert_eq!(loaded_config[U32_KEY], 777);
+        assert_eq!(loaded_config[USIZE_KEY], 2048);
+        assert_eq!(loaded_config[STRING_KEY], "hello_toml");
+        assert!(loaded_config[BOOL_KEY]);
+        assert_eq!(loaded_config[F64_KEY], 1.5);
+        assert_eq!(
+            loaded_config[DURATION_KEY],
+            std::time::Duration::from_mins(2)
+        );
+        assert!(!loaded_config.contains_key(I64_KEY));
+
+        std::fs::write(&temp_path, "test_u32_key = \"lots\"\nno_such_key = 1\n").unwrap();
+        let err = from_toml(&temp_path).unwrap_err().to_string();
+        assert!(err.contains(U32_KEY.name()), "{err}");
+        assert!(err.contains("unknown configuration key"), "{err}");
+        assert!(err.contains("test_config.toml"), "{err}");
+
+        let _ = std::fs::remove_file(&temp_path);
+    }
+
     // Verify that the INTROSPECT meta-attribute attaches structured
     // introspection metadata to an attribute key and that the
     // metadata can be retrieved through the attrs API.
@@ -0,0 +1,342 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Declarative validation of configuration values.
+//!
+//! A key declares its constraints with `@validate(...)` in
+//! `declare_attrs!`, after its `@meta(...)` attributes:
+//!
+//! ```ignore
+//! declare_attrs! {
+//!     /// Number of messages after which to send an acknowledgment.
+//!     @meta(CONFIG = ConfigAttr::new(
+//!         Some("HYPERACTOR_MESSAGE_ACK_EVERY_N_MESSAGES".to_string()),
+//!         None,
+//!     ))
+//!     @validate(Validator::range(1..))
+//!     pub attr MESSAGE_ACK_EVERY_N_MESSAGES: u64 = 1000;
+//! }
+//! ```
+//!
+//! Constraints spanning several keys are registered with
+//! [`config_constraint!`](crate::config_constraint) and checked against
+//! the effective configuration.
+//!
+//! Validation is enforced when layers are installed through
+//! [`global::set`](crate::global::set),
+//! [`global::create_or_merge`](crate::global::create_or_merge),
+//! [`global::init_from_env`](crate::global::init_from_env) and the
+//! file loaders, such as
+//! [`global::init_from_yaml`](crate::global::init_from_yaml). Test
+//! overrides (`ConfigLock::override_key`) are not validated, and
+//! declared defaults are assumed valid.
+
+use std::ops::Bound;
+use std::ops::RangeBounds;
+use std::path::PathBuf;
+
+use crate::attrs::AttrValue;
+use crate::attrs::Attrs;
+use crate::global::Source;
+
+type Check<T> = Box<dyn Fn(&T) -> Result<(), String> + Send + Sync>;
+
+/// A set of checks on values of type `T`. A value is valid if it
+/// passes all of them.
+pub struct Validator<T> {
+    checks: Vec<Check<T>>,
+}
+
+impl<T: AttrValue> Validator<T> {
+    /// Require the value to be within `range`.
+    pub fn range(range: impl RangeBounds<T>) -> Self
+    where
+        T: PartialOrd,
+    {
+        let start = range.start_bound().cloned();
+        let end = range.end_bound().cloned();
+        Self::custom(move |value: &T| {
+            match &start {
+                Bound::Included(min) if value < min => {
+                    return Err(format!("must be at least {}", min.display()));
+                }
+                Bound::Excluded(min) if value <= min => {
+                    return Err(format!("must be greater than {}", min.display()));
+                }
+                _ => {}
+            }
+            match &end {
+                Bound::Included(max) if value > max => {
+                    Err(format!("must be at most {}", max.display()))
+                }
+                Bound::Excluded(max) if value >= max => {
+                    Err(format!("must be less than {}", max.display()))
+                }
+                _ => Ok(()),
+            }
+        })
+    }
+
+    /// Require the value to be one of `allowed`.
+    pub fn one_of(allowed: impl IntoIterator<Item = T>) -> Self
+    where
+        T: PartialEq,
+    {
+        let allowed: Vec<T> = allowed.into_iter().collect();
+        Self::custom(move |value: &T| {
+            if allowed.contains(value) {
+                Ok(())
+            } else {
+                let allowed: Vec<_> = allowed.iter().map(AttrValue::display).collect();
+                Err(format!("must be one of: {}", allowed.join(", ")))
+            }
+        })
+    }
+
+    /// Require the value to be non-empty.
+    pub fn non_empty() -> Self
+    where
+        T: Emptiable,
+    {
+        Self::custom(|value: &T| {
+            if value.is_empty() {
+                Err("must not be empty".to_string())
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    /// Check the value with `check`, which returns a description of
+    /// the problem if the value is invalid.
+    pub fn custom(check: impl Fn(&T) -> Result<(), String> + Send + Sync + 'static) -> Self {
+        Self {
+            checks: vec![Box::new(check)],
+        }
+    }
+
+    /// Additionally require the checks of `other`.
+    pub fn and(mut self, other: Self) -> Self {
+        self.checks.extend(other.checks);
+        self
+    }
+
+    /// Check `value`, returning the first failed check's description.
+    pub fn check(&self, value: &T) -> Result<(), String> {
+        self.checks.iter().try_for_each(|check| check(value))
+    }
+}
+
+/// Values that can be empty, for [`Validator::non_empty`].
+pub trait Emptiable {
+    /// Whether the value is empty.
+    fn is_empty(&self) -> bool;
+}
+
+impl Emptiable for String {
+    fn is_empty(&self) -> bool {
+        String::is_empty(self)
+    }
+}
+
+impl Emptiable for PathBuf {
+    fn is_empty(&self) -> bool {
+        self.as_os_str().is_empty()
+    }
+}
+
+impl<T> Emptiable for Vec<T> {
+    fn is_empty(&self) -> bool {
+        Vec::is_empty(self)
+    }
+}
+
+/// A constraint over several configuration keys, registered with
+/// [`config_constraint!`](crate::config_constraint).
+pub struct Constraint {
+    /// Fully qualified name of the constraint.
+    pub name: &'static str,
+    /// Check the effective configuration, returning a description of
+    /// the problem if it violates the constraint.
+    pub check: fn(&Attrs) -> Result<(), String>,
+}
+
+inventory::collect!(Constraint);
+
+/// Register a constraint over several configuration keys. The check
+/// receives the effective configuration that would result from
+/// installing a layer; the layer is rejected if it introduces a
+/// violation.
+///
+/// ```ignore
+/// hyperactor_config::config_constraint!(cleanup_before_stop, |config| {
+///     if config[CLEANUP_TIMEOUT] < config[STOP_ACTOR_TIMEOUT] {
+///         Ok(())
+///     } else {
+///         Err("cleanup_timeout must be less than stop_actor_timeout".to_string())
+///     }
+/// });
+/// ```
+#[macro_export]
+macro_rules! config_constraint {
+    ($name:ident, $check:expr) => {
+        $crate::submit! {
+            $crate::validate::Constraint {
+                name: concat!(std::module_path!(), "::", stringify!($name)),
+                check: $check,
+            }
+        }
+    };
+}
+
+/// Build the erased validation function stored in
+/// [`AttrKeyInfo::validate`](crate::attrs::AttrKeyInfo::validate).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __attr_validator {
+    ($type:ty) => {
+        None
+    };
+    ($type:ty, $validator:expr) => {
+        Some(|value: &dyn $crate::attrs::SerializableValue| {
+            static VALIDATOR: std::sync::LazyLock<$crate::validate::Validator<$type>> =
+                std::sync::LazyLock::new(|| $validator);
+            let value = value.as_any().downcast_ref::<$type>().unwrap();
+            VALIDATOR.check(value)
+        })
+    };
+}
+
+/// A configuration value or layer rejected by validation.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ValidationError {
+    /// The value could not be parsed.
+    #[error("invalid value {value:?} for config key {key} from {layer:?} layer: {reason}")]
+    Unparsable {
+        key: String,
+        layer: Source,
+        value: String,
+        reason: String,
+    },
+    /// The value failed the key's validator.
+    #[error("invalid value {value} for config key {key} from {layer:?} layer: {reason}")]
+    Invalid {
+        key: String,
+        layer: Source,
+        value: String,
+        reason: String,
+    },
+    /// The layer would violate a cross-key constraint.
+    #[error("config layer {layer:?} violates constraint {constraint}: {reason}")]
+    Constraint {
+        constraint: String,
+        layer: Source,
+        reason: String,
+    },
+}
+
+impl ValidationError {
+    /// The layer the rejected value or layer was installed into.
+    pub fn layer(&self) -> Source {
+        match self {
+            Self::Unparsable { layer, .. }
+            | Self::Invalid { layer, .. }
+            | Self::Constraint { layer, .. } => *layer,
+        }
+    }
+
+    /// The rejected key, if the error concerns a single key.
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Self::Unparsable { key, .. } | Self::Invalid { key, .. } => Some(key),
+            Self::Constraint { .. } => None,
+        }
+    }
+}
+
+/// Check every value in `attrs` against its key's validator, removing
+/// the invalid ones. Returns the errors for the removed values.
+pub(crate) fn strip_invalid(layer: Source, attrs: &mut Attrs) -> Vec<ValidationError> {
+    let invalid: Vec<_> = attrs
+        .iter()
+        .filter_map(|(name, value)| {
+            let info = crate::attrs::lookup_key_info(crate::attrs::fnv1a_hash(name.as_bytes()))?;
+            let reason = (info.validate?)(value).err()?;
+            Some((
+                name,
+                ValidationError::Invalid {
+                    key: name.to_string(),
+                    layer,
+                    value: (info.display)(value),
+                    reason,
+                },
+            ))
+        })
+        .collect();
+    invalid
+        .into_iter()
+        .map(|(name, error)| {
+            attrs.remove_value_by_name(name);
+            error
+        })
+        .collect()
+}
+
+/// Names of the constraints violated by the effective configuration
+/// `config`, with their descriptions.
+pub(crate) fn violated_constraints(config: &Attrs) -> Vec<(&'static str, String)> {
+    inventory::iter::<Constraint>()
+        .filter_map(|constraint| {
+            (constraint.check)(config)
+                .err()
+                .map(|reason| (constraint.name, reason))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validators() {
+        let range = Validator::<u64>::range(1..=10);
+        assert!(range.check(&1).is_ok());
+        assert!(range.check(&10).is_ok());
+        assert_eq!(range.check(&0), Err("must be at least 1".to_string()));
+        assert_eq!(range.check(&11), Err("must be at most 10".to_string()));
+
+        let open = Validator::<f32>::range(0.0..1.0);
+        assert!(open.check(&0.5).is_ok());
+        assert_eq!(open.check(&1.0), Err("must be less than 1".to_string()));
+
+        let one_of = Validator::one_of(["a".to_string(), "b".to_string()]);
+        assert!(one_of.check(&"a".to_string()).is_ok());
+        assert_eq!(
+            one_of.check(&"c".to_string()),
+            Err("must be one of: a, b".to_string())
+        );
+
+        let combined = Validator::<String>::non_empty().and(Validator::custom(|value: &String| {
+            if value.starts_with('/') {
+                Ok(())
+            } else {
+                Err("must be an absolute path".to_string())
+            }
+        }));
+        assert_eq!(
+            combined.check(&String::new()),
+            Err("must not be empty".to_string())
+        );
+        assert_eq!(
+            combined.check(&"tmp".to_string()),
+            Err("must be an absolute path".to_string())
+        );
+        assert!(combined.check(&"/tmp".to_string()).is_ok());
+    }
+}
@@ -69,7 +69,11 @@ use tracing::Instrument;
 use tracing::Level;
 use typeuri::Named;
 
+use crate::config::MESH_PROC_CPU_QUOTA_PERCENT;
 use crate::config::MESH_PROC_LAUNCHER_KIND;
+use crate::config::MESH_PROC_MEMORY_HIGH;
+use crate::config::MESH_PROC_MEMORY_MAX;
+use crate::config::MESH_PROC_PIDS_MAX;
 use crate::host_mesh::host_agent::HostAgent;
 use crate::host_mesh::host_agent::HostAgentMode;
 use crate::logging::OutputTarget;
@@ -81,6 +85,9 @@ use crate::proc_launcher::ProcExitKind;
 use crate::proc_launcher::ProcExitResult;
 use crate::proc_launcher::ProcLauncher;
 use crate::proc_launcher::ProcLauncherError;
+use crate::proc_launcher::ProcResourceUsage;
+#[cfg(target_os = "linux")]
+use crate::proc_launcher::SandboxProcLauncher;
 use crate::proc_launcher::StdioHandling;
 #[cfg(target_os = "linux")]
 use crate::proc_launcher::SystemdProcLauncher;
@@ -157,6 +164,16 @@ declare_attrs! {
     ))
     pub attr MESH_BOOTSTRAP_ENABLE_PDEATHSIG: bool = true;
 
+    /// If enabled, [`bootstrap`] loads the process's File configuration
+    /// layer from [`hyperactor_config::global::default_config_dirs`].
+    /// Off by default, so that stray files on a host do not change the
+    /// configuration of the processes it runs.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_BOOTSTRAP_LOAD_CONFIG_DIRS".to_string()),
+        Some("mesh_bootstrap_load_config_dirs".to_string()),
+    ))
+    pub attr MESH_BOOTSTRAP_LOAD_CONFIG_DIRS: bool = false;
+
     /// Maximum number of child terminations to run concurrently
     /// during bulk shutdown. Prevents unbounded spawning of
     /// termination tasks (which could otherwise spike CPU, I/O, or
@@ -713,17 +730,37 @@ pub enum ProcStatus {
     /// (bootstrap error, unexpected condition, etc.). (Both levels:
     /// catch-all failure.)
     Failed { reason: String },
+    // Variants below were added later; they are kept at the end so
+    // that serialized variant indices of earlier ones do not change.
+    /// The process was killed by the kernel OOM killer for exceeding
+    /// its memory limit. `memory_max` is the limit in effect, if
+    /// known. (Process-level: abnormal termination.)
+    OomKilled { memory_max: Option<u64> },
+    /// The proc is serving but has failed `failures` consecutive
+    /// health probes; `reason` describes the latest failure. Returns
+    /// to `Ready` when a probe succeeds. (Proc-level: wedged or
+    /// degraded; Process-level: still running.)
+    Unhealthy {
+        started_at: SystemTime,
+        addr: ChannelAddr,
+        agent: hyperactor_reference::ActorRef<ProcAgent>,
+        failures: usize,
+        reason: String,
+    },
 }
 
 impl ProcStatus {
     /// Returns `true` if the proc is in a terminal (exited) state:
-    /// [`ProcStatus::Stopped`], [`ProcStatus::Killed`], or
-    /// [`ProcStatus::Failed`].
+    /// [`ProcStatus::Stopped`], [`ProcStatus::Killed`],
+    /// [`ProcStatus::OomKilled`], or [`ProcStatus::Failed`].
     #[inline]
     pub fn is_exit(&self) -> bool {
         matches!(
             self,
-            ProcStatus::Stopped { .. } | ProcStatus::Killed { .. } | ProcStatus::Failed { .. }
+            ProcStatus::Stopped { .. }
+                | ProcStatus::Killed { .. }
+                | ProcStatus::OomKilled { .. }
+                | ProcStatus::Failed { .. }
         )
     }
 }
@@ -748,6 +785,22 @@ impl std::fmt::Display for ProcStatus {
                     .unwrap_or_default();
                 write!(f, "Ready at {addr}{uptime}")
             }
+            ProcStatus::Unhealthy {
+                started_at,
+                addr,
+                failures,
+                reason,
+                ..
+            } => {
+                let uptime = started_at
+                    .elapsed()
+                    .map(|d| format!(" up {}", format_duration(d)))
+                    .unwrap_or_default();
+                write!(
+                    f,
+                    "Unhealthy(failures={failures}: {reason}) at {addr}{uptime}"
+                )
+            }
             ProcStatus::Stopping { started_at } => {
                 let uptime = started_at
                     .elapsed()
@@ -766,6 +819,10 @@ impl std::fmt::Display for ProcStatus {
                     write!(f, "Killed(sig={signal})")
                 }
             }
+            ProcStatus::OomKilled { memory_max } => match memory_max {
+                Some(bytes) => write!(f, "OomKilled(memory.max={bytes})"),
+                None => write!(f, "OomKilled"),
+            },
             ProcStatus::Failed { reason } => write!(f, "Failed({reason})"),
         }
     }
@@ -1071,6 +1128,67 @@ impl BootstrapProcHandle {
         })
     }
 
+    /// Record that the proc failed its latest health probe, `failures`
+    /// times in a row.
+    ///
+    /// Moves `Ready` to [`ProcStatus::Unhealthy`], or updates the
+    /// failure count and reason of an already unhealthy proc. Returns
+    /// `false` (leaving the state unchanged) in any other state: a
+    /// proc that is not serving, or already stopping, has no health
+    /// to report.
+    pub(crate) fn mark_unhealthy(&self, failures: usize, reason: String) -> bool {
+        self.transition(|st| match st {
+            ProcStatus::Ready {
+                started_at,
+                addr,
+                agent,
+            } => {
+                let (started_at, addr, agent) = (*started_at, addr.clone(), agent.clone());
+                *st = ProcStatus::Unhealthy {
+                    started_at,
+                    addr,
+                    agent,
+                    failures,
+                    reason,
+                };
+                true
+            }
+            ProcStatus::Unhealthy {
+                failures: current_failures,
+                reason: current_reason,
+                ..
+            } => {
+                *current_failures = failures;
+                *current_reason = reason;
+                true
+            }
+            _ => false,
+        })
+    }
+
+    /// Record that an unhealthy proc passed a health probe, moving it
+    /// back to [`ProcStatus::Ready`]. Returns `false` if the proc was
+    /// not unhealthy.
+    pub(crate) fn mark_healthy(&self) -> bool {
+        self.transition(|st| match st {
+            ProcStatus::Unhealthy {
+                started_at,
+                addr,
+                agent,
+                ..
+            } => {
+                let (started_at, addr, agent) = (*started_at, addr.clone(), agent.clone());
+                *st = ProcStatus::Ready {
+                    started_at,
+                    addr,
+                    agent,
+                };
+                true
+            }
+            _ => false,
+        })
+    }
+
     /// Record that a stop has been requested for the proc (e.g. a
     /// graceful shutdown via SIGTERM), but the underlying process has
     /// not yet fully exited.
@@ -1082,7 +1200,7 @@ impl BootstrapProcHandle {
                 *st = ProcStatus::Stopping { started_at };
                 true
             }
-            ProcStatus::Ready { started_at, .. } => {
+            ProcStatus::Ready { started_at, .. } | ProcStatus::Unhealthy { started_at, .. } => {
                 *st = ProcStatus::Stopping { started_at };
                 true
             }
@@ -1101,6 +1219,7 @@ impl BootstrapProcHandle {
             ProcStatus::Starting
             | ProcStatus::Running { .. }
             | ProcStatus::Ready { .. }
+            | ProcStatus::Unhealthy { .. }
             | ProcStatus::Stopping { .. } => {
                 *st = ProcStatus::Stopped {
                     exit_code,
@@ -1125,6 +1244,7 @@ impl BootstrapProcHandle {
             ProcStatus::Starting
             | ProcStatus::Running { .. }
             | ProcStatus::Ready { .. }
+            | ProcStatus::Unhealthy { .. }
             | ProcStatus::Stopping { .. } => {
                 *st = ProcStatus::Killed {
                     signal,
@@ -1142,6 +1262,28 @@ impl BootstrapProcHandle {
         })
     }
 
+    /// Record that the process was killed by the OOM killer for
+    /// exceeding its memory limit.
+    pub(crate) fn mark_oom_killed(&self, memory_max: Option<u64>) -> bool {
+        self.transition(|st| match *st {
+            ProcStatus::Starting
+            | ProcStatus::Running { .. }
+            | ProcStatus::Ready { .. }
+            | ProcStatus::Unhealthy { .. }
+            | ProcStatus::Stopping { .. } => {
+                *st = ProcStatus::OomKilled { memory_max };
+                true
+            }
+            _ => {
+                tracing::warn!(
+                    "illegal transition: {:?} -> OomKilled; leaving status unchanged",
+                    *st
+                );
+                false
+            }
+        })
+    }
+
     /// Record that the proc or its process failed for an unexpected
     /// reason (bootstrap error, spawn failure, etc.).
     pub(crate) fn mark_failed<S: Into<String>>(&self, reason: S) -> bool {
@@ -1149,6 +1291,7 @@ impl BootstrapProcHandle {
             ProcStatus::Starting
             | ProcStatus::Running { .. }
             | ProcStatus::Ready { .. }
+            | ProcStatus::Unhealthy { .. }
             | ProcStatus::Stopping { .. } => {
                 *st = ProcStatus::Failed {
                     reason: reason.into(),
@@ -1169,8 +1312,9 @@ impl BootstrapProcHandle {
     /// it.
     ///
     /// Terminal means [`ProcStatus::Stopped`],
-    /// [`ProcStatus::Killed`], or [`ProcStatus::Failed`]. If the
-    /// current status is already terminal, returns immediately.
+    /// [`ProcStatus::Killed`], [`ProcStatus::OomKilled`], or
+    /// [`ProcStatus::Failed`]. If the current status is already
+    /// terminal, returns immediately.
     ///
     /// Non-consuming: `BootstrapProcHandle` is a supervisor, not the
     /// owner of the OS process, so you can call `wait()` from
@@ -1199,10 +1343,11 @@ impl BootstrapProcHandle {
     }
 
     /// Wait until the proc reaches the [`ProcStatus::Ready`] state.
+    /// An [`ProcStatus::Unhealthy`] proc has been `Ready` and also
+    /// counts.
     ///
-    /// If the proc hits a terminal state ([`ProcStatus::Stopped`],
-    /// [`ProcStatus::Killed`], or [`ProcStatus::Failed`]) before ever
-    /// becoming `Ready`, this returns
+    /// If the proc hits a terminal state (see
+    /// [`ProcStatus::is_exit`]) before ever becoming `Ready`, this returns
     /// `Err(ReadyError::Terminal(status))`. If the internal watch
     /// channel closes unexpectedly, this returns
     /// `Err(ReadyError::ChannelClosed)`. Otherwise it returns
@@ -1221,7 +1366,7 @@ impl BootstrapProcHandle {
         loop {
             let st = rx.borrow().clone();
             match &st {
-                ProcStatus::Ready { .. } => return Ok(()),
+                ProcStatus::Ready { .. } | ProcStatus::Unhealthy { .. } => return Ok(()),
                 s if s.is_exit() => return Err(ReadyError::Terminal(st)),
                 _non_terminal => {
                     if rx.changed().await.is_err() {
@@ -1332,7 +1477,9 @@ impl hyperactor::host::ProcHandle for BootstrapProcHandle {
     #[inline]
     fn addr(&self) -> Option<ChannelAddr> {
         match &*self.status.lock().expect("status mutex poisoned") {
-            ProcStatus::Ready { addr, .. } => Some(addr.clone()),
+            ProcStatus::Ready { addr, .. } | ProcStatus::Unhealthy { addr, .. } => {
+                Some(addr.clone())
+            }
             _ => None,
         }
     }
@@ -1340,7 +1487,9 @@ impl hyperactor::host::ProcHandle for BootstrapProcHandle {
     #[inline]
     fn agent_ref(&self) -> Option<hyperactor_reference::ActorRef<Self::Agent>> {
         match &*self.status.lock().expect("status mutex poisoned") {
-            ProcStatus::Ready { agent, .. } => Some(agent.clone()),
+            ProcStatus::Ready { agent, .. } | ProcStatus::Unhealthy { agent, .. } => {
+                Some(agent.clone())
+            }
             _ => None,
         }
     }
@@ -1619,11 +1768,14 @@ impl<T: Into<PathBuf>> From<T> for BootstrapCommand {
 /// - [`LauncherKind::Systemd`]: delegates supervision to `systemd
 ///   --user` by creating transient `.service` units and observing
 ///   lifecycle via D-Bus.
+/// - [`LauncherKind::Sandbox`]: like `Native`, but each proc runs in
+///   fresh user, mount, PID and (optionally) network namespaces.
 ///
 /// Configuration/parsing:
 /// - The empty string and `"native"` map to [`LauncherKind::Native`]
 ///   (default).
 /// - `"systemd"` maps to [`LauncherKind::Systemd`].
+/// - `"sandbox"` maps to [`LauncherKind::Sandbox`].
 /// - Any other value is rejected as [`io::ErrorKind::InvalidInput`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum LauncherKind {
@@ -1634,6 +1786,9 @@ pub(crate) enum LauncherKind {
     /// D-Bus.
     #[cfg(target_os = "linux")]
     Systemd,
+    /// Spawn OS children directly, each inside a namespace sandbox.
+    #[cfg(target_os = "linux")]
+    Sandbox,
 }
 
 impl FromStr for LauncherKind {
@@ -1645,6 +1800,7 @@ impl FromStr for LauncherKind {
     /// ignored):
     /// - `""` or `"native"` → [`LauncherKind::Native`]
     /// - `"systemd"` → [`LauncherKind::Systemd`] (Linux only)
+    /// - `"sandbox"` → [`LauncherKind::Sandbox`] (Linux only)
     ///
     /// Returns [`io::ErrorKind::InvalidInput`] for any other string.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -1652,12 +1808,14 @@ impl FromStr for LauncherKind {
             "" | "native" => Ok(Self::Native),
             #[cfg(target_os = "linux")]
             "systemd" => Ok(Self::Systemd),
+            #[cfg(target_os = "linux")]
+            "sandbox" => Ok(Self::Sandbox),
             other => Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
                 format!(
                     "unknown proc launcher kind {other:?}; expected 'native'{}",
                     if cfg!(target_os = "linux") {
-                        " or 'systemd'"
+                        ", 'systemd' or 'sandbox'"
                     } else {
                         ""
                     }
@@ -1780,6 +1938,8 @@ impl BootstrapProcManager {
                 LauncherKind::Native => Arc::new(NativeProcLauncher::new()),
                 #[cfg(target_os = "linux")]
                 LauncherKind::Systemd => Arc::new(SystemdProcLauncher::new()),
+                #[cfg(target_os = "linux")]
+                LauncherKind::Sandbox => Arc::new(SandboxProcLauncher::new()),
             }
         })
     }
@@ -1808,6 +1968,52 @@ impl BootstrapProcManager {
         self.children.lock().await.get(proc_id).map(|h| h.status())
     }
 
+    /// Record a failed health probe for the given proc; see
+    /// [`BootstrapProcHandle::mark_unhealthy`]. Returns `false` if the
+    /// proc is unknown or not serving.
+    pub(crate) async fn mark_unhealthy(
+        &self,
+        proc_id: &hyperactor_reference::ProcId,
+        failures: usize,
+        reason: String,
+    ) -> bool {
+        match self.children.lock().await.get(proc_id) {
+            Some(handle) => handle.mark_unhealthy(failures, reason),
+            None => false,
+        }
+    }
+
+    /// Record a passed health probe for the given proc; see
+    /// [`BootstrapProcHandle::mark_healthy`]. Returns `false` if the
+    /// proc is unknown or was not unhealthy.
+    pub(crate) async fn mark_healthy(&self, proc_id: &hyperactor_reference::ProcId) -> bool {
+        match self.children.lock().await.get(proc_id) {
+            Some(handle) => handle.mark_healthy(),
+            None => false,
+        }
+    }
+
+    /// Return the resource usage of the given proc, as accounted by
+    /// the launcher backend (see
+    /// [`ProcLauncher::resource_usage`]). `None` if the backend
+    /// does not account the proc.
+    pub async fn resource_usage(
+        &self,
+        proc_id: &hyperactor_reference::ProcId,
+    ) -> Option<ProcResourceUsage> {
+        self.launcher().resource_usage(proc_id).await
+    }
+
+    /// Return the handle of the given proc, if the proc is known to
+    /// this manager. The proc stays registered, so its status remains
+    /// observable through [`Self::status`] and [`Self::watch`].
+    pub(crate) async fn handle(
+        &self,
+        proc_id: &hyperactor_reference::ProcId,
+    ) -> Option<BootstrapProcHandle> {
+        self.children.lock().await.get(proc_id).cloned()
+    }
+
     /// Return a watch receiver for the given proc's status stream,
     /// if the proc is known to this manager.
     pub async fn watch(
@@ -1934,6 +2140,17 @@ impl BootstrapProcManager {
                         "killed by signal {signal}"
                     );
                 }
+                ProcExitKind::OomKilled { memory_max } => {
+                    let _ = handle.mark_oom_killed(memory_max);
+                    tracing::warn!(
+                        name = "ProcStatus",
+                        status = "Exited::OomKilled",
+                        %proc_id,
+                        memory_max,
+                        tail = tail_str,
+                        "proc killed by the OOM killer"
+                    );
+                }
                 ProcExitKind::Failed { reason } => {
                     let _ = handle.mark_failed(&reason);
                     tracing::info!(
@@ -1950,6 +2167,20 @@ impl BootstrapProcManager {
 }
 
 pub use crate::proc_launcher::ProcBind;
+pub use crate::proc_launcher::ProcResources;
+
+/// Per-proc resource limits from the `MESH_PROC_*` limit keys, with
+/// 0 meaning "unset". `None` if no limit is set.
+fn proc_resources(overrides: &Attrs) -> Option<ProcResources> {
+    let limit = |v: u64| (v > 0).then_some(v);
+    let resources = ProcResources {
+        memory_max: limit(override_or_global(overrides, MESH_PROC_MEMORY_MAX)),
+        memory_high: limit(override_or_global(overrides, MESH_PROC_MEMORY_HIGH)),
+        cpu_quota_percent: limit(override_or_global(overrides, MESH_PROC_CPU_QUOTA_PERCENT)),
+        pids_max: limit(override_or_global(overrides, MESH_PROC_PIDS_MAX)),
+    };
+    (!resources.is_empty()).then_some(resources)
+}
 
 /// The configuration used for bootstrapped procs.
 pub struct BootstrapProcConfig {
@@ -2059,6 +2290,7 @@ impl ProcManager for BootstrapProcManager {
                 None
             },
             proc_bind: config.proc_bind.clone(),
+            resources: proc_resources(overrides),
         };
 
         // Launch via the configured launcher backend.
@@ -2232,43 +2464,68 @@ impl hyperactor::host::BulkTerminate for BootstrapProcManager {
             guard.drain().map(|(_, v)| v).collect()
         };
 
-        let attempted = handles.len();
-        let mut ok = 0usize;
+        terminate_handles(cx, handles, timeout, max_in_flight, reason).await
+    }
+}
 
-        let results = stream::iter(handles.into_iter().map(|h| async move {
-            match h.terminate(cx, timeout, reason).await {
-                Ok(_) | Err(hyperactor::host::TerminateError::AlreadyTerminated(_)) => {
-                    // Treat "already terminal" as success.
-                    true
-                }
-                Err(e) => {
-                    tracing::warn!(error=%e, "terminate_all: failed to terminate child");
-                    false
-                }
+/// Gracefully terminate the procs behind `handles`, with at most
+/// `max_in_flight` terminations running at once. This is the
+/// termination pass of `BootstrapProcManager::terminate_all`, also
+/// used to stop a subset of procs that must stay registered with the
+/// manager.
+///
+/// Returns a [`TerminateSummary`] with counts of how many procs were
+/// attempted, how many successfully terminated (including those that
+/// were already terminal), and how many failed.
+pub(crate) async fn terminate_handles(
+    cx: &impl context::Actor,
+    handles: Vec<BootstrapProcHandle>,
+    timeout: Duration,
+    max_in_flight: usize,
+    reason: &str,
+) -> TerminateSummary {
+    let attempted = handles.len();
+    let mut ok = 0usize;
+
+    let results = stream::iter(handles.into_iter().map(|h| async move {
+        match h.terminate(cx, timeout, reason).await {
+            Ok(_) | Err(hyperactor::host::TerminateError::AlreadyTerminated(_)) => {
+                // Treat "already terminal" as success.
+                true
             }
-        }))
-        .buffer_unordered(max_in_flight.max(1))
-        .collect::<Vec<bool>>()
-        .await;
-
-        for r in results {
-            if r {
-                ok += 1;
+            Err(e) => {
+                tracing::warn!(error=%e, "terminate_handles: failed to terminate child");
+                false
             }
         }
-
-        TerminateSummary {
-            attempted,
-            ok,
-            failed: attempted.saturating_sub(ok),
+    }))
+    .buffer_unordered(max_in_flight.max(1))
+    .collect::<Vec<bool>>()
+    .await;
+
+    for r in results {
+        if r {
+            ok += 1;
         }
     }
+
+    TerminateSummary {
+        attempted,
+        ok,
+        failed: attempted.saturating_sub(ok),
+    }
 }
 
 /// Entry point to processes managed by hyperactor_mesh. Any process that is part
 /// of a hyperactor_mesh program should call [`bootstrap`], which then configures
 /// the process according to how it is invoked.
 ///
+/// If [`MESH_BOOTSTRAP_LOAD_CONFIG_DIRS`] is set, bootstrap first loads
+/// the process's File configuration layer from the directories in
+/// [`hyperactor_config::global::default_config_dirs`]. Programs that
+/// bootstrap otherwise can call
+/// [`hyperactor_config::global::init_from_config_dirs`] themselves.
+///
 /// If bootstrap returns any error, it is defunct from the point of view of hyperactor_mesh,
 /// and the process should likely exit:
 ///
@@ -2282,6 +2539,19 @@ impl hyperactor::host::BulkTerminate for BootstrapProcManager {
 /// Else if the bootstrap returns Ok, the process has cleaned up successfully and
 /// should exit the "main" of the program.
 pub async fn bootstrap() -> anyhow::Result<i32> {
+    // Site, user and project config files, if opted in. A broken
+    // file is reported but does not prevent the process from starting.
+    if hyperactor_config::global::get(MESH_BOOTSTRAP_LOAD_CONFIG_DIRS) {
+        match hyperactor_config::global::init_from_config_dirs(
+            hyperactor_config::global::default_config_dirs(),
+        ) {
+            Ok(files) if !files.is_empty() => {
+                tracing::info!(?files, "bootstrap: loaded configuration files");
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("bootstrap: ignoring configuration files: {e:#}"),
+        }
+    }
     let boot = Bootstrap::get_from_env()?.unwrap_or_else(Bootstrap::default);
     boot.bootstrap().await
 }
@@ -2893,6 +3163,77 @@ mod tests {
             ));
         }
 
+        #[tokio::test]
+        async fn running_to_oom_killed_ok() {
+            let h = handle_for_test();
+            assert!(h.mark_running(std::time::SystemTime::now()));
+            assert!(h.mark_oom_killed(Some(1 << 30)));
+            let status = h.status();
+            assert!(status.is_exit());
+            assert_eq!(status.to_string(), "OomKilled(memory.max=1073741824)");
+            // Terminal: a later exit report does not overwrite it.
+            assert!(!h.mark_killed(9, false));
+        }
+
+        #[tokio::test]
+        async fn ready_to_unhealthy_and_back() {
+            let h = handle_for_test();
+            // Not serving yet: no health to report.
+            assert!(!h.mark_unhealthy(1, "early".into()));
+            let started_at = std::time::SystemTime::now();
+            assert!(h.mark_running(started_at));
+            let agent = hyperactor_reference::ActorRef::attest(
+                h.proc_id()
+                    .actor_id(crate::proc_agent::PROC_AGENT_ACTOR_NAME, 0),
+            );
+            let addr = ChannelAddr::any(ChannelTransport::Unix);
+            assert!(h.mark_ready(addr.clone(), agent));
+
+            assert!(h.mark_unhealthy(3, "timed out".into()));
+            assert!(h.mark_unhealthy(4, "still timed out".into()));
+            match h.status() {
+                ProcStatus::Unhealthy {
+                    started_at: t,
+                    addr: a,
+                    failures,
+                    reason,
+                    ..
+                } => {
+                    assert_eq!(t, started_at);
+                    assert_eq!(a, addr);
+                    assert_eq!(failures, 4);
+                    assert_eq!(reason, "still timed out");
+                }
+                other => panic!("expected Unhealthy, got {other:?}"),
+            }
+            // Still serving: address and agent remain available.
+            assert!(h.agent_ref().is_some());
+            h.ready_inner()
+                .await
+                .expect("unhealthy proc has been ready");
+
+            assert!(h.mark_healthy());
+            assert!(matches!(h.status(), ProcStatus::Ready { .. }));
+            assert!(!h.mark_healthy());
+        }
+
+        #[tokio::test]
+        async fn unhealthy_to_stopping_and_exit_ok() {
+            let h = handle_for_test();
+            assert!(h.mark_running(std::time::SystemTime::now()));
+            let agent = hyperactor_reference::ActorRef::attest(
+                h.proc_id()
+                    .actor_id(crate::proc_agent::PROC_AGENT_ACTOR_NAME, 0),
+            );
+            assert!(h.mark_ready(ChannelAddr::any(ChannelTransport::Unix), agent));
+            assert!(h.mark_unhealthy(3, "wedged".into()));
+            assert!(h.mark_stopping());
+            // Stopping procs are no longer probed.
+            assert!(!h.mark_unhealthy(4, "wedged".into()));
+            assert!(!h.mark_healthy());
+            assert!(h.mark_killed(9, false));
+        }
+
         #[tokio::test]
         async fn running_to_failed_ok() {
             let h = handle_for_test();
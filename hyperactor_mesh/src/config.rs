@@ -111,7 +111,7 @@ declare_attrs! {
     pub attr MAX_CAST_DIMENSION_SIZE: usize = 16;
 
     /// Which builtin process launcher backend to use.
-    /// Accepted values: "native" (default), "systemd".
+    /// Accepted values: "native" (default), "systemd", "sandbox".
     /// Trimmed and lowercased before matching.
     ///
     /// **Precedence:** Python spawner (via SetProcSpawner) overrides this.
@@ -121,6 +121,104 @@ declare_attrs! {
     ))
     pub attr MESH_PROC_LAUNCHER_KIND: String = String::new();
 
+    /// Sandbox launcher only: also give each proc a fresh network
+    /// namespace with just a loopback interface. The proc can then
+    /// reach only pathname Unix sockets visible in its sandbox, so
+    /// the host's backend and bootstrap channels must be served on
+    /// such addresses; TCP and abstract Unix channels (the default)
+    /// are unreachable.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_PROC_SANDBOX_NETWORK".to_string()),
+        Some("proc_sandbox_network".to_string()),
+    ).process_local())
+    pub attr MESH_PROC_SANDBOX_NETWORK: bool = false;
+
+    /// Sandbox launcher only: host directory under which each proc
+    /// gets a private writable workspace, also its working
+    /// directory. Sibling workspaces are hidden from the proc. Empty
+    /// (default) gives procs no writable workspace.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_PROC_SANDBOX_WORKSPACE_ROOT".to_string()),
+        Some("proc_sandbox_workspace_root".to_string()),
+    ).process_local())
+    pub attr MESH_PROC_SANDBOX_WORKSPACE_ROOT: String = String::new();
+
+    /// Delegated cgroup v2 directory under which the native launcher
+    /// creates one leaf cgroup per proc. Must be writable by the host
+    /// process and contain no processes itself. Empty (default)
+    /// disables cgroup placement; resource limits then fail the
+    /// launch.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_PROC_CGROUP_ROOT".to_string()),
+        Some("proc_cgroup_root".to_string()),
+    ).process_local())
+    pub attr MESH_PROC_CGROUP_ROOT: String = String::new();
+
+    /// Hard memory limit per launched proc, in bytes (`memory.max`).
+    /// A proc exceeding it is OOM-killed on its own. 0 = no limit.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_PROC_MEMORY_MAX".to_string()),
+        Some("proc_memory_max".to_string()),
+    ))
+    pub attr MESH_PROC_MEMORY_MAX: u64 = 0;
+
+    /// Memory throttling threshold per launched proc, in bytes
+    /// (`memory.high`). 0 = no threshold.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_PROC_MEMORY_HIGH".to_string()),
+        Some("proc_memory_high".to_string()),
+    ))
+    pub attr MESH_PROC_MEMORY_HIGH: u64 = 0;
+
+    /// CPU quota per launched proc, in percent of one CPU
+    /// (`cpu.max`); 250 allows 2.5 CPUs. 0 = no quota.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_PROC_CPU_QUOTA_PERCENT".to_string()),
+        Some("proc_cpu_quota_percent".to_string()),
+    ))
+    pub attr MESH_PROC_CPU_QUOTA_PERCENT: u64 = 0;
+
+    /// Maximum number of tasks per launched proc (`pids.max`).
+    /// 0 = no limit.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_PROC_PIDS_MAX".to_string()),
+        Some("proc_pids_max".to_string()),
+    ))
+    pub attr MESH_PROC_PIDS_MAX: u64 = 0;
+
+    /// Interval at which the host agent probes each ready proc
+    /// through its `ProcAgent`. 0 (default) disables health probing.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_PROC_HEALTH_PROBE_INTERVAL".to_string()),
+        Some("proc_health_probe_interval".to_string()),
+    ))
+    pub attr MESH_PROC_HEALTH_PROBE_INTERVAL: Duration = Duration::ZERO;
+
+    /// How long a health probe may go unanswered before it counts
+    /// as failed.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_PROC_HEALTH_PROBE_TIMEOUT".to_string()),
+        Some("proc_health_probe_timeout".to_string()),
+    ))
+    pub attr MESH_PROC_HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Number of consecutive failed health probes after which a proc
+    /// is reported `Unhealthy`.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_PROC_HEALTH_FAILURE_THRESHOLD".to_string()),
+        Some("proc_health_failure_threshold".to_string()),
+    ))
+    pub attr MESH_PROC_HEALTH_FAILURE_THRESHOLD: usize = 3;
+
+    /// Whether the host agent stops a proc once it becomes
+    /// `Unhealthy`, escalating to a kill after the process exit
+    /// timeout.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_PROC_HEALTH_TERMINATE_UNHEALTHY".to_string()),
+        Some("proc_health_terminate_unhealthy".to_string()),
+    ))
+    pub attr MESH_PROC_HEALTH_TERMINATE_UNHEALTHY: bool = false;
+
     /// Default socket address for the mesh admin HTTP server.
     ///
     /// Parsed as a `SocketAddr` (e.g. `[::]:1729`, `0.0.0.0:8080`).
@@ -156,6 +254,52 @@ declare_attrs! {
     ))
     pub attr MESH_ADMIN_MAX_CONCURRENT_RESOLVES: usize = 2;
 
+    /// Number of events the mesh admin retains for `GET /v1/events`
+    /// resumption (EV-2). Also bounds how far a live stream may lag
+    /// before it is cut with an `event_gap` error (EV-4).
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_ADMIN_EVENT_BUFFER_SIZE".to_string()),
+        Some("mesh_admin_event_buffer_size".to_string()),
+    ))
+    pub attr MESH_ADMIN_EVENT_BUFFER_SIZE: usize = 1024;
+
+    /// Path to a file holding the bearer token that authorizes mesh
+    /// admin actions (`POST /v1/actions/*`). Empty disables token
+    /// authentication. The token is read from a file rather than
+    /// configured directly so that it never shows up in config dumps.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_ADMIN_ACTION_TOKEN_FILE".to_string()),
+        Some("mesh_admin_action_token_file".to_string()),
+    ))
+    pub attr MESH_ADMIN_ACTION_TOKEN_FILE: String = String::new();
+
+    /// Comma-separated mTLS client identities (`sha256:<hex>` leaf
+    /// certificate fingerprints) allowed to perform mesh admin
+    /// actions. `*` admits any client certificate the server's CA
+    /// verified. Empty disables mTLS authentication of actions.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_ADMIN_ACTION_ALLOWED_CLIENTS".to_string()),
+        Some("mesh_admin_action_allowed_clients".to_string()),
+    ))
+    pub attr MESH_ADMIN_ACTION_ALLOWED_CLIENTS: String = String::new();
+
+    /// Number of audit entries the mesh admin retains for
+    /// `GET /v1/actions/audit`. Every entry is also logged.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_ADMIN_ACTION_AUDIT_SIZE".to_string()),
+        Some("mesh_admin_action_audit_size".to_string()),
+    ))
+    pub attr MESH_ADMIN_ACTION_AUDIT_SIZE: usize = 256;
+
+    /// How long a synchronous mesh admin action (host drain or
+    /// shutdown) may take before the request fails with
+    /// `gateway_timeout`. The action itself keeps running.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_ADMIN_ACTION_TIMEOUT".to_string()),
+        Some("mesh_admin_action_timeout".to_string()),
+    ))
+    pub attr MESH_ADMIN_ACTION_TIMEOUT: Duration = Duration::from_mins(2);
+
     /// Timeout for the config-push barrier during `HostMesh::attach()`.
     ///
     /// When attaching to pre-existing workers (simple bootstrap), the
@@ -169,6 +313,16 @@ declare_attrs! {
     ))
     pub attr MESH_ATTACH_CONFIG_TIMEOUT: Duration = Duration::from_secs(10);
 
+    /// How long a host agent waits for each of its procs to
+    /// acknowledge a runtime config push (see
+    /// `HostMeshRef::set_runtime_config`). The client waits twice as
+    /// long for each host.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_MESH_RUNTIME_CONFIG_TIMEOUT".to_string()),
+        Some("mesh_runtime_config_timeout".to_string()),
+    ))
+    pub attr MESH_RUNTIME_CONFIG_TIMEOUT: Duration = Duration::from_secs(10);
+
     /// Timeout for targeted introspection queries that hit a single,
     /// specific host. Kept short so a slow or dying actor cannot block
     /// the single-threaded MeshAdminAgent message loop.
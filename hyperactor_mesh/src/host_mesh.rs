@@ -23,6 +23,7 @@ use crate::supervision::MeshFailure;
 
 pub mod host_agent;
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::hash::Hash;
 use std::ops::Deref;
@@ -60,11 +61,13 @@ use crate::host_mesh::host_agent::HostAgentMode;
 use crate::host_mesh::host_agent::HostMeshAgentProcMeshTrampoline;
 use crate::host_mesh::host_agent::ProcManagerSpawnFn;
 use crate::host_mesh::host_agent::ProcState;
+use crate::host_mesh::host_agent::RestartProcsClient;
 use crate::host_mesh::host_agent::SetClientConfigClient;
 use crate::host_mesh::host_agent::ShutdownHostClient;
 use crate::mesh_controller::HostMeshController;
 use crate::mesh_controller::ProcMeshController;
 use crate::proc_agent::ProcAgent;
+use crate::proc_agent::RecordedActor;
 use crate::proc_mesh::ProcRef;
 use crate::resource;
 use crate::resource::CreateOrUpdateClient;
@@ -73,6 +76,9 @@ use crate::resource::GetRankStatusClient;
 use crate::resource::RankedValues;
 use crate::resource::Status;
 use crate::resource::WaitRankStatusClient;
+use crate::runtime_config::RuntimeConfigAck;
+use crate::runtime_config::RuntimeConfigStatus;
+use crate::runtime_config::SetHostRuntimeConfigClient;
 use crate::transport::DEFAULT_TRANSPORT;
 
 /// Actor name for `HostMeshController` when spawned as a named child.
@@ -1164,6 +1170,68 @@ impl HostMeshRef {
         }
     }
 
+    /// Push a runtime config delta to every host in this mesh and to
+    /// every live proc on those hosts, in parallel. Each process merges
+    /// `attrs` into its `Source::Runtime` layer.
+    ///
+    /// Returns one acknowledgement per proc; see the RC-* invariants in
+    /// [`crate::runtime_config`]. Each host is given twice
+    /// `MESH_RUNTIME_CONFIG_TIMEOUT` to reply, so that it can report
+    /// its own slow procs first.
+    ///
+    /// The caller's own process is not changed, and procs spawned
+    /// later bootstrap from the caller's propagated configuration
+    /// (RC-4). Callers that want later procs to see the delta must
+    /// also merge it locally:
+    ///
+    /// ```ignore
+    /// hyperactor_config::global::create_or_merge(Source::Runtime, attrs.clone());
+    /// let acks = host_mesh.set_runtime_config(cx, attrs).await;
+    /// ```
+    pub async fn set_runtime_config(
+        &self,
+        cx: &impl context::Actor,
+        attrs: hyperactor_config::attrs::Attrs,
+    ) -> Vec<RuntimeConfigAck> {
+        let timeout =
+            hyperactor_config::global::get(crate::config::MESH_RUNTIME_CONFIG_TIMEOUT) * 2;
+        let replies = futures::future::join_all(self.values().map(|host| {
+            let attrs = attrs.clone();
+            async move {
+                let agent = host.mesh_agent();
+                let reason =
+                    match tokio::time::timeout(timeout, agent.set_host_runtime_config(cx, attrs))
+                        .await
+                    {
+                        Ok(Ok(acks)) => return acks,
+                        Ok(Err(e)) => e.to_string(),
+                        Err(_) => format!(
+                            "host did not reply within {}",
+                            humantime::format_duration(timeout)
+                        ),
+                    };
+                tracing::warn!(
+                    host = %agent.actor_id(),
+                    reason = %reason,
+                    "runtime config push to host agent failed",
+                );
+                vec![RuntimeConfigAck {
+                    proc_id: agent.actor_id().proc_id().clone(),
+                    status: RuntimeConfigStatus::Unacknowledged(reason),
+                }]
+            }
+        }))
+        .await;
+
+        let acks: Vec<_> = replies.into_iter().flatten().collect();
+        tracing::info!(
+            procs = acks.len(),
+            applied = acks.iter().filter(|ack| ack.is_applied()).count(),
+            "runtime config push complete",
+        );
+        acks
+    }
+
     /// Spawn a ProcMesh onto this host mesh. The per_host extent specifies the shape
     /// of the procs to spawn on each host.
     ///
@@ -1448,6 +1516,16 @@ impl HostMeshRef {
             // port entries and messages (including introspection queries)
             // are returned as undeliverable.
             let _: hyperactor::reference::ActorRef<ProcMeshController> = controller_handle.bind();
+            // Feed the admin event stream, if this process runs an admin.
+            if let Some(admin) = crate::mesh_admin::local_admin()
+                && let Err(e) = crate::mesh_admin::watch_proc_mesh(cx, &admin, mesh)
+            {
+                tracing::warn!(
+                    proc_mesh = %mesh.name(),
+                    "failed to watch proc mesh for the admin: {:#}",
+                    e,
+                );
+            }
         }
         mesh
     }
@@ -1575,6 +1653,98 @@ impl HostMeshRef {
         Ok(())
     }
 
+    /// Restart each of `procs` in place, recreating the given actors
+    /// on its replacement (see RR-* in [`host_agent`]). The
+    /// replacements run `bootstrap_command`, or the procs' current
+    /// command, with `env` set on top. Returns once every proc is
+    /// running again, or with the first failure.
+    pub(crate) async fn restart_procs(
+        &self,
+        cx: &impl hyperactor::context::Actor,
+        procs: Vec<(hyperactor_reference::ProcId, Vec<RecordedActor>)>,
+        bootstrap_command: Option<BootstrapCommand>,
+        env: HashMap<String, String>,
+    ) -> anyhow::Result<()> {
+        // As in `drain`, old procs are stopped through the host's
+        // termination pass, bounded per host.
+        let stop_timeout = hyperactor_config::global::get(crate::bootstrap::MESH_TERMINATE_TIMEOUT);
+        let max_in_flight =
+            hyperactor_config::global::get(crate::bootstrap::MESH_TERMINATE_CONCURRENCY)
+                .clamp(1, 256);
+
+        // One request per host, keeping the procs' order.
+        let mut by_host: Vec<(
+            HostRef,
+            Vec<(hyperactor_reference::ProcId, Vec<RecordedActor>)>,
+        )> = Vec::new();
+        for (proc_id, actors) in procs {
+            let host = HostRef(proc_id.addr().clone());
+            match by_host.iter_mut().find(|(other, _)| *other == host) {
+                Some((_, host_procs)) => host_procs.push((proc_id, actors)),
+                None => by_host.push((host, vec![(proc_id, actors)])),
+            }
+        }
+
+        let mut pending = Vec::new();
+        let mut max_per_host = 0;
+        for (host, host_procs) in by_host {
+            max_per_host = max_per_host.max(host_procs.len());
+            let mut proc_ids = Vec::with_capacity(host_procs.len());
+            let mut requests = Vec::with_capacity(host_procs.len());
+            for (proc_id, actors) in host_procs {
+                // As in `stop_proc_mesh`, the host agent knows the proc
+                // by the name in its id.
+                let proc_name = proc_id.name().parse::<Name>()?;
+                proc_ids.push((proc_id, proc_name.clone()));
+                requests.push((proc_name, actors));
+            }
+            if let Some(reason) = host
+                .mesh_agent()
+                .restart_procs(
+                    cx,
+                    requests,
+                    bootstrap_command.clone(),
+                    env.clone(),
+                    stop_timeout,
+                    max_in_flight,
+                )
+                .await?
+            {
+                anyhow::bail!("failed to restart procs on {}: {}", host, reason);
+            }
+            for (proc_id, proc_name) in proc_ids {
+                let (port, rx) = cx.mailbox().open_port::<crate::StatusOverlay>();
+                host.mesh_agent()
+                    .wait_rank_status(cx, proc_name, Status::Running, port.bind())
+                    .await?;
+                tracing::info!(name = "ProcMeshStatus", %proc_id, status = "Restart::Sent");
+                pending.push((proc_id, rx));
+            }
+        }
+
+        // Old procs may take the full grace period to stop, in as
+        // many rounds as the concurrency bound requires, before their
+        // replacements are spawned.
+        let rounds = max_per_host.div_ceil(max_in_flight) as u32;
+        let max_wait = stop_timeout * rounds + hyperactor_config::global::get(PROC_SPAWN_MAX_IDLE);
+        for (proc_id, mut rx) in pending {
+            let overlay = tokio::time::timeout(max_wait, rx.recv())
+                .await
+                .map_err(|_| {
+                    anyhow::anyhow!("timed out waiting for proc {} to restart", proc_id)
+                })??;
+            let status = overlay
+                .runs()
+                .next()
+                .map_or(Status::NotExist, |(_, status)| status.clone());
+            if status != Status::Running {
+                anyhow::bail!("proc {} failed to restart: {}", proc_id, status);
+            }
+            tracing::info!(name = "ProcMeshStatus", %proc_id, status = "Restarted");
+        }
+        Ok(())
+    }
+
     /// Get the state of all procs with Name in this host mesh.
     /// The procs iterator must be in rank order.
     /// The returned ValueMesh will have a non-empty inner state unless there
@@ -1794,6 +1964,7 @@ pub async fn spawn_admin(
         ),
     )?;
     let admin_ref = agent_handle.bind();
+    crate::mesh_admin::set_local_admin(admin_ref.clone());
     Ok(admin_ref)
 }
 
@@ -2300,6 +2471,59 @@ mod tests {
         let _ = hm.shutdown(instance).await;
     }
 
+    #[tokio::test]
+    #[cfg(fbcode_build)]
+    async fn test_set_runtime_config() {
+        let _config = hyperactor_config::global::lock();
+        let instance = testing::instance();
+
+        let mut hm = testing::host_mesh(2).await;
+        let proc_mesh = hm
+            .spawn(instance, "test", Extent::unity(), None)
+            .await
+            .unwrap();
+        let actor_mesh: ActorMesh<testactor::TestActor> =
+            proc_mesh.spawn(instance, "test", &()).await.unwrap();
+
+        let mut delta = Attrs::new();
+        delta.set(
+            hyperactor::config::HOST_SPAWN_READY_TIMEOUT,
+            Duration::from_mins(7),
+        );
+        let acks = hm.set_runtime_config(instance, delta).await;
+        // RC-3: each host's service proc, and each spawned proc.
+        assert_eq!(acks.len(), 4, "{acks:?}");
+        assert!(acks.iter().all(RuntimeConfigAck::is_applied), "{acks:?}");
+
+        let (tx, mut rx) = instance.open_port();
+        actor_mesh
+            .cast(instance, GetConfigAttrs(tx.bind()))
+            .unwrap();
+        for _ in 0..2 {
+            let attrs = bincode::deserialize::<Attrs>(&rx.recv().await.unwrap()).unwrap();
+            assert_eq!(
+                *attrs
+                    .get(hyperactor::config::HOST_SPAWN_READY_TIMEOUT)
+                    .unwrap(),
+                Duration::from_mins(7)
+            );
+        }
+
+        // RC-2: invalid values are rejected, and reported per proc.
+        let mut delta = Attrs::new();
+        delta.set(hyperactor::config::MESSAGE_ACK_EVERY_N_MESSAGES, 0);
+        let acks = hm.set_runtime_config(instance, delta).await;
+        assert_eq!(acks.len(), 4, "{acks:?}");
+        for ack in &acks {
+            let RuntimeConfigStatus::Rejected(errors) = &ack.status else {
+                panic!("expected rejection: {ack:?}");
+            };
+            assert_eq!(errors.len(), 1, "{errors:?}");
+        }
+
+        let _ = hm.shutdown(instance).await;
+    }
+
     // ---- SA-* invariant tests ----
 
     #[tokio::test]
@@ -7,6 +7,58 @@
  */
 
 //! The mesh agent actor that manages a host.
+//!
+//! ## Health probing
+//!
+//! Readiness is a one-time bootstrap callback; after it, the host
+//! agent probes each process-mode proc every
+//! `MESH_PROC_HEALTH_PROBE_INTERVAL` by sending a [`HealthCheck`] to
+//! its `ProcAgent` (which also runs any probe installed with
+//! [`crate::proc_agent::set_health_probe`]).
+//!
+//! - **HP-1 (one-outstanding):** At most one probe per proc is in
+//!   flight. A probe fails when it reports an error or is unanswered
+//!   after `MESH_PROC_HEALTH_PROBE_TIMEOUT`; a late reply is ignored.
+//! - **HP-2 (threshold):** After `MESH_PROC_HEALTH_FAILURE_THRESHOLD`
+//!   consecutive failures the proc is reported
+//!   [`bootstrap::ProcStatus::Unhealthy`]; the next passed probe
+//!   returns it to `Ready`.
+//! - **HP-3 (terminate):** With `MESH_PROC_HEALTH_TERMINATE_UNHEALTHY`
+//!   set, a proc is stopped once, when it first becomes unhealthy,
+//!   through the same path as `resource::Stop`.
+//! - **HP-4 (live interval):** Changes to
+//!   `MESH_PROC_HEALTH_PROBE_INTERVAL` take effect without a restart:
+//!   setting it to zero stops probing at the next tick, and setting a
+//!   non-zero value while probing is stopped starts it again. At most
+//!   one tick is pending at any time.
+//!
+//! In-process (local) hosts have no proc status and are not probed.
+//!
+//! ## Rolling restart
+//!
+//! A [`RestartProcs`] replaces process-mode procs with fresh ones,
+//! typically running a new bootstrap command, and recreates each
+//! proc's actors on its replacement from the specs recorded by its
+//! `ProcAgent`.
+//!
+//! - **RR-1 (same identity):** The replacement is spawned under the
+//!   same name once the old proc has terminated, so its `ProcId`, and
+//!   the `ActorId`s of the recreated actors, are unchanged.
+//! - **RR-2 (initializing):** Until the restart completes, the proc
+//!   reports `Status::Initializing` rather than its raw status, so
+//!   that the planned stop is not mistaken for a failure.
+//! - **RR-3 (barrier):** The restart completes only after the
+//!   replacement's `ProcAgent` has handled the recreation of every
+//!   recorded actor, in the original order. The barrier is a
+//!   [`Barrier`], not a health check, so that a wedged user probe
+//!   cannot hold up the restart.
+//! - **RR-4 (bounded stop):** The old procs are stopped off the
+//!   agent's message loop by a `RestartWorker`, through the same
+//!   termination pass as `DrainHost`, with at most `max_in_flight`
+//!   terminations running at once.
+//!
+//! If the replacement fails to spawn, or terminates before the
+//! restart completes, the proc reports its failed status.
 
 // EnumAsInner generates code that triggers a false positive
 // unused_assignments lint on struct variant fields. #[allow] on the
@@ -52,16 +104,34 @@ use crate::Name;
 use crate::bootstrap;
 use crate::bootstrap::BootstrapCommand;
 use crate::bootstrap::BootstrapProcConfig;
+use crate::bootstrap::BootstrapProcHandle;
 use crate::bootstrap::BootstrapProcManager;
+use crate::config::MESH_PROC_HEALTH_FAILURE_THRESHOLD;
+use crate::config::MESH_PROC_HEALTH_PROBE_INTERVAL;
+use crate::config::MESH_PROC_HEALTH_PROBE_TIMEOUT;
+use crate::config::MESH_PROC_HEALTH_TERMINATE_UNHEALTHY;
+use crate::config::MESH_RUNTIME_CONFIG_TIMEOUT;
 use crate::config_dump::ConfigDump;
 use crate::config_dump::ConfigDumpResult;
+use crate::proc_agent::ActorSpec;
+use crate::proc_agent::Barrier;
+use crate::proc_agent::BarrierReached;
+use crate::proc_agent::HealthCheck;
+use crate::proc_agent::HealthCheckResult;
 use crate::proc_agent::ProcAgent;
+use crate::proc_agent::RecordedActor;
+use crate::proc_launcher::ProcResourceUsage;
 use crate::pyspy::PySpyDump;
 use crate::pyspy::PySpyProfile;
 use crate::pyspy::PySpyProfileWorker;
 use crate::pyspy::PySpyWorker;
 use crate::resource;
 use crate::resource::ProcSpec;
+use crate::runtime_config;
+use crate::runtime_config::RuntimeConfigAck;
+use crate::runtime_config::RuntimeConfigStatus;
+use crate::runtime_config::SetHostRuntimeConfig;
+use crate::runtime_config::SetRuntimeConfigClient;
 
 pub(crate) type ProcManagerSpawnFuture =
     Pin<Box<dyn Future<Output = anyhow::Result<ActorHandle<ProcAgent>>> + Send>>;
@@ -158,6 +228,18 @@ impl HostAgentMode {
         }
     }
 
+    /// Launcher-accounted resource usage of a proc, if any. Only
+    /// process-mode hosts account resources.
+    async fn proc_resource_usage(
+        &self,
+        proc_id: &hyperactor_reference::ProcId,
+    ) -> Option<ProcResourceUsage> {
+        match self {
+            HostAgentMode::Process { host, .. } => host.manager().resource_usage(proc_id).await,
+            HostAgentMode::Local(_) => None,
+        }
+    }
+
     /// The bootstrap command used by the process manager, if any.
     fn bootstrap_command(&self) -> Option<BootstrapCommand> {
         match self {
@@ -170,7 +252,9 @@ impl HostAgentMode {
 #[derive(Debug)]
 pub(crate) struct ProcCreationState {
     pub(crate) rank: usize,
-    pub(crate) host_mesh_name: Option<crate::Name>,
+    /// The spec the proc was spawned with; reused when it is
+    /// restarted.
+    pub(crate) spec: ProcSpec,
     pub(crate) created: Result<
         (
             hyperactor_reference::ProcId,
@@ -207,6 +291,84 @@ struct ProcStatusChanged {
     name: Name,
 }
 
+/// Self-message that sends the next round of health probes (see
+/// HP-* in the module doc). Not exported.
+#[derive(Debug, Serialize, Deserialize, Named)]
+struct HealthProbeTick;
+
+/// Self-notification sent by the config watcher when
+/// `MESH_PROC_HEALTH_PROBE_INTERVAL` changes (HP-4). Not exported.
+#[derive(Debug, Serialize, Deserialize, Named)]
+struct HealthProbeIntervalChanged;
+
+/// Self-message that fails `probe_id` if it is still unanswered (HP-1).
+/// Not exported.
+#[derive(Debug, Serialize, Deserialize, Named)]
+struct HealthProbeTimeout {
+    probe_id: u64,
+}
+
+/// Health probing state of one proc.
+#[derive(Debug, Default)]
+struct ProcHealth {
+    /// Id of the probe in flight, if any.
+    outstanding: Option<u64>,
+    /// Consecutive failed probes.
+    failures: usize,
+}
+
+/// A restart in progress (see RR-* in the module doc).
+#[derive(Debug)]
+struct PendingRestart {
+    /// Command override for the replacement.
+    bootstrap_command: Option<BootstrapCommand>,
+    /// Environment set on top of the replacement's command.
+    env: HashMap<String, String>,
+    /// Actors to recreate on the replacement, in creation order.
+    actors: Vec<RecordedActor>,
+    /// Id of the [`Barrier`] sent after the actors, once the
+    /// replacement has been spawned (RR-3).
+    barrier: Option<u64>,
+}
+
+/// Child actor that stops the procs being restarted (RR-4) and exits.
+/// Unlike [`DrainWorker`], it does not take the host: the procs stay
+/// registered with the manager, and their status bridges report each
+/// termination, upon which the host agent spawns the replacement.
+#[hyperactor::export(handlers = [])]
+struct RestartWorker {
+    handles: Vec<BootstrapProcHandle>,
+    timeout: Duration,
+    max_in_flight: usize,
+}
+
+#[async_trait]
+impl Actor for RestartWorker {
+    async fn init(&mut self, this: &Instance<Self>) -> Result<(), anyhow::Error> {
+        let summary = bootstrap::terminate_handles(
+            this,
+            std::mem::take(&mut self.handles),
+            self.timeout,
+            self.max_in_flight.clamp(1, 256),
+            "rolling restart",
+        )
+        .await;
+        tracing::info!(?summary, "stopped procs for restart");
+        this.stop("restart stop complete")?;
+        Ok(())
+    }
+}
+
+impl fmt::Debug for RestartWorker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RestartWorker")
+            .field("num_procs", &self.handles.len())
+            .field("timeout", &self.timeout)
+            .field("max_in_flight", &self.max_in_flight)
+            .finish()
+    }
+}
+
 /// Sent by DrainWorker back to HostAgent when draining completes.
 /// Not exported — delivered locally via PortHandle (no serialization).
 struct DrainComplete {
@@ -267,6 +429,64 @@ impl fmt::Debug for DrainWorker {
     }
 }
 
+/// Child actor that pushes a runtime config delta to the host's live
+/// procs, replies with every acknowledgement (RC-3), and exits, so
+/// that slow procs do not hold up the host agent's message loop.
+#[derive(Debug)]
+#[hyperactor::export(handlers = [])]
+struct RuntimeConfigWorker {
+    attrs: Attrs,
+    /// Acknowledgements gathered before the worker was spawned.
+    acks: Vec<RuntimeConfigAck>,
+    procs: Vec<(
+        hyperactor_reference::ProcId,
+        hyperactor_reference::ActorRef<ProcAgent>,
+    )>,
+    timeout: Duration,
+    result: Option<hyperactor_reference::OncePortRef<Vec<RuntimeConfigAck>>>,
+}
+
+#[async_trait]
+impl Actor for RuntimeConfigWorker {
+    async fn init(&mut self, this: &Instance<Self>) -> Result<(), anyhow::Error> {
+        let timeout = self.timeout;
+        let procs = std::mem::take(&mut self.procs);
+        let mut acks = std::mem::take(&mut self.acks);
+        acks.extend(
+            futures::future::join_all(procs.into_iter().map(|(proc_id, agent)| {
+                let attrs = self.attrs.clone();
+                async move {
+                    let reason =
+                        match tokio::time::timeout(timeout, agent.set_runtime_config(this, attrs))
+                            .await
+                        {
+                            Ok(Ok(ack)) => return ack,
+                            Ok(Err(e)) => e.to_string(),
+                            Err(_) => format!(
+                                "no acknowledgement within {}",
+                                humantime::format_duration(timeout)
+                            ),
+                        };
+                    RuntimeConfigAck {
+                        proc_id,
+                        status: RuntimeConfigStatus::Unacknowledged(reason),
+                    }
+                }
+            }))
+            .await,
+        );
+
+        // Reply is best-effort: the caller may have timed out.
+        if let Some(result) = self.result.take()
+            && let Err(e) = result.send(this, acks)
+        {
+            tracing::warn!("HostAgent: SetHostRuntimeConfig reply undeliverable: {e}");
+        }
+        this.stop("runtime config pushed")?;
+        Ok(())
+    }
+}
+
 #[hyperactor::export(
     handlers=[
         resource::CreateOrUpdate<ProcSpec>,
@@ -282,6 +502,10 @@ impl fmt::Debug for DrainWorker {
         PySpyDump,
         PySpyProfile,
         ConfigDump,
+        SetHostRuntimeConfig,
+        HealthCheckResult,
+        RestartProcs,
+        BarrierReached,
     ]
 )]
 pub struct HostAgent {
@@ -302,6 +526,16 @@ pub struct HostAgent {
     watching: HashSet<Name>,
     /// Port handle for sending `ProcStatusChanged` to self. Set in `init()`.
     proc_status_port: Option<PortHandle<ProcStatusChanged>>,
+    /// Health probing state of procs that have been probed.
+    health: HashMap<Name, ProcHealth>,
+    /// Id of the last health probe sent.
+    last_probe_id: u64,
+    /// Whether a [`HealthProbeTick`] is pending (HP-4).
+    probe_armed: bool,
+    /// Id of the last restart [`Barrier`] sent (RR-3).
+    last_barrier_id: u64,
+    /// Procs being restarted.
+    restarting: HashMap<Name, PendingRestart>,
     /// Lazily initialized ProcAgent on the host's local proc.
     /// Boots on first [`GetLocalProc`] (LP-1 — see
     /// `hyperactor::host::LOCAL_PROC_NAME`).
@@ -323,6 +557,11 @@ impl HostAgent {
             pending_proc_waiters: HashMap::new(),
             watching: HashSet::new(),
             proc_status_port: None,
+            health: HashMap::new(),
+            last_probe_id: 0,
+            probe_armed: false,
+            last_barrier_id: 0,
+            restarting: HashMap::new(),
             local_mesh_agent: OnceLock::new(),
             mailbox_handle: None,
         }
@@ -394,7 +633,7 @@ impl HostAgent {
         let matching_names: Vec<crate::Name> = self
             .created
             .iter()
-            .filter(|(_, state)| state.host_mesh_name.as_ref() == filter)
+            .filter(|(_, state)| state.spec.host_mesh_name.as_ref() == filter)
             .map(|(name, _)| name.clone())
             .collect();
 
@@ -546,6 +785,10 @@ impl Actor for HostAgent {
                     // outside the iteration. Stale keys (terminal
                     // actors) may appear but are harmless — the TUI
                     // handles "not found" gracefully.
+                    //
+                    // The generation is read first so that it never
+                    // runs ahead of the state reported with it.
+                    let generation = proc.introspect_generation();
                     let all_keys = proc.all_instance_keys();
                     let mut actors: Vec<hyperactor::introspect::IntrospectRef> =
                         Vec::with_capacity(all_keys.len());
@@ -561,6 +804,7 @@ impl Actor for HostAgent {
                     attrs.set(crate::introspect::PROC_NAME, label.to_string());
                     attrs.set(crate::introspect::NUM_ACTORS, actors.len());
                     attrs.set(crate::introspect::SYSTEM_CHILDREN, system_actors.clone());
+                    attrs.set(crate::introspect::INTROSPECT_GENERATION, generation);
                     // PD-*: include hosting-process memory so QueryChild
                     // results match the publish path. HostAgent cannot
                     // aggregate per-actor queue depth for these procs
@@ -615,6 +859,23 @@ impl Actor for HostAgent {
 
         self.proc_status_port = Some(this.port::<ProcStatusChanged>());
 
+        self.arm_health_probes(this)?;
+        // HP-4: re-arm when the interval is changed at runtime, e.g.
+        // through `SetHostRuntimeConfig`.
+        let mut probe_interval =
+            hyperactor_config::global::subscribe(MESH_PROC_HEALTH_PROBE_INTERVAL);
+        let interval_changed = this.port::<HealthProbeIntervalChanged>();
+        tokio::spawn(async move {
+            while probe_interval.changed().await.is_ok() {
+                if interval_changed
+                    .send(Instance::<()>::self_client(), HealthProbeIntervalChanged)
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
         Ok(())
     }
 }
@@ -683,7 +944,7 @@ impl Handler<resource::CreateOrUpdate<ProcSpec>> for HostAgent {
             create_or_update.name.clone(),
             ProcCreationState {
                 rank,
-                host_mesh_name: create_or_update.spec.host_mesh_name.clone(),
+                spec: create_or_update.spec,
                 created,
             },
         );
@@ -794,7 +1055,8 @@ impl Handler<resource::GetRankStatus> for HostAgent {
                     Some(host) => host.proc_status(proc_id).await.0,
                     None => resource::Status::Unknown,
                 };
-                (*rank, raw_status.clamp_min(self.min_proc_status()))
+                let status = self.restart_status(&get_rank_status.name, raw_status);
+                (*rank, status.clamp_min(self.min_proc_status()))
             }
             Some(ProcCreationState {
                 rank,
@@ -847,6 +1109,7 @@ impl Handler<resource::WaitRankStatus> for HostAgent {
                     Some(host) => host.proc_status(proc_id).await.0,
                     None => Status::Stopped,
                 };
+                let status = self.restart_status(&msg.name, status);
 
                 // If already at or past the requested threshold, reply immediately.
                 if status >= msg.min_status {
@@ -899,6 +1162,10 @@ impl Handler<ProcStatusChanged> for HostAgent {
         use crate::StatusOverlay;
         use crate::resource::Status;
 
+        if self.restarting.contains_key(&msg.name) {
+            self.advance_restart(cx, &msg.name).await;
+        }
+
         let status = match self.created.get(&msg.name) {
             Some(ProcCreationState {
                 created: Ok((proc_id, _)),
@@ -908,16 +1175,19 @@ impl Handler<ProcStatusChanged> for HostAgent {
                 None => Status::Stopped,
             },
             Some(ProcCreationState {
-                created: Err(_), ..
+                created: Err(e), ..
             }) => {
-                // Already replied with Failed when they were stashed.
-                return Ok(());
+                // Waiters stashed while the proc existed are flushed
+                // here if a restart failed to spawn its replacement;
+                // others were replied with Failed when they arrived.
+                Status::Failed(e.to_string())
             }
             None => {
                 // Proc not created yet, nothing to flush.
                 return Ok(());
             }
         };
+        let status = self.restart_status(&msg.name, status);
 
         let Some(waiters) = self.pending_proc_waiters.get_mut(&msg.name) else {
             return Ok(());
@@ -943,7 +1213,344 @@ impl Handler<ProcStatusChanged> for HostAgent {
     }
 }
 
+#[async_trait]
+impl Handler<HealthProbeTick> for HostAgent {
+    async fn handle(&mut self, cx: &Context<Self>, _: HealthProbeTick) -> anyhow::Result<()> {
+        let interval = hyperactor_config::global::get(MESH_PROC_HEALTH_PROBE_INTERVAL);
+        if interval.is_zero() || self.host().is_none() {
+            // Disabled since, or shut down: stop probing.
+            self.probe_armed = false;
+            return Ok(());
+        }
+        self.send_health_probes(cx).await?;
+        cx.self_message_with_delay(HealthProbeTick, interval)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Handler<HealthProbeIntervalChanged> for HostAgent {
+    async fn handle(
+        &mut self,
+        cx: &Context<Self>,
+        _: HealthProbeIntervalChanged,
+    ) -> anyhow::Result<()> {
+        self.arm_health_probes(cx)
+    }
+}
+
+#[async_trait]
+impl Handler<HealthCheckResult> for HostAgent {
+    async fn handle(&mut self, cx: &Context<Self>, msg: HealthCheckResult) -> anyhow::Result<()> {
+        self.record_health_probe(cx, msg.probe_id, msg.result).await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Handler<BarrierReached> for HostAgent {
+    async fn handle(&mut self, _cx: &Context<Self>, msg: BarrierReached) -> anyhow::Result<()> {
+        let restarted = self
+            .restarting
+            .iter()
+            .find(|(_, restart)| restart.barrier == Some(msg.id))
+            .map(|(name, _)| name.clone());
+        if let Some(name) = restarted {
+            // RR-3: the replacement has recreated its actors.
+            self.restarting.remove(&name);
+            tracing::info!(proc_name = %name, "proc restarted");
+            self.notify_proc_status_changed(&name);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Handler<HealthProbeTimeout> for HostAgent {
+    async fn handle(&mut self, cx: &Context<Self>, msg: HealthProbeTimeout) -> anyhow::Result<()> {
+        let timeout = hyperactor_config::global::get(MESH_PROC_HEALTH_PROBE_TIMEOUT);
+        self.record_health_probe(
+            cx,
+            msg.probe_id,
+            Err(format!(
+                "no reply within {}",
+                humantime::format_duration(timeout)
+            )),
+        )
+        .await;
+        Ok(())
+    }
+}
+
 impl HostAgent {
+    /// Schedule the first [`HealthProbeTick`] if probing is enabled
+    /// and no tick is pending (HP-4).
+    fn arm_health_probes(&mut self, this: &Instance<Self>) -> anyhow::Result<()> {
+        let interval = hyperactor_config::global::get(MESH_PROC_HEALTH_PROBE_INTERVAL);
+        if self.probe_armed || interval.is_zero() || self.host().is_none() {
+            return Ok(());
+        }
+        this.self_message_with_delay(HealthProbeTick, interval)?;
+        self.probe_armed = true;
+        Ok(())
+    }
+
+    /// Probe every serving process-mode proc that has no probe in
+    /// flight (HP-1).
+    async fn send_health_probes(&mut self, cx: &Context<'_, Self>) -> anyhow::Result<()> {
+        let Some(HostAgentMode::Process { host, .. }) = self.host() else {
+            return Ok(());
+        };
+        let mut serving = Vec::new();
+        for (name, state) in &self.created {
+            let Ok((proc_id, agent)) = &state.created else {
+                continue;
+            };
+            if matches!(
+                host.manager().status(proc_id).await,
+                Some(bootstrap::ProcStatus::Ready { .. } | bootstrap::ProcStatus::Unhealthy { .. })
+            ) {
+                serving.push((name.clone(), agent.clone()));
+            }
+        }
+        self.health
+            .retain(|name, _| serving.iter().any(|(serving_name, _)| serving_name == name));
+
+        let timeout = hyperactor_config::global::get(MESH_PROC_HEALTH_PROBE_TIMEOUT);
+        let reply = cx.port::<HealthCheckResult>().bind();
+        for (name, agent) in serving {
+            let health = self.health.entry(name).or_default();
+            if health.outstanding.is_some() {
+                continue;
+            }
+            self.last_probe_id += 1;
+            let probe_id = self.last_probe_id;
+            health.outstanding = Some(probe_id);
+            // An unreachable proc must not take down the host agent;
+            // the timeout below counts it as a failure.
+            let mut agent_port = agent.port();
+            agent_port.return_undeliverable(false);
+            let _ = agent_port.send(
+                cx,
+                HealthCheck {
+                    probe_id,
+                    reply: reply.clone(),
+                },
+            );
+            cx.self_message_with_delay(HealthProbeTimeout { probe_id }, timeout)?;
+        }
+        Ok(())
+    }
+
+    /// Record the outcome of `probe_id`, updating the proc's status
+    /// (HP-2) and stopping it if configured (HP-3). Outcomes of
+    /// probes no longer in flight are ignored.
+    async fn record_health_probe(
+        &mut self,
+        cx: &Context<'_, Self>,
+        probe_id: u64,
+        result: Result<(), String>,
+    ) {
+        let Some((name, health)) = self
+            .health
+            .iter_mut()
+            .find(|(_, health)| health.outstanding == Some(probe_id))
+        else {
+            return;
+        };
+        health.outstanding = None;
+        let failures = match &result {
+            Ok(()) => std::mem::take(&mut health.failures),
+            Err(_) => {
+                health.failures += 1;
+                health.failures
+            }
+        };
+        let name = name.clone();
+
+        let Some(ProcCreationState {
+            created: Ok((proc_id, _)),
+            ..
+        }) = self.created.get(&name)
+        else {
+            return;
+        };
+        let Some(host) = self.host() else {
+            return;
+        };
+        let HostAgentMode::Process {
+            host: process_host, ..
+        } = host
+        else {
+            return;
+        };
+        let threshold = hyperactor_config::global::get(MESH_PROC_HEALTH_FAILURE_THRESHOLD).max(1);
+        match result {
+            Ok(()) => {
+                if failures >= threshold && process_host.manager().mark_healthy(proc_id).await {
+                    tracing::info!(proc_name = %name, %proc_id, "proc is healthy again");
+                }
+            }
+            Err(reason) => {
+                tracing::warn!(
+                    proc_name = %name,
+                    %proc_id,
+                    failures,
+                    %reason,
+                    "proc failed health probe"
+                );
+                if failures < threshold
+                    || !process_host
+                        .manager()
+                        .mark_unhealthy(proc_id, failures, reason.clone())
+                        .await
+                {
+                    return;
+                }
+                if failures == threshold
+                    && hyperactor_config::global::get(MESH_PROC_HEALTH_TERMINATE_UNHEALTHY)
+                {
+                    let timeout =
+                        hyperactor_config::global::get(hyperactor::config::PROCESS_EXIT_TIMEOUT);
+                    host.request_stop(cx, proc_id, timeout, &format!("unhealthy: {reason}"))
+                        .await;
+                    self.notify_proc_status_changed(&name);
+                }
+            }
+        }
+    }
+
+    /// The status to report for a proc whose raw status is `raw`:
+    /// `Initializing` while it is being restarted (RR-2), unless its
+    /// replacement has already terminated.
+    fn restart_status(&self, name: &Name, raw: resource::Status) -> resource::Status {
+        match self.restarting.get(name) {
+            Some(PendingRestart {
+                barrier: Some(_), ..
+            }) if raw.is_terminated() => raw,
+            Some(_) => resource::Status::Initializing,
+            None => raw,
+        }
+    }
+
+    /// Drive the restart of `name` on a status change: once the old
+    /// proc has terminated, spawn the replacement under the same name
+    /// (RR-1) and recreate its actors, followed by the barrier probe
+    /// (RR-3). A replacement that terminates before the barrier
+    /// abandons the restart.
+    async fn advance_restart(&mut self, cx: &Context<'_, Self>, name: &Name) {
+        let Some(ProcCreationState {
+            rank,
+            spec,
+            created: Ok((proc_id, _)),
+        }) = self.created.get(name)
+        else {
+            self.restarting.remove(name);
+            return;
+        };
+        let (rank, mut spec, proc_id) = (*rank, spec.clone(), proc_id.clone());
+        let Some(host) = self.host() else {
+            return;
+        };
+        if !host.proc_status(&proc_id).await.0.is_terminated() {
+            return;
+        }
+        let Some(restart) = self.restarting.get(name) else {
+            return;
+        };
+        if restart.barrier.is_some() {
+            tracing::warn!(
+                proc_name = %name,
+                %proc_id,
+                "replacement proc terminated during restart"
+            );
+            self.restarting.remove(name);
+            return;
+        }
+        let (bootstrap_command, env) = (restart.bootstrap_command.clone(), restart.env.clone());
+
+        let Some(HostAgentMode::Process { host, .. }) = self.host_mut() else {
+            return;
+        };
+        if bootstrap_command.is_some() || !env.is_empty() {
+            let mut command = bootstrap_command
+                .or_else(|| spec.bootstrap_command.clone())
+                .unwrap_or_else(|| host.manager().command().clone());
+            command.env.extend(env);
+            spec.bootstrap_command = Some(command);
+        }
+        host.forget_proc(&name.to_string());
+        let created = host
+            .spawn(
+                name.to_string(),
+                BootstrapProcConfig {
+                    create_rank: rank,
+                    client_config_override: spec.client_config_override.clone(),
+                    proc_bind: spec.proc_bind.clone(),
+                    bootstrap_command: spec.bootstrap_command.clone(),
+                },
+            )
+            .await;
+        // The old proc's bridge has exited, and its probes are moot.
+        self.watching.remove(name);
+        self.health.remove(name);
+
+        let spawned = created
+            .as_ref()
+            .ok()
+            .map(|(proc_id, agent)| (proc_id.clone(), agent.clone()));
+        if let Err(e) = &created {
+            tracing::error!(proc_name = %name, "failed to spawn replacement proc: {}", e);
+            self.restarting.remove(name);
+        }
+        self.created.insert(
+            name.clone(),
+            ProcCreationState {
+                rank,
+                spec,
+                created,
+            },
+        );
+
+        if let Some((proc_id, agent)) = spawned {
+            let actors = self
+                .restarting
+                .get_mut(name)
+                .map(|restart| std::mem::take(&mut restart.actors))
+                .unwrap_or_default();
+            // The replacement must not take down the host agent if it
+            // dies; its status bridge reports that.
+            let mut create_port = agent.port::<resource::CreateOrUpdate<ActorSpec>>();
+            create_port.return_undeliverable(false);
+            for actor in actors {
+                let _ = create_port.send(
+                    cx,
+                    resource::CreateOrUpdate {
+                        name: actor.name,
+                        rank: resource::Rank::new(actor.create_rank),
+                        spec: actor.spec,
+                    },
+                );
+            }
+            self.last_barrier_id += 1;
+            let id = self.last_barrier_id;
+            let mut barrier_port = agent.port::<Barrier>();
+            barrier_port.return_undeliverable(false);
+            let _ = barrier_port.send(
+                cx,
+                Barrier {
+                    id,
+                    reply: cx.port::<BarrierReached>().bind(),
+                },
+            );
+            if let Some(restart) = self.restarting.get_mut(name) {
+                restart.barrier = Some(id);
+            }
+            self.start_watch_bridge(name, &proc_id).await;
+        }
+        self.publish_introspect_properties(cx);
+    }
+
     /// Send a `ProcStatusChanged` self-notification for the given proc name.
     fn notify_proc_status_changed(&self, name: &Name) {
         if let Some(port) = &self.proc_status_port {
@@ -1106,6 +1713,129 @@ impl Handler<DrainComplete> for HostAgent {
     }
 }
 
+/// Replace each proc in `procs` with a fresh one, recreating its
+/// actors on it (see RR-* in the module doc). The old procs are
+/// stopped with a grace period of `timeout` before escalation, with
+/// at most `max_in_flight` stops running at once. `ack` receives
+/// `None` once the restart has begun, or the reason it was refused,
+/// in which case no proc is restarted; callers then wait for each
+/// proc to reach `Running` with [`resource::WaitRankStatus`].
+#[derive(Serialize, Deserialize, Debug, Named, Handler, RefClient, HandleClient)]
+pub struct RestartProcs {
+    /// The procs to restart, each with the actors to recreate on its
+    /// replacement, in creation order.
+    pub procs: Vec<(Name, Vec<RecordedActor>)>,
+    /// Command for the replacements; `None` keeps each proc's current
+    /// command.
+    pub bootstrap_command: Option<BootstrapCommand>,
+    /// Environment variables set on top of the replacements' command.
+    pub env: HashMap<String, String>,
+    pub timeout: std::time::Duration,
+    /// Max number of procs to stop concurrently on this host.
+    pub max_in_flight: usize,
+    #[reply]
+    pub ack: hyperactor_reference::PortRef<Option<String>>,
+}
+wirevalue::register_type!(RestartProcs);
+
+impl HostAgent {
+    /// The id of proc `name` if it can be restarted, or the reason it
+    /// cannot.
+    fn restartable(&self, name: &Name) -> Result<hyperactor_reference::ProcId, String> {
+        match (self.host(), self.created.get(name)) {
+            _ if self.restarting.contains_key(name) => {
+                Err(format!("proc {} is already restarting", name))
+            }
+            (
+                Some(HostAgentMode::Process { .. }),
+                Some(ProcCreationState {
+                    created: Ok((proc_id, _)),
+                    ..
+                }),
+            ) => Ok(proc_id.clone()),
+            (Some(HostAgentMode::Process { .. }), Some(_)) => {
+                Err(format!("proc {} was never spawned", name))
+            }
+            (Some(HostAgentMode::Process { .. }), None) => Err(format!("no proc named {}", name)),
+            (Some(HostAgentMode::Local(_)), _) => {
+                Err("procs of in-process hosts cannot be restarted".to_string())
+            }
+            (None, _) => Err("HostAgent has already shut down".to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl Handler<RestartProcs> for HostAgent {
+    async fn handle(&mut self, cx: &Context<Self>, msg: RestartProcs) -> anyhow::Result<()> {
+        let RestartProcs {
+            procs,
+            bootstrap_command,
+            env,
+            timeout,
+            max_in_flight,
+            ack,
+        } = msg;
+        let mut proc_ids = Vec::with_capacity(procs.len());
+        for (name, _) in &procs {
+            let refusal = if proc_ids.iter().any(|(other, _)| other == name) {
+                Err(format!("proc {} is listed more than once", name))
+            } else {
+                self.restartable(name)
+            };
+            match refusal {
+                Ok(proc_id) => proc_ids.push((name.clone(), proc_id)),
+                Err(reason) => {
+                    tracing::warn!(proc_name = %name, %reason, "refusing to restart procs");
+                    let _ = ack.send(cx, Some(reason));
+                    return Ok(());
+                }
+            }
+        }
+        let Some(HostAgentMode::Process { host, .. }) = self.host() else {
+            // All procs were checked to be on a process-mode host.
+            let _ = ack.send(cx, None);
+            return Ok(());
+        };
+
+        let mut handles = Vec::with_capacity(proc_ids.len());
+        for (name, proc_id) in &proc_ids {
+            if let Some(handle) = host.manager().handle(proc_id).await {
+                handles.push(handle);
+            }
+            tracing::info!(proc_name = %name, %proc_id, "restarting proc");
+        }
+        for ((name, actors), (_, proc_id)) in procs.into_iter().zip(&proc_ids) {
+            self.restarting.insert(
+                name.clone(),
+                PendingRestart {
+                    bootstrap_command: bootstrap_command.clone(),
+                    env: env.clone(),
+                    actors,
+                    barrier: None,
+                },
+            );
+            // The bridge reports the old proc's termination, upon
+            // which the replacement is spawned.
+            self.start_watch_bridge(&name, proc_id).await;
+        }
+        // RR-4: stop the old procs off the message loop.
+        cx.spawn_with_name(
+            "restart_worker",
+            RestartWorker {
+                handles,
+                timeout,
+                max_in_flight,
+            },
+        )?;
+        let _ = ack.send(cx, None);
+        for (name, _) in &proc_ids {
+            self.notify_proc_status_changed(name);
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Handler<ShutdownHost> for HostAgent {
     async fn handle(&mut self, cx: &Context<Self>, msg: ShutdownHost) -> anyhow::Result<()> {
@@ -1157,6 +1887,9 @@ pub struct ProcState {
     pub mesh_agent: hyperactor_reference::ActorRef<ProcAgent>,
     pub bootstrap_command: Option<BootstrapCommand>,
     pub proc_status: Option<bootstrap::ProcStatus>,
+    /// Resource usage accounted by the launcher (e.g. the proc's
+    /// cgroup), if available.
+    pub resource_usage: Option<ProcResourceUsage>,
 }
 wirevalue::register_type!(ProcState);
 
@@ -1173,14 +1906,22 @@ impl Handler<resource::GetState<ProcState>> for HostAgent {
                 created: Ok((proc_id, mesh_agent)),
                 ..
             }) => {
-                let (raw_status, proc_status, bootstrap_command) = match self.host() {
+                let (raw_status, proc_status, bootstrap_command, resource_usage) = match self.host()
+                {
                     Some(host) => {
                         let (status, proc_status) = host.proc_status(proc_id).await;
-                        (status, proc_status, host.bootstrap_command())
+                        (
+                            status,
+                            proc_status,
+                            host.bootstrap_command(),
+                            host.proc_resource_usage(proc_id).await,
+                        )
                     }
-                    None => (resource::Status::Unknown, None, None),
+                    None => (resource::Status::Unknown, None, None, None),
                 };
-                let status = raw_status.clamp_min(self.min_proc_status());
+                let status = self
+                    .restart_status(&get_state.name, raw_status)
+                    .clamp_min(self.min_proc_status());
                 resource::State {
                     name: get_state.name.clone(),
                     status,
@@ -1190,6 +1931,7 @@ impl Handler<resource::GetState<ProcState>> for HostAgent {
                         mesh_agent: mesh_agent.clone(),
                         bootstrap_command,
                         proc_status,
+                        resource_usage,
                     }),
                     generation: 0,
                     timestamp: std::time::SystemTime::now(),
@@ -1272,6 +2014,46 @@ impl Handler<SetClientConfig> for HostAgent {
     }
 }
 
+#[async_trait]
+impl Handler<SetHostRuntimeConfig> for HostAgent {
+    async fn handle(
+        &mut self,
+        cx: &Context<Self>,
+        message: SetHostRuntimeConfig,
+    ) -> anyhow::Result<()> {
+        let acks = vec![runtime_config::apply(
+            cx.self_id().proc_id(),
+            message.attrs.clone(),
+        )];
+
+        let mut procs = Vec::new();
+        if let Some(host) = self.host() {
+            for state in self.created.values() {
+                let Ok((proc_id, agent)) = &state.created else {
+                    continue;
+                };
+                if !host.proc_status(proc_id).await.0.is_terminated() {
+                    procs.push((proc_id.clone(), agent.clone()));
+                }
+            }
+        }
+
+        // RC-3: the worker waits for every live proc, up to the
+        // timeout, off the message loop.
+        cx.spawn_with_name(
+            "runtime_config_worker",
+            RuntimeConfigWorker {
+                attrs: message.attrs,
+                acks,
+                procs,
+                timeout: hyperactor_config::global::get(MESH_RUNTIME_CONFIG_TIMEOUT),
+                result: Some(message.result),
+            },
+        )?;
+        Ok(())
+    }
+}
+
 /// Boot the ProcAgent on the host's local proc (LP-1).
 ///
 /// The local proc starts empty; this message activates it by spawning
@@ -1444,6 +2226,8 @@ mod tests {
 
     use super::*;
     use crate::bootstrap::ProcStatus;
+    #[cfg(fbcode_build)]
+    use crate::proc_launcher::ProcLauncher;
     use crate::resource::CreateOrUpdateClient;
     use crate::resource::GetStateClient;
     use crate::resource::StopClient;
@@ -1832,4 +2616,350 @@ mod tests {
             }
         );
     }
+
+    /// RestartProcs replaces a proc under the same ProcId (RR-1), and
+    /// the proc reports Running again once the replacement is up.
+    #[tokio::test]
+    #[cfg(fbcode_build)]
+    async fn test_restart_proc() {
+        let host = Host::new(
+            BootstrapProcManager::new(BootstrapCommand::test()).unwrap(),
+            ChannelTransport::Unix.any(),
+        )
+        .await
+        .unwrap();
+
+        let system_proc = host.system_proc().clone();
+        let host_agent = system_proc
+            .spawn(
+                HOST_MESH_AGENT_ACTOR_NAME,
+                HostAgent::new(HostAgentMode::Process {
+                    host,
+                    shutdown_tx: None,
+                }),
+            )
+            .unwrap();
+
+        let client_proc = Proc::direct(ChannelTransport::Unix.any(), "client".to_string()).unwrap();
+        let (client, _client_handle) = client_proc.instance("client").unwrap();
+
+        let name = Name::new("proc1").unwrap();
+        host_agent
+            .create_or_update(
+                &client,
+                name.clone(),
+                resource::Rank::new(0),
+                ProcSpec::default(),
+            )
+            .await
+            .unwrap();
+        let state = host_agent.get_state(&client, name.clone()).await.unwrap();
+        let (old_proc_id, old_started_at) = match state.state {
+            Some(ProcState {
+                proc_id,
+                proc_status: Some(ProcStatus::Ready { started_at, .. }),
+                ..
+            }) => (proc_id, started_at),
+            other => panic!("proc is not ready: {:?}", other),
+        };
+
+        let refusal = host_agent
+            .restart_procs(
+                &client,
+                vec![(name.clone(), Vec::new())],
+                None,
+                HashMap::from([("RESTARTED".to_string(), "1".to_string())]),
+                Duration::from_secs(5),
+                16,
+            )
+            .await
+            .unwrap();
+        assert_eq!(refusal, None);
+
+        let (port, mut rx) = client.open_port::<crate::StatusOverlay>();
+        host_agent
+            .wait_rank_status(
+                &client,
+                name.clone(),
+                resource::Status::Running,
+                port.bind(),
+            )
+            .await
+            .unwrap();
+        let overlay = tokio::time::timeout(Duration::from_secs(30), rx.recv())
+            .await
+            .expect("reply timed out")
+            .expect("reply channel closed");
+        assert_eq!(
+            overlay.runs().next().map(|(_, status)| status.clone()),
+            Some(resource::Status::Running)
+        );
+
+        assert_matches!(
+            host_agent.get_state(&client, name).await.unwrap(),
+            resource::State {
+                status: resource::Status::Running,
+                state: Some(ProcState {
+                    proc_id,
+                    proc_status: Some(ProcStatus::Ready { started_at, .. }),
+                    ..
+                }),
+                ..
+            } if proc_id == old_proc_id && started_at != old_started_at
+        );
+
+        // Unknown procs cannot be restarted, and refuse the whole
+        // request.
+        let refusal = host_agent
+            .restart_procs(
+                &client,
+                vec![
+                    (name.clone(), Vec::new()),
+                    (Name::new("unknown").unwrap(), Vec::new()),
+                ],
+                None,
+                HashMap::new(),
+                Duration::from_secs(5),
+                16,
+            )
+            .await
+            .unwrap();
+        assert!(refusal.is_some());
+    }
+
+    /// A native launcher that records the pid of every proc it
+    /// launches, so that tests can wedge a proc with SIGSTOP.
+    #[cfg(fbcode_build)]
+    struct PidRecordingLauncher {
+        inner: crate::proc_launcher::NativeProcLauncher,
+        pids: std::sync::Arc<std::sync::Mutex<HashMap<hyperactor_reference::ProcId, u32>>>,
+    }
+
+    #[cfg(fbcode_build)]
+    #[async_trait]
+    impl ProcLauncher for PidRecordingLauncher {
+        async fn launch(
+            &self,
+            proc_id: &hyperactor_reference::ProcId,
+            opts: crate::proc_launcher::LaunchOptions,
+        ) -> Result<crate::proc_launcher::LaunchResult, crate::proc_launcher::ProcLauncherError>
+        {
+            let result = self.inner.launch(proc_id, opts).await?;
+            if let Some(pid) = result.pid {
+                self.pids.lock().unwrap().insert(proc_id.clone(), pid);
+            }
+            Ok(result)
+        }
+
+        async fn terminate(
+            &self,
+            proc_id: &hyperactor_reference::ProcId,
+            timeout: Duration,
+        ) -> Result<(), crate::proc_launcher::ProcLauncherError> {
+            self.inner.terminate(proc_id, timeout).await
+        }
+
+        async fn kill(
+            &self,
+            proc_id: &hyperactor_reference::ProcId,
+        ) -> Result<(), crate::proc_launcher::ProcLauncherError> {
+            self.inner.kill(proc_id).await
+        }
+    }
+
+    /// Spawn a process-mode host agent with one ready proc named
+    /// "proc1". Returns the agent, a client and its handle, the proc's
+    /// name and the pid of its process.
+    #[cfg(fbcode_build)]
+    async fn spawn_probed_proc() -> (
+        ActorHandle<HostAgent>,
+        Instance<()>,
+        ActorHandle<()>,
+        Name,
+        nix::unistd::Pid,
+    ) {
+        let manager = BootstrapProcManager::new(BootstrapCommand::test()).unwrap();
+        let pids = std::sync::Arc::new(std::sync::Mutex::new(HashMap::new()));
+        manager
+            .set_launcher(std::sync::Arc::new(PidRecordingLauncher {
+                inner: crate::proc_launcher::NativeProcLauncher::new(),
+                pids: pids.clone(),
+            }))
+            .unwrap();
+        let host = Host::new(manager, ChannelTransport::Unix.any())
+            .await
+            .unwrap();
+
+        let system_proc = host.system_proc().clone();
+        let host_agent = system_proc
+            .spawn(
+                HOST_MESH_AGENT_ACTOR_NAME,
+                HostAgent::new(HostAgentMode::Process {
+                    host,
+                    shutdown_tx: None,
+                }),
+            )
+            .unwrap();
+
+        let client_proc = Proc::direct(ChannelTransport::Unix.any(), "client".to_string()).unwrap();
+        let (client, client_handle) = client_proc.instance("client").unwrap();
+
+        let name = Name::new("proc1").unwrap();
+        host_agent
+            .create_or_update(
+                &client,
+                name.clone(),
+                resource::Rank::new(0),
+                ProcSpec::default(),
+            )
+            .await
+            .unwrap();
+        let proc_id = match host_agent
+            .get_state(&client, name.clone())
+            .await
+            .unwrap()
+            .state
+        {
+            Some(ProcState {
+                proc_id,
+                proc_status: Some(ProcStatus::Ready { .. }),
+                ..
+            }) => proc_id,
+            other => panic!("proc is not ready: {:?}", other),
+        };
+        let pid = pids.lock().unwrap()[&proc_id];
+        (
+            host_agent,
+            client,
+            client_handle,
+            name,
+            nix::unistd::Pid::from_raw(pid as i32),
+        )
+    }
+
+    /// Poll the proc's status until `pred` holds, returning the first
+    /// status that satisfies it.
+    #[cfg(fbcode_build)]
+    async fn wait_proc_status(
+        host_agent: &ActorHandle<HostAgent>,
+        client: &Instance<()>,
+        name: &Name,
+        pred: impl Fn(&ProcStatus) -> bool,
+    ) -> ProcStatus {
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(30);
+        loop {
+            let state = host_agent.get_state(client, name.clone()).await.unwrap();
+            if let Some(ProcState {
+                proc_status: Some(status),
+                ..
+            }) = state.state
+                && pred(&status)
+            {
+                return status;
+            }
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "timed out waiting for proc status"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    /// Unanswered probes fail after MESH_PROC_HEALTH_PROBE_TIMEOUT
+    /// (HP-1), the proc becomes Unhealthy only once the failure
+    /// threshold is reached (HP-2), and a passed probe returns it to
+    /// Ready.
+    #[tokio::test]
+    #[cfg(fbcode_build)]
+    async fn test_health_probe_threshold_and_recovery() {
+        let config = hyperactor_config::global::lock();
+        let _interval =
+            config.override_key(MESH_PROC_HEALTH_PROBE_INTERVAL, Duration::from_millis(100));
+        let _timeout =
+            config.override_key(MESH_PROC_HEALTH_PROBE_TIMEOUT, Duration::from_millis(200));
+        let _threshold = config.override_key(MESH_PROC_HEALTH_FAILURE_THRESHOLD, 3);
+
+        let (host_agent, client, _client_handle, name, pid) = spawn_probed_proc().await;
+
+        // A responsive proc stays ready across several probe rounds.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_matches!(
+            wait_proc_status(&host_agent, &client, &name, |_| true).await,
+            ProcStatus::Ready { .. }
+        );
+
+        // Wedge the proc: its agent can no longer answer.
+        nix::sys::signal::kill(pid, nix::sys::signal::Signal::SIGSTOP).unwrap();
+        let status = wait_proc_status(&host_agent, &client, &name, |status| {
+            !matches!(status, ProcStatus::Ready { .. })
+        })
+        .await;
+        assert_matches!(
+            status,
+            ProcStatus::Unhealthy { failures: 3, ref reason, .. } if reason.starts_with("no reply within")
+        );
+
+        nix::sys::signal::kill(pid, nix::sys::signal::Signal::SIGCONT).unwrap();
+        wait_proc_status(&host_agent, &client, &name, |status| {
+            matches!(status, ProcStatus::Ready { .. })
+        })
+        .await;
+    }
+
+    /// With MESH_PROC_HEALTH_TERMINATE_UNHEALTHY set, a proc that
+    /// becomes unhealthy is stopped (HP-3).
+    #[tokio::test]
+    #[cfg(fbcode_build)]
+    async fn test_health_probe_terminate_unhealthy() {
+        let config = hyperactor_config::global::lock();
+        let _interval =
+            config.override_key(MESH_PROC_HEALTH_PROBE_INTERVAL, Duration::from_millis(100));
+        let _timeout =
+            config.override_key(MESH_PROC_HEALTH_PROBE_TIMEOUT, Duration::from_millis(200));
+        let _threshold = config.override_key(MESH_PROC_HEALTH_FAILURE_THRESHOLD, 2);
+        let _terminate = config.override_key(MESH_PROC_HEALTH_TERMINATE_UNHEALTHY, true);
+        let _exit_timeout = config.override_key(
+            hyperactor::config::PROCESS_EXIT_TIMEOUT,
+            Duration::from_secs(1),
+        );
+
+        let (host_agent, client, _client_handle, name, pid) = spawn_probed_proc().await;
+
+        nix::sys::signal::kill(pid, nix::sys::signal::Signal::SIGSTOP).unwrap();
+        // The stopped process cannot act on SIGTERM; it is killed
+        // once the exit timeout lapses.
+        let status = wait_proc_status(&host_agent, &client, &name, ProcStatus::is_exit).await;
+        assert_matches!(status, ProcStatus::Killed { .. });
+    }
+
+    /// Probing starts when MESH_PROC_HEALTH_PROBE_INTERVAL is set
+    /// after the host agent has started, without a restart (HP-4).
+    #[tokio::test]
+    #[cfg(fbcode_build)]
+    async fn test_health_probe_interval_change_rearms() {
+        let config = hyperactor_config::global::lock();
+        let _interval = config.override_key(MESH_PROC_HEALTH_PROBE_INTERVAL, Duration::ZERO);
+        let _timeout =
+            config.override_key(MESH_PROC_HEALTH_PROBE_TIMEOUT, Duration::from_millis(200));
+        let _threshold = config.override_key(MESH_PROC_HEALTH_FAILURE_THRESHOLD, 1);
+
+        let (host_agent, client, _client_handle, name, pid) = spawn_probed_proc().await;
+        nix::sys::signal::kill(pid, nix::sys::signal::Signal::SIGSTOP).unwrap();
+
+        // Probing is disabled: the wedged proc goes unnoticed.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        assert_matches!(
+            wait_proc_status(&host_agent, &client, &name, |_| true).await,
+            ProcStatus::Ready { .. }
+        );
+
+        let _enabled =
+            config.override_key(MESH_PROC_HEALTH_PROBE_INTERVAL, Duration::from_millis(100));
+        wait_proc_status(&host_agent, &client, &name, |status| {
+            matches!(status, ProcStatus::Unhealthy { .. })
+        })
+        .await;
+
+        nix::sys::signal::kill(pid, nix::sys::signal::Signal::SIGCONT).unwrap();
+    }
 }
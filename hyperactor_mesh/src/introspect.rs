@@ -352,6 +352,16 @@ declare_attrs! {
     })
     pub attr FAILED_ACTOR_COUNT: usize = 0;
 
+    /// Introspection generation of a proc, read before the rest of
+    /// the proc's state. Unchanged between two reads iff no actor in
+    /// the proc changed in between. Absent when the payload comes
+    /// from published (possibly stale) attrs.
+    @meta(INTROSPECT = IntrospectAttr {
+        name: "introspect_generation".into(),
+        desc: "Introspection generation of this proc".into(),
+    })
+    pub attr INTROSPECT_GENERATION: u64;
+
     /// Timestamp when the mesh was started.
     @meta(INTROSPECT = IntrospectAttr {
         name: "started_at".into(),
@@ -666,6 +676,8 @@ pub struct ProcAttrsView {
     pub failed_actor_count: usize,
     /// Runtime debug/operational stats (PD-*).
     pub debug: ProcDebugStats,
+    /// Introspection generation, when resolved from live state.
+    pub generation: Option<u64>,
 }
 
 impl ProcAttrsView {
@@ -693,6 +705,7 @@ impl ProcAttrsView {
         }
 
         let debug = ProcDebugStats::from_attrs(attrs);
+        let generation = attrs.get(INTROSPECT_GENERATION).copied();
 
         Ok(Self {
             proc_name,
@@ -703,6 +716,7 @@ impl ProcAttrsView {
             is_poisoned,
             failed_actor_count,
             debug,
+            generation,
         })
     }
 
@@ -718,6 +732,9 @@ impl ProcAttrsView {
         attrs.set(IS_POISONED, self.is_poisoned);
         attrs.set(FAILED_ACTOR_COUNT, self.failed_actor_count);
         self.debug.to_attrs(&mut attrs);
+        if let Some(generation) = self.generation {
+            attrs.set(INTROSPECT_GENERATION, generation);
+        }
         attrs
     }
 }
@@ -899,6 +916,7 @@ pub enum NodeProperties {
         is_poisoned: bool,
         failed_actor_count: usize,
         debug: ProcDebugStats,
+        generation: Option<u64>,
     },
     /// Runtime metadata for a single actor instance.
     Actor {
@@ -977,6 +995,7 @@ impl IntoNodeProperties for ProcAttrsView {
             is_poisoned: self.is_poisoned,
             failed_actor_count: self.failed_actor_count,
             debug: self.debug,
+            generation: self.generation,
         }
     }
 }
@@ -1147,6 +1166,7 @@ mod tests {
             ("stopped_retention_cap", STOPPED_RETENTION_CAP.attrs()),
             ("is_poisoned", IS_POISONED.attrs()),
             ("failed_actor_count", FAILED_ACTOR_COUNT.attrs()),
+            ("introspect_generation", INTROSPECT_GENERATION.attrs()),
             ("started_at", STARTED_AT.attrs()),
             ("started_by", STARTED_BY.attrs()),
             ("num_hosts", NUM_HOSTS.attrs()),
@@ -1231,6 +1251,7 @@ mod tests {
             is_poisoned: false,
             failed_actor_count: 0,
             debug: Default::default(),
+            generation: Some(3),
         }
     }
 
@@ -1300,6 +1321,7 @@ mod tests {
                 actor_work_queue_depth_total: 42,
                 actor_work_queue_depth_max: 7,
             },
+            generation: None,
         };
         let rt = ProcAttrsView::from_attrs(&view.to_attrs()).unwrap();
         assert_eq!(rt, view);
@@ -1636,6 +1658,7 @@ mod tests {
                     is_poisoned: false,
                     failed_actor_count: 0,
                     debug: Default::default(),
+                    generation: None,
                 },
                 children: vec![NodeRef::Actor(actor_id.clone())],
                 parent: Some(NodeRef::Host(actor_id.clone())),
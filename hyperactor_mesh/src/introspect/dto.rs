@@ -140,6 +140,8 @@ pub enum NodePropertiesDto {
         failed_actor_count: usize,
         /// Runtime debug/operational stats.
         debug: ProcDebugStatsDto,
+        /// Introspection generation, when resolved from live state.
+        generation: Option<u64>,
     },
     /// Runtime metadata for a single actor instance.
     Actor {
@@ -245,6 +247,7 @@ impl From<NodeProperties> for NodePropertiesDto {
                 is_poisoned,
                 failed_actor_count,
                 debug,
+                generation,
             } => Self::Proc {
                 proc_name,
                 num_actors,
@@ -261,6 +264,7 @@ impl From<NodeProperties> for NodePropertiesDto {
                     actor_work_queue_depth_total: debug.actor_work_queue_depth_total,
                     actor_work_queue_depth_max: debug.actor_work_queue_depth_max,
                 },
+                generation,
             },
             NodeProperties::Actor {
                 actor_status,
@@ -371,6 +375,7 @@ impl TryFrom<NodePropertiesDto> for NodeProperties {
                 is_poisoned,
                 failed_actor_count,
                 debug,
+                generation,
             } => Self::Proc {
                 proc_name,
                 num_actors,
@@ -387,6 +392,7 @@ impl TryFrom<NodePropertiesDto> for NodeProperties {
                     actor_work_queue_depth_total: debug.actor_work_queue_depth_total,
                     actor_work_queue_depth_max: debug.actor_work_queue_depth_max,
                 },
+                generation,
             },
             NodePropertiesDto::Actor {
                 actor_status,
@@ -517,6 +523,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: Some(12),
             },
             children: vec![NodeRef::Actor(test_actor_id())],
             parent: Some(NodeRef::Host(test_host_actor_id())),
@@ -35,7 +35,9 @@ pub mod introspect;
 pub mod logging;
 pub mod mesh;
 pub mod mesh_admin;
+pub mod mesh_admin_actions;
 pub mod mesh_admin_client;
+pub mod mesh_admin_events;
 pub mod mesh_controller;
 pub mod mesh_selection;
 mod metrics;
@@ -45,6 +47,7 @@ pub mod proc_mesh;
 pub mod pyspy;
 pub mod reference;
 pub mod resource;
+pub mod runtime_config;
 pub mod shared_cell;
 pub mod shortuuid;
 pub mod supervision;
@@ -58,9 +58,22 @@ use tracing::Level;
 use typeuri::Named;
 
 use crate::bootstrap::BOOTSTRAP_LOG_CHANNEL;
+use crate::comm::multicast::CAST_POINT;
 use crate::shortuuid::ShortUuid;
 
 mod line_prefixing_writer;
+mod rank_files;
+mod record;
+
+pub use rank_files::RankFileLayout;
+pub use rank_files::RankFiles;
+pub use rank_files::RotationPolicy;
+use record::CompiledLogFilter;
+use record::LevelCarry;
+pub use record::LogFilter;
+pub use record::LogLevel;
+pub use record::LogRecord;
+use record::LogRingBuffer;
 
 pub(crate) const DEFAULT_AGGREGATE_WINDOW_SEC: u64 = 5;
 const MAX_LINE_SIZE: usize = 4 * 1024;
@@ -88,6 +101,54 @@ declare_attrs! {
         Some("prefix_with_rank".to_string()),
     ))
     pub attr PREFIX_WITH_RANK: bool = true;
+
+    /// Number of recent log records each log forwarder retains for
+    /// `LogForwardMessage::QueryRecords`, irrespective of the filter
+    /// in effect. Zero disables retention.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_LOG_RECORD_BUFFER".to_string()),
+        Some("log_record_buffer".to_string()),
+    ))
+    pub attr LOG_RECORD_BUFFER: usize = 1000;
+
+    /// How long a log search waits for the forwarders' replies before
+    /// returning the records received so far.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_LOG_SEARCH_TIMEOUT".to_string()),
+        Some("log_search_timeout".to_string()),
+    ))
+    pub attr LOG_SEARCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Per-rank log files on the client are rotated before they would
+    /// exceed this many bytes. Zero disables size-based rotation.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_LOG_RANK_FILE_MAX_BYTES".to_string()),
+        Some("log_rank_file_max_bytes".to_string()),
+    ))
+    pub attr LOG_RANK_FILE_MAX_BYTES: u64 = 100 * 1024 * 1024;
+
+    /// Per-rank log files on the client are rotated once they are this
+    /// old. Zero disables time-based rotation.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_LOG_RANK_FILE_MAX_AGE".to_string()),
+        Some("log_rank_file_max_age".to_string()),
+    ))
+    pub attr LOG_RANK_FILE_MAX_AGE: Duration = Duration::ZERO;
+
+    /// Number of rotated per-rank log files kept for each rank and
+    /// stream; older ones are deleted.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_LOG_RANK_FILE_MAX_FILES".to_string()),
+        Some("log_rank_file_max_files".to_string()),
+    ))
+    pub attr LOG_RANK_FILE_MAX_FILES: usize = 5;
+
+    /// Whether rotated per-rank log files are gzipped.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("HYPERACTOR_LOG_RANK_FILE_COMPRESS".to_string()),
+        Some("log_rank_file_compress".to_string()),
+    ))
+    pub attr LOG_RANK_FILE_COMPRESS: bool = true;
 }
 
 /// Calculate the Levenshtein distance between two strings
@@ -293,6 +354,16 @@ pub enum LogMessage {
         payload: wirevalue::Any,
     },
 
+    /// Structured log records that passed the forwarder's filter.
+    Records {
+        /// The hostname of the process that generated the log
+        hostname: String,
+        /// String representation of the ProcId that generated the log
+        proc_id: String,
+        /// The records, in the order they were logged
+        records: Vec<LogRecord>,
+    },
+
     /// Flush the log
     Flush {
         /// Indicate if the current flush is synced or non-synced.
@@ -327,6 +398,15 @@ pub enum LogClientMessage {
         /// Return to the caller the current flush version
         version: hyperactor_reference::OncePortRef<u64>,
     },
+
+    /// Also write received lines to per-rank files under `dir`, laid
+    /// out according to `layout` (see [`RankFiles`]), with rotation
+    /// governed by the `LOG_RANK_FILE_*` config. If `dir` is None,
+    /// stop writing per-rank files.
+    SetRankFiles {
+        dir: Option<String>,
+        layout: RankFileLayout,
+    },
 }
 
 /// Trait for sending logs
@@ -981,9 +1061,28 @@ pub enum LogForwardMessage {
 
     /// Flush the log with a version number.
     ForceSyncFlush { version: u64 },
+
+    /// Forward only records accepted by `filter`. Rejected records are
+    /// still retained in the forwarder's ring buffer. An empty filter
+    /// restores unfiltered forwarding.
+    SetFilter { filter: LogFilter },
+
+    /// Reply with the forwarder's rank, if known, and the most recent
+    /// `limit` retained records accepted by `filter`, oldest first.
+    QueryRecords {
+        filter: LogFilter,
+        limit: usize,
+        #[binding(include)]
+        reply: hyperactor_reference::PortRef<(Option<usize>, Vec<LogRecord>)>,
+    },
 }
 
 /// A log forwarder that receives the log from its parent process and forward it back to the client
+///
+/// Every line received is parsed into a [`LogRecord`] and retained in a
+/// bounded ring buffer (see [`LOG_RECORD_BUFFER`]). While a filter is
+/// set, only accepted lines are forwarded, as `LogMessage::Records`;
+/// otherwise the raw payload is forwarded as is.
 #[hyperactor::export(
     spawn = true,
     handlers = [LogForwardMessage {cast = true}],
@@ -994,6 +1093,13 @@ pub struct LogForwardActor {
     next_flush_deadline: SystemTime,
     logging_client_ref: hyperactor_reference::ActorRef<LogClientActor>,
     stream_to_client: bool,
+    filter: Option<CompiledLogFilter>,
+    records: LogRingBuffer,
+    /// The level carried over to unleveled lines, per stream.
+    levels: LevelCarry,
+    /// This proc's rank in the forwarder mesh, learned from the first
+    /// cast received.
+    rank: Option<usize>,
 }
 
 #[async_trait]
@@ -1059,6 +1165,10 @@ impl hyperactor::RemoteSpawn for LogForwardActor {
             next_flush_deadline: now,
             logging_client_ref,
             stream_to_client: true,
+            filter: None,
+            records: LogRingBuffer::new(hyperactor_config::global::get(LOG_RECORD_BUFFER)),
+            levels: LevelCarry::default(),
+            rank: None,
         })
     }
 }
@@ -1101,12 +1211,44 @@ impl LogForwardMessageHandler for LogForwardActor {
                 output_target,
                 payload,
             }) => {
+                let now = std::time::SystemTime::now();
+                let records: Vec<LogRecord> = deserialize_message_lines(&payload)?
+                    .into_iter()
+                    .flatten()
+                    .map(|line| {
+                        let mut record = LogRecord::parse(&line, output_target, self.rank, now);
+                        self.levels.carry(&mut record);
+                        record
+                    })
+                    .collect();
+                for record in &records {
+                    self.records.push(record.clone());
+                }
+
                 if self.stream_to_client {
-                    self.logging_client_ref
-                        .log(ctx, hostname, proc_id, output_target, payload)
-                        .await?;
+                    match &self.filter {
+                        None => {
+                            self.logging_client_ref
+                                .log(ctx, hostname, proc_id, output_target, payload)
+                                .await?;
+                        }
+                        Some(filter) => {
+                            let records: Vec<LogRecord> = records
+                                .into_iter()
+                                .filter(|record| filter.matches(record))
+                                .collect();
+                            if !records.is_empty() {
+                                self.logging_client_ref
+                                    .records(ctx, hostname, proc_id, records)
+                                    .await?;
+                            }
+                        }
+                    }
                 }
             }
+            Ok(LogMessage::Records { .. }) => {
+                tracing::warn!("log forwarder received unexpected structured records; dropping");
+            }
             Err(e) => {
                 return Err(e.into());
             }
@@ -1141,6 +1283,54 @@ impl LogForwardMessageHandler for LogForwardActor {
             .await
             .map_err(anyhow::Error::from)
     }
+
+    async fn set_filter(
+        &mut self,
+        cx: &Context<Self>,
+        filter: LogFilter,
+    ) -> Result<(), anyhow::Error> {
+        self.learn_rank(cx);
+        if filter.is_empty() {
+            self.filter = None;
+            return Ok(());
+        }
+        match filter.compile() {
+            Ok(filter) => self.filter = Some(filter),
+            // Keep forwarding under the previous filter rather than
+            // failing the forwarder.
+            Err(e) => tracing::error!("ignoring invalid log filter {:?}: {}", filter, e),
+        }
+        Ok(())
+    }
+
+    async fn query_records(
+        &mut self,
+        cx: &Context<Self>,
+        filter: LogFilter,
+        limit: usize,
+        reply: hyperactor_reference::PortRef<(Option<usize>, Vec<LogRecord>)>,
+    ) -> Result<(), anyhow::Error> {
+        self.learn_rank(cx);
+        let records = match filter.compile() {
+            Ok(filter) => self.records.query(&filter, limit),
+            Err(e) => {
+                tracing::error!("invalid log query filter {:?}: {}", filter, e);
+                Vec::new()
+            }
+        };
+        reply.send(cx, (self.rank, records))?;
+        Ok(())
+    }
+}
+
+impl LogForwardActor {
+    /// Records parsed before the first cast carry only the rank from
+    /// the line's `"[<rank>] "` prefix, if any.
+    fn learn_rank(&mut self, cx: &Context<Self>) {
+        if self.rank.is_none() {
+            self.rank = cx.headers().get(CAST_POINT).map(|point| point.rank());
+        }
+    }
 }
 
 /// Deserialize a serialized message and split it into UTF-8 lines
@@ -1182,6 +1372,8 @@ pub struct LogClientActor {
     current_flush_version: u64,
     current_flush_port: Option<hyperactor_reference::OncePortRef<()>>,
     current_unflushed_procs: usize,
+
+    rank_files: Option<RankFiles>,
 }
 
 impl Default for LogClientActor {
@@ -1199,6 +1391,7 @@ impl Default for LogClientActor {
             current_flush_version: 0,
             current_flush_port: None,
             current_unflushed_procs: 0,
+            rank_files: None,
         }
     }
 }
@@ -1245,53 +1438,53 @@ impl LogClientActor {
 
     fn flush_internal(&mut self) {
         self.print_aggregators();
+        if let Some(rank_files) = &mut self.rank_files
+            && let Err(e) = rank_files.flush()
+        {
+            tracing::error!("failed to flush rank log files: {}", e);
+        }
         self.last_flush_time = std::time::SystemTime::now();
         self.next_flush_deadline = None;
     }
-}
-
-impl Drop for LogClientActor {
-    fn drop(&mut self) {
-        // Flush the remaining logs before shutting down
-        self.print_aggregators();
-    }
-}
 
-#[async_trait]
-#[hyperactor::handle(LogMessage)]
-impl LogMessageHandler for LogClientActor {
-    async fn log(
+    /// Print or aggregate forwarded lines, scheduling the next
+    /// aggregation flush as needed.
+    fn ingest_lines(
         &mut self,
         cx: &Context<Self>,
-        hostname: String,
-        proc_id: String,
-        output_target: OutputTarget,
-        payload: wirevalue::Any,
+        hostname: &str,
+        proc_id: &str,
+        message_lines: impl Iterator<Item = (OutputTarget, String)>,
     ) -> Result<(), anyhow::Error> {
-        // Deserialize the message and process line by line with UTF-8
-        let message_line_groups = deserialize_message_lines(&payload)?;
-        let hostname = hostname.as_str();
+        let message_lines: Vec<(OutputTarget, String)> = message_lines.collect();
+        if let Some(rank_files) = &mut self.rank_files {
+            let lines = message_lines
+                .iter()
+                .map(|(output_target, line)| (*output_target, line.as_str()));
+            if let Err(e) = rank_files.write(hostname, proc_id, lines) {
+                tracing::error!("failed to write rank log files: {}", e);
+            }
+        }
 
-        let message_lines: Vec<String> = message_line_groups.into_iter().flatten().collect();
         match self.aggregate_window_sec {
             None => {
-                for line in message_lines {
-                    Self::print_log_line(hostname, &proc_id, output_target, line);
+                for (output_target, line) in message_lines {
+                    Self::print_log_line(hostname, proc_id, output_target, line);
                 }
                 self.last_flush_time = std::time::SystemTime::now();
             }
             Some(window) => {
-                for line in message_lines {
+                for (output_target, line) in message_lines {
                     if let Some(aggregator) = self.aggregators.get_mut(&output_target) {
                         if let Err(e) = aggregator.add_line(&line) {
                             tracing::error!("error adding log line: {}", e);
                             // For the sake of completeness, flush the log lines.
-                            Self::print_log_line(hostname, &proc_id, output_target, line);
+                            Self::print_log_line(hostname, proc_id, output_target, line);
                         }
                     } else {
                         tracing::error!("unknown output target: {:?}", output_target);
                         // For the sake of completeness, flush the log lines.
-                        Self::print_log_line(hostname, &proc_id, output_target, line);
+                        Self::print_log_line(hostname, proc_id, output_target, line);
                     }
                 }
 
@@ -1327,6 +1520,47 @@ impl LogMessageHandler for LogClientActor {
 
         Ok(())
     }
+}
+
+impl Drop for LogClientActor {
+    fn drop(&mut self) {
+        // Flush the remaining logs before shutting down
+        self.print_aggregators();
+    }
+}
+
+#[async_trait]
+#[hyperactor::handle(LogMessage)]
+impl LogMessageHandler for LogClientActor {
+    async fn log(
+        &mut self,
+        cx: &Context<Self>,
+        hostname: String,
+        proc_id: String,
+        output_target: OutputTarget,
+        payload: wirevalue::Any,
+    ) -> Result<(), anyhow::Error> {
+        // Deserialize the message and process line by line with UTF-8
+        let message_line_groups = deserialize_message_lines(&payload)?;
+        let message_lines = message_line_groups
+            .into_iter()
+            .flatten()
+            .map(|line| (output_target, line));
+        self.ingest_lines(cx, &hostname, &proc_id, message_lines)
+    }
+
+    async fn records(
+        &mut self,
+        cx: &Context<Self>,
+        hostname: String,
+        proc_id: String,
+        records: Vec<LogRecord>,
+    ) -> Result<(), anyhow::Error> {
+        let message_lines = records
+            .into_iter()
+            .map(|record| (record.output_target, record.line));
+        self.ingest_lines(cx, &hostname, &proc_id, message_lines)
+    }
 
     async fn flush(
         &mut self,
@@ -1415,6 +1649,26 @@ impl LogClientMessageHandler for LogClientActor {
             .map_err(anyhow::Error::from)?;
         Ok(())
     }
+
+    async fn set_rank_files(
+        &mut self,
+        _cx: &Context<Self>,
+        dir: Option<String>,
+        layout: RankFileLayout,
+    ) -> Result<(), anyhow::Error> {
+        // Dropping the previous files flushes them.
+        self.rank_files = None;
+        if let Some(dir) = dir {
+            match RankFiles::open(Path::new(&dir), layout, RotationPolicy::from_config()) {
+                Ok(rank_files) => {
+                    tracing::info!("writing per-rank logs to {}", rank_files.root().display());
+                    self.rank_files = Some(rank_files);
+                }
+                Err(e) => tracing::error!("failed to open rank log files in {}: {:#}", dir, e),
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
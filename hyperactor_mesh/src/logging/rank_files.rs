@@ -0,0 +1,595 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Per-rank log files on the client.
+//!
+//! [`RankFiles`] demultiplexes the lines received by a
+//! `LogClientActor` into one file per rank and stream:
+//!
+//! ```text
+//! {dir}/{mesh}/index.json
+//! {dir}/{mesh}/{coords}.stdout.log
+//! {dir}/{mesh}/{coords}.stderr.log
+//! ```
+//!
+//! where `{coords}` spells out the rank's point in the mesh, e.g.
+//! `hosts_1-gpus_3`. Lines from procs not in the mesh go to
+//! `unknown.{stdout,stderr}.log`.
+//!
+//! Invariants:
+//! - **RF-1 (rotation):** A file is rotated before a write would take
+//!   it past [`RotationPolicy::max_bytes`], or once it is older than
+//!   [`RotationPolicy::max_age`]. A single line is never split across
+//!   files, so a line longer than `max_bytes` gets a file of its own.
+//! - **RF-2 (retention):** Rotated files are renamed to
+//!   `{coords}.{stream}.{timestamp}.log` (gzipped in the background to
+//!   `.log.gz` if [`RotationPolicy::compress`]), and at most
+//!   [`RotationPolicy::max_files`] of them are kept per file; the
+//!   oldest are deleted first. Timestamps sort lexicographically.
+//! - **RF-3 (index):** `index.json` lists every rank with its
+//!   coordinates, proc id, hostname (once a line from it has been
+//!   seen) and file names. It is rewritten atomically whenever a
+//!   hostname is learned, so readers never observe a partial index.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context as _;
+use chrono::Local;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use ndslice::Extent;
+use ndslice::view::Ranked;
+use serde::Deserialize;
+use serde::Serialize;
+use typeuri::Named;
+
+use super::LOG_RANK_FILE_COMPRESS;
+use super::LOG_RANK_FILE_MAX_AGE;
+use super::LOG_RANK_FILE_MAX_BYTES;
+use super::LOG_RANK_FILE_MAX_FILES;
+use super::OutputTarget;
+use crate::proc_mesh::ProcMeshRef;
+
+const INDEX_FILE: &str = "index.json";
+const UNKNOWN_STEM: &str = "unknown";
+
+/// The ranks of a proc mesh, as needed to name and index per-rank
+/// log files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Named)]
+pub struct RankFileLayout {
+    /// The mesh name; files are written under `{dir}/{mesh}`.
+    pub mesh: String,
+    /// The shape of the mesh, used to turn ranks into coordinates.
+    pub extent: Extent,
+    /// The proc id (as rendered by `ProcId`'s `Display`) of each rank,
+    /// indexed by rank.
+    pub proc_ids: Vec<String>,
+}
+wirevalue::register_type!(RankFileLayout);
+
+impl RankFileLayout {
+    /// The layout of `proc_mesh`.
+    pub fn for_proc_mesh(proc_mesh: &ProcMeshRef) -> Self {
+        let region = proc_mesh.region();
+        Self {
+            mesh: proc_mesh.name().to_string(),
+            extent: region.extent(),
+            proc_ids: (0..region.num_ranks())
+                .filter_map(|rank| proc_mesh.get(rank))
+                .map(|proc_ref| proc_ref.proc_id().to_string())
+                .collect(),
+        }
+    }
+
+    /// The file stem for `rank`: its coordinates as
+    /// `{label}_{coord}` pairs joined by `-`, or `rank_{rank}` for a
+    /// 0-dimensional mesh.
+    fn stem(&self, rank: usize) -> String {
+        match self.extent.point_of_rank(rank) {
+            Ok(point) if !self.extent.labels().is_empty() => self
+                .extent
+                .labels()
+                .iter()
+                .zip(point.coords())
+                .map(|(label, coord)| format!("{}_{}", label, coord))
+                .collect::<Vec<_>>()
+                .join("-"),
+            _ => format!("rank_{}", rank),
+        }
+    }
+}
+
+/// When to rotate per-rank log files and what to keep.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RotationPolicy {
+    /// Rotate before a file would exceed this many bytes. Zero
+    /// disables size-based rotation.
+    pub max_bytes: u64,
+    /// Rotate files older than this. Zero disables time-based
+    /// rotation.
+    pub max_age: Duration,
+    /// The number of rotated files to keep per rank and stream.
+    pub max_files: usize,
+    /// Whether to gzip rotated files.
+    pub compress: bool,
+}
+
+impl RotationPolicy {
+    /// The policy given by the `LOG_RANK_FILE_*` config keys.
+    pub fn from_config() -> Self {
+        Self {
+            max_bytes: hyperactor_config::global::get(LOG_RANK_FILE_MAX_BYTES),
+            max_age: hyperactor_config::global::get(LOG_RANK_FILE_MAX_AGE),
+            max_files: hyperactor_config::global::get(LOG_RANK_FILE_MAX_FILES),
+            compress: hyperactor_config::global::get(LOG_RANK_FILE_COMPRESS),
+        }
+    }
+}
+
+/// An entry of `index.json`.
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexEntry {
+    rank: usize,
+    coords: HashMap<String, usize>,
+    proc_id: String,
+    hostname: Option<String>,
+    stdout: String,
+    stderr: String,
+}
+
+/// The contents of `index.json`.
+#[derive(Debug, Serialize, Deserialize)]
+struct Index {
+    mesh: String,
+    extent: String,
+    ranks: Vec<IndexEntry>,
+}
+
+/// The open file for one rank and stream.
+#[derive(Debug)]
+struct RotatingFile {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    size: u64,
+    opened: Instant,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf) -> anyhow::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("opening {}", path.display()))?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+            size,
+            opened: Instant::now(),
+        })
+    }
+
+    fn needs_rotation(&self, policy: &RotationPolicy, incoming: u64) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+        (policy.max_bytes > 0 && self.size + incoming > policy.max_bytes)
+            || (!policy.max_age.is_zero() && self.opened.elapsed() >= policy.max_age)
+    }
+
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.write_all(b"\n")?;
+        self.size += line.len() as u64 + 1;
+        Ok(())
+    }
+}
+
+/// Per-rank log files for one mesh. See the module documentation.
+#[derive(Debug)]
+pub struct RankFiles {
+    root: PathBuf,
+    layout: RankFileLayout,
+    policy: RotationPolicy,
+    ranks_by_proc: HashMap<String, usize>,
+    hostnames: Vec<Option<String>>,
+    files: HashMap<(Option<usize>, OutputTarget), RotatingFile>,
+}
+
+impl RankFiles {
+    /// Create `{dir}/{mesh}` and write the initial index. Files are
+    /// opened lazily, on the first line for each rank and stream.
+    pub fn open(
+        dir: &Path,
+        layout: RankFileLayout,
+        policy: RotationPolicy,
+    ) -> anyhow::Result<Self> {
+        let root = dir.join(&layout.mesh);
+        std::fs::create_dir_all(&root)
+            .with_context(|| format!("creating log directory {}", root.display()))?;
+        let ranks_by_proc = layout
+            .proc_ids
+            .iter()
+            .enumerate()
+            .map(|(rank, proc_id)| (proc_id.clone(), rank))
+            .collect();
+        let this = Self {
+            root,
+            hostnames: vec![None; layout.proc_ids.len()],
+            layout,
+            policy,
+            ranks_by_proc,
+            files: HashMap::new(),
+        };
+        this.write_index()?;
+        Ok(this)
+    }
+
+    /// The directory the files are written to.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Append `lines`, received from `proc_id` on `hostname`, to the
+    /// files of the proc's rank.
+    pub fn write<'a>(
+        &mut self,
+        hostname: &str,
+        proc_id: &str,
+        lines: impl IntoIterator<Item = (OutputTarget, &'a str)>,
+    ) -> anyhow::Result<()> {
+        let rank = self.ranks_by_proc.get(proc_id).copied();
+        if let Some(rank) = rank
+            && self.hostnames[rank].is_none()
+        {
+            self.hostnames[rank] = Some(hostname.to_string());
+            self.write_index()?;
+        }
+
+        for (output_target, line) in lines {
+            let key = (rank, output_target);
+            let incoming = line.len() as u64 + 1;
+            if let Some(file) = self.files.get(&key)
+                && file.needs_rotation(&self.policy, incoming)
+            {
+                let file = self.files.remove(&key).expect("file is present");
+                self.rotate(file)?;
+            }
+            if !self.files.contains_key(&key) {
+                let path = self.root.join(self.file_name(rank, output_target));
+                self.files.insert(key, RotatingFile::open(path)?);
+            }
+            let file = self.files.get_mut(&key).expect("file is present");
+            file.write_line(line)?;
+        }
+        Ok(())
+    }
+
+    /// Flush buffered lines to disk.
+    pub fn flush(&mut self) -> anyhow::Result<()> {
+        for file in self.files.values_mut() {
+            file.writer.flush()?;
+        }
+        Ok(())
+    }
+
+    fn file_name(&self, rank: Option<usize>, output_target: OutputTarget) -> String {
+        let stem = match rank {
+            Some(rank) => self.layout.stem(rank),
+            None => UNKNOWN_STEM.to_string(),
+        };
+        format!("{}.{}.log", stem, stream_name(output_target))
+    }
+
+    /// Rotate `file` out of place, then prune old rotations (RF-2).
+    fn rotate(&self, mut file: RotatingFile) -> anyhow::Result<()> {
+        file.writer.flush()?;
+        drop(file.writer);
+
+        let file_name = file
+            .path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("log file names are UTF-8")
+            .to_string();
+        let base = file_name
+            .strip_suffix(".log")
+            .expect("log file ends in .log");
+        // Rotations within the same microsecond get a sequence number,
+        // which still sorts after the plain timestamp.
+        let timestamp = Local::now().format("%Y%m%dT%H%M%S%.6f").to_string();
+        let mut rotated = self.root.join(format!("{}.{}.log", base, timestamp));
+        let mut seq = 0;
+        while rotated.exists() || gz_path(&rotated).exists() {
+            seq += 1;
+            rotated = self
+                .root
+                .join(format!("{}.{}-{}.log", base, timestamp, seq));
+        }
+        std::fs::rename(&file.path, &rotated)
+            .with_context(|| format!("rotating {}", file.path.display()))?;
+
+        if self.policy.compress {
+            std::thread::spawn(move || {
+                if let Err(e) = compress(&rotated) {
+                    tracing::warn!("failed to compress {}: {}", rotated.display(), e);
+                }
+            });
+        }
+
+        self.prune(base)
+    }
+
+    /// Delete all but the newest `max_files` rotations of `base`.
+    fn prune(&self, base: &str) -> anyhow::Result<()> {
+        let prefix = format!("{}.", base);
+        let mut rotated = Vec::new();
+        for entry in std::fs::read_dir(&self.root)? {
+            let name = entry?.file_name();
+            let Some(name) = name.to_str() else {
+                continue;
+            };
+            let Some(rest) = name.strip_prefix(&prefix) else {
+                continue;
+            };
+            // Skip the live file, and other streams sharing the prefix.
+            let Some(timestamp) = rest
+                .strip_suffix(".log.gz")
+                .or_else(|| rest.strip_suffix(".log"))
+            else {
+                continue;
+            };
+            if !timestamp.is_empty() && timestamp.starts_with(|c: char| c.is_ascii_digit()) {
+                rotated.push((timestamp.to_string(), name.to_string()));
+            }
+        }
+        // A rotation being compressed briefly exists under both names;
+        // count it once.
+        rotated.sort();
+        rotated.dedup_by(|a, b| a.0 == b.0);
+        let excess = rotated.len().saturating_sub(self.policy.max_files);
+        for (timestamp, _) in rotated.into_iter().take(excess) {
+            for suffix in [".log", ".log.gz"] {
+                let path = self.root.join(format!("{}{}{}", prefix, timestamp, suffix));
+                match std::fs::remove_file(&path) {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(e) => {
+                        return Err(e).with_context(|| format!("removing {}", path.display()));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Atomically replace `index.json` (RF-3).
+    fn write_index(&self) -> anyhow::Result<()> {
+        let ranks = (0..self.layout.proc_ids.len())
+            .map(|rank| {
+                let coords = match self.layout.extent.point_of_rank(rank) {
+                    Ok(point) => self
+                        .layout
+                        .extent
+                        .labels()
+                        .iter()
+                        .cloned()
+                        .zip(point.coords())
+                        .collect(),
+                    Err(_) => HashMap::new(),
+                };
+                IndexEntry {
+                    rank,
+                    coords,
+                    proc_id: self.layout.proc_ids[rank].clone(),
+                    hostname: self.hostnames[rank].clone(),
+                    stdout: self.file_name(Some(rank), OutputTarget::Stdout),
+                    stderr: self.file_name(Some(rank), OutputTarget::Stderr),
+                }
+            })
+            .collect();
+        let index = Index {
+            mesh: self.layout.mesh.clone(),
+            extent: self.layout.extent.to_string(),
+            ranks,
+        };
+
+        let tmp = self.root.join(format!("{}.tmp", INDEX_FILE));
+        std::fs::write(&tmp, serde_json::to_vec_pretty(&index)?)?;
+        std::fs::rename(&tmp, self.root.join(INDEX_FILE))?;
+        Ok(())
+    }
+}
+
+impl Drop for RankFiles {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            tracing::warn!("failed to flush rank log files: {}", e);
+        }
+    }
+}
+
+fn stream_name(output_target: OutputTarget) -> &'static str {
+    match output_target {
+        OutputTarget::Stdout => "stdout",
+        OutputTarget::Stderr => "stderr",
+    }
+}
+
+fn gz_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".gz");
+    PathBuf::from(name)
+}
+
+/// Gzip `path` to `{path}.gz`, removing the original once the
+/// compressed file is complete.
+fn compress(path: &Path) -> anyhow::Result<()> {
+    let gz_path = gz_path(path);
+    let mut tmp_name = gz_path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+
+    let mut input = File::open(path)?;
+    let mut encoder = GzEncoder::new(
+        BufWriter::new(File::create(&tmp_path)?),
+        Compression::default(),
+    );
+    std::io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?.flush()?;
+    std::fs::rename(&tmp_path, &gz_path)?;
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+    use ndslice::extent;
+
+    use super::*;
+
+    fn layout() -> RankFileLayout {
+        RankFileLayout {
+            mesh: "trainers".to_string(),
+            extent: extent!(hosts = 2, gpus = 2),
+            proc_ids: (0..4).map(|rank| format!("proc_{}", rank)).collect(),
+        }
+    }
+
+    fn policy(max_bytes: u64, max_files: usize, compress: bool) -> RotationPolicy {
+        RotationPolicy {
+            max_bytes,
+            max_age: Duration::ZERO,
+            max_files,
+            compress,
+        }
+    }
+
+    fn rotations(root: &Path, base: &str) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(root)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .filter(|name| name.starts_with(base) && name != &format!("{}.log", base))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_demultiplex_and_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files = RankFiles::open(dir.path(), layout(), policy(0, 1, false)).unwrap();
+        assert_eq!(files.root(), dir.path().join("trainers"));
+
+        files
+            .write(
+                "host_a",
+                "proc_3",
+                [
+                    (OutputTarget::Stdout, "out 1"),
+                    (OutputTarget::Stderr, "err 1"),
+                    (OutputTarget::Stdout, "out 2"),
+                ],
+            )
+            .unwrap();
+        files
+            .write("host_b", "nobody", [(OutputTarget::Stdout, "stray")])
+            .unwrap();
+        files.flush().unwrap();
+
+        let root = files.root().to_path_buf();
+        let read = |name: &str| std::fs::read_to_string(root.join(name)).unwrap();
+        assert_eq!(read("hosts_1-gpus_1.stdout.log"), "out 1\nout 2\n");
+        assert_eq!(read("hosts_1-gpus_1.stderr.log"), "err 1\n");
+        assert_eq!(read("unknown.stdout.log"), "stray\n");
+
+        let index: Index = serde_json::from_str(&read(INDEX_FILE)).unwrap();
+        assert_eq!(index.mesh, "trainers");
+        assert_eq!(index.ranks.len(), 4);
+        let entry = &index.ranks[3];
+        assert_eq!(entry.proc_id, "proc_3");
+        assert_eq!(entry.hostname.as_deref(), Some("host_a"));
+        assert_eq!(entry.coords["hosts"], 1);
+        assert_eq!(entry.coords["gpus"], 1);
+        assert_eq!(entry.stdout, "hosts_1-gpus_1.stdout.log");
+        assert_eq!(index.ranks[0].hostname, None);
+    }
+
+    #[test]
+    fn test_size_rotation_and_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        // Each line is 10 bytes with its newline; two fit in a file.
+        let mut files = RankFiles::open(dir.path(), layout(), policy(20, 2, false)).unwrap();
+        for i in 0..9 {
+            let line = format!("line {:04}", i);
+            files
+                .write("host", "proc_0", [(OutputTarget::Stdout, line.as_str())])
+                .unwrap();
+        }
+        files.flush().unwrap();
+
+        let root = files.root().to_path_buf();
+        assert_eq!(
+            std::fs::read_to_string(root.join("hosts_0-gpus_0.stdout.log")).unwrap(),
+            "line 0008\n"
+        );
+        let rotated = rotations(&root, "hosts_0-gpus_0.stdout");
+        assert_eq!(rotated.len(), 2);
+        assert_eq!(
+            std::fs::read_to_string(root.join(&rotated[0])).unwrap(),
+            "line 0004\nline 0005\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join(&rotated[1])).unwrap(),
+            "line 0006\nline 0007\n"
+        );
+    }
+
+    #[test]
+    fn test_compressed_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files = RankFiles::open(dir.path(), layout(), policy(10, 5, true)).unwrap();
+        for line in ["aaaaaaaaa", "bbbbbbbbb"] {
+            files
+                .write("host", "proc_1", [(OutputTarget::Stderr, line)])
+                .unwrap();
+        }
+        let root = files.root().to_path_buf();
+
+        // Compression runs in the background.
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let rotated = loop {
+            let rotated = rotations(&root, "hosts_0-gpus_1.stderr");
+            if rotated.len() == 1 && rotated[0].ends_with(".log.gz") {
+                break rotated;
+            }
+            assert!(
+                Instant::now() < deadline,
+                "rotation not compressed: {:?}",
+                rotated
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        let mut contents = String::new();
+        GzDecoder::new(File::open(root.join(&rotated[0])).unwrap())
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "aaaaaaaaa\n");
+    }
+}
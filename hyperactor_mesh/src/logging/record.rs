@@ -0,0 +1,673 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Structured log records.
+//!
+//! Forwarders parse each line a child process writes to stdout/stderr
+//! into a [`LogRecord`], so that filtering can happen next to the
+//! producer instead of on the client. Parsing is best-effort: a line
+//! that matches none of the known formats still becomes a record,
+//! just without a level or timestamp of its own.
+//!
+//! Recognized formats, tried in order after stripping the
+//! `"[<rank>] "` prefix added by `StreamFwder`:
+//!
+//! - glog: `I0123 12:34:56.789012 1234 file.cc:42] message`
+//! - JSON objects, as emitted by `tracing-subscriber`'s JSON layer or
+//!   Python's structured loggers (`level`/`levelname`/`severity`,
+//!   `timestamp`/`time`/`ts`, `message`/`msg`/`fields.message`,
+//!   `actor_id`/`fields.actor_id`).
+//! - plain text with an upper-case level keyword (`INFO`, `WARNING`,
+//!   ...) among its first few whitespace-separated tokens.
+//!
+//! In every format an `actor_id=<id>` token in the message is picked
+//! up as the record's actor id.
+//!
+//! Lines without a level of their own, such as the lines of a Python
+//! traceback, are taken to continue the last leveled line on the same
+//! stream, and inherit its level (see [`LevelCarry`]).
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use chrono::TimeZone;
+use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use typeuri::Named;
+
+use super::OutputTarget;
+
+/// Number of leading tokens searched for a level keyword in plain-text
+/// lines.
+const LEVEL_SEARCH_TOKENS: usize = 4;
+
+static GLOG_PREFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([IWEF])(\d{2})(\d{2}) (\d{2}:\d{2}:\d{2}\.\d+)\s+\d+\s+[^\]]*\]\s?(.*)$")
+        .expect("valid glog regex")
+});
+
+/// Severity of a log record, ordered from least to most severe.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    Named
+)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl LogLevel {
+    fn from_glog(c: &str) -> Option<Self> {
+        match c {
+            "I" => Some(Self::Info),
+            "W" => Some(Self::Warn),
+            "E" => Some(Self::Error),
+            "F" => Some(Self::Fatal),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = anyhow::Error;
+
+    /// Parses a level name case-insensitively, accepting the Python
+    /// spellings (`WARNING`, `CRITICAL`) as well as the Rust ones.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "TRACE" => Ok(Self::Trace),
+            "DEBUG" => Ok(Self::Debug),
+            "INFO" => Ok(Self::Info),
+            "WARN" | "WARNING" => Ok(Self::Warn),
+            "ERROR" => Ok(Self::Error),
+            "FATAL" | "CRITICAL" => Ok(Self::Fatal),
+            _ => anyhow::bail!("unknown log level: {}", s),
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Trace => "TRACE",
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Warn => "WARN",
+            Self::Error => "ERROR",
+            Self::Fatal => "FATAL",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A single line of child output, with whatever structure could be
+/// recovered from it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Named)]
+pub struct LogRecord {
+    /// When the line was logged: the timestamp embedded in the line
+    /// if one was found, otherwise when the forwarder received it.
+    pub timestamp: SystemTime,
+    /// The severity, if the line carried one.
+    pub level: Option<LogLevel>,
+    /// The rank of the proc that produced the line, if known.
+    pub rank: Option<usize>,
+    /// The actor that logged the line, if the line named one.
+    pub actor_id: Option<String>,
+    /// The stream the line was written to.
+    pub output_target: OutputTarget,
+    /// The message body, with rank and format prefixes removed.
+    pub message: String,
+    /// The line exactly as the child wrote it (including the rank
+    /// prefix). This is what the client prints.
+    pub line: String,
+    /// For a line without a level, the level of the last leveled
+    /// line on the same stream, which it is taken to continue.
+    #[serde(default)]
+    pub inherited_level: Option<LogLevel>,
+}
+wirevalue::register_type!(LogRecord);
+
+impl LogRecord {
+    /// Parse `line` into a record. `rank` is the rank of the proc the
+    /// line came from, if the caller knows it; otherwise the rank is
+    /// taken from the `"[<rank>] "` prefix, if present. `received` is
+    /// used as the timestamp when the line doesn't carry one.
+    pub fn parse(
+        line: &str,
+        output_target: OutputTarget,
+        rank: Option<usize>,
+        received: SystemTime,
+    ) -> Self {
+        let (prefix_rank, body) = strip_rank_prefix(line);
+        let mut record = Self {
+            timestamp: received,
+            level: None,
+            rank: rank.or(prefix_rank),
+            actor_id: None,
+            output_target,
+            message: body.to_string(),
+            line: line.to_string(),
+            inherited_level: None,
+        };
+
+        if !record.parse_glog(body) && !record.parse_json(body) {
+            record.level = body
+                .split_whitespace()
+                .take(LEVEL_SEARCH_TOKENS)
+                .find_map(level_keyword);
+        }
+        if record.actor_id.is_none() {
+            record.actor_id = actor_id_token(&record.message);
+        }
+        record
+    }
+
+    /// The record's own level, or else the one it inherited.
+    pub fn effective_level(&self) -> Option<LogLevel> {
+        self.level.or(self.inherited_level)
+    }
+
+    fn parse_glog(&mut self, body: &str) -> bool {
+        let Some(caps) = GLOG_PREFIX.captures(body) else {
+            return false;
+        };
+        self.level = LogLevel::from_glog(&caps[1]);
+        if let Some(timestamp) = glog_timestamp(&caps[2], &caps[3], &caps[4]) {
+            self.timestamp = timestamp;
+        }
+        self.message = caps[5].to_string();
+        true
+    }
+
+    fn parse_json(&mut self, body: &str) -> bool {
+        if !body.trim_start().starts_with('{') {
+            return false;
+        }
+        let Ok(serde_json::Value::Object(object)) = serde_json::from_str(body) else {
+            return false;
+        };
+        let fields = object.get("fields").and_then(|f| f.as_object());
+        let get = |keys: &[&str]| {
+            keys.iter().find_map(|k| {
+                object
+                    .get(*k)
+                    .or_else(|| fields.and_then(|f| f.get(*k)))
+                    .filter(|v| !v.is_null())
+            })
+        };
+
+        self.level = get(&["level", "levelname", "severity"])
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse().ok());
+        if let Some(timestamp) = get(&["timestamp", "time", "ts"]).and_then(json_timestamp) {
+            self.timestamp = timestamp;
+        }
+        if let Some(message) = get(&["message", "msg"]) {
+            self.message = match message.as_str() {
+                Some(s) => s.to_string(),
+                None => message.to_string(),
+            };
+        }
+        self.actor_id = get(&["actor_id"])
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        true
+    }
+}
+
+/// Tracks the level of the last leveled record on each stream of a
+/// proc, so that the unleveled lines following it inherit that level
+/// instead of passing level filters as [`LogLevel::Info`].
+#[derive(Debug, Clone, Default)]
+pub struct LevelCarry {
+    stdout: Option<LogLevel>,
+    stderr: Option<LogLevel>,
+}
+
+impl LevelCarry {
+    /// Record `record`'s level if it has one, or else set its
+    /// inherited level. Records must be passed in the order they were
+    /// written.
+    pub fn carry(&mut self, record: &mut LogRecord) {
+        let last = match record.output_target {
+            OutputTarget::Stdout => &mut self.stdout,
+            OutputTarget::Stderr => &mut self.stderr,
+        };
+        match record.level {
+            Some(level) => *last = Some(level),
+            None => record.inherited_level = *last,
+        }
+    }
+}
+
+/// Split off the `"[<rank>] "` prefix written by `StreamFwder`.
+fn strip_rank_prefix(line: &str) -> (Option<usize>, &str) {
+    let Some(rest) = line.strip_prefix('[') else {
+        return (None, line);
+    };
+    let Some((rank, body)) = rest.split_once("] ") else {
+        return (None, line);
+    };
+    match rank.parse() {
+        Ok(rank) => (Some(rank), body),
+        Err(_) => (None, line),
+    }
+}
+
+/// A level keyword must be upper case, so that prose like "info" in a
+/// message isn't mistaken for a level. Only the token's leading word
+/// is considered, so `[INFO]` and Python's `WARNING:root:...` match.
+fn level_keyword(token: &str) -> Option<LogLevel> {
+    let word = token
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .find(|word| !word.is_empty())?;
+    if word.chars().any(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    word.parse().ok()
+}
+
+fn actor_id_token(message: &str) -> Option<String> {
+    let (_, rest) = message.split_once("actor_id=")?;
+    let id: String = rest
+        .trim_start_matches('"')
+        .chars()
+        .take_while(|c| !c.is_whitespace() && !matches!(c, '"' | ',' | '}' | ')'))
+        .collect();
+    (!id.is_empty()).then_some(id)
+}
+
+/// glog timestamps have no year; assume the current one, in local time.
+fn glog_timestamp(month: &str, day: &str, time: &str) -> Option<SystemTime> {
+    let date =
+        NaiveDate::from_ymd_opt(Local::now().year(), month.parse().ok()?, day.parse().ok()?)?;
+    let time = NaiveTime::parse_from_str(time, "%H:%M:%S%.f").ok()?;
+    let local = Local.from_local_datetime(&date.and_time(time)).single()?;
+    Some(local.into())
+}
+
+/// Accepts RFC 3339 strings and (fractional) seconds since the epoch.
+fn json_timestamp(value: &serde_json::Value) -> Option<SystemTime> {
+    if let Some(secs) = value.as_f64() {
+        return (secs >= 0.0).then(|| SystemTime::UNIX_EPOCH + Duration::from_secs_f64(secs));
+    }
+    let parsed = DateTime::parse_from_rfc3339(value.as_str()?).ok()?;
+    Some(parsed.into())
+}
+
+/// A filter over log records, set by the client and evaluated by each
+/// forwarder so that rejected lines never leave the proc.
+///
+/// All criteria must match. Records are compared by their
+/// [effective level](LogRecord::effective_level), and those without
+/// one are treated as [`LogLevel::Info`]; records with an unknown rank never match a
+/// rank selection.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Named)]
+pub struct LogFilter {
+    /// Drop records less severe than this.
+    pub min_level: Option<LogLevel>,
+    /// Keep only records from these ranks.
+    pub ranks: Option<Vec<usize>>,
+    /// Keep only records whose line matches this regular expression.
+    pub pattern: Option<String>,
+}
+wirevalue::register_type!(LogFilter);
+
+impl LogFilter {
+    /// Whether this filter accepts every record.
+    pub fn is_empty(&self) -> bool {
+        self.min_level.is_none() && self.ranks.is_none() && self.pattern.is_none()
+    }
+
+    /// Compile the filter for evaluation, validating its pattern.
+    pub fn compile(&self) -> anyhow::Result<CompiledLogFilter> {
+        let pattern = match &self.pattern {
+            Some(pattern) => Some(Regex::new(pattern)?),
+            None => None,
+        };
+        Ok(CompiledLogFilter {
+            min_level: self.min_level,
+            ranks: self.ranks.clone(),
+            pattern,
+        })
+    }
+}
+
+/// A [`LogFilter`] with its pattern compiled.
+#[derive(Debug, Clone)]
+pub struct CompiledLogFilter {
+    min_level: Option<LogLevel>,
+    ranks: Option<Vec<usize>>,
+    pattern: Option<Regex>,
+}
+
+impl CompiledLogFilter {
+    /// Whether `record` passes the filter.
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        if let Some(min_level) = self.min_level
+            && record.effective_level().unwrap_or(LogLevel::Info) < min_level
+        {
+            return false;
+        }
+        if let Some(ranks) = &self.ranks
+            && !record.rank.is_some_and(|rank| ranks.contains(&rank))
+        {
+            return false;
+        }
+        if let Some(pattern) = &self.pattern
+            && !pattern.is_match(&record.line)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// A bounded buffer holding the most recent records of a proc,
+/// regardless of the filter in effect, so they can be searched after
+/// the fact.
+#[derive(Debug, Clone)]
+pub struct LogRingBuffer {
+    capacity: usize,
+    records: VecDeque<LogRecord>,
+}
+
+impl LogRingBuffer {
+    /// Create a buffer retaining at most `capacity` records. A zero
+    /// capacity retains nothing.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Append a record, evicting the oldest one if the buffer is full.
+    pub fn push(&mut self, record: LogRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+
+    /// The most recent `limit` records accepted by `filter`, oldest
+    /// first.
+    pub fn query(&self, filter: &CompiledLogFilter, limit: usize) -> Vec<LogRecord> {
+        let mut matched: Vec<LogRecord> = self
+            .records
+            .iter()
+            .rev()
+            .filter(|record| filter.matches(record))
+            .take(limit)
+            .cloned()
+            .collect();
+        matched.reverse();
+        matched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> LogRecord {
+        LogRecord::parse(line, OutputTarget::Stdout, None, SystemTime::UNIX_EPOCH)
+    }
+
+    #[test]
+    fn test_parse_plain_line() {
+        let record = parse("hello world");
+        assert_eq!(record.level, None);
+        assert_eq!(record.rank, None);
+        assert_eq!(record.message, "hello world");
+        assert_eq!(record.line, "hello world");
+        assert_eq!(record.timestamp, SystemTime::UNIX_EPOCH);
+    }
+
+    #[test]
+    fn test_parse_rank_prefix() {
+        let record = parse("[3] WARNING:root:disk almost full");
+        assert_eq!(record.rank, Some(3));
+        assert_eq!(record.level, Some(LogLevel::Warn));
+        assert_eq!(record.message, "WARNING:root:disk almost full");
+        assert_eq!(record.line, "[3] WARNING:root:disk almost full");
+
+        // The caller's rank wins over the prefix.
+        let record = LogRecord::parse(
+            "[3] hi",
+            OutputTarget::Stderr,
+            Some(7),
+            SystemTime::UNIX_EPOCH,
+        );
+        assert_eq!(record.rank, Some(7));
+
+        // Not a rank prefix.
+        assert_eq!(parse("[abc] hi").rank, None);
+    }
+
+    #[test]
+    fn test_parse_glog() {
+        let record =
+            parse("[0] E0312 08:09:10.123456  4242 trainer.cc:17] actor_id=w[0].a[1] oops");
+        assert_eq!(record.rank, Some(0));
+        assert_eq!(record.level, Some(LogLevel::Error));
+        assert_eq!(record.message, "actor_id=w[0].a[1] oops");
+        assert_eq!(record.actor_id.as_deref(), Some("w[0].a[1]"));
+        assert_ne!(record.timestamp, SystemTime::UNIX_EPOCH);
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let record = parse(
+            r#"{"timestamp":"2025-01-02T03:04:05Z","level":"DEBUG","fields":{"message":"step done","actor_id":"m[2].w[0]"}}"#,
+        );
+        assert_eq!(record.level, Some(LogLevel::Debug));
+        assert_eq!(record.message, "step done");
+        assert_eq!(record.actor_id.as_deref(), Some("m[2].w[0]"));
+        assert_eq!(
+            record.timestamp,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(1735787045)
+        );
+
+        let record = parse(r#"{"levelname": "CRITICAL", "msg": "dead", "ts": 1.5}"#);
+        assert_eq!(record.level, Some(LogLevel::Fatal));
+        assert_eq!(record.message, "dead");
+        assert_eq!(
+            record.timestamp,
+            SystemTime::UNIX_EPOCH + Duration::from_millis(1500)
+        );
+
+        // Malformed JSON falls back to plain text.
+        let record = parse("{ERROR not json");
+        assert_eq!(record.level, Some(LogLevel::Error));
+    }
+
+    #[test]
+    fn test_level_keyword_requires_upper_case() {
+        assert_eq!(parse("some info about things").level, None);
+        assert_eq!(
+            parse("2025-01-01 [INFO] started").level,
+            Some(LogLevel::Info)
+        );
+        // Only the leading tokens are searched.
+        assert_eq!(parse("a b c d e ERROR").level, None);
+    }
+
+    #[test]
+    fn test_filter() {
+        let records = [
+            parse("[0] DEBUG noisy"),
+            parse("[1] INFO ready"),
+            parse("[1] unleveled"),
+            parse("[2] ERROR loss is nan"),
+            parse("no rank ERROR"),
+        ];
+        let matching = |filter: LogFilter| -> Vec<&str> {
+            let filter = filter.compile().unwrap();
+            records
+                .iter()
+                .filter(|r| filter.matches(r))
+                .map(|r| r.line.as_str())
+                .collect()
+        };
+
+        assert!(LogFilter::default().is_empty());
+        assert_eq!(matching(LogFilter::default()).len(), records.len());
+        assert_eq!(
+            matching(LogFilter {
+                min_level: Some(LogLevel::Info),
+                ..Default::default()
+            }),
+            vec![
+                "[1] INFO ready",
+                "[1] unleveled",
+                "[2] ERROR loss is nan",
+                "no rank ERROR"
+            ]
+        );
+        assert_eq!(
+            matching(LogFilter {
+                ranks: Some(vec![1, 2]),
+                min_level: Some(LogLevel::Warn),
+                ..Default::default()
+            }),
+            vec!["[2] ERROR loss is nan"]
+        );
+        assert_eq!(
+            matching(LogFilter {
+                pattern: Some("r.ady|nan$".to_string()),
+                ..Default::default()
+            }),
+            vec!["[1] INFO ready", "[2] ERROR loss is nan"]
+        );
+        assert!(
+            LogFilter {
+                pattern: Some("(".to_string()),
+                ..Default::default()
+            }
+            .compile()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_level_carry() {
+        let mut carry = LevelCarry::default();
+        let mut records = [
+            parse("[0] unleveled"),
+            parse("[0] ERROR Traceback (most recent call last):"),
+            parse("[0]   File \"train.py\", line 3, in <module>"),
+            LogRecord::parse(
+                "[0] stderr line",
+                OutputTarget::Stderr,
+                None,
+                SystemTime::UNIX_EPOCH,
+            ),
+            parse("[0] DEBUG next"),
+            parse("[0] more"),
+        ];
+        for record in &mut records {
+            carry.carry(record);
+        }
+        let levels: Vec<_> = records
+            .iter()
+            .map(|r| (r.level, r.effective_level()))
+            .collect();
+        assert_eq!(
+            levels,
+            vec![
+                (None, None),
+                (Some(LogLevel::Error), Some(LogLevel::Error)),
+                (None, Some(LogLevel::Error)),
+                // Streams are tracked separately.
+                (None, None),
+                (Some(LogLevel::Debug), Some(LogLevel::Debug)),
+                (None, Some(LogLevel::Debug)),
+            ]
+        );
+
+        let warn = LogFilter {
+            min_level: Some(LogLevel::Warn),
+            ..Default::default()
+        }
+        .compile()
+        .unwrap();
+        let matched: Vec<_> = records
+            .iter()
+            .filter(|r| warn.matches(r))
+            .map(|r| r.message.as_str())
+            .collect();
+        assert_eq!(
+            matched,
+            vec![
+                "ERROR Traceback (most recent call last):",
+                "  File \"train.py\", line 3, in <module>"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ring_buffer() {
+        let mut buffer = LogRingBuffer::new(3);
+        for i in 0..5 {
+            buffer.push(parse(&format!("[{}] INFO line {}", i % 2, i)));
+        }
+        let all = LogFilter::default().compile().unwrap();
+        let lines = |records: Vec<LogRecord>| -> Vec<String> {
+            records.into_iter().map(|r| r.message).collect()
+        };
+        assert_eq!(
+            lines(buffer.query(&all, usize::MAX)),
+            vec!["INFO line 2", "INFO line 3", "INFO line 4"]
+        );
+        assert_eq!(
+            lines(buffer.query(&all, 2)),
+            vec!["INFO line 3", "INFO line 4"]
+        );
+        let rank0 = LogFilter {
+            ranks: Some(vec![0]),
+            ..Default::default()
+        }
+        .compile()
+        .unwrap();
+        assert_eq!(
+            lines(buffer.query(&rank0, usize::MAX)),
+            vec!["INFO line 2", "INFO line 4"]
+        );
+
+        let mut disabled = LogRingBuffer::new(0);
+        disabled.push(parse("x"));
+        assert!(disabled.query(&all, usize::MAX).is_empty());
+    }
+}
@@ -333,8 +333,34 @@
 //! The relationship between `host` and `url` (formerly AI-4) is
 //! now a constructor guarantee of [`AdminInfo::new`] rather than a
 //! live invariant. It is not in this registry.
+//!
+//! # Event stream
+//!
+//! `GET /v1/events` is a server-sent event stream of actor and proc
+//! starts and terminal transitions, and of mesh supervision failures,
+//! backed by the agent's [`EventLog`]. The agent is fed by the proc
+//! agent lifecycle streams installed with [`watch_proc_mesh`] and by
+//! the `MeshFailure` subscriptions installed with
+//! [`watch_actor_mesh`]. [`crate::host_mesh::spawn_admin`] registers
+//! the new agent as this process's admin, and every proc mesh and
+//! actor mesh spawned from the process afterwards is watched
+//! automatically. Clients resume with the
+//! `Last-Event-ID` header (or `?since=<id>`); a resumption point
+//! that the log no longer covers is rejected with an `event_gap`
+//! error envelope (HTTP 410). The EV-* invariants are documented in
+//! [`crate::mesh_admin_events`].
+//!
+//! # Actions
+//!
+//! `POST /v1/actions/{stop_actor,stop_proc,drain_proc,drain_host}/..`
+//! are the only routes that change the mesh. They are authenticated
+//! with a bearer token or an allowed mTLS client certificate (the
+//! connection's identity is attached via `ConnectInfo<ClientIdentity>`),
+//! and every request is audited. The AC-* invariants are documented
+//! in [`crate::mesh_admin_actions`].
 
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::io;
 use std::sync::Arc;
 use std::time::Duration;
@@ -343,11 +369,17 @@ use async_trait::async_trait;
 use axum::Json;
 use axum::Router;
 use axum::extract::Path as AxumPath;
+use axum::extract::RawQuery;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
+use axum::response::sse::Event;
+use axum::response::sse::KeepAlive;
+use axum::response::sse::Sse;
 use axum::routing::get;
 use axum::routing::post;
+use futures::Stream;
+use futures::StreamExt;
 use hyperactor::Actor;
 use hyperactor::ActorHandle;
 use hyperactor::Context;
@@ -362,11 +394,14 @@ use hyperactor::introspect::IntrospectResult;
 use hyperactor::introspect::IntrospectView;
 use hyperactor::mailbox::open_once_port;
 use hyperactor::reference as hyperactor_reference;
+use hyperactor::supervision::ActorSupervisionEvent;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value;
 use tokio::net::TcpListener;
 use tokio_rustls::TlsAcceptor;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 use typeuri::Named;
 
 use crate::config_dump::ConfigDump;
@@ -377,8 +412,23 @@ use crate::introspect::NodePayload;
 use crate::introspect::NodeProperties;
 use crate::introspect::dto::NodePayloadDto;
 use crate::introspect::to_node_payload;
+use crate::mesh_admin_actions::ActionAuditEntry;
+use crate::mesh_admin_actions::ActionAuditLog;
+use crate::mesh_admin_actions::ActionAuth;
+use crate::mesh_admin_actions::ActionOutcome;
+use crate::mesh_admin_actions::ActionRequest;
+use crate::mesh_admin_actions::ActionResponse;
+use crate::mesh_admin_actions::AdminActionKind;
+use crate::mesh_admin_actions::AuditRecord;
+use crate::mesh_admin_actions::ClientIdentity;
+use crate::mesh_admin_events::EventGap;
+use crate::mesh_admin_events::EventLog;
+use crate::mesh_admin_events::EventSubscription;
+use crate::mesh_admin_events::MeshEvent;
+use crate::mesh_controller::Subscribe;
 use crate::proc_agent::PROC_AGENT_ACTOR_NAME;
 use crate::proc_agent::ProcAgent;
+use crate::proc_agent::WatchSupervision;
 use crate::pyspy::PySpyDump;
 use crate::pyspy::PySpyOpts;
 use crate::pyspy::PySpyProfile;
@@ -386,6 +436,7 @@ use crate::pyspy::PySpyProfileOpts;
 use crate::pyspy::PySpyProfileResult;
 use crate::pyspy::PySpyResult;
 use crate::pyspy::ValidatedProfileRequest;
+use crate::supervision::MeshFailure;
 
 /// Send an `IntrospectMessage` to an actor and receive the reply.
 /// Encapsulates open_once_port + send + timeout + error handling.
@@ -500,6 +551,43 @@ impl ApiError {
             details,
         }
     }
+
+    /// Create an "unauthorized" error: missing or invalid credentials
+    /// for an action route (AC-1).
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self {
+            code: "unauthorized".to_string(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Create a "forbidden" error: the caller is known but may not
+    /// perform actions (AC-1, AC-2).
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self {
+            code: "forbidden".to_string(),
+            message: message.into(),
+            details: None,
+        }
+    }
+}
+
+/// EV-4: a resumption point the event log no longer covers. The
+/// bounds travel in `details` so clients can tell how much they
+/// missed.
+impl From<EventGap> for ApiError {
+    fn from(gap: EventGap) -> Self {
+        Self {
+            code: "event_gap".to_string(),
+            message: gap.to_string(),
+            details: Some(serde_json::json!({
+                "requested": gap.requested,
+                "oldest": gap.oldest,
+                "latest": gap.latest,
+            })),
+        }
+    }
 }
 
 impl IntoResponse for ApiError {
@@ -507,6 +595,9 @@ impl IntoResponse for ApiError {
         let status = match self.code.as_str() {
             "not_found" => StatusCode::NOT_FOUND,
             "bad_request" => StatusCode::BAD_REQUEST,
+            "unauthorized" => StatusCode::UNAUTHORIZED,
+            "forbidden" => StatusCode::FORBIDDEN,
+            "event_gap" => StatusCode::GONE,
             "gateway_timeout" => StatusCode::GATEWAY_TIMEOUT,
             "service_unavailable" => StatusCode::SERVICE_UNAVAILABLE,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
@@ -615,7 +706,18 @@ wirevalue::register_type!(ResolveReferenceMessage);
 /// supports reference-based navigation (`GET /v1/{reference}`) by
 /// resolving HTTP path references into typed `NodePayload` values
 /// plus child references.
-#[hyperactor::export(handlers = [MeshAdminMessage, ResolveReferenceMessage])]
+///
+/// It also records supervision events it is told about into an
+/// [`EventLog`] served at `GET /v1/events`: `ActorSupervisionEvent`s
+/// from proc agents it watches via [`watch_proc_mesh`], and
+/// `MeshFailure`s from mesh controllers it was subscribed to via
+/// [`watch_actor_mesh`].
+#[hyperactor::export(handlers = [
+    MeshAdminMessage,
+    ResolveReferenceMessage,
+    ActorSupervisionEvent,
+    Option<MeshFailure>,
+])]
 pub struct MeshAdminAgent {
     /// Map of host address string → `HostAgent` reference used to
     /// fan out our target admin queries.
@@ -680,6 +782,10 @@ pub struct MeshAdminAgent {
 
     /// Username who started the mesh.
     started_by: String,
+
+    /// Event log behind `GET /v1/events`, shared with the HTTP
+    /// bridge. The agent writes; stream handlers read.
+    events: Arc<EventLog>,
 }
 
 impl MeshAdminAgent {
@@ -728,6 +834,9 @@ impl MeshAdminAgent {
             telemetry_url,
             started_at,
             started_by,
+            events: Arc::new(EventLog::new(hyperactor_config::global::get(
+                crate::config::MESH_ADMIN_EVENT_BUFFER_SIZE,
+            ))),
         }
     }
 }
@@ -743,6 +852,7 @@ impl std::fmt::Debug for MeshAdminAgent {
             .field("admin_host", &self.admin_host)
             .field("started_at", &self.started_at)
             .field("started_by", &self.started_by)
+            .field("latest_event_id", &self.events.latest_id())
             .finish()
     }
 }
@@ -824,6 +934,12 @@ struct BridgeState {
     http_client: reqwest::Client,
     /// Self-identification metadata, populated during admin init.
     admin_info: AdminInfo,
+    /// The agent's event log, streamed by `GET /v1/events`.
+    events: Arc<EventLog>,
+    /// Who may call `POST /v1/actions/*` (AC-1, AC-2).
+    action_auth: ActionAuth,
+    /// Record of every action request (AC-3).
+    action_audit: ActionAuditLog,
 }
 
 /// A TCP listener that performs a TLS handshake on each accepted
@@ -837,6 +953,33 @@ struct TlsListener {
     acceptor: TlsAcceptor,
 }
 
+/// AC-5: each TLS connection is tagged with the fingerprint of the
+/// client certificate it presented, for action authorization.
+impl axum::extract::connect_info::Connected<axum::serve::IncomingStream<'_, TlsListener>>
+    for ClientIdentity
+{
+    fn connect_info(stream: axum::serve::IncomingStream<'_, TlsListener>) -> Self {
+        let (_, connection) = stream.io().get_ref();
+        ClientIdentity::from_peer_certificates(
+            *stream.remote_addr(),
+            connection.peer_certificates(),
+        )
+    }
+}
+
+/// Plain HTTP connections carry no client identity; actions on them
+/// require a bearer token.
+impl axum::extract::connect_info::Connected<axum::serve::IncomingStream<'_, TcpListener>>
+    for ClientIdentity
+{
+    fn connect_info(stream: axum::serve::IncomingStream<'_, TcpListener>) -> Self {
+        ClientIdentity {
+            remote_addr: Some(*stream.remote_addr()),
+            certificate: None,
+        }
+    }
+}
+
 impl axum::serve::Listener for TlsListener {
     type Io = tokio_rustls::server::TlsStream<tokio::net::TcpStream>;
     type Addr = std::net::SocketAddr;
@@ -959,6 +1102,16 @@ impl Actor for MeshAdminAgent {
                 this.self_id().proc_id().to_string(),
                 admin_url,
             )?,
+            events: Arc::clone(&self.events),
+            // An unusable action policy disables the actions (AC-2),
+            // not the read-only API.
+            action_auth: ActionAuth::from_config().unwrap_or_else(|e| {
+                tracing::error!("mesh admin actions disabled: {:#}", e);
+                ActionAuth::default()
+            }),
+            action_audit: ActionAuditLog::new(hyperactor_config::global::get(
+                crate::config::MESH_ADMIN_ACTION_AUDIT_SIZE,
+            )),
         });
         let router = create_mesh_admin_router(bridge_state);
 
@@ -967,15 +1120,17 @@ impl Actor for MeshAdminAgent {
                 tcp: listener,
                 acceptor,
             };
+            let service = router.into_make_service_with_connect_info::<ClientIdentity>();
             tokio::spawn(async move {
-                if let Err(e) = axum::serve(tls_listener, router).await {
+                if let Err(e) = axum::serve(tls_listener, service).await {
                     tracing::error!("mesh admin server (mTLS) error: {}", e);
                 }
             });
         } else {
             // OSS fallback: plain HTTP (only reachable when !fbcode_build).
+            let service = router.into_make_service_with_connect_info::<ClientIdentity>();
             tokio::spawn(async move {
-                if let Err(e) = axum::serve(listener, router).await {
+                if let Err(e) = axum::serve(listener, service).await {
                     tracing::error!("mesh admin server error: {}", e);
                 }
             });
@@ -1080,6 +1235,99 @@ impl Handler<ResolveReferenceMessage> for MeshAdminAgent {
     }
 }
 
+/// Record actor starts and terminal transitions forwarded to the
+/// admin agent. Never fails (MA-R1).
+#[async_trait]
+impl Handler<ActorSupervisionEvent> for MeshAdminAgent {
+    async fn handle(
+        &mut self,
+        _cx: &Context<Self>,
+        event: ActorSupervisionEvent,
+    ) -> Result<(), anyhow::Error> {
+        self.events.record_supervision(&event);
+        Ok(())
+    }
+}
+
+/// Record mesh failures from controllers the admin subscribed to
+/// with [`watch_actor_mesh`]. Controllers periodically send `None`
+/// as a liveness signal; it carries no event.
+#[async_trait]
+impl Handler<Option<MeshFailure>> for MeshAdminAgent {
+    async fn handle(
+        &mut self,
+        _cx: &Context<Self>,
+        failure: Option<MeshFailure>,
+    ) -> Result<(), anyhow::Error> {
+        if let Some(failure) = failure {
+            self.events.record_mesh_failure(&failure);
+        }
+        Ok(())
+    }
+}
+
+/// The admin agent most recently spawned in this process by
+/// [`crate::host_mesh::spawn_admin`]. Meshes spawned from this process
+/// are watched by it.
+static LOCAL_ADMIN: std::sync::Mutex<Option<hyperactor_reference::ActorRef<MeshAdminAgent>>> =
+    std::sync::Mutex::new(None);
+
+/// Make `admin` the admin agent that watches meshes spawned from this
+/// process from now on.
+pub(crate) fn set_local_admin(admin: hyperactor_reference::ActorRef<MeshAdminAgent>) {
+    *LOCAL_ADMIN
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(admin);
+}
+
+/// The admin agent watching meshes spawned from this process, if any.
+pub(crate) fn local_admin() -> Option<hyperactor_reference::ActorRef<MeshAdminAgent>> {
+    LOCAL_ADMIN
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone()
+}
+
+/// Subscribe `admin`'s event stream to the lifecycle of the procs in
+/// `mesh`.
+///
+/// Casts a [`WatchSupervision`] with the admin's
+/// `ActorSupervisionEvent` port to the mesh's proc agents, so proc
+/// and actor starts and terminal transitions on those procs appear on
+/// `GET /v1/events`. Done automatically for proc meshes spawned after
+/// [`crate::host_mesh::spawn_admin`].
+pub fn watch_proc_mesh(
+    cx: &impl hyperactor::context::Actor,
+    admin: &hyperactor_reference::ActorRef<MeshAdminAgent>,
+    mesh: &crate::ProcMeshRef,
+) -> anyhow::Result<()> {
+    mesh.agent_mesh()
+        .cast(cx, WatchSupervision(admin.port::<ActorSupervisionEvent>()))?;
+    Ok(())
+}
+
+/// Subscribe `admin`'s event stream to the supervision failures of
+/// `mesh`.
+///
+/// Sends a `Subscribe` to the mesh's controller with the admin's
+/// `Option<MeshFailure>` port, so every `MeshFailure` the controller
+/// reports also appears on `GET /v1/events`. Fails if the mesh has no
+/// controller (e.g. a ref deserialized without one). Done
+/// automatically for actor meshes spawned after
+/// [`crate::host_mesh::spawn_admin`].
+pub fn watch_actor_mesh<A: hyperactor::actor::Referable>(
+    cx: &impl hyperactor::context::Actor,
+    admin: &hyperactor_reference::ActorRef<MeshAdminAgent>,
+    mesh: &crate::ActorMeshRef<A>,
+) -> anyhow::Result<()> {
+    let controller = mesh
+        .controller()
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("actor mesh {} has no controller", mesh.name()))?;
+    controller.send(cx, Subscribe(admin.port::<Option<MeshFailure>>()))?;
+    Ok(())
+}
+
 impl MeshAdminAgent {
     /// Core resolver for the reference-based admin API.
     ///
@@ -1485,6 +1733,7 @@ impl MeshAdminAgent {
 /// - `GET /v1/schema/error` — JSON Schema for `ApiErrorEnvelope`.
 /// - `GET /v1/openapi.json` — OpenAPI 3.1 spec (embeds JSON Schemas).
 /// - `GET /v1/tree` — ASCII topology dump.
+/// - `GET /v1/events` — SSE stream of lifecycle and supervision events.
 /// - `POST /v1/query` — proxy SQL query to the dashboard server.
 /// - `GET /v1/pyspy/{*proc_reference}` — py-spy stack dump for a proc.
 /// - `POST /v1/pyspy_dump/{*proc_reference}` — py-spy dump + store in Datafusion.
@@ -1503,6 +1752,7 @@ fn create_mesh_admin_router(bridge_state: Arc<BridgeState>) -> Router {
         .route("/v1/schema/error", get(serve_error_schema))
         .route("/v1/openapi.json", get(serve_openapi))
         .route("/v1/tree", get(tree_dump))
+        .route("/v1/events", get(events_stream))
         .route("/v1/query", post(query_proxy))
         .route("/v1/pyspy/{*proc_reference}", get(pyspy_bridge))
         .route(
@@ -1514,6 +1764,23 @@ fn create_mesh_admin_router(bridge_state: Arc<BridgeState>) -> Router {
             post(pyspy_profile_svg),
         )
         .route("/v1/config/{*proc_reference}", get(config_bridge))
+        .route("/v1/actions/audit", get(action_audit))
+        .route(
+            "/v1/actions/stop_actor/{*actor_reference}",
+            post(stop_actor_action),
+        )
+        .route(
+            "/v1/actions/stop_proc/{*proc_reference}",
+            post(stop_proc_action),
+        )
+        .route(
+            "/v1/actions/drain_proc/{*proc_reference}",
+            post(drain_proc_action),
+        )
+        .route(
+            "/v1/actions/drain_host/{*host_reference}",
+            post(drain_host_action),
+        )
         .route("/v1/{*reference}", get(resolve_reference_bridge))
         .with_state(bridge_state)
 }
@@ -1661,6 +1928,14 @@ pub fn build_openapi_spec() -> serde_json::Value {
         .expect("AdminInfo schema must be serializable");
     let mut profile_opts_schema = serde_json::to_value(schemars::schema_for!(PySpyProfileOpts))
         .expect("PySpyProfileOpts schema must be serializable");
+    let mut mesh_event_schema = serde_json::to_value(schemars::schema_for!(MeshEvent))
+        .expect("MeshEvent schema must be serializable");
+    let mut action_request_schema = serde_json::to_value(schemars::schema_for!(ActionRequest))
+        .expect("ActionRequest schema must be serializable");
+    let mut action_response_schema = serde_json::to_value(schemars::schema_for!(ActionResponse))
+        .expect("ActionResponse schema must be serializable");
+    let mut audit_entry_schema = serde_json::to_value(schemars::schema_for!(ActionAuditEntry))
+        .expect("ActionAuditEntry schema must be serializable");
 
     // Hoist $defs into a shared components/schemas map so
     // OpenAPI tools can resolve references.
@@ -1673,6 +1948,10 @@ pub fn build_openapi_spec() -> serde_json::Value {
     hoist_defs(&mut pyspy_dump_response_schema, &mut shared_schemas);
     hoist_defs(&mut admin_info_schema, &mut shared_schemas);
     hoist_defs(&mut profile_opts_schema, &mut shared_schemas);
+    hoist_defs(&mut mesh_event_schema, &mut shared_schemas);
+    hoist_defs(&mut action_request_schema, &mut shared_schemas);
+    hoist_defs(&mut action_response_schema, &mut shared_schemas);
+    hoist_defs(&mut audit_entry_schema, &mut shared_schemas);
     shared_schemas.insert("NodePayload".into(), node_schema);
     shared_schemas.insert("ApiErrorEnvelope".into(), error_schema);
     shared_schemas.insert("PySpyResult".into(), pyspy_schema);
@@ -1684,6 +1963,10 @@ pub fn build_openapi_spec() -> serde_json::Value {
     );
     shared_schemas.insert("AdminInfo".into(), admin_info_schema);
     shared_schemas.insert("PySpyProfileOpts".into(), profile_opts_schema);
+    shared_schemas.insert("MeshEvent".into(), mesh_event_schema);
+    shared_schemas.insert("ActionRequest".into(), action_request_schema);
+    shared_schemas.insert("ActionResponse".into(), action_response_schema);
+    shared_schemas.insert("ActionAuditEntry".into(), audit_entry_schema);
 
     // Rewrite any remaining $defs refs in the hoisted component schemas.
     for value in shared_schemas.values_mut() {
@@ -1834,7 +2117,9 @@ pub fn build_openapi_spec() -> serde_json::Value {
                                                         "default_value": { "type": ["string", "null"] },
                                                         "source": { "type": "string" },
                                                         "changed_from_default": { "type": "boolean" },
-                                                        "env_var": { "type": ["string", "null"] }
+                                                        "env_var": { "type": ["string", "null"] },
+                                                        "validation_error": { "type": ["string", "null"] },
+                                                        "file": { "type": ["string", "null"] }
                                                     }
                                                 }
                                             }
@@ -1958,6 +2243,44 @@ pub fn build_openapi_spec() -> serde_json::Value {
                 }
             }),
         );
+        paths.insert(
+            "/v1/events".into(),
+            serde_json::json!({
+                "get": {
+                    "summary": "Server-sent event stream of lifecycle and supervision events",
+                    "operationId": "streamEvents",
+                    "description": "Streams terminal actor/proc transitions and mesh supervision failures as text/event-stream. Each frame has `event: mesh_event`, `id:` equal to the MeshEvent id, and a JSON MeshEvent as data. Resume with the Last-Event-ID header (or the since query parameter). A stream that falls behind ends with an `event: stream_error` frame carrying an ApiErrorEnvelope.",
+                    "parameters": [
+                        {
+                            "name": "Last-Event-ID",
+                            "in": "header",
+                            "required": false,
+                            "description": "Resume after this event id. Takes precedence over since.",
+                            "schema": { "type": "integer", "minimum": 0 }
+                        },
+                        {
+                            "name": "since",
+                            "in": "query",
+                            "required": false,
+                            "description": "Resume after this event id; 0 replays every retained event. Omit for live events only.",
+                            "schema": { "type": "integer", "minimum": 0 }
+                        }
+                    ],
+                    "responses": {
+                        "200": {
+                            "description": "Event stream; each data payload is a MeshEvent",
+                            "content": {
+                                "text/event-stream": {
+                                    "schema": { "$ref": "#/components/schemas/MeshEvent" }
+                                }
+                            }
+                        },
+                        "400": error_response("Bad request (malformed resume id)"),
+                        "410": error_response("Event gap (resume id no longer retained); re-fetch the tree and subscribe without a resume id")
+                    }
+                }
+            }),
+        );
         paths.insert(
             "/v1/pyspy_profile_svg/{proc_reference}".into(),
             serde_json::json!({
@@ -1994,6 +2317,141 @@ pub fn build_openapi_spec() -> serde_json::Value {
                 }
             }),
         );
+
+        // Mutating actions (AC-*). Each requires a bearer token or an
+        // allowed mTLS client certificate.
+        let security = serde_json::json!([{ "bearerAuth": [] }, { "mutualTLS": [] }]);
+        let actions = [
+            (
+                "stop_actor",
+                "actor_reference",
+                "URL-encoded actor reference (ActorId)",
+                "stopActor",
+                "Stop an actor",
+                "Asks the actor's ProcAgent to stop it (the per-rank equivalent of ActorMesh::stop). Returns 202 once the stop is delivered.",
+            ),
+            (
+                "stop_proc",
+                "proc_reference",
+                "URL-encoded proc reference (ProcId)",
+                "stopProc",
+                "Stop a proc",
+                "Asks the HostAgent to stop the proc (the per-rank equivalent of ProcMesh::stop). Returns 202 once the stop is delivered.",
+            ),
+            (
+                "drain_proc",
+                "proc_reference",
+                "URL-encoded proc reference (ProcId)",
+                "drainProc",
+                "Drain a proc",
+                "Stops every actor on the proc via its ProcAgent; the proc exits once they have stopped. Returns 202 once the request is delivered.",
+            ),
+            (
+                "drain_host",
+                "host_reference",
+                "URL-encoded host reference (host:<ActorId> or the HostAgent ActorId)",
+                "drainHost",
+                "Drain or shut down a host",
+                "Sends DrainHost (or ShutdownHost when shutdown is true) to the HostAgent and waits for its acknowledgement. Returns 200 when the drain completed.",
+            ),
+        ];
+        for (action, param, param_desc, operation_id, summary, description) in actions {
+            paths.insert(
+                format!("/v1/actions/{action}/{{{param}}}"),
+                serde_json::json!({
+                    "post": {
+                        "summary": summary,
+                        "operationId": operation_id,
+                        "description": description,
+                        "security": security,
+                        "parameters": [{
+                            "name": param,
+                            "in": "path",
+                            "required": true,
+                            "description": param_desc,
+                            "schema": { "type": "string" }
+                        }],
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/ActionRequest" }
+                                }
+                            }
+                        },
+                        "responses": {
+                            "200": {
+                                "description": "Action completed",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/ActionResponse" }
+                                    }
+                                }
+                            },
+                            "202": {
+                                "description": "Action accepted; the target stops asynchronously",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/ActionResponse" }
+                                    }
+                                }
+                            },
+                            "400": error_response("Bad request (malformed or unsupported target)"),
+                            "401": error_response("Unauthorized (missing or invalid credentials)"),
+                            "403": error_response("Forbidden (client not allowed, or actions disabled)"),
+                            "404": error_response("Target not reachable"),
+                            "500": error_response("Internal error"),
+                            "504": error_response("Gateway timeout (action still in progress)")
+                        }
+                    }
+                }),
+            );
+        }
+        paths.insert(
+            "/v1/actions/audit".into(),
+            serde_json::json!({
+                "get": {
+                    "summary": "Recent action audit entries",
+                    "operationId": "getActionAudit",
+                    "description": "Returns the retained audit log of action requests, oldest first. Requires the same credentials as the actions.",
+                    "security": security,
+                    "responses": {
+                        "200": {
+                            "description": "Audit entries",
+                            "content": {
+                                "application/json": {
+                                    "schema": {
+                                        "type": "array",
+                                        "items": { "$ref": "#/components/schemas/ActionAuditEntry" }
+                                    }
+                                }
+                            }
+                        },
+                        "401": error_response("Unauthorized (missing or invalid credentials)"),
+                        "403": error_response("Forbidden (client not allowed, or actions disabled)")
+                    }
+                }
+            }),
+        );
+    }
+    if let Some(components) = spec
+        .pointer_mut("/components")
+        .and_then(|v| v.as_object_mut())
+    {
+        components.insert(
+            "securitySchemes".into(),
+            serde_json::json!({
+                "bearerAuth": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "description": "Token from HYPERACTOR_MESH_ADMIN_ACTION_TOKEN_FILE"
+                },
+                "mutualTLS": {
+                    "type": "mutualTLS",
+                    "description": "Client certificate listed in HYPERACTOR_MESH_ADMIN_ACTION_ALLOWED_CLIENTS"
+                }
+            }),
+        );
     }
 
     spec
@@ -2531,6 +2989,342 @@ async fn config_bridge(
     Ok(Json(result))
 }
 
+/// Decode an action target path segment (leading slash stripped,
+/// percent-decoded).
+fn decode_action_target(raw: &str) -> Result<String, ApiError> {
+    let trimmed = raw.trim_start_matches('/');
+    if trimmed.is_empty() {
+        return Err(ApiError::bad_request("empty target reference", None));
+    }
+    urlencoding::decode(trimmed)
+        .map(|cow| cow.into_owned())
+        .map_err(|_| {
+            ApiError::bad_request(
+                "malformed percent-encoding: decoded bytes are not valid UTF-8",
+                None,
+            )
+        })
+}
+
+/// Parse a `drain_host` target: either a host node reference
+/// (`host:<actor_id>`, as listed by `GET /v1/root`) or the bare
+/// `HostAgent` `ActorId`.
+fn parse_host_target(target: &str) -> Result<hyperactor_reference::ActorId, ApiError> {
+    let actor_id: hyperactor_reference::ActorId = target
+        .strip_prefix("host:")
+        .unwrap_or(target)
+        .parse()
+        .map_err(|e| ApiError::bad_request(format!("invalid host reference: {}", e), None))?;
+    if actor_id.name() != HOST_MESH_AGENT_ACTOR_NAME {
+        return Err(ApiError::bad_request(
+            format!("{} is not a host agent", actor_id),
+            None,
+        ));
+    }
+    Ok(actor_id)
+}
+
+/// Fail with `not_found` unless `actor_id` answers an introspection
+/// probe.
+async fn require_reachable(
+    state: &BridgeState,
+    actor_id: &hyperactor_reference::ActorId,
+    what: &str,
+) -> Result<(), ApiError> {
+    if probe_actor(&state.bridge_cx, actor_id).await? {
+        Ok(())
+    } else {
+        Err(ApiError::not_found(
+            format!("{} is not reachable ({})", what, actor_id),
+            None,
+        ))
+    }
+}
+
+fn action_send_error(
+    target: &hyperactor_reference::ActorId,
+    e: impl std::fmt::Display,
+) -> ApiError {
+    ApiError {
+        code: "internal_error".to_string(),
+        message: format!("failed to send action to {}: {}", target, e),
+        details: None,
+    }
+}
+
+/// Carry out an authorized action. Fire-and-forget stops return
+/// `Accepted`; host drains wait for the agent's ack and return
+/// `Completed`.
+async fn perform_action(
+    state: &BridgeState,
+    action: AdminActionKind,
+    target: &str,
+    reason: String,
+    shutdown: bool,
+) -> Result<ActionOutcome, ApiError> {
+    let cx = &state.bridge_cx;
+    match action {
+        AdminActionKind::StopActor => {
+            let actor_id: hyperactor_reference::ActorId = target.parse().map_err(|e| {
+                ApiError::bad_request(format!("invalid actor reference: {}", e), None)
+            })?;
+            if actor_id.proc_id().base_name() == SERVICE_PROC_NAME {
+                return Err(ApiError::bad_request(
+                    "actors on the service proc belong to the host; use drain_host",
+                    None,
+                ));
+            }
+            let name: crate::Name = actor_id.name().parse().map_err(|e| {
+                ApiError::bad_request(format!("actor was not spawned by a mesh: {}", e), None)
+            })?;
+            require_reachable(state, &actor_id, "actor").await?;
+            let agent: hyperactor_reference::ActorRef<ProcAgent> =
+                hyperactor_reference::ActorRef::attest(
+                    actor_id.proc_id().actor_id(PROC_AGENT_ACTOR_NAME, 0),
+                );
+            agent
+                .send(cx, crate::resource::Stop { name, reason })
+                .map_err(|e| action_send_error(agent.actor_id(), e))?;
+            Ok(ActionOutcome::Accepted)
+        }
+        AdminActionKind::StopProc => {
+            let (_, proc_id) = parse_proc_reference(target)?;
+            if proc_id.base_name() == SERVICE_PROC_NAME {
+                return Err(ApiError::bad_request(
+                    "the service proc cannot be stopped on its own; use drain_host",
+                    None,
+                ));
+            }
+            let name: crate::Name = proc_id.name().parse().map_err(|e| {
+                ApiError::bad_request(format!("proc was not spawned by a host: {}", e), None)
+            })?;
+            let agent: hyperactor_reference::ActorRef<HostAgent> =
+                hyperactor_reference::ActorRef::attest(
+                    hyperactor_reference::ProcId::with_name(
+                        proc_id.addr().clone(),
+                        SERVICE_PROC_NAME,
+                    )
+                    .actor_id(HOST_MESH_AGENT_ACTOR_NAME, 0),
+                );
+            require_reachable(state, agent.actor_id(), "host agent").await?;
+            agent
+                .send(cx, crate::resource::Stop { name, reason })
+                .map_err(|e| action_send_error(agent.actor_id(), e))?;
+            Ok(ActionOutcome::Accepted)
+        }
+        AdminActionKind::DrainProc => match resolve_proc_handler(state, target).await? {
+            ResolvedProcHandler::Host(_) => Err(ApiError::bad_request(
+                "the service proc cannot be drained on its own; use drain_host",
+                None,
+            )),
+            ResolvedProcHandler::Proc(agent) => {
+                agent
+                    .send(cx, crate::resource::StopAll { reason })
+                    .map_err(|e| action_send_error(agent.actor_id(), e))?;
+                Ok(ActionOutcome::Accepted)
+            }
+        },
+        AdminActionKind::DrainHost => {
+            let agent_id = parse_host_target(target)?;
+            require_reachable(state, &agent_id, "host agent").await?;
+            let host = crate::host_mesh::HostRef::try_from(hyperactor_reference::ActorRef::<
+                HostAgent,
+            >::attest(agent_id.clone()))
+            .map_err(|e| ApiError::bad_request(e.to_string(), None))?;
+            tracing::info!(%agent_id, shutdown, %reason, "mesh admin draining host");
+            let timeout = hyperactor_config::global::get(crate::config::MESH_ADMIN_ACTION_TIMEOUT);
+            let work = async {
+                if shutdown {
+                    host.shutdown(cx).await
+                } else {
+                    host.drain(cx, None).await
+                }
+            };
+            tokio::time::timeout(timeout, work)
+                .await
+                .map_err(|_| ApiError {
+                    code: "gateway_timeout".to_string(),
+                    message: format!(
+                        "host {} did not acknowledge within {:?}; the drain continues",
+                        agent_id, timeout
+                    ),
+                    details: None,
+                })?
+                .map_err(|e| ApiError {
+                    code: "internal_error".to_string(),
+                    message: format!("failed to drain host {}: {}", agent_id, e),
+                    details: None,
+                })?;
+            Ok(ActionOutcome::Completed)
+        }
+    }
+}
+
+/// Shared body of the `POST /v1/actions/*` handlers: authorize
+/// (AC-1, AC-2), perform, and audit (AC-3) exactly once whatever the
+/// result. The body is taken as raw bytes and parsed only after
+/// authorization, so that a missing or malformed body is rejected
+/// through the same path and audited like any other failure.
+async fn action_bridge(
+    state: &BridgeState,
+    action: AdminActionKind,
+    raw_target: &str,
+    headers: &axum::http::HeaderMap,
+    extensions: &axum::http::Extensions,
+    body: &[u8],
+) -> Result<(StatusCode, Json<ActionResponse>), ApiError> {
+    let identity = extensions
+        .get::<axum::extract::ConnectInfo<ClientIdentity>>()
+        .map(|info| &info.0);
+    let target = decode_action_target(raw_target);
+
+    let mut request = None;
+    let (principal, result) = match state.action_auth.authorize(headers, identity) {
+        Ok(principal) => {
+            let result = match (ActionRequest::parse(body), &target) {
+                (Err(e), _) => Err(e),
+                (Ok(_), Err(e)) => Err(ApiError::bad_request(e.message.clone(), None)),
+                (Ok(parsed), Ok(target)) => {
+                    let reason = parsed
+                        .reason
+                        .clone()
+                        .unwrap_or_else(|| format!("{} via mesh admin by {}", action, principal));
+                    let shutdown = parsed.shutdown.unwrap_or(false);
+                    request = Some(parsed);
+                    perform_action(state, action, target, reason, shutdown).await
+                }
+            };
+            (Some(principal), result)
+        }
+        Err(e) => (None, Err(e)),
+    };
+
+    let outcome = match (&principal, &result) {
+        (None, _) => ActionOutcome::Rejected,
+        (Some(_), Err(_)) => ActionOutcome::Failed,
+        (Some(_), Ok(outcome)) => *outcome,
+    };
+    let target = target.unwrap_or_else(|_| raw_target.to_string());
+    let entry = state.action_audit.record(AuditRecord {
+        action,
+        target: &target,
+        principal: principal.as_deref(),
+        identity,
+        reason: request.as_ref().and_then(|r| r.reason.as_deref()),
+        outcome,
+        error: result.as_ref().err().map(|e| e.code.as_str()),
+    });
+
+    let outcome = result?;
+    let status = match outcome {
+        ActionOutcome::Accepted => StatusCode::ACCEPTED,
+        _ => StatusCode::OK,
+    };
+    Ok((
+        status,
+        Json(ActionResponse {
+            audit_id: entry.id,
+            action,
+            target,
+            outcome,
+        }),
+    ))
+}
+
+/// `POST /v1/actions/stop_actor/{actor_reference}` — stop one actor
+/// through its proc's `ProcAgent`.
+async fn stop_actor_action(
+    State(state): State<Arc<BridgeState>>,
+    AxumPath(actor_reference): AxumPath<String>,
+    headers: axum::http::HeaderMap,
+    extensions: axum::http::Extensions,
+    body: axum::body::Bytes,
+) -> Result<(StatusCode, Json<ActionResponse>), ApiError> {
+    action_bridge(
+        &state,
+        AdminActionKind::StopActor,
+        &actor_reference,
+        &headers,
+        &extensions,
+        &body,
+    )
+    .await
+}
+
+/// `POST /v1/actions/stop_proc/{proc_reference}` — ask the host
+/// agent to stop one proc.
+async fn stop_proc_action(
+    State(state): State<Arc<BridgeState>>,
+    AxumPath(proc_reference): AxumPath<String>,
+    headers: axum::http::HeaderMap,
+    extensions: axum::http::Extensions,
+    body: axum::body::Bytes,
+) -> Result<(StatusCode, Json<ActionResponse>), ApiError> {
+    action_bridge(
+        &state,
+        AdminActionKind::StopProc,
+        &proc_reference,
+        &headers,
+        &extensions,
+        &body,
+    )
+    .await
+}
+
+/// `POST /v1/actions/drain_proc/{proc_reference}` — stop every actor
+/// on a proc; the proc exits once they are gone.
+async fn drain_proc_action(
+    State(state): State<Arc<BridgeState>>,
+    AxumPath(proc_reference): AxumPath<String>,
+    headers: axum::http::HeaderMap,
+    extensions: axum::http::Extensions,
+    body: axum::body::Bytes,
+) -> Result<(StatusCode, Json<ActionResponse>), ApiError> {
+    action_bridge(
+        &state,
+        AdminActionKind::DrainProc,
+        &proc_reference,
+        &headers,
+        &extensions,
+        &body,
+    )
+    .await
+}
+
+/// `POST /v1/actions/drain_host/{host_reference}` — drain (or, with
+/// `shutdown`, tear down) a host.
+async fn drain_host_action(
+    State(state): State<Arc<BridgeState>>,
+    AxumPath(host_reference): AxumPath<String>,
+    headers: axum::http::HeaderMap,
+    extensions: axum::http::Extensions,
+    body: axum::body::Bytes,
+) -> Result<(StatusCode, Json<ActionResponse>), ApiError> {
+    action_bridge(
+        &state,
+        AdminActionKind::DrainHost,
+        &host_reference,
+        &headers,
+        &extensions,
+        &body,
+    )
+    .await
+}
+
+/// `GET /v1/actions/audit` — retained audit entries, oldest first.
+/// Requires the same credentials as the actions themselves.
+async fn action_audit(
+    State(state): State<Arc<BridgeState>>,
+    headers: axum::http::HeaderMap,
+    extensions: axum::http::Extensions,
+) -> Result<Json<Vec<ActionAuditEntry>>, ApiError> {
+    let identity = extensions
+        .get::<axum::extract::ConnectInfo<ClientIdentity>>()
+        .map(|info| &info.0);
+    state.action_auth.authorize(&headers, identity)?;
+    Ok(Json(state.action_audit.entries()))
+}
+
 /// Resolve an opaque reference string to a `NodePayload` via the
 /// actor-based resolver.
 ///
@@ -2793,6 +3587,115 @@ async fn tree_dump(
     Ok(output)
 }
 
+/// SSE header a reconnecting `EventSource` sends with the id of the
+/// last event it saw.
+const LAST_EVENT_ID: &str = "last-event-id";
+
+/// Determine where a `GET /v1/events` subscription resumes.
+///
+/// The `Last-Event-ID` header wins over the `since` query parameter:
+/// a browser `EventSource` keeps its original URL on reconnect but
+/// updates the header. Neither present means "live events only".
+fn parse_resume_point(
+    headers: &axum::http::HeaderMap,
+    raw_query: Option<&str>,
+) -> Result<Option<u64>, ApiError> {
+    let parse = |source: &str, value: &str| {
+        value.trim().parse::<u64>().map(Some).map_err(|_| {
+            ApiError::bad_request(
+                format!("{source} must be a non-negative event id, got {value:?}"),
+                None,
+            )
+        })
+    };
+    if let Some(value) = headers.get(LAST_EVENT_ID) {
+        let value = value
+            .to_str()
+            .map_err(|_| ApiError::bad_request("Last-Event-ID is not valid ASCII", None))?;
+        return parse("Last-Event-ID", value);
+    }
+    let since = raw_query.and_then(|query| {
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == "since")
+            .map(|(_, value)| value.into_owned())
+    });
+    match since {
+        Some(value) => parse("since", &value),
+        None => Ok(None),
+    }
+}
+
+/// Encode one event as an SSE frame (`event: mesh_event`, `id:` =
+/// event id per EV-1, JSON `MeshEvent` data).
+fn mesh_event_frame(event: &MeshEvent) -> Event {
+    Event::default()
+        .id(event.id.to_string())
+        .event("mesh_event")
+        .json_data(event)
+        .unwrap_or_else(|e| {
+            stream_error_frame(ApiError {
+                code: "internal_error".to_string(),
+                message: format!("failed to encode event {}: {}", event.id, e),
+                details: None,
+            })
+        })
+}
+
+/// Encode an `ApiErrorEnvelope` as a terminal SSE frame
+/// (`event: stream_error`).
+fn stream_error_frame(error: ApiError) -> Event {
+    let envelope = ApiErrorEnvelope { error };
+    Event::default()
+        .event("stream_error")
+        .data(serde_json::to_string(&envelope).unwrap_or_default())
+}
+
+/// Turn a subscription into SSE frames: the backlog, then live
+/// events. Lagging behind the live buffer ends the stream with an
+/// `event_gap` error frame (EV-4); the client resumes from the last
+/// id it received.
+fn mesh_event_stream(
+    subscription: EventSubscription,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    let backlog = futures::stream::iter(subscription.backlog.into_iter().map(Ok));
+    let live = BroadcastStream::new(subscription.live);
+    backlog.chain(live).scan(false, |cut, item| {
+        if *cut {
+            return futures::future::ready(None);
+        }
+        let frame = match item {
+            Ok(event) => mesh_event_frame(&event),
+            Err(BroadcastStreamRecvError::Lagged(missed)) => {
+                *cut = true;
+                stream_error_frame(ApiError {
+                    code: "event_gap".to_string(),
+                    message: format!(
+                        "stream fell {missed} events behind; resume with Last-Event-ID"
+                    ),
+                    details: Some(serde_json::json!({ "missed": missed })),
+                })
+            }
+        };
+        futures::future::ready(Some(Ok(frame)))
+    })
+}
+
+/// `GET /v1/events` — server-sent event stream of mesh events.
+///
+/// Resumes after the id in `Last-Event-ID` (or `?since=`), replaying
+/// retained events before switching to live ones (EV-3). Errors
+/// detected before the stream starts — a malformed resume id, or a
+/// gap (EV-4) — are returned as a plain `ApiErrorEnvelope` response.
+async fn events_stream(
+    State(state): State<Arc<BridgeState>>,
+    headers: axum::http::HeaderMap,
+    RawQuery(raw_query): RawQuery,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let after = parse_resume_point(&headers, raw_query.as_deref())?;
+    let subscription = state.events.subscribe(after)?;
+    Ok(Sse::new(mesh_event_stream(subscription)).keep_alive(KeepAlive::default()))
+}
+
 /// Derive a short display label from a reference string for the ASCII
 /// tree.
 ///
@@ -4148,4 +5051,121 @@ mod tests {
             "non-service proc should resolve to Proc variant"
         );
     }
+
+    /// The `Last-Event-ID` header wins over `?since=`; malformed ids
+    /// are `bad_request`.
+    #[test]
+    fn events_parse_resume_point() {
+        let mut headers = axum::http::HeaderMap::new();
+        assert_eq!(parse_resume_point(&headers, None).unwrap(), None);
+        assert_eq!(
+            parse_resume_point(&headers, Some("x=1&since=5")).unwrap(),
+            Some(5)
+        );
+        headers.insert(LAST_EVENT_ID, "9".parse().unwrap());
+        assert_eq!(
+            parse_resume_point(&headers, Some("since=5")).unwrap(),
+            Some(9)
+        );
+        headers.insert(LAST_EVENT_ID, "-1".parse().unwrap());
+        assert_eq!(
+            parse_resume_point(&headers, None).unwrap_err().code,
+            "bad_request"
+        );
+    }
+
+    /// EV-4: a gap maps to a 410 `event_gap` envelope with bounds.
+    #[test]
+    fn events_gap_is_gone() {
+        let error = ApiError::from(EventGap {
+            requested: 1,
+            oldest: 4,
+            latest: 6,
+        });
+        assert_eq!(error.code, "event_gap");
+        assert_eq!(error.details.as_ref().unwrap()["oldest"], 4);
+        assert_eq!(error.into_response().status(), StatusCode::GONE);
+    }
+
+    /// EV-1 and EV-3 over the wire: the SSE body replays the backlog
+    /// with `id:` equal to the event id, and parses back with the
+    /// streaming client's parser.
+    #[tokio::test]
+    async fn events_stream_round_trips_through_client_parser() {
+        use hyperactor::actor::ActorStatus;
+        use hyperactor::testing::ids::test_actor_id;
+
+        use crate::mesh_admin_client::SseParser;
+
+        let log = EventLog::new(8);
+        for name in ["a", "b", "c"] {
+            log.record_supervision(&ActorSupervisionEvent::new(
+                test_actor_id("proc", name),
+                None,
+                ActorStatus::generic_failure("boom"),
+                None,
+            ));
+        }
+        let subscription = log.subscribe(Some(1)).unwrap();
+        // Dropping the log closes the live channel, ending the stream.
+        drop(log);
+
+        let response = Sse::new(mesh_event_stream(subscription)).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let frames = SseParser::default().feed(&body);
+        let ids: Vec<_> = frames.iter().map(|f| f.id.clone().unwrap()).collect();
+        assert_eq!(ids, vec!["2", "3"]);
+        for frame in &frames {
+            assert_eq!(frame.event.as_deref(), Some("mesh_event"));
+            let event: MeshEvent = serde_json::from_str(&frame.data).unwrap();
+            assert_eq!(frame.id.as_deref(), Some(event.id.to_string().as_str()));
+        }
+    }
+
+    /// AC-1: authentication failures map to 401, authorization
+    /// failures to 403.
+    #[test]
+    fn actions_auth_errors_map_to_status() {
+        assert_eq!(
+            ApiError::unauthorized("no").into_response().status(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            ApiError::forbidden("no").into_response().status(),
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    /// `drain_host` accepts a `host:` node reference or the bare
+    /// `HostAgent` id, and nothing else.
+    #[test]
+    fn actions_parse_host_target() {
+        let addr: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let service = test_proc_id_with_addr(ChannelAddr::Tcp(addr), SERVICE_PROC_NAME);
+        let agent_id = service.actor_id(HOST_MESH_AGENT_ACTOR_NAME, 0);
+
+        assert_eq!(
+            parse_host_target(&format!("host:{}", agent_id)).unwrap(),
+            agent_id
+        );
+        assert_eq!(parse_host_target(&agent_id.to_string()).unwrap(), agent_id);
+
+        let other = service.actor_id("not_a_host_agent", 0);
+        assert_eq!(
+            parse_host_target(&other.to_string()).unwrap_err().code,
+            "bad_request"
+        );
+        assert_eq!(
+            parse_host_target("host:garbage").unwrap_err().code,
+            "bad_request"
+        );
+    }
+
+    #[test]
+    fn actions_decode_target() {
+        assert_eq!(decode_action_target("/a%2Fb").unwrap(), "a/b");
+        assert_eq!(decode_action_target("/").unwrap_err().code, "bad_request");
+    }
 }
@@ -0,0 +1,638 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Authentication and audit for mesh admin actions.
+//!
+//! The mesh admin HTTP API is read-only except for the
+//! `POST /v1/actions/*` routes, which stop actors, stop or drain
+//! procs, and drain or shut down hosts. This module holds the parts
+//! of those routes that do not talk to actors: the request and
+//! response types, the [`ActionAuth`] policy that decides who may
+//! act, and the [`ActionAuditLog`] every request is recorded in. The
+//! handlers themselves live in [`crate::mesh_admin`].
+//!
+//! A caller authenticates with either
+//!
+//! - a bearer token (`Authorization: Bearer <token>`) matching the
+//!   contents of `MESH_ADMIN_ACTION_TOKEN_FILE`, or
+//! - an mTLS client certificate whose identity is listed in
+//!   `MESH_ADMIN_ACTION_ALLOWED_CLIENTS` (or any verified certificate
+//!   when the list is `*`).
+//!
+//! ## Action invariants (AC-*)
+//!
+//! - **AC-1 (authorize first):** No message is sent to any actor
+//!   until the request is authorized. Missing or wrong credentials
+//!   fail with `unauthorized` (HTTP 401); a verified client identity
+//!   that is not allowed fails with `forbidden` (HTTP 403).
+//! - **AC-2 (off by default):** With neither a token nor allowed
+//!   client identities configured, every action fails with
+//!   `forbidden`. Read-only routes are unaffected.
+//! - **AC-3 (audited):** Every action request — accepted, completed,
+//!   rejected or failed — produces exactly one [`ActionAuditEntry`],
+//!   logged as a `MeshAdminAudit` tracing event and retained in the
+//!   [`ActionAuditLog`] before the response is returned.
+//! - **AC-4 (no token leaks):** Tokens are compared in constant time
+//!   and are never logged, audited or echoed back.
+//! - **AC-5 (client identity):** The mTLS identity of a connection
+//!   is the SHA-256 fingerprint of the leaf certificate the peer
+//!   presented, rendered as `sha256:<lowercase hex>`.
+
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use axum::http::HeaderMap;
+use axum::http::header::AUTHORIZATION;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::mesh_admin::ApiError;
+
+/// The mutating operations exposed under `POST /v1/actions/*`.
+///
+/// - `stop_actor`: stop one actor through its proc's agent (the
+///   per-rank equivalent of `ActorMesh::stop`).
+/// - `stop_proc`: ask the host agent to stop one proc (the per-rank
+///   equivalent of `ProcMesh::stop`).
+/// - `drain_proc`: stop every actor on a proc, after which the proc
+///   shuts itself down.
+/// - `drain_host`: terminate every user proc on a host (`DrainHost`),
+///   or tear the host down entirely when `shutdown` is set
+///   (`ShutdownHost`).
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    schemars::JsonSchema
+)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminActionKind {
+    StopActor,
+    StopProc,
+    DrainProc,
+    DrainHost,
+}
+
+impl AdminActionKind {
+    /// Wire name of the action, as serialized and used in the route.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::StopActor => "stop_actor",
+            Self::StopProc => "stop_proc",
+            Self::DrainProc => "drain_proc",
+            Self::DrainHost => "drain_host",
+        }
+    }
+}
+
+impl std::fmt::Display for AdminActionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Request body of every `POST /v1/actions/*` route. All fields are
+/// optional, so `{}` is a valid body.
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    schemars::JsonSchema
+)]
+pub struct ActionRequest {
+    /// Operator-supplied reason, forwarded to the stopped resource
+    /// and recorded in the audit log.
+    pub reason: Option<String>,
+    /// `drain_host` only: shut the host down (`ShutdownHost`) instead
+    /// of draining its procs (`DrainHost`). Ignored by other actions.
+    pub shutdown: Option<bool>,
+}
+
+impl ActionRequest {
+    /// Parse a request body. An empty body is treated as `{}`;
+    /// anything else must be a JSON object of this shape.
+    pub fn parse(body: &[u8]) -> Result<Self, ApiError> {
+        if body.iter().all(u8::is_ascii_whitespace) {
+            return Ok(Self::default());
+        }
+        serde_json::from_slice(body)
+            .map_err(|e| ApiError::bad_request(format!("invalid action request: {}", e), None))
+    }
+}
+
+/// How an action ended.
+///
+/// - `accepted`: the stop request was delivered; the target winds
+///   down asynchronously (watch `GET /v1/events` or re-fetch it).
+/// - `completed`: the action finished before the response was sent.
+/// - `rejected`: authentication or authorization failed (AC-1).
+/// - `failed`: the action was authorized but could not be carried
+///   out.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    schemars::JsonSchema
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionOutcome {
+    Accepted,
+    Completed,
+    Rejected,
+    Failed,
+}
+
+impl ActionOutcome {
+    /// Wire name of the outcome, as serialized.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accepted => "accepted",
+            Self::Completed => "completed",
+            Self::Rejected => "rejected",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Successful response of a `POST /v1/actions/*` route.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ActionResponse {
+    /// Id of the audit entry recorded for this request (AC-3).
+    pub audit_id: u64,
+    /// The action performed.
+    pub action: AdminActionKind,
+    /// Reference string of the target node.
+    pub target: String,
+    /// Either `accepted` (HTTP 202) or `completed` (HTTP 200).
+    pub outcome: ActionOutcome,
+}
+
+/// One record of the action audit log (AC-3).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ActionAuditEntry {
+    /// Monotonic audit id, starting at 1.
+    pub id: u64,
+    /// When the request finished (ISO 8601 timestamp string).
+    pub at: String,
+    /// The requested action.
+    pub action: AdminActionKind,
+    /// Target reference as given in the request path (decoded).
+    pub target: String,
+    /// Who asked: `token` or `mtls:sha256:<hex>`. Absent when the
+    /// request was not authenticated.
+    pub principal: Option<String>,
+    /// Peer address of the HTTP connection, if known.
+    pub remote_addr: Option<String>,
+    /// Reason forwarded to the target.
+    pub reason: Option<String>,
+    /// How the request ended.
+    pub outcome: ActionOutcome,
+    /// Error code of a rejected or failed request.
+    pub error: Option<String>,
+}
+
+/// Per-connection identity the admin server attaches to each request
+/// (via axum's `ConnectInfo`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientIdentity {
+    /// Peer socket address.
+    pub remote_addr: Option<SocketAddr>,
+    /// Fingerprint of the verified client certificate, if the
+    /// connection is mTLS (AC-5).
+    pub certificate: Option<String>,
+}
+
+impl ClientIdentity {
+    /// Identity of a TLS connection from the certificate chain the
+    /// peer presented (leaf first).
+    pub fn from_peer_certificates(
+        remote_addr: SocketAddr,
+        chain: Option<&[impl AsRef<[u8]>]>,
+    ) -> Self {
+        Self {
+            remote_addr: Some(remote_addr),
+            certificate: chain
+                .and_then(|chain| chain.first())
+                .map(|leaf| certificate_fingerprint(leaf.as_ref())),
+        }
+    }
+}
+
+/// AC-5: `sha256:<hex>` fingerprint of a DER-encoded certificate.
+pub fn certificate_fingerprint(der: &[u8]) -> String {
+    let digest = Sha256::digest(der);
+    let mut out = String::with_capacity(7 + digest.len() * 2);
+    out.push_str("sha256:");
+    for byte in digest {
+        out.push_str(&format!("{:02x}", byte));
+    }
+    out
+}
+
+/// Who may perform actions.
+#[derive(Clone, Default)]
+pub struct ActionAuth {
+    token: Option<String>,
+    allowed_clients: AllowedClients,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+enum AllowedClients {
+    #[default]
+    None,
+    Any,
+    Listed(Vec<String>),
+}
+
+// Never print the token (AC-4).
+impl std::fmt::Debug for ActionAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActionAuth")
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("allowed_clients", &self.allowed_clients)
+            .finish()
+    }
+}
+
+impl ActionAuth {
+    /// Build a policy from an optional token and an allowed-clients
+    /// list in the `MESH_ADMIN_ACTION_ALLOWED_CLIENTS` format.
+    pub fn new(token: Option<String>, allowed_clients: &str) -> Self {
+        let allowed_clients = match allowed_clients.trim() {
+            "" => AllowedClients::None,
+            "*" => AllowedClients::Any,
+            list => AllowedClients::Listed(
+                list.split(',')
+                    .map(|s| s.trim().to_ascii_lowercase())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+            ),
+        };
+        Self {
+            token: token.filter(|t| !t.is_empty()),
+            allowed_clients,
+        }
+    }
+
+    /// Build the policy from global config, reading the token file
+    /// if one is configured.
+    pub fn from_config() -> anyhow::Result<Self> {
+        let token_file =
+            hyperactor_config::global::get_cloned(crate::config::MESH_ADMIN_ACTION_TOKEN_FILE);
+        let token = if token_file.is_empty() {
+            None
+        } else {
+            let contents = std::fs::read_to_string(&token_file).map_err(|e| {
+                anyhow::anyhow!(
+                    "cannot read mesh admin action token file {}: {}",
+                    token_file,
+                    e
+                )
+            })?;
+            let token = contents.trim().to_string();
+            if token.is_empty() {
+                anyhow::bail!("mesh admin action token file {} is empty", token_file);
+            }
+            Some(token)
+        };
+        Ok(Self::new(
+            token,
+            &hyperactor_config::global::get_cloned(
+                crate::config::MESH_ADMIN_ACTION_ALLOWED_CLIENTS,
+            ),
+        ))
+    }
+
+    /// Whether any authentication method is configured (AC-2).
+    pub fn enabled(&self) -> bool {
+        self.token.is_some() || self.allowed_clients != AllowedClients::None
+    }
+
+    /// Authorize a request, returning the principal to audit.
+    ///
+    /// A bearer token, when present, is decisive: a wrong token is
+    /// rejected even if the connection also carries an allowed
+    /// certificate.
+    pub fn authorize(
+        &self,
+        headers: &HeaderMap,
+        identity: Option<&ClientIdentity>,
+    ) -> Result<String, ApiError> {
+        if !self.enabled() {
+            return Err(ApiError::forbidden(
+                "mesh admin actions are disabled; configure \
+                 HYPERACTOR_MESH_ADMIN_ACTION_TOKEN_FILE or \
+                 HYPERACTOR_MESH_ADMIN_ACTION_ALLOWED_CLIENTS",
+            ));
+        }
+
+        if let Some(value) = headers.get(AUTHORIZATION) {
+            let presented = value
+                .to_str()
+                .ok()
+                .and_then(|v| v.strip_prefix("Bearer "))
+                .map(str::trim)
+                .ok_or_else(|| ApiError::unauthorized("malformed Authorization header"))?;
+            return match &self.token {
+                Some(token) if constant_time_eq(presented.as_bytes(), token.as_bytes()) => {
+                    Ok("token".to_string())
+                }
+                _ => Err(ApiError::unauthorized("invalid bearer token")),
+            };
+        }
+
+        let Some(certificate) = identity.and_then(|id| id.certificate.as_ref()) else {
+            return Err(ApiError::unauthorized(
+                "actions require a bearer token or an mTLS client certificate",
+            ));
+        };
+        let allowed = match &self.allowed_clients {
+            AllowedClients::None => false,
+            AllowedClients::Any => true,
+            AllowedClients::Listed(list) => list.iter().any(|c| c == certificate),
+        };
+        if allowed {
+            Ok(format!("mtls:{}", certificate))
+        } else {
+            Err(ApiError::forbidden(format!(
+                "client {} is not allowed to perform actions",
+                certificate
+            )))
+        }
+    }
+}
+
+/// AC-4: compare without short-circuiting on the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Bounded, in-memory record of action requests (AC-3).
+#[derive(Debug)]
+pub struct ActionAuditLog {
+    inner: Mutex<AuditInner>,
+}
+
+#[derive(Debug)]
+struct AuditInner {
+    next_id: u64,
+    capacity: usize,
+    retained: VecDeque<ActionAuditEntry>,
+}
+
+/// Fields of an audit entry supplied by the caller; the log assigns
+/// the id and timestamp.
+#[derive(Debug, Clone)]
+pub struct AuditRecord<'a> {
+    pub action: AdminActionKind,
+    pub target: &'a str,
+    pub principal: Option<&'a str>,
+    pub identity: Option<&'a ClientIdentity>,
+    pub reason: Option<&'a str>,
+    pub outcome: ActionOutcome,
+    pub error: Option<&'a str>,
+}
+
+impl ActionAuditLog {
+    /// A log retaining at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(AuditInner {
+                next_id: 1,
+                capacity: capacity.max(1),
+                retained: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Record one request, log it, and return the stored entry.
+    pub fn record(&self, record: AuditRecord<'_>) -> ActionAuditEntry {
+        let mut inner = self.inner.lock().unwrap();
+        let entry = ActionAuditEntry {
+            id: inner.next_id,
+            at: humantime::format_rfc3339_millis(SystemTime::now()).to_string(),
+            action: record.action,
+            target: record.target.to_string(),
+            principal: record.principal.map(str::to_string),
+            remote_addr: record
+                .identity
+                .and_then(|id| id.remote_addr)
+                .map(|addr| addr.to_string()),
+            reason: record.reason.map(str::to_string),
+            outcome: record.outcome,
+            error: record.error.map(str::to_string),
+        };
+        inner.next_id += 1;
+        if inner.retained.len() == inner.capacity {
+            inner.retained.pop_front();
+        }
+        inner.retained.push_back(entry.clone());
+        drop(inner);
+
+        tracing::info!(
+            name = "MeshAdminAudit",
+            audit_id = entry.id,
+            action = %entry.action,
+            target = %entry.target,
+            principal = entry.principal.as_deref().unwrap_or("-"),
+            remote_addr = entry.remote_addr.as_deref().unwrap_or("-"),
+            reason = entry.reason.as_deref().unwrap_or("-"),
+            outcome = entry.outcome.as_str(),
+            error = entry.error.as_deref().unwrap_or("-"),
+            "mesh admin action",
+        );
+        entry
+    }
+
+    /// Retained entries, oldest first.
+    pub fn entries(&self) -> Vec<ActionAuditEntry> {
+        self.inner
+            .lock()
+            .unwrap()
+            .retained
+            .iter()
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+        );
+        headers
+    }
+
+    fn mtls(certificate: &str) -> ClientIdentity {
+        ClientIdentity {
+            remote_addr: Some("127.0.0.1:4242".parse().unwrap()),
+            certificate: Some(certificate.to_string()),
+        }
+    }
+
+    // AC-2: nothing configured, nothing allowed.
+    #[test]
+    fn disabled_policy_forbids_everything() {
+        let auth = ActionAuth::new(None, "");
+        assert!(!auth.enabled());
+        let err = auth
+            .authorize(&bearer("x"), Some(&mtls("sha256:aa")))
+            .unwrap_err();
+        assert_eq!(err.code, "forbidden");
+    }
+
+    // AC-1: token checks.
+    #[test]
+    fn bearer_token_is_required_and_checked() {
+        let auth = ActionAuth::new(Some("s3cret".to_string()), "");
+        assert_eq!(auth.authorize(&bearer("s3cret"), None).unwrap(), "token");
+        assert_eq!(
+            auth.authorize(&bearer("wrong"), None).unwrap_err().code,
+            "unauthorized"
+        );
+        assert_eq!(
+            auth.authorize(&HeaderMap::new(), None).unwrap_err().code,
+            "unauthorized"
+        );
+        let mut basic = HeaderMap::new();
+        basic.insert(
+            AUTHORIZATION,
+            HeaderValue::from_static("Basic Zm9vOmJhcg=="),
+        );
+        assert_eq!(
+            auth.authorize(&basic, None).unwrap_err().code,
+            "unauthorized"
+        );
+    }
+
+    // AC-1 + AC-5: client certificate identities.
+    #[test]
+    fn mtls_identity_is_matched_against_allow_list() {
+        let auth = ActionAuth::new(None, "sha256:AA, sha256:bb");
+        assert_eq!(
+            auth.authorize(&HeaderMap::new(), Some(&mtls("sha256:aa")))
+                .unwrap(),
+            "mtls:sha256:aa"
+        );
+        assert_eq!(
+            auth.authorize(&HeaderMap::new(), Some(&mtls("sha256:cc")))
+                .unwrap_err()
+                .code,
+            "forbidden"
+        );
+        // A connection without a certificate is unauthenticated.
+        assert_eq!(
+            auth.authorize(&HeaderMap::new(), Some(&ClientIdentity::default()))
+                .unwrap_err()
+                .code,
+            "unauthorized"
+        );
+
+        let any = ActionAuth::new(None, "*");
+        assert!(
+            any.authorize(&HeaderMap::new(), Some(&mtls("sha256:cc")))
+                .is_ok()
+        );
+        // A presented token is decisive even with an allowed cert.
+        assert_eq!(
+            any.authorize(&bearer("nope"), Some(&mtls("sha256:cc")))
+                .unwrap_err()
+                .code,
+            "unauthorized"
+        );
+    }
+
+    #[test]
+    fn request_body_is_parsed_leniently() {
+        assert_eq!(ActionRequest::parse(b"").unwrap(), ActionRequest::default());
+        assert_eq!(
+            ActionRequest::parse(b"  \n").unwrap(),
+            ActionRequest::default()
+        );
+        assert_eq!(
+            ActionRequest::parse(br#"{"reason":"maintenance","shutdown":true}"#).unwrap(),
+            ActionRequest {
+                reason: Some("maintenance".to_string()),
+                shutdown: Some(true),
+            }
+        );
+        assert_eq!(
+            ActionRequest::parse(b"{not json").unwrap_err().code,
+            "bad_request"
+        );
+        assert_eq!(
+            ActionRequest::parse(br#"{"shutdown":"yes"}"#)
+                .unwrap_err()
+                .code,
+            "bad_request"
+        );
+    }
+
+    #[test]
+    fn fingerprint_is_sha256_hex_of_leaf() {
+        let identity = ClientIdentity::from_peer_certificates(
+            "127.0.0.1:1".parse().unwrap(),
+            Some(&[b"leaf".as_slice(), b"intermediate".as_slice()]),
+        );
+        assert_eq!(identity.certificate, Some(certificate_fingerprint(b"leaf")));
+        assert_eq!(
+            certificate_fingerprint(b""),
+            "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    // AC-3 + AC-4: entries are bounded, ordered, and carry no token.
+    #[test]
+    fn audit_log_is_bounded_and_ordered() {
+        let log = ActionAuditLog::new(2);
+        let identity = mtls("sha256:aa");
+        for target in ["a", "b", "c"] {
+            log.record(AuditRecord {
+                action: AdminActionKind::StopActor,
+                target,
+                principal: Some("token"),
+                identity: Some(&identity),
+                reason: None,
+                outcome: ActionOutcome::Accepted,
+                error: None,
+            });
+        }
+        let entries = log.entries();
+        assert_eq!(entries.iter().map(|e| e.id).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(entries[1].target, "c");
+        assert_eq!(entries[1].remote_addr.as_deref(), Some("127.0.0.1:4242"));
+        let debug = format!("{:?}", ActionAuth::new(Some("s3cret".to_string()), ""));
+        assert!(!debug.contains("s3cret"));
+    }
+}
@@ -17,6 +17,17 @@
 //! This module centralizes that logic so that every mesh-admin client
 //! (admin TUI, integration tests, future tooling) shares the same
 //! correct code path.
+//!
+//! It also provides [`MeshEventStream`], a client for the
+//! `GET /v1/events` server-sent event stream that decodes frames into
+//! [`MeshEvent`]s and reconnects with `Last-Event-ID` (EV-3 in
+//! [`crate::mesh_admin_events`]).
+
+use std::collections::VecDeque;
+
+use crate::mesh_admin::ApiErrorEnvelope;
+use crate::mesh_admin_events::EventGap;
+use crate::mesh_admin_events::MeshEvent;
 
 /// Configure TLS on a `reqwest::ClientBuilder` by adding a root CA,
 /// and optionally a client identity (cert + key) for mutual TLS.
@@ -114,3 +125,245 @@ pub fn add_tls(
 
     (builder, true)
 }
+
+/// One dispatched server-sent event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseFrame {
+    /// The `id:` field, if the frame carried one.
+    pub id: Option<String>,
+    /// The `event:` field; `None` means the default `message` type.
+    pub event: Option<String>,
+    /// The `data:` lines, joined with `\n`.
+    pub data: String,
+}
+
+/// Incremental `text/event-stream` parser.
+///
+/// Bytes are buffered until a full line is available, so frames and
+/// UTF-8 sequences may be split across chunks arbitrarily. Comment
+/// lines (keep-alives) are skipped; frames without data are not
+/// dispatched.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buf: Vec<u8>,
+    id: Option<String>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Feed a chunk of the response body; returns every frame it
+    /// completed.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseFrame> {
+        self.buf.extend_from_slice(chunk);
+        let mut frames = Vec::new();
+        while let Some(end) = self.buf.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buf.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches('\n').trim_end_matches('\r');
+            if line.is_empty() {
+                if let Some(frame) = self.dispatch() {
+                    frames.push(frame);
+                }
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+            match field {
+                "id" => self.id = Some(value.to_string()),
+                "event" => self.event = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                _ => {}
+            }
+        }
+        frames
+    }
+
+    fn dispatch(&mut self) -> Option<SseFrame> {
+        let id = self.id.take();
+        let event = self.event.take();
+        if self.data.is_empty() {
+            return None;
+        }
+        let data = std::mem::take(&mut self.data).join("\n");
+        Some(SseFrame { id, event, data })
+    }
+}
+
+/// Client for the mesh admin `GET /v1/events` stream.
+///
+/// [`next`](Self::next) yields events in id order. When it returns
+/// `Ok(None)` (the server closed the stream) or an error (e.g. the
+/// stream fell behind and was cut, EV-4), call
+/// [`reconnect`](Self::reconnect) to resume after the last event
+/// received. `reconnect` fails with [`EventGap`] when the server no
+/// longer retains that point; the caller must then re-fetch the
+/// tree and [`connect`](Self::connect) without a resume id.
+pub struct MeshEventStream {
+    client: reqwest::Client,
+    base_url: String,
+    response: reqwest::Response,
+    parser: SseParser,
+    pending: VecDeque<SseFrame>,
+    last_event_id: Option<u64>,
+}
+
+impl MeshEventStream {
+    /// Open the stream at `base_url` (e.g. `https://host:1729`).
+    ///
+    /// `after = None` delivers only events recorded from now on;
+    /// `Some(n)` replays retained events with id `> n` first.
+    pub async fn connect(
+        client: reqwest::Client,
+        base_url: impl Into<String>,
+        after: Option<u64>,
+    ) -> anyhow::Result<Self> {
+        let base_url = base_url.into();
+        let response = open_event_stream(&client, &base_url, after).await?;
+        Ok(Self {
+            client,
+            base_url,
+            response,
+            parser: SseParser::default(),
+            pending: VecDeque::new(),
+            last_event_id: after,
+        })
+    }
+
+    /// Id of the last event returned by `next` (or the resume id
+    /// passed to `connect` if none yet).
+    pub fn last_event_id(&self) -> Option<u64> {
+        self.last_event_id
+    }
+
+    /// Re-open the stream, resuming after [`last_event_id`](Self::last_event_id).
+    pub async fn reconnect(&mut self) -> anyhow::Result<()> {
+        self.response = open_event_stream(&self.client, &self.base_url, self.last_event_id).await?;
+        self.parser = SseParser::default();
+        self.pending.clear();
+        Ok(())
+    }
+
+    /// Next event, or `Ok(None)` once the server ends the stream.
+    pub async fn next(&mut self) -> anyhow::Result<Option<MeshEvent>> {
+        loop {
+            if let Some(frame) = self.pending.pop_front() {
+                if frame.event.as_deref() == Some("stream_error") {
+                    return Err(envelope_error(&frame.data));
+                }
+                let event: MeshEvent = serde_json::from_str(&frame.data)
+                    .map_err(|e| anyhow::anyhow!("malformed mesh event {:?}: {}", frame.data, e))?;
+                self.last_event_id = Some(event.id);
+                return Ok(Some(event));
+            }
+            match self.response.chunk().await? {
+                Some(chunk) => self.pending.extend(self.parser.feed(&chunk)),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Issue `GET /v1/events`, mapping an error envelope to an error
+/// (a typed [`EventGap`] for `event_gap`).
+async fn open_event_stream(
+    client: &reqwest::Client,
+    base_url: &str,
+    after: Option<u64>,
+) -> anyhow::Result<reqwest::Response> {
+    let url = format!("{}/v1/events", base_url.trim_end_matches('/'));
+    let mut request = client
+        .get(&url)
+        .header(reqwest::header::ACCEPT, "text/event-stream");
+    if let Some(after) = after {
+        request = request.header("Last-Event-ID", after.to_string());
+    }
+    let response = request.send().await?;
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    Err(envelope_error(&body).context(format!("GET {} failed with {}", url, status)))
+}
+
+/// Decode an `ApiErrorEnvelope` body into an error. `event_gap`
+/// envelopes carrying resume bounds become [`EventGap`].
+fn envelope_error(body: &str) -> anyhow::Error {
+    let Ok(ApiErrorEnvelope { error }) = serde_json::from_str::<ApiErrorEnvelope>(body) else {
+        return anyhow::anyhow!("unexpected error response: {}", body);
+    };
+    if error.code == "event_gap"
+        && let Some(details) = &error.details
+        && let Ok(gap) = serde_json::from_value::<EventGapDetails>(details.clone())
+    {
+        return anyhow::Error::new(EventGap {
+            requested: gap.requested,
+            oldest: gap.oldest,
+            latest: gap.latest,
+        });
+    }
+    anyhow::anyhow!("{}: {}", error.code, error.message)
+}
+
+/// `details` of a pre-stream `event_gap` envelope.
+#[derive(serde::Deserialize)]
+struct EventGapDetails {
+    requested: u64,
+    oldest: u64,
+    latest: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_parser_handles_split_chunks() {
+        let mut parser = SseParser::default();
+        let mut frames = parser.feed(b": keep-alive\n\nid: 7\nevent: mesh_");
+        assert!(frames.is_empty());
+        frames.extend(parser.feed(b"event\r\ndata: {\"a\":\ndata:1}\r\n\r\nda"));
+        frames.extend(parser.feed(b"ta: x\n\n"));
+        assert_eq!(
+            frames,
+            vec![
+                SseFrame {
+                    id: Some("7".to_string()),
+                    event: Some("mesh_event".to_string()),
+                    data: "{\"a\":\n1}".to_string(),
+                },
+                SseFrame {
+                    id: None,
+                    event: None,
+                    data: "x".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_envelope_error_decodes_event_gap() {
+        let body = r#"{"error":{"code":"event_gap","message":"gone","details":{"requested":1,"oldest":5,"latest":9}}}"#;
+        let err = envelope_error(body);
+        assert_eq!(
+            err.downcast_ref::<EventGap>(),
+            Some(&EventGap {
+                requested: 1,
+                oldest: 5,
+                latest: 9,
+            })
+        );
+
+        let lagged =
+            r#"{"error":{"code":"event_gap","message":"fell behind","details":{"missed":3}}}"#;
+        let err = envelope_error(lagged);
+        assert!(err.downcast_ref::<EventGap>().is_none());
+        assert_eq!(err.to_string(), "event_gap: fell behind");
+    }
+}
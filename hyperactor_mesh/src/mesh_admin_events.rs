@@ -0,0 +1,463 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Event log behind the mesh admin `GET /v1/events` stream.
+//!
+//! `MeshAdminAgent` records topology and status changes it learns
+//! about from the supervision machinery — the lifecycle streams of
+//! the proc agents it watches (see
+//! [`crate::mesh_admin::watch_proc_mesh`]) and `MeshFailure`s from
+//! the mesh controllers it watches (see
+//! [`crate::mesh_admin::watch_actor_mesh`]) — into an [`EventLog`]. The HTTP bridge serves the log as a server-sent
+//! event stream: each [`MeshEvent`] is one SSE frame whose `id:`
+//! field is the event id, so a reconnecting client can resume with
+//! the standard `Last-Event-ID` header.
+//!
+//! Starts are reported for procs whose agent the admin watches and
+//! for the actors those agents spawn. Nodes the admin does not watch
+//! (meshes spawned before the admin, system actors) only show up by
+//! walking the tree via `GET /v1/{reference}`.
+//!
+//! ## Event-stream invariants (EV-*)
+//!
+//! - **EV-1 (monotonic ids):** Event ids start at 1 and increase by
+//!   one per recorded event for the lifetime of the admin agent. The
+//!   SSE `id:` field of a frame equals `MeshEvent::id`.
+//! - **EV-2 (bounded retention):** The log retains at most
+//!   `MESH_ADMIN_EVENT_BUFFER_SIZE` events; recording past capacity
+//!   evicts the oldest.
+//! - **EV-3 (gap-free resume):** A subscription resuming after id
+//!   `n` yields every event with id `> n` exactly once and in order:
+//!   the retained backlog, then live events. The backlog snapshot and
+//!   the live receiver are taken under the same lock, so no event
+//!   falls between them.
+//! - **EV-4 (gaps are explicit):** If an event after `n` has already
+//!   been evicted, or `n` is newer than anything this log has issued
+//!   (e.g. the admin restarted), resumption fails with [`EventGap`]
+//!   (`event_gap`, HTTP 410) instead of silently skipping. A live
+//!   subscriber that falls behind the broadcast buffer receives a
+//!   final `event_gap` error frame and the stream ends.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+use hyperactor::actor::ActorStatus;
+use hyperactor::supervision::ActorSupervisionEvent;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::proc_agent::PROC_AGENT_ACTOR_NAME;
+use crate::supervision::MeshFailure;
+
+/// What a [`MeshEvent`] records.
+///
+/// - `actor_stopped` / `actor_failed`: an actor reached a terminal
+///   status; `reference` is its `ActorId`.
+/// - `proc_stopped` / `proc_failed`: the agent of a proc reached a
+///   terminal status, taking the proc with it; `reference` is the
+///   `ProcId`.
+/// - `mesh_failed`: a mesh controller reported a supervision failure
+///   for some or all ranks of an actor mesh.
+/// - `actor_started`: a watched proc agent spawned an actor;
+///   `reference` is its `ActorId`.
+/// - `proc_started`: a watched proc is up; `reference` is the
+///   `ProcId`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    schemars::JsonSchema
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MeshEventKind {
+    ActorStopped,
+    ActorFailed,
+    ProcStopped,
+    ProcFailed,
+    MeshFailed,
+    ActorStarted,
+    ProcStarted,
+}
+
+impl MeshEventKind {
+    /// Wire name of the kind, as serialized.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ActorStopped => "actor_stopped",
+            Self::ActorFailed => "actor_failed",
+            Self::ProcStopped => "proc_stopped",
+            Self::ProcFailed => "proc_failed",
+            Self::MeshFailed => "mesh_failed",
+            Self::ActorStarted => "actor_started",
+            Self::ProcStarted => "proc_started",
+        }
+    }
+}
+
+/// One entry of the mesh admin event stream (`GET /v1/events`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct MeshEvent {
+    /// Monotonic event id, also sent as the SSE `id:` field (EV-1).
+    pub id: u64,
+    /// When the underlying event occurred (ISO 8601 timestamp string).
+    pub at: String,
+    /// What changed.
+    pub kind: MeshEventKind,
+    /// Reference string of the affected node, fetchable via
+    /// `GET /v1/{reference}`.
+    pub reference: String,
+    /// Name of the actor mesh the event was reported for, if any.
+    pub mesh: Option<String>,
+    /// Ranks of `mesh` the event applies to. Empty means the whole
+    /// mesh, or that the event did not come from a mesh controller.
+    pub ranks: Vec<usize>,
+    /// Human-readable status (e.g. the failure reason).
+    pub status: String,
+}
+
+/// Resumption point is not covered by the log (EV-4).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "cannot resume after event {requested}: log holds events {oldest}..={latest}; \
+     re-fetch the tree and subscribe without a resume id"
+)]
+pub struct EventGap {
+    /// The id the client asked to resume after.
+    pub requested: u64,
+    /// Oldest id still retained (`latest + 1` when the log is empty).
+    pub oldest: u64,
+    /// Newest id issued so far (0 before the first event).
+    pub latest: u64,
+}
+
+/// Retained backlog plus live receiver returned by
+/// [`EventLog::subscribe`].
+#[derive(Debug)]
+pub struct EventSubscription {
+    /// Retained events after the resumption point, oldest first.
+    pub backlog: Vec<MeshEvent>,
+    /// Events recorded after the subscription was taken.
+    pub live: broadcast::Receiver<MeshEvent>,
+}
+
+#[derive(Debug)]
+struct EventLogInner {
+    next_id: u64,
+    retained: VecDeque<MeshEvent>,
+    live: broadcast::Sender<MeshEvent>,
+}
+
+/// Bounded, resumable log of [`MeshEvent`]s shared between the
+/// admin agent (writer) and its HTTP bridge (readers).
+#[derive(Debug)]
+pub struct EventLog {
+    capacity: usize,
+    inner: Mutex<EventLogInner>,
+}
+
+impl EventLog {
+    /// Create a log retaining at most `capacity` events (EV-2). Live
+    /// subscribers may lag by up to `capacity` events before their
+    /// stream is cut (EV-4).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let (live, _) = broadcast::channel(capacity);
+        Self {
+            capacity,
+            inner: Mutex::new(EventLogInner {
+                next_id: 1,
+                retained: VecDeque::with_capacity(capacity),
+                live,
+            }),
+        }
+    }
+
+    /// Lock the log. The state is updated with single assignments
+    /// that cannot panic midway, so a lock poisoned by a panicking
+    /// reader or writer still guards a consistent log.
+    fn lock(&self) -> MutexGuard<'_, EventLogInner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Id of the newest recorded event, or 0 if none.
+    pub fn latest_id(&self) -> u64 {
+        self.lock().next_id - 1
+    }
+
+    /// Assign the next id to an event and publish it.
+    fn push(
+        &self,
+        at: std::time::SystemTime,
+        kind: MeshEventKind,
+        reference: String,
+        mesh: Option<String>,
+        ranks: Vec<usize>,
+        status: String,
+    ) -> MeshEvent {
+        let mut inner = self.lock();
+        let event = MeshEvent {
+            id: inner.next_id,
+            at: humantime::format_rfc3339_millis(at).to_string(),
+            kind,
+            reference,
+            mesh,
+            ranks,
+            status,
+        };
+        inner.next_id += 1;
+        if inner.retained.len() == self.capacity {
+            inner.retained.pop_front();
+        }
+        inner.retained.push_back(event.clone());
+        // No receivers is fine: nobody is streaming right now.
+        let _ = inner.live.send(event.clone());
+        event
+    }
+
+    /// Record an actor start (`Created`) or terminal status. Events
+    /// for a proc's agent are recorded as proc events. Other statuses
+    /// are ignored.
+    pub fn record_supervision(&self, event: &ActorSupervisionEvent) -> Option<MeshEvent> {
+        let status = &event.actor_status;
+        let is_proc = event.actor_id.name() == PROC_AGENT_ACTOR_NAME;
+        let kind = match (status, is_proc) {
+            (ActorStatus::Created, false) => MeshEventKind::ActorStarted,
+            (ActorStatus::Created, true) => MeshEventKind::ProcStarted,
+            (s, false) if s.is_failed() => MeshEventKind::ActorFailed,
+            (s, true) if s.is_failed() => MeshEventKind::ProcFailed,
+            (s, false) if s.is_terminal() => MeshEventKind::ActorStopped,
+            (s, true) if s.is_terminal() => MeshEventKind::ProcStopped,
+            _ => return None,
+        };
+        let reference = if is_proc {
+            event.actor_id.proc_id().to_string()
+        } else {
+            event.actor_id.to_string()
+        };
+        Some(self.push(
+            event.occurred_at,
+            kind,
+            reference,
+            None,
+            Vec::new(),
+            status.to_string(),
+        ))
+    }
+
+    /// Record a mesh-level supervision failure.
+    pub fn record_mesh_failure(&self, failure: &MeshFailure) -> MeshEvent {
+        self.push(
+            failure.event.occurred_at,
+            MeshEventKind::MeshFailed,
+            failure.event.actor_id.to_string(),
+            failure.actor_mesh_name.clone(),
+            failure.crashed_ranks.clone(),
+            failure.event.actor_status.to_string(),
+        )
+    }
+
+    /// Subscribe to the log (EV-3).
+    ///
+    /// With `after = None` the backlog is empty and only events
+    /// recorded from now on are delivered. With `after = Some(n)`
+    /// the backlog holds every retained event with id `> n`; `n = 0`
+    /// replays the whole retained log.
+    pub fn subscribe(&self, after: Option<u64>) -> Result<EventSubscription, EventGap> {
+        let inner = self.lock();
+        let live = inner.live.subscribe();
+        let Some(after) = after else {
+            return Ok(EventSubscription {
+                backlog: Vec::new(),
+                live,
+            });
+        };
+        let latest = inner.next_id - 1;
+        let oldest = inner.retained.front().map_or(inner.next_id, |e| e.id);
+        // EV-4: `after + 1` must still be retained (or not issued
+        // yet), and `after` must be an id this log has issued.
+        if after > latest || after + 1 < oldest {
+            return Err(EventGap {
+                requested: after,
+                oldest,
+                latest,
+            });
+        }
+        let backlog = inner
+            .retained
+            .iter()
+            .filter(|e| e.id > after)
+            .cloned()
+            .collect();
+        Ok(EventSubscription { backlog, live })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperactor::testing::ids::test_actor_id;
+
+    use super::*;
+
+    fn stopped(actor: &str) -> ActorSupervisionEvent {
+        ActorSupervisionEvent::new(
+            test_actor_id("proc", actor),
+            None,
+            ActorStatus::Stopped("done".to_string()),
+            None,
+        )
+    }
+
+    fn ids(events: &[MeshEvent]) -> Vec<u64> {
+        events.iter().map(|e| e.id).collect()
+    }
+
+    // EV-1: ids start at 1 and increase by one.
+    #[test]
+    fn test_ids_are_monotonic() {
+        let log = EventLog::new(8);
+        assert_eq!(log.latest_id(), 0);
+        let a = log.record_supervision(&stopped("a")).unwrap();
+        let b = log.record_supervision(&stopped("b")).unwrap();
+        assert_eq!((a.id, b.id), (1, 2));
+        assert_eq!(log.latest_id(), 2);
+    }
+
+    #[test]
+    fn test_classifies_supervision_events() {
+        let log = EventLog::new(8);
+        let actor = log.record_supervision(&stopped("worker")).unwrap();
+        assert_eq!(actor.kind, MeshEventKind::ActorStopped);
+        assert_eq!(actor.reference, test_actor_id("proc", "worker").to_string());
+
+        let failed = ActorSupervisionEvent::new(
+            test_actor_id("proc", PROC_AGENT_ACTOR_NAME),
+            None,
+            ActorStatus::generic_failure("boom"),
+            None,
+        );
+        let proc = log.record_supervision(&failed).unwrap();
+        assert_eq!(proc.kind, MeshEventKind::ProcFailed);
+        assert_eq!(
+            proc.reference,
+            test_actor_id("proc", PROC_AGENT_ACTOR_NAME)
+                .proc_id()
+                .to_string()
+        );
+        assert!(proc.status.contains("boom"));
+
+        let idle = ActorSupervisionEvent::new(
+            test_actor_id("proc", "worker"),
+            None,
+            ActorStatus::Idle,
+            None,
+        );
+        assert!(log.record_supervision(&idle).is_none());
+        assert_eq!(log.latest_id(), 2);
+
+        let created = |actor: &str| {
+            ActorSupervisionEvent::new(
+                test_actor_id("proc", actor),
+                None,
+                ActorStatus::Created,
+                None,
+            )
+        };
+        let started = log.record_supervision(&created("worker")).unwrap();
+        assert_eq!(started.kind, MeshEventKind::ActorStarted);
+        assert_eq!(
+            started.reference,
+            test_actor_id("proc", "worker").to_string()
+        );
+        let proc_started = log
+            .record_supervision(&created(PROC_AGENT_ACTOR_NAME))
+            .unwrap();
+        assert_eq!(proc_started.kind, MeshEventKind::ProcStarted);
+        assert_eq!(proc_started.reference, proc.reference);
+    }
+
+    // A panic while the lock is held does not wedge the log.
+    #[test]
+    fn test_survives_poisoned_lock() {
+        let log = std::sync::Arc::new(EventLog::new(8));
+        log.record_supervision(&stopped("a"));
+        let poisoner = log.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.inner.lock().unwrap();
+            panic!("poison the event log");
+        })
+        .join();
+        assert!(log.inner.is_poisoned());
+        assert_eq!(log.record_supervision(&stopped("b")).unwrap().id, 2);
+        assert_eq!(ids(&log.subscribe(Some(0)).unwrap().backlog), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_records_mesh_failure() {
+        let log = EventLog::new(8);
+        let failure = MeshFailure {
+            actor_mesh_name: Some("trainers".to_string()),
+            event: stopped("trainer"),
+            crashed_ranks: vec![1, 3],
+        };
+        let event = log.record_mesh_failure(&failure);
+        assert_eq!(event.kind, MeshEventKind::MeshFailed);
+        assert_eq!(event.mesh.as_deref(), Some("trainers"));
+        assert_eq!(event.ranks, vec![1, 3]);
+    }
+
+    // EV-2 and EV-3: resumption replays exactly the retained suffix,
+    // then continues with live events.
+    #[tokio::test]
+    async fn test_resume_replays_backlog_then_live() {
+        let log = EventLog::new(3);
+        for name in ["a", "b", "c", "d"] {
+            log.record_supervision(&stopped(name));
+        }
+        let mut sub = log.subscribe(Some(2)).unwrap();
+        assert_eq!(ids(&sub.backlog), vec![3, 4]);
+        log.record_supervision(&stopped("e"));
+        assert_eq!(sub.live.recv().await.unwrap().id, 5);
+
+        let again = log.subscribe(Some(2)).unwrap();
+        assert_eq!(ids(&again.backlog), vec![3, 4, 5]);
+
+        let fresh = log.subscribe(None).unwrap();
+        assert!(fresh.backlog.is_empty());
+    }
+
+    // EV-4: evicted or never-issued resume points are gaps.
+    #[test]
+    fn test_resume_gap() {
+        let log = EventLog::new(2);
+        for name in ["a", "b", "c"] {
+            log.record_supervision(&stopped(name));
+        }
+        assert_eq!(
+            log.subscribe(Some(0)).unwrap_err(),
+            EventGap {
+                requested: 0,
+                oldest: 2,
+                latest: 3,
+            }
+        );
+        assert!(log.subscribe(Some(1)).is_ok());
+        assert!(log.subscribe(Some(3)).unwrap().backlog.is_empty());
+        assert_eq!(log.subscribe(Some(4)).unwrap_err().latest, 3);
+
+        let empty = EventLog::new(2);
+        assert!(empty.subscribe(Some(0)).unwrap().backlog.is_empty());
+        assert!(empty.subscribe(Some(1)).is_err());
+    }
+}
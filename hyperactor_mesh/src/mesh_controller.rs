@@ -892,6 +892,17 @@ impl<A: Referable> Handler<CheckState> for ActorMeshController<A> {
                 self.self_check_state_message(cx)?;
                 return Ok(());
             }
+            // A proc being replaced by a rolling restart reports
+            // Initializing (RR-2 in the host agent); its actors cannot
+            // be queried until the replacement has recreated them.
+            if proc_states
+                .iter()
+                .any(|(_rank, state)| state.status == resource::Status::Initializing)
+            {
+                send_heartbeat(cx, &self.health_state);
+                self.self_check_state_message(cx)?;
+                return Ok(());
+            }
         }
 
         // Now that we know the proc mesh is alive, check for actor state changes.
@@ -16,6 +16,7 @@
 
 use std::collections::HashMap;
 use std::mem::take;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::RwLock;
@@ -65,6 +66,8 @@ use crate::pyspy::PySpyProfile;
 use crate::pyspy::PySpyProfileWorker;
 use crate::pyspy::PySpyWorker;
 use crate::resource;
+use crate::runtime_config;
+use crate::runtime_config::SetRuntimeConfig;
 
 /// Actor name used when spawning the proc agent on user procs.
 pub const PROC_AGENT_ACTOR_NAME: &str = "proc_agent";
@@ -223,6 +226,9 @@ impl State {
 #[derive(Debug)]
 struct ActorInstanceState {
     create_rank: usize,
+    /// The spec the actor was created from, kept so that the actor can
+    /// be recreated on a replacement proc (see [`GetActorSpecs`]).
+    spec: ActorSpec,
     spawn: Result<hyperactor_reference::ActorId, anyhow::Error>,
     /// True once a stop signal has been sent. This does *not* mean the actor
     /// has reached a terminal state — that is determined by observing
@@ -384,6 +390,11 @@ struct SelfCheck {}
         PySpyDump,
         PySpyProfile,
         ConfigDump,
+        SetRuntimeConfig,
+        HealthCheck,
+        GetActorSpecs,
+        Barrier,
+        WatchSupervision { cast = true },
     ]
 )]
 pub struct ProcAgent {
@@ -392,6 +403,8 @@ pub struct ProcAgent {
     state: State,
     /// Actors created and tracked through the resource behavior.
     actor_states: HashMap<Name, ActorInstanceState>,
+    /// Names in `actor_states`, in creation order.
+    spawn_order: Vec<Name>,
     /// If true, and supervisor is None, record supervision events to be reported
     /// to owning actors later.
     record_supervision_events: bool,
@@ -408,6 +421,13 @@ pub struct ProcAgent {
     stopping_all: bool,
     /// If set, check for expired actors whose keepalive has lapsed.
     mesh_orphan_timeout: Option<Duration>,
+    /// True while a user-defined health probe is running. Checks
+    /// arriving meanwhile are answered as failures without starting
+    /// another probe.
+    health_probe_in_flight: bool,
+    /// Ports subscribed with [`WatchSupervision`]. They receive the
+    /// actor lifecycle of this proc, best-effort.
+    supervision_watchers: Vec<hyperactor_reference::PortRef<ActorSupervisionEvent>>,
 }
 
 impl ProcAgent {
@@ -423,6 +443,7 @@ impl ProcAgent {
             remote: Remote::collect(),
             state: State::UnconfiguredV0 { sender },
             actor_states: HashMap::new(),
+            spawn_order: Vec::new(),
             record_supervision_events: false,
             introspect_dirty: false,
             shutdown_tx: None,
@@ -430,6 +451,8 @@ impl ProcAgent {
             // v0 procs don't have an owner they can check for, so they should
             // never try to kill the children.
             mesh_orphan_timeout: None,
+            health_probe_in_flight: false,
+            supervision_watchers: Vec::new(),
         };
         let handle = proc.spawn::<Self>("mesh", agent)?;
         Ok((proc, handle))
@@ -453,11 +476,14 @@ impl ProcAgent {
             remote: Remote::collect(),
             state: State::V1,
             actor_states: HashMap::new(),
+            spawn_order: Vec::new(),
             record_supervision_events: true,
             introspect_dirty: false,
             shutdown_tx,
             stopping_all: false,
             mesh_orphan_timeout: orphan_timeout,
+            health_probe_in_flight: false,
+            supervision_watchers: Vec::new(),
         };
         proc.spawn::<Self>(PROC_AGENT_ACTOR_NAME, agent)
     }
@@ -469,12 +495,48 @@ impl ProcAgent {
         self.actor_states.values().all(|state| state.is_terminal())
     }
 
+    /// Forward `event` to every [`WatchSupervision`] watcher.
+    fn notify_watchers(&self, cx: &impl hyperactor::context::Actor, event: &ActorSupervisionEvent) {
+        for watcher in &self.supervision_watchers {
+            if let Err(e) = watcher.send(cx, event.clone()) {
+                tracing::debug!(
+                    "failed to forward supervision event to watcher {}: {}",
+                    watcher.port_id(),
+                    e,
+                );
+            }
+        }
+    }
+
+    /// Report a lifecycle `status` of `actor_id` to the watchers.
+    fn notify_lifecycle(
+        &self,
+        cx: &impl hyperactor::context::Actor,
+        actor_id: hyperactor_reference::ActorId,
+        status: hyperactor::actor::ActorStatus,
+    ) {
+        if !self.supervision_watchers.is_empty() {
+            self.notify_watchers(
+                cx,
+                &ActorSupervisionEvent::new(actor_id, None, status, None),
+            );
+        }
+    }
+
     /// Trigger process shutdown. Flushes the forwarder first so that
     /// supervision events reach their destinations, then sends through
     /// `shutdown_tx` if available, otherwise calls `process::exit`.
-    async fn shutdown(&mut self) {
+    async fn shutdown(&mut self, cx: &Context<Self>) {
         let has_errors = self.actor_states.values().any(|state| state.has_errors());
         let exit_code = if has_errors { 1 } else { 0 };
+        // The proc goes down with this agent; watchers see it as the
+        // agent stopping.
+        let reason = format!("proc exiting with code {}", exit_code);
+        self.notify_lifecycle(
+            cx,
+            cx.self_id().clone(),
+            hyperactor::actor::ActorStatus::Stopped(reason),
+        );
 
         let flush_timeout =
             hyperactor_config::global::get(hyperactor::config::FORWARDER_FLUSH_TIMEOUT);
@@ -623,6 +685,10 @@ impl Actor for ProcAgent {
             // test_query_child_proc_returns_live_children.
             if let hyperactor::reference::Reference::Proc(proc_id) = child_ref {
                 if proc_id == proc.proc_id() {
+                    // Read the generation before any state it covers,
+                    // so that an unchanged generation implies every
+                    // actor payload resolved afterwards is current.
+                    let generation = proc.introspect_generation();
                     let (mut children, mut system_children) = collect_live_children(&proc);
 
                     let mut stopped_children: Vec<crate::introspect::NodeRef> = Vec::new();
@@ -680,6 +746,7 @@ impl Actor for ProcAgent {
                     );
                     attrs.set(crate::introspect::IS_POISONED, is_poisoned);
                     attrs.set(crate::introspect::FAILED_ACTOR_COUNT, failed_actor_count);
+                    attrs.set(crate::introspect::INTROSPECT_GENERATION, generation);
 
                     // PD-*: include proc debug stats in QueryChild
                     // to prevent resolution drift from the publish path.
@@ -925,9 +992,10 @@ impl Handler<ActorSupervisionEvent> for ProcAgent {
             // If StopAll was requested, check whether all actors have now
             // reached terminal state. If so, shut down the process.
             if self.stopping_all && self.all_actors_terminal() {
-                self.shutdown().await;
+                self.shutdown(cx).await;
             }
         }
+        self.notify_watchers(cx, &event);
         if let Some(supervisor) = self.state.supervisor() {
             supervisor.send(cx, event)?;
         } else if !self.record_supervision_events && event.is_error() {
@@ -996,6 +1064,203 @@ impl Handler<ConfigDump> for ProcAgent {
     }
 }
 
+#[async_trait]
+impl Handler<SetRuntimeConfig> for ProcAgent {
+    async fn handle(
+        &mut self,
+        cx: &Context<Self>,
+        message: SetRuntimeConfig,
+    ) -> Result<(), anyhow::Error> {
+        let ack = runtime_config::apply(cx.self_id().proc_id(), message.attrs);
+        // Reply is best-effort: the host agent may have timed out.
+        let _ = message.result.send(cx, ack);
+        Ok(())
+    }
+}
+
+/// A user-defined health probe, run by [`ProcAgent`] on every
+/// [`HealthCheck`]. Resolves to `Err(reason)` when the proc is
+/// unhealthy, e.g. when a Python probe cannot acquire the GIL.
+pub type HealthProbe =
+    Arc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> + Send + Sync>;
+
+/// The health probe installed in this process, if any.
+static HEALTH_PROBE: RwLock<Option<HealthProbe>> = RwLock::new(None);
+
+/// Install the health probe for this process, replacing any previous
+/// one. Without a probe, a [`HealthCheck`] only shows that the
+/// `ProcAgent` message loop is responsive.
+pub fn set_health_probe(probe: HealthProbe) {
+    *HEALTH_PROBE.write().expect("health probe lock poisoned") = Some(probe);
+}
+
+/// Liveness probe sent by the host agent. The proc agent answers on
+/// `reply` with a [`HealthCheckResult`] carrying the same `probe_id`;
+/// an unanswered probe is a failure.
+#[derive(Debug, Serialize, Deserialize, Named, Handler, HandleClient, RefClient)]
+pub struct HealthCheck {
+    /// Caller-chosen id echoed in the result.
+    pub probe_id: u64,
+    #[reply]
+    pub reply: hyperactor_reference::PortRef<HealthCheckResult>,
+}
+wirevalue::register_type!(HealthCheck);
+
+/// Outcome of a [`HealthCheck`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Named)]
+pub struct HealthCheckResult {
+    /// The probe being answered.
+    pub probe_id: u64,
+    /// `Err(reason)` if the user-defined probe failed.
+    pub result: Result<(), String>,
+}
+wirevalue::register_type!(HealthCheckResult);
+
+/// Completion of a user-defined probe, sent to self by the task
+/// running it. Local only.
+struct HealthProbeDone {
+    reply: hyperactor_reference::PortRef<HealthCheckResult>,
+    result: HealthCheckResult,
+}
+
+#[async_trait]
+impl Handler<HealthCheck> for ProcAgent {
+    async fn handle(&mut self, cx: &Context<Self>, message: HealthCheck) -> anyhow::Result<()> {
+        let HealthCheck { probe_id, reply } = message;
+        let probe = HEALTH_PROBE
+            .read()
+            .expect("health probe lock poisoned")
+            .clone();
+        let Some(probe) = probe else {
+            // Replies are best-effort: the prober may have timed out.
+            let _ = reply.send(
+                cx,
+                HealthCheckResult {
+                    probe_id,
+                    result: Ok(()),
+                },
+            );
+            return Ok(());
+        };
+        // A probe that has not finished since the previous check is
+        // itself the answer; never stack a second one behind it.
+        if self.health_probe_in_flight {
+            let _ = reply.send(
+                cx,
+                HealthCheckResult {
+                    probe_id,
+                    result: Err("previous health probe has not completed".to_string()),
+                },
+            );
+            return Ok(());
+        }
+        self.health_probe_in_flight = true;
+        // Run the probe off the message loop so that a wedged probe
+        // cannot stall the agent, and bound it so that the task ends
+        // even if the probe never resolves.
+        let timeout = hyperactor_config::global::get(crate::config::MESH_PROC_HEALTH_PROBE_TIMEOUT);
+        let port = cx.port::<HealthProbeDone>();
+        tokio::spawn(async move {
+            let result = match tokio::time::timeout(timeout, probe()).await {
+                Ok(result) => result,
+                Err(_) => Err(format!(
+                    "health probe did not complete within {}",
+                    humantime::format_duration(timeout)
+                )),
+            };
+            let _ = port.send(
+                Instance::<()>::self_client(),
+                HealthProbeDone {
+                    reply,
+                    result: HealthCheckResult { probe_id, result },
+                },
+            );
+        });
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Handler<HealthProbeDone> for ProcAgent {
+    async fn handle(&mut self, cx: &Context<Self>, message: HealthProbeDone) -> anyhow::Result<()> {
+        self.health_probe_in_flight = false;
+        let _ = message.reply.send(cx, message.result);
+        Ok(())
+    }
+}
+
+/// Ordering barrier. The proc agent answers on `reply` with the same
+/// `id` once it has handled every message sent to it before the
+/// barrier. Unlike a [`HealthCheck`], it runs no user-defined probe,
+/// so it is answered promptly even while that probe is wedged.
+#[derive(Debug, Serialize, Deserialize, Named, Handler, HandleClient, RefClient)]
+pub struct Barrier {
+    /// Caller-chosen id echoed in the reply.
+    pub id: u64,
+    #[reply]
+    pub reply: hyperactor_reference::PortRef<BarrierReached>,
+}
+wirevalue::register_type!(Barrier);
+
+/// Reply to a [`Barrier`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Named)]
+pub struct BarrierReached {
+    /// The barrier being answered.
+    pub id: u64,
+}
+wirevalue::register_type!(BarrierReached);
+
+#[async_trait]
+impl Handler<Barrier> for ProcAgent {
+    async fn handle(&mut self, cx: &Context<Self>, message: Barrier) -> anyhow::Result<()> {
+        // Reply is best-effort: the host agent may be gone.
+        let _ = message.reply.send(cx, BarrierReached { id: message.id });
+        Ok(())
+    }
+}
+
+/// Subscribe `watcher` to the actor lifecycle of this proc, as
+/// `ActorSupervisionEvent`s: every supervision event the agent
+/// handles, a `Created` event for each actor it spawns afterwards,
+/// and `Stopped` for the agent itself when the proc shuts down. The
+/// agent also reports itself as `Created` on subscription. Delivery
+/// is best-effort; messages to a watcher that has gone away are
+/// dropped. Used to feed the mesh admin event stream.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Named, Bind, Unbind)]
+pub struct WatchSupervision(pub hyperactor_reference::PortRef<ActorSupervisionEvent>);
+wirevalue::register_type!(WatchSupervision);
+
+#[async_trait]
+impl Handler<WatchSupervision> for ProcAgent {
+    async fn handle(
+        &mut self,
+        cx: &Context<Self>,
+        message: WatchSupervision,
+    ) -> anyhow::Result<()> {
+        let WatchSupervision(mut watcher) = message;
+        if self.supervision_watchers.contains(&watcher) {
+            return Ok(());
+        }
+        watcher.return_undeliverable(false);
+        // Only the new watcher learns that this proc is up.
+        let started = ActorSupervisionEvent::new(
+            cx.self_id().clone(),
+            None,
+            hyperactor::actor::ActorStatus::Created,
+            None,
+        );
+        if let Err(e) = watcher.send(cx, started) {
+            tracing::debug!(
+                "failed to send start event to watcher {}: {}",
+                watcher.port_id(),
+                e,
+            );
+        }
+        self.supervision_watchers.push(watcher);
+        Ok(())
+    }
+}
+
 // Implement the resource behavior for managing actors:
 
 /// Actor spec.
@@ -1043,6 +1308,7 @@ impl Handler<resource::CreateOrUpdate<ActorSpec>> for ProcAgent {
                         "Cannot spawn new actors on mesh with supervision events"
                     )),
                     create_rank,
+                    spec: create_or_update.spec,
                     stop_initiated: false,
                     supervision_event: None,
                     subscribers: Vec::new(),
@@ -1051,17 +1317,19 @@ impl Handler<resource::CreateOrUpdate<ActorSpec>> for ProcAgent {
                     pending_wait_status: Vec::new(),
                 },
             );
+            self.spawn_order.push(create_or_update.name);
             return Ok(());
         }
 
         let ActorSpec {
             actor_type,
             params_data,
-        } = create_or_update.spec;
+        } = create_or_update.spec.clone();
         self.actor_states.insert(
             create_or_update.name.clone(),
             ActorInstanceState {
                 create_rank,
+                spec: create_or_update.spec,
                 spawn: self
                     .remote
                     .gspawn(
@@ -1080,12 +1348,65 @@ impl Handler<resource::CreateOrUpdate<ActorSpec>> for ProcAgent {
                 pending_wait_status: Vec::new(),
             },
         );
+        if let Ok(actor_id) = &self.actor_states[&create_or_update.name].spawn {
+            self.notify_lifecycle(
+                cx,
+                actor_id.clone(),
+                hyperactor::actor::ActorStatus::Created,
+            );
+        }
+        self.spawn_order.push(create_or_update.name);
 
         self.publish_introspect_properties(cx);
         Ok(())
     }
 }
 
+/// A live actor as recorded by [`ProcAgent`], with the spec needed to
+/// recreate it under the same name on another proc.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Named)]
+pub struct RecordedActor {
+    /// The actor's resource name.
+    pub name: Name,
+    /// The rank of the proc that created the actor.
+    pub create_rank: usize,
+    /// The spec the actor was created from.
+    pub spec: ActorSpec,
+}
+wirevalue::register_type!(RecordedActor);
+
+/// Query the actors that this proc is running, in creation order.
+/// Actors that failed to spawn, or that are stopping or stopped, are
+/// omitted. Used by rolling restarts to repopulate a replacement proc.
+#[derive(Debug, Serialize, Deserialize, Named, Handler, HandleClient, RefClient)]
+pub struct GetActorSpecs {
+    #[reply]
+    pub reply: hyperactor_reference::OncePortRef<Vec<RecordedActor>>,
+}
+wirevalue::register_type!(GetActorSpecs);
+
+#[async_trait]
+impl Handler<GetActorSpecs> for ProcAgent {
+    async fn handle(&mut self, cx: &Context<Self>, message: GetActorSpecs) -> anyhow::Result<()> {
+        let actors = self
+            .spawn_order
+            .iter()
+            .filter_map(|name| {
+                let state = self.actor_states.get(name)?;
+                (state.spawn.is_ok() && !state.stop_initiated && !state.is_terminal()).then(|| {
+                    RecordedActor {
+                        name: name.clone(),
+                        create_rank: state.create_rank,
+                        spec: state.spec.clone(),
+                    }
+                })
+            })
+            .collect();
+        let _ = message.reply.send(cx, actors);
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Handler<resource::Stop> for ProcAgent {
     async fn handle(&mut self, cx: &Context<Self>, message: resource::Stop) -> anyhow::Result<()> {
@@ -1118,7 +1439,7 @@ impl Handler<resource::Stop> for ProcAgent {
 impl Handler<resource::StopAll> for ProcAgent {
     async fn handle(
         &mut self,
-        _cx: &Context<Self>,
+        cx: &Context<Self>,
         message: resource::StopAll,
     ) -> anyhow::Result<()> {
         self.stopping_all = true;
@@ -1142,7 +1463,7 @@ impl Handler<resource::StopAll> for ProcAgent {
 
         // If there are no actors to stop, shut down immediately.
         if self.all_actors_terminal() {
-            self.shutdown().await;
+            self.shutdown(cx).await;
         }
 
         Ok(())
@@ -2207,4 +2528,97 @@ mod tests {
         // Unblock the actor.
         gate.notify_one();
     }
+
+    // A wedged user probe is bounded by MESH_PROC_HEALTH_PROBE_TIMEOUT,
+    // and checks arriving while it runs fail immediately instead of
+    // stacking further probes behind it.
+    #[tokio::test]
+    async fn test_health_probe_single_flight_and_timeout() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::atomic::Ordering;
+
+        use hyperactor::Proc;
+        use hyperactor::actor::ActorStatus;
+        use hyperactor::channel::ChannelTransport;
+
+        let config = hyperactor_config::global::lock();
+        let _timeout = config.override_key(
+            crate::config::MESH_PROC_HEALTH_PROBE_TIMEOUT,
+            Duration::from_millis(500),
+        );
+
+        let started = Arc::new(AtomicUsize::new(0));
+        let started_clone = started.clone();
+        set_health_probe(Arc::new(
+            move || -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> {
+                started_clone.fetch_add(1, Ordering::SeqCst);
+                Box::pin(std::future::pending())
+            },
+        ));
+
+        let proc = Proc::direct(ChannelTransport::Unix.any(), "test_proc".to_string()).unwrap();
+        let agent_handle = ProcAgent::boot_v1(proc.clone(), None).unwrap();
+        agent_handle
+            .status()
+            .wait_for(|s| matches!(s, ActorStatus::Idle))
+            .await
+            .unwrap();
+
+        let client_proc = Proc::direct(ChannelTransport::Unix.any(), "client".to_string()).unwrap();
+        let (client, _client_handle) = client_proc.instance("client").unwrap();
+        let (reply, mut rx) = client.open_port::<HealthCheckResult>();
+        let reply = reply.bind();
+
+        agent_handle
+            .send(
+                &client,
+                HealthCheck {
+                    probe_id: 1,
+                    reply: reply.clone(),
+                },
+            )
+            .unwrap();
+        agent_handle
+            .send(
+                &client,
+                HealthCheck {
+                    probe_id: 2,
+                    reply: reply.clone(),
+                },
+            )
+            .unwrap();
+
+        // The second check is answered first, without running the probe.
+        let second = rx.recv().await.unwrap();
+        assert_eq!(second.probe_id, 2);
+        assert!(second.result.is_err());
+        assert_eq!(started.load(Ordering::SeqCst), 1);
+
+        let first = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+            .await
+            .expect("wedged probe was not timed out")
+            .unwrap();
+        assert_eq!(first.probe_id, 1);
+        assert!(
+            first
+                .result
+                .as_ref()
+                .unwrap_err()
+                .contains("did not complete"),
+            "{:?}",
+            first.result
+        );
+
+        // Once the timed-out probe is retired, the next check runs a
+        // fresh one.
+        agent_handle
+            .send(&client, HealthCheck { probe_id: 3, reply })
+            .unwrap();
+        let third = rx.recv().await.unwrap();
+        assert_eq!(third.probe_id, 3);
+        assert!(third.result.is_err());
+        assert_eq!(started.load(Ordering::SeqCst), 2);
+
+        *HEALTH_PROBE.write().unwrap() = None;
+    }
 }
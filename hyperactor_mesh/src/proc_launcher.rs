@@ -39,6 +39,22 @@
 //! available to the manager for forwarding and tail collection
 //! (`Captured`), inherited (`Inherited`), or handled entirely by the
 //! launcher (`ManagedByLauncher`).
+//!
+//! ## Resource limits
+//!
+//! [`ProcResources`] carries per-proc memory, CPU-quota and pids
+//! limits. The native launcher applies them through a leaf cgroup
+//! under a delegated cgroup v2 root (see `cgroup.rs`); the systemd
+//! launcher sets the equivalent unit properties. Launchers that
+//! enforce limits also report [`ProcResourceUsage`] and classify
+//! kernel OOM kills as [`ProcExitKind::OomKilled`].
+//!
+//! ## Sandboxing
+//!
+//! On Linux, the sandbox launcher (`sandbox.rs`) is the native
+//! launcher with each proc started in fresh user, mount, PID and
+//! optionally network namespaces: the host filesystem read-only, a
+//! private `/tmp`, and a per-proc writable workspace.
 #![allow(dead_code, unused_imports)] // Temporary
 
 use std::collections::HashMap;
@@ -61,6 +77,11 @@ use crate::bootstrap::BootstrapCommand;
 mod native;
 pub(crate) use native::NativeProcLauncher;
 
+mod cgroup;
+#[cfg(target_os = "linux")]
+mod sandbox;
+#[cfg(target_os = "linux")]
+pub(crate) use sandbox::SandboxProcLauncher;
 #[cfg(target_os = "linux")]
 mod systemd;
 #[cfg(target_os = "linux")]
@@ -119,6 +140,10 @@ pub enum ProcExitKind {
     Signaled { signal: i32, core_dumped: bool },
     /// Launcher level failure (spawn/wait/plumbing failed).
     Failed { reason: String },
+    /// Killed by the kernel OOM killer after exceeding the memory
+    /// limit of its cgroup. `memory_max` is the limit that applied,
+    /// if the launcher set one.
+    OomKilled { memory_max: Option<u64> },
 }
 
 /// Terminal status of a proc.
@@ -191,6 +216,57 @@ impl From<HashMap<String, String>> for ProcBind {
     }
 }
 
+/// Per-process resource limits, enforced through cgroup v2.
+///
+/// `None` leaves the corresponding limit unset (inherited from the
+/// parent cgroup). The native launcher writes these to the proc's
+/// leaf cgroup (`memory.max`, `memory.high`, `cpu.max`,
+/// `pids.max`); the systemd launcher maps them to `MemoryMax=`,
+/// `MemoryHigh=`, `CPUQuota=` and `TasksMax=`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Named)]
+pub struct ProcResources {
+    /// Hard memory limit in bytes. A proc that exceeds it is killed
+    /// by the kernel OOM killer, without affecting its host.
+    pub memory_max: Option<u64>,
+    /// Memory throttling threshold in bytes. Above it the kernel
+    /// reclaims aggressively instead of killing.
+    pub memory_high: Option<u64>,
+    /// CPU quota as a percentage of one CPU (e.g. `250` = 2.5 CPUs).
+    pub cpu_quota_percent: Option<u64>,
+    /// Maximum number of tasks (processes and threads).
+    pub pids_max: Option<u64>,
+}
+wirevalue::register_type!(ProcResources);
+
+impl ProcResources {
+    /// True if no limit is set.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Resource usage of a launched proc, as accounted by its cgroup.
+///
+/// Fields are `None` when the launcher cannot read them (no cgroup,
+/// controller not enabled, or the kernel lacks the counter). Values
+/// are never fabricated.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Named)]
+pub struct ProcResourceUsage {
+    /// Current memory usage in bytes (`memory.current`).
+    pub memory_current: Option<u64>,
+    /// Peak memory usage in bytes (`memory.peak`).
+    pub memory_peak: Option<u64>,
+    /// Total CPU time consumed, in microseconds (`cpu.stat`
+    /// `usage_usec`).
+    pub cpu_usage_usec: Option<u64>,
+    /// Current number of tasks (`pids.current`).
+    pub pids_current: Option<u64>,
+    /// Number of processes in the proc killed by the OOM killer
+    /// (`memory.events` `oom_kill`).
+    pub oom_kills: Option<u64>,
+}
+wirevalue::register_type!(ProcResourceUsage);
+
 /// Per-launch policy computed by the manager and handed to the
 /// launcher.
 ///
@@ -265,6 +341,13 @@ pub struct LaunchOptions {
     /// unit properties for systemd). Launchers that do not support
     /// it may ignore this field.
     pub proc_bind: Option<ProcBind>,
+
+    /// Optional resource limits for this proc.
+    ///
+    /// Unlike `proc_bind`, limits are not advisory: a launcher that
+    /// cannot enforce a requested limit must fail the launch rather
+    /// than run the proc unconstrained.
+    pub resources: Option<ProcResources>,
 }
 
 /// Format a human-readable process name for diagnostics and logs.
@@ -385,4 +468,16 @@ pub trait ProcLauncher: Send + Sync + 'static {
     /// should not be treated as an error unless the backend cannot
     /// determine state.
     async fn kill(&self, proc_id: &hyperactor_reference::ProcId) -> Result<(), ProcLauncherError>;
+
+    /// Current resource usage of a running proc.
+    ///
+    /// Returns `None` if the proc is unknown, has exited, or the
+    /// backend does not account resources per proc. The default
+    /// implementation reports nothing.
+    async fn resource_usage(
+        &self,
+        _proc_id: &hyperactor_reference::ProcId,
+    ) -> Option<ProcResourceUsage> {
+        None
+    }
 }
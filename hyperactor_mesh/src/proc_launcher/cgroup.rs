@@ -0,0 +1,275 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! cgroup v2 placement and accounting for natively launched procs.
+//!
+//! The native launcher gives each proc its own leaf cgroup under a
+//! delegated root directory ([`MESH_PROC_CGROUP_ROOT`]). The root
+//! must be writable by the host process and must not contain
+//! processes itself (cgroup v2's "no internal processes" rule), e.g.
+//! a subdirectory of a systemd unit started with `Delegate=yes`.
+//!
+//! Invariants:
+//!
+//! - **CG-1 (explicit-root):** Procs are placed in cgroups only when
+//!   a root is configured. Requesting limits without a root fails
+//!   the launch; a proc never runs silently unconstrained.
+//! - **CG-2 (join-before-exec):** The child joins its leaf in
+//!   `pre_exec`, before `exec`, so none of the proc's code runs
+//!   outside its limits. Descendants inherit the cgroup.
+//! - **CG-3 (oom-attribution):** An exit is classified as an OOM
+//!   kill only when the leader died of `SIGKILL` and the leaf's
+//!   `memory.events` reports `oom_kill > 0`.
+//! - **CG-4 (best-effort-cleanup):** After exit, stragglers are
+//!   killed through `cgroup.kill` and the leaf is removed. Cleanup
+//!   failures are logged, never reported as launch or exit errors.
+//!
+//! [`MESH_PROC_CGROUP_ROOT`]: crate::config::MESH_PROC_CGROUP_ROOT
+
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::fmt::Write as _;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::path::PathBuf;
+
+use hyperactor::reference as hyperactor_reference;
+
+use crate::proc_launcher::ProcResourceUsage;
+use crate::proc_launcher::ProcResources;
+
+/// `cpu.max` period, in microseconds (the kernel default).
+const CPU_PERIOD_USEC: u64 = 100_000;
+
+/// Controllers enabled in the root's `cgroup.subtree_control`.
+const CONTROLLERS: [&str; 3] = ["memory", "cpu", "pids"];
+
+/// A proc's leaf cgroup.
+#[derive(Debug)]
+pub(crate) struct ProcCgroup {
+    /// Leaf directory.
+    path: PathBuf,
+    /// `<path>/cgroup.procs`, pre-encoded so the child can join
+    /// without allocating after `fork` (CG-2).
+    procs_file: CString,
+}
+
+impl ProcCgroup {
+    /// Create the leaf for `proc_id` under `root` and write
+    /// `resources` to it.
+    ///
+    /// A stale leaf left by an earlier launcher for the same proc is
+    /// removed first. On error the leaf is removed again.
+    pub(crate) fn create(
+        root: &Path,
+        proc_id: &hyperactor_reference::ProcId,
+        resources: &ProcResources,
+    ) -> std::io::Result<Self> {
+        enable_controllers(root);
+        let path = root.join(leaf_name(proc_id));
+        if path.exists() {
+            let _ = std::fs::remove_dir(&path);
+        }
+        std::fs::create_dir(&path)?;
+        let procs_file = CString::new(path.join("cgroup.procs").as_os_str().as_bytes())
+            .map_err(std::io::Error::other)?;
+        let cgroup = Self { path, procs_file };
+        if let Err(e) = cgroup.apply(resources) {
+            cgroup.remove();
+            return Err(e);
+        }
+        Ok(cgroup)
+    }
+
+    fn apply(&self, resources: &ProcResources) -> std::io::Result<()> {
+        for (file, value) in limit_writes(resources) {
+            std::fs::write(self.path.join(file), value).map_err(|e| {
+                std::io::Error::new(e.kind(), format!("{}/{file}: {e}", self.path.display()))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// `cgroup.procs` of the leaf, for [`join_in_child`].
+    pub(crate) fn procs_file(&self) -> &CStr {
+        &self.procs_file
+    }
+
+    /// Read the leaf's accounting files.
+    pub(crate) fn usage(&self) -> ProcResourceUsage {
+        let read = |file: &str| std::fs::read_to_string(self.path.join(file)).ok();
+        ProcResourceUsage {
+            memory_current: read("memory.current").and_then(|s| parse_value(&s)),
+            memory_peak: read("memory.peak").and_then(|s| parse_value(&s)),
+            cpu_usage_usec: read("cpu.stat").and_then(|s| parse_keyed(&s, "usage_usec")),
+            pids_current: read("pids.current").and_then(|s| parse_value(&s)),
+            oom_kills: read("memory.events").and_then(|s| parse_keyed(&s, "oom_kill")),
+        }
+    }
+
+    /// Kill any process left in the leaf and remove it (CG-4).
+    pub(crate) fn remove(&self) {
+        // `cgroup.kill` exists since Linux 5.14; older kernels leave
+        // stragglers to the process-group signal.
+        let _ = std::fs::write(self.path.join("cgroup.kill"), "1");
+        // Removal fails while killed tasks are still exiting.
+        for _ in 0..50 {
+            match std::fs::remove_dir(&self.path) {
+                Ok(()) => return,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+                Err(_) => std::thread::sleep(std::time::Duration::from_millis(10)),
+            }
+        }
+        tracing::warn!(path = %self.path.display(), "failed to remove proc cgroup");
+    }
+}
+
+/// Move the calling process into the cgroup whose `cgroup.procs` is
+/// `procs_file`.
+///
+/// Async-signal-safe: only `open`, `write` and `close`, no
+/// allocation. Intended for `pre_exec` (CG-2).
+pub(crate) fn join_in_child(procs_file: &CStr) -> std::io::Result<()> {
+    // SAFETY: `procs_file` is a valid NUL-terminated path; the
+    // buffer passed to `write` is a static one-byte string. Writing
+    // "0" to `cgroup.procs` moves the writer itself.
+    unsafe {
+        let fd = libc::open(procs_file.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let rc = libc::write(fd, b"0".as_ptr().cast(), 1);
+        let err = std::io::Error::last_os_error();
+        libc::close(fd);
+        if rc != 1 {
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+/// Best-effort: enable the accounting controllers for children of
+/// `root`. A controller that is not available leaves its limit
+/// files missing, which [`ProcCgroup::create`] then reports.
+fn enable_controllers(root: &Path) {
+    let control = root.join("cgroup.subtree_control");
+    for controller in CONTROLLERS {
+        if let Err(e) = std::fs::write(&control, format!("+{controller}")) {
+            tracing::debug!(
+                root = %root.display(),
+                controller,
+                error = %e,
+                "could not enable cgroup controller",
+            );
+        }
+    }
+}
+
+/// Per-proc directory name for `proc_id` (cgroup leaves, sandbox
+/// workspaces): the hex-encoded id, so it is stable, unique, and
+/// free of `/`.
+pub(crate) fn leaf_name(proc_id: &hyperactor_reference::ProcId) -> String {
+    let s = proc_id.to_string();
+    let mut name = String::with_capacity(6 + s.len() * 2);
+    name.push_str("proc-");
+    for b in s.as_bytes() {
+        write!(&mut name, "{:02x}", b).unwrap();
+    }
+    name
+}
+
+/// Control-file writes that apply `resources`.
+fn limit_writes(resources: &ProcResources) -> Vec<(&'static str, String)> {
+    let mut writes = Vec::new();
+    if let Some(bytes) = resources.memory_high {
+        writes.push(("memory.high", bytes.to_string()));
+    }
+    if let Some(bytes) = resources.memory_max {
+        writes.push(("memory.max", bytes.to_string()));
+        // Kill the whole proc on OOM, not just the largest task, so
+        // the proc never limps on with a dead worker.
+        writes.push(("memory.oom.group", "1".to_string()));
+    }
+    if let Some(percent) = resources.cpu_quota_percent {
+        let quota = percent * CPU_PERIOD_USEC / 100;
+        writes.push(("cpu.max", format!("{quota} {CPU_PERIOD_USEC}")));
+    }
+    if let Some(max) = resources.pids_max {
+        writes.push(("pids.max", max.to_string()));
+    }
+    writes
+}
+
+/// Parse a single-value control file. `max` (no limit) yields
+/// `None`.
+fn parse_value(contents: &str) -> Option<u64> {
+    contents.trim().parse().ok()
+}
+
+/// Parse `key` from a flat-keyed control file (`key value` lines).
+fn parse_keyed(contents: &str, key: &str) -> Option<u64> {
+    contents.lines().find_map(|line| {
+        let (k, v) = line.split_once(' ')?;
+        if k == key {
+            v.trim().parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperactor::testing::ids::test_proc_id;
+
+    use super::*;
+
+    #[test]
+    fn limit_writes_cover_requested_limits_only() {
+        assert!(limit_writes(&ProcResources::default()).is_empty());
+
+        let writes = limit_writes(&ProcResources {
+            memory_max: Some(4 << 30),
+            memory_high: None,
+            cpu_quota_percent: Some(250),
+            pids_max: Some(512),
+        });
+        assert_eq!(
+            writes,
+            vec![
+                ("memory.max", (4u64 << 30).to_string()),
+                ("memory.oom.group", "1".to_string()),
+                ("cpu.max", "250000 100000".to_string()),
+                ("pids.max", "512".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_control_files() {
+        assert_eq!(parse_value("1048576\n"), Some(1048576));
+        assert_eq!(parse_value("max\n"), None);
+
+        let events = "low 0\nhigh 12\nmax 3\noom 1\noom_kill 1\noom_group_kill 1\n";
+        assert_eq!(parse_keyed(events, "oom_kill"), Some(1));
+        assert_eq!(parse_keyed(events, "oom"), Some(1));
+        assert_eq!(parse_keyed(events, "missing"), None);
+
+        let stat = "usage_usec 52000\nuser_usec 40000\nsystem_usec 12000\n";
+        assert_eq!(parse_keyed(stat, "usage_usec"), Some(52000));
+    }
+
+    #[test]
+    fn leaf_name_is_stable_and_path_safe() {
+        let a = leaf_name(&test_proc_id("a"));
+        assert_eq!(a, leaf_name(&test_proc_id("a")));
+        assert_ne!(a, leaf_name(&test_proc_id("b")));
+        assert!(a.starts_with("proc-"));
+        assert!(a[5..].chars().all(|c| c.is_ascii_hexdigit()));
+    }
+}
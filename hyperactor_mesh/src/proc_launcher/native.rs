@@ -57,6 +57,14 @@
 //! manager can do that when it attaches log forwarding for captured
 //! pipes.
 //!
+//! ## Sandboxing
+//!
+//! On Linux, a launcher built with `NativeProcLauncher::sandboxed`
+//! additionally enters a namespace sandbox in `pre_exec`, after the
+//! process group and cgroup are set up (see the `sandbox` module).
+//! The spawned pid is then the sandbox supervisor, which mirrors the
+//! proc's exit status, so everything below applies unchanged.
+//!
 //! ## Termination semantics
 //!
 //! Termination is implemented with POSIX signals:
@@ -67,13 +75,23 @@
 //! A small best-effort PID registry is maintained to support
 //! signaling and to avoid leaking long-lived children if the launcher
 //! is dropped during teardown.
+//!
+//! ## Resource limits
+//!
+//! When [`MESH_PROC_CGROUP_ROOT`] is set, each proc runs in its own
+//! leaf cgroup under that root, with [`LaunchOptions::resources`]
+//! applied to it (see `cgroup.rs` for CG-*). The leaf backs
+//! [`ProcLauncher::resource_usage`] and lets the exit monitor tell
+//! an OOM kill from any other `SIGKILL`.
 
 #![allow(dead_code, unused_imports)] // Temporary.
 
 use std::collections::HashMap;
+use std::ffi::CString;
 use std::fmt;
 use std::os::unix::process::CommandExt;
 use std::os::unix::process::ExitStatusExt;
+use std::path::Path;
 use std::process::ExitStatus;
 use std::process::Stdio;
 use std::sync::Arc;
@@ -92,6 +110,7 @@ use crate::bootstrap::Bootstrap;
 use crate::bootstrap::BootstrapCommand;
 use crate::bootstrap::BootstrapProcConfig;
 use crate::bootstrap::PROCESS_NAME_ENV;
+use crate::config::MESH_PROC_CGROUP_ROOT;
 use crate::proc_launcher::LaunchOptions;
 use crate::proc_launcher::LaunchResult;
 use crate::proc_launcher::ProcBind;
@@ -99,8 +118,16 @@ use crate::proc_launcher::ProcExitKind;
 use crate::proc_launcher::ProcExitResult;
 use crate::proc_launcher::ProcLauncher;
 use crate::proc_launcher::ProcLauncherError;
+use crate::proc_launcher::ProcResourceUsage;
+use crate::proc_launcher::ProcResources;
 use crate::proc_launcher::StdioHandling;
+use crate::proc_launcher::cgroup;
+use crate::proc_launcher::cgroup::ProcCgroup;
 use crate::proc_launcher::format_process_name;
+#[cfg(target_os = "linux")]
+use crate::proc_launcher::sandbox::PROC_WORKSPACE_ENV;
+#[cfg(target_os = "linux")]
+use crate::proc_launcher::sandbox::Sandbox;
 
 /// Native OS process launcher.
 ///
@@ -129,6 +156,16 @@ pub(crate) struct NativeProcLauncher {
     /// Missing entries are treated as idempotent success (already
     /// exited / unknown).
     pid_table: Arc<Mutex<HashMap<hyperactor_reference::ProcId, u32>>>,
+
+    /// Leaf cgroups of running procs, when a cgroup root is
+    /// configured. Maintained like `pid_table`: inserted after spawn,
+    /// removed by the exit monitor.
+    cgroups: Arc<Mutex<HashMap<hyperactor_reference::ProcId, Arc<ProcCgroup>>>>,
+
+    /// Whether procs enter a namespace [`Sandbox`] before `exec`
+    /// (see [`SandboxProcLauncher`](super::SandboxProcLauncher)).
+    #[cfg(target_os = "linux")]
+    sandboxed: bool,
 }
 
 impl NativeProcLauncher {
@@ -145,7 +182,43 @@ impl NativeProcLauncher {
     pub fn new() -> Self {
         Self {
             pid_table: Arc::new(Mutex::new(HashMap::new())),
+            cgroups: Arc::new(Mutex::new(HashMap::new())),
+            #[cfg(target_os = "linux")]
+            sandboxed: false,
+        }
+    }
+
+    /// Create a native launcher whose procs each run in a fresh
+    /// namespace sandbox.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn sandboxed() -> Self {
+        Self {
+            sandboxed: true,
+            ..Self::new()
+        }
+    }
+
+    /// Create the proc's leaf cgroup if a cgroup root is configured.
+    ///
+    /// CG-1: requesting limits without a root is a launch error.
+    fn create_cgroup(
+        proc_id: &hyperactor_reference::ProcId,
+        resources: Option<&ProcResources>,
+    ) -> Result<Option<ProcCgroup>, ProcLauncherError> {
+        let root = hyperactor_config::global::get_cloned(MESH_PROC_CGROUP_ROOT);
+        if root.is_empty() {
+            return match resources {
+                Some(resources) => Err(ProcLauncherError::Launch(std::io::Error::other(format!(
+                    "resource limits {resources:?} requested but no cgroup root is \
+                         configured (HYPERACTOR_MESH_PROC_CGROUP_ROOT)"
+                )))),
+                None => Ok(None),
+            };
         }
+        let default = ProcResources::default();
+        ProcCgroup::create(Path::new(&root), proc_id, resources.unwrap_or(&default))
+            .map(Some)
+            .map_err(ProcLauncherError::Launch)
     }
 
     /// Send a POSIX signal to `pid`.
@@ -266,6 +339,22 @@ fn exit_kind_from_status(status: &ExitStatus) -> ProcExitKind {
     }
 }
 
+/// Reclassify a `SIGKILL` as an OOM kill if the proc's cgroup
+/// recorded one (CG-3).
+fn classify_oom(
+    kind: ProcExitKind,
+    oom_kills: Option<u64>,
+    memory_max: Option<u64>,
+) -> ProcExitKind {
+    match kind {
+        ProcExitKind::Signaled {
+            signal: libc::SIGKILL,
+            ..
+        } if oom_kills.unwrap_or(0) > 0 => ProcExitKind::OomKilled { memory_max },
+        kind => kind,
+    }
+}
+
 #[async_trait]
 impl ProcLauncher for NativeProcLauncher {
     /// Launch a bootstrap proc as a native OS child process.
@@ -323,6 +412,21 @@ impl ProcLauncher for NativeProcLauncher {
             None => opts.command.clone(),
         };
 
+        // Namespace sandbox plan, if sandboxed. Prepared before the
+        // cgroup so a failure here leaves nothing to clean up.
+        #[cfg(target_os = "linux")]
+        let sandbox = if self.sandboxed {
+            Some(Sandbox::prepare(proc_id, &opts).map_err(ProcLauncherError::Launch)?)
+        } else {
+            None
+        };
+
+        // Leaf cgroup with the requested limits, if configured.
+        let resources = opts.resources.as_ref().filter(|r| !r.is_empty());
+        let memory_max = resources.and_then(|r| r.memory_max);
+        let cgroup = Self::create_cgroup(proc_id, resources)?;
+        let procs_file: Option<CString> = cgroup.as_ref().map(|c| c.procs_file().to_owned());
+
         // New Tokio Command from BootstrapCommand (template)
         let mut cmd = command.new();
 
@@ -338,6 +442,11 @@ impl ProcLauncher for NativeProcLauncher {
             cmd.env(BOOTSTRAP_LOG_CHANNEL, addr.to_string());
         }
 
+        #[cfg(target_os = "linux")]
+        if let Some(workspace) = sandbox.as_ref().and_then(|s| s.workspace()) {
+            cmd.env(PROC_WORKSPACE_ENV, workspace);
+        }
+
         // Stdio behavior gated on 'want_stdio'.
         if opts.want_stdio {
             cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
@@ -351,18 +460,38 @@ impl ProcLauncher for NativeProcLauncher {
         // SAFETY: runs in the child between fork and exec. We must not
         // allocate or do anything complex here.
         unsafe {
-            cmd.pre_exec(|| {
+            cmd.pre_exec(move || {
                 // setpgid(0, 0) => make this process the leader of a new process group.
                 if libc::setpgid(0, 0) != 0 {
                     return Err(std::io::Error::last_os_error());
                 }
+                // CG-2: join the leaf cgroup before exec.
+                if let Some(procs_file) = &procs_file {
+                    cgroup::join_in_child(procs_file)?;
+                }
+                // Last, so the supervisor and the sandboxed proc
+                // share the process group and cgroup.
+                #[cfg(target_os = "linux")]
+                if let Some(sandbox) = &sandbox {
+                    sandbox.enter_in_child()?;
+                }
                 Ok(())
             });
         }
 
         let started_at = std::time::SystemTime::now();
 
-        let mut child = cmd.spawn().map_err(ProcLauncherError::Launch)?;
+        let mut child = match cmd.spawn() {
+            Ok(child) => child,
+            Err(e) => {
+                // `remove` may sleep while the leaf drains; keep it
+                // off the runtime (CG-4).
+                if let Some(cgroup) = cgroup {
+                    let _ = tokio::task::spawn_blocking(move || cgroup.remove()).await;
+                }
+                return Err(ProcLauncherError::Launch(e));
+            }
+        };
         let pid = child.id().expect("spawned child pid unavailable");
 
         // Record PID in current span and log spawn event.
@@ -374,6 +503,10 @@ impl ProcLauncher for NativeProcLauncher {
             let mut table = self.pid_table.lock().expect("pid_table mutex poisoned");
             table.insert(proc_id.clone(), pid);
         }
+        if let Some(cgroup) = cgroup {
+            let mut table = self.cgroups.lock().expect("cgroups mutex poisoned");
+            table.insert(proc_id.clone(), Arc::new(cgroup));
+        }
 
         // Extract stdio handles only if requested (only present if
         // piped).
@@ -388,6 +521,7 @@ impl ProcLauncher for NativeProcLauncher {
         // Terminal status channel.
         let (exit_tx, exit_rx) = oneshot::channel();
         let pid_table = Arc::clone(&self.pid_table);
+        let cgroups = Arc::clone(&self.cgroups);
 
         let proc_id = proc_id.clone();
         // Propagate the launch span into the exit monitor so that
@@ -411,6 +545,21 @@ impl ProcLauncher for NativeProcLauncher {
                     },
                 };
 
+                // CG-3: read the OOM counter before the leaf goes
+                // away, then clean it up off the runtime (CG-4).
+                let cgroup = cgroups
+                    .lock()
+                    .expect("cgroups mutex poisoned")
+                    .remove(&proc_id);
+                let kind = match cgroup {
+                    Some(cgroup) => {
+                        let oom_kills = cgroup.usage().oom_kills;
+                        tokio::task::spawn_blocking(move || cgroup.remove());
+                        classify_oom(kind, oom_kills, memory_max)
+                    }
+                    None => kind,
+                };
+
                 tracing::debug!(?kind, "exit_observed");
 
                 // Once the process has reached a terminal state (or we
@@ -580,6 +729,21 @@ impl ProcLauncher for NativeProcLauncher {
 
         Ok(())
     }
+
+    /// Read the proc's cgroup accounting.
+    ///
+    /// `None` unless the proc is running in a leaf cgroup, i.e. a
+    /// cgroup root is configured.
+    async fn resource_usage(
+        &self,
+        proc_id: &hyperactor_reference::ProcId,
+    ) -> Option<ProcResourceUsage> {
+        let cgroup = {
+            let table = self.cgroups.lock().expect("cgroups mutex poisoned");
+            table.get(proc_id).cloned()
+        }?;
+        Some(cgroup.usage())
+    }
 }
 
 impl fmt::Debug for NativeProcLauncher {
@@ -727,6 +891,7 @@ mod tests {
             tail_lines: 0,
             log_channel: Some(log_channel.clone()),
             proc_bind: None,
+            resources: None,
         };
 
         let lr = launcher.launch(&proc_id, opts).await.expect("launch");
@@ -815,6 +980,7 @@ mod tests {
                 tail_lines: 0,
                 log_channel: None,
                 proc_bind: None,
+                resources: None,
             };
             let lr = launcher.launch(&proc_id, opts).await.expect("launch");
             let (lines, stderr_bytes) = read_captured_lines(lr.stdio).await;
@@ -850,6 +1016,7 @@ mod tests {
                 tail_lines: 0,
                 log_channel: None,
                 proc_bind: None,
+                resources: None,
             };
             let lr = launcher.launch(&proc_id, opts).await.expect("launch");
 
@@ -888,6 +1055,7 @@ mod tests {
             tail_lines: 0,
             log_channel: None,
             proc_bind: None,
+            resources: None,
         };
 
         let lr = launcher.launch(&proc_id, opts).await.expect("launch");
@@ -927,6 +1095,7 @@ mod tests {
             tail_lines: 0,
             log_channel: None,
             proc_bind: None,
+            resources: None,
         };
 
         let lr = launcher.launch(&proc_id, opts).await.expect("launch");
@@ -1000,6 +1169,7 @@ mod tests {
             tail_lines: 0,
             log_channel: None,
             proc_bind: None,
+            resources: None,
         };
 
         let lr = launcher.launch(&proc_id, opts).await.expect("launch");
@@ -1051,6 +1221,67 @@ mod tests {
         }
     }
 
+    // CG-3: only a SIGKILL with a recorded OOM kill is reclassified.
+    #[test]
+    fn classify_oom_requires_sigkill_and_oom_event() {
+        let sigkill = ProcExitKind::Signaled {
+            signal: libc::SIGKILL,
+            core_dumped: false,
+        };
+        assert!(matches!(
+            classify_oom(sigkill.clone(), Some(1), Some(1 << 30)),
+            ProcExitKind::OomKilled {
+                memory_max: Some(limit)
+            } if limit == 1 << 30
+        ));
+        assert!(matches!(
+            classify_oom(sigkill.clone(), Some(0), None),
+            ProcExitKind::Signaled { .. }
+        ));
+        assert!(matches!(
+            classify_oom(sigkill, None, None),
+            ProcExitKind::Signaled { .. }
+        ));
+        let sigterm = ProcExitKind::Signaled {
+            signal: libc::SIGTERM,
+            core_dumped: false,
+        };
+        assert!(matches!(
+            classify_oom(sigterm, Some(1), None),
+            ProcExitKind::Signaled { .. }
+        ));
+        assert!(matches!(
+            classify_oom(ProcExitKind::Exited { code: 0 }, Some(1), None),
+            ProcExitKind::Exited { code: 0 }
+        ));
+    }
+
+    // CG-1: limits without a cgroup root fail the launch instead of
+    // running the proc unconstrained.
+    #[tokio::test]
+    async fn launch_with_limits_requires_cgroup_root() {
+        let launcher = NativeProcLauncher::new();
+        let bootstrap = Bootstrap::default();
+        let proc_id = hyperactor_reference::ProcId::with_name(any_unix_addr(), "limited");
+        let opts = LaunchOptions {
+            command: with_sh("exit 0"),
+            bootstrap_payload: bootstrap.to_env_safe_string().unwrap(),
+            process_name: proc_id.to_string(),
+            want_stdio: false,
+            tail_lines: 0,
+            log_channel: None,
+            proc_bind: None,
+            resources: Some(ProcResources {
+                memory_max: Some(1 << 30),
+                ..Default::default()
+            }),
+        };
+        let err = launcher.launch(&proc_id, opts).await.unwrap_err();
+        assert!(matches!(err, ProcLauncherError::Launch(_)), "{err}");
+        assert!(launcher.pid_table.lock().unwrap().is_empty());
+        assert_eq!(launcher.resource_usage(&proc_id).await, None);
+    }
+
     /// Verify that `NativeProcLauncher::Drop` kills child processes.
     ///
     /// This test exercises the critical cleanup path: when a launcher
@@ -1086,6 +1317,7 @@ while True:
             tail_lines: 0,
             log_channel: None,
             proc_bind: None,
+            resources: None,
         };
 
         // Keep stdout in outer scope so we can read after launcher
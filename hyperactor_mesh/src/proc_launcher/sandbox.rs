@@ -0,0 +1,758 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Namespace-sandboxed `ProcLauncher` (Linux).
+//!
+//! [`SandboxProcLauncher`] runs each proc like the native launcher,
+//! but inside fresh user, mount and PID namespaces (and optionally a
+//! network namespace), so procs of different tenants can share a
+//! host without seeing each other's files or processes. No container
+//! runtime is involved: supervision, signaling, stdio and cgroup
+//! placement are the native launcher's, and this module only adds
+//! what happens in the child between `fork` and `exec`.
+//!
+//! ## Process layout
+//!
+//! ```text
+//! host ── supervisor   launched pid, process-group leader
+//!           └── init   pid 1 of the proc's PID namespace
+//!                 └── proc   the exec'd bootstrap command
+//! ```
+//!
+//! - The *supervisor* stays in the host's PID namespace, so the
+//!   native launcher waits for and signals it as usual. It keeps all
+//!   signals blocked, waits for init, and then exits the way the proc
+//!   exited.
+//! - *init* sets up the filesystem view, forks the proc, reaps
+//!   orphans, and reports the proc's wait status to the supervisor
+//!   over a pipe. When init exits, the kernel kills whatever is left
+//!   in the namespace.
+//!
+//! ## Filesystem view
+//!
+//! - The host's mount tree is visible, read-only.
+//! - `/tmp` and `/dev/shm` are private tmpfs mounts.
+//! - The host's proc socket directory stays writable, so the proc can
+//!   serve its mailbox socket and dial its peers.
+//! - With [`MESH_PROC_SANDBOX_WORKSPACE_ROOT`] set, the proc gets a
+//!   writable workspace `<root>/proc-<hex id>`, which is also its
+//!   working directory and is exported in [`PROC_WORKSPACE_ENV`].
+//!   Sibling workspaces are hidden under a tmpfs. Workspaces outlive
+//!   their procs.
+//! - `/proc` is remounted for the new PID namespace.
+//!
+//! With [`MESH_PROC_SANDBOX_NETWORK`], the proc also gets a network
+//! namespace whose only interface is loopback.
+//!
+//! Requires unprivileged user namespaces and Linux >= 5.12
+//! (`open_tree`, `move_mount`, `mount_setattr`).
+//!
+//! Invariants:
+//!
+//! - **SB-1 (setup-before-exec):** The proc's code runs only after
+//!   every namespace and mount is in place. Any setup failure fails
+//!   the launch; a proc never runs partially sandboxed.
+//! - **SB-2 (exit-mirroring):** The supervisor exits with the proc's
+//!   exit code or dies of the proc's terminating signal, so `exit_rx`
+//!   reports what the native launcher would. Core dumps are not
+//!   reported.
+//! - **SB-3 (tree-teardown):** Signals to the process group reach the
+//!   proc; init ignores them and the supervisor defers them. When the
+//!   proc exits, or init or the supervisor is killed, every process
+//!   left in the namespace is killed.
+//! - **SB-4 (host-identity):** The proc keeps the host's uid and gid.
+//!   For a non-root host it holds no capabilities after `exec` and
+//!   cannot undo the sandbox mounts.
+//!
+//! [`MESH_PROC_SANDBOX_NETWORK`]: crate::config::MESH_PROC_SANDBOX_NETWORK
+//! [`MESH_PROC_SANDBOX_WORKSPACE_ROOT`]: crate::config::MESH_PROC_SANDBOX_WORKSPACE_ROOT
+
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use hyperactor::reference as hyperactor_reference;
+
+use crate::bootstrap::Bootstrap;
+use crate::bootstrap::MESH_BOOTSTRAP_ENABLE_PDEATHSIG;
+use crate::config::MESH_PROC_SANDBOX_NETWORK;
+use crate::config::MESH_PROC_SANDBOX_WORKSPACE_ROOT;
+use crate::proc_launcher::LaunchOptions;
+use crate::proc_launcher::LaunchResult;
+use crate::proc_launcher::NativeProcLauncher;
+use crate::proc_launcher::ProcLauncher;
+use crate::proc_launcher::ProcLauncherError;
+use crate::proc_launcher::ProcResourceUsage;
+use crate::proc_launcher::cgroup::leaf_name;
+
+/// Environment variable carrying the proc's workspace path, when it
+/// has one.
+pub(crate) const PROC_WORKSPACE_ENV: &str = "HYPERACTOR_MESH_PROC_WORKSPACE";
+
+// Mount API constants from <linux/mount.h>, not exported by libc.
+const OPEN_TREE_CLONE: libc::c_uint = 0x1;
+const MOVE_MOUNT_F_EMPTY_PATH: libc::c_uint = 0x4;
+const MOUNT_ATTR_RDONLY: u64 = 0x1;
+
+/// `struct mount_attr` for `mount_setattr(2)`.
+#[repr(C)]
+#[allow(dead_code)] // Read by the kernel.
+struct MountAttr {
+    attr_set: u64,
+    attr_clr: u64,
+    propagation: u64,
+    userns_fd: u64,
+}
+
+/// Proc launcher that runs each proc in its own namespaces.
+///
+/// A [`NativeProcLauncher`] that enters a [`Sandbox`] before `exec`;
+/// see the module docs for the process layout and filesystem view.
+#[derive(Debug)]
+pub(crate) struct SandboxProcLauncher {
+    native: NativeProcLauncher,
+}
+
+impl SandboxProcLauncher {
+    /// Create a new sandbox launcher. Sandbox settings are read from
+    /// the global config at each launch.
+    pub fn new() -> Self {
+        Self {
+            native: NativeProcLauncher::sandboxed(),
+        }
+    }
+}
+
+#[async_trait]
+impl ProcLauncher for SandboxProcLauncher {
+    /// Launch a proc in a fresh sandbox. Fails with
+    /// [`ProcLauncherError::Launch`] if the sandbox cannot be set up
+    /// (SB-1), e.g. when user namespaces are disabled.
+    async fn launch(
+        &self,
+        proc_id: &hyperactor_reference::ProcId,
+        opts: LaunchOptions,
+    ) -> Result<LaunchResult, ProcLauncherError> {
+        self.native.launch(proc_id, opts).await
+    }
+
+    async fn terminate(
+        &self,
+        proc_id: &hyperactor_reference::ProcId,
+        timeout: Duration,
+    ) -> Result<(), ProcLauncherError> {
+        self.native.terminate(proc_id, timeout).await
+    }
+
+    async fn kill(&self, proc_id: &hyperactor_reference::ProcId) -> Result<(), ProcLauncherError> {
+        self.native.kill(proc_id).await
+    }
+
+    async fn resource_usage(
+        &self,
+        proc_id: &hyperactor_reference::ProcId,
+    ) -> Option<ProcResourceUsage> {
+        self.native.resource_usage(proc_id).await
+    }
+}
+
+/// Sandbox plan for one launch, fully encoded before `fork` so the
+/// child never allocates.
+#[derive(Debug)]
+pub(crate) struct Sandbox {
+    /// `unshare(2)` flags.
+    flags: libc::c_int,
+    /// Contents of `/proc/self/uid_map`.
+    uid_map: Vec<u8>,
+    /// Contents of `/proc/self/gid_map`.
+    gid_map: Vec<u8>,
+    /// Host pid, if the supervisor should die with the host.
+    host_pid: Option<libc::pid_t>,
+    /// Whether `/dev/shm` exists and gets a private tmpfs.
+    dev_shm: bool,
+    /// Host socket directory, kept writable.
+    socket_dir: Option<CString>,
+    /// Workspace root, covered by a tmpfs.
+    workspace_root: Option<CString>,
+    /// The proc's workspace, kept writable.
+    workspace: Option<CString>,
+    /// Mount points to create after the private mounts, outermost
+    /// first.
+    mkdirs: Vec<CString>,
+}
+
+impl Sandbox {
+    /// Plan the sandbox for `proc_id`, creating its workspace on the
+    /// host if a workspace root is configured.
+    pub(crate) fn prepare(
+        proc_id: &hyperactor_reference::ProcId,
+        opts: &LaunchOptions,
+    ) -> std::io::Result<Self> {
+        let mut flags = libc::CLONE_NEWUSER | libc::CLONE_NEWNS | libc::CLONE_NEWPID;
+        if hyperactor_config::global::get(MESH_PROC_SANDBOX_NETWORK) {
+            flags |= libc::CLONE_NEWNET;
+        }
+        // SAFETY: `geteuid`/`getegid` cannot fail and touch no memory.
+        let (uid, gid) = unsafe { (libc::geteuid(), libc::getegid()) };
+
+        let socket_dir = match Bootstrap::from_env_safe_string(&opts.bootstrap_payload) {
+            Ok(Bootstrap::Proc {
+                socket_dir_path, ..
+            }) => Some(std::fs::canonicalize(socket_dir_path)?),
+            _ => None,
+        };
+        let root = hyperactor_config::global::get_cloned(MESH_PROC_SANDBOX_WORKSPACE_ROOT);
+        let (workspace_root, workspace) = if root.is_empty() {
+            (None, None)
+        } else {
+            let workspace = Path::new(&root).join(leaf_name(proc_id));
+            std::fs::create_dir_all(&workspace)?;
+            let workspace = std::fs::canonicalize(workspace)?;
+            let root = workspace.parent().map(Path::to_path_buf);
+            (root, Some(workspace))
+        };
+
+        let mut mkdirs = Vec::new();
+        for dir in socket_dir.iter().chain(workspace.iter()) {
+            let mut ancestors: Vec<&Path> = dir.ancestors().collect();
+            ancestors.pop(); // "/"
+            for ancestor in ancestors.into_iter().rev() {
+                mkdirs.push(path_cstring(ancestor)?);
+            }
+        }
+
+        Ok(Self {
+            flags,
+            uid_map: format!("{uid} {uid} 1\n").into_bytes(),
+            gid_map: format!("{gid} {gid} 1\n").into_bytes(),
+            host_pid: hyperactor_config::global::get(MESH_BOOTSTRAP_ENABLE_PDEATHSIG)
+                .then(|| std::process::id() as libc::pid_t),
+            dev_shm: Path::new("/dev/shm").is_dir(),
+            socket_dir: socket_dir.as_deref().map(path_cstring).transpose()?,
+            workspace_root: workspace_root.as_deref().map(path_cstring).transpose()?,
+            workspace: workspace.as_deref().map(path_cstring).transpose()?,
+            mkdirs,
+        })
+    }
+
+    /// The proc's workspace on the host, if it has one.
+    pub(crate) fn workspace(&self) -> Option<&Path> {
+        self.workspace
+            .as_deref()
+            .map(|c| Path::new(OsStr::from_bytes(c.to_bytes())))
+    }
+
+    /// Enter the sandbox from the launched child.
+    ///
+    /// Returns only in the proc, which then `exec`s; the supervisor
+    /// and init never return. An error returned from init still
+    /// fails the launch: the standard library reports it to `spawn`
+    /// and the supervisor exits with init (SB-1).
+    ///
+    /// # Safety
+    ///
+    /// Must run in a freshly forked child, i.e. in `pre_exec`. Only
+    /// async-signal-safe calls are made and nothing allocates.
+    pub(crate) unsafe fn enter_in_child(&self) -> std::io::Result<()> {
+        // SAFETY: raw syscalls on pre-encoded, NUL-terminated paths
+        // and plain-old-data buffers; see the function contract.
+        unsafe {
+            if let Some(host_pid) = self.host_pid {
+                check(libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL))?;
+                if libc::getppid() != host_pid {
+                    return Err(std::io::Error::from_raw_os_error(libc::ESRCH));
+                }
+            }
+            check(libc::unshare(self.flags))?;
+            write_file(c"/proc/self/setgroups", b"deny")?;
+            write_file(c"/proc/self/uid_map", &self.uid_map)?;
+            write_file(c"/proc/self/gid_map", &self.gid_map)?;
+
+            let mut pipe = [0; 2];
+            check(libc::pipe2(pipe.as_mut_ptr(), libc::O_CLOEXEC))?;
+            let [status_rd, status_wr] = pipe;
+
+            // The supervisor keeps every signal blocked (SB-3); init
+            // restores the mask so the proc starts with it.
+            let mut all: libc::sigset_t = std::mem::zeroed();
+            let mut mask: libc::sigset_t = std::mem::zeroed();
+            libc::sigfillset(&mut all);
+            libc::sigprocmask(libc::SIG_BLOCK, &all, &mut mask);
+
+            let init = libc::fork();
+            if init < 0 {
+                let err = std::io::Error::last_os_error();
+                libc::sigprocmask(libc::SIG_SETMASK, &mask, std::ptr::null_mut());
+                return Err(err);
+            }
+            if init > 0 {
+                libc::close(status_wr);
+                close_fds_except(status_rd);
+                supervise(init, status_rd);
+            }
+
+            // Init: pid 1 of the new PID namespace.
+            libc::sigprocmask(libc::SIG_SETMASK, &mask, std::ptr::null_mut());
+            libc::close(status_rd);
+            check(libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL))?;
+            self.setup_mounts()?;
+            if self.flags & libc::CLONE_NEWNET != 0 {
+                loopback_up()?;
+            }
+            if let Some(workspace) = &self.workspace {
+                check(libc::chdir(workspace.as_ptr()))?;
+            }
+
+            let proc = libc::fork();
+            if proc < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            if proc == 0 {
+                return Ok(());
+            }
+            close_fds_except(status_wr);
+            reap(proc, status_wr)
+        }
+    }
+
+    /// Build the filesystem view (see the module docs). Runs in init.
+    unsafe fn setup_mounts(&self) -> std::io::Result<()> {
+        // SAFETY: see `enter_in_child`.
+        unsafe {
+            // Keep every change below out of the host's mount tree.
+            check(libc::mount(
+                std::ptr::null(),
+                c"/".as_ptr(),
+                std::ptr::null(),
+                libc::MS_REC | libc::MS_PRIVATE,
+                std::ptr::null(),
+            ))?;
+
+            // Detach writable copies before the tree goes read-only.
+            let socket_tree = match &self.socket_dir {
+                Some(dir) => Some(clone_tree(dir)?),
+                None => None,
+            };
+            let workspace_tree = match &self.workspace {
+                Some(dir) => Some(clone_tree(dir)?),
+                None => None,
+            };
+
+            let attr = MountAttr {
+                attr_set: MOUNT_ATTR_RDONLY,
+                attr_clr: 0,
+                propagation: 0,
+                userns_fd: 0,
+            };
+            check(libc::syscall(
+                libc::SYS_mount_setattr,
+                libc::AT_FDCWD,
+                c"/".as_ptr(),
+                libc::AT_RECURSIVE as libc::c_uint,
+                &attr as *const MountAttr,
+                std::mem::size_of::<MountAttr>(),
+            ) as libc::c_int)?;
+
+            mount_tmpfs(c"/tmp", c"mode=1777")?;
+            if self.dev_shm {
+                mount_tmpfs(c"/dev/shm", c"mode=1777")?;
+            }
+            // Mount points under the private mounts must be created,
+            // before and again after covering the workspace root.
+            self.make_mount_points()?;
+            if let Some(root) = &self.workspace_root {
+                mount_tmpfs(root, c"mode=0755")?;
+                self.make_mount_points()?;
+            }
+            if let (Some(tree), Some(dir)) = (socket_tree, &self.socket_dir) {
+                attach_tree(tree, dir)?;
+            }
+            if let (Some(tree), Some(dir)) = (workspace_tree, &self.workspace) {
+                attach_tree(tree, dir)?;
+            }
+
+            check(libc::mount(
+                c"proc".as_ptr(),
+                c"/proc".as_ptr(),
+                c"proc".as_ptr(),
+                libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+                std::ptr::null(),
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// Create missing mount points; existing ones are left alone.
+    unsafe fn make_mount_points(&self) -> std::io::Result<()> {
+        for dir in &self.mkdirs {
+            // SAFETY: `dir` is NUL-terminated.
+            if unsafe { libc::mkdir(dir.as_ptr(), 0o755) } != 0 {
+                let err = std::io::Error::last_os_error();
+                if err.raw_os_error() != Some(libc::EEXIST) {
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Supervisor: wait for init, then exit like the proc (SB-2).
+unsafe fn supervise(init: libc::pid_t, status_rd: libc::c_int) -> ! {
+    // SAFETY: see `Sandbox::enter_in_child`.
+    unsafe {
+        let mut status = 0;
+        loop {
+            let rc = libc::waitpid(init, &mut status, 0);
+            if rc == init {
+                break;
+            }
+            if rc < 0 && std::io::Error::last_os_error().raw_os_error() != Some(libc::EINTR) {
+                libc::_exit(127);
+            }
+        }
+        // Without a report init died before the proc did; mirror init.
+        let mut buf = [0u8; 4];
+        if libc::read(status_rd, buf.as_mut_ptr().cast(), buf.len()) == buf.len() as isize {
+            status = libc::c_int::from_ne_bytes(buf);
+        }
+        exit_like(status)
+    }
+}
+
+/// Exit with `status`: the same exit code, or death by the same
+/// signal.
+unsafe fn exit_like(status: libc::c_int) -> ! {
+    // SAFETY: see `Sandbox::enter_in_child`.
+    unsafe {
+        if libc::WIFSIGNALED(status) {
+            let sig = libc::WTERMSIG(status);
+            // Never leave a core of the supervisor behind.
+            let no_core = libc::rlimit {
+                rlim_cur: 0,
+                rlim_max: 0,
+            };
+            libc::setrlimit(libc::RLIMIT_CORE, &no_core);
+            libc::signal(sig, libc::SIG_DFL);
+            let mut set: libc::sigset_t = std::mem::zeroed();
+            libc::sigemptyset(&mut set);
+            libc::sigaddset(&mut set, sig);
+            libc::kill(libc::getpid(), sig);
+            libc::sigprocmask(libc::SIG_UNBLOCK, &set, std::ptr::null_mut());
+            // Only reached for signals that are not fatal by default.
+            libc::_exit(128 + sig);
+        }
+        libc::_exit(libc::WEXITSTATUS(status))
+    }
+}
+
+/// Init: reap every child until the proc exits, then report its wait
+/// status and exit, taking the namespace down with it (SB-3).
+unsafe fn reap(proc: libc::pid_t, status_wr: libc::c_int) -> ! {
+    // SAFETY: see `Sandbox::enter_in_child`.
+    unsafe {
+        loop {
+            let mut status = 0;
+            let rc = libc::waitpid(-1, &mut status, 0);
+            if rc == proc {
+                let buf = status.to_ne_bytes();
+                libc::write(status_wr, buf.as_ptr().cast(), buf.len());
+                libc::_exit(0);
+            }
+            if rc < 0 && std::io::Error::last_os_error().raw_os_error() != Some(libc::EINTR) {
+                libc::_exit(127);
+            }
+        }
+    }
+}
+
+/// `Err(errno)` if `rc` is negative.
+fn check(rc: libc::c_int) -> std::io::Result<libc::c_int> {
+    if rc < 0 {
+        Err(std::io::Error::last_os_error())
+    } else {
+        Ok(rc)
+    }
+}
+
+fn path_cstring(path: &Path) -> std::io::Result<CString> {
+    CString::new(path.as_os_str().as_bytes()).map_err(std::io::Error::other)
+}
+
+/// Write `data` to the existing file at `path`.
+unsafe fn write_file(path: &CStr, data: &[u8]) -> std::io::Result<()> {
+    // SAFETY: `path` is NUL-terminated and `data` outlives the call.
+    unsafe {
+        let fd = check(libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC))?;
+        let rc = libc::write(fd, data.as_ptr().cast(), data.len());
+        let err = std::io::Error::last_os_error();
+        libc::close(fd);
+        if rc != data.len() as isize {
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+/// Close every descriptor above stdio except `keep`, including the
+/// standard library's exec-error pipe: `spawn` returns only once all
+/// copies of it are closed.
+unsafe fn close_fds_except(keep: libc::c_int) {
+    let keep = keep as libc::c_uint;
+    // SAFETY: `close_range` only closes descriptors.
+    unsafe {
+        let first: libc::c_uint = 3;
+        if keep > first {
+            libc::syscall(libc::SYS_close_range, first, keep - 1, 0);
+        }
+        libc::syscall(libc::SYS_close_range, keep + 1, libc::c_uint::MAX, 0);
+    }
+}
+
+unsafe fn mount_tmpfs(target: &CStr, options: &CStr) -> std::io::Result<()> {
+    // SAFETY: all arguments are NUL-terminated strings.
+    check(unsafe {
+        libc::mount(
+            c"tmpfs".as_ptr(),
+            target.as_ptr(),
+            c"tmpfs".as_ptr(),
+            libc::MS_NOSUID | libc::MS_NODEV,
+            options.as_ptr().cast(),
+        )
+    })?;
+    Ok(())
+}
+
+/// Detached recursive copy of the mount tree at `path`.
+unsafe fn clone_tree(path: &CStr) -> std::io::Result<libc::c_int> {
+    // SAFETY: `path` is NUL-terminated.
+    check(unsafe {
+        libc::syscall(
+            libc::SYS_open_tree,
+            libc::AT_FDCWD,
+            path.as_ptr(),
+            OPEN_TREE_CLONE | libc::O_CLOEXEC as libc::c_uint | libc::AT_RECURSIVE as libc::c_uint,
+        ) as libc::c_int
+    })
+}
+
+/// Attach the detached tree `tree` at `target` and close it.
+unsafe fn attach_tree(tree: libc::c_int, target: &CStr) -> std::io::Result<()> {
+    // SAFETY: `tree` is an `open_tree` descriptor and `target` is
+    // NUL-terminated.
+    unsafe {
+        let rc = libc::syscall(
+            libc::SYS_move_mount,
+            tree,
+            c"".as_ptr(),
+            libc::AT_FDCWD,
+            target.as_ptr(),
+            MOVE_MOUNT_F_EMPTY_PATH,
+        ) as libc::c_int;
+        let err = std::io::Error::last_os_error();
+        libc::close(tree);
+        if rc < 0 {
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+/// Bring up `lo` in a fresh network namespace.
+unsafe fn loopback_up() -> std::io::Result<()> {
+    // SAFETY: `ifreq` is plain old data; the ioctls read and write
+    // only `req`.
+    unsafe {
+        let fd = check(libc::socket(
+            libc::AF_INET,
+            libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+            0,
+        ))?;
+        let mut req: libc::ifreq = std::mem::zeroed();
+        req.ifr_name[0] = b'l' as libc::c_char;
+        req.ifr_name[1] = b'o' as libc::c_char;
+        let mut rc = libc::ioctl(fd, libc::SIOCGIFFLAGS as _, &mut req);
+        if rc == 0 {
+            req.ifr_ifru.ifru_flags |= libc::IFF_UP as libc::c_short;
+            rc = libc::ioctl(fd, libc::SIOCSIFFLAGS as _, &req);
+        }
+        let err = std::io::Error::last_os_error();
+        libc::close(fd);
+        if rc != 0 {
+            return Err(err);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use hyperactor::testing::ids::test_proc_id;
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+    use crate::bootstrap::BootstrapCommand;
+    use crate::proc_launcher::ProcExitKind;
+    use crate::proc_launcher::StdioHandling;
+    use crate::proc_launcher::format_process_name;
+
+    fn sh_opts(proc_id: &hyperactor_reference::ProcId, script: &str) -> LaunchOptions {
+        LaunchOptions {
+            command: BootstrapCommand {
+                program: PathBuf::from("/bin/sh"),
+                args: vec!["-c".into(), script.into()],
+                ..Default::default()
+            },
+            bootstrap_payload: Bootstrap::default().to_env_safe_string().unwrap(),
+            process_name: format_process_name(proc_id),
+            want_stdio: true,
+            tail_lines: 0,
+            log_channel: None,
+            proc_bind: None,
+            resources: None,
+        }
+    }
+
+    /// Whether an unprivileged process may create the sandbox's
+    /// namespaces here. Only this lets the end-to-end tests skip; any
+    /// other launch failure fails them.
+    fn user_namespaces_available() -> bool {
+        use std::os::unix::process::CommandExt;
+
+        let mut cmd = std::process::Command::new("/bin/true");
+        // SAFETY: `unshare` is async-signal-safe and allocates nothing.
+        unsafe {
+            cmd.pre_exec(|| {
+                let flags = libc::CLONE_NEWUSER | libc::CLONE_NEWNS | libc::CLONE_NEWPID;
+                if libc::unshare(flags) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        match cmd.status() {
+            Ok(_) => true,
+            Err(e)
+                if matches!(
+                    e.raw_os_error(),
+                    Some(libc::EPERM | libc::EINVAL | libc::ENOSPC)
+                ) =>
+            {
+                eprintln!("skipping: user namespaces unavailable: {e}");
+                false
+            }
+            Err(e) => panic!("probing user namespaces failed: {e}"),
+        }
+    }
+
+    #[test]
+    fn prepare_creates_workspace_and_mount_points() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = hyperactor_config::global::lock();
+        let _root = config.override_key(
+            MESH_PROC_SANDBOX_WORKSPACE_ROOT,
+            dir.path().to_string_lossy().into_owned(),
+        );
+        let proc_id = test_proc_id("ws");
+        let sandbox = Sandbox::prepare(&proc_id, &sh_opts(&proc_id, "true")).unwrap();
+
+        let workspace = sandbox.workspace().unwrap().to_path_buf();
+        assert!(workspace.is_dir());
+        assert_eq!(
+            workspace.parent(),
+            Some(std::fs::canonicalize(dir.path()).unwrap().as_path())
+        );
+        // Mount points are created outermost first, ending with the
+        // workspace itself.
+        assert_eq!(
+            sandbox.mkdirs.last().map(|c| c.to_bytes()),
+            Some(workspace.as_os_str().as_bytes())
+        );
+        assert!(
+            sandbox
+                .mkdirs
+                .windows(2)
+                .all(|w| w[1].to_bytes().starts_with(w[0].to_bytes()))
+        );
+        assert_eq!(sandbox.flags & libc::CLONE_NEWNET, 0);
+    }
+
+    /// End to end: the proc is pid 2 of its own PID namespace, `/tmp`
+    /// is private, the host tree is read-only and the exit code is
+    /// mirrored. Skipped where user namespaces are unavailable.
+    #[tokio::test]
+    async fn sandboxed_proc_is_isolated() {
+        let marker = format!("sandbox-marker-{}", std::process::id());
+        let script = format!(
+            "echo $$; touch /tmp/{marker} && echo tmp-ok; \
+             touch /usr/{marker} 2>/dev/null || echo ro-ok; exit 3"
+        );
+        let launcher = SandboxProcLauncher::new();
+        let proc_id = test_proc_id("sandboxed");
+        if !user_namespaces_available() {
+            return;
+        }
+        let lr = launcher
+            .launch(&proc_id, sh_opts(&proc_id, &script))
+            .await
+            .expect("launch sandboxed proc");
+
+        let StdioHandling::Captured { mut stdout, .. } = lr.stdio else {
+            panic!("expected captured stdio");
+        };
+        let mut out = String::new();
+        stdout.read_to_string(&mut out).await.unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines, vec!["2", "tmp-ok", "ro-ok"]);
+        assert!(!Path::new("/tmp").join(&marker).exists());
+
+        let exit = lr.exit_rx.await.unwrap();
+        assert!(
+            matches!(exit.kind, ProcExitKind::Exited { code: 3 }),
+            "unexpected exit {:?}",
+            exit.kind
+        );
+    }
+
+    /// Killing the process group tears down the whole sandbox and is
+    /// reported as the signal.
+    #[tokio::test]
+    async fn kill_tears_down_sandbox() {
+        let launcher = SandboxProcLauncher::new();
+        let proc_id = test_proc_id("sandbox-kill");
+        if !user_namespaces_available() {
+            return;
+        }
+        let lr = launcher
+            .launch(&proc_id, sh_opts(&proc_id, "sleep 600 & wait"))
+            .await
+            .expect("launch sandboxed proc");
+        launcher.kill(&proc_id).await.unwrap();
+        let exit = tokio::time::timeout(Duration::from_secs(10), lr.exit_rx)
+            .await
+            .expect("exit not observed")
+            .unwrap();
+        assert!(
+            matches!(
+                exit.kind,
+                ProcExitKind::Signaled {
+                    signal: libc::SIGKILL,
+                    ..
+                }
+            ),
+            "unexpected exit {:?}",
+            exit.kind
+        );
+    }
+}
@@ -53,6 +53,14 @@
 //!   [`StdioHandling::ManagedByLauncher`] and does not attempt to
 //!   stream stdout/stderr. Tests verify behavior via files in
 //!   `XDG_RUNTIME_DIR` not stdout capture.
+//! - Resource limits ([`LaunchOptions::resources`]) become the
+//!   unit properties `MemoryMax`, `MemoryHigh`, `CPUQuotaPerSecUSec`
+//!   and `TasksMax`. systemd silently ignores them if the user
+//!   manager has not been delegated the corresponding controllers,
+//!   so before starting a unit with limits we check the manager's
+//!   `cgroup.controllers` and fail the launch if one is missing. An
+//!   OOM kill is reported by systemd as `Result=oom-kill` and mapped
+//!   to [`ProcExitKind::OomKilled`].
 //! - Drop cleanup is best-effort: on drop we attempt to stop all
 //!   tracked units, but it runs on a detached thread and may not
 //!   complete if the process is already exiting.
@@ -90,9 +98,12 @@ use crate::proc_launcher::ProcExitKind;
 use crate::proc_launcher::ProcExitResult;
 use crate::proc_launcher::ProcLauncher;
 use crate::proc_launcher::ProcLauncherError;
+use crate::proc_launcher::ProcResourceUsage;
+use crate::proc_launcher::ProcResources;
 use crate::proc_launcher::StdioHandling;
 use crate::proc_launcher::format_process_name;
 use crate::systemd::SystemdManagerProxy;
+use crate::systemd::SystemdServiceProxy;
 use crate::systemd::SystemdUnitHandle;
 use crate::systemd::start_transient_service_clean;
 
@@ -391,7 +402,8 @@ fn map_exit_from_result(result: &str, status: i32, active: &str, sub: &str) -> P
 /// 2. Subscribes to ActiveState/SubState change signals
 /// 3. Waits for terminal state (signal-driven with safety polling)
 /// 4. Reads ExecMainCode/ExecMainStatus/Result properties
-/// 5. Maps to [`ProcExitKind`]
+/// 5. Maps to [`ProcExitKind`] (`Result=oom-kill` becomes
+///    [`ProcExitKind::OomKilled`] with `memory_max`)
 /// 6. Removes the proc from the units map
 /// 7. Sends the result on `exit_tx`
 async fn monitor_exit(
@@ -399,6 +411,7 @@ async fn monitor_exit(
     units: Arc<std::sync::Mutex<HashMap<hyperactor_reference::ProcId, String>>>,
     proc_id: hyperactor_reference::ProcId,
     handle: SystemdUnitHandle,
+    memory_max: Option<u64>,
     exit_tx: oneshot::Sender<ProcExitResult>,
 ) {
     // Create proxies ONCE.
@@ -578,8 +591,14 @@ async fn monitor_exit(
         tokio::time::sleep(Duration::from_millis(25)).await;
     }
 
-    // Map exit to ProcExitKind using the helper function.
-    let kind = map_exit_kind(code, status, &result, &active, &sub);
+    // Map exit to ProcExitKind using the helper function. An OOM
+    // kill shows up as CLD_KILLED/SIGKILL; only `Result` tells it
+    // apart.
+    let kind = if result == "oom-kill" {
+        ProcExitKind::OomKilled { memory_max }
+    } else {
+        map_exit_kind(code, status, &result, &active, &sub)
+    };
     send_and_cleanup(kind, &proc_id, &units, exit_tx);
 }
 
@@ -783,8 +802,9 @@ impl SystemdProcLauncher {
         proc_id: &hyperactor_reference::ProcId,
         exec_start: Vec<(String, Vec<String>, bool)>,
         env_kv: Vec<String>,
+        resources: Option<&ProcResources>,
     ) -> Vec<(&'a str, Value<'a>)> {
-        vec![
+        let mut props = vec![
             (
                 "Description",
                 Value::from(format!("monarch proc {}", proc_id)),
@@ -801,7 +821,59 @@ impl SystemdProcLauncher {
             ("TimeoutStopUSec", Value::from(5_000_000u64)),
             ("ExecStart", Value::from(exec_start)),
             ("Environment", Value::from(env_kv)),
-        ]
+        ];
+        if let Some(resources) = resources {
+            if let Some(bytes) = resources.memory_max {
+                props.push(("MemoryMax", Value::from(bytes)));
+                // Kill the whole unit on OOM, like the native
+                // launcher's `memory.oom.group`.
+                props.push(("OOMPolicy", Value::from("kill")));
+            }
+            if let Some(bytes) = resources.memory_high {
+                props.push(("MemoryHigh", Value::from(bytes)));
+            }
+            if let Some(percent) = resources.cpu_quota_percent {
+                // CPU time per wall-clock second: 100% = 1s.
+                props.push(("CPUQuotaPerSecUSec", Value::from(percent * 10_000)));
+            }
+            if let Some(max) = resources.pids_max {
+                props.push(("TasksMax", Value::from(max)));
+            }
+        }
+        props
+    }
+
+    /// Fail unless the user manager has been delegated every cgroup
+    /// controller that `resources` needs; systemd would otherwise
+    /// accept the limits and silently not enforce them.
+    async fn check_delegation(
+        conn: &Connection,
+        resources: &ProcResources,
+    ) -> Result<(), ProcLauncherError> {
+        let launch_err = |msg: String| ProcLauncherError::Launch(std::io::Error::other(msg));
+        let required = required_controllers(resources);
+        if required.is_empty() {
+            return Ok(());
+        }
+        let control_group = SystemdManagerProxy::new(conn)
+            .await
+            .map_err(|e| launch_err(format!("connect to systemd manager: {e}")))?
+            .control_group()
+            .await
+            .map_err(|e| launch_err(format!("read manager ControlGroup: {e}")))?;
+        let path = format!("/sys/fs/cgroup{control_group}/cgroup.controllers");
+        let available = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| launch_err(format!("read {path}: {e}")))?;
+        let missing = missing_controllers(&available, &required);
+        if !missing.is_empty() {
+            return Err(launch_err(format!(
+                "resource limits require cgroup controllers not delegated to the \
+                 systemd user manager ({path}): {}",
+                missing.join(", ")
+            )));
+        }
+        Ok(())
     }
 
     /// Shared implementation for terminate/kill via systemd StopUnit.
@@ -920,13 +992,19 @@ impl ProcLauncher for SystemdProcLauncher {
         // helper methods
         let exec_start = Self::build_exec_start(&opts);
         let env_kv = Self::build_env(&opts)?;
-        let props = Self::build_unit_props(proc_id, exec_start, env_kv);
+        let props = Self::build_unit_props(proc_id, exec_start, env_kv, opts.resources.as_ref());
+        let memory_max = opts.resources.as_ref().and_then(|r| r.memory_max);
 
         let aux = Vec::new();
 
         // Get or establish D-Bus connection (lazy initialization).
         let conn = self.connection().await?;
 
+        // Limits that systemd cannot enforce fail the launch.
+        if let Some(resources) = opts.resources.as_ref() {
+            Self::check_delegation(conn, resources).await?;
+        }
+
         // Start unit and resolve object path
         let handle = start_transient_service_clean(conn, &unit, "replace", props, aux)
             .await
@@ -956,7 +1034,15 @@ impl ProcLauncher for SystemdProcLauncher {
         // Spawn exit monitor task (propagate launch span for tracing).
         let launch_span = tracing::Span::current();
         tokio::spawn(
-            monitor_exit(conn, units, proc_id_for_monitor, handle, exit_tx).instrument(launch_span),
+            monitor_exit(
+                conn,
+                units,
+                proc_id_for_monitor,
+                handle,
+                memory_max,
+                exit_tx,
+            )
+            .instrument(launch_span),
         );
 
         Ok(LaunchResult {
@@ -1013,6 +1099,35 @@ impl ProcLauncher for SystemdProcLauncher {
     async fn kill(&self, proc_id: &hyperactor_reference::ProcId) -> Result<(), ProcLauncherError> {
         self.stop_unit_impl(proc_id, StopOp::Kill).await
     }
+
+    /// Resource usage from the unit's accounting properties.
+    ///
+    /// Counters systemd does not track (reported as `u64::MAX`) are
+    /// `None`. `oom_kills` is not exposed by systemd and is always
+    /// `None`; OOM kills are reported through the exit kind.
+    async fn resource_usage(
+        &self,
+        proc_id: &hyperactor_reference::ProcId,
+    ) -> Option<ProcResourceUsage> {
+        let unit = units_lock_recover(&self.units).get(proc_id).cloned()?;
+        let conn = self.connection().await.ok()?;
+        let manager = SystemdManagerProxy::new(conn).await.ok()?;
+        let path = manager.get_unit(&unit).await.ok()?;
+        let svc = SystemdServiceProxy::builder(conn)
+            .path(path)
+            .ok()?
+            .build()
+            .await
+            .ok()?;
+        let known = |v: zbus::Result<u64>| v.ok().filter(|&v| v != u64::MAX);
+        Some(ProcResourceUsage {
+            memory_current: known(svc.memory_current().await),
+            memory_peak: known(svc.memory_peak().await),
+            cpu_usage_usec: known(svc.cpu_usage_nsec().await).map(|ns| ns / 1_000),
+            pids_current: known(svc.tasks_current().await),
+            oom_kills: None,
+        })
+    }
 }
 
 impl Drop for SystemdProcLauncher {
@@ -1092,6 +1207,33 @@ impl Drop for SystemdProcLauncher {
     }
 }
 
+/// cgroup controllers that must be available to enforce
+/// `resources`.
+fn required_controllers(resources: &ProcResources) -> Vec<&'static str> {
+    let mut required = Vec::new();
+    if resources.memory_max.is_some() || resources.memory_high.is_some() {
+        required.push("memory");
+    }
+    if resources.cpu_quota_percent.is_some() {
+        required.push("cpu");
+    }
+    if resources.pids_max.is_some() {
+        required.push("pids");
+    }
+    required
+}
+
+/// The controllers in `required` that are absent from `available`,
+/// the contents of a `cgroup.controllers` file.
+fn missing_controllers<'a>(available: &str, required: &[&'a str]) -> Vec<&'a str> {
+    let available: Vec<&str> = available.split_whitespace().collect();
+    required
+        .iter()
+        .filter(|c| !available.contains(c))
+        .copied()
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -1201,6 +1343,7 @@ mod tests {
             tail_lines: 0,
             log_channel: Some(log_channel.clone()),
             proc_bind: None,
+            resources: None,
         };
 
         let lr = launcher.launch(&proc_id, opts).await.expect("launch");
@@ -1298,6 +1441,7 @@ mod tests {
             tail_lines: 0,
             log_channel: None,
             proc_bind: None,
+            resources: None,
         };
 
         let lr = launcher.launch(&proc_id, opts).await.expect("launch");
@@ -1340,6 +1484,7 @@ mod tests {
             tail_lines: 0,
             log_channel: None,
             proc_bind: None,
+            resources: None,
         };
 
         let lr = launcher.launch(&proc_id, opts).await.expect("launch");
@@ -1402,6 +1547,7 @@ mod tests {
             tail_lines: 0,
             log_channel: None,
             proc_bind: None,
+            resources: None,
         };
 
         let lr = launcher.launch(&proc_id, opts).await.expect("launch");
@@ -1577,6 +1723,7 @@ mod tests {
                 tail_lines: 0,
                 log_channel: None,
                 proc_bind: None,
+                resources: None,
             };
 
             let lr = launcher.launch(&proc_id, opts).await.expect("launch");
@@ -1671,6 +1818,7 @@ mod tests {
             tail_lines: 0,
             log_channel: None,
             proc_bind: None,
+            resources: None,
         };
 
         // IMPORTANT: lr must be mutable because we take &mut
@@ -1711,4 +1859,50 @@ mod tests {
             other => panic!("expected Signaled or Exited, got {other:?}"),
         }
     }
+
+    /// Resource limits become the corresponding unit properties;
+    /// without limits no resource-control property is set.
+    #[test]
+    fn unit_props_carry_resource_limits() {
+        let proc_id = test_proc_id("limits");
+        let names = |props: &[(&str, Value<'_>)]| -> Vec<String> {
+            props.iter().map(|(k, _)| k.to_string()).collect()
+        };
+
+        let plain = SystemdProcLauncher::build_unit_props(&proc_id, vec![], vec![], None);
+        assert!(!names(&plain).iter().any(|k| k.starts_with("Memory")));
+
+        let resources = ProcResources {
+            memory_max: Some(1 << 30),
+            memory_high: None,
+            cpu_quota_percent: Some(150),
+            pids_max: Some(64),
+        };
+        let props =
+            SystemdProcLauncher::build_unit_props(&proc_id, vec![], vec![], Some(&resources));
+        let get = |key: &str| props.iter().find(|(k, _)| *k == key).map(|(_, v)| v);
+        assert_eq!(get("MemoryMax"), Some(&Value::from(1u64 << 30)));
+        assert_eq!(get("OOMPolicy"), Some(&Value::from("kill")));
+        assert_eq!(get("MemoryHigh"), None);
+        assert_eq!(get("CPUQuotaPerSecUSec"), Some(&Value::from(1_500_000u64)));
+        assert_eq!(get("TasksMax"), Some(&Value::from(64u64)));
+    }
+
+    /// Limits fail the launch unless their controllers are
+    /// delegated.
+    #[test]
+    fn missing_controllers_are_detected() {
+        let resources = ProcResources {
+            memory_max: None,
+            memory_high: Some(1 << 20),
+            cpu_quota_percent: None,
+            pids_max: Some(64),
+        };
+        let required = required_controllers(&resources);
+        assert_eq!(required, vec!["memory", "pids"]);
+        assert!(missing_controllers("cpuset cpu io memory pids\n", &required).is_empty());
+        assert_eq!(missing_controllers("cpu pids\n", &required), vec!["memory"]);
+        assert_eq!(missing_controllers("", &required), vec!["memory", "pids"]);
+        assert!(required_controllers(&ProcResources::default()).is_empty());
+    }
 }
@@ -61,15 +61,19 @@ use crate::alloc::Alloc;
 use crate::alloc::AllocExt;
 use crate::alloc::AllocatedProc;
 use crate::assign::Ranks;
+use crate::bootstrap::BootstrapCommand;
 use crate::comm::CommMeshConfig;
+use crate::host_mesh::PROC_SPAWN_MAX_IDLE;
 use crate::host_mesh::host_agent::ProcState;
 use crate::host_mesh::mesh_to_rankedvalues_with_default;
 use crate::mesh_controller::ActorMeshController;
 use crate::proc_agent;
 use crate::proc_agent::ActorState;
+use crate::proc_agent::GetActorSpecs;
 use crate::proc_agent::MeshAgentMessageClient;
 use crate::proc_agent::ProcAgent;
 use crate::proc_agent::ReconfigurableMailboxSender;
+use crate::proc_agent::RecordedActor;
 use crate::resource;
 use crate::resource::GetRankStatus;
 use crate::resource::Status;
@@ -163,7 +167,6 @@ pub struct ProcMesh {
     #[allow(dead_code)]
     name: Name,
     allocation: ProcMeshAllocation,
-    #[allow(dead_code)]
     comm_actor_name: Option<Name>,
     current_ref: ProcMeshRef,
 }
@@ -582,6 +585,92 @@ impl ProcMesh {
         }
     }
 
+    /// Restart this mesh in place: replace its procs, in waves of at
+    /// most `wave_size`, with procs running `bootstrap_command` (or
+    /// their current command) with `env` set on top. Each proc is
+    /// stopped gracefully, and its actors are recreated on the
+    /// replacement from their original spawn parameters, under the
+    /// same names; any state they accumulated is lost. References to
+    /// the mesh and its actors remain valid.
+    ///
+    /// A wave starts only once every proc of the previous one is
+    /// running again; the first failure ends the restart. Only meshes
+    /// spawned on a host mesh can be restarted.
+    pub async fn rolling_restart(
+        &self,
+        cx: &impl context::Actor,
+        bootstrap_command: Option<BootstrapCommand>,
+        env: HashMap<String, String>,
+        wave_size: usize,
+    ) -> anyhow::Result<()> {
+        let ProcMeshAllocation::Owned { hosts, ranks, .. } = &self.allocation else {
+            anyhow::bail!(
+                "proc mesh {} was not spawned on a host mesh and cannot be restarted",
+                self.name
+            );
+        };
+        let query_timeout = hyperactor_config::global::get(PROC_SPAWN_MAX_IDLE);
+        let comm_actors: Option<HashMap<usize, hyperactor_reference::ActorRef<CommActor>>> =
+            self.comm_actor_name.as_ref().map(|name| {
+                ranks
+                    .iter()
+                    .enumerate()
+                    .map(|(rank, proc_ref)| (rank, proc_ref.attest(name)))
+                    .collect()
+            });
+
+        let wave_size = wave_size.max(1);
+        for (wave, procs) in ranks.chunks(wave_size).enumerate() {
+            // Record every proc's actors before stopping any of them.
+            let mut restarts = Vec::with_capacity(procs.len());
+            for proc_ref in procs {
+                let (reply, reply_rx) = cx.mailbox().open_once_port::<Vec<RecordedActor>>();
+                let mut agent_port = proc_ref.agent.port::<GetActorSpecs>();
+                agent_port.return_undeliverable(false);
+                agent_port.send(
+                    cx,
+                    GetActorSpecs {
+                        reply: reply.bind(),
+                    },
+                )?;
+                let actors = tokio::time::timeout(query_timeout, reply_rx.recv())
+                    .await
+                    .map_err(|_| {
+                        anyhow::anyhow!("timed out querying the actors of {}", proc_ref.proc_id)
+                    })??;
+                restarts.push((proc_ref.proc_id.clone(), actors));
+            }
+            tracing::info!(
+                name = "ProcMeshStatus",
+                proc_mesh = %self.name,
+                wave,
+                num_procs = procs.len(),
+                status = "RollingRestart::Wave",
+            );
+            hosts
+                .restart_procs(cx, restarts, bootstrap_command.clone(), env.clone())
+                .await?;
+
+            // The recreated comm actors must be put back into mesh mode.
+            if let Some(comm_actors) = &comm_actors {
+                let first_rank = wave * wave_size;
+                for rank in first_rank..first_rank + procs.len() {
+                    comm_actors[&rank]
+                        .send(cx, CommMeshConfig::new(rank, comm_actors.clone()))
+                        .map_err(|e| {
+                            Error::SendingError(comm_actors[&rank].actor_id().clone(), Box::new(e))
+                        })?;
+                }
+            }
+        }
+        tracing::info!(
+            name = "ProcMeshStatus",
+            proc_mesh = %self.name,
+            status = "RollingRestart::Done",
+        );
+        Ok(())
+    }
+
     #[cfg(test)]
     pub(crate) fn ranks(&self) -> Arc<Vec<ProcRef>> {
         self.allocation.ranks()
@@ -1176,6 +1265,16 @@ impl ProcMeshRef {
             // Controller and ActorMesh both depend on references from each other, break
             // the cycle by setting the controller after the fact.
             mesh.set_controller(Some(controller.bind()));
+            // Feed the admin event stream, if this process runs an admin.
+            if let Some(admin) = crate::mesh_admin::local_admin()
+                && let Err(e) = crate::mesh_admin::watch_actor_mesh(cx, &admin, &mesh)
+            {
+                tracing::warn!(
+                    actor_mesh = %mesh.name(),
+                    "failed to watch actor mesh for the admin: {:#}",
+                    e,
+                );
+            }
         }
         // Notify telemetry that an actor mesh was created.
         {
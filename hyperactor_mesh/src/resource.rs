@@ -138,11 +138,17 @@ impl From<bootstrap::ProcStatus> for Status {
         use bootstrap::ProcStatus;
         match status {
             ProcStatus::Starting => Status::Initializing,
-            ProcStatus::Running { .. } | ProcStatus::Ready { .. } => Status::Running,
+            // An unhealthy proc is still running; it only fails
+            // once it exits (e.g. when stopped for being unhealthy).
+            ProcStatus::Running { .. }
+            | ProcStatus::Ready { .. }
+            | ProcStatus::Unhealthy { .. } => Status::Running,
             ProcStatus::Stopping { .. } => Status::Stopping,
             ProcStatus::Stopped { .. } => Status::Stopped,
             ProcStatus::Failed { reason } => Status::Failed(reason),
-            ProcStatus::Killed { .. } => Status::Failed(format!("{}", status)),
+            ProcStatus::Killed { .. } | ProcStatus::OomKilled { .. } => {
+                Status::Failed(format!("{}", status))
+            }
         }
     }
 }
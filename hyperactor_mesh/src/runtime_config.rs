@@ -0,0 +1,188 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Mesh-wide runtime reconfiguration.
+//!
+//! [`HostMeshRef::set_runtime_config`](crate::host_mesh::HostMeshRef::set_runtime_config)
+//! pushes a configuration delta to every host agent in a mesh, and each
+//! host agent forwards it to the procs it runs. Every process applies
+//! the delta to its `Source::Runtime` layer and acknowledges it.
+//! Components that read their configuration with
+//! `hyperactor_config::global::get`, or that hold a
+//! `hyperactor_config::global::subscribe` receiver, see the new values
+//! without a restart.
+//!
+//! ## Runtime config invariants (RC-*)
+//!
+//! - **RC-1 (merge):** A delta is merged into each process's Runtime
+//!   layer: keys absent from the delta keep their values, and the Env
+//!   and TestOverride layers still take precedence. Keys whose
+//!   effective value comes from one of those layers after the merge
+//!   are reported as shadowed in that proc's acknowledgement.
+//! - **RC-2 (validated):** Each process validates the delta
+//!   independently. Rejected values are reported in that proc's
+//!   acknowledgement; the rest of the delta still applies.
+//! - **RC-3 (per-proc acknowledgement):** The result has one entry
+//!   for each host's service proc and for each live proc the host
+//!   agent has created. A proc that does not acknowledge within
+//!   `MESH_RUNTIME_CONFIG_TIMEOUT` is reported as unacknowledged; an
+//!   unresponsive host is reported as a single unacknowledged entry for
+//!   its service proc.
+//! - **RC-4 (not inherited):** The delta is not recorded by the host
+//!   agent. Procs spawned later bootstrap from the client's
+//!   propagated configuration, so a client that wants them to see the
+//!   delta must also merge it into its own Runtime layer.
+
+use hyperactor::HandleClient;
+use hyperactor::Handler;
+use hyperactor::RefClient;
+use hyperactor::reference as hyperactor_reference;
+use hyperactor_config::attrs::Attrs;
+use hyperactor_config::global::Source;
+use serde::Deserialize;
+use serde::Serialize;
+use typeuri::Named;
+
+/// The outcome of a runtime config push in one proc.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Named)]
+pub enum RuntimeConfigStatus {
+    /// The whole delta was applied.
+    Applied,
+    /// Some values, or the whole delta, were rejected by validation
+    /// (RC-2). Everything else was applied.
+    Rejected(Vec<String>),
+    /// The proc did not acknowledge the delta (RC-3), for the given
+    /// reason. It may or may not have applied it.
+    Unacknowledged(String),
+    /// The whole delta was applied, but the given keys take their
+    /// effective value from a higher-priority layer (Env or
+    /// TestOverride), so the new values do not take effect (RC-1).
+    Shadowed(Vec<String>),
+}
+wirevalue::register_type!(RuntimeConfigStatus);
+
+/// A proc's acknowledgement of a runtime config push.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Named)]
+pub struct RuntimeConfigAck {
+    /// The proc this acknowledgement is for.
+    pub proc_id: hyperactor_reference::ProcId,
+    /// What happened to the delta in that proc.
+    pub status: RuntimeConfigStatus,
+}
+wirevalue::register_type!(RuntimeConfigAck);
+
+impl RuntimeConfigAck {
+    /// Whether the whole delta was applied and takes effect.
+    pub fn is_applied(&self) -> bool {
+        self.status == RuntimeConfigStatus::Applied
+    }
+}
+
+/// Apply a runtime config delta to the receiving proc.
+///
+/// Handled by `ProcAgent`.
+#[derive(Debug, Serialize, Deserialize, Named, Handler, HandleClient, RefClient)]
+pub struct SetRuntimeConfig {
+    pub attrs: Attrs,
+    #[reply]
+    pub result: hyperactor_reference::OncePortRef<RuntimeConfigAck>,
+}
+wirevalue::register_type!(SetRuntimeConfig);
+
+/// Apply a runtime config delta to a host's service proc and to every
+/// live proc on the host, replying with one acknowledgement per proc
+/// (RC-3).
+///
+/// Handled by `HostAgent`.
+#[derive(Debug, Serialize, Deserialize, Named, Handler, HandleClient, RefClient)]
+pub struct SetHostRuntimeConfig {
+    pub attrs: Attrs,
+    #[reply]
+    pub result: hyperactor_reference::OncePortRef<Vec<RuntimeConfigAck>>,
+}
+wirevalue::register_type!(SetHostRuntimeConfig);
+
+/// Merge `attrs` into this process's Runtime layer (RC-1, RC-2).
+///
+/// Rejections take precedence over shadowing in the returned status.
+pub(crate) fn apply(proc_id: &hyperactor_reference::ProcId, attrs: Attrs) -> RuntimeConfigAck {
+    let keys: Vec<String> = attrs.iter().map(|(name, _)| name.to_string()).collect();
+    let status = match hyperactor_config::global::try_create_or_merge(Source::Runtime, attrs) {
+        Ok(()) => {
+            // RC-1: a key is shadowed if, after the merge, its
+            // effective value comes from a layer above Runtime.
+            let shadowed: Vec<String> = hyperactor_config::global::config_entries()
+                .into_iter()
+                .filter(|entry| {
+                    keys.contains(&entry.name)
+                        && matches!(entry.source, Source::Env | Source::TestOverride)
+                })
+                .map(|entry| entry.name)
+                .collect();
+            if shadowed.is_empty() {
+                RuntimeConfigStatus::Applied
+            } else {
+                RuntimeConfigStatus::Shadowed(shadowed)
+            }
+        }
+        Err(errors) => {
+            RuntimeConfigStatus::Rejected(errors.iter().map(ToString::to_string).collect())
+        }
+    };
+    tracing::info!(proc_id = %proc_id, ?status, "applied runtime config delta");
+    RuntimeConfigAck {
+        proc_id: proc_id.clone(),
+        status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    // RC-1: a key set by a higher-priority layer is reported as
+    // shadowed; the other keys of the delta still count as applied.
+    #[test]
+    fn test_apply_reports_shadowed_keys() {
+        let config = hyperactor_config::global::lock();
+        let proc_id = hyperactor_reference::ProcId::with_name(
+            hyperactor::channel::ChannelAddr::Local(0),
+            "runtime_config_test",
+        );
+
+        let mut delta = Attrs::new();
+        delta.set(
+            hyperactor::config::HOST_SPAWN_READY_TIMEOUT,
+            Duration::from_mins(7),
+        );
+        assert!(apply(&proc_id, delta.clone()).is_applied());
+
+        let _guard = config.override_key(
+            hyperactor::config::HOST_SPAWN_READY_TIMEOUT,
+            Duration::from_mins(3),
+        );
+        let ack = apply(&proc_id, delta);
+        assert_eq!(ack.proc_id, proc_id);
+        assert_eq!(
+            ack.status,
+            RuntimeConfigStatus::Shadowed(vec![
+                hyperactor::config::HOST_SPAWN_READY_TIMEOUT
+                    .name()
+                    .to_string()
+            ])
+        );
+        assert_eq!(
+            hyperactor_config::global::get(hyperactor::config::HOST_SPAWN_READY_TIMEOUT),
+            Duration::from_mins(3)
+        );
+
+        hyperactor_config::global::clear(Source::Runtime);
+    }
+}
@@ -104,6 +104,11 @@ pub(crate) trait SystemdManager {
     /// Return the D-Bus object path for a unit so we can inspect it
     /// further (active state, result, etc.).
     fn get_unit(&self, name: &str) -> Result<OwnedObjectPath>;
+
+    /// The manager's own cgroup, relative to the cgroup v2 mount.
+    /// Transient units are created below it.
+    #[zbus(property)]
+    fn control_group(&self) -> Result<String>;
 }
 
 /// Minimal view of a single systemd unit, used to query its state
@@ -154,6 +159,26 @@ pub(crate) trait SystemdService {
     /// "exit-code", "signal", ...).
     #[zbus(property)]
     fn result(&self) -> zbus::Result<String>;
+
+    /// Current memory usage of the unit's cgroup, in bytes
+    /// (`u64::MAX` if not accounted).
+    #[zbus(property)]
+    fn memory_current(&self) -> zbus::Result<u64>;
+
+    /// Peak memory usage of the unit's cgroup, in bytes (`u64::MAX`
+    /// if not accounted; systemd >= 256).
+    #[zbus(property)]
+    fn memory_peak(&self) -> zbus::Result<u64>;
+
+    /// CPU time consumed by the unit, in nanoseconds (`u64::MAX` if
+    /// not accounted).
+    #[zbus(property, name = "CPUUsageNSec")]
+    fn cpu_usage_nsec(&self) -> zbus::Result<u64>;
+
+    /// Current number of tasks in the unit (`u64::MAX` if not
+    /// accounted).
+    #[zbus(property)]
+    fn tasks_current(&self) -> zbus::Result<u64>;
 }
 
 /// A started unit + its resolved object path.
@@ -0,0 +1,133 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! End-to-end test for the mesh admin `GET /v1/events` stream on a
+//! local multiprocess host mesh.
+//!
+//! The admin is spawned before the meshes, so `spawn_admin` makes it
+//! this process's admin and the proc and actor meshes spawned
+//! afterwards are watched automatically. The procs are real child
+//! processes running the `hyperactor_mesh_test_bootstrap` binary.
+//! Runs over plain HTTP, which the admin falls back to when no TLS
+//! certificates are configured (MA-T1).
+
+use std::time::Duration;
+
+use hyperactor_mesh::ActorMesh;
+use hyperactor_mesh::bootstrap::BootstrapCommand;
+use hyperactor_mesh::global_context::context;
+use hyperactor_mesh::host_mesh::HostMesh;
+use hyperactor_mesh::host_mesh::spawn_admin;
+use hyperactor_mesh::mesh_admin::MeshAdminMessageClient;
+use hyperactor_mesh::mesh_admin_client::MeshEventStream;
+use hyperactor_mesh::mesh_admin_events::MeshEvent;
+use hyperactor_mesh::mesh_admin_events::MeshEventKind;
+use hyperactor_mesh::testactor::GetActorId;
+use hyperactor_mesh::testactor::TestActor;
+use ndslice::extent;
+use ndslice::view::Ranked;
+
+/// Read events until one of `kind` arrives for each reference in
+/// `references`, returning them in arrival order.
+async fn expect_events(
+    stream: &mut MeshEventStream,
+    kind: MeshEventKind,
+    references: &[String],
+) -> Vec<MeshEvent> {
+    let mut found: Vec<MeshEvent> = Vec::new();
+    while found.len() < references.len() {
+        let event = tokio::time::timeout(Duration::from_secs(60), stream.next())
+            .await
+            .unwrap_or_else(|_| panic!("timed out waiting for {} events", kind.as_str()))
+            .unwrap()
+            .expect("event stream ended");
+        if event.kind == kind
+            && references.contains(&event.reference)
+            && !found.iter().any(|seen| seen.reference == event.reference)
+        {
+            found.push(event);
+        }
+    }
+    found
+}
+
+/// The ids of the actors in `actor_mesh`, as they report them.
+async fn actor_ids(
+    instance: &impl hyperactor::context::Actor,
+    actor_mesh: &ActorMesh<TestActor>,
+) -> Vec<String> {
+    let (tx, mut rx) = instance.mailbox().open_port();
+    actor_mesh.cast(instance, GetActorId(tx.bind())).unwrap();
+    let mut ids = Vec::new();
+    for _ in 0..2 {
+        let (id, _) = tokio::time::timeout(Duration::from_secs(30), rx.recv())
+            .await
+            .expect("GetActorId timed out")
+            .unwrap();
+        ids.push(id.to_string());
+    }
+    ids
+}
+
+/// Procs and actors spawned after the admin show up on the event
+/// stream as they start, and stopping an actor mesh is reported for
+/// each of its actors.
+#[tokio::test]
+async fn test_events_report_started_and_stopped_actors() {
+    let cx = context().await;
+    let instance = cx.actor_instance;
+    let mut host_mesh = HostMesh::local_with_bootstrap(BootstrapCommand::from(env!(
+        "CARGO_BIN_EXE_hyperactor_mesh_test_bootstrap"
+    )))
+    .await
+    .unwrap();
+
+    let admin = spawn_admin(
+        [&host_mesh],
+        instance,
+        Some("127.0.0.1:0".parse().unwrap()),
+        None,
+    )
+    .await
+    .unwrap();
+    let admin_url = admin
+        .get_admin_addr(instance)
+        .await
+        .unwrap()
+        .addr
+        .expect("admin must report an address");
+    let mut stream = MeshEventStream::connect(reqwest::Client::new(), admin_url, None)
+        .await
+        .unwrap();
+
+    let proc_mesh = host_mesh
+        .spawn(instance, "watched", extent!(replica = 2), None)
+        .await
+        .unwrap();
+    let procs: Vec<String> = (0..2)
+        .map(|rank| proc_mesh.get(rank).unwrap().proc_id().to_string())
+        .collect();
+    expect_events(&mut stream, MeshEventKind::ProcStarted, &procs).await;
+
+    let mut actor_mesh: ActorMesh<TestActor> =
+        proc_mesh.spawn(instance, "test", &()).await.unwrap();
+    let actors = actor_ids(instance, &actor_mesh).await;
+    expect_events(&mut stream, MeshEventKind::ActorStarted, &actors).await;
+
+    // Stop real actors: the proc agents forward their terminal
+    // supervision events to the admin.
+    actor_mesh
+        .stop(instance, "test stop".to_string())
+        .await
+        .unwrap();
+    let stopped = expect_events(&mut stream, MeshEventKind::ActorStopped, &actors).await;
+    assert!(stopped.iter().all(|event| event.status.contains("stop")));
+    assert!(stopped.windows(2).all(|pair| pair[0].id < pair[1].id));
+
+    host_mesh.shutdown(instance).await.unwrap();
+}
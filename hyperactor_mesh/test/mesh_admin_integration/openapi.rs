@@ -74,6 +74,12 @@ impl OpenApiValidator {
             "/v1/pyspy_dump/{proc_reference}",
             "/v1/pyspy_profile_svg/{proc_reference}",
             "/v1/tree",
+            "/v1/events",
+            "/v1/actions/stop_actor/{actor_reference}",
+            "/v1/actions/stop_proc/{proc_reference}",
+            "/v1/actions/drain_proc/{proc_reference}",
+            "/v1/actions/drain_host/{host_reference}",
+            "/v1/actions/audit",
             "/v1/schema",
             "/v1/schema/admin",
             "/v1/schema/error",
@@ -111,6 +117,10 @@ impl OpenApiValidator {
             ("/v1/pyspy/{proc_reference}", "get"),
             ("/v1/pyspy_dump/{proc_reference}", "post"),
             ("/v1/pyspy_profile_svg/{proc_reference}", "post"),
+            ("/v1/actions/stop_actor/{actor_reference}", "post"),
+            ("/v1/actions/stop_proc/{proc_reference}", "post"),
+            ("/v1/actions/drain_proc/{proc_reference}", "post"),
+            ("/v1/actions/drain_host/{host_reference}", "post"),
         ];
         for &(path, method) in cases {
             let params = self
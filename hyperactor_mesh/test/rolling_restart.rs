@@ -0,0 +1,126 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! End-to-end test for `ProcMesh::rolling_restart` on a local
+//! multiprocess host mesh.
+//!
+//! Uses only public `hyperactor_mesh` APIs: the procs are real child
+//! processes running the `hyperactor_mesh_test_bootstrap` binary, so
+//! the restart goes through the host agent, the bootstrap proc
+//! manager, and the replacements' `ProcAgent`s.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use hyperactor::reference::ActorId;
+use hyperactor_config::Attrs;
+use hyperactor_mesh::ActorMesh;
+use hyperactor_mesh::bootstrap::BootstrapCommand;
+use hyperactor_mesh::global_context::context;
+use hyperactor_mesh::host_mesh::HostMesh;
+use hyperactor_mesh::testactor::GetActorId;
+use hyperactor_mesh::testactor::GetConfigAttrs;
+use hyperactor_mesh::testactor::TestActor;
+use ndslice::extent;
+
+/// The sorted ids of the actors in `actor_mesh`, as they report them.
+async fn actor_ids(
+    instance: &impl hyperactor::context::Actor,
+    actor_mesh: &ActorMesh<TestActor>,
+) -> Vec<ActorId> {
+    let (tx, mut rx) = instance.mailbox().open_port();
+    actor_mesh.cast(instance, GetActorId(tx.bind())).unwrap();
+    let mut ids = Vec::new();
+    for _ in 0..2 {
+        let (id, _) = tokio::time::timeout(Duration::from_secs(30), rx.recv())
+            .await
+            .expect("GetActorId timed out")
+            .unwrap();
+        ids.push(id);
+    }
+    ids.sort();
+    ids
+}
+
+/// `HOST_SPAWN_READY_TIMEOUT` as seen by each actor in `actor_mesh`.
+async fn spawn_ready_timeouts(
+    instance: &impl hyperactor::context::Actor,
+    actor_mesh: &ActorMesh<TestActor>,
+) -> Vec<Duration> {
+    let (tx, mut rx) = instance.mailbox().open_port();
+    actor_mesh
+        .cast(instance, GetConfigAttrs(tx.bind()))
+        .unwrap();
+    let mut timeouts = Vec::new();
+    for _ in 0..2 {
+        let attrs = tokio::time::timeout(Duration::from_secs(30), rx.recv())
+            .await
+            .expect("GetConfigAttrs timed out")
+            .unwrap();
+        let attrs = bincode::deserialize::<Attrs>(&attrs).unwrap();
+        timeouts.push(
+            attrs
+                .get(hyperactor::config::HOST_SPAWN_READY_TIMEOUT)
+                .copied()
+                .unwrap_or_default(),
+        );
+    }
+    timeouts
+}
+
+/// Restarting a mesh one proc at a time replaces its procs with ones
+/// running under the new environment, and recreates its actors under
+/// the same ids.
+#[tokio::test]
+async fn test_rolling_restart() {
+    let cx = context().await;
+    let instance = cx.actor_instance;
+    let mut host_mesh = HostMesh::local_with_bootstrap(BootstrapCommand::from(env!(
+        "CARGO_BIN_EXE_hyperactor_mesh_test_bootstrap"
+    )))
+    .await
+    .unwrap();
+
+    let proc_mesh = host_mesh
+        .spawn(instance, "restarted", extent!(replica = 2), None)
+        .await
+        .unwrap();
+    let actor_mesh: ActorMesh<TestActor> = proc_mesh.spawn(instance, "test", &()).await.unwrap();
+
+    let restarted_timeout = Duration::from_mins(7);
+    let ids_before = actor_ids(instance, &actor_mesh).await;
+    assert!(
+        spawn_ready_timeouts(instance, &actor_mesh)
+            .await
+            .iter()
+            .all(|timeout| *timeout != restarted_timeout)
+    );
+
+    proc_mesh
+        .rolling_restart(
+            instance,
+            None,
+            HashMap::from([(
+                "HYPERACTOR_HOST_SPAWN_READY_TIMEOUT".to_string(),
+                "7m".to_string(),
+            )]),
+            1,
+        )
+        .await
+        .unwrap();
+
+    // The actors run on the replacements, which picked up the new
+    // environment, under the ids they had before.
+    assert_eq!(
+        spawn_ready_timeouts(instance, &actor_mesh).await,
+        vec![restarted_timeout; 2]
+    );
+    assert_eq!(actor_ids(instance, &actor_mesh).await, ids_before);
+
+    host_mesh.shutdown(instance).await.unwrap();
+}
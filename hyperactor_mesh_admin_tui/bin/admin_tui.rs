@@ -12,6 +12,7 @@
 //! delegates to [`hyperactor_mesh_admin_tui_lib::run`] for the actual TUI.
 
 use std::io;
+use std::path::PathBuf;
 
 use clap::Parser;
 use hyperactor_mesh_admin_tui_lib::LangName;
@@ -31,8 +32,8 @@ struct Args {
     /// Accepts `host:port` (scheme auto-detected) or an explicit URL
     /// like `https://host:port`. `mast_conda:///<job-name>` handles
     /// are currently disabled (returns an error).
-    #[arg(long, short)]
-    addr: String,
+    #[arg(long, short, required_unless_present = "replay")]
+    addr: Option<String>,
 
     /// Admin port override (currently unused — `mast_conda:///`
     /// resolution is disabled).
@@ -66,6 +67,25 @@ struct Args {
     /// Run diagnostics and print a JSON report to stdout, then exit.
     #[arg(long)]
     diagnose: bool,
+
+    /// Replay snapshot bundles offline instead of attaching to a
+    /// live admin server.
+    ///
+    /// Accepts a snapshot bundle, a delta chain, or a directory whose
+    /// subdirectories are bundles and/or chains. Step between
+    /// snapshots with ←/→.
+    #[arg(long, conflicts_with_all = ["addr", "diagnose"])]
+    replay: Option<PathBuf>,
+
+    /// File holding the mesh admin action token, sent as a bearer
+    /// credential with stop/drain actions and profiles.
+    #[arg(long, env = "HYPERACTOR_MESH_ADMIN_ACTION_TOKEN_FILE")]
+    action_token_file: Option<PathBuf>,
+
+    /// File where saved search views are persisted. Defaults to
+    /// `$XDG_CONFIG_HOME/monarch/admin_tui_views.json`.
+    #[arg(long, env = "HYPERACTOR_MESH_ADMIN_TUI_VIEWS_FILE")]
+    views_file: Option<PathBuf>,
 }
 
 #[cfg(fbcode_build)]
@@ -81,20 +101,24 @@ async fn main() -> io::Result<()> {
 }
 
 async fn run() -> io::Result<()> {
-    let mut args = Args::parse();
+    let args = Args::parse();
 
     // Resolve the admin address via AdminHandle (handles mast_conda:///,
     // bare host:port scheme inference, and explicit URLs uniformly).
-    args.addr = hyperactor_mesh::mesh_admin::AdminHandle::parse(&args.addr)
-        .resolve(args.admin_port)
-        .await
-        .unwrap_or_else(|e| {
-            eprintln!("{:#}", e);
-            std::process::exit(1);
-        });
+    // Replay runs offline and has no address.
+    let addr = match &args.addr {
+        Some(addr) => hyperactor_mesh::mesh_admin::AdminHandle::parse(addr)
+            .resolve(args.admin_port)
+            .await
+            .unwrap_or_else(|e| {
+                eprintln!("{:#}", e);
+                std::process::exit(1);
+            }),
+        None => String::new(),
+    };
 
     let config = TuiConfig {
-        addr: args.addr,
+        addr,
         refresh_ms: args.refresh_ms,
         theme: args.theme,
         lang: args.lang,
@@ -102,6 +126,9 @@ async fn run() -> io::Result<()> {
         tls_cert: args.tls_cert,
         tls_key: args.tls_key,
         diagnose: args.diagnose,
+        replay: args.replay,
+        action_token_file: args.action_token_file,
+        views_file: args.views_file,
     };
 
     hyperactor_mesh_admin_tui_lib::run(config).await
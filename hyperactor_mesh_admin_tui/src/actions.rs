@@ -9,6 +9,8 @@
 use hyperactor::reference::ProcId;
 use hyperactor_mesh::introspect::NodeRef;
 
+use crate::operator::OperatorAction;
+
 /// Result of handling a key event.
 #[derive(Debug)]
 pub(crate) enum KeyResult {
@@ -26,4 +28,13 @@ pub(crate) enum KeyResult {
     RunPySpy(ProcId),
     /// Fetch the config dump for the given proc.
     RunConfig(ProcId),
+    /// Open a confirmation overlay for an operator action (OP-1).
+    ProposeAction(OperatorAction),
+    /// Send the operator action pending in the overlay (OP-1).
+    ConfirmAction,
+    /// A search was committed; expand the tree so matches load
+    /// (SE-3).
+    SearchCommitted,
+    /// Load every mesh member and open the rank heatmap (HM-*).
+    OpenHeatmap,
 }
@@ -9,6 +9,7 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::io;
+use std::time::SystemTime;
 
 use crossterm::event::Event;
 use crossterm::event::EventStream;
@@ -33,6 +34,7 @@ use crate::FetchState;
 use crate::KeyResult;
 use crate::LangName;
 use crate::NodeType;
+use crate::Subject;
 use crate::Theme;
 use crate::ThemeName;
 use crate::TreeNode;
@@ -42,17 +44,29 @@ use crate::collapse_all;
 use crate::collect_expanded_refs;
 use crate::collect_failed_refs;
 use crate::collect_refs;
+use crate::collect_search_frontier;
+use crate::collect_unfetched_refs;
 use crate::derive_label;
 use crate::diagnostics::run_diagnostics;
 use crate::fetch_with_join;
 use crate::find_at_depth_from_root_mut;
+use crate::flatten_matching;
 use crate::flatten_tree;
 use crate::get_cached_payload;
+use crate::heatmap::HeatmapView;
+use crate::heatmap::MeshGrid;
+use crate::heatmap::collect_meshes;
 use crate::is_failed_node;
 use crate::is_stopped_node;
 use crate::is_system_node;
+use crate::operator::DEFAULT_PROFILE_DURATION_S;
+use crate::operator::OperatorAction;
+use crate::operator::run_operator_action;
 use crate::overlay::Overlay;
 use crate::render::ui;
+use crate::replay::Timeline;
+use crate::search::SavedViews;
+use crate::search::SearchState;
 use crate::sorted_children;
 use crate::timeouts::TuiTimeoutPolicy;
 
@@ -126,10 +140,32 @@ pub(crate) struct App {
     /// The running or completed overlay-producing async job (TUI-21).
     /// `None` iff `overlay` is also `None`.
     pub(crate) active_job: Option<ActiveJob>,
-    /// Active overlay (py-spy, config, or diagnostics content).
+    /// Active overlay (py-spy, config, diagnostics or operator action
+    /// content).
     /// When `Some`, the detail pane renders the overlay instead of
     /// node details. Dismissed with Esc, scrolled with j/k.
     pub(crate) overlay: Option<Overlay>,
+
+    /// Snapshot timeline when replaying offline (RP-*). When `Some`,
+    /// every payload comes from the current frame and `client` /
+    /// `base_url` are unused (RP-2).
+    pub(crate) replay: Option<Timeline>,
+
+    /// Bearer token sent with operator actions (OP-6). `None` relies
+    /// on the client certificate alone.
+    pub(crate) action_token: Option<String>,
+
+    /// The `/` prompt and the tree filter it applies (SE-*, SR-*).
+    pub(crate) search: SearchState,
+    /// Saved views available to `@name` terms (SR-2).
+    pub(crate) views: SavedViews,
+    /// Nodes expanded to load a committed search or the heatmap,
+    /// collapsed again once neither needs them (SE-1).
+    auto_expanded: Vec<(NodeRef, usize)>,
+
+    /// Rank heatmap selection; `Some` while the heatmap replaces the
+    /// tree pane (HM-*).
+    pub(crate) heatmap: Option<HeatmapView>,
 }
 
 impl App {
@@ -168,9 +204,21 @@ impl App {
             lang_name,
             active_job: None,
             overlay: None,
+            replay: None,
+            action_token: None,
+            search: SearchState::default(),
+            views: SavedViews::default(),
+            auto_expanded: Vec::new(),
+            heatmap: None,
         }
     }
 
+    /// Switch the app to offline replay of `timeline` (RP-2).
+    pub(crate) fn with_replay(mut self, timeline: Timeline) -> Self {
+        self.replay = Some(timeline);
+        self
+    }
+
     // Controlled mutation API - single entry point for tree modifications.
 
     /// Replace the entire tree structure.
@@ -209,11 +257,17 @@ impl App {
     /// - `force = true`: bypass cache (used in refresh).
     /// - Errors are always retried (preserve current behavior).
     /// - Stale entries (`generation < refresh_gen`) are refetched.
+    ///
+    /// In replay mode the payload is served from the current frame
+    /// instead (RP-2).
     pub(crate) async fn fetch_node_state(
         &mut self,
         reference: &NodeRef,
         force: bool,
     ) -> FetchState<NodePayload> {
+        if let Some(timeline) = &self.replay {
+            return timeline.fetch(reference, self.refresh_gen);
+        }
         fetch_with_join(
             &self.client,
             &self.base_url,
@@ -239,15 +293,44 @@ impl App {
     /// ancestor is collapsed is hidden. The returned indices are in
     /// on-screen order (top-to-bottom).
     ///
+    /// While a search filter is active, expansion state is ignored and
+    /// only matching nodes and their ancestors are returned (SE-1).
+    ///
     /// Purely derived from tree structure - no caching, correct by
     /// construction.
     pub(crate) fn visible_rows(&self) -> VisibleRows<'_> {
         match self.tree() {
+            Some(root) if self.search.is_active() => {
+                VisibleRows::new(flatten_matching(root, &|node| self.search_matches(node)))
+            }
             Some(root) => VisibleRows::new(flatten_tree(root)),
             None => VisibleRows::new(Vec::new()),
         }
     }
 
+    /// True if `node` itself satisfies the active search query.
+    pub(crate) fn search_matches(&self, node: &TreeNode) -> bool {
+        self.search.query.matches(&Subject {
+            node,
+            payload: self.get_cached_payload(&node.reference),
+        })
+    }
+
+    /// Positions of visible rows that match the search themselves,
+    /// as opposed to ancestors shown for context.
+    pub(crate) fn search_match_positions(&self) -> Vec<usize> {
+        if !self.search.is_active() {
+            return Vec::new();
+        }
+        self.visible_rows()
+            .as_slice()
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| self.search_matches(row.node))
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
     /// Get the currently selected node's type and label.
     ///
     /// Returns `None` if no node is selected or tree is empty.
@@ -275,6 +358,12 @@ impl App {
         self.error = None;
         self.refresh_gen += 1;
 
+        // RP-2: in replay mode the whole frame is the cache, so tree
+        // building below never leaves it.
+        if let Some(timeline) = &self.replay {
+            self.fetch_cache = timeline.seed_cache(self.refresh_gen);
+        }
+
         // Save expanded and failed state before rebuilding.
         // Track (reference, depth) pairs to handle dual appearances correctly.
         let mut expanded_keys = HashSet::new();
@@ -349,7 +438,8 @@ impl App {
             children: root_children,
         }));
 
-        // Prune stale cache entries (collect owned refs to avoid borrow issues).
+        // Prune stale cache entries (collect owned refs to avoid borrow
+        // issues). Replay keeps the whole frame cached (RP-2).
         let live_refs: HashSet<NodeRef> = if let Some(root) = self.tree() {
             let mut refs = HashSet::new();
             collect_refs(root, &mut refs);
@@ -357,8 +447,10 @@ impl App {
         } else {
             HashSet::new()
         };
-        self.fetch_cache
-            .retain(|k, _| matches!(k, NodeRef::Root) || live_refs.contains(k));
+        if self.replay.is_none() {
+            self.fetch_cache
+                .retain(|k, _| matches!(k, NodeRef::Root) || live_refs.contains(k));
+        }
 
         // Restore selection position.
         let rows = self.visible_rows();
@@ -544,6 +636,278 @@ impl App {
         false
     }
 
+    /// Load what a committed search needs to see (SE-3).
+    pub(crate) async fn expand_for_search(&mut self) {
+        let needs_payload = self.search.query.needs_payload();
+        self.expand_all(needs_payload).await;
+    }
+
+    /// Expand collapsed hosts and procs until none remain, recording
+    /// them in `auto_expanded`. With `fetch_placeholders`, also fetch
+    /// every placeholder's payload.
+    async fn expand_all(&mut self, fetch_placeholders: bool) {
+        loop {
+            let frontier = match self.tree() {
+                Some(root) => collect_search_frontier(root),
+                None => return,
+            };
+            let mut progressed = false;
+            for (reference, depth) in frontier {
+                if self.expand_node(&reference, depth).await {
+                    self.auto_expanded.push((reference, depth));
+                    progressed = true;
+                }
+            }
+            if !progressed {
+                break;
+            }
+        }
+        if fetch_placeholders {
+            let unfetched = self.tree().map(collect_unfetched_refs).unwrap_or_default();
+            for reference in unfetched {
+                self.fetch_node_state(&reference, false).await;
+            }
+        }
+    }
+
+    /// Collapse `auto_expanded` once neither a search filter nor the
+    /// heatmap needs it (SE-1).
+    fn release_auto_expanded(&mut self) {
+        if self.search.is_active() || self.heatmap.is_some() {
+            return;
+        }
+        let expanded = std::mem::take(&mut self.auto_expanded);
+        self.mutate_tree(|root| {
+            for (reference, depth) in &expanded {
+                if let Some(node) = find_at_depth_from_root_mut(root, reference, *depth) {
+                    node.expanded = false;
+                }
+            }
+        });
+    }
+
+    /// Re-derive the cursor after the filter changed, selecting the
+    /// first match.
+    pub(crate) fn on_search_changed(&mut self) {
+        let len = self.visible_rows().len();
+        self.cursor.update_len(len);
+        if let Some(&first) = self.search_match_positions().first() {
+            self.cursor.set_pos(first);
+        }
+        self.ensure_cursor_visible();
+    }
+
+    /// Drop the filter and collapse what committed searches expanded
+    /// (SE-1).
+    fn clear_search(&mut self) {
+        self.search.clear();
+        self.release_auto_expanded();
+        self.on_search_changed();
+    }
+
+    /// Actor meshes in the loaded tree (HM-3). Handler ages are
+    /// measured against the replayed frame's capture time in replay.
+    pub(crate) fn heatmap_grids(&self) -> Vec<MeshGrid> {
+        let now = match &self.replay {
+            Some(timeline) => timeline.current().snapshot_ts,
+            None => SystemTime::now(),
+        };
+        match self.tree() {
+            Some(root) => collect_meshes(root, &self.fetch_cache, now),
+            None => Vec::new(),
+        }
+    }
+
+    /// Load every mesh member and show the heatmap, preselecting the
+    /// tree's selected actor if it belongs to a mesh.
+    pub(crate) async fn open_heatmap(&mut self) {
+        let selected = self.selected_reference().cloned();
+        self.expand_all(true).await;
+        let grids = self.heatmap_grids();
+        let mut view = HeatmapView::default();
+        if let Some(reference) = selected {
+            view.focus(&grids, &reference);
+        }
+        self.heatmap = Some(view);
+    }
+
+    /// Refetch the selected mesh's members and their procs, so cell
+    /// colors follow the refresh cadence. Fresh cache entries are not
+    /// refetched.
+    pub(crate) async fn refresh_heatmap(&mut self) {
+        let Some(view) = &self.heatmap else {
+            return;
+        };
+        let grids = self.heatmap_grids();
+        let Some(idx) = view.mesh_index(&grids) else {
+            return;
+        };
+        let mut refs = Vec::new();
+        for cell in grids[idx].cells.iter().flatten().flatten() {
+            if let NodeRef::Actor(actor_id) = &cell.actor {
+                refs.push(NodeRef::Proc(actor_id.proc_id().clone()));
+            }
+            refs.push(cell.actor.clone());
+        }
+        for reference in refs {
+            self.fetch_node_state(&reference, false).await;
+        }
+    }
+
+    /// Close the heatmap. With `drill`, select its selected member in
+    /// the tree and keep the expansions that make it visible.
+    fn close_heatmap(&mut self, drill: bool) {
+        let target = drill
+            .then(|| {
+                let grids = self.heatmap_grids();
+                let view = self.heatmap.as_ref()?;
+                view.selected(&grids).map(|cell| cell.actor.clone())
+            })
+            .flatten();
+        self.heatmap = None;
+        if target.is_some() {
+            self.auto_expanded.clear();
+        } else {
+            self.release_auto_expanded();
+        }
+        let rows = self.visible_rows();
+        let pos = target.and_then(|t| {
+            rows.as_slice()
+                .iter()
+                .position(|row| row.node.reference == t)
+        });
+        self.cursor.update_len(rows.len());
+        if let Some(pos) = pos {
+            self.cursor.set_pos(pos);
+        }
+        self.ensure_cursor_visible();
+    }
+
+    /// Handle a key while the heatmap is open. Returns `None` for keys
+    /// it does not own, which then reach the replay slider.
+    fn on_heatmap_key(&mut self, key: KeyEvent) -> Option<KeyResult> {
+        let grids = self.heatmap_grids();
+        let view = self.heatmap.as_mut()?;
+        let moved = match key.code {
+            KeyCode::Char('q') => {
+                self.should_quit = true;
+                return Some(KeyResult::None);
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.should_quit = true;
+                return Some(KeyResult::None);
+            }
+            KeyCode::Up | KeyCode::Char('k') => view.step(&grids, -1, 0),
+            KeyCode::Down | KeyCode::Char('j') => view.step(&grids, 1, 0),
+            KeyCode::Left | KeyCode::Char('h') => view.step(&grids, 0, -1),
+            KeyCode::Right | KeyCode::Char('l') => view.step(&grids, 0, 1),
+            KeyCode::Tab => view.cycle_mesh(&grids, true),
+            KeyCode::BackTab => view.cycle_mesh(&grids, false),
+            KeyCode::Char('v') => {
+                view.metric = view.metric.next();
+                false
+            }
+            KeyCode::Enter => {
+                self.close_heatmap(true);
+                true
+            }
+            KeyCode::Esc | KeyCode::Char('m') => {
+                self.close_heatmap(false);
+                true
+            }
+            KeyCode::Char('[' | ']' | '{' | '}') => return None,
+            _ => false,
+        };
+        Some(if moved {
+            KeyResult::DetailChanged
+        } else {
+            KeyResult::None
+        })
+    }
+
+    /// Move the cursor to the next (`forward`) or previous match,
+    /// wrapping around.
+    fn jump_to_match(&mut self, forward: bool) -> bool {
+        let positions = self.search_match_positions();
+        let pos = self.cursor.pos();
+        let target = if forward {
+            positions
+                .iter()
+                .copied()
+                .find(|&p| p > pos)
+                .or(positions.first().copied())
+        } else {
+            positions
+                .iter()
+                .rev()
+                .copied()
+                .find(|&p| p < pos)
+                .or(positions.last().copied())
+        };
+        match target {
+            Some(target) if target != pos => {
+                self.cursor.set_pos(target);
+                self.ensure_cursor_visible();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Replace the prompt text; refilter if the query changed (SR-1).
+    fn set_search_input(&mut self, input: String) -> KeyResult {
+        let before = self.search.applied.clone();
+        self.search.set_input(input, &self.views);
+        if self.search.applied == before {
+            return KeyResult::None;
+        }
+        self.on_search_changed();
+        KeyResult::DetailChanged
+    }
+
+    /// Handle a key while the `/` prompt has focus (SE-2).
+    fn on_search_key(&mut self, key: KeyEvent) -> KeyResult {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => {
+                self.clear_search();
+                KeyResult::DetailChanged
+            }
+            KeyCode::Enter => {
+                let before = self.search.applied.clone();
+                if self.search.run_command(&mut self.views) {
+                    if self.search.applied == before {
+                        return KeyResult::None;
+                    }
+                    self.on_search_changed();
+                    return KeyResult::DetailChanged;
+                }
+                self.search.commit();
+                if self.search.is_active() {
+                    KeyResult::SearchCommitted
+                } else {
+                    KeyResult::None
+                }
+            }
+            KeyCode::Backspace => {
+                let mut input = self.search.input.clone();
+                input.pop();
+                self.set_search_input(input)
+            }
+            KeyCode::Char('c') if ctrl => {
+                self.should_quit = true;
+                KeyResult::None
+            }
+            KeyCode::Char('u') if ctrl => self.set_search_input(String::new()),
+            KeyCode::Char(c) if !ctrl => {
+                let mut input = self.search.input.clone();
+                input.push(c);
+                self.set_search_input(input)
+            }
+            _ => KeyResult::None,
+        }
+    }
+
     /// Update the right-hand detail pane for the currently selected
     /// row.
     ///
@@ -556,8 +920,14 @@ impl App {
         self.detail = None;
         self.detail_error = None;
 
-        // Get reference first (releases tree borrow).
-        let reference = self.selected_reference().cloned();
+        // Get reference first (releases tree borrow). The heatmap's
+        // selected member takes precedence over the tree selection.
+        let reference = match &self.heatmap {
+            Some(view) => view
+                .selected(&self.heatmap_grids())
+                .map(|cell| cell.actor.clone()),
+            None => self.selected_reference().cloned(),
+        };
 
         if let Some(node_ref) = reference {
             let state = self.fetch_node_state(&node_ref, false).await;
@@ -613,6 +983,24 @@ impl App {
         }
     }
 
+    /// Resolve the `x` (stop/drain) action for the current selection
+    /// (OP-5).
+    ///
+    /// - Actor selected → stop that actor.
+    /// - Proc selected → stop that proc.
+    /// - Host selected → drain that host.
+    /// - Root / empty tree → `None`.
+    pub(crate) fn stop_action_target(&self) -> Option<OperatorAction> {
+        let rows = self.visible_rows();
+        let row = rows.get(&self.cursor)?;
+        match &row.node.reference {
+            NodeRef::Actor(actor_id) => Some(OperatorAction::StopActor(actor_id.clone())),
+            NodeRef::Proc(proc_id) => Some(OperatorAction::StopProc(proc_id.clone())),
+            NodeRef::Host(agent_id) => Some(OperatorAction::DrainHost(agent_id.clone())),
+            NodeRef::Root => None,
+        }
+    }
+
     /// Set the active job and build its overlay. TUI-21: both fields
     /// are always set together. Replacing a prior variant drops its
     /// receiver, cancelling any in-flight async work (PY-2).
@@ -753,6 +1141,56 @@ impl App {
         });
     }
 
+    /// Open the confirmation overlay for `action` (OP-1). Nothing is
+    /// sent until [`App::confirm_action`].
+    pub(crate) fn propose_action(&mut self, action: OperatorAction) {
+        self.set_job(ActiveJob::Operator {
+            action,
+            confirmed: false,
+            rx: None,
+            lines: vec![],
+            completed_at: None,
+        });
+    }
+
+    /// Send the pending operator action and wait for its result in
+    /// the same overlay. No-op unless an unconfirmed action is open.
+    pub(crate) fn confirm_action(&mut self) {
+        let Some(ActiveJob::Operator {
+            action,
+            confirmed,
+            rx,
+            ..
+        }) = &mut self.active_job
+        else {
+            return;
+        };
+        if *confirmed {
+            return;
+        }
+        let op = match action {
+            OperatorAction::Profile { .. } => crate::timeouts::RequestOp::PySpyProfile,
+            _ => crate::timeouts::RequestOp::OperatorAction,
+        };
+        let timeout = self.policy.request_timeout(op);
+        let (tx, new_rx) = oneshot::channel();
+        *confirmed = true;
+        *rx = Some(new_rx);
+        // TP-9: the timeout is applied inside run_operator_action.
+        let fut = run_operator_action(
+            self.client.clone(),
+            self.base_url.clone(),
+            self.action_token.clone(),
+            action.clone(),
+            timeout,
+            self.theme.scheme,
+        );
+        tokio::spawn(async move {
+            let _ = tx.send(fut.await);
+        });
+        self.rebuild_overlay();
+    }
+
     /// Handle a single keypress and update in-memory UI state.
     ///
     /// Returns a `KeyResult` describing whether only the
@@ -793,6 +1231,51 @@ impl App {
             return KeyResult::None;
         }
 
+        // SE-2: the search prompt captures every key while open.
+        if self.search.editing {
+            return self.on_search_key(key);
+        }
+
+        // The heatmap owns navigation while open; only replay slider
+        // keys fall through.
+        let heatmap_open = self.heatmap.is_some();
+        if let Some(result) = self.on_heatmap_key(key) {
+            return result;
+        }
+
+        // Replay: step the time slider. RP-2: actions that need a live
+        // admin server are disabled.
+        if let Some(timeline) = &mut self.replay {
+            let plain = !key.modifiers.contains(KeyModifiers::CONTROL);
+            let target = match key.code {
+                KeyCode::Left | KeyCode::Char('[') => Some(timeline.pos().saturating_sub(1)),
+                KeyCode::Right | KeyCode::Char(']') => Some(timeline.pos() + 1),
+                KeyCode::Char('{') => Some(0),
+                KeyCode::Char('}') => Some(usize::MAX),
+                KeyCode::Char('d')
+                | KeyCode::Char('p')
+                | KeyCode::Char('C')
+                | KeyCode::Char('x')
+                | KeyCode::Char('P')
+                    if plain =>
+                {
+                    // OP-4: operator actions are disabled too.
+                    return KeyResult::None;
+                }
+                _ => None,
+            };
+            if let Some(target) = target {
+                return if timeline.seek(target) {
+                    KeyResult::NeedsRefresh
+                } else {
+                    KeyResult::None
+                };
+            }
+        }
+        if heatmap_open {
+            return KeyResult::None;
+        }
+
         let rows = self.visible_rows();
 
         match key.code {
@@ -932,6 +1415,42 @@ impl App {
                     KeyResult::None
                 }
             }
+            KeyCode::Char('x') => {
+                // OP-1/OP-5: propose stop/drain; sent only on confirm.
+                match self.stop_action_target() {
+                    Some(action) => KeyResult::ProposeAction(action),
+                    None => KeyResult::None,
+                }
+            }
+            KeyCode::Char('P') => {
+                // OP-5: same target resolution as pyspy_proc_ref.
+                match self.pyspy_proc_ref() {
+                    Some(proc) => KeyResult::ProposeAction(OperatorAction::Profile {
+                        proc,
+                        duration_s: DEFAULT_PROFILE_DURATION_S,
+                    }),
+                    None => KeyResult::None,
+                }
+            }
+            KeyCode::Char('m') => KeyResult::OpenHeatmap,
+            KeyCode::Char('/') => {
+                // Open the search prompt, keeping the current filter
+                // text for editing.
+                self.search.editing = true;
+                self.search.message = None;
+                KeyResult::None
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                if self.jump_to_match(key.code == KeyCode::Char('n')) {
+                    KeyResult::DetailChanged
+                } else {
+                    KeyResult::None
+                }
+            }
+            KeyCode::Esc if self.search.is_active() => {
+                self.clear_search();
+                KeyResult::DetailChanged
+            }
             KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 // Page up (Ctrl+U, vi-style)
                 if self.cursor.page_up(10) {
@@ -990,6 +1509,24 @@ impl App {
                 }
                 _ => KeyResult::None,
             },
+            Some(ActiveJob::Operator {
+                action,
+                confirmed: false,
+                ..
+            }) => match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => KeyResult::ConfirmAction,
+                KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char('-') => {
+                    let max = hyperactor_config::global::get(
+                        hyperactor_mesh::config::MESH_ADMIN_PYSPY_MAX_PROFILE_DURATION,
+                    );
+                    action
+                        .step_duration(key.code != KeyCode::Char('-'), max)
+                        .map_or(KeyResult::None, KeyResult::ProposeAction)
+                }
+                _ => KeyResult::None,
+            },
+            // OP-1: a sent action is never re-sent from the overlay.
+            Some(ActiveJob::Operator { .. }) => KeyResult::None,
             None => KeyResult::None,
         }
     }
@@ -1219,6 +1756,7 @@ pub(crate) fn config_json_to_lines(
         let name = entry.get("name").and_then(|n| n.as_str()).unwrap_or("");
         let value = entry.get("value").and_then(|v| v.as_str()).unwrap_or("");
         let source = entry.get("source").and_then(|s| s.as_str()).unwrap_or("?");
+        let file = entry.get("file").and_then(|f| f.as_str());
         let changed = entry
             .get("changed_from_default")
             .and_then(|c| c.as_bool())
@@ -1264,7 +1802,13 @@ pub(crate) fn config_json_to_lines(
         lines.push(Line::from(vec![
             Span::styled(format!("    {key_name:<40}"), key_style),
             Span::styled(format!("{value:<20}"), value_style),
-            Span::styled(source.to_string(), source_style),
+            Span::styled(
+                match file {
+                    Some(file) => format!("{source} ({file})"),
+                    None => source.to_string(),
+                },
+                source_style,
+            ),
         ]));
     }
 
@@ -1303,6 +1847,15 @@ async fn recv_active_job(job: &mut Option<ActiveJob>) -> ActiveJobEvent {
             };
             ActiveJobEvent::ConfigResult(lines)
         }
+        Some(ActiveJob::Operator {
+            rx: Some(inner), ..
+        }) => {
+            let lines = match inner.await {
+                Ok(l) => l,
+                Err(_) => vec![Line::from("(action task dropped)")],
+            };
+            ActiveJobEvent::OperatorResult(lines)
+        }
         _ => std::future::pending().await,
     }
 }
@@ -1321,6 +1874,7 @@ pub(crate) fn refresh_policy_for_job(job: &Option<ActiveJob>) -> crate::timeouts
         Some(ActiveJob::Diagnostics { running: true, .. }) => RefreshPolicy::Suspend,
         Some(ActiveJob::PySpy { rx: Some(_), .. }) => RefreshPolicy::Suspend,
         Some(ActiveJob::Config { rx: Some(_), .. }) => RefreshPolicy::Suspend,
+        Some(ActiveJob::Operator { rx: Some(_), .. }) => RefreshPolicy::Suspend,
         // Completed overlays: operation finished, user is reading results.
         Some(_) => RefreshPolicy::Baseline,
     }
@@ -1336,7 +1890,9 @@ pub(crate) async fn run_app(
 ) -> io::Result<()> {
     let refresh_ms = app.policy.refresh_interval.as_millis() as u64;
     let mut refresh_interval = tokio::time::interval(app.policy.refresh_interval);
-    app.refresh_interval_label = if refresh_ms >= 1000 && refresh_ms.is_multiple_of(1000) {
+    app.refresh_interval_label = if app.replay.is_some() {
+        String::new()
+    } else if refresh_ms >= 1000 && refresh_ms.is_multiple_of(1000) {
         format!("{}s", refresh_ms / 1000)
     } else {
         format!("{}ms", refresh_ms)
@@ -1365,8 +1921,10 @@ pub(crate) async fn run_app(
                 use crate::timeouts::RefreshPolicy;
                 let effective = RefreshPolicy::Baseline
                     .join(&refresh_policy_for_job(&app.active_job));
-                if effective == RefreshPolicy::Baseline {
+                // RP-2: a replayed frame never changes on its own.
+                if effective == RefreshPolicy::Baseline && app.replay.is_none() {
                     app.refresh().await;
+                    app.refresh_heatmap().await;
                 }
             }
             maybe_event = events.next() => {
@@ -1410,6 +1968,21 @@ pub(crate) async fn run_app(
                             KeyResult::RunConfig(proc_id) => {
                                 app.start_config(proc_id);
                             }
+                            KeyResult::ProposeAction(action) => {
+                                app.propose_action(action);
+                            }
+                            KeyResult::ConfirmAction => {
+                                app.confirm_action();
+                            }
+                            KeyResult::SearchCommitted => {
+                                app.expand_for_search().await;
+                                app.on_search_changed();
+                                app.update_selected_detail().await;
+                            }
+                            KeyResult::OpenHeatmap => {
+                                app.open_heatmap().await;
+                                app.update_selected_detail().await;
+                            }
                             KeyResult::None => {}
                         }
                     }
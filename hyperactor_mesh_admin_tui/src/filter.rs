@@ -6,7 +6,43 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+//! Node predicates and the tree filter expression language.
+//!
+//! A [`Query`] is a whitespace-separated conjunction of terms:
+//!
+//! - `text` — case-insensitive substring of any searchable field
+//!   (label, reference, actor name, actor type, last handler, status
+//!   reason, failure message). `*` and `?` act as wildcards.
+//! - `field:pattern` — `pattern` must match the whole value of
+//!   `field` (one of `status`, `proc`, `host`, `actor`, `type`,
+//!   `handler`), case-insensitively, with `*`/`?` wildcards.
+//! - `field:~regex` / `~regex` — regex search instead of a glob.
+//! - `-term` — negation.
+//!
+//! Example: `status:failed proc:trainer* host:~gpu12`.
+//!
+//! Invariants:
+//!
+//! - **FQ-1 (conjunction):** A node matches iff every term matches;
+//!   the empty query matches every node.
+//! - **FQ-2 (existential-field):** A term matches iff some value of
+//!   its field matches. Fields a node does not have (e.g. `handler`
+//!   on a proc, or anything payload-derived on an unfetched
+//!   placeholder) contribute no values, so a positive term on them
+//!   fails and a negated one succeeds.
+//! - **FQ-3 (total-parse):** `Query::parse` either returns a query
+//!   or an error naming the offending term; it never panics on user
+//!   input.
+
+use std::fmt;
+
+use hyperactor_mesh::introspect::NodePayload;
 use hyperactor_mesh::introspect::NodeProperties;
+use hyperactor_mesh::introspect::NodeRef;
+use regex::Regex;
+use regex::RegexBuilder;
+
+use crate::model::TreeNode;
 
 /// Returns true if the node's actor status indicates it has stopped or failed.
 pub(crate) fn is_stopped_node(properties: &NodeProperties) -> bool {
@@ -45,6 +81,278 @@ pub(crate) fn is_system_node(properties: &NodeProperties) -> bool {
     )
 }
 
+/// A field a filter term can be restricted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Field {
+    /// Actor status (`running`, `stopped:…`, `failed:…`), plus
+    /// `stopped` / `failed` / `poisoned` classifications.
+    Status,
+    /// Name of the node's proc (the proc itself for proc nodes).
+    Proc,
+    /// Address of the node's host.
+    Host,
+    /// Actor name.
+    Actor,
+    /// Actor type.
+    Type,
+    /// Last message handler.
+    Handler,
+}
+
+impl Field {
+    /// All fields, in the order they are listed in error messages.
+    pub(crate) const ALL: &'static [Field] = &[
+        Field::Status,
+        Field::Proc,
+        Field::Host,
+        Field::Actor,
+        Field::Type,
+        Field::Handler,
+    ];
+
+    /// Name of the field as written in a query.
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Field::Status => "status",
+            Field::Proc => "proc",
+            Field::Host => "host",
+            Field::Actor => "actor",
+            Field::Type => "type",
+            Field::Handler => "handler",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|f| f.as_str() == s)
+    }
+
+    /// Whether the field is read from a fetched payload rather than
+    /// from the node reference.
+    fn needs_payload(&self) -> bool {
+        matches!(self, Field::Status | Field::Type | Field::Handler)
+    }
+}
+
+/// Compile a term pattern into a case-insensitive regex. A leading
+/// `~` selects a regex; otherwise `*`/`?` are wildcards and, when
+/// `anchored`, the glob must match the whole value.
+fn compile_pattern(text: &str, anchored: bool) -> Result<Regex, String> {
+    let source = match text.strip_prefix('~') {
+        Some(re) => re.to_string(),
+        None => {
+            let mut re = String::new();
+            for c in text.chars() {
+                match c {
+                    '*' => re.push_str(".*"),
+                    '?' => re.push('.'),
+                    c => re.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+                }
+            }
+            if anchored { format!("^(?:{re})$") } else { re }
+        }
+    };
+    RegexBuilder::new(&source)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| format!("invalid pattern `{text}`: {e}"))
+}
+
+/// One term of a [`Query`].
+#[derive(Debug, Clone)]
+struct Term {
+    negated: bool,
+    /// `None` for free text.
+    field: Option<Field>,
+    pattern: Regex,
+}
+
+/// A parsed filter expression (FQ-*).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Query {
+    terms: Vec<Term>,
+}
+
+/// Error from [`Query::parse`] (FQ-3).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QueryError(pub(crate) String);
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl Query {
+    /// Parse a filter expression.
+    pub(crate) fn parse(input: &str) -> Result<Self, QueryError> {
+        let mut terms = Vec::new();
+        for word in input.split_whitespace() {
+            let (negated, word) = match word.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() => (true, rest),
+                _ => (false, word),
+            };
+            let (field, text) = match word.split_once(':') {
+                Some((name, value)) => match Field::parse(&name.to_ascii_lowercase()) {
+                    Some(field) => (Some(field), value),
+                    None => {
+                        let known: Vec<_> = Field::ALL.iter().map(|f| f.as_str()).collect();
+                        return Err(QueryError(format!(
+                            "unknown field `{name}` (expected one of {})",
+                            known.join(", ")
+                        )));
+                    }
+                },
+                None => (None, word),
+            };
+            if text.is_empty() || text == "~" {
+                return Err(QueryError(format!("empty pattern in `{word}`")));
+            }
+            let pattern = compile_pattern(text, field.is_some()).map_err(QueryError)?;
+            terms.push(Term {
+                negated,
+                field,
+                pattern,
+            });
+        }
+        Ok(Self { terms })
+    }
+
+    /// True if the query has no terms and therefore matches every
+    /// node (FQ-1).
+    pub(crate) fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Whether evaluating the query reads fetched payloads, so that
+    /// unfetched placeholders should be fetched before matching.
+    pub(crate) fn needs_payload(&self) -> bool {
+        self.terms
+            .iter()
+            .any(|t| t.field.is_none_or(|f| f.needs_payload()))
+    }
+
+    /// Evaluate the query against one node (FQ-1, FQ-2).
+    pub(crate) fn matches(&self, subject: &Subject<'_>) -> bool {
+        self.terms.iter().all(|term| {
+            let hit = match term.field {
+                Some(field) => subject
+                    .values(field)
+                    .iter()
+                    .any(|v| term.pattern.is_match(v)),
+                None => subject
+                    .text_values()
+                    .iter()
+                    .any(|v| term.pattern.is_match(v)),
+            };
+            hit != term.negated
+        })
+    }
+}
+
+/// The searchable view of one tree node: the node itself plus its
+/// cached payload, when one has been fetched.
+pub(crate) struct Subject<'a> {
+    pub(crate) node: &'a TreeNode,
+    pub(crate) payload: Option<&'a NodePayload>,
+}
+
+impl Subject<'_> {
+    /// Values of `field` for this node (FQ-2).
+    fn values(&self, field: Field) -> Vec<String> {
+        let reference = &self.node.reference;
+        let props = self.payload.map(|p| &p.properties);
+        let mut out = Vec::new();
+        match field {
+            Field::Status => {
+                if let Some(NodeProperties::Actor { actor_status, .. }) = props {
+                    if let Some((kind, _)) = actor_status.split_once(':') {
+                        out.push(kind.to_string());
+                    }
+                    out.push(actor_status.clone());
+                }
+                if let Some(NodeProperties::Proc {
+                    is_poisoned: true, ..
+                }) = props
+                {
+                    out.push("poisoned".to_string());
+                }
+                // Placeholders only know what the parent proc reported.
+                if props.is_none() && self.node.stopped {
+                    out.push("stopped".to_string());
+                }
+                if self.node.failed || props.is_some_and(is_failed_node) {
+                    out.push("failed".to_string());
+                }
+            }
+            Field::Proc => match reference {
+                NodeRef::Proc(proc_id) => out.push(proc_id.name().to_string()),
+                NodeRef::Actor(actor_id) => out.push(actor_id.proc_id().name().to_string()),
+                NodeRef::Root | NodeRef::Host(_) => {}
+            },
+            Field::Host => {
+                match reference {
+                    NodeRef::Host(agent_id) | NodeRef::Actor(agent_id) => {
+                        out.push(agent_id.proc_id().addr().to_string())
+                    }
+                    NodeRef::Proc(proc_id) => out.push(proc_id.addr().to_string()),
+                    NodeRef::Root => {}
+                }
+                if let Some(NodeProperties::Host { addr, .. }) = props {
+                    out.push(addr.clone());
+                }
+            }
+            Field::Actor => {
+                if let NodeRef::Actor(actor_id) = reference {
+                    out.push(actor_id.name().to_string());
+                    out.push(self.node.label.clone());
+                }
+            }
+            Field::Type => {
+                if let Some(NodeProperties::Actor { actor_type, .. }) = props {
+                    out.push(actor_type.clone());
+                }
+            }
+            Field::Handler => {
+                if let Some(NodeProperties::Actor {
+                    last_message_handler: Some(handler),
+                    ..
+                }) = props
+                {
+                    out.push(handler.clone());
+                }
+            }
+        }
+        out
+    }
+
+    /// Values searched by free-text terms.
+    fn text_values(&self) -> Vec<String> {
+        let mut out = vec![self.node.label.clone(), self.node.reference.to_string()];
+        if let NodeRef::Actor(actor_id) = &self.node.reference {
+            out.push(actor_id.name().to_string());
+        }
+        if let Some(NodeProperties::Actor {
+            actor_status,
+            actor_type,
+            last_message_handler,
+            failure_info,
+            ..
+        }) = self.payload.map(|p| &p.properties)
+        {
+            out.push(actor_status.clone());
+            out.push(actor_type.clone());
+            out.extend(last_message_handler.clone());
+            if let Some(info) = failure_info {
+                out.push(info.error_message.clone());
+                out.extend(info.root_cause_name.clone());
+            }
+        }
+        out
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::SystemTime;
@@ -122,6 +430,7 @@ mod tests {
             is_poisoned: false,
             failed_actor_count: 0,
             debug: Default::default(),
+            generation: None,
         };
         assert!(!is_stopped_node(&proc_props));
     }
@@ -164,6 +473,7 @@ mod tests {
             is_poisoned: true,
             failed_actor_count: 1,
             debug: Default::default(),
+            generation: None,
         };
         assert!(is_failed_node(&props));
     }
@@ -179,7 +489,130 @@ mod tests {
             is_poisoned: false,
             failed_actor_count: 0,
             debug: Default::default(),
+            generation: None,
         };
         assert!(!is_failed_node(&props));
     }
+
+    fn actor_payload(name: &str, proc_name: &str, status: &str) -> (TreeNode, NodePayload) {
+        use std::str::FromStr;
+        let id =
+            hyperactor::reference::ActorId::from_str(&format!("unix:@gpu3,{proc_name},{name}[0]"))
+                .unwrap();
+        let payload = NodePayload {
+            identity: NodeRef::Actor(id.clone()),
+            properties: NodeProperties::Actor {
+                actor_status: status.to_string(),
+                actor_type: "monarch::Trainer".to_string(),
+                messages_processed: 0,
+                created_at: Some(SystemTime::UNIX_EPOCH),
+                last_message_handler: Some("handle_step".to_string()),
+                total_processing_time_us: 0,
+                flight_recorder: None,
+                failure_info: None,
+                is_system: false,
+            },
+            children: vec![],
+            parent: None,
+            as_of: SystemTime::UNIX_EPOCH,
+        };
+        (
+            TreeNode::from_payload(NodeRef::Actor(id), &payload),
+            payload,
+        )
+    }
+
+    fn query_matches(query: &str, node: &TreeNode, payload: Option<&NodePayload>) -> bool {
+        Query::parse(query)
+            .unwrap()
+            .matches(&Subject { node, payload })
+    }
+
+    // FQ-1: the empty query matches everything.
+    #[test]
+    fn query_empty_matches_all() {
+        let (node, _) = actor_payload("worker", "trainer_0", "running");
+        assert!(Query::parse("  ").unwrap().is_empty());
+        assert!(query_matches("", &node, None));
+    }
+
+    #[test]
+    fn query_free_text_is_case_insensitive_substring() {
+        let (node, payload) = actor_payload("philosopher", "trainer_0", "running");
+        assert!(query_matches("PHILO", &node, Some(&payload)));
+        assert!(query_matches("step", &node, Some(&payload)));
+        assert!(!query_matches("waiter", &node, Some(&payload)));
+    }
+
+    #[test]
+    fn query_field_glob_is_anchored() {
+        let (node, payload) = actor_payload("worker", "trainer_0", "running");
+        assert!(query_matches("proc:trainer*", &node, Some(&payload)));
+        assert!(!query_matches("proc:trainer", &node, Some(&payload)));
+        assert!(query_matches("proc:trainer_?", &node, Some(&payload)));
+        assert!(query_matches("actor:worker", &node, Some(&payload)));
+    }
+
+    #[test]
+    fn query_field_regex() {
+        let (node, payload) = actor_payload("worker", "trainer_0", "running");
+        assert!(query_matches("host:~gpu3$", &node, Some(&payload)));
+        assert!(!query_matches("host:~gpu12", &node, Some(&payload)));
+        assert!(query_matches("handler:~^handle_", &node, Some(&payload)));
+    }
+
+    #[test]
+    fn query_status_matches_kind_and_reason() {
+        let (node, payload) = actor_payload("worker", "p", "failed:panic in handler");
+        assert!(query_matches("status:failed", &node, Some(&payload)));
+        assert!(query_matches("status:*panic*", &node, Some(&payload)));
+        assert!(!query_matches("status:stopped", &node, Some(&payload)));
+    }
+
+    // FQ-1: terms are conjunctive; '-' negates.
+    #[test]
+    fn query_conjunction_and_negation() {
+        let (node, payload) = actor_payload("worker", "trainer_0", "running");
+        assert!(query_matches(
+            "status:running proc:trainer*",
+            &node,
+            Some(&payload)
+        ));
+        assert!(!query_matches(
+            "status:running proc:eval*",
+            &node,
+            Some(&payload)
+        ));
+        assert!(query_matches("-status:failed", &node, Some(&payload)));
+        assert!(!query_matches("-worker", &node, Some(&payload)));
+    }
+
+    // FQ-2: payload-derived fields have no values on a placeholder.
+    #[test]
+    fn query_placeholder_has_no_payload_fields() {
+        let (node, _) = actor_payload("worker", "trainer_0", "running");
+        assert!(!query_matches("type:*", &node, None));
+        assert!(query_matches("-handler:*", &node, None));
+        assert!(query_matches("proc:trainer_0", &node, None));
+    }
+
+    // FQ-3: bad input is an error, not a panic.
+    #[test]
+    fn query_parse_errors() {
+        let err = Query::parse("colour:red").unwrap_err();
+        assert!(err.0.contains("unknown field `colour`"), "{err}");
+        assert!(Query::parse("proc:").is_err());
+        assert!(Query::parse("proc:~(").is_err());
+    }
+
+    #[test]
+    fn query_needs_payload() {
+        assert!(
+            !Query::parse("proc:a host:b actor:c")
+                .unwrap()
+                .needs_payload()
+        );
+        assert!(Query::parse("status:failed").unwrap().needs_payload());
+        assert!(Query::parse("worker").unwrap().needs_payload());
+    }
 }
@@ -346,6 +346,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: None,
             },
         );
         assert_eq!(derive_label(&payload), "myproc  (4 actors: 4 user)");
@@ -364,6 +365,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: None,
             },
         );
         assert_eq!(
@@ -385,6 +387,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: None,
             },
         );
         assert_eq!(
@@ -410,6 +413,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: None,
             },
         );
         assert!(derive_label(&payload).contains("3 stopped (max retained)"));
@@ -428,6 +432,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: None,
             },
         );
         let label = derive_label(&payload);
@@ -448,6 +453,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: None,
             },
         );
         assert_eq!(
@@ -469,6 +475,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: None,
             },
         );
         assert_eq!(derive_label(&payload), "myproc  (2 actors: 2 stopped)");
@@ -491,6 +498,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: None,
             },
         );
         let label = derive_label(&payload);
@@ -511,6 +519,7 @@ mod tests {
                 is_poisoned: true,
                 failed_actor_count: 1,
                 debug: Default::default(),
+                generation: None,
             },
         );
         let label = derive_label(&payload);
@@ -530,6 +539,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: None,
             },
         );
         let label = derive_label(&payload);
@@ -0,0 +1,736 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Rank heatmap: actor meshes laid out on a host × rank grid.
+//!
+//! The admin API reports hosts, procs and actors but not mesh
+//! extents, so meshes are recovered from naming. `HostMesh::spawn`
+//! names the procs of a proc mesh `{mesh}_{rank}` on every host,
+//! where `rank` is the per-host rank, and an actor mesh spawns one
+//! actor under the same name on each of those procs. A mesh's grid
+//! is therefore `hosts × per-host ranks`, matching the extent the
+//! mesh was spawned with (`hosts` concatenated with `per_host`).
+//!
+//! Invariants:
+//!
+//! - **HM-1 (name-grouping):** Two actors belong to the same mesh iff
+//!   they have the same actor name and live on procs named
+//!   `{base}_{rank}` with the same `base`. System actors and procs
+//!   without a rank suffix never appear in a grid.
+//! - **HM-2 (grid-placement):** A member's row is its host, in tree
+//!   order; its column is its proc's per-host rank. When two members
+//!   claim a cell, the first in tree order wins. When ranks are
+//!   sparse (the highest rank exceeds [`MAX_COLS_PER_MEMBER`] times
+//!   the member count), columns list only the observed ranks, in
+//!   order, so that grid size is bounded by the number of members
+//!   rather than by rank values parsed from proc names.
+//! - **HM-3 (projection):** Grids are recomputed from the tree and the
+//!   fetch cache on every call; the view stores only its selection,
+//!   which is clamped to the current grid when read.
+//! - **HM-4 (relative-scale):** Numeric metrics are bucketed into
+//!   [`HEAT_LEVELS`] levels relative to the mesh's own min..max, so
+//!   a single outlier stands out. A uniform mesh is all level 0.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use hyperactor::introspect::RecordedEvent;
+use hyperactor_mesh::introspect::NodePayload;
+use hyperactor_mesh::introspect::NodeProperties;
+use hyperactor_mesh::introspect::NodeRef;
+
+use crate::FetchState;
+use crate::get_cached_payload;
+use crate::model::TreeNode;
+use crate::theme::Labels;
+
+/// Largest ratio of dense grid columns to members before a mesh's
+/// columns are compacted to its observed ranks (HM-2).
+pub(crate) const MAX_COLS_PER_MEMBER: usize = 4;
+
+/// Number of color buckets for numeric metrics (HM-4).
+pub(crate) const HEAT_LEVELS: usize = 4;
+
+/// What a heatmap cell's color encodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum HeatMetric {
+    /// Actor lifecycle status.
+    #[default]
+    Status,
+    /// Deepest actor message queue in the member's proc
+    /// (`actor_work_queue_depth_max`).
+    QueueDepth,
+    /// Time since the member's newest flight-recorder event, i.e. its
+    /// last handler activity.
+    HandlerAge,
+}
+
+impl HeatMetric {
+    /// The metric `v` switches to.
+    pub(crate) fn next(self) -> Self {
+        match self {
+            Self::Status => Self::QueueDepth,
+            Self::QueueDepth => Self::HandlerAge,
+            Self::HandlerAge => Self::Status,
+        }
+    }
+
+    /// Display name.
+    pub(crate) fn label(self, labels: &Labels) -> &'static str {
+        match self {
+            Self::Status => labels.heat_metric_status,
+            Self::QueueDepth => labels.heat_metric_queue,
+            Self::HandlerAge => labels.heat_metric_age,
+        }
+    }
+}
+
+/// Lifecycle status of a mesh member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CellStatus {
+    Running,
+    Stopped,
+    Failed,
+    /// No payload fetched yet.
+    Unknown,
+}
+
+/// One mesh member.
+#[derive(Debug, Clone)]
+pub(crate) struct HeatCell {
+    pub(crate) actor: NodeRef,
+    pub(crate) status: CellStatus,
+    pub(crate) queue_depth: Option<u64>,
+    pub(crate) handler_age: Option<Duration>,
+}
+
+impl HeatCell {
+    /// Numeric value of `metric`, if it has one.
+    pub(crate) fn value(&self, metric: HeatMetric) -> Option<f64> {
+        match metric {
+            HeatMetric::Status => None,
+            HeatMetric::QueueDepth => self.queue_depth.map(|d| d as f64),
+            HeatMetric::HandlerAge => self.handler_age.map(|a| a.as_secs_f64()),
+        }
+    }
+
+    /// Human-readable value of `metric`.
+    pub(crate) fn display(&self, metric: HeatMetric) -> String {
+        match metric {
+            HeatMetric::Status => format!("{:?}", self.status).to_lowercase(),
+            HeatMetric::QueueDepth => self
+                .queue_depth
+                .map_or_else(|| "-".to_string(), |d| d.to_string()),
+            HeatMetric::HandlerAge => self.handler_age.map_or_else(
+                || "-".to_string(),
+                |a| humantime::format_duration(Duration::from_secs(a.as_secs())).to_string(),
+            ),
+        }
+    }
+}
+
+/// An actor mesh laid out by host and per-host rank (HM-2).
+#[derive(Debug, Clone)]
+pub(crate) struct MeshGrid {
+    /// Actor name shared by every member; identifies the mesh.
+    pub(crate) actor_name: String,
+    /// Proc mesh base name (`base` in `{base}_{rank}`).
+    pub(crate) proc_base: String,
+    /// Row labels: one per host with at least one member.
+    pub(crate) hosts: Vec<String>,
+    /// Number of columns: highest per-host rank + 1, or the number of
+    /// distinct observed ranks when they are sparse.
+    pub(crate) cols: usize,
+    /// The per-host rank shown in each column.
+    pub(crate) ranks: Vec<usize>,
+    /// `cells[row][col]`.
+    pub(crate) cells: Vec<Vec<Option<HeatCell>>>,
+}
+
+impl MeshGrid {
+    /// Actor name without its uuid suffix.
+    pub(crate) fn display_name(&self) -> &str {
+        base_name(&self.actor_name)
+    }
+
+    /// Number of members.
+    pub(crate) fn members(&self) -> usize {
+        self.cells.iter().flatten().flatten().count()
+    }
+
+    pub(crate) fn cell(&self, row: usize, col: usize) -> Option<&HeatCell> {
+        self.cells.get(row)?.get(col)?.as_ref()
+    }
+
+    /// Range of `metric` over the mesh, if any member has a value.
+    pub(crate) fn range(&self, metric: HeatMetric) -> Option<(f64, f64)> {
+        self.cells
+            .iter()
+            .flatten()
+            .flatten()
+            .filter_map(|c| c.value(metric))
+            .fold(None, |acc, v| match acc {
+                None => Some((v, v)),
+                Some((lo, hi)) => Some((lo.min(v), hi.max(v))),
+            })
+    }
+
+    /// Color bucket of `cell` for a numeric `metric`, in
+    /// `0..HEAT_LEVELS` (HM-4). `None` for status or missing values.
+    pub(crate) fn level(&self, cell: &HeatCell, metric: HeatMetric) -> Option<usize> {
+        let v = cell.value(metric)?;
+        let (lo, hi) = self.range(metric)?;
+        if hi <= lo {
+            return Some(0);
+        }
+        let scaled = (v - lo) / (hi - lo) * HEAT_LEVELS as f64;
+        Some((scaled as usize).min(HEAT_LEVELS - 1))
+    }
+}
+
+/// Split a proc name of the form `{base}_{rank}` (optionally with a
+/// `-{uuid}` suffix) into `(base, rank)`.
+pub(crate) fn parse_rank_proc(proc_name: &str) -> Option<(&str, usize)> {
+    let (base, rank) = base_name(proc_name).rsplit_once('_')?;
+    if base.is_empty() || rank.is_empty() || !rank.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((base, rank.parse().ok()?))
+}
+
+/// A mesh name without its `-{uuid}` suffix.
+fn base_name(name: &str) -> &str {
+    name.split_once('-').map_or(name, |(base, _)| base)
+}
+
+fn cell_status(node: &TreeNode, payload: Option<&NodePayload>) -> CellStatus {
+    match payload.map(|p| &p.properties) {
+        Some(NodeProperties::Actor { actor_status, .. }) => {
+            let kind = actor_status
+                .split_once(':')
+                .map_or(actor_status.as_str(), |(k, _)| k);
+            match kind {
+                "failed" => CellStatus::Failed,
+                "stopped" => CellStatus::Stopped,
+                _ => CellStatus::Running,
+            }
+        }
+        _ if node.failed => CellStatus::Failed,
+        _ if node.stopped => CellStatus::Stopped,
+        _ => CellStatus::Unknown,
+    }
+}
+
+/// Age of the newest flight-recorder event at `now`.
+fn handler_age(payload: Option<&NodePayload>, now: SystemTime) -> Option<Duration> {
+    let Some(NodeProperties::Actor {
+        flight_recorder: Some(json),
+        ..
+    }) = payload.map(|p| &p.properties)
+    else {
+        return None;
+    };
+    let events: Vec<RecordedEvent> = serde_json::from_str(json).ok()?;
+    let newest = events
+        .iter()
+        .filter_map(|e| chrono::DateTime::parse_from_rfc3339(&e.timestamp).ok())
+        .max()?;
+    now.duration_since(SystemTime::from(newest)).ok()
+}
+
+/// Recover actor meshes from the tree (HM-1, HM-2, HM-3).
+///
+/// Only loaded nodes are considered; payload-derived values come from
+/// `cache`. Meshes with fewer than two members are omitted. The
+/// result is sorted by proc base, then actor name.
+pub(crate) fn collect_meshes(
+    root: &TreeNode,
+    cache: &HashMap<NodeRef, FetchState<NodePayload>>,
+    now: SystemTime,
+) -> Vec<MeshGrid> {
+    // (proc base, actor name) -> host index -> (label, rank -> cell).
+    type Rows = BTreeMap<usize, (String, BTreeMap<usize, HeatCell>)>;
+    let mut meshes: HashMap<(String, String), Rows> = HashMap::new();
+
+    for (host_idx, host) in root.children.iter().enumerate() {
+        for proc_node in &host.children {
+            let NodeRef::Proc(proc_id) = &proc_node.reference else {
+                continue;
+            };
+            if proc_node.is_system {
+                continue;
+            }
+            let Some((base, rank)) = parse_rank_proc(proc_id.name()) else {
+                continue;
+            };
+            let queue_depth = match get_cached_payload(cache, &proc_node.reference)
+                .map(|p| &p.properties)
+            {
+                Some(NodeProperties::Proc { debug, .. }) => Some(debug.actor_work_queue_depth_max),
+                _ => None,
+            };
+            for actor_node in &proc_node.children {
+                let NodeRef::Actor(actor_id) = &actor_node.reference else {
+                    continue;
+                };
+                if actor_node.is_system {
+                    continue;
+                }
+                let payload = get_cached_payload(cache, &actor_node.reference);
+                // HM-1: same actor name on a different proc mesh is a
+                // different mesh.
+                let rows = meshes
+                    .entry((base.to_string(), actor_id.name().to_string()))
+                    .or_default();
+                let (_, row) = rows
+                    .entry(host_idx)
+                    .or_insert_with(|| (host.label.clone(), BTreeMap::new()));
+                // HM-2: first member in tree order keeps the cell.
+                row.entry(rank).or_insert_with(|| HeatCell {
+                    actor: actor_node.reference.clone(),
+                    status: cell_status(actor_node, payload),
+                    queue_depth,
+                    handler_age: handler_age(payload, now),
+                });
+            }
+        }
+    }
+
+    let mut grids: Vec<MeshGrid> = meshes
+        .into_iter()
+        .filter_map(|((proc_base, actor_name), rows)| {
+            let members: usize = rows.values().map(|(_, r)| r.len()).sum();
+            if members < 2 {
+                return None;
+            }
+            let max_rank = rows
+                .values()
+                .filter_map(|(_, r)| r.keys().next_back())
+                .max()
+                .copied()
+                .unwrap_or(0);
+            // HM-2: rank values come from proc names, so they only
+            // size the grid while it stays proportional to the mesh.
+            let ranks: Vec<usize> = if max_rank < members.saturating_mul(MAX_COLS_PER_MEMBER) {
+                (0..=max_rank).collect()
+            } else {
+                let observed: BTreeSet<usize> =
+                    rows.values().flat_map(|(_, r)| r.keys().copied()).collect();
+                observed.into_iter().collect()
+            };
+            let mut hosts = Vec::new();
+            let mut cells = Vec::new();
+            for (_, (label, row)) in rows {
+                hosts.push(label);
+                let mut line = vec![None; ranks.len()];
+                for (rank, cell) in row {
+                    if let Ok(col) = ranks.binary_search(&rank) {
+                        line[col] = Some(cell);
+                    }
+                }
+                cells.push(line);
+            }
+            Some(MeshGrid {
+                actor_name,
+                proc_base,
+                hosts,
+                cols: ranks.len(),
+                ranks,
+                cells,
+            })
+        })
+        .collect();
+    grids.sort_by(|a, b| (&a.proc_base, &a.actor_name).cmp(&(&b.proc_base, &b.actor_name)));
+    grids
+}
+
+/// Heatmap selection state (HM-3).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HeatmapView {
+    /// `actor_name` of the selected mesh; `None` selects the first.
+    pub(crate) mesh: Option<String>,
+    pub(crate) row: usize,
+    pub(crate) col: usize,
+    pub(crate) metric: HeatMetric,
+}
+
+impl HeatmapView {
+    /// Index of the selected mesh in `grids`, falling back to the
+    /// first when the selected one has disappeared.
+    pub(crate) fn mesh_index(&self, grids: &[MeshGrid]) -> Option<usize> {
+        if grids.is_empty() {
+            return None;
+        }
+        Some(
+            self.mesh
+                .as_ref()
+                .and_then(|name| grids.iter().position(|g| &g.actor_name == name))
+                .unwrap_or(0),
+        )
+    }
+
+    /// Selected `(row, col)` clamped to `grid`.
+    pub(crate) fn position(&self, grid: &MeshGrid) -> (usize, usize) {
+        (
+            self.row.min(grid.hosts.len().saturating_sub(1)),
+            self.col.min(grid.cols.saturating_sub(1)),
+        )
+    }
+
+    /// The selected member, if the selected position holds one.
+    pub(crate) fn selected<'g>(&self, grids: &'g [MeshGrid]) -> Option<&'g HeatCell> {
+        let grid = &grids[self.mesh_index(grids)?];
+        let (row, col) = self.position(grid);
+        grid.cell(row, col)
+    }
+
+    /// Move the selection by `(drow, dcol)` within the grid. Returns
+    /// `false` if it did not move.
+    pub(crate) fn step(&mut self, grids: &[MeshGrid], drow: isize, dcol: isize) -> bool {
+        let Some(idx) = self.mesh_index(grids) else {
+            return false;
+        };
+        let grid = &grids[idx];
+        let (row, col) = self.position(grid);
+        let new_row = row
+            .saturating_add_signed(drow)
+            .min(grid.hosts.len().saturating_sub(1));
+        let new_col = col
+            .saturating_add_signed(dcol)
+            .min(grid.cols.saturating_sub(1));
+        self.row = new_row;
+        self.col = new_col;
+        (new_row, new_col) != (row, col)
+    }
+
+    /// Select the next (`forward`) or previous mesh, wrapping.
+    pub(crate) fn cycle_mesh(&mut self, grids: &[MeshGrid], forward: bool) -> bool {
+        let Some(idx) = self.mesh_index(grids) else {
+            return false;
+        };
+        let n = grids.len();
+        let next = if forward {
+            (idx + 1) % n
+        } else {
+            (idx + n - 1) % n
+        };
+        self.mesh = Some(grids[next].actor_name.clone());
+        self.row = 0;
+        self.col = 0;
+        next != idx
+    }
+
+    /// Select the member `actor`, if it is in any grid.
+    pub(crate) fn focus(&mut self, grids: &[MeshGrid], actor: &NodeRef) -> bool {
+        for grid in grids {
+            for (row, line) in grid.cells.iter().enumerate() {
+                for (col, cell) in line.iter().enumerate() {
+                    if cell.as_ref().is_some_and(|c| &c.actor == actor) {
+                        self.mesh = Some(grid.actor_name.clone());
+                        self.row = row;
+                        self.col = col;
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use hyperactor::reference::ActorId;
+    use hyperactor::reference::ProcId;
+    use hyperactor_mesh::introspect::ProcDebugStats;
+
+    use super::*;
+    use crate::model::NodeType;
+
+    fn node(reference: NodeRef, node_type: NodeType, children: Vec<TreeNode>) -> TreeNode {
+        TreeNode {
+            label: reference.to_string(),
+            reference,
+            node_type,
+            expanded: true,
+            fetched: true,
+            has_children: !children.is_empty(),
+            stopped: false,
+            failed: false,
+            is_system: false,
+            children,
+        }
+    }
+
+    fn host(name: &str, procs: Vec<TreeNode>) -> TreeNode {
+        let id = ActorId::from_str(&format!("unix:@{name},service,host_agent[0]")).unwrap();
+        let mut host = node(NodeRef::Host(id), NodeType::Host, procs);
+        host.label = name.to_string();
+        host
+    }
+
+    fn proc_with(host: &str, proc_name: &str, actors: &[&str]) -> TreeNode {
+        let proc_id = ProcId::from_str(&format!("unix:@{host},{proc_name}")).unwrap();
+        let children = actors
+            .iter()
+            .map(|a| {
+                let id = ActorId::from_str(&format!("unix:@{host},{proc_name},{a}[0]")).unwrap();
+                node(NodeRef::Actor(id), NodeType::Actor, vec![])
+            })
+            .collect();
+        node(NodeRef::Proc(proc_id), NodeType::Proc, children)
+    }
+
+    fn tree(hosts: Vec<TreeNode>) -> TreeNode {
+        node(NodeRef::Root, NodeType::Root, hosts)
+    }
+
+    fn actor_payload(reference: &NodeRef, status: &str, recorder: Option<String>) -> NodePayload {
+        NodePayload {
+            identity: reference.clone(),
+            properties: NodeProperties::Actor {
+                actor_status: status.to_string(),
+                actor_type: "Trainer".to_string(),
+                messages_processed: 0,
+                created_at: None,
+                last_message_handler: None,
+                total_processing_time_us: 0,
+                flight_recorder: recorder,
+                is_system: false,
+                failure_info: None,
+            },
+            children: vec![],
+            parent: None,
+            as_of: SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    fn ready(payload: NodePayload) -> FetchState<NodePayload> {
+        FetchState::Ready {
+            stamp: crate::Stamp {
+                ts_micros: 0,
+                seq: 0,
+            },
+            generation: 0,
+            value: payload,
+        }
+    }
+
+    /// Two hosts × two ranks of `trainer_{r}` procs, each running
+    /// `learner-abc`, plus an unranked proc and a singleton actor.
+    fn mesh_tree() -> TreeNode {
+        tree(vec![
+            host(
+                "h0",
+                vec![
+                    proc_with("h0", "trainer_0-aaa", &["learner-abc", "solo"]),
+                    proc_with("h0", "trainer_1-bbb", &["learner-abc"]),
+                    proc_with("h0", "local", &["learner-abc"]),
+                ],
+            ),
+            host(
+                "h1",
+                vec![
+                    proc_with("h1", "trainer_0-ccc", &["learner-abc"]),
+                    proc_with("h1", "trainer_1-ddd", &["learner-abc"]),
+                ],
+            ),
+        ])
+    }
+
+    #[test]
+    fn parse_rank_proc_accepts_mesh_procs() {
+        assert_eq!(parse_rank_proc("trainer_3-x1y2"), Some(("trainer", 3)));
+        assert_eq!(parse_rank_proc("my_mesh_12"), Some(("my_mesh", 12)));
+        assert_eq!(parse_rank_proc("service"), None);
+        assert_eq!(parse_rank_proc("trainer_x"), None);
+        assert_eq!(parse_rank_proc("_3"), None);
+    }
+
+    // HM-1, HM-2: members are placed by host row and rank column;
+    // unranked procs and singletons are ignored.
+    #[test]
+    fn collect_meshes_builds_host_by_rank_grid() {
+        let grids = collect_meshes(&mesh_tree(), &HashMap::new(), SystemTime::now());
+        assert_eq!(grids.len(), 1);
+        let grid = &grids[0];
+        assert_eq!(grid.display_name(), "learner");
+        assert_eq!(grid.proc_base, "trainer");
+        assert_eq!(grid.hosts, vec!["h0", "h1"]);
+        assert_eq!(grid.cols, 2);
+        assert_eq!(grid.members(), 4);
+        let NodeRef::Actor(id) = &grid.cell(1, 1).unwrap().actor else {
+            panic!("cell must hold an actor");
+        };
+        assert_eq!(id.proc_id().name(), "trainer_1-ddd");
+        // No payloads cached yet.
+        assert_eq!(grid.cell(0, 0).unwrap().status, CellStatus::Unknown);
+    }
+
+    // HM-2: a host missing a rank leaves an empty cell.
+    #[test]
+    fn collect_meshes_leaves_holes() {
+        let root = tree(vec![
+            host(
+                "h0",
+                vec![
+                    proc_with("h0", "w_0", &["a"]),
+                    proc_with("h0", "w_2", &["a"]),
+                ],
+            ),
+            host("h1", vec![proc_with("h1", "w_1", &["a"])]),
+        ]);
+        let grids = collect_meshes(&root, &HashMap::new(), SystemTime::now());
+        let grid = &grids[0];
+        assert_eq!(grid.cols, 3);
+        assert!(grid.cell(0, 1).is_none());
+        assert!(grid.cell(1, 0).is_none());
+        assert!(grid.cell(1, 1).is_some());
+    }
+
+    // HM-2: huge or sparse ranks compact the columns instead of
+    // allocating one per rank value.
+    #[test]
+    fn collect_meshes_compacts_sparse_ranks() {
+        let root = tree(vec![
+            host(
+                "h0",
+                vec![
+                    proc_with("h0", "w_3", &["a"]),
+                    proc_with("h0", "w_99999999999", &["a"]),
+                ],
+            ),
+            host("h1", vec![proc_with("h1", "w_3", &["a"])]),
+        ]);
+        let grids = collect_meshes(&root, &HashMap::new(), SystemTime::now());
+        let grid = &grids[0];
+        assert_eq!(grid.cols, 2);
+        assert_eq!(grid.ranks, vec![3, 99999999999]);
+        assert!(grid.cell(0, 0).is_some());
+        assert!(grid.cell(0, 1).is_some());
+        assert!(grid.cell(1, 0).is_some());
+        assert!(grid.cell(1, 1).is_none());
+    }
+
+    #[test]
+    fn collect_meshes_reads_cached_status_queue_and_age() {
+        let root = mesh_tree();
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let mut cache = HashMap::new();
+        let h0 = &root.children[0];
+        let failed_actor = &h0.children[0].children[0].reference;
+        cache.insert(
+            failed_actor.clone(),
+            ready(actor_payload(failed_actor, "failed: boom", None)),
+        );
+        let last_event = humantime::format_rfc3339_millis(now - Duration::from_secs(90));
+        let recorder = serde_json::json!([{
+            "timestamp": last_event.to_string(),
+            "seq": 1,
+            "level": "INFO",
+            "target": "t",
+            "name": "handle",
+            "fields": {},
+        }])
+        .to_string();
+        let busy_actor = &h0.children[1].children[0].reference;
+        cache.insert(
+            busy_actor.clone(),
+            ready(actor_payload(busy_actor, "idle", Some(recorder))),
+        );
+        let busy_proc = &h0.children[1].reference;
+        cache.insert(
+            busy_proc.clone(),
+            ready(NodePayload {
+                identity: busy_proc.clone(),
+                properties: NodeProperties::Proc {
+                    proc_name: "trainer_1".to_string(),
+                    num_actors: 1,
+                    system_children: vec![],
+                    stopped_children: vec![],
+                    stopped_retention_cap: 0,
+                    is_poisoned: false,
+                    failed_actor_count: 0,
+                    debug: ProcDebugStats {
+                        actor_work_queue_depth_total: 7,
+                        actor_work_queue_depth_max: 7,
+                        ..Default::default()
+                    },
+                },
+                children: vec![],
+                parent: None,
+                as_of: SystemTime::UNIX_EPOCH,
+            }),
+        );
+
+        let grids = collect_meshes(&root, &cache, now);
+        let grid = &grids[0];
+        assert_eq!(grid.cell(0, 0).unwrap().status, CellStatus::Failed);
+        let busy = grid.cell(0, 1).unwrap();
+        assert_eq!(busy.status, CellStatus::Running);
+        assert_eq!(busy.queue_depth, Some(7));
+        assert_eq!(busy.handler_age, Some(Duration::from_secs(90)));
+        assert_eq!(busy.display(HeatMetric::HandlerAge), "1m 30s");
+    }
+
+    // HM-4: levels are relative to the mesh's range.
+    #[test]
+    fn level_is_relative_to_range() {
+        let mut grid = collect_meshes(&mesh_tree(), &HashMap::new(), SystemTime::now()).remove(0);
+        for (i, cell) in grid.cells.iter_mut().flatten().flatten().enumerate() {
+            cell.queue_depth = Some(if i == 3 { 100 } else { 1 });
+        }
+        let metric = HeatMetric::QueueDepth;
+        assert_eq!(grid.level(grid.cell(0, 0).unwrap(), metric), Some(0));
+        assert_eq!(
+            grid.level(grid.cell(1, 1).unwrap(), metric),
+            Some(HEAT_LEVELS - 1)
+        );
+        assert_eq!(
+            grid.level(grid.cell(0, 0).unwrap(), HeatMetric::Status),
+            None
+        );
+
+        for cell in grid.cells.iter_mut().flatten().flatten() {
+            cell.queue_depth = Some(5);
+        }
+        assert_eq!(grid.level(grid.cell(1, 1).unwrap(), metric), Some(0));
+    }
+
+    // HM-3: selection is clamped to the current grid.
+    #[test]
+    fn view_navigation_is_clamped() {
+        let grids = collect_meshes(&mesh_tree(), &HashMap::new(), SystemTime::now());
+        let mut view = HeatmapView::default();
+        assert!(view.step(&grids, 1, 1));
+        assert!(!view.step(&grids, 1, 1));
+        assert_eq!((view.row, view.col), (1, 1));
+        view.row = 10;
+        assert_eq!(view.position(&grids[0]), (1, 1));
+        assert!(view.step(&grids, -5, 0));
+        assert_eq!((view.row, view.col), (0, 1));
+        assert!(!view.cycle_mesh(&grids, true));
+    }
+
+    #[test]
+    fn view_focus_selects_member() {
+        let root = mesh_tree();
+        let grids = collect_meshes(&root, &HashMap::new(), SystemTime::now());
+        let target = root.children[1].children[0].children[0].reference.clone();
+        let mut view = HeatmapView::default();
+        assert!(view.focus(&grids, &target));
+        assert_eq!((view.row, view.col), (1, 0));
+        assert_eq!(view.selected(&grids).map(|c| &c.actor), Some(&target));
+        assert!(!view.focus(&grids, &NodeRef::Root));
+    }
+}
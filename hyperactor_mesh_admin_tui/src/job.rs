@@ -17,6 +17,7 @@ use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 
 use crate::diagnostics::DiagResult;
+use crate::operator::OperatorAction;
 use crate::overlay::Overlay;
 use crate::render::detail_pane::build_diag_overlay;
 use crate::theme::Labels;
@@ -60,6 +61,18 @@ pub(crate) enum ActiveJob {
         lines: Vec<Line<'static>>,
         completed_at: Option<String>,
     },
+    /// A confirmable operator action (OP-*).
+    ///
+    /// Starts unconfirmed with `rx == None`, showing the prompt.
+    /// Confirming sets `confirmed` and `rx`; the result then follows
+    /// the same rx/lines semantics as PySpy.
+    Operator {
+        action: OperatorAction,
+        confirmed: bool,
+        rx: Option<oneshot::Receiver<Vec<Line<'static>>>>,
+        lines: Vec<Line<'static>>,
+        completed_at: Option<String>,
+    },
 }
 
 impl ActiveJob {
@@ -197,6 +210,74 @@ impl ActiveJob {
                     max_scroll: Cell::new(u16::MAX),
                 }
             }
+            ActiveJob::Operator {
+                action,
+                confirmed,
+                rx,
+                lines,
+                completed_at,
+            } => {
+                let scheme = &theme.scheme;
+                let labels = &theme.labels;
+                let sep = labels.separator;
+                let loading = rx.is_some();
+                let heading = Span::styled(
+                    format!("{}: {}", action.verb(), action.short()),
+                    Style::default().add_modifier(Modifier::BOLD),
+                );
+
+                let title = if !*confirmed {
+                    Line::from(vec![
+                        heading,
+                        Span::styled(format!("{sep}{}", labels.op_confirm), scheme.error),
+                    ])
+                } else if loading {
+                    Line::from(vec![
+                        heading,
+                        Span::styled(format!("{sep}{}", labels.diag_running), scheme.info),
+                    ])
+                } else if let Some(ts) = completed_at {
+                    Line::from(vec![
+                        heading,
+                        Span::styled(
+                            format!("{sep}{}", labels.diag_completed_at),
+                            scheme.detail_label,
+                        ),
+                        Span::raw(" "),
+                        Span::styled(ts.clone(), scheme.stat_timing),
+                    ])
+                } else {
+                    Line::from(heading)
+                };
+
+                let status_line = if loading {
+                    let what = match action {
+                        OperatorAction::Profile { duration_s, .. } => {
+                            format!("profiling for {duration_s}s")
+                        }
+                        _ => format!("waiting for {}", action.verb()),
+                    };
+                    Some(Line::from(vec![
+                        Span::styled(labels.diag_running, scheme.info),
+                        Span::styled(format!("{sep}{what}"), scheme.detail_label),
+                    ]))
+                } else {
+                    None
+                };
+
+                Overlay {
+                    title,
+                    status_line,
+                    lines: if *confirmed {
+                        lines.clone()
+                    } else {
+                        action.prompt_lines(scheme)
+                    },
+                    loading,
+                    scroll: Cell::new(0),
+                    max_scroll: Cell::new(u16::MAX),
+                }
+            }
         }
     }
 
@@ -210,6 +291,16 @@ impl ActiveJob {
             Some(ActiveJob::Diagnostics { .. }) => labels.footer_diag_completed_help_text,
             Some(ActiveJob::PySpy { .. }) => labels.footer_pyspy_help_text,
             Some(ActiveJob::Config { .. }) => labels.footer_config_help_text,
+            Some(ActiveJob::Operator {
+                confirmed: false,
+                action: OperatorAction::Profile { .. },
+                ..
+            }) => labels.footer_op_profile_confirm_help_text,
+            Some(ActiveJob::Operator {
+                confirmed: false, ..
+            }) => labels.footer_op_confirm_help_text,
+            Some(ActiveJob::Operator { rx: Some(_), .. }) => labels.footer_diag_running_help_text,
+            Some(ActiveJob::Operator { .. }) => labels.footer_op_done_help_text,
             None => labels.footer_help_text,
         }
     }
@@ -270,6 +361,21 @@ impl ActiveJob {
                     debug_assert!(false, "ConfigResult delivered to non-Config job");
                 }
             }
+            ActiveJobEvent::OperatorResult(new_lines) => {
+                if let ActiveJob::Operator {
+                    rx,
+                    lines,
+                    completed_at,
+                    ..
+                } = self
+                {
+                    *rx = None;
+                    *lines = new_lines;
+                    *completed_at = Some(Local::now().format("%H:%M:%S").to_string());
+                } else {
+                    debug_assert!(false, "OperatorResult delivered to non-Operator job");
+                }
+            }
         }
     }
 }
@@ -279,4 +385,5 @@ pub(crate) enum ActiveJobEvent {
     DiagResult(Option<DiagResult>),
     PySpyResult(Vec<Line<'static>>),
     ConfigResult(Vec<Line<'static>>),
+    OperatorResult(Vec<Line<'static>>),
 }
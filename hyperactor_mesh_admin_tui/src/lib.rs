@@ -13,6 +13,11 @@
 //! details on the right pane, including actor flight recorder events
 //! when an actor is selected.
 //!
+//! With [`TuiConfig::replay`] set, the TUI instead opens a directory
+//! of introspection snapshot bundles offline and steps through them
+//! with a time slider, highlighting nodes added, removed or failed
+//! between steps (see `replay.rs`, RP-*).
+//!
 //! Binaries construct a [`TuiConfig`] (after resolving any
 //! `mast_conda:///` handles themselves) and call [`run`].
 //!
@@ -36,7 +41,8 @@
 //! - **TUI-4 (failed-always-visible):** Failed nodes are always
 //!   visible regardless of the `show_stopped` toggle.
 //! - **TUI-5 (single-fetch-path):** All cache writes go through
-//!   `fetch_with_join` (no direct inserts).
+//!   `fetch_with_join` (no direct inserts). Replay replaces the
+//!   cache wholesale with the current frame instead (RP-2).
 //! - **TUI-6 (refresh-staleness):** `FetchState::Ready` with
 //!   `generation < refresh_gen` is refetched; errors always retry.
 //! - **TUI-7 (synthetic-root):** The root node is synthetic and
@@ -143,6 +149,55 @@
 //!   Enforced by `set_job`: each variant drops any prior receiver;
 //!   `recv_active_job` fires only for the variant currently stored.
 //!
+//! Operator action invariants:
+//!
+//! - **OP-1 (confirm-before-send):** `x` and `P` only open a
+//!   confirmation overlay. No request is sent until `y`/Enter, and
+//!   Esc discards the proposal without contacting the server.
+//! - **OP-2 (target-capture):** The target reference is captured in
+//!   `OperatorAction` when the action is proposed; refreshes and
+//!   cursor moves cannot retarget a pending or running action.
+//! - **OP-3 (overlay-ownership):** Like PY-2, the result receiver
+//!   lives inside the `Operator` variant, so dismissing the overlay
+//!   stops waiting for the result. The server-side action is not
+//!   rolled back.
+//! - **OP-4 (replay-inert):** `x` and `P` are disabled in replay
+//!   mode (RP-2).
+//! - **OP-5 (selection-totality):** `x` stops the selected actor,
+//!   stops the selected proc, or drains the selected host; no-op on
+//!   Root. `P` uses the PY-4 target resolution.
+//! - **OP-6 (api-only):** Every action is a mesh admin API request,
+//!   authenticated with the client certificate and, when
+//!   [`TuiConfig::action_token_file`] is set, a bearer token.
+//!
+//! Search invariants (see `search.rs` for SR-*):
+//!
+//! - **SE-1 (filtered-projection):** While a filter is active,
+//!   `visible_rows()` is `flatten_matching` over the same tree;
+//!   clearing the filter collapses what committed searches expanded,
+//!   restoring the expansion state the user left.
+//! - **SE-2 (prompt-capture):** While the `/` prompt has focus every
+//!   printable key edits the prompt; no topology binding fires.
+//! - **SE-3 (bounded-expansion):** Committing a search expands only
+//!   collapsed hosts and procs, and fetches placeholder payloads only
+//!   when the query has free-text or status/type/handler terms. Expansion runs
+//!   until the frontier is empty, which the finite host/proc depth
+//!   bounds.
+//!
+//! Heatmap view invariants (see `heatmap.rs` for HM-*):
+//!
+//! - **HV-1 (key-capture):** While the heatmap is open it owns
+//!   navigation; only the replay slider keys (`[`, `]`, `{`, `}`)
+//!   fall through, so no tree binding fires against a hidden cursor.
+//! - **HV-2 (shared-expansion):** Opening the heatmap expands hosts
+//!   and procs like SE-3 and records them with search expansions.
+//!   They are collapsed only once neither the heatmap nor a filter
+//!   needs them.
+//! - **HV-3 (drill-through):** Enter closes the heatmap and selects
+//!   the cell's actor in the tree, keeping the expansions that make
+//!   its row visible. The detail pane always shows the selected cell
+//!   while the heatmap is open.
+//!
 //! Laziness + recursion benefits:
 //! - **Lazy expansion**: proc/actor children are placeholders until
 //!   expanded, keeping refresh costs bounded and scaling work to what
@@ -172,10 +227,14 @@ mod diagnostics;
 mod fetch;
 mod filter;
 mod format;
+mod heatmap;
 mod job;
 mod model;
+mod operator;
 mod overlay;
 mod render;
+mod replay;
+mod search;
 mod theme;
 pub(crate) mod timeouts;
 mod tree;
@@ -190,6 +249,7 @@ pub(crate) use std::collections::HashMap;
 pub(crate) use std::collections::HashSet;
 use std::io;
 use std::io::IsTerminal;
+use std::path::PathBuf;
 use std::time::Duration;
 
 pub(crate) use actions::*;
@@ -228,6 +288,15 @@ pub(crate) use tree::*;
 /// The `addr` field must be a resolved address (`host:port` or
 /// `https://host:port`), not a MAST handle. MAST resolution is
 /// the binary's responsibility.
+///
+/// When `replay` is set, `addr` and `diagnose` are ignored and the
+/// TUI replays the snapshot bundles under that directory offline.
+///
+/// `action_token_file` names a file holding the mesh admin action
+/// token, sent as a bearer credential with operator actions (OP-6).
+///
+/// `views_file` overrides where saved search views are persisted;
+/// by default `$XDG_CONFIG_HOME/monarch/admin_tui_views.json`.
 pub struct TuiConfig {
     pub addr: String,
     pub refresh_ms: u64,
@@ -237,6 +306,9 @@ pub struct TuiConfig {
     pub tls_cert: Option<String>,
     pub tls_key: Option<String>,
     pub diagnose: bool,
+    pub replay: Option<PathBuf>,
+    pub action_token_file: Option<PathBuf>,
+    pub views_file: Option<PathBuf>,
 }
 
 // Terminal setup / teardown
@@ -317,7 +389,18 @@ pub async fn run(config: TuiConfig) -> io::Result<()> {
     let policy = timeouts::TuiTimeoutPolicy::from_config(&config);
     let (base_url, client) = client::build_client(&config);
 
-    if config.diagnose {
+    let timeline = match &config.replay {
+        Some(dir) => Some(replay::Timeline::load(dir).map_err(|e| {
+            io::Error::other(format!(
+                "failed to load replay from {}: {:#}",
+                dir.display(),
+                e
+            ))
+        })?),
+        None => None,
+    };
+
+    if config.diagnose && timeline.is_none() {
         return run_diagnose(client, base_url, policy).await;
     }
 
@@ -326,14 +409,57 @@ pub async fn run(config: TuiConfig) -> io::Result<()> {
         return Ok(());
     }
 
+    let action_token = match &config.action_token_file {
+        Some(path) => Some(
+            std::fs::read_to_string(path)
+                .map_err(|e| {
+                    io::Error::other(format!(
+                        "failed to read action token from {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?
+                .trim()
+                .to_string(),
+        ),
+        None => None,
+    };
+
+    let views = match config
+        .views_file
+        .clone()
+        .or_else(search::default_views_path)
+    {
+        // A broken views file must not keep the TUI from starting.
+        // The views are kept in memory only, so that saving cannot
+        // clobber the file before the user has looked at it.
+        Some(path) => search::SavedViews::load(path).unwrap_or_else(|e| {
+            eprintln!("warning: ignoring saved views: {e:#}");
+            search::SavedViews::default()
+        }),
+        None => search::SavedViews::default(),
+    };
+
     let mut app = App::new(base_url, client, config.theme, config.lang, policy);
+    app.action_token = action_token;
+    app.views = views;
+    if let Some(timeline) = timeline {
+        app = app.with_replay(timeline);
+    }
     let spinner = ProgressBar::new_spinner();
     spinner.set_style(
         ProgressStyle::default_spinner()
             .template("{spinner:.cyan} {msg}")
             .expect("valid template"),
     );
-    spinner.set_message(format!("mesh-admin — Connecting to {} ...", app.base_url));
+    spinner.set_message(match &app.replay {
+        Some(timeline) => format!(
+            "mesh-admin — Loaded {} snapshots from {}",
+            timeline.len(),
+            timeline.source().display()
+        ),
+        None => format!("mesh-admin — Connecting to {} ...", app.base_url),
+    });
     spinner.enable_steady_tick(Duration::from_millis(80));
 
     let splash_start = tokio::time::Instant::now();
@@ -666,6 +666,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: None,
             },
             children: vec![],
             parent: None,
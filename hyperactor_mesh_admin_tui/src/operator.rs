@@ -0,0 +1,367 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Operator actions on the selected node (OP-*).
+//!
+//! An [`OperatorAction`] is proposed from the topology view, shown
+//! in a confirmation overlay, and only sent once the operator
+//! confirms. Every action goes through the mesh admin API: stop and
+//! drain use `POST /v1/actions/*`, profiling uses
+//! `POST /v1/pyspy_profile_svg/{proc}`.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::Local;
+use hyperactor::reference::ActorId;
+use hyperactor::reference::ProcId;
+use hyperactor_mesh::mesh_admin_actions::ActionRequest;
+use hyperactor_mesh::mesh_admin_actions::ActionResponse;
+use hyperactor_mesh::pyspy::PySpyProfileOpts;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::app::parse_error_envelope;
+use crate::theme::ColorScheme;
+
+/// Profile durations offered by `+`/`-` in the confirmation overlay,
+/// in seconds. Durations above `MESH_ADMIN_PYSPY_MAX_PROFILE_DURATION`
+/// are skipped.
+pub(crate) const PROFILE_DURATIONS_S: &[u32] = &[5, 10, 30, 60, 120, 300];
+
+/// Duration preselected when a profile is proposed.
+pub(crate) const DEFAULT_PROFILE_DURATION_S: u32 = 10;
+
+/// Sampling rate used for TUI-initiated profiles.
+const PROFILE_RATE_HZ: u32 = 100;
+
+/// A mutating or long-running operation against one node.
+///
+/// Targets are captured when the action is proposed (OP-2), so a
+/// topology refresh while the confirmation is open cannot retarget
+/// it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum OperatorAction {
+    /// `POST /v1/actions/stop_actor/{actor}`.
+    StopActor(ActorId),
+    /// `POST /v1/actions/stop_proc/{proc}`.
+    StopProc(ProcId),
+    /// `POST /v1/actions/drain_host/host:{agent}`.
+    DrainHost(ActorId),
+    /// `POST /v1/pyspy_profile_svg/{proc}` for `duration_s` seconds.
+    Profile { proc: ProcId, duration_s: u32 },
+}
+
+impl OperatorAction {
+    /// Short verb shown in the overlay title.
+    pub(crate) fn verb(&self) -> &'static str {
+        match self {
+            Self::StopActor(_) => "stop actor",
+            Self::StopProc(_) => "stop proc",
+            Self::DrainHost(_) => "drain host",
+            Self::Profile { .. } => "profile",
+        }
+    }
+
+    /// Full reference string of the target, as sent to the API.
+    pub(crate) fn target(&self) -> String {
+        match self {
+            Self::StopActor(actor_id) => actor_id.to_string(),
+            Self::StopProc(proc_id) | Self::Profile { proc: proc_id, .. } => proc_id.to_string(),
+            Self::DrainHost(agent_id) => format!("host:{agent_id}"),
+        }
+    }
+
+    /// Short target label for the overlay title.
+    pub(crate) fn short(&self) -> String {
+        match self {
+            Self::StopActor(actor_id) => actor_id.name().to_string(),
+            Self::StopProc(proc_id) | Self::Profile { proc: proc_id, .. } => {
+                proc_id.name().to_string()
+            }
+            Self::DrainHost(agent_id) => agent_id.proc_id().addr().to_string(),
+        }
+    }
+
+    /// Lines describing what will happen, shown before confirmation.
+    pub(crate) fn prompt_lines(&self, scheme: &ColorScheme) -> Vec<Line<'static>> {
+        let effect = match self {
+            Self::StopActor(_) => "The actor is asked to stop; its proc keeps running.".to_string(),
+            Self::StopProc(_) => {
+                "The proc and every actor in it are stopped by the host agent.".to_string()
+            }
+            Self::DrainHost(_) => {
+                "Every proc on the host is stopped; the host agent stays up.".to_string()
+            }
+            Self::Profile { duration_s, .. } => format!(
+                "py-spy samples the proc for {duration_s}s at {PROFILE_RATE_HZ} Hz \
+                 and the flamegraph is saved locally."
+            ),
+        };
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("target: ", scheme.detail_label),
+                Span::raw(self.target()),
+            ]),
+            Line::from(""),
+            Line::from(effect),
+            Line::from(""),
+        ];
+        if let Self::Profile { duration_s, .. } = self {
+            lines.push(Line::from(vec![
+                Span::styled("duration: ", scheme.detail_label),
+                Span::styled(format!("{duration_s}s"), scheme.stat_timing),
+                Span::styled("  (+/- to change)", scheme.detail_label),
+            ]));
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            "Press y or Enter to confirm, Esc to cancel.",
+            scheme.info,
+        )));
+        lines
+    }
+
+    /// Return a copy with the profile duration stepped to the next
+    /// (`up`) or previous preset, capped at `max`. Returns `None` for
+    /// non-profile actions and when already at the end of the range.
+    pub(crate) fn step_duration(&self, up: bool, max: Duration) -> Option<Self> {
+        let Self::Profile { proc, duration_s } = self else {
+            return None;
+        };
+        let max_s = u32::try_from(max.as_secs()).unwrap_or(u32::MAX);
+        let allowed = PROFILE_DURATIONS_S.iter().copied().filter(|d| *d <= max_s);
+        let next = if up {
+            allowed.filter(|d| d > duration_s).min()
+        } else {
+            allowed.filter(|d| d < duration_s).max()
+        }?;
+        Some(Self::Profile {
+            proc: proc.clone(),
+            duration_s: next,
+        })
+    }
+
+    /// Admin API URL for this action.
+    fn url(&self, base_url: &str) -> String {
+        let route = match self {
+            Self::StopActor(_) => "actions/stop_actor",
+            Self::StopProc(_) => "actions/stop_proc",
+            Self::DrainHost(_) => "actions/drain_host",
+            Self::Profile { .. } => "pyspy_profile_svg",
+        };
+        format!(
+            "{base_url}/v1/{route}/{}",
+            urlencoding::encode(&self.target())
+        )
+    }
+}
+
+/// Send `action` to the admin API and render the outcome.
+///
+/// Never fails: transport errors, API error envelopes and timeouts
+/// all become display lines. `token` is sent as a bearer credential
+/// when set; without it the request relies on the client certificate.
+pub(crate) async fn run_operator_action(
+    client: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+    action: OperatorAction,
+    timeout: Duration,
+    scheme: ColorScheme,
+) -> Vec<Line<'static>> {
+    let result = tokio::time::timeout(timeout, async {
+        let mut req = client.post(action.url(&base_url));
+        if let Some(token) = &token {
+            req = req.bearer_auth(token);
+        }
+        req = match &action {
+            OperatorAction::Profile { duration_s, .. } => req.json(&PySpyProfileOpts {
+                duration_s: *duration_s,
+                rate_hz: PROFILE_RATE_HZ,
+                native: false,
+                threads: false,
+                nonblocking: false,
+            }),
+            _ => req.json(&ActionRequest {
+                reason: Some("mesh admin tui".to_string()),
+                shutdown: None,
+            }),
+        };
+        let resp = req
+            .send()
+            .await
+            .map_err(|e| format!("request failed: {e}"))?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let mut lines = vec![Line::from(Span::styled(
+                format!("HTTP {status}"),
+                scheme.error,
+            ))];
+            if let Ok(json) = resp.json::<serde_json::Value>().await {
+                lines.extend(parse_error_envelope(&json));
+            }
+            return Ok::<_, String>(lines);
+        }
+        match &action {
+            OperatorAction::Profile { .. } => {
+                let svg = resp
+                    .bytes()
+                    .await
+                    .map_err(|e| format!("reading profile failed: {e}"))?;
+                let path = write_profile(&action, &svg)
+                    .map_err(|e| format!("writing profile failed: {e}"))?;
+                Ok(vec![
+                    Line::from(Span::styled("profile saved", scheme.detail_status_ok)),
+                    Line::from(vec![
+                        Span::styled("path: ", scheme.detail_label),
+                        Span::raw(path.display().to_string()),
+                    ]),
+                    Line::from(vec![
+                        Span::styled("size: ", scheme.detail_label),
+                        Span::raw(format!("{} bytes", svg.len())),
+                    ]),
+                ])
+            }
+            _ => match resp.json::<ActionResponse>().await {
+                Err(e) => Ok(vec![Line::from(format!("parse error: {e}"))]),
+                Ok(r) => Ok(action_response_to_lines(&r, &scheme)),
+            },
+        }
+    })
+    .await;
+    match result {
+        Err(_) => vec![Line::from(format!(
+            "request timed out after {}s",
+            timeout.as_secs()
+        ))],
+        Ok(Ok(lines)) => lines,
+        Ok(Err(e)) => vec![Line::from(e)],
+    }
+}
+
+/// Render a successful `ActionResponse`.
+pub(crate) fn action_response_to_lines(
+    r: &ActionResponse,
+    scheme: &ColorScheme,
+) -> Vec<Line<'static>> {
+    vec![
+        Line::from(vec![
+            Span::styled("outcome: ", scheme.detail_label),
+            Span::styled(r.outcome.as_str().to_string(), scheme.detail_status_ok),
+        ]),
+        Line::from(vec![
+            Span::styled("action: ", scheme.detail_label),
+            Span::raw(r.action.to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled("target: ", scheme.detail_label),
+            Span::raw(r.target.clone()),
+        ]),
+        Line::from(vec![
+            Span::styled("audit id: ", scheme.detail_label),
+            Span::raw(r.audit_id.to_string()),
+        ]),
+    ]
+}
+
+/// Write a profile flamegraph to a new local file and return its
+/// path. The file gets an unpredictable name and is created
+/// exclusively, so a file or symlink planted in the shared temp
+/// directory is never followed or overwritten.
+fn write_profile(action: &OperatorAction, svg: &[u8]) -> std::io::Result<PathBuf> {
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let name: String = action
+        .short()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let mut file = tempfile::Builder::new()
+        .prefix(&format!("mesh-admin-profile-{name}-{stamp}-"))
+        .suffix(".svg")
+        .tempfile()?;
+    file.write_all(svg)?;
+    let (_, path) = file.keep().map_err(|e| e.error)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn proc_id() -> ProcId {
+        ProcId::from_str("unix:@test,worker").unwrap()
+    }
+
+    fn profile(duration_s: u32) -> OperatorAction {
+        OperatorAction::Profile {
+            proc: proc_id(),
+            duration_s,
+        }
+    }
+
+    #[test]
+    fn step_duration_walks_presets() {
+        let max = Duration::from_secs(300);
+        assert_eq!(profile(10).step_duration(true, max), Some(profile(30)));
+        assert_eq!(profile(10).step_duration(false, max), Some(profile(5)));
+        assert_eq!(profile(5).step_duration(false, max), None);
+        assert_eq!(profile(300).step_duration(true, max), None);
+    }
+
+    #[test]
+    fn step_duration_respects_server_max() {
+        let max = Duration::from_secs(45);
+        assert_eq!(profile(30).step_duration(true, max), None);
+        assert_eq!(profile(10).step_duration(true, max), Some(profile(30)));
+    }
+
+    #[test]
+    fn step_duration_ignores_non_profile_actions() {
+        let action = OperatorAction::StopProc(proc_id());
+        assert_eq!(action.step_duration(true, Duration::from_secs(300)), None);
+    }
+
+    #[test]
+    fn url_encodes_target() {
+        let action = OperatorAction::StopProc(proc_id());
+        assert_eq!(
+            action.url("http://h:1"),
+            format!(
+                "http://h:1/v1/actions/stop_proc/{}",
+                urlencoding::encode("unix:@test,worker")
+            )
+        );
+        assert!(
+            profile(10)
+                .url("http://h:1")
+                .contains("/v1/pyspy_profile_svg/")
+        );
+    }
+
+    #[test]
+    fn drain_host_targets_host_reference() {
+        let agent = ActorId::from_str("unix:@test,service,host_agent[0]").unwrap();
+        let action = OperatorAction::DrainHost(agent.clone());
+        assert_eq!(action.target(), format!("host:{agent}"));
+    }
+
+    #[test]
+    fn profiles_are_written_to_fresh_files() {
+        let a = write_profile(&profile(10), b"<svg/>").unwrap();
+        let b = write_profile(&profile(10), b"<svg/>").unwrap();
+        assert_ne!(a, b);
+        assert_eq!(std::fs::read(&a).unwrap(), b"<svg/>");
+        assert!(a.extension().is_some_and(|ext| ext == "svg"));
+        std::fs::remove_file(a).unwrap();
+        std::fs::remove_file(b).unwrap();
+    }
+}
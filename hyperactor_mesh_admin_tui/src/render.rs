@@ -13,8 +13,10 @@
 // - detail
 // - detail_error
 // - error
+// - heatmap, fetch_cache, tree (via heatmap_grids)
 // - lang_name
 // - refresh_interval_label
+// - replay
 // - show_stopped
 // - show_system
 // - theme
@@ -23,6 +25,7 @@
 // Keep this list accurate; it bounds Step 2.
 
 pub mod detail_pane;
+pub mod heatmap_pane;
 pub mod status_bar;
 pub mod tree_pane;
 
@@ -31,6 +34,7 @@ use ratatui::layout::Direction;
 use ratatui::layout::Layout;
 
 use self::detail_pane::render_detail_pane;
+use self::heatmap_pane::render_heatmap;
 use self::status_bar::render_footer;
 use self::status_bar::render_header;
 use self::tree_pane::render_topology_tree;
@@ -57,15 +61,19 @@ pub(crate) fn ui(frame: &mut ratatui::Frame<'_>, app: &App) {
 
 /// Render the main body of the UI.
 ///
-/// Splits the screen into a left topology pane and a right detail
-/// pane. When diagnostics is active the topology tree is dimmed
-/// (non-interactive) and the right pane shows the diagnostics view.
+/// Splits the screen into a left topology pane (or the rank heatmap,
+/// when open) and a right detail pane. When diagnostics is active
+/// the topology tree is dimmed (non-interactive) and the right pane
+/// shows the diagnostics view.
 pub(crate) fn render_body(frame: &mut ratatui::Frame<'_>, area: ratatui::layout::Rect, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
         .split(area);
 
-    render_topology_tree(frame, chunks[0], app);
+    match &app.heatmap {
+        Some(view) => render_heatmap(frame, chunks[0], app, view),
+        None => render_topology_tree(frame, chunks[0], app),
+    }
     render_detail_pane(frame, chunks[1], app);
 }
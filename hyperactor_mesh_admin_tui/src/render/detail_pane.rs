@@ -846,6 +846,7 @@ mod tests {
                     actor_work_queue_depth_total: 42,
                     actor_work_queue_depth_max: 7,
                 },
+                generation: None,
             },
             children: vec![],
             parent: None,
@@ -0,0 +1,202 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Block;
+use ratatui::widgets::Borders;
+use ratatui::widgets::Paragraph;
+
+use crate::App;
+use crate::heatmap::CellStatus;
+use crate::heatmap::HEAT_LEVELS;
+use crate::heatmap::HeatCell;
+use crate::heatmap::HeatMetric;
+use crate::heatmap::HeatmapView;
+use crate::heatmap::MeshGrid;
+use crate::theme::ColorScheme;
+
+/// Width of the host label column, in cells.
+const HOST_LABEL_WIDTH: usize = 16;
+
+/// Lines used by the column header, legend and selection summary.
+const CHROME_LINES: usize = 4;
+
+/// Render the rank heatmap (left pane, replacing the tree).
+///
+/// One row per host, one column per per-host rank (HM-2). Rows
+/// scroll to keep the selected cell visible.
+pub(crate) fn render_heatmap(
+    frame: &mut ratatui::Frame<'_>,
+    area: Rect,
+    app: &App,
+    view: &HeatmapView,
+) {
+    let scheme = &app.theme.scheme;
+    let l = &app.theme.labels;
+    let grids = app.heatmap_grids();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(scheme.border);
+
+    let Some(idx) = view.mesh_index(&grids) else {
+        let p = Paragraph::new(Span::styled(l.heatmap_empty, scheme.info))
+            .block(block.title(l.pane_heatmap));
+        frame.render_widget(p, area);
+        return;
+    };
+    let grid = &grids[idx];
+    let (sel_row, sel_col) = view.position(grid);
+    let title = format!(
+        "{} {}/{}: {} on {} ({}×{}, {} actors) — {}",
+        l.pane_heatmap,
+        idx + 1,
+        grids.len(),
+        grid.display_name(),
+        grid.proc_base,
+        grid.hosts.len(),
+        grid.cols,
+        grid.members(),
+        view.metric.label(l),
+    );
+
+    let visible_rows = (area.height as usize)
+        .saturating_sub(2 + CHROME_LINES)
+        .max(1);
+    let offset = sel_row.saturating_sub(visible_rows - 1);
+
+    let mut lines = Vec::new();
+    let mut header = vec![Span::raw(" ".repeat(HOST_LABEL_WIDTH))];
+    for rank in &grid.ranks {
+        header.push(Span::styled(
+            format!("{:<3}", rank % 1000),
+            scheme.detail_label,
+        ));
+    }
+    lines.push(Line::from(header));
+
+    for (row, host) in grid
+        .hosts
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible_rows)
+    {
+        let label_style = if row == sel_row {
+            scheme.stat_selection
+        } else {
+            scheme.node_host
+        };
+        let mut spans = vec![Span::styled(host_label(host), label_style)];
+        for col in 0..grid.cols {
+            let selected = (row, col) == (sel_row, sel_col);
+            spans.push(match grid.cell(row, col) {
+                Some(cell) => {
+                    let style = cell_style(grid, cell, view.metric, scheme);
+                    if selected {
+                        Span::styled("[] ", style.add_modifier(Modifier::BOLD))
+                    } else {
+                        Span::styled("██ ", style)
+                    }
+                }
+                None if selected => Span::styled("[] ", scheme.detail_label),
+                None => Span::styled("·  ", scheme.detail_label),
+            });
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(legend(grid, view.metric, scheme));
+    if let Some(cell) = grid.cell(sel_row, sel_col) {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("rank {} ", grid.ranks[sel_col]),
+                scheme.stat_selection,
+            ),
+            Span::styled(format!("on {}: ", grid.hosts[sel_row]), scheme.detail_label),
+            Span::raw(cell.display(view.metric)),
+        ]));
+    }
+
+    let p = Paragraph::new(lines).block(block.title(title));
+    frame.render_widget(p, area);
+}
+
+/// Host label padded or truncated to `HOST_LABEL_WIDTH`.
+fn host_label(host: &str) -> String {
+    let width = HOST_LABEL_WIDTH - 1;
+    let chars: Vec<char> = host.chars().collect();
+    let shown: String = if chars.len() > width {
+        let tail: String = chars[chars.len() - (width - 1)..].iter().collect();
+        format!("…{tail}")
+    } else {
+        host.to_string()
+    };
+    format!("{shown:<width$} ")
+}
+
+/// Style for a numeric bucket (HM-4): green to red.
+fn level_style(level: usize, scheme: &ColorScheme) -> Style {
+    match level {
+        0 => scheme.detail_status_ok,
+        1 => scheme.info,
+        2 => scheme.detail_status_warn,
+        _ => scheme.detail_status_failed,
+    }
+}
+
+fn status_style(status: CellStatus, scheme: &ColorScheme) -> Style {
+    match status {
+        CellStatus::Running => scheme.detail_status_ok,
+        CellStatus::Stopped => scheme.detail_stopped,
+        CellStatus::Failed => scheme.detail_status_failed,
+        CellStatus::Unknown => scheme.detail_label,
+    }
+}
+
+fn cell_style(grid: &MeshGrid, cell: &HeatCell, metric: HeatMetric, scheme: &ColorScheme) -> Style {
+    match metric {
+        HeatMetric::Status => status_style(cell.status, scheme),
+        _ => grid
+            .level(cell, metric)
+            .map_or(scheme.detail_label, |level| level_style(level, scheme)),
+    }
+}
+
+/// Color key for the current metric.
+fn legend(grid: &MeshGrid, metric: HeatMetric, scheme: &ColorScheme) -> Line<'static> {
+    if metric == HeatMetric::Status {
+        let mut spans = Vec::new();
+        for (status, name) in [
+            (CellStatus::Running, "running"),
+            (CellStatus::Stopped, "stopped"),
+            (CellStatus::Failed, "failed"),
+            (CellStatus::Unknown, "unknown"),
+        ] {
+            spans.push(Span::styled("██ ", status_style(status, scheme)));
+            spans.push(Span::styled(format!("{name}  "), scheme.detail_label));
+        }
+        return Line::from(spans);
+    }
+    let Some((lo, hi)) = grid.range(metric) else {
+        return Line::from(Span::styled("no data", scheme.detail_label));
+    };
+    let mut spans = vec![Span::styled(format!("{lo:.0} "), scheme.detail_label)];
+    for level in 0..HEAT_LEVELS {
+        spans.push(Span::styled("██", level_style(level, scheme)));
+    }
+    spans.push(Span::styled(format!(" {hi:.0}"), scheme.detail_label));
+    if metric == HeatMetric::HandlerAge {
+        spans.push(Span::styled(" s", scheme.detail_label));
+    }
+    Line::from(spans)
+}
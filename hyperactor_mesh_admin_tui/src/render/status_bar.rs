@@ -8,6 +8,7 @@
 
 use hyperactor_mesh::introspect::NodeProperties;
 use ratatui::layout::Rect;
+use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::text::Line;
 use ratatui::text::Span;
@@ -18,9 +19,14 @@ use ratatui::widgets::Paragraph;
 use crate::ActiveJob;
 use crate::App;
 use crate::model::NodeType;
+use crate::replay::NodeChange;
+use crate::replay::slider;
 use crate::theme::LangName;
 use crate::theme::ThemeName;
 
+/// Width of the replay time slider in the header, in cells.
+const REPLAY_SLIDER_WIDTH: usize = 24;
+
 /// Render the top status/header bar.
 ///
 /// Displays a colorful, information-dense header with topology stats,
@@ -76,13 +82,54 @@ pub(crate) fn render_header(frame: &mut ratatui::Frame<'_>, area: Rect, app: &Ap
     } else {
         (None, None)
     };
+    // Uptime relative to now is meaningless for a replayed frame.
+    let uptime_str = uptime_str.filter(|_| app.replay.is_none());
 
     // Line 1: App name • URL • up: 2h 34m • @user • sys:off • ⟳ 1s
+    // In replay the URL is replaced by the source, the time slider,
+    // the frame's capture time and its change counts (RP-4).
     let mut line1_spans = vec![
         Span::styled(l.app_name, app.theme.scheme.app_name),
         Span::styled(l.separator, app.theme.scheme.stat_label),
-        Span::styled(&app.base_url, app.theme.scheme.stat_url),
     ];
+    if let Some(timeline) = &app.replay {
+        let scheme = &app.theme.scheme;
+        line1_spans.extend(vec![
+            Span::styled(l.replay, scheme.stat_label),
+            Span::styled(timeline.source().display().to_string(), scheme.stat_url),
+            Span::styled(l.separator, scheme.stat_label),
+            Span::styled(
+                slider(timeline.pos(), timeline.len(), REPLAY_SLIDER_WIDTH),
+                scheme.stat_selection,
+            ),
+            Span::styled(
+                format!(" {}/{}", timeline.pos() + 1, timeline.len()),
+                scheme.stat_timing,
+            ),
+            Span::styled(l.separator, scheme.stat_label),
+            Span::styled(
+                crate::format::format_system_time_iso(&timeline.current().snapshot_ts),
+                scheme.stat_timing,
+            ),
+            Span::styled(l.separator, scheme.stat_label),
+            Span::styled(
+                format!("+{}", timeline.count(NodeChange::Added)),
+                scheme.node_added,
+            ),
+            Span::styled(" ", Style::default()),
+            Span::styled(
+                format!("-{}", timeline.count(NodeChange::Removed)),
+                scheme.node_removed.remove_modifier(Modifier::CROSSED_OUT),
+            ),
+            Span::styled(" ", Style::default()),
+            Span::styled(
+                format!("!{}", timeline.count(NodeChange::Failed)),
+                scheme.node_failed.add_modifier(Modifier::BOLD),
+            ),
+        ]);
+    } else {
+        line1_spans.push(Span::styled(&app.base_url, app.theme.scheme.stat_url));
+    }
 
     // Add uptime if available
     if let Some(uptime) = uptime_str {
@@ -189,15 +236,69 @@ pub(crate) fn render_header(frame: &mut ratatui::Frame<'_>, area: Rect, app: &Ap
 ///
 /// Shows mode-specific hints: topology navigation when the tree is
 /// active, diagnostics navigation when the diagnostics pane is
-/// active.
+/// active, and the time-slider keys when replaying.
 pub(crate) fn render_footer(frame: &mut ratatui::Frame<'_>, area: Rect, app: &App) {
-    let text = ActiveJob::footer_text(&app.active_job, &app.theme.labels);
+    let heatmap = app.heatmap.is_some() && !app.search.editing;
+    if app.active_job.is_none() && !heatmap && (app.search.editing || app.search.is_active()) {
+        let footer = Paragraph::new(search_footer_line(app))
+            .style(app.theme.scheme.footer_help)
+            .block(Block::default().borders(Borders::TOP));
+        frame.render_widget(footer, area);
+        return;
+    }
+    let text = if heatmap && app.active_job.is_none() {
+        app.theme.labels.footer_heatmap_help_text
+    } else if app.replay.is_some() && app.active_job.is_none() {
+        app.theme.labels.footer_replay_help_text
+    } else {
+        ActiveJob::footer_text(&app.active_job, &app.theme.labels)
+    };
     let footer = Paragraph::new(text)
         .style(app.theme.scheme.footer_help)
         .block(Block::default().borders(Borders::TOP));
     frame.render_widget(footer, area);
 }
 
+/// Footer line for the search prompt or the active filter (SE-*):
+/// the prompt text, then either the parse error, the last command's
+/// feedback, or the match count, then key help.
+fn search_footer_line(app: &App) -> Line<'static> {
+    let l = &app.theme.labels;
+    let scheme = &app.theme.scheme;
+    let search = &app.search;
+    let mut spans = if search.editing {
+        vec![
+            Span::styled("/", scheme.info),
+            Span::raw(search.input.clone()),
+            Span::styled("▏", scheme.info),
+        ]
+    } else {
+        vec![
+            Span::styled(l.search_filter, scheme.info),
+            Span::raw(search.applied.clone()),
+        ]
+    };
+    spans.push(Span::raw("  "));
+    if let Some(error) = &search.error {
+        spans.push(Span::styled(error.clone(), scheme.error));
+    } else if let Some(message) = &search.message {
+        spans.push(Span::styled(message.clone(), scheme.info));
+    } else if search.is_active() {
+        spans.push(Span::raw(format!(
+            "({} {})",
+            app.search_match_positions().len(),
+            l.search_matches
+        )));
+    }
+    spans.push(Span::raw(l.separator));
+    spans.push(Span::raw(if search.editing {
+        l.footer_search_edit_help_text
+    } else {
+        l.footer_search_active_help_text
+    }));
+    Line::from(spans)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,13 +58,23 @@ pub(crate) fn render_topology_tree(frame: &mut ratatui::Frame<'_>, area: Rect, a
                 "  "
             };
 
-            // Style precedence: inactive > selected > failed > stopped >
-            // system > node-type.  When pane_inactive the entire pane is dimmed
-            // and the selection/failed/system colours must not bleed through.
+            // RP-4: replay highlights what changed since the previous frame.
+            let change = app
+                .replay
+                .as_ref()
+                .and_then(|timeline| timeline.change(&node.reference));
+            let change_glyph = change.map(|c| c.glyph()).unwrap_or("");
+
+            // Style precedence: inactive > selected > replay change >
+            // failed > stopped > system > node-type.  When pane_inactive
+            // the entire pane is dimmed and the selection/failed/system
+            // colours must not bleed through.
             let style = if pane_inactive {
                 scheme.detail_stopped
             } else if vis_idx == app.cursor.pos() {
                 scheme.stat_selection.add_modifier(Modifier::BOLD)
+            } else if let Some(change) = change {
+                scheme.change_style(change)
             } else if node.failed {
                 scheme.node_failed
             } else if node.stopped {
@@ -83,7 +93,10 @@ pub(crate) fn render_topology_tree(frame: &mut ratatui::Frame<'_>, area: Rect, a
             };
 
             ListItem::new(Line::from(Span::styled(
-                format!("{}{}{}{}{}", marker, indent, connector, fold, node.label),
+                format!(
+                    "{}{}{}{}{}{}",
+                    marker, indent, connector, fold, change_glyph, node.label
+                ),
                 style,
             )))
         })
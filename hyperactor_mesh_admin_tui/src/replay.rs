@@ -0,0 +1,638 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Offline timeline replay from snapshot bundles.
+//!
+//! `--replay <dir>` opens captured introspection snapshots instead of
+//! a live admin server. `dir` may be a single snapshot bundle (has
+//! `manifest.json`), a delta chain (has `chain.json`), or a directory
+//! whose immediate subdirectories are bundles and/or chains. Every
+//! snapshot found becomes one [`ReplayFrame`]; the [`Timeline`] steps
+//! between them and classifies what changed since the previous frame
+//! so the tree can highlight it.
+//!
+//! # Replay invariants (RP-*)
+//!
+//! - **RP-1 (time order):** Frames are ordered by `(snapshot_ts,
+//!   snapshot_id)`. A snapshot reachable from more than one source
+//!   (e.g. a standalone bundle and a chain base) appears once.
+//! - **RP-2 (offline):** In replay mode every payload is served from
+//!   the current frame. The app issues no HTTP requests: periodic
+//!   refresh is off and the diagnostics, py-spy and config actions
+//!   are disabled.
+//! - **RP-3 (closed frame):** Every child reference in a frame
+//!   resolves to a payload of the same frame. Edges to nodes the
+//!   capture did not record are dropped on load.
+//! - **RP-4 (change classification):** Relative to the previous
+//!   frame, a node is `Added` if it was absent, `Removed` if it is
+//!   now absent, and `Failed` if it is failed now but was not
+//!   before (including nodes that appear already failed). The first
+//!   frame has no changes.
+//! - **RP-5 (removed ghosts):** Removed nodes stay in the view,
+//!   with their last payload, under their previous parent, so they
+//!   can be highlighted in place.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+use hyperactor_mesh::introspect::NodePayload;
+use hyperactor_mesh::introspect::NodeRef;
+use monarch_introspection_snapshot::bundle::read_bundle;
+use monarch_introspection_snapshot::chain::replay_chain;
+use monarch_introspection_snapshot::convert::restore_node;
+use monarch_introspection_snapshot::delta::SnapshotIndex;
+use monarch_introspection_snapshot::push::batches_to_data;
+
+use crate::fetch::FetchState;
+use crate::fetch::Stamp;
+use crate::filter::is_failed_node;
+
+/// How a node changed since the previous frame (RP-4).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum NodeChange {
+    /// Present now, absent in the previous frame.
+    Added,
+    /// Present in the previous frame, absent now (RP-5).
+    Removed,
+    /// Failed now, not failed in the previous frame.
+    Failed,
+}
+
+impl NodeChange {
+    /// Tree-row prefix marking the change.
+    pub(crate) fn glyph(self) -> &'static str {
+        match self {
+            NodeChange::Added => "+ ",
+            NodeChange::Removed => "- ",
+            NodeChange::Failed => "! ",
+        }
+    }
+}
+
+/// One captured snapshot, restored to admin API payloads.
+pub(crate) struct ReplayFrame {
+    pub(crate) snapshot_id: String,
+    pub(crate) snapshot_ts: SystemTime,
+    pub(crate) payloads: HashMap<NodeRef, NodePayload>,
+}
+
+impl ReplayFrame {
+    /// Restore every node of `index`, dropping dangling child edges
+    /// (RP-3) and filling in `parent` from the child edges. A proc
+    /// parent wins over a supervising actor, matching the live API.
+    pub(crate) fn from_index(index: &SnapshotIndex) -> anyhow::Result<Self> {
+        let mut restored = Vec::with_capacity(index.nodes.len());
+        for converted in index.nodes.values() {
+            restored.push(restore_node(converted)?);
+        }
+        let present: HashSet<NodeRef> = restored.iter().map(|p| p.identity.clone()).collect();
+
+        let mut parents: HashMap<NodeRef, NodeRef> = HashMap::new();
+        for payload in &mut restored {
+            payload.children.retain(|c| present.contains(c));
+            for child in &payload.children {
+                if !parents.contains_key(child) || matches!(payload.identity, NodeRef::Proc(_)) {
+                    parents.insert(child.clone(), payload.identity.clone());
+                }
+            }
+        }
+
+        let payloads = restored
+            .into_iter()
+            .map(|mut payload| {
+                payload.parent = parents.get(&payload.identity).cloned();
+                (payload.identity.clone(), payload)
+            })
+            .collect();
+        Ok(Self {
+            snapshot_id: index.snapshot_id().to_owned(),
+            snapshot_ts: UNIX_EPOCH
+                + Duration::from_micros(u64::try_from(index.snapshot.snapshot_ts).unwrap_or(0)),
+            payloads,
+        })
+    }
+}
+
+/// Classify each node that changed between `prev` and `cur` (RP-4).
+pub(crate) fn classify_changes(
+    prev: Option<&ReplayFrame>,
+    cur: &ReplayFrame,
+) -> HashMap<NodeRef, NodeChange> {
+    let mut changes = HashMap::new();
+    let Some(prev) = prev else {
+        return changes;
+    };
+    for (reference, payload) in &cur.payloads {
+        let failed = is_failed_node(&payload.properties);
+        match prev.payloads.get(reference) {
+            None if failed => {
+                changes.insert(reference.clone(), NodeChange::Failed);
+            }
+            None => {
+                changes.insert(reference.clone(), NodeChange::Added);
+            }
+            Some(before) if failed && !is_failed_node(&before.properties) => {
+                changes.insert(reference.clone(), NodeChange::Failed);
+            }
+            Some(_) => {}
+        }
+    }
+    for reference in prev.payloads.keys() {
+        if !cur.payloads.contains_key(reference) {
+            changes.insert(reference.clone(), NodeChange::Removed);
+        }
+    }
+    changes
+}
+
+/// Load the snapshots of a single bundle or chain directory, or
+/// `None` if `dir` is neither.
+fn load_source(dir: &Path) -> anyhow::Result<Option<Vec<SnapshotIndex>>> {
+    if dir.join("chain.json").is_file() {
+        let (_, snapshots) = replay_chain(dir)?;
+        return Ok(Some(snapshots));
+    }
+    if dir.join("manifest.json").is_file() {
+        let (batches, _) = read_bundle(dir)?;
+        let index = SnapshotIndex::from_data(batches_to_data(&batches)?)?;
+        return Ok(Some(vec![index]));
+    }
+    Ok(None)
+}
+
+/// An ordered sequence of frames with a position, the changes at
+/// that position, and the payload view the tree is built from.
+pub(crate) struct Timeline {
+    source: PathBuf,
+    frames: Vec<ReplayFrame>,
+    pos: usize,
+    changes: HashMap<NodeRef, NodeChange>,
+    view: HashMap<NodeRef, NodePayload>,
+}
+
+impl Timeline {
+    /// Load every snapshot under `dir` (see module docs) and position
+    /// the timeline at the first frame. Errors if none is found.
+    pub(crate) fn load(dir: &Path) -> anyhow::Result<Self> {
+        let mut snapshots = match load_source(dir)? {
+            Some(snapshots) => snapshots,
+            None => {
+                let mut subdirs = Vec::new();
+                for entry in
+                    fs::read_dir(dir).with_context(|| format!("failed to read {:?}", dir))?
+                {
+                    let path = entry?.path();
+                    if path.is_dir() {
+                        subdirs.push(path);
+                    }
+                }
+                subdirs.sort();
+                let mut snapshots = Vec::new();
+                for subdir in subdirs {
+                    if let Some(found) = load_source(&subdir)
+                        .with_context(|| format!("failed to load {:?}", subdir))?
+                    {
+                        snapshots.extend(found);
+                    }
+                }
+                snapshots
+            }
+        };
+        anyhow::ensure!(
+            !snapshots.is_empty(),
+            "no snapshot bundles or chains found in {:?}",
+            dir,
+        );
+
+        // RP-1: time order, one frame per snapshot.
+        snapshots.sort_by(|a, b| {
+            (a.snapshot.snapshot_ts, a.snapshot_id())
+                .cmp(&(b.snapshot.snapshot_ts, b.snapshot_id()))
+        });
+        snapshots.dedup_by(|a, b| a.snapshot_id() == b.snapshot_id());
+
+        let frames = snapshots
+            .iter()
+            .map(ReplayFrame::from_index)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self::from_frames(dir.to_path_buf(), frames))
+    }
+
+    /// Build a timeline over `frames`, which must be non-empty and
+    /// already in time order.
+    pub(crate) fn from_frames(source: PathBuf, frames: Vec<ReplayFrame>) -> Self {
+        assert!(!frames.is_empty(), "timeline needs at least one frame");
+        let mut timeline = Self {
+            source,
+            frames,
+            pos: 0,
+            changes: HashMap::new(),
+            view: HashMap::new(),
+        };
+        timeline.rebuild();
+        timeline
+    }
+
+    /// The directory the timeline was loaded from.
+    pub(crate) fn source(&self) -> &Path {
+        &self.source
+    }
+
+    /// Number of frames (always at least one).
+    pub(crate) fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Index of the current frame.
+    pub(crate) fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// The current frame.
+    pub(crate) fn current(&self) -> &ReplayFrame {
+        &self.frames[self.pos]
+    }
+
+    /// Move to frame `pos`, clamped to the last frame. Returns
+    /// whether the position changed.
+    pub(crate) fn seek(&mut self, pos: usize) -> bool {
+        let pos = pos.min(self.frames.len() - 1);
+        if pos == self.pos {
+            return false;
+        }
+        self.pos = pos;
+        self.rebuild();
+        true
+    }
+
+    /// The change recorded for `reference` at the current frame.
+    pub(crate) fn change(&self, reference: &NodeRef) -> Option<NodeChange> {
+        self.changes.get(reference).copied()
+    }
+
+    /// Number of changed nodes of the given kind at the current frame.
+    pub(crate) fn count(&self, kind: NodeChange) -> usize {
+        self.changes.values().filter(|c| **c == kind).count()
+    }
+
+    /// The payload shown for `reference`: the current frame's, or the
+    /// last known one for a removed node (RP-5).
+    pub(crate) fn payload(&self, reference: &NodeRef) -> Option<&NodePayload> {
+        self.view.get(reference)
+    }
+
+    /// Serve `reference` from the current frame (RP-2).
+    pub(crate) fn fetch(&self, reference: &NodeRef, generation: u64) -> FetchState<NodePayload> {
+        match self.payload(reference) {
+            Some(payload) => FetchState::Ready {
+                stamp: self.stamp(),
+                generation,
+                value: payload.clone(),
+            },
+            None => FetchState::Error {
+                stamp: self.stamp(),
+                msg: format!(
+                    "{} is not in snapshot {}",
+                    reference,
+                    self.current().snapshot_id
+                ),
+            },
+        }
+    }
+
+    /// A fetch cache holding every payload of the current view, so a
+    /// refresh at `generation` never leaves the frame (RP-2, RP-3).
+    pub(crate) fn seed_cache(&self, generation: u64) -> HashMap<NodeRef, FetchState<NodePayload>> {
+        self.view
+            .keys()
+            .map(|reference| (reference.clone(), self.fetch(reference, generation)))
+            .collect()
+    }
+
+    fn stamp(&self) -> Stamp {
+        let ts_micros = self
+            .current()
+            .snapshot_ts
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros() as u64;
+        Stamp {
+            ts_micros,
+            seq: self.pos as u64,
+        }
+    }
+
+    /// Recompute changes and the view for the current position.
+    fn rebuild(&mut self) {
+        let cur = &self.frames[self.pos];
+        let prev = self.pos.checked_sub(1).map(|p| &self.frames[p]);
+        self.changes = classify_changes(prev, cur);
+
+        // RP-5: graft removed nodes back under their previous parent.
+        let mut view = cur.payloads.clone();
+        if let Some(prev) = prev {
+            let mut removed: Vec<&NodeRef> = self
+                .changes
+                .iter()
+                .filter(|(_, change)| **change == NodeChange::Removed)
+                .map(|(reference, _)| reference)
+                .collect();
+            removed.sort_by_key(|r| r.to_string());
+            for reference in &removed {
+                view.insert((*reference).clone(), prev.payloads[*reference].clone());
+            }
+            for reference in removed {
+                if let Some(parent) = &prev.payloads[reference].parent
+                    && let Some(parent_payload) = view.get_mut(parent)
+                    && !parent_payload.children.contains(reference)
+                {
+                    parent_payload.children.push(reference.clone());
+                }
+            }
+        }
+        self.view = view;
+    }
+}
+
+/// Render a position slider of `width` cells, e.g. `━━━●──────`.
+pub(crate) fn slider(pos: usize, len: usize, width: usize) -> String {
+    if width == 0 {
+        return String::new();
+    }
+    let marker = if len <= 1 {
+        0
+    } else {
+        pos.min(len - 1) * (width - 1) / (len - 1)
+    };
+    (0..width)
+        .map(|i| match i.cmp(&marker) {
+            std::cmp::Ordering::Less => '━',
+            std::cmp::Ordering::Equal => '●',
+            std::cmp::Ordering::Greater => '─',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+
+    use hyperactor::reference::ActorId;
+    use hyperactor::reference::ProcId;
+    use hyperactor_mesh::introspect::FailureInfo;
+    use hyperactor_mesh::introspect::NodeProperties;
+    use monarch_introspection_snapshot::bundle::write_bundle;
+    use monarch_introspection_snapshot::chain::ChainWriter;
+    use monarch_introspection_snapshot::convert::convert_node;
+    use monarch_introspection_snapshot::delta::SnapshotDelta;
+    use monarch_introspection_snapshot::push::drain_to_batches;
+    use monarch_introspection_snapshot::schema::SnapshotRow;
+
+    use super::*;
+
+    fn proc_id() -> ProcId {
+        ProcId::from_str("unix:@test,worker").unwrap()
+    }
+
+    fn actor_id(name: &str) -> ActorId {
+        ActorId::from_str(&format!("unix:@test,worker,{}[0]", name)).unwrap()
+    }
+
+    fn t(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(1_700_000_000 + secs)
+    }
+
+    fn root(children: Vec<NodeRef>) -> NodePayload {
+        NodePayload {
+            identity: NodeRef::Root,
+            properties: NodeProperties::Root {
+                num_hosts: 0,
+                started_at: t(0),
+                started_by: "user".to_owned(),
+                system_children: vec![],
+            },
+            children,
+            parent: None,
+            as_of: t(0),
+        }
+    }
+
+    fn proc_node(actors: &[&str]) -> NodePayload {
+        NodePayload {
+            identity: NodeRef::Proc(proc_id()),
+            properties: NodeProperties::Proc {
+                proc_name: "worker".to_owned(),
+                num_actors: actors.len(),
+                system_children: vec![],
+                stopped_children: vec![],
+                stopped_retention_cap: 0,
+                is_poisoned: false,
+                failed_actor_count: 0,
+                debug: Default::default(),
+                generation: None,
+            },
+            children: actors.iter().map(|a| NodeRef::Actor(actor_id(a))).collect(),
+            parent: None,
+            as_of: t(0),
+        }
+    }
+
+    fn actor_node(name: &str, failed: bool) -> NodePayload {
+        NodePayload {
+            identity: NodeRef::Actor(actor_id(name)),
+            properties: NodeProperties::Actor {
+                actor_status: if failed { "failed: boom" } else { "running" }.to_owned(),
+                actor_type: "A".to_owned(),
+                messages_processed: 0,
+                created_at: None,
+                last_message_handler: None,
+                total_processing_time_us: 0,
+                flight_recorder: None,
+                is_system: false,
+                failure_info: failed.then(|| FailureInfo {
+                    error_message: "boom".to_owned(),
+                    root_cause_actor: actor_id(name),
+                    root_cause_name: None,
+                    occurred_at: t(1),
+                    is_propagated: false,
+                }),
+            },
+            children: vec![],
+            parent: None,
+            as_of: t(0),
+        }
+    }
+
+    /// A snapshot with root → proc → the given actors, where
+    /// `failed` names the failed ones.
+    fn index(id: &str, secs: u64, actors: &[&str], failed: &[&str]) -> SnapshotIndex {
+        let mut payloads = vec![root(vec![NodeRef::Proc(proc_id())]), proc_node(actors)];
+        payloads.extend(actors.iter().map(|a| actor_node(a, failed.contains(a))));
+        let nodes: BTreeMap<String, _> = payloads
+            .iter()
+            .map(|p| {
+                let converted = convert_node(id, p).unwrap();
+                (converted.node.node_id.clone(), converted)
+            })
+            .collect();
+        SnapshotIndex {
+            snapshot: SnapshotRow {
+                snapshot_id: id.to_owned(),
+                snapshot_ts: (1_700_000_000 + secs as i64) * 1_000_000,
+            },
+            nodes,
+        }
+    }
+
+    fn frame(index: &SnapshotIndex) -> ReplayFrame {
+        ReplayFrame::from_index(index).unwrap()
+    }
+
+    // RP-3: dangling edges are dropped and parents are filled in.
+    #[test]
+    fn test_frame_is_closed() {
+        let mut idx = index("s1", 0, &["a"], &[]);
+        let mut dangling = idx.nodes[&NodeRef::Proc(proc_id()).to_string()].clone();
+        dangling.children.push(
+            convert_node("s1", &proc_node(&["missing"]))
+                .unwrap()
+                .children
+                .remove(0),
+        );
+        idx.nodes.insert(dangling.node.node_id.clone(), dangling);
+
+        let frame = frame(&idx);
+        let proc_payload = &frame.payloads[&NodeRef::Proc(proc_id())];
+        assert_eq!(proc_payload.children, vec![NodeRef::Actor(actor_id("a"))]);
+        assert_eq!(proc_payload.parent, Some(NodeRef::Root));
+        assert_eq!(
+            frame.payloads[&NodeRef::Actor(actor_id("a"))].parent,
+            Some(NodeRef::Proc(proc_id()))
+        );
+    }
+
+    // RP-4: added, removed and newly failed nodes are classified; the
+    // first frame has no changes.
+    #[test]
+    fn test_classify_changes() {
+        let f1 = frame(&index("s1", 0, &["a", "b", "c"], &["c"]));
+        let f2 = frame(&index("s2", 1, &["a", "c", "d", "e"], &["a", "c", "e"]));
+
+        assert!(classify_changes(None, &f1).is_empty());
+        let changes = classify_changes(Some(&f1), &f2);
+        assert_eq!(changes.len(), 4);
+        assert_eq!(changes[&NodeRef::Actor(actor_id("a"))], NodeChange::Failed);
+        assert_eq!(changes[&NodeRef::Actor(actor_id("b"))], NodeChange::Removed);
+        assert_eq!(changes[&NodeRef::Actor(actor_id("d"))], NodeChange::Added);
+        assert_eq!(changes[&NodeRef::Actor(actor_id("e"))], NodeChange::Failed);
+        // "c" was already failed.
+        assert!(!changes.contains_key(&NodeRef::Actor(actor_id("c"))));
+    }
+
+    // RP-5: removed nodes stay in the view under their old parent
+    // until the timeline moves past the frame that removed them.
+    #[test]
+    fn test_removed_ghosts() {
+        let mut timeline = Timeline::from_frames(
+            PathBuf::from("/replay"),
+            vec![
+                frame(&index("s1", 0, &["a", "b"], &[])),
+                frame(&index("s2", 1, &["a"], &[])),
+                frame(&index("s3", 2, &["a"], &[])),
+            ],
+        );
+        let b = NodeRef::Actor(actor_id("b"));
+        assert!(timeline.payload(&b).is_some());
+        assert_eq!(timeline.change(&b), None);
+
+        assert!(timeline.seek(1));
+        assert_eq!(timeline.change(&b), Some(NodeChange::Removed));
+        assert_eq!(timeline.count(NodeChange::Removed), 1);
+        assert!(timeline.payload(&b).is_some());
+        let proc_payload = timeline.payload(&NodeRef::Proc(proc_id())).unwrap();
+        assert!(proc_payload.children.contains(&b));
+
+        assert!(timeline.seek(usize::MAX));
+        assert_eq!(timeline.pos(), 2);
+        assert!(timeline.payload(&b).is_none());
+        assert!(!timeline.seek(5));
+    }
+
+    // RP-2: fetches are answered from the frame, including errors
+    // for unknown references.
+    #[test]
+    fn test_fetch_serves_frame() {
+        let timeline = Timeline::from_frames(
+            PathBuf::from("/replay"),
+            vec![frame(&index("s1", 0, &["a"], &[]))],
+        );
+        assert!(matches!(
+            timeline.fetch(&NodeRef::Actor(actor_id("a")), 3),
+            FetchState::Ready { generation: 3, .. }
+        ));
+        assert!(matches!(
+            timeline.fetch(&NodeRef::Actor(actor_id("zz")), 3),
+            FetchState::Error { .. }
+        ));
+        assert_eq!(timeline.seed_cache(3).len(), 3);
+    }
+
+    // RP-1: a directory of bundles and chains loads in time order,
+    // and a chain base that also exists as a bundle appears once.
+    #[test]
+    fn test_load_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let s1 = index("s1", 0, &["a"], &[]);
+        let s2 = index("s2", 10, &["a", "b"], &[]);
+        let s3 = index("s3", 20, &["b"], &[]);
+
+        let mut chain = ChainWriter::create(&dir.path().join("chain"), s2.clone()).unwrap();
+        chain.append(&SnapshotDelta::diff(&s2, &s3)).unwrap();
+        write_bundle(
+            &dir.path().join("z-first"),
+            &drain_to_batches(s1.into_data()).unwrap(),
+        )
+        .unwrap();
+        write_bundle(
+            &dir.path().join("a-dup"),
+            &drain_to_batches(s2.into_data()).unwrap(),
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("unrelated")).unwrap();
+
+        let timeline = Timeline::load(dir.path()).unwrap();
+        let ids: Vec<&str> = timeline
+            .frames
+            .iter()
+            .map(|f| f.snapshot_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["s1", "s2", "s3"]);
+
+        let chain_only = Timeline::load(&dir.path().join("chain")).unwrap();
+        assert_eq!(chain_only.len(), 2);
+
+        assert!(Timeline::load(&dir.path().join("unrelated")).is_err());
+    }
+
+    #[test]
+    fn test_slider() {
+        assert_eq!(slider(0, 1, 5), "●────");
+        assert_eq!(slider(0, 3, 5), "●────");
+        assert_eq!(slider(1, 3, 5), "━━●──");
+        assert_eq!(slider(2, 3, 5), "━━━━●");
+        assert_eq!(slider(0, 3, 0), "");
+    }
+}
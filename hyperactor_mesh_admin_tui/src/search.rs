@@ -0,0 +1,386 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Incremental tree search and saved views.
+//!
+//! `/` opens a prompt whose text is parsed as a filter [`Query`] on
+//! every keystroke; the tree then shows only matching nodes and their
+//! ancestors (TR-2). A term `@name` expands to the saved view `name`.
+//! Prompt commands `:save NAME` and `:delete NAME` manage saved views,
+//! which persist as JSON in the views file.
+//!
+//! Invariants:
+//!
+//! - **SR-1 (last-good-query):** `SearchState::query` is always the
+//!   parse of `SearchState::applied`. Input that fails to parse, or
+//!   a pending prompt command, leaves the previous query in effect
+//!   and records the error for display.
+//! - **SR-2 (flat-views):** A saved view may not reference other
+//!   views, so `@name` expansion is a single, terminating pass.
+//! - **SR-3 (durable-save):** Saving or deleting a view rewrites the
+//!   whole file through a temporary file and rename, so a crash never
+//!   leaves a truncated views file.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::filter::Query;
+use crate::filter::QueryError;
+
+/// State of the `/` search prompt and the filter it applies.
+#[derive(Debug, Default)]
+pub(crate) struct SearchState {
+    /// Text currently in the prompt.
+    pub(crate) input: String,
+    /// Whether the prompt has keyboard focus.
+    pub(crate) editing: bool,
+    /// The prompt text that produced `query` (SR-1).
+    pub(crate) applied: String,
+    /// The filter last applied with Enter; what `:save` saves.
+    pub(crate) committed: String,
+    /// The filter in effect. Empty when no search is active.
+    pub(crate) query: Query,
+    /// Why `input` is not in effect, if it is not.
+    pub(crate) error: Option<String>,
+    /// Feedback from the last prompt command.
+    pub(crate) message: Option<String>,
+}
+
+impl SearchState {
+    /// True if the tree is currently filtered.
+    pub(crate) fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    /// Replace the prompt text and re-evaluate it (SR-1).
+    ///
+    /// Prompt commands (`:…`) are not evaluated until submitted.
+    pub(crate) fn set_input(&mut self, input: String, views: &SavedViews) {
+        self.input = input;
+        self.message = None;
+        if self.input.trim_start().starts_with(':') {
+            self.error = None;
+            return;
+        }
+        match views
+            .expand(&self.input)
+            .and_then(|expr| Query::parse(&expr))
+        {
+            Ok(query) => {
+                self.query = query;
+                self.applied = self.input.clone();
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    /// Close the prompt, keeping the filter in effect.
+    pub(crate) fn commit(&mut self) {
+        self.editing = false;
+        self.committed = self.applied.clone();
+    }
+
+    /// Run the prompt command in `input`, if it is one. Returns
+    /// `false` when `input` is a plain query. On success the prompt
+    /// returns to the committed filter.
+    pub(crate) fn run_command(&mut self, views: &mut SavedViews) -> bool {
+        let Some(command) = self.input.trim().strip_prefix(':') else {
+            return false;
+        };
+        let mut words = command.split_whitespace();
+        let result = match (words.next(), words.next(), words.next()) {
+            (Some("save"), Some(name), None) => {
+                if self.committed.trim().is_empty() {
+                    Err("nothing to save: no filter is applied".to_string())
+                } else {
+                    views
+                        .save(name, self.committed.trim())
+                        .map(|()| format!("saved view @{name}"))
+                        .map_err(|e| format!("{e:#}"))
+                }
+            }
+            (Some("delete"), Some(name), None) => match views.remove(name) {
+                Ok(true) => Ok(format!("deleted view @{name}")),
+                Ok(false) => Err(format!("no view named @{name}")),
+                Err(e) => Err(format!("{e:#}")),
+            },
+            _ => Err("usage: :save NAME | :delete NAME".to_string()),
+        };
+        match result {
+            Ok(message) => {
+                self.set_input(self.committed.clone(), views);
+                self.message = Some(message);
+            }
+            Err(error) => self.error = Some(error),
+        }
+        true
+    }
+
+    /// Drop the filter and close the prompt.
+    pub(crate) fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// On-disk form of the views file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ViewsFile {
+    views: BTreeMap<String, String>,
+}
+
+/// Named filter expressions, persisted in a JSON file.
+#[derive(Debug, Default)]
+pub(crate) struct SavedViews {
+    /// Where views are persisted. `None` keeps them in memory only
+    /// and makes `save`/`remove` fail.
+    path: Option<PathBuf>,
+    views: BTreeMap<String, String>,
+}
+
+impl SavedViews {
+    /// Load views from `path`. A missing file is an empty set.
+    pub(crate) fn load(path: PathBuf) -> anyhow::Result<Self> {
+        let views = match std::fs::read_to_string(&path) {
+            Ok(text) => {
+                serde_json::from_str::<ViewsFile>(&text)
+                    .with_context(|| format!("parsing views file {}", path.display()))?
+                    .views
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("reading views file {}", path.display()));
+            }
+        };
+        Ok(Self {
+            path: Some(path),
+            views,
+        })
+    }
+
+    /// Names of all saved views, sorted.
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        self.views.keys().map(String::as_str)
+    }
+
+    /// Expand every `@name` term in `input` (SR-2). `-@name` is
+    /// accepted only for single-term views.
+    pub(crate) fn expand(&self, input: &str) -> Result<String, QueryError> {
+        let mut out = Vec::new();
+        for word in input.split_whitespace() {
+            let (negated, rest) = match word.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, word),
+            };
+            let Some(name) = rest.strip_prefix('@') else {
+                out.push(word.to_string());
+                continue;
+            };
+            let expr = self
+                .views
+                .get(name)
+                .ok_or_else(|| QueryError(format!("no view named @{name}")))?;
+            if !negated {
+                out.extend(expr.split_whitespace().map(str::to_string));
+                continue;
+            }
+            // Terms are a conjunction, so only a one-term view can be
+            // negated without a disjunction.
+            let mut terms = expr.split_whitespace();
+            let (Some(term), None) = (terms.next(), terms.next()) else {
+                return Err(QueryError(format!(
+                    "cannot negate @{name}: it has more than one term"
+                )));
+            };
+            out.push(match term.strip_prefix('-') {
+                Some(positive) => positive.to_string(),
+                None => format!("-{term}"),
+            });
+        }
+        Ok(out.join(" "))
+    }
+
+    /// Save `expr` as view `name` and persist (SR-2, SR-3).
+    pub(crate) fn save(&mut self, name: &str, expr: &str) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            name.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'),
+            "view names may only contain letters, digits, `_` and `-`"
+        );
+        anyhow::ensure!(
+            !expr
+                .split_whitespace()
+                .any(|t| t.trim_start_matches('-').starts_with('@')),
+            "views cannot reference other views"
+        );
+        let previous = self.views.insert(name.to_string(), expr.to_string());
+        if let Err(e) = self.persist() {
+            match previous {
+                Some(prev) => self.views.insert(name.to_string(), prev),
+                None => self.views.remove(name),
+            };
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Delete view `name` and persist. Returns `false` if it did not
+    /// exist.
+    pub(crate) fn remove(&mut self, name: &str) -> anyhow::Result<bool> {
+        let Some(previous) = self.views.remove(name) else {
+            return Ok(false);
+        };
+        if let Err(e) = self.persist() {
+            self.views.insert(name.to_string(), previous);
+            return Err(e);
+        }
+        Ok(true)
+    }
+
+    /// Write all views to the views file (SR-3).
+    fn persist(&self) -> anyhow::Result<()> {
+        let path = self.path.as_deref().context("no views file configured")?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        }
+        let file = ViewsFile {
+            views: self.views.clone(),
+        };
+        let tmp = tmp_path(path);
+        std::fs::write(&tmp, serde_json::to_string_pretty(&file)? + "\n")
+            .with_context(|| format!("writing {}", tmp.display()))?;
+        std::fs::rename(&tmp, path).with_context(|| format!("replacing {}", path.display()))
+    }
+}
+
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+/// Default views file: `$XDG_CONFIG_HOME/monarch/admin_tui_views.json`,
+/// falling back to `$HOME/.config`. `None` if neither is set.
+pub(crate) fn default_views_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    Some(config_dir.join("monarch").join("admin_tui_views.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn views_in(dir: &tempfile::TempDir) -> SavedViews {
+        SavedViews::load(dir.path().join("views.json")).unwrap()
+    }
+
+    #[test]
+    fn load_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(views_in(&dir).names().count(), 0);
+    }
+
+    // SR-3: saved views survive a reload.
+    #[test]
+    fn save_and_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut views = views_in(&dir);
+        views.save("trainers", "proc:trainer*").unwrap();
+        let reloaded = views_in(&dir);
+        assert_eq!(reloaded.names().collect::<Vec<_>>(), vec!["trainers"]);
+        assert_eq!(
+            reloaded.expand("@trainers status:failed").unwrap(),
+            "proc:trainer* status:failed"
+        );
+        assert!(!dir.path().join("views.json.tmp").exists());
+    }
+
+    #[test]
+    fn remove_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut views = views_in(&dir);
+        views.save("a", "x").unwrap();
+        assert!(views.remove("a").unwrap());
+        assert!(!views.remove("a").unwrap());
+        assert_eq!(views_in(&dir).names().count(), 0);
+    }
+
+    // SR-2: views cannot nest.
+    #[test]
+    fn save_rejects_view_references_and_bad_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut views = views_in(&dir);
+        assert!(views.save("a", "@b").is_err());
+        assert!(views.save("a b", "x").is_err());
+        assert_eq!(views.names().count(), 0);
+    }
+
+    #[test]
+    fn expand_negated_view() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut views = views_in(&dir);
+        views.save("sys", "-proc:service").unwrap();
+        views.save("two", "a b").unwrap();
+        assert_eq!(views.expand("-@sys x").unwrap(), "proc:service x");
+        assert!(views.expand("-@two").is_err());
+        assert!(views.expand("@missing").is_err());
+    }
+
+    #[test]
+    fn save_without_file_fails_and_rolls_back() {
+        let mut views = SavedViews::default();
+        assert!(views.save("a", "x").is_err());
+        assert_eq!(views.names().count(), 0);
+    }
+
+    // SR-1: a bad edit keeps the last good query in effect.
+    #[test]
+    fn search_state_keeps_last_good_query() {
+        let views = SavedViews::default();
+        let mut state = SearchState::default();
+        state.set_input("proc:trainer*".to_string(), &views);
+        assert!(state.is_active());
+        assert!(state.error.is_none());
+        state.set_input("proc:trainer* colour:red".to_string(), &views);
+        assert!(state.error.is_some());
+        assert!(state.is_active());
+        assert_eq!(state.applied, "proc:trainer*");
+    }
+
+    #[test]
+    fn search_state_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut views = views_in(&dir);
+        let mut state = SearchState::default();
+        state.set_input(":save failing".to_string(), &views);
+        assert!(state.run_command(&mut views));
+        assert!(state.error.is_some());
+        state.set_input("status:failed".to_string(), &views);
+        state.commit();
+        state.set_input(String::new(), &views);
+        state.set_input(":save failing".to_string(), &views);
+        assert!(state.run_command(&mut views));
+        assert_eq!(state.message.as_deref(), Some("saved view @failing"));
+        // Success restores the committed filter.
+        assert_eq!(state.input, "status:failed");
+        assert_eq!(state.applied, "status:failed");
+        state.set_input(":bogus".to_string(), &views);
+        assert!(state.run_command(&mut views));
+        assert!(state.error.is_some());
+        state.set_input("status:failed".to_string(), &views);
+        assert!(!state.run_command(&mut views));
+    }
+}
@@ -23,6 +23,7 @@ use super::*;
 use crate::diagnostics::DiagOutcome;
 use crate::diagnostics::DiagPhase;
 use crate::diagnostics::DiagResult;
+use crate::heatmap::HeatmapView;
 use crate::timeouts::TuiTimeoutPolicy;
 
 /// Test-only convenience policy with current production defaults.
@@ -37,6 +38,9 @@ fn test_policy() -> TuiTimeoutPolicy {
         tls_cert: None,
         tls_key: None,
         diagnose: false,
+        replay: None,
+        action_token_file: None,
+        views_file: None,
     })
 }
 
@@ -2508,3 +2512,702 @@ fn refresh_policy_transitions_with_job_lifecycle() {
         RefreshPolicy::Baseline,
     );
 }
+
+// ── Operator action tests (OP-1 through OP-5) ─────────────────────────────
+//
+// OP-3 (overlay-ownership): structural, same argument as PY-2.
+// OP-6 (api-only): request construction is covered by the unit tests
+//   in operator.rs; sending needs a live admin server.
+
+use crate::operator::DEFAULT_PROFILE_DURATION_S;
+use crate::operator::OperatorAction;
+
+fn key(c: char) -> KeyEvent {
+    KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+}
+
+fn host_node(name: &str) -> TreeNode {
+    TreeNode {
+        reference: host(name),
+        label: name.into(),
+        node_type: NodeType::Host,
+        expanded: false,
+        fetched: true,
+        has_children: false,
+        stopped: false,
+        failed: false,
+        is_system: false,
+        children: vec![],
+    }
+}
+
+// OP-5: 'x' stops the selected actor.
+#[test]
+fn operator_x_on_actor_proposes_stop_actor() {
+    let mut app = make_app_with_cursor(vec![actor_node("actor1")], 0);
+    let NodeRef::Actor(id) = actor("actor1") else {
+        unreachable!()
+    };
+    assert!(matches!(
+        app.on_key(key('x')),
+        KeyResult::ProposeAction(OperatorAction::StopActor(a)) if a == id
+    ));
+}
+
+// OP-5: 'x' stops the selected proc.
+#[test]
+fn operator_x_on_proc_proposes_stop_proc() {
+    let mut app = make_app_with_cursor(vec![proc_node("worker")], 0);
+    assert!(matches!(
+        app.on_key(key('x')),
+        KeyResult::ProposeAction(OperatorAction::StopProc(_))
+    ));
+}
+
+// OP-5: 'x' drains the selected host.
+#[test]
+fn operator_x_on_host_proposes_drain_host() {
+    let mut app = make_app_with_cursor(vec![host_node("host1")], 0);
+    let action = match app.on_key(key('x')) {
+        KeyResult::ProposeAction(action) => action,
+        other => panic!("unexpected {other:?}"),
+    };
+    assert!(matches!(action, OperatorAction::DrainHost(_)));
+    assert!(action.target().starts_with("host:"));
+}
+
+// OP-5: 'x' on an empty tree is a no-op.
+#[test]
+fn operator_x_on_empty_tree_is_noop() {
+    let mut app = make_app_with_cursor(vec![], 0);
+    assert!(matches!(app.on_key(key('x')), KeyResult::None));
+}
+
+// OP-5: 'P' follows PY-4 target resolution.
+#[test]
+fn operator_profile_uses_pyspy_target() {
+    let mut app = make_app_with_cursor(vec![proc_node("worker")], 0);
+    assert!(matches!(
+        app.on_key(key('P')),
+        KeyResult::ProposeAction(OperatorAction::Profile { duration_s, .. })
+            if duration_s == DEFAULT_PROFILE_DURATION_S
+    ));
+    let mut app = make_app_with_cursor(vec![host_node("host1")], 0);
+    assert!(matches!(app.on_key(key('P')), KeyResult::None));
+}
+
+// OP-1: proposing opens an unconfirmed overlay and sends nothing.
+#[test]
+fn operator_propose_opens_unconfirmed_overlay() {
+    let mut app = make_app_with_cursor(vec![proc_node("worker")], 0);
+    let Some(action) = app.stop_action_target() else {
+        panic!("proc selection must resolve")
+    };
+    app.propose_action(action);
+    assert!(app.overlay.is_some());
+    assert!(matches!(
+        app.active_job,
+        Some(ActiveJob::Operator {
+            confirmed: false,
+            rx: None,
+            ..
+        })
+    ));
+    let overlay = app.overlay.as_ref().unwrap();
+    assert!(line_text(&overlay.title).contains("stop proc"));
+    assert!(
+        overlay
+            .lines
+            .iter()
+            .any(|l| line_text(l).contains("confirm"))
+    );
+    // OP-1: Esc discards the proposal.
+    app.on_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(app.active_job.is_none());
+    assert!(app.overlay.is_none());
+}
+
+// OP-1: y and Enter confirm; other keys do not.
+#[test]
+fn operator_overlay_confirm_keys() {
+    let mut app = make_app_with_cursor(vec![proc_node("worker")], 0);
+    app.propose_action(app.stop_action_target().unwrap());
+    assert!(matches!(app.on_key(key('y')), KeyResult::ConfirmAction));
+    assert!(matches!(
+        app.on_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
+        KeyResult::ConfirmAction
+    ));
+    assert!(matches!(app.on_key(key('n')), KeyResult::None));
+    assert!(matches!(app.on_key(key('x')), KeyResult::None));
+}
+
+// OP-1: a confirmed action is never re-sent from the overlay.
+#[test]
+fn operator_overlay_confirmed_ignores_confirm_keys() {
+    let mut app = make_app_with_cursor(vec![proc_node("worker")], 0);
+    app.set_job(ActiveJob::Operator {
+        action: app.stop_action_target().unwrap(),
+        confirmed: true,
+        rx: None,
+        lines: vec![],
+        completed_at: Some("14:30:00".to_string()),
+    });
+    assert!(matches!(app.on_key(key('y')), KeyResult::None));
+}
+
+// OP-1: +/- re-propose the profile with an adjacent duration.
+#[test]
+fn operator_overlay_adjusts_profile_duration() {
+    let mut app = make_app_with_cursor(vec![proc_node("worker")], 0);
+    let KeyResult::ProposeAction(action) = app.on_key(key('P')) else {
+        panic!("expected proposal")
+    };
+    app.propose_action(action);
+    let KeyResult::ProposeAction(longer) = app.on_key(key('+')) else {
+        panic!("expected longer profile")
+    };
+    assert!(matches!(
+        longer,
+        OperatorAction::Profile { duration_s, .. } if duration_s > DEFAULT_PROFILE_DURATION_S
+    ));
+    let KeyResult::ProposeAction(shorter) = app.on_key(key('-')) else {
+        panic!("expected shorter profile")
+    };
+    assert!(matches!(
+        shorter,
+        OperatorAction::Profile { duration_s, .. } if duration_s < DEFAULT_PROFILE_DURATION_S
+    ));
+}
+
+// OP-1: footer reflects the confirm / running / done phases.
+#[test]
+fn footer_text_operator_phases() {
+    let labels = Labels::en();
+    let NodeRef::Proc(proc) = proc_ref("worker") else {
+        unreachable!()
+    };
+    let job = |confirmed, rx, action| {
+        Some(ActiveJob::Operator {
+            action,
+            confirmed,
+            rx,
+            lines: vec![],
+            completed_at: None,
+        })
+    };
+    let stop = OperatorAction::StopProc(proc.clone());
+    let profile = OperatorAction::Profile {
+        proc,
+        duration_s: DEFAULT_PROFILE_DURATION_S,
+    };
+    assert_eq!(
+        ActiveJob::footer_text(&job(false, None, stop.clone()), &labels),
+        labels.footer_op_confirm_help_text
+    );
+    assert_eq!(
+        ActiveJob::footer_text(&job(false, None, profile), &labels),
+        labels.footer_op_profile_confirm_help_text
+    );
+    let (_tx, rx) = tokio::sync::oneshot::channel();
+    assert_eq!(
+        ActiveJob::footer_text(&job(true, Some(rx), stop.clone()), &labels),
+        labels.footer_diag_running_help_text
+    );
+    assert_eq!(
+        ActiveJob::footer_text(&job(true, None, stop), &labels),
+        labels.footer_op_done_help_text
+    );
+}
+
+// TP-10: operator action in flight → Suspend; awaiting confirmation
+// or completed → Baseline.
+#[test]
+fn refresh_policy_operator_action() {
+    let NodeRef::Proc(proc) = proc_ref("worker") else {
+        unreachable!()
+    };
+    let (_tx, rx) = tokio::sync::oneshot::channel();
+    let job = |confirmed, rx| {
+        Some(ActiveJob::Operator {
+            action: OperatorAction::StopProc(proc.clone()),
+            confirmed,
+            rx,
+            lines: vec![],
+            completed_at: None,
+        })
+    };
+    assert_eq!(
+        refresh_policy_for_job(&job(false, None)),
+        RefreshPolicy::Baseline
+    );
+    assert_eq!(
+        refresh_policy_for_job(&job(true, Some(rx))),
+        RefreshPolicy::Suspend
+    );
+    assert_eq!(
+        refresh_policy_for_job(&job(true, None)),
+        RefreshPolicy::Baseline
+    );
+}
+
+// OP-3: the result lands in the operator overlay.
+#[test]
+fn on_event_operator_result() {
+    let NodeRef::Proc(proc) = proc_ref("worker") else {
+        unreachable!()
+    };
+    let (_tx, rx) = tokio::sync::oneshot::channel();
+    let mut job = ActiveJob::Operator {
+        action: OperatorAction::StopProc(proc),
+        confirmed: true,
+        rx: Some(rx),
+        lines: vec![],
+        completed_at: None,
+    };
+    job.on_event(ActiveJobEvent::OperatorResult(vec![
+        ratatui::text::Line::from("outcome: completed"),
+    ]));
+    let ActiveJob::Operator {
+        rx,
+        lines,
+        completed_at,
+        ..
+    } = job
+    else {
+        unreachable!()
+    };
+    assert!(rx.is_none());
+    assert_eq!(lines.len(), 1);
+    assert!(completed_at.is_some());
+}
+
+// ── Search tests (SE-1 through SE-3) ───────────────────────────────────────
+//
+// SR-* are covered by unit tests in search.rs, FQ-* in filter.rs.
+// SE-3's expansion loop needs a live admin server.
+
+fn type_keys(app: &mut App, text: &str) {
+    for c in text.chars() {
+        app.on_key(key(c));
+    }
+}
+
+fn type_search(app: &mut App, text: &str) {
+    app.on_key(key('/'));
+    type_keys(app, text);
+}
+
+fn visible_labels(app: &App) -> Vec<String> {
+    app.visible_rows()
+        .as_slice()
+        .iter()
+        .map(|row| row.node.label.clone())
+        .collect()
+}
+
+fn search_host() -> TreeNode {
+    let mut trainer = proc_node("trainer0");
+    trainer.has_children = true;
+    trainer.children = vec![actor_node("learner")];
+    let mut service = proc_node("service");
+    service.has_children = true;
+    service.children = vec![actor_node("agent")];
+    let mut host = host_node("host1");
+    host.has_children = true;
+    host.children = vec![trainer, service];
+    host
+}
+
+// SE-1: the filter shows matches and their ancestors, regardless of
+// expansion; clearing it restores the unfiltered rows.
+#[test]
+fn search_filters_collapsed_subtrees() {
+    let mut app = make_app_with_cursor(vec![search_host()], 0);
+    assert_eq!(visible_labels(&app), vec!["host1"]);
+    type_search(&mut app, "proc:trainer*");
+    assert!(app.search.is_active());
+    assert_eq!(visible_labels(&app), vec!["host1", "trainer0"]);
+    assert_eq!(app.cursor.len(), 2);
+    // The cursor lands on the first match, not its ancestor.
+    assert_eq!(app.cursor.pos(), 1);
+
+    app.on_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(!app.search.is_active());
+    assert!(!app.search.editing);
+    assert_eq!(visible_labels(&app), vec!["host1"]);
+    assert_eq!(app.cursor.len(), 1);
+}
+
+// SE-2: the open prompt captures keys that are bindings elsewhere.
+#[test]
+fn search_prompt_captures_keys() {
+    let mut app = make_app_with_cursor(vec![proc_node("worker")], 0);
+    type_search(&mut app, "qxs");
+    assert!(!app.should_quit);
+    assert!(app.active_job.is_none());
+    assert!(!app.show_system);
+    assert_eq!(app.search.input, "qxs");
+    app.on_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    assert_eq!(app.search.input, "qx");
+}
+
+// SE-1: free text matches labels; edits refilter incrementally.
+#[test]
+fn search_free_text_is_incremental() {
+    let mut app = make_app_with_cursor(vec![search_host()], 0);
+    type_search(&mut app, "learn");
+    assert_eq!(visible_labels(&app), vec!["host1", "trainer0", "learner"]);
+    for _ in 0.."learn".len() {
+        app.on_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    }
+    assert_eq!(visible_labels(&app), vec!["host1"]);
+    type_keys(&mut app, "agent");
+    assert_eq!(visible_labels(&app), vec!["host1", "service", "agent"]);
+}
+
+// SR-1: an unparseable edit keeps the previous filter on screen.
+#[test]
+fn search_bad_input_keeps_previous_filter() {
+    let mut app = make_app_with_cursor(vec![search_host()], 0);
+    type_search(&mut app, "proc:service ~(");
+    assert!(app.search.error.is_some());
+    assert_eq!(visible_labels(&app), vec!["host1", "service"]);
+}
+
+// SE-3: Enter commits an active filter for expansion; an empty
+// prompt commits nothing.
+#[test]
+fn search_enter_commits() {
+    let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+    let mut app = make_app_with_cursor(vec![search_host()], 0);
+    type_search(&mut app, "actor:learner");
+    assert!(matches!(app.on_key(enter), KeyResult::SearchCommitted));
+    assert!(!app.search.editing);
+    assert!(app.search.is_active());
+
+    let mut app = make_app_with_cursor(vec![search_host()], 0);
+    app.on_key(key('/'));
+    assert!(matches!(app.on_key(enter), KeyResult::None));
+    assert!(!app.search.editing);
+}
+
+// n/N step through matches, wrapping, skipping non-matching ancestors.
+#[test]
+fn search_next_prev_match() {
+    let mut app = make_app_with_cursor(
+        vec![
+            actor_node("learner0"),
+            actor_node("agent"),
+            actor_node("learner1"),
+        ],
+        0,
+    );
+    type_search(&mut app, "actor:learner*");
+    app.on_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(app.search_match_positions(), vec![0, 1]);
+    assert_eq!(app.cursor.pos(), 0);
+    assert!(matches!(app.on_key(key('n')), KeyResult::DetailChanged));
+    assert_eq!(app.cursor.pos(), 1);
+    app.on_key(key('n'));
+    assert_eq!(app.cursor.pos(), 0);
+    app.on_key(key('N'));
+    assert_eq!(app.cursor.pos(), 1);
+}
+
+// `:save` saves the committed filter and restores it in the prompt.
+#[test]
+fn search_save_view_and_recall() {
+    let dir = tempfile::tempdir().unwrap();
+    let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+    let mut app = make_app_with_cursor(vec![search_host()], 0);
+    app.views = crate::search::SavedViews::load(dir.path().join("views.json")).unwrap();
+    type_search(&mut app, "actor:agent");
+    app.on_key(enter);
+    app.on_key(key('/'));
+    app.on_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+    type_keys(&mut app, ":save agents");
+    assert!(matches!(app.on_key(enter), KeyResult::DetailChanged));
+    assert!(app.search.editing);
+    assert!(app.search.error.is_none());
+    assert_eq!(app.search.input, "actor:agent");
+    assert_eq!(visible_labels(&app), vec!["host1", "service", "agent"]);
+
+    app.on_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    type_search(&mut app, "@agents");
+    assert_eq!(visible_labels(&app), vec!["host1", "service", "agent"]);
+}
+
+// Saving without a views file reports an error instead of failing
+// silently.
+#[test]
+fn search_save_without_views_file_reports_error() {
+    let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+    let mut app = make_app_with_cursor(vec![search_host()], 0);
+    type_search(&mut app, "actor:agent");
+    app.on_key(enter);
+    app.on_key(key('/'));
+    app.on_key(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL));
+    type_keys(&mut app, ":save agents");
+    assert!(matches!(app.on_key(enter), KeyResult::None));
+    assert!(app.search.editing);
+    assert!(
+        app.search
+            .error
+            .as_deref()
+            .is_some_and(|e| e.contains("no views file"))
+    );
+}
+
+// ── Replay invariant coverage ──────────────────────────────────────────────
+//
+// RP-1, RP-3, RP-4 and RP-5 are covered by unit tests in replay.rs.
+// The tests below drive App through a replayed timeline (RP-2).
+
+fn replay_frame(id: &str, actors: &[&str]) -> crate::replay::ReplayFrame {
+    let worker = proc_ref("worker");
+    let mut payloads = HashMap::new();
+    payloads.insert(
+        root(),
+        NodePayload {
+            identity: root(),
+            properties: NodeProperties::Root {
+                num_hosts: 0,
+                started_at: SystemTime::UNIX_EPOCH,
+                started_by: "user".into(),
+                system_children: vec![],
+            },
+            children: vec![worker.clone()],
+            parent: None,
+            as_of: SystemTime::UNIX_EPOCH,
+        },
+    );
+    payloads.insert(
+        worker.clone(),
+        NodePayload {
+            identity: worker.clone(),
+            properties: NodeProperties::Proc {
+                proc_name: "worker".into(),
+                num_actors: actors.len(),
+                system_children: vec![],
+                stopped_children: vec![],
+                stopped_retention_cap: 0,
+                is_poisoned: false,
+                failed_actor_count: 0,
+                debug: Default::default(),
+                generation: None,
+            },
+            children: actors.iter().map(|a| actor(a)).collect(),
+            parent: Some(root()),
+            as_of: SystemTime::UNIX_EPOCH,
+        },
+    );
+    for name in actors {
+        payloads.insert(
+            actor(name),
+            NodePayload {
+                identity: actor(name),
+                properties: NodeProperties::Actor {
+                    actor_status: "running".into(),
+                    actor_type: "TestActor".into(),
+                    messages_processed: 0,
+                    created_at: None,
+                    last_message_handler: None,
+                    total_processing_time_us: 0,
+                    flight_recorder: None,
+                    is_system: false,
+                    failure_info: None,
+                },
+                children: vec![],
+                parent: Some(worker.clone()),
+                as_of: SystemTime::UNIX_EPOCH,
+            },
+        );
+    }
+    crate::replay::ReplayFrame {
+        snapshot_id: id.into(),
+        snapshot_ts: SystemTime::UNIX_EPOCH,
+        payloads,
+    }
+}
+
+fn replay_app(frames: Vec<crate::replay::ReplayFrame>) -> App {
+    // An unroutable base URL: any HTTP request would fail the test.
+    App::new(
+        "http://invalid.invalid:1".to_string(),
+        reqwest::Client::new(),
+        ThemeName::Nord,
+        LangName::En,
+        test_policy(),
+    )
+    .with_replay(crate::replay::Timeline::from_frames(
+        std::path::PathBuf::from("/replay"),
+        frames,
+    ))
+}
+
+fn child_refs(app: &App) -> Vec<NodeRef> {
+    app.tree().unwrap().children[0]
+        .children
+        .iter()
+        .map(|c| c.reference.clone())
+        .collect()
+}
+
+// RP-2, RP-5: the tree is built from the current frame; stepping
+// forward keeps expansion and grafts the removed actor back in.
+#[tokio::test]
+async fn replay_step_rebuilds_tree_from_frame() {
+    let mut app = replay_app(vec![
+        replay_frame("s1", &["a", "b"]),
+        replay_frame("s2", &["a", "c"]),
+    ]);
+    app.refresh().await;
+    assert!(app.error.is_none());
+    assert!(app.expand_node(&proc_ref("worker"), 0).await);
+    assert_eq!(child_refs(&app), vec![actor("a"), actor("b")]);
+
+    let key = KeyEvent::new(KeyCode::Right, KeyModifiers::NONE);
+    assert!(matches!(app.on_key(key), KeyResult::NeedsRefresh));
+    app.refresh().await;
+    assert_eq!(child_refs(&app), vec![actor("a"), actor("b"), actor("c")]);
+    let timeline = app.replay.as_ref().unwrap();
+    assert_eq!(
+        timeline.change(&actor("b")),
+        Some(crate::replay::NodeChange::Removed)
+    );
+    assert_eq!(
+        timeline.change(&actor("c")),
+        Some(crate::replay::NodeChange::Added)
+    );
+
+    // Already at the last frame.
+    assert!(matches!(app.on_key(key), KeyResult::None));
+    let first = KeyEvent::new(KeyCode::Char('{'), KeyModifiers::NONE);
+    assert!(matches!(app.on_key(first), KeyResult::NeedsRefresh));
+    assert_eq!(app.replay.as_ref().unwrap().pos(), 0);
+}
+
+// RP-2: actions that need a live admin server are disabled.
+#[test]
+fn replay_disables_live_actions() {
+    let mut app = replay_app(vec![replay_frame("s1", &["a"])]);
+    app.set_tree(Some(TreeNode {
+        reference: root(),
+        label: "Root".into(),
+        node_type: NodeType::Root,
+        expanded: true,
+        fetched: true,
+        has_children: true,
+        stopped: false,
+        failed: false,
+        is_system: false,
+        children: vec![proc_node("worker")],
+    }));
+    app.cursor.update_len(1);
+    // OP-4: operator actions included.
+    for c in ['p', 'C', 'd', 'x', 'P'] {
+        let key = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        assert!(matches!(app.on_key(key), KeyResult::None));
+    }
+}
+
+// ── Heatmap tests (HV-1 through HV-3) ──────────────────────────────────────
+
+/// Host `name` with procs `trainer_0` and `trainer_1`, each running
+/// one `learner` actor, fully expanded.
+fn heatmap_host(name: &str) -> TreeNode {
+    let mut host = host_node(name);
+    host.expanded = true;
+    host.has_children = true;
+    host.children = (0..2)
+        .map(|rank| {
+            let proc_name = format!("trainer_{rank}");
+            let id = format!("unix:@{name},{proc_name},learner[0]");
+            let mut learner = actor_node("learner");
+            learner.reference =
+                NodeRef::Actor(hyperactor_reference::ActorId::from_str(&id).unwrap());
+            let mut proc = proc_node(&proc_name);
+            proc.reference = NodeRef::Proc(
+                hyperactor_reference::ProcId::from_str(&format!("unix:@{name},{proc_name}"))
+                    .unwrap(),
+            );
+            proc.expanded = true;
+            proc.has_children = true;
+            proc.children = vec![learner];
+            proc
+        })
+        .collect();
+    host
+}
+
+fn heatmap_app() -> App {
+    let mut app = make_app_with_cursor(vec![heatmap_host("h0"), heatmap_host("h1")], 0);
+    app.heatmap = Some(HeatmapView::default());
+    app
+}
+
+// `m` asks the event loop to open the heatmap.
+#[test]
+fn heatmap_key_opens() {
+    let mut app = make_app_with_cursor(vec![heatmap_host("h0")], 0);
+    assert!(matches!(app.on_key(key('m')), KeyResult::OpenHeatmap));
+}
+
+// HV-1: while open, the heatmap owns navigation and tree bindings do
+// not fire.
+#[test]
+fn heatmap_captures_keys() {
+    let mut app = heatmap_app();
+    let grids = app.heatmap_grids();
+    assert_eq!(grids.len(), 1);
+    assert_eq!(grids[0].hosts, vec!["h0", "h1"]);
+
+    assert!(matches!(app.on_key(key('j')), KeyResult::DetailChanged));
+    assert!(matches!(app.on_key(key('l')), KeyResult::DetailChanged));
+    let view = app.heatmap.as_ref().unwrap();
+    assert_eq!(view.position(&grids[0]), (1, 1));
+    // Already in the corner.
+    assert!(matches!(app.on_key(key('l')), KeyResult::None));
+
+    for c in ['s', 'd', 'x', 'c', '/'] {
+        assert!(matches!(app.on_key(key(c)), KeyResult::None));
+    }
+    assert!(app.active_job.is_none());
+    assert!(!app.show_system);
+    assert!(!app.search.editing);
+    assert_eq!(app.cursor.pos(), 0);
+
+    app.on_key(key('q'));
+    assert!(app.should_quit);
+}
+
+// HV-3: Enter closes the heatmap and selects the cell's actor.
+#[test]
+fn heatmap_enter_drills_to_actor() {
+    let mut app = heatmap_app();
+    app.on_key(key('j'));
+    app.on_key(key('l'));
+    let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+    assert!(matches!(app.on_key(enter), KeyResult::DetailChanged));
+    assert!(app.heatmap.is_none());
+    let expected = hyperactor_reference::ActorId::from_str("unix:@h1,trainer_1,learner[0]");
+    assert_eq!(
+        app.selected_reference(),
+        Some(&NodeRef::Actor(expected.unwrap()))
+    );
+}
+
+// Esc and `m` return to the tree without moving its cursor.
+#[test]
+fn heatmap_escape_closes() {
+    let mut app = heatmap_app();
+    app.on_key(key('j'));
+    let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+    assert!(matches!(app.on_key(esc), KeyResult::DetailChanged));
+    assert!(app.heatmap.is_none());
+    assert_eq!(app.cursor.pos(), 0);
+
+    app.heatmap = Some(HeatmapView::default());
+    app.on_key(key('m'));
+    assert!(app.heatmap.is_none());
+}
@@ -12,6 +12,7 @@ use ratatui::style::Modifier;
 use ratatui::style::Style;
 
 use crate::model::NodeType;
+use crate::replay::NodeChange;
 
 /// Selectable color theme.
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
@@ -74,6 +75,7 @@ pub(crate) struct Labels {
     pub(crate) stopped: &'static str,
     pub(crate) stopped_on: &'static str,
     pub(crate) stopped_off: &'static str,
+    pub(crate) replay: &'static str,
 
     // Detail pane labels
     pub(crate) hosts: &'static str,
@@ -134,6 +136,19 @@ pub(crate) struct Labels {
     pub(crate) diag_note_user_proc: &'static str,
     pub(crate) diag_note_user_actor: &'static str,
 
+    // Operator action overlay (OP-*)
+    pub(crate) op_confirm: &'static str,
+
+    // Search prompt (SE-*)
+    pub(crate) search_filter: &'static str,
+    pub(crate) search_matches: &'static str,
+
+    // Rank heatmap (HM-*)
+    pub(crate) heat_metric_status: &'static str,
+    pub(crate) heat_metric_queue: &'static str,
+    pub(crate) heat_metric_age: &'static str,
+    pub(crate) heatmap_empty: &'static str,
+
     // Pane titles
     pub(crate) pane_topology: &'static str,
     pub(crate) pane_details: &'static str,
@@ -144,6 +159,7 @@ pub(crate) struct Labels {
     pub(crate) pane_actor_details: &'static str,
     pub(crate) pane_flight_recorder: &'static str,
     pub(crate) pane_diagnostics: &'static str,
+    pub(crate) pane_heatmap: &'static str,
 
     // Footer
     pub(crate) footer_help_text: &'static str,
@@ -151,6 +167,13 @@ pub(crate) struct Labels {
     pub(crate) footer_diag_completed_help_text: &'static str,
     pub(crate) footer_pyspy_help_text: &'static str,
     pub(crate) footer_config_help_text: &'static str,
+    pub(crate) footer_replay_help_text: &'static str,
+    pub(crate) footer_op_confirm_help_text: &'static str,
+    pub(crate) footer_op_profile_confirm_help_text: &'static str,
+    pub(crate) footer_op_done_help_text: &'static str,
+    pub(crate) footer_search_edit_help_text: &'static str,
+    pub(crate) footer_search_active_help_text: &'static str,
+    pub(crate) footer_heatmap_help_text: &'static str,
 }
 
 impl Labels {
@@ -169,6 +192,7 @@ impl Labels {
             stopped: "stopped:",
             stopped_on: "on",
             stopped_off: "off",
+            replay: "replay: ",
             hosts: "Hosts: ",
             started_by: "Started by: ",
             uptime_detail: "Uptime: ",
@@ -218,6 +242,13 @@ impl Labels {
             diag_note_proc_agent: "proc agent — manages actor spawn and lifecycle on this proc",
             diag_note_user_proc: "user proc — your workload is alive",
             diag_note_user_actor: "user actor — reachable through full stack",
+            op_confirm: "confirm?",
+            search_filter: "filter: ",
+            search_matches: "matches",
+            heat_metric_status: "status",
+            heat_metric_queue: "queue depth (proc max)",
+            heat_metric_age: "last activity age",
+            heatmap_empty: "No actor meshes loaded. Meshes are recognized by procs named <mesh>_<rank>.",
             pane_topology: "Topology",
             pane_details: "Details",
             pane_error: "Error",
@@ -227,11 +258,19 @@ impl Labels {
             pane_actor_details: "Actor Details",
             pane_flight_recorder: "Flight Recorder",
             pane_diagnostics: "Diagnostics",
-            footer_help_text: "q: quit | j/k: navigate | g/G: top/bottom | Tab: expand/collapse | c: collapse all | s: system procs | h: stopped actors | d: diag | p: py-spy | C: config",
+            pane_heatmap: "Rank Heatmap",
+            footer_help_text: "q: quit | j/k: navigate | g/G: top/bottom | Tab: expand/collapse | c: collapse all | s: system procs | h: stopped actors | d: diag | p: py-spy | C: config | x: stop/drain | P: profile | /: search | m: heatmap",
             footer_diag_running_help_text: "q: quit | Esc: cancel | j/k: scroll",
             footer_diag_completed_help_text: "q: quit | Esc: back to topology | j/k: scroll | r: rerun",
             footer_pyspy_help_text: "q: quit | Esc: back to topology | j/k: scroll | p: refresh",
             footer_config_help_text: "q: quit | Esc: back to topology | j/k: scroll | C: refresh",
+            footer_replay_help_text: "q: quit | j/k: navigate | ←/→ or [/]: step snapshot | {/}: first/last | Tab: expand/collapse | c: collapse all | s: system procs | h: stopped actors",
+            footer_op_confirm_help_text: "q: quit | Esc: cancel | y/Enter: confirm",
+            footer_op_profile_confirm_help_text: "q: quit | Esc: cancel | y/Enter: confirm | +/-: duration",
+            footer_op_done_help_text: "q: quit | Esc: back to topology | j/k: scroll",
+            footer_search_edit_help_text: "Enter: apply | Esc: clear | @NAME: saved view | :save NAME | :delete NAME",
+            footer_search_active_help_text: "n/N: next/prev match | /: edit filter | Esc: clear filter",
+            footer_heatmap_help_text: "q: quit | ←↓↑→/hjkl: move | Tab: next mesh | v: metric | Enter: show in tree | Esc/m: close",
         }
     }
 
@@ -250,6 +289,7 @@ impl Labels {
             stopped: "已停止:",
             stopped_on: "开",
             stopped_off: "关",
+            replay: "回放: ",
             hosts: "主机: ",
             started_by: "启动者: ",
             uptime_detail: "运行时间: ",
@@ -299,6 +339,13 @@ impl Labels {
             diag_note_proc_agent: "进程代理 — 管理此进程上的Actor派生和生命周期",
             diag_note_user_proc: "用户进程 — 您的工作负载正在运行",
             diag_note_user_actor: "用户Actor — 可通过完整堆栈访问",
+            op_confirm: "确认?",
+            search_filter: "过滤: ",
+            search_matches: "个匹配",
+            heat_metric_status: "状态",
+            heat_metric_queue: "队列深度(进程最大值)",
+            heat_metric_age: "距上次活动",
+            heatmap_empty: "未加载执行器网格。网格通过名为 <mesh>_<rank> 的进程识别。",
             pane_topology: "拓扑",
             pane_details: "详情",
             pane_error: "错误",
@@ -308,11 +355,19 @@ impl Labels {
             pane_actor_details: "执行器详情",
             pane_flight_recorder: "飞行记录器",
             pane_diagnostics: "诊断",
-            footer_help_text: "q: 退出 | j/k: 导航 | g/G: 顶部/底部 | Tab: 展开/折叠 | c: 全部折叠 | s: 系统进程 | h: 已停止 | d: 诊断 | p: py-spy | C: 配置",
+            pane_heatmap: "Rank 热力图",
+            footer_help_text: "q: 退出 | j/k: 导航 | g/G: 顶部/底部 | Tab: 展开/折叠 | c: 全部折叠 | s: 系统进程 | h: 已停止 | d: 诊断 | p: py-spy | C: 配置 | x: 停止/排空 | P: 性能剖析 | /: 搜索 | m: 热力图",
             footer_diag_running_help_text: "q: 退出 | Esc: 取消 | j/k: 滚动",
             footer_diag_completed_help_text: "q: 退出 | Esc: 返回拓扑 | j/k: 滚动 | r: 重新运行",
             footer_pyspy_help_text: "q: 退出 | Esc: 返回拓扑 | j/k: 滚动 | p: 刷新",
             footer_config_help_text: "q: 退出 | Esc: 返回拓扑 | j/k: 滚动 | C: 刷新",
+            footer_replay_help_text: "q: 退出 | j/k: 导航 | ←/→ 或 [/]: 切换快照 | {/}: 首个/最后 | Tab: 展开/折叠 | c: 全部折叠 | s: 系统进程 | h: 已停止",
+            footer_op_confirm_help_text: "q: 退出 | Esc: 取消 | y/Enter: 确认",
+            footer_op_profile_confirm_help_text: "q: 退出 | Esc: 取消 | y/Enter: 确认 | +/-: 时长",
+            footer_op_done_help_text: "q: 退出 | Esc: 返回拓扑 | j/k: 滚动",
+            footer_search_edit_help_text: "Enter: 应用 | Esc: 清除 | @名称: 已保存视图 | :save 名称 | :delete 名称",
+            footer_search_active_help_text: "n/N: 下一个/上一个匹配 | /: 编辑过滤 | Esc: 清除过滤",
+            footer_heatmap_help_text: "q: 退出 | ←↓↑→/hjkl: 移动 | Tab: 下一个网格 | v: 指标 | Enter: 在树中显示 | Esc/m: 关闭",
         }
     }
 }
@@ -361,6 +416,11 @@ pub(crate) struct ColorScheme {
     pub(crate) node_system_actor: Style,
     pub(crate) node_user_actor: Style,
 
+    // Replay change highlights (RP-4).
+    pub(crate) node_added: Style,
+    pub(crate) node_removed: Style,
+    pub(crate) node_newly_failed: Style,
+
     // Semantic states
     pub(crate) error: Style,
     pub(crate) info: Style,
@@ -425,6 +485,17 @@ impl ColorScheme {
             node_system_actor: Style::default().fg(frost_dark),
             node_user_actor: Style::default().fg(aurora_green),
 
+            // Replay change highlights
+            node_added: Style::default()
+                .fg(aurora_green)
+                .add_modifier(Modifier::BOLD),
+            node_removed: Style::default()
+                .fg(aurora_orange)
+                .add_modifier(Modifier::CROSSED_OUT),
+            node_newly_failed: Style::default()
+                .fg(aurora_red)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+
             // Semantic states
             error: Style::default().fg(aurora_red),
             info: Style::default().fg(frost_cyan),
@@ -487,6 +558,15 @@ impl ColorScheme {
             node_system_actor: Style::default().fg(orange),
             node_user_actor: Style::default().fg(green),
 
+            // Replay change highlights
+            node_added: Style::default().fg(green).add_modifier(Modifier::BOLD),
+            node_removed: Style::default()
+                .fg(orange)
+                .add_modifier(Modifier::CROSSED_OUT),
+            node_newly_failed: Style::default()
+                .fg(red)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+
             // Semantic states
             error: Style::default().fg(red),
             info: Style::default().fg(cyan),
@@ -519,6 +599,15 @@ impl ColorScheme {
             NodeType::Actor => self.node_actor,
         }
     }
+
+    /// Return the highlight style for a replay change.
+    pub(crate) fn change_style(&self, change: NodeChange) -> Style {
+        match change {
+            NodeChange::Added => self.node_added,
+            NodeChange::Removed => self.node_removed,
+            NodeChange::Failed => self.node_newly_failed,
+        }
+    }
 }
 
 /// Complete visual presentation — colors + text.
@@ -45,6 +45,10 @@ pub(crate) enum RequestOp {
     ConfigDump,
     /// `GET /v1/pyspy/{proc_reference}`.
     PySpyDump,
+    /// `POST /v1/actions/*` (stop actor, stop proc, drain host).
+    OperatorAction,
+    /// `POST /v1/pyspy_profile_svg/{proc_reference}`.
+    PySpyProfile,
 }
 
 /// All [`RequestOp`] variants, for iteration in law-based tests.
@@ -53,6 +57,8 @@ pub(crate) const ALL_REQUEST_OPS: &[RequestOp] = &[
     RequestOp::InteractiveFetch,
     RequestOp::ConfigDump,
     RequestOp::PySpyDump,
+    RequestOp::OperatorAction,
+    RequestOp::PySpyProfile,
 ];
 
 /// Per-probe budgets, applied via `tokio::time::timeout` inside the
@@ -89,6 +95,8 @@ pub(crate) struct TuiTimeoutPolicy {
     interactive_fetch: Duration,
     config_dump: Duration,
     pyspy_dump: Duration,
+    operator_action: Duration,
+    pyspy_profile: Duration,
     diagnostics_probe: Duration,
     diagnostics_run: Duration,
 }
@@ -105,15 +113,27 @@ impl TuiTimeoutPolicy {
     ///
     /// Diagnostics budgets preserve the existing effective values
     /// from the pre-policy implementation.
+    ///
+    /// Operator actions get the server's synchronous action budget
+    /// plus slack, so a slow drain surfaces the server's
+    /// `gateway_timeout` rather than a client-side timeout. Profiles
+    /// get the longest allowed capture plus the py-spy client budget.
     pub fn from_config(config: &TuiConfig) -> Self {
+        let pyspy_dump = hyperactor_config::global::get(
+            hyperactor_mesh::config::MESH_ADMIN_PYSPY_CLIENT_TIMEOUT,
+        );
         Self {
             refresh_interval: Duration::from_millis(config.refresh_ms),
             diagnostics_probe_slow: Duration::from_millis(500),
             interactive_fetch: Duration::from_secs(5),
             config_dump: Duration::from_secs(8),
-            pyspy_dump: hyperactor_config::global::get(
-                hyperactor_mesh::config::MESH_ADMIN_PYSPY_CLIENT_TIMEOUT,
-            ),
+            pyspy_dump,
+            operator_action: hyperactor_config::global::get(
+                hyperactor_mesh::config::MESH_ADMIN_ACTION_TIMEOUT,
+            ) + Duration::from_secs(5),
+            pyspy_profile: hyperactor_config::global::get(
+                hyperactor_mesh::config::MESH_ADMIN_PYSPY_MAX_PROFILE_DURATION,
+            ) + pyspy_dump,
             diagnostics_probe: Duration::from_secs(5),
             diagnostics_run: Duration::from_secs(120),
         }
@@ -125,6 +145,8 @@ impl TuiTimeoutPolicy {
             RequestOp::InteractiveFetch => self.interactive_fetch,
             RequestOp::ConfigDump => self.config_dump,
             RequestOp::PySpyDump => self.pyspy_dump,
+            RequestOp::OperatorAction => self.operator_action,
+            RequestOp::PySpyProfile => self.pyspy_profile,
         }
     }
 
@@ -187,6 +209,9 @@ mod tests {
             tls_cert: None,
             tls_key: None,
             diagnose: false,
+            replay: None,
+            action_token_file: None,
+            views_file: None,
         }
     }
 
@@ -220,6 +245,25 @@ mod tests {
         assert_eq!(policy.request_timeout(RequestOp::PySpyDump), expected);
     }
 
+    // TP-6/TP-9: operator actions outlast the server's action budget.
+    #[test]
+    fn from_config_request_budget_operator_action() {
+        let policy = TuiTimeoutPolicy::from_config(&default_config());
+        let server =
+            hyperactor_config::global::get(hyperactor_mesh::config::MESH_ADMIN_ACTION_TIMEOUT);
+        assert!(policy.request_timeout(RequestOp::OperatorAction) > server);
+    }
+
+    // TP-6/TP-9: profile budget covers the longest allowed capture.
+    #[test]
+    fn from_config_request_budget_pyspy_profile() {
+        let policy = TuiTimeoutPolicy::from_config(&default_config());
+        let max = hyperactor_config::global::get(
+            hyperactor_mesh::config::MESH_ADMIN_PYSPY_MAX_PROFILE_DURATION,
+        );
+        assert!(policy.request_timeout(RequestOp::PySpyProfile) > max);
+    }
+
     // TP-6: probe budget.
     #[test]
     fn from_config_preserves_probe_timeout() {
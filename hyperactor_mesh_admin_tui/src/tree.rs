@@ -15,6 +15,10 @@
 //!   the fold completes. The raw pointer is valid for the input
 //!   lifetime because the fold visits each node exactly once and
 //!   we break immediately after capturing the pointer.
+//! - **TR-2 (matching-subtree):** `flatten_matching` keeps a node iff
+//!   it matches or has a kept descendant, regardless of expansion
+//!   state. Rows keep their tree order and depth, so every match is
+//!   shown under its full ancestor chain.
 
 use std::collections::HashSet;
 
@@ -50,6 +54,32 @@ pub(crate) fn flatten_visible<'a>(node: &'a TreeNode, depth: usize) -> Vec<FlatR
     })
 }
 
+/// Flatten only the nodes that match `keep` and their ancestors
+/// (TR-2).
+///
+/// Ancestors of a match are shown as if expanded, so a match deep in
+/// a collapsed subtree is still reachable; everything else is
+/// hidden.
+pub(crate) fn flatten_matching<'a, P>(root: &'a TreeNode, keep: &P) -> Vec<FlatRow<'a>>
+where
+    P: Fn(&TreeNode) -> bool,
+{
+    root.children
+        .iter()
+        .flat_map(|child| {
+            fold_tree_with_depth(child, 0, &|n, d, child_results: Vec<Vec<FlatRow<'a>>>| {
+                let below: Vec<FlatRow<'a>> = child_results.into_iter().flatten().collect();
+                if below.is_empty() && !keep(n) {
+                    return Vec::new();
+                }
+                let mut rows = vec![FlatRow { node: n, depth: d }];
+                rows.extend(below);
+                rows
+            })
+        })
+        .collect()
+}
+
 /// Generic tree fold - unified traversal abstraction.
 ///
 /// Applies `f` to each node in pre-order DFS, accumulating a result.
@@ -274,6 +304,41 @@ pub(crate) fn collect_failed_refs(
     out.extend(refs);
 }
 
+/// Collect (reference, depth) of collapsed hosts and procs that
+/// have children, in pre-order. These are the nodes a search must
+/// expand to see every actor.
+pub(crate) fn collect_search_frontier(root: &TreeNode) -> Vec<(NodeRef, usize)> {
+    root.children
+        .iter()
+        .flat_map(|child| {
+            fold_tree_with_depth(child, 0, &|n, d, child_results| {
+                let mut out: Vec<(NodeRef, usize)> = Vec::new();
+                if !n.expanded
+                    && n.has_children
+                    && matches!(n.reference, NodeRef::Host(_) | NodeRef::Proc(_))
+                {
+                    out.push((n.reference.clone(), d));
+                }
+                out.extend(child_results.into_iter().flatten());
+                out
+            })
+        })
+        .collect()
+}
+
+/// Collect references of placeholder nodes whose payload has not
+/// been fetched.
+pub(crate) fn collect_unfetched_refs(root: &TreeNode) -> Vec<NodeRef> {
+    fold_tree(root, &|n, child_results| {
+        let mut out: Vec<NodeRef> = Vec::new();
+        if !n.fetched {
+            out.push(n.reference.clone());
+        }
+        out.extend(child_results.into_iter().flatten());
+        out
+    })
+}
+
 /// Collapse all nodes using fold-based traversal.
 pub(crate) fn collapse_all(node: &mut TreeNode) {
     use std::ops::ControlFlow;
@@ -2113,4 +2178,88 @@ mod tests {
         assert!(failed_keys.contains(&(host("host1"), 0)));
         assert!(failed_keys.contains(&(proc_ref("proc1"), 1)));
     }
+
+    fn leaf(reference: NodeRef, node_type: NodeType) -> TreeNode {
+        TreeNode {
+            label: reference.to_string(),
+            reference,
+            node_type,
+            expanded: false,
+            fetched: true,
+            has_children: false,
+            stopped: false,
+            failed: false,
+            is_system: false,
+            children: vec![],
+        }
+    }
+
+    fn search_tree() -> TreeNode {
+        let mut proc1 = leaf(proc_ref("proc1"), NodeType::Proc);
+        proc1.has_children = true;
+        proc1.children = vec![
+            leaf(actor("alpha"), NodeType::Actor),
+            leaf(actor("beta"), NodeType::Actor),
+        ];
+        let mut proc2 = leaf(proc_ref("proc2"), NodeType::Proc);
+        proc2.has_children = true;
+        proc2.children = vec![leaf(actor("gamma"), NodeType::Actor)];
+        let mut host1 = leaf(host("host1"), NodeType::Host);
+        host1.has_children = true;
+        host1.children = vec![proc1, proc2];
+        let mut root_node = leaf(root(), NodeType::Root);
+        root_node.expanded = true;
+        root_node.children = vec![host1];
+        root_node
+    }
+
+    // TR-2: a match in a collapsed subtree is shown with its ancestors.
+    #[test]
+    fn flatten_matching_shows_ancestor_chain() {
+        let tree = search_tree();
+        let rows = flatten_matching(&tree, &|n| n.reference == actor("beta"));
+        let refs: Vec<_> = rows
+            .iter()
+            .map(|r| (r.node.reference.clone(), r.depth))
+            .collect();
+        assert_eq!(
+            refs,
+            vec![
+                (host("host1"), 0),
+                (proc_ref("proc1"), 1),
+                (actor("beta"), 2)
+            ]
+        );
+    }
+
+    // TR-2: non-matching siblings and subtrees are hidden.
+    #[test]
+    fn flatten_matching_hides_non_matches() {
+        let tree = search_tree();
+        let rows = flatten_matching(&tree, &|n| n.reference == proc_ref("proc2"));
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].node.reference, proc_ref("proc2"));
+        assert!(flatten_matching(&tree, &|_| false).is_empty());
+    }
+
+    #[test]
+    fn search_frontier_lists_collapsed_hosts_and_procs() {
+        let tree = search_tree();
+        let frontier = collect_search_frontier(&tree);
+        assert_eq!(
+            frontier,
+            vec![
+                (host("host1"), 0),
+                (proc_ref("proc1"), 1),
+                (proc_ref("proc2"), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn unfetched_refs_lists_placeholders() {
+        let mut tree = search_tree();
+        tree.children[0].children[1].children[0].fetched = false;
+        assert_eq!(collect_unfetched_refs(&tree), vec![actor("gamma")]);
+    }
 }
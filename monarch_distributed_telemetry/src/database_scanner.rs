@@ -35,12 +35,15 @@ use serde_multipart::Part;
 use crate::EntityDispatcher;
 use crate::QueryResponse;
 use crate::RecordBatchSink;
+use crate::StandingQueryDelta;
 use crate::pyspy_table::PySpyDumpBuffer;
 use crate::pyspy_table::PySpyFrameBuffer;
 use crate::pyspy_table::PySpyLocalVariableBuffer;
 use crate::pyspy_table::PySpyStackTraceBuffer;
 use crate::serialize_batch;
 use crate::serialize_schema;
+use crate::standing_query::StandingQuery;
+use crate::standing_query::StandingQueryRegistry;
 use crate::timestamp_to_micros;
 
 /// Wraps a table's data so we can dynamically push new batches.
@@ -133,9 +136,13 @@ impl LiveTableData {
 /// - **TS-4 (ownership preserved):** Storage ownership remains in
 ///   `monarch_distributed_telemetry`. `TableStore` is a handle, not
 ///   an independent store.
+/// - **TS-5 (standing queries observe ingest):** Batches ingested
+///   through the handle are dispatched to the scanner's standing
+///   queries, exactly as batches from the telemetry sinks are.
 #[derive(Clone)]
 pub struct TableStore {
     inner: Arc<StdMutex<HashMap<String, Arc<LiveTableData>>>>,
+    standing_queries: StandingQueryRegistry,
 }
 
 impl TableStore {
@@ -146,6 +153,7 @@ impl TableStore {
     pub fn new_empty() -> Self {
         Self {
             inner: Arc::new(StdMutex::new(HashMap::new())),
+            standing_queries: StandingQueryRegistry::default(),
         }
     }
 
@@ -168,7 +176,12 @@ impl TableStore {
                 .or_insert_with(|| Arc::new(LiveTableData::new(batch.schema())))
                 .clone()
         };
-        table.push(batch).await;
+        if self.standing_queries.has_queries_for(table_name) {
+            table.push(batch.clone()).await;
+            self.standing_queries.dispatch(table_name, batch);
+        } else {
+            table.push(batch).await;
+        }
         Ok(())
     }
 
@@ -223,6 +236,8 @@ pub struct DatabaseScanner {
     sink: Option<RecordBatchSink>,
     /// Handle to flush the EntityDispatcher for entity events (actors, meshes)
     dispatcher: Option<EntityDispatcher>,
+    /// Standing queries evaluated against every ingested batch
+    standing_queries: StandingQueryRegistry,
 }
 
 #[pymethods]
@@ -236,6 +251,7 @@ impl DatabaseScanner {
             retention_us: retention_secs as i64 * 1_000_000,
             sink: None,
             dispatcher: None,
+            standing_queries: StandingQueryRegistry::default(),
         };
 
         // Create and register a RecordBatchSink for trace events (spans, events)
@@ -271,7 +287,8 @@ impl DatabaseScanner {
                     .unwrap(),
             ),
         ] {
-            Self::push_batch_to_tables(&scanner.table_data, name, batch).unwrap();
+            Self::push_batch_to_tables(&scanner.table_data, &scanner.standing_queries, name, batch)
+                .unwrap();
         }
 
         Ok(scanner)
@@ -399,6 +416,90 @@ impl DatabaseScanner {
             &dest_ref,
         )
     }
+
+    /// Register a standing query whose deltas are sent to the dest port.
+    ///
+    /// The query is evaluated against every batch subsequently ingested
+    /// into `table_name`; rows matching `where_clause` (projected onto
+    /// `columns`) are sent as `StandingQueryDelta` messages. Registering
+    /// an existing `query_id` replaces it. If the table exists, the
+    /// query is planned against its schema here and registration fails
+    /// on unknown columns, invalid SQL, or aggregates.
+    ///
+    /// Args:
+    ///     dest: The destination PortId to send deltas to
+    ///     query_id: Identifier for the query, unique within this scanner
+    ///     table_name: Name of the table to watch
+    ///     where_clause: Optional SQL WHERE clause selecting rows
+    ///     columns: Optional list of column names to deliver
+    #[pyo3(signature = (dest, query_id, table_name, where_clause=None, columns=None))]
+    fn register_standing_query(
+        &self,
+        py: Python<'_>,
+        dest: &PyPortId,
+        query_id: String,
+        table_name: String,
+        where_clause: Option<String>,
+        columns: Option<Vec<String>>,
+    ) -> PyResult<()> {
+        let actor_module = py.import("monarch.actor")?;
+        let ctx = actor_module.call_method0("context")?;
+        let actor_instance_obj = ctx.getattr("actor_instance")?;
+        let py_instance: PyRef<'_, PyInstance> = actor_instance_obj.extract()?;
+        let instance: Instance<PythonActor> = py_instance.clone_for_py();
+
+        let dest_port_id: reference::PortId = dest.clone().into();
+        let dest_ref: reference::PortRef<StandingQueryDelta> =
+            reference::PortRef::attest(dest_port_id);
+
+        let query = StandingQuery {
+            query_id,
+            table_name,
+            where_clause,
+            columns,
+        };
+        let schema = self
+            .table_schema(&query.table_name)
+            .map_err(|e| PyException::new_err(e.to_string()))?;
+
+        // Registration starts the evaluation task on this runtime.
+        let runtime = get_tokio_runtime().handle().clone();
+        let _guard = runtime.enter();
+        let rank = self.rank;
+        self.standing_queries
+            .register(
+                query,
+                schema,
+                Box::new(move |query, batch| {
+                    let msg = StandingQueryDelta {
+                        query_id: query.query_id.clone(),
+                        rank,
+                        data: Part::from(serialize_batch(&batch)?),
+                    };
+                    dest_ref.send(&instance, msg)?;
+                    Ok(())
+                }),
+            )
+            .map_err(|e| PyException::new_err(e.to_string()))
+    }
+
+    /// Unregister a standing query. Returns whether it was registered.
+    fn unregister_standing_query(&self, query_id: &str) -> PyResult<bool> {
+        self.standing_queries
+            .unregister(query_id)
+            .map_err(|e| PyException::new_err(e.to_string()))
+    }
+
+    /// Get the ids of the registered standing queries.
+    fn standing_query_ids(&self) -> PyResult<Vec<String>> {
+        Ok(self
+            .standing_queries
+            .queries()
+            .map_err(|e| PyException::new_err(e.to_string()))?
+            .into_iter()
+            .map(|q| q.query_id)
+            .collect())
+    }
 }
 
 impl DatabaseScanner {
@@ -416,8 +517,13 @@ impl DatabaseScanner {
     ///   an existing table appends rows.
     /// - **ID-4 (error surface):** Lock poisoning propagates as
     ///   `Err`. `push()` itself is infallible.
+    /// - **ID-5 (standing queries):** Non-empty batches are queued for
+    ///   the standing queries on `table_name` after being appended
+    ///   (SQ-2). Queueing never blocks, and standing-query failures
+    ///   never fail ingestion (SQ-4, SQ-5).
     fn push_batch_to_tables(
         table_data: &Arc<StdMutex<HashMap<String, Arc<LiveTableData>>>>,
+        standing_queries: &StandingQueryRegistry,
         table_name: &str,
         batch: RecordBatch,
     ) -> anyhow::Result<()> {
@@ -435,10 +541,18 @@ impl DatabaseScanner {
         // Use block_in_place + Handle::current() when called from within a tokio
         // runtime (e.g., from notify_sent_message on a worker thread), otherwise
         // fall back to creating/reusing a runtime via get_tokio_runtime().
+        let ingest = async {
+            if standing_queries.has_queries_for(table_name) {
+                table.push(batch.clone()).await;
+                standing_queries.dispatch(table_name, batch);
+            } else {
+                table.push(batch).await;
+            }
+        };
         if let Ok(handle) = tokio::runtime::Handle::try_current() {
-            tokio::task::block_in_place(|| handle.block_on(table.push(batch)));
+            tokio::task::block_in_place(|| handle.block_on(ingest));
         } else {
-            get_tokio_runtime().block_on(table.push(batch));
+            get_tokio_runtime().block_on(ingest);
         }
         Ok(())
     }
@@ -449,11 +563,14 @@ impl DatabaseScanner {
     /// to receive trace events and store them as queryable tables.
     pub fn create_record_batch_sink(&self, batch_size: usize) -> RecordBatchSink {
         let table_data = self.table_data.clone();
+        let standing_queries = self.standing_queries.clone();
 
         RecordBatchSink::new(
             batch_size,
             Box::new(move |table_name, batch| {
-                if let Err(e) = Self::push_batch_to_tables(&table_data, table_name, batch) {
+                if let Err(e) =
+                    Self::push_batch_to_tables(&table_data, &standing_queries, table_name, batch)
+                {
                     tracing::error!("Failed to push batch to table {}: {}", table_name, e);
                 }
             }),
@@ -466,11 +583,14 @@ impl DatabaseScanner {
     /// to receive entity events (actors, meshes) and store them as queryable tables.
     pub fn create_entity_dispatcher(&self, batch_size: usize) -> EntityDispatcher {
         let table_data = self.table_data.clone();
+        let standing_queries = self.standing_queries.clone();
 
         EntityDispatcher::new(
             batch_size,
             Box::new(move |table_name, batch| {
-                if let Err(e) = Self::push_batch_to_tables(&table_data, table_name, batch) {
+                if let Err(e) =
+                    Self::push_batch_to_tables(&table_data, &standing_queries, table_name, batch)
+                {
                     tracing::error!("Failed to push batch to table {}: {}", table_name, e);
                 }
             }),
@@ -540,6 +660,7 @@ impl DatabaseScanner {
         });
         Self::push_batch_to_tables(
             &self.table_data,
+            &self.standing_queries,
             "pyspy_dumps",
             dump_buf.drain_to_record_batch()?,
         )?;
@@ -636,16 +757,19 @@ impl DatabaseScanner {
 
         Self::push_batch_to_tables(
             &self.table_data,
+            &self.standing_queries,
             "pyspy_stack_traces",
             trace_buf.drain_to_record_batch()?,
         )?;
         Self::push_batch_to_tables(
             &self.table_data,
+            &self.standing_queries,
             "pyspy_frames",
             frame_buf.drain_to_record_batch()?,
         )?;
         Self::push_batch_to_tables(
             &self.table_data,
+            &self.standing_queries,
             "pyspy_local_variables",
             local_buf.drain_to_record_batch()?,
         )?;
@@ -676,9 +800,25 @@ impl DatabaseScanner {
     pub fn table_store(&self) -> TableStore {
         TableStore {
             inner: self.table_data.clone(),
+            standing_queries: self.standing_queries.clone(),
         }
     }
 
+    /// Return the registry of standing queries evaluated on ingest.
+    pub fn standing_queries(&self) -> &StandingQueryRegistry {
+        &self.standing_queries
+    }
+
+    /// The schema of `table_name`, or `None` if the table does not
+    /// exist yet.
+    fn table_schema(&self, table_name: &str) -> anyhow::Result<Option<SchemaRef>> {
+        let guard = self
+            .table_data
+            .lock()
+            .map_err(|_| anyhow::anyhow!("lock poisoned"))?;
+        Ok(guard.get(table_name).map(|t| t.schema()))
+    }
+
     fn execute_scan_streaming(
         &self,
         table_name: &str,
@@ -909,6 +1049,7 @@ mod tests {
             retention_us: 0,
             sink: None,
             dispatcher: None,
+            standing_queries: StandingQueryRegistry::default(),
         };
 
         let json = r#"{
@@ -1144,6 +1285,7 @@ mod tests {
             retention_us: 0,
             sink: None,
             dispatcher: None,
+            standing_queries: StandingQueryRegistry::default(),
         };
 
         let json =
@@ -1163,6 +1305,7 @@ mod tests {
             retention_us: 0,
             sink: None,
             dispatcher: None,
+            standing_queries: StandingQueryRegistry::default(),
         };
         assert!(scanner.store_pyspy_dump("x", "p", "not json").is_err());
     }
@@ -1175,6 +1318,7 @@ mod tests {
             retention_us: 0,
             sink: None,
             dispatcher: None,
+            standing_queries: StandingQueryRegistry::default(),
         };
 
         let json = r#"{
@@ -1423,6 +1567,44 @@ mod tests {
         );
     }
 
+    // TS-5: batches ingested through the handle reach the scanner's
+    // standing queries.
+    #[tokio::test]
+    async fn test_table_store_ingest_dispatches_standing_queries() {
+        let store = TableStore::new_empty();
+        let seen = Arc::new(StdMutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        store
+            .standing_queries
+            .register(
+                StandingQuery {
+                    query_id: "big".into(),
+                    table_name: "t".into(),
+                    where_clause: Some("x > 10".into()),
+                    columns: None,
+                },
+                None,
+                Box::new(move |_, batch| {
+                    seen_clone.lock().unwrap().push(batch.num_rows());
+                    Ok(())
+                }),
+            )
+            .unwrap();
+
+        store
+            .ingest_batch("t", make_batch(&[5, 20, 30]))
+            .await
+            .unwrap();
+        store.standing_queries.flush().await;
+
+        assert_eq!(*seen.lock().unwrap(), vec![2], "TS-5: matching rows only");
+        assert_eq!(
+            query_row_count("t", store.table_provider("t").unwrap().unwrap()).await,
+            3,
+            "TS-5: all rows are still stored"
+        );
+    }
+
     // TS-3: table_provider for unknown table returns None.
     #[test]
     fn test_table_store_missing_table() {
@@ -12,6 +12,10 @@
 //! 2. DistributedTelemetryActor (Python): Orchestrates children, wraps DatabaseScanner
 //! 3. QueryEngine (Rust): DataFusion query execution, creates ports, collects results
 //!
+//! Standing queries (see [`standing_query`]) are registered with each
+//! DatabaseScanner and evaluated incrementally as batches are ingested,
+//! streaming deltas back over a port.
+//!
 //! Data flows directly Rust-to-Rust via PortRef for efficiency.
 
 pub mod database_scanner;
@@ -19,6 +23,7 @@ mod entity_dispatcher;
 pub mod pyspy_table;
 pub mod query_engine;
 mod record_batch_sink;
+pub mod standing_query;
 
 pub use database_scanner::DatabaseScanner;
 use datafusion::arrow::datatypes::SchemaRef;
@@ -40,6 +45,9 @@ pub use record_batch_sink::reset_flush_count;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_multipart::Part;
+pub use standing_query::PlannedQuery;
+pub use standing_query::StandingQuery;
+pub use standing_query::StandingQueryRegistry;
 use typeuri::Named;
 
 /// Response message for streaming query results.
@@ -52,6 +60,20 @@ pub struct QueryResponse {
     pub data: Part,
 }
 
+/// Delta message for a standing query.
+/// Carries the rows of a newly ingested batch that matched the query,
+/// sent by each scanner as data arrives. The stream has no completion
+/// message; it ends when the query is unregistered.
+#[derive(Debug, Clone, Serialize, Deserialize, Named, Bind, Unbind)]
+pub struct StandingQueryDelta {
+    /// Id of the standing query that produced this delta.
+    pub query_id: String,
+    /// Rank of the scanner that ingested the rows.
+    pub rank: usize,
+    /// Matching rows in Arrow IPC format.
+    pub data: Part,
+}
+
 // ============================================================================
 // Serialization helpers
 // ============================================================================
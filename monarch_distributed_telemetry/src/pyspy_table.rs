@@ -24,7 +24,9 @@ pub struct PySpyDump {
     pub dump_id: String,
     /// Ingestion timestamp, not the py-spy capture time. We record when the
     /// result was stored rather than when the snapshot was taken because the
-    /// py-spy JSON does not carry a capture timestamp.
+    /// py-spy JSON does not carry a capture timestamp. Microseconds since
+    /// the epoch, like the `timestamp_us` columns of the other telemetry
+    /// tables, rather than an Arrow timestamp.
     pub timestamp_us: i64,
     pub pid: i32,
     pub binary: String,
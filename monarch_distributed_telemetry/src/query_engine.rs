@@ -45,9 +45,11 @@ use pyo3::exceptions::PyException;
 use pyo3::prelude::*;
 use pyo3::types::PyBytes;
 use pyo3::types::PyModule;
+use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 
 use crate::QueryResponse;
+use crate::StandingQueryDelta;
 
 // ============================================================================
 // Deserialization helpers
@@ -492,7 +494,128 @@ impl QueryEngine {
     }
 }
 
+/// Client side of a standing query registered across the telemetry
+/// actor tree.
+///
+/// Opens a port, registers the query on every DatabaseScanner via the
+/// actor's `register_standing_query` endpoint, and yields the deltas
+/// sent back as scanners ingest matching rows.
+#[pyclass(
+    name = "StandingQuerySubscription",
+    module = "monarch._rust_bindings.monarch_distributed_telemetry.query_engine"
+)]
+pub struct StandingQuerySubscription {
+    query_id: String,
+    actor: Py<PyAny>,
+    receiver: Arc<Mutex<PortReceiver<StandingQueryDelta>>>,
+}
+
+#[pymethods]
+impl StandingQuerySubscription {
+    /// Register a standing query.
+    ///
+    /// Args:
+    ///     actor: A singleton DistributedTelemetryActor (ActorMesh) to register with
+    ///     query_id: Identifier for the query
+    ///     table_name: Name of the table to watch
+    ///     where_clause: Optional SQL WHERE clause selecting rows
+    ///     columns: Optional list of column names to deliver
+    #[new]
+    #[pyo3(signature = (actor, query_id, table_name, where_clause=None, columns=None))]
+    fn new(
+        py: Python<'_>,
+        actor: Py<PyAny>,
+        query_id: String,
+        table_name: String,
+        where_clause: Option<String>,
+        columns: Option<Vec<String>>,
+    ) -> PyResult<Self> {
+        let actor_module = py.import("monarch.actor")?;
+        let ctx = actor_module.call_method0("context")?;
+        let actor_instance_obj = ctx.getattr("actor_instance")?;
+        let py_instance: PyRef<'_, PyInstance> = actor_instance_obj.extract()?;
+        let instance: Instance<PythonActor> = py_instance.clone_for_py();
+
+        let (handle, receiver) = instance.mailbox().open_port::<StandingQueryDelta>();
+        let dest_port_id: PyPortId = handle.bind().port_id().clone().into();
+
+        actor
+            .getattr(py, "register_standing_query")?
+            .call_method1(
+                py,
+                "call",
+                (
+                    dest_port_id,
+                    query_id.clone(),
+                    table_name,
+                    where_clause,
+                    columns,
+                ),
+            )?
+            .call_method0(py, "get")?;
+
+        Ok(Self {
+            query_id,
+            actor,
+            receiver: Arc::new(Mutex::new(receiver)),
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<StandingQuerySubscription {}>", self.query_id)
+    }
+
+    /// The id of the standing query.
+    #[getter]
+    fn query_id(&self) -> &str {
+        &self.query_id
+    }
+
+    /// Wait for the next delta and return `(rank, data)` where `data` is
+    /// an Arrow IPC stream. Returns None if `timeout` (in seconds)
+    /// elapses first.
+    #[pyo3(signature = (timeout=None))]
+    fn next<'py>(
+        &self,
+        py: Python<'py>,
+        timeout: Option<f64>,
+    ) -> PyResult<Option<(usize, Bound<'py, PyBytes>)>> {
+        let receiver = self.receiver.clone();
+        let delta = py
+            .detach(|| {
+                get_tokio_runtime().block_on(async move {
+                    let mut receiver = receiver.lock().await;
+                    match timeout {
+                        Some(secs) => tokio::time::timeout(
+                            std::time::Duration::from_secs_f64(secs),
+                            receiver.recv(),
+                        )
+                        .await
+                        .ok()
+                        .transpose(),
+                        None => receiver.recv().await.map(Some),
+                    }
+                })
+            })
+            .map_err(|e| PyException::new_err(e.to_string()))?;
+        Ok(delta.map(|StandingQueryDelta { rank, data, .. }| {
+            (rank, PyBytes::new(py, &data.into_bytes()))
+        }))
+    }
+
+    /// Unregister the query on every scanner. Deltas already in flight
+    /// may still be returned by `next`.
+    fn cancel(&self, py: Python<'_>) -> PyResult<()> {
+        self.actor
+            .getattr(py, "unregister_standing_query")?
+            .call_method1(py, "call", (self.query_id.clone(),))?
+            .call_method0(py, "get")?;
+        Ok(())
+    }
+}
+
 pub fn register_python_bindings(module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_class::<QueryEngine>()?;
+    module.add_class::<StandingQuerySubscription>()?;
     Ok(())
 }
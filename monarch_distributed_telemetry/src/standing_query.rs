@@ -0,0 +1,590 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Standing queries - continuous, incrementally evaluated filters over
+//! the telemetry tables.
+//!
+//! A standing query is registered once with a [`DatabaseScanner`] and
+//! is then evaluated against every `RecordBatch` ingested into its
+//! table. Only the rows of the newly ingested batch are considered, so
+//! the cost of evaluation is proportional to the ingest rate rather
+//! than to the size of the table. Matching rows are delivered as
+//! deltas over a port; no full scan is ever performed.
+//!
+//! Ingestion runs on telemetry hot paths, under the sink and
+//! dispatcher locks, so it only enqueues the batch. Evaluation happens
+//! on a dedicated task that drains a bounded queue, and each query's
+//! predicate is planned once, not per batch.
+//!
+//! A standing query is a row predicate (`WHERE` clause) plus an
+//! optional column projection. Aggregates (e.g. "p99 latency exceeds
+//! X") are out of scope: they need state across batches and a window
+//! definition, and registering a clause that uses one fails. Compute
+//! them on the subscriber side over the delta stream, or poll with a
+//! one-shot query.
+//!
+//! [`DatabaseScanner`]: crate::DatabaseScanner
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::Mutex as StdMutex;
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use datafusion::arrow::compute::filter_record_batch;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::common::DFSchema;
+use datafusion::common::cast::as_boolean_array;
+use datafusion::logical_expr::utils::find_aggregate_exprs;
+use datafusion::physical_expr::PhysicalExpr;
+use datafusion::prelude::SessionContext;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+
+/// Number of ingested batches that may wait for evaluation. Batches
+/// ingested while the queue is full are not evaluated (SQ-5).
+const EVALUATION_QUEUE_CAPACITY: usize = 1024;
+
+/// Callback invoked with the rows of a newly ingested batch that match
+/// a standing query. An error means the subscriber is gone; the query
+/// is unregistered.
+pub type DeltaCallback =
+    Box<dyn Fn(&StandingQuery, RecordBatch) -> anyhow::Result<()> + Send + Sync>;
+
+/// Definition of a standing query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StandingQuery {
+    /// Caller-chosen identifier, unique within a scanner.
+    pub query_id: String,
+    /// Table the query is evaluated against.
+    pub table_name: String,
+    /// SQL `WHERE` clause selecting the rows to deliver. `None`
+    /// delivers every ingested row. Must not use aggregates.
+    pub where_clause: Option<String>,
+    /// Names of the columns to deliver. `None` delivers all columns.
+    pub columns: Option<Vec<String>>,
+}
+
+impl StandingQuery {
+    /// Plan this query against its table's schema: resolve the
+    /// projected columns and turn the `WHERE` clause into a physical
+    /// expression. Fails on unknown columns, invalid SQL, and
+    /// aggregates (SQ-6).
+    pub fn plan(&self, schema: SchemaRef) -> anyhow::Result<PlannedQuery> {
+        let projection = match &self.columns {
+            Some(columns) if !columns.is_empty() => Some(
+                columns
+                    .iter()
+                    .map(|c| schema.index_of(c))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            _ => None,
+        };
+        let predicate = match &self.where_clause {
+            Some(clause) => {
+                let df_schema = DFSchema::try_from(schema.as_ref().clone())?;
+                let ctx = SessionContext::new();
+                let expr = ctx.parse_sql_expr(clause, &df_schema)?;
+                anyhow::ensure!(
+                    find_aggregate_exprs([&expr]).is_empty(),
+                    "standing query {}: aggregates are not supported in a WHERE clause; \
+                     aggregate over the delta stream instead",
+                    self.query_id
+                );
+                Some(ctx.create_physical_expr(expr, &df_schema)?)
+            }
+            None => None,
+        };
+        Ok(PlannedQuery {
+            schema,
+            predicate,
+            projection,
+        })
+    }
+
+    /// Evaluate this query against a single batch, returning the
+    /// matching rows, if any. Plans the query on every call; the
+    /// registry plans once and reuses the plan.
+    pub fn evaluate(&self, batch: &RecordBatch) -> anyhow::Result<Option<RecordBatch>> {
+        self.plan(batch.schema())?.evaluate(batch)
+    }
+}
+
+/// A [`StandingQuery`] planned against a table schema.
+pub struct PlannedQuery {
+    schema: SchemaRef,
+    predicate: Option<Arc<dyn PhysicalExpr>>,
+    projection: Option<Vec<usize>>,
+}
+
+impl PlannedQuery {
+    /// The matching rows of `batch`, projected, or `None` if no row
+    /// matches (SQ-3).
+    pub fn evaluate(&self, batch: &RecordBatch) -> anyhow::Result<Option<RecordBatch>> {
+        let filtered = match &self.predicate {
+            Some(predicate) => {
+                let mask = predicate.evaluate(batch)?.into_array(batch.num_rows())?;
+                filter_record_batch(batch, as_boolean_array(&mask)?)?
+            }
+            None => batch.clone(),
+        };
+        if filtered.num_rows() == 0 {
+            return Ok(None);
+        }
+        Ok(Some(match &self.projection {
+            Some(projection) => filtered.project(projection)?,
+            None => filtered,
+        }))
+    }
+}
+
+struct RegisteredQuery {
+    query: StandingQuery,
+    callback: DeltaCallback,
+    /// Plan for the table's schema, made at registration when the
+    /// table exists, otherwise on the first ingested batch.
+    plan: StdMutex<Option<Arc<PlannedQuery>>>,
+}
+
+impl RegisteredQuery {
+    /// The plan for `schema`, planning (again) if there is none yet or
+    /// the table's schema changed.
+    fn plan_for(&self, schema: SchemaRef) -> anyhow::Result<Arc<PlannedQuery>> {
+        let mut plan = self
+            .plan
+            .lock()
+            .map_err(|_| anyhow::anyhow!("lock poisoned"))?;
+        if let Some(plan) = plan.as_ref()
+            && plan.schema == schema
+        {
+            return Ok(plan.clone());
+        }
+        let planned = Arc::new(self.query.plan(schema)?);
+        *plan = Some(planned.clone());
+        Ok(planned)
+    }
+}
+
+/// Work for the evaluation task.
+enum Evaluation {
+    /// A batch that was just appended to `table_name`.
+    Batch {
+        table_name: String,
+        batch: RecordBatch,
+    },
+    /// Acknowledge once everything queued before has been evaluated.
+    Flush(oneshot::Sender<()>),
+}
+
+type Queries = Arc<StdMutex<BTreeMap<String, Arc<RegisteredQuery>>>>;
+
+/// Shared registry of the standing queries installed on a scanner.
+///
+/// # Standing-query invariants (SQ-*)
+///
+/// - **SQ-1 (incremental):** A query is only ever evaluated against
+///   the batch being ingested, never against previously stored rows.
+/// - **SQ-2 (after store):** Batches are queued for evaluation after
+///   they have been appended to their table, so a subscriber that
+///   reacts to a delta with a one-shot query observes the delta's
+///   rows.
+/// - **SQ-3 (no empty deltas):** Batches with no matching rows are not
+///   delivered.
+/// - **SQ-4 (self-cleaning):** A query whose callback fails, or that
+///   fails to plan or evaluate, is unregistered. Ingestion itself
+///   never fails because of a standing query.
+/// - **SQ-5 (off the ingest path):** Ingestion only enqueues the
+///   batch on a bounded queue; one task per registry evaluates
+///   queued batches in ingest order. When the queue is full the
+///   batch is skipped for standing queries and counted in
+///   [`dropped_batches`](Self::dropped_batches); ingestion never
+///   waits on evaluation.
+/// - **SQ-6 (planned once):** A query is planned when it is
+///   registered (or on the first batch if its table does not exist
+///   yet) and the plan is reused for every batch. Only row
+///   predicates plan; aggregates are rejected.
+#[derive(Clone, Default)]
+pub struct StandingQueryRegistry {
+    inner: Queries,
+    /// Sender side of the evaluation queue, created with the task on
+    /// the first registration.
+    queue: Arc<OnceLock<mpsc::Sender<Evaluation>>>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl StandingQueryRegistry {
+    /// Install a standing query, replacing any existing query with the
+    /// same id. `schema` is the schema of the query's table if it
+    /// exists; the query is then planned now and registration fails
+    /// if it does not plan (SQ-6).
+    ///
+    /// Starts the evaluation task on first use, so it must be called
+    /// within a tokio runtime.
+    pub fn register(
+        &self,
+        query: StandingQuery,
+        schema: Option<SchemaRef>,
+        callback: DeltaCallback,
+    ) -> anyhow::Result<()> {
+        let plan = schema
+            .map(|schema| query.plan(schema).map(Arc::new))
+            .transpose()?;
+        self.start_evaluator()?;
+        let mut guard = self
+            .inner
+            .lock()
+            .map_err(|_| anyhow::anyhow!("lock poisoned"))?;
+        guard.insert(
+            query.query_id.clone(),
+            Arc::new(RegisteredQuery {
+                query,
+                callback,
+                plan: StdMutex::new(plan),
+            }),
+        );
+        Ok(())
+    }
+
+    /// Remove a standing query. Returns whether it was registered.
+    pub fn unregister(&self, query_id: &str) -> anyhow::Result<bool> {
+        let mut guard = self
+            .inner
+            .lock()
+            .map_err(|_| anyhow::anyhow!("lock poisoned"))?;
+        Ok(guard.remove(query_id).is_some())
+    }
+
+    /// The currently registered queries, ordered by id.
+    pub fn queries(&self) -> anyhow::Result<Vec<StandingQuery>> {
+        let guard = self
+            .inner
+            .lock()
+            .map_err(|_| anyhow::anyhow!("lock poisoned"))?;
+        Ok(guard.values().map(|r| r.query.clone()).collect())
+    }
+
+    /// Number of ingested batches skipped because the evaluation
+    /// queue was full (SQ-5).
+    pub fn dropped_batches(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Wait until every batch queued so far has been evaluated and
+    /// its deltas delivered.
+    pub async fn flush(&self) {
+        let Some(queue) = self.queue.get() else {
+            return;
+        };
+        let (tx, rx) = oneshot::channel();
+        if queue.send(Evaluation::Flush(tx)).await.is_ok() {
+            let _ = rx.await;
+        }
+    }
+
+    /// Whether any query targets `table_name`. Used as a fast path so
+    /// ingestion pays nothing when no query is interested.
+    pub(crate) fn has_queries_for(&self, table_name: &str) -> bool {
+        self.inner
+            .lock()
+            .map(|guard| guard.values().any(|r| r.query.table_name == table_name))
+            .unwrap_or(false)
+    }
+
+    /// Queue a newly ingested batch for evaluation against the queries
+    /// on `table_name` (SQ-5). Never blocks.
+    pub(crate) fn dispatch(&self, table_name: &str, batch: RecordBatch) {
+        if batch.num_rows() == 0 {
+            return;
+        }
+        let Some(queue) = self.queue.get() else {
+            return;
+        };
+        let work = Evaluation::Batch {
+            table_name: table_name.to_string(),
+            batch,
+        };
+        if let Err(mpsc::error::TrySendError::Full(_)) = queue.try_send(work) {
+            let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+            tracing::warn!(
+                "standing query evaluation queue full, skipping a batch for {} \
+                 ({} skipped so far)",
+                table_name,
+                dropped
+            );
+        }
+    }
+
+    /// Spawn the evaluation task unless it is already running.
+    fn start_evaluator(&self) -> anyhow::Result<()> {
+        if self.queue.get().is_some() {
+            return Ok(());
+        }
+        let handle = tokio::runtime::Handle::try_current()
+            .map_err(|_| anyhow::anyhow!("standing queries require a tokio runtime"))?;
+        self.queue.get_or_init(|| {
+            let (tx, rx) = mpsc::channel(EVALUATION_QUEUE_CAPACITY);
+            handle.spawn(Self::evaluate_queued(self.inner.clone(), rx));
+            tx
+        });
+        Ok(())
+    }
+
+    /// Body of the evaluation task. Ends when the registry is dropped.
+    async fn evaluate_queued(queries: Queries, mut rx: mpsc::Receiver<Evaluation>) {
+        while let Some(work) = rx.recv().await {
+            match work {
+                Evaluation::Batch { table_name, batch } => {
+                    Self::evaluate_batch(&queries, &table_name, &batch)
+                }
+                Evaluation::Flush(done) => {
+                    let _ = done.send(());
+                }
+            }
+        }
+    }
+
+    /// Evaluate every query on `table_name` against a newly ingested
+    /// batch and deliver the matching rows (SQ-1, SQ-3, SQ-4).
+    fn evaluate_batch(queries: &Queries, table_name: &str, batch: &RecordBatch) {
+        let interested: Vec<Arc<RegisteredQuery>> = match queries.lock() {
+            Ok(guard) => guard
+                .values()
+                .filter(|r| r.query.table_name == table_name)
+                .cloned()
+                .collect(),
+            Err(_) => return,
+        };
+
+        for registered in interested {
+            let query = &registered.query;
+            let result = registered
+                .plan_for(batch.schema())
+                .and_then(|plan| plan.evaluate(batch))
+                .and_then(|delta| match delta {
+                    Some(delta) => (registered.callback)(query, delta),
+                    None => Ok(()),
+                });
+            if let Err(e) = result {
+                tracing::warn!(
+                    "standing query {} on {} failed, unregistering: {}",
+                    query.query_id,
+                    table_name,
+                    e
+                );
+                if let Ok(mut guard) = queries.lock() {
+                    // Only remove the entry we evaluated; it may have
+                    // been replaced concurrently.
+                    if guard
+                        .get(&query.query_id)
+                        .is_some_and(|r| Arc::ptr_eq(r, &registered))
+                    {
+                        guard.remove(&query.query_id);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datafusion::arrow::array::Int64Array;
+    use datafusion::arrow::array::StringArray;
+    use datafusion::arrow::datatypes::DataType;
+    use datafusion::arrow::datatypes::Field;
+    use datafusion::arrow::datatypes::Schema;
+
+    use super::*;
+
+    fn make_batch(values: &[(i64, &str)]) -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("x", DataType::Int64, false),
+            Field::new("level", DataType::Utf8, false),
+        ]));
+        let xs = Int64Array::from(values.iter().map(|(x, _)| *x).collect::<Vec<_>>());
+        let levels = StringArray::from(values.iter().map(|(_, l)| *l).collect::<Vec<_>>());
+        RecordBatch::try_new(schema, vec![Arc::new(xs), Arc::new(levels)]).unwrap()
+    }
+
+    fn query(id: &str, where_clause: Option<&str>) -> StandingQuery {
+        StandingQuery {
+            query_id: id.into(),
+            table_name: "events".into(),
+            where_clause: where_clause.map(String::from),
+            columns: None,
+        }
+    }
+
+    fn collecting_callback() -> (DeltaCallback, Arc<StdMutex<Vec<(String, usize)>>>) {
+        let seen = Arc::new(StdMutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let callback: DeltaCallback = Box::new(move |q, batch| {
+            seen_clone
+                .lock()
+                .unwrap()
+                .push((q.query_id.clone(), batch.num_rows()));
+            Ok(())
+        });
+        (callback, seen)
+    }
+
+    // SQ-1, SQ-3: only matching rows of the ingested batch are
+    // delivered; batches with no matches produce no delta.
+    #[tokio::test]
+    async fn test_dispatch_delivers_matching_rows() {
+        let registry = StandingQueryRegistry::default();
+        let (callback, seen) = collecting_callback();
+        registry
+            .register(query("errors", Some("level = 'ERROR'")), None, callback)
+            .unwrap();
+
+        registry.dispatch(
+            "events",
+            make_batch(&[(1, "INFO"), (2, "ERROR"), (3, "ERROR")]),
+        );
+        registry.dispatch("events", make_batch(&[(4, "INFO")]));
+        registry.flush().await;
+
+        assert_eq!(*seen.lock().unwrap(), vec![("errors".to_string(), 2)]);
+    }
+
+    // Queries only observe batches for their own table.
+    #[tokio::test]
+    async fn test_dispatch_ignores_other_tables() {
+        let registry = StandingQueryRegistry::default();
+        let (callback, seen) = collecting_callback();
+        registry
+            .register(query("all", None), None, callback)
+            .unwrap();
+
+        assert!(!registry.has_queries_for("spans"));
+        registry.dispatch("spans", make_batch(&[(1, "INFO")]));
+        registry.flush().await;
+
+        assert!(seen.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_projection() {
+        let q = StandingQuery {
+            columns: Some(vec!["level".into()]),
+            ..query("p", Some("x > 1"))
+        };
+        let delta = q
+            .evaluate(&make_batch(&[(1, "INFO"), (2, "WARN")]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(delta.num_columns(), 1);
+        assert_eq!(delta.num_rows(), 1);
+        assert!(q.evaluate(&make_batch(&[(1, "INFO")])).unwrap().is_none());
+    }
+
+    // SQ-6: a query on an existing table is planned at registration,
+    // and clauses that do not plan are rejected there.
+    #[tokio::test]
+    async fn test_register_plans_against_schema() {
+        let registry = StandingQueryRegistry::default();
+        let schema = make_batch(&[]).schema();
+        let (callback, _) = collecting_callback();
+        assert!(
+            registry
+                .register(
+                    query("bad", Some("no_such_column = 1")),
+                    Some(schema.clone()),
+                    callback
+                )
+                .is_err()
+        );
+        let (callback, _) = collecting_callback();
+        let unknown_column = StandingQuery {
+            columns: Some(vec!["nope".into()]),
+            ..query("bad", None)
+        };
+        assert!(
+            registry
+                .register(unknown_column, Some(schema.clone()), callback)
+                .is_err()
+        );
+        let (callback, _) = collecting_callback();
+        registry
+            .register(query("good", Some("x > 1")), Some(schema), callback)
+            .unwrap();
+        assert_eq!(registry.queries().unwrap().len(), 1);
+    }
+
+    // Aggregates are out of scope and rejected with a clear error.
+    #[test]
+    fn test_aggregates_are_rejected() {
+        let error = query("p99", Some("max(x) > 10"))
+            .plan(make_batch(&[]).schema())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("aggregates"), "{}", error);
+    }
+
+    // SQ-4: a failing callback or invalid SQL unregisters the query.
+    #[tokio::test]
+    async fn test_failed_query_is_unregistered() {
+        let registry = StandingQueryRegistry::default();
+        registry
+            .register(
+                query("gone", None),
+                None,
+                Box::new(|_, _| Err(anyhow::anyhow!("port closed"))),
+            )
+            .unwrap();
+        let (callback, _seen) = collecting_callback();
+        registry
+            .register(query("bad_sql", Some("no_such_column = 1")), None, callback)
+            .unwrap();
+
+        registry.dispatch("events", make_batch(&[(1, "INFO")]));
+        registry.flush().await;
+
+        assert!(registry.queries().unwrap().is_empty());
+    }
+
+    // SQ-5: dispatch never waits on evaluation; batches that do not
+    // fit in the queue are skipped and counted. The current-thread
+    // runtime does not run the evaluation task until the test yields.
+    #[tokio::test]
+    async fn test_full_queue_skips_batches() {
+        let registry = StandingQueryRegistry::default();
+        let (callback, seen) = collecting_callback();
+        registry
+            .register(query("all", None), None, callback)
+            .unwrap();
+
+        for x in 0..EVALUATION_QUEUE_CAPACITY + 2 {
+            registry.dispatch("events", make_batch(&[(x as i64, "INFO")]));
+        }
+        assert_eq!(registry.dropped_batches(), 2);
+        registry.flush().await;
+        assert_eq!(seen.lock().unwrap().len(), EVALUATION_QUEUE_CAPACITY);
+    }
+
+    #[tokio::test]
+    async fn test_register_replaces_and_unregister() {
+        let registry = StandingQueryRegistry::default();
+        let (callback, _) = collecting_callback();
+        registry.register(query("q", None), None, callback).unwrap();
+        let (callback, _) = collecting_callback();
+        registry
+            .register(query("q", Some("x > 0")), None, callback)
+            .unwrap();
+
+        let queries = registry.queries().unwrap();
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].where_clause.as_deref(), Some("x > 0"));
+
+        assert!(registry.unregister("q").unwrap());
+        assert!(!registry.unregister("q").unwrap());
+    }
+}
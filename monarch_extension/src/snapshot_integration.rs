@@ -12,6 +12,7 @@
 //! `monarch_introspection_snapshot::integration`. Called from Python
 //! during telemetry/admin startup.
 
+use std::path::PathBuf;
 use std::time::Duration;
 
 use monarch_distributed_telemetry::database_scanner::DatabaseScanner;
@@ -19,6 +20,7 @@ use monarch_hyperactor::context::PyInstance;
 use monarch_hyperactor::host_mesh::PyMeshAdminRef;
 use monarch_introspection_snapshot::integration::register_snapshot_schemas;
 use monarch_introspection_snapshot::integration::start_periodic_snapshots;
+use monarch_introspection_snapshot::service::CaptureMode;
 use pyo3::prelude::*;
 
 /// Pre-register the 9 snapshot table schemas in a `DatabaseScanner`.
@@ -42,13 +44,21 @@ fn pre_register_snapshot_schemas_py(py: Python<'_>, scanner: &DatabaseScanner) -
 /// Fire-and-forget: the actor is spawned on the same proc as the
 /// mesh admin. Framework lifecycle (proc teardown) stops it.
 /// Returns nothing (SI-5).
+///
+/// When `rebase_every` is given, ticks capture incrementally and
+/// every `rebase_every`-th tick is a full capture. `chain_root`
+/// additionally writes base-plus-delta chains under that directory;
+/// it requires `rebase_every`.
 #[pyfunction]
 #[pyo3(name = "_start_periodic_snapshots")]
+#[pyo3(signature = (scanner, admin_ref, instance, interval_secs, rebase_every = None, chain_root = None))]
 fn start_periodic_snapshots_py(
     scanner: &DatabaseScanner,
     admin_ref: &PyMeshAdminRef,
     instance: &PyInstance,
     interval_secs: f64,
+    rebase_every: Option<usize>,
+    chain_root: Option<PathBuf>,
 ) -> PyResult<()> {
     let table_store = scanner.table_store();
     let admin_ref = admin_ref.actor_ref();
@@ -60,9 +70,21 @@ fn start_periodic_snapshots_py(
         )));
     }
     let interval = Duration::from_secs_f64(interval_secs);
+    let mode = match (rebase_every, chain_root) {
+        (Some(rebase_every), chain_root) => CaptureMode::Incremental {
+            rebase_every,
+            chain_root,
+        },
+        (None, None) => CaptureMode::Full,
+        (None, Some(_)) => {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "chain_root requires rebase_every",
+            ));
+        }
+    };
 
     let _guard = pyo3_async_runtimes::tokio::get_runtime().enter();
-    start_periodic_snapshots(&**instance, table_store, admin_ref, interval)
+    start_periodic_snapshots(&**instance, table_store, admin_ref, interval, mode)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("{:#}", e)))
 }
 
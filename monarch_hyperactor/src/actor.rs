@@ -219,6 +219,7 @@ wirevalue::submit! {
         typeid: <PythonMessage as wirevalue::Named>::typeid,
         port: <PythonMessage as wirevalue::Named>::port,
         dump: Some(<PythonMessage as wirevalue::NamedDumpable>::dump),
+        transcode: Some(<PythonMessage as wirevalue::NamedDumpable>::transcode),
         arm_unchecked: <PythonMessage as wirevalue::Named>::arm_unchecked,
         endpoint_name: |ptr| {
             // SAFETY: ptr points to a PythonMessage.
@@ -242,6 +243,7 @@ wirevalue::submit! {
         typeid: <IndexedErasedUnbound<PythonMessage> as wirevalue::Named>::typeid,
         port: <IndexedErasedUnbound<PythonMessage> as wirevalue::Named>::port,
         dump: None,
+        transcode: None,
         arm_unchecked: <IndexedErasedUnbound<PythonMessage> as wirevalue::Named>::arm_unchecked,
         endpoint_name: |ptr| {
             // SAFETY: ptr points to an IndexedErasedUnbound<PythonMessage>.
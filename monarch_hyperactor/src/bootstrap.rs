@@ -6,6 +6,11 @@
  * LICENSE file in the root directory of this source tree.
  */
 
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
 use futures::future::try_join_all;
 use hyperactor::channel::ChannelAddr;
 use hyperactor_mesh::Bootstrap;
@@ -15,6 +20,7 @@ use hyperactor_mesh::bootstrap::bootstrap;
 use hyperactor_mesh::host_mesh::HostMesh;
 use monarch_types::MapPyErr;
 use pyo3::Bound;
+use pyo3::Py;
 use pyo3::PyAny;
 use pyo3::PyResult;
 use pyo3::Python;
@@ -29,6 +35,7 @@ use pyo3::wrap_pyfunction;
 use crate::host_mesh::PyHostMesh;
 use crate::pytokio::PyPythonTask;
 use crate::runtime::monarch_with_gil;
+use crate::runtime::monarch_with_gil_blocking;
 
 #[pyfunction]
 #[pyo3(signature = ())]
@@ -154,6 +161,45 @@ pub fn attach_to_workers<'py>(
     })
 }
 
+/// Install `probe` as this process's health probe. The host agent
+/// calls it on every health check; the proc is healthy when it
+/// returns `None` or `True`, and unhealthy when it returns `False`,
+/// raises, or cannot acquire the GIL within
+/// `MESH_PROC_HEALTH_PROBE_TIMEOUT`.
+#[pyfunction]
+pub fn set_health_probe(probe: Py<PyAny>) {
+    let probe = Arc::new(probe);
+    // Set while a call is blocked on the GIL or running; a wedged
+    // interpreter must not accumulate one blocked thread per check.
+    let running = Arc::new(AtomicBool::new(false));
+    hyperactor_mesh::proc_agent::set_health_probe(Arc::new(
+        move || -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> {
+            let probe = probe.clone();
+            let running = running.clone();
+            Box::pin(async move {
+                if running.swap(true, Ordering::AcqRel) {
+                    return Err("previous health probe is still waiting for the GIL".to_string());
+                }
+                let result = tokio::task::spawn_blocking(move || {
+                    let result = monarch_with_gil_blocking(|py| match probe.bind(py).call0() {
+                        Ok(value) if value.is_none() => Ok(()),
+                        Ok(value) => match value.extract::<bool>() {
+                            Ok(true) => Ok(()),
+                            Ok(false) => Err("health probe returned False".to_string()),
+                            Err(err) => Err(format!("health probe returned a non-bool: {err}")),
+                        },
+                        Err(err) => Err(format!("health probe raised: {err}")),
+                    });
+                    running.store(false, Ordering::Release);
+                    result
+                })
+                .await;
+                result.unwrap_or_else(|err| Err(format!("health probe panicked: {err}")))
+            })
+        },
+    ));
+}
+
 pub fn register_python_bindings(hyperactor_mod: &Bound<'_, PyModule>) -> PyResult<()> {
     let f = wrap_pyfunction!(bootstrap_main, hyperactor_mod)?;
     f.setattr(
@@ -176,5 +222,12 @@ pub fn register_python_bindings(hyperactor_mod: &Bound<'_, PyModule>) -> PyResul
     )?;
     hyperactor_mod.add_function(f)?;
 
+    let f = wrap_pyfunction!(set_health_probe, hyperactor_mod)?;
+    f.setattr(
+        "__module__",
+        "monarch._rust_bindings.monarch_hyperactor.bootstrap",
+    )?;
+    hyperactor_mod.add_function(f)?;
+
     Ok(())
 }
@@ -52,6 +52,8 @@ pub enum PyEncoding {
     Bincode,
     Json,
     Multipart,
+    Cbor,
+    MessagePack,
 }
 
 impl From<wirevalue::Encoding> for PyEncoding {
@@ -60,6 +62,8 @@ impl From<wirevalue::Encoding> for PyEncoding {
             wirevalue::Encoding::Bincode => PyEncoding::Bincode,
             wirevalue::Encoding::Json => PyEncoding::Json,
             wirevalue::Encoding::Multipart => PyEncoding::Multipart,
+            wirevalue::Encoding::Cbor => PyEncoding::Cbor,
+            wirevalue::Encoding::MessagePack => PyEncoding::MessagePack,
         }
     }
 }
@@ -70,6 +74,8 @@ impl From<PyEncoding> for wirevalue::Encoding {
             PyEncoding::Bincode => wirevalue::Encoding::Bincode,
             PyEncoding::Json => wirevalue::Encoding::Json,
             PyEncoding::Multipart => wirevalue::Encoding::Multipart,
+            PyEncoding::Cbor => wirevalue::Encoding::Cbor,
+            PyEncoding::MessagePack => wirevalue::Encoding::MessagePack,
         }
     }
 }
@@ -325,8 +331,9 @@ where
 ///
 /// This is the write-path for the "Python configuration layer": it
 /// takes a typed key/value and merges it into `Source::Runtime` via
-/// `create_or_merge`. No other layers
+/// `try_create_or_merge`. No other layers
 /// (Env/File/TestOverride/ClientOverride/Defaults) are affected.
+/// Values rejected by the key's validator raise a `ValueError`.
 fn set_runtime_config_py<T: AttrValue + Debug>(
     key: &'static dyn ErasedKey,
     value: T,
@@ -335,8 +342,10 @@ fn set_runtime_config_py<T: AttrValue + Debug>(
     let key = key.downcast_ref().expect("cannot fail");
     let mut attrs = Attrs::new();
     attrs.set(key.clone(), value);
-    hyperactor_config::global::create_or_merge(Source::Runtime, attrs);
-    Ok(())
+    hyperactor_config::global::try_create_or_merge(Source::Runtime, attrs).map_err(|errors| {
+        let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        PyValueError::new_err(errors.join("; "))
+    })
 }
 
 /// Bridge a single Python kwarg into a typed Runtime config update.
@@ -783,7 +792,13 @@ mod tests {
         Python::initialize();
         Python::attach(|py| {
             // Test all enum variants roundtrip
-            for variant in [PyEncoding::Bincode, PyEncoding::Json, PyEncoding::Multipart] {
+            for variant in [
+                PyEncoding::Bincode,
+                PyEncoding::Json,
+                PyEncoding::Multipart,
+                PyEncoding::Cbor,
+                PyEncoding::MessagePack,
+            ] {
                 let py_obj = Bound::new(py, variant).unwrap().into_any();
                 let back: PyEncoding = py_obj.extract().unwrap();
                 assert_eq!(back, variant);
@@ -823,6 +838,14 @@ mod tests {
                 PyEncoding::from(wirevalue::Encoding::Multipart),
                 PyEncoding::Multipart
             );
+            assert_eq!(
+                PyEncoding::from(wirevalue::Encoding::Cbor),
+                PyEncoding::Cbor
+            );
+            assert_eq!(
+                PyEncoding::from(wirevalue::Encoding::MessagePack),
+                PyEncoding::MessagePack
+            );
         });
     }
 
@@ -30,10 +30,15 @@ use hyperactor_config::Flattrs;
 use hyperactor_mesh::ActorMesh;
 use hyperactor_mesh::actor_mesh::ActorMeshRef;
 use hyperactor_mesh::bootstrap::MESH_ENABLE_LOG_FORWARDING;
+use hyperactor_mesh::logging::LOG_SEARCH_TIMEOUT;
 use hyperactor_mesh::logging::LogClientActor;
 use hyperactor_mesh::logging::LogClientMessage;
+use hyperactor_mesh::logging::LogFilter;
 use hyperactor_mesh::logging::LogForwardActor;
 use hyperactor_mesh::logging::LogForwardMessage;
+use hyperactor_mesh::logging::LogLevel;
+use hyperactor_mesh::logging::LogRecord;
+use hyperactor_mesh::logging::RankFileLayout;
 use monarch_types::SerializablePyErr;
 use ndslice::View;
 use pyo3::Bound;
@@ -150,7 +155,11 @@ impl LoggerRuntimeMessageHandler for LoggerRuntimeActor {
 ///   - toggle streaming vs "stay quiet" (`set_mode(...)`),
 ///   - adjust the per-proc Python log level (`set_mode(...)`),
 ///   - force a sync flush of forwarded output and wait for completion
-///     (`flush(...)`).
+///     (`flush(...)`),
+///   - restrict which lines are forwarded (`set_filter(...)`) and
+///     search each proc's recently retained lines (`search(...)`),
+///   - additionally write forwarded output to rotating per-rank files
+///     (`set_rank_files(...)`).
 ///
 /// Drop semantics:
 ///   Dropping the Python handle runs `Drop` on this Rust struct,
@@ -190,6 +199,10 @@ pub struct LoggingMeshClient {
     // receives forwarded output, aggregates and buffers it, and
     // coordinates sync flush barriers.
     client_actor: ActorHandle<LogClientActor>,
+
+    // How the procs of the mesh map to per-rank log files, captured at
+    // spawn time for `set_rank_files(..)`.
+    rank_file_layout: RankFileLayout,
 }
 
 impl LoggingMeshClient {
@@ -257,6 +270,82 @@ impl LoggingMeshClient {
 
         Ok(())
     }
+
+    /// Ask every forwarder for its retained records accepted by
+    /// `filter`, and merge the replies into the `limit` most recent
+    /// records across the mesh, oldest first. Forwarders that have
+    /// not replied within `LOG_SEARCH_TIMEOUT` are skipped; their
+    /// ranks are returned alongside the records, in order.
+    async fn search_internal(
+        cx: &impl context::Actor,
+        forwarder_mesh: ActorMeshRef<LogForwardActor>,
+        filter: LogFilter,
+        limit: usize,
+    ) -> Result<(Vec<LogRecord>, Vec<usize>), anyhow::Error> {
+        let (reply_tx, mut reply_rx) = cx.instance().open_port::<(Option<usize>, Vec<LogRecord>)>();
+        forwarder_mesh.cast(
+            cx,
+            LogForwardMessage::QueryRecords {
+                filter,
+                limit,
+                reply: reply_tx.bind(),
+            },
+        )?;
+
+        let num_ranks = forwarder_mesh.region().num_ranks();
+        let deadline =
+            tokio::time::Instant::now() + hyperactor_config::global::get(LOG_SEARCH_TIMEOUT);
+        let mut records = Vec::new();
+        let mut replied = vec![false; num_ranks];
+        let mut num_replies = 0;
+        while num_replies < num_ranks {
+            let Ok(reply) = tokio::time::timeout_at(deadline, reply_rx.recv()).await else {
+                break;
+            };
+            let (rank, rank_records) = reply?;
+            if let Some(rank) = rank
+                && let Some(replied) = replied.get_mut(rank)
+            {
+                *replied = true;
+            }
+            num_replies += 1;
+            records.extend(rank_records);
+        }
+        let missing: Vec<usize> = if num_replies < num_ranks {
+            (0..num_ranks).filter(|rank| !replied[*rank]).collect()
+        } else {
+            Vec::new()
+        };
+        if !missing.is_empty() {
+            tracing::warn!(?missing, "log search timed out waiting for some ranks");
+        }
+
+        records.sort_by_key(|record| record.timestamp);
+        let excess = records.len().saturating_sub(limit);
+        records.drain(..excess);
+        Ok((records, missing))
+    }
+}
+
+/// Build and validate a `LogFilter` from its Python arguments.
+fn log_filter(
+    min_level: Option<String>,
+    ranks: Option<Vec<usize>>,
+    pattern: Option<String>,
+) -> PyResult<LogFilter> {
+    let min_level = min_level
+        .map(|level| level.parse::<LogLevel>())
+        .transpose()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    let filter = LogFilter {
+        min_level,
+        ranks,
+        pattern,
+    };
+    filter
+        .compile()
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    Ok(filter)
 }
 
 #[pymethods]
@@ -334,6 +423,7 @@ impl LoggingMeshClient {
                 forwarder_mesh,
                 logger_mesh,
                 client_actor,
+                rank_file_layout: RankFileLayout::for_proc_mesh(&proc_mesh),
             })
         })
     }
@@ -460,6 +550,127 @@ impl LoggingMeshClient {
                 .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))
         })
     }
+
+    /// Write forwarded output to per-rank files under `dir`, in
+    /// addition to the terminal, or stop doing so if `dir` is `None`.
+    ///
+    /// Files are laid out as `{dir}/{mesh}/{coords}.{stdout,stderr}.log`
+    /// next to an `index.json` mapping ranks to proc ids and hosts, and
+    /// are rotated according to the `log_rank_file_*` config.
+    #[pyo3(signature = (instance, dir=None))]
+    fn set_rank_files(&self, instance: &PyInstance, dir: Option<String>) -> PyResult<()> {
+        // Surface an unusable directory here rather than only in the
+        // client actor's log.
+        if let Some(dir) = &dir {
+            std::fs::create_dir_all(dir).map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!(
+                    "cannot create log directory {}: {}",
+                    dir, e
+                ))
+            })?;
+        }
+        self.client_actor
+            .send(
+                instance.deref(),
+                LogClientMessage::SetRankFiles {
+                    dir,
+                    layout: self.rank_file_layout.clone(),
+                },
+            )
+            .map_err(anyhow::Error::msg)?;
+        Ok(())
+    }
+
+    /// Restrict which lines the forwarders stream back to the client.
+    ///
+    /// The filter is evaluated on each remote proc, so rejected lines
+    /// never reach the client. A line is forwarded if it is at least
+    /// `min_level` (lines without a recognizable level count as
+    /// `INFO`), comes from one of `ranks`, and matches the regular
+    /// expression `pattern`; `None` leaves a criterion unconstrained.
+    /// Calling this with no criteria restores unfiltered forwarding.
+    ///
+    /// Rejected lines are still retained for `search(...)`. This is a
+    /// no-op if log forwarding was disabled at startup.
+    #[pyo3(signature = (instance, min_level=None, ranks=None, pattern=None))]
+    fn set_filter(
+        &self,
+        instance: &PyInstance,
+        min_level: Option<String>,
+        ranks: Option<Vec<usize>>,
+        pattern: Option<String>,
+    ) -> PyResult<()> {
+        let filter = log_filter(min_level, ranks, pattern)?;
+        if let Some(fwd_mesh) = &self.forwarder_mesh {
+            fwd_mesh
+                .cast(instance.deref(), LogForwardMessage::SetFilter { filter })
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Search the lines recently retained by each remote proc,
+    /// whether or not they were forwarded. Criteria are as for
+    /// `set_filter(...)`. Returns at most `limit` of the most recent
+    /// matches across the mesh, oldest first, as
+    /// `(timestamp, level, rank, actor_id, line)` tuples, where
+    /// `timestamp` is in seconds since the epoch and `level` is the
+    /// line's own level or the one it inherited from the line it
+    /// continues.
+    ///
+    /// Procs that do not reply within `HYPERACTOR_LOG_SEARCH_TIMEOUT`
+    /// are skipped: the result is `(records, missing_ranks)`, where
+    /// `missing_ranks` lists their ranks.
+    ///
+    /// Returns empty lists if log forwarding was disabled at
+    /// startup.
+    #[pyo3(signature = (instance, min_level=None, ranks=None, pattern=None, limit=100))]
+    fn search(
+        &self,
+        instance: &PyInstance,
+        min_level: Option<String>,
+        ranks: Option<Vec<usize>>,
+        pattern: Option<String>,
+        limit: usize,
+    ) -> PyResult<PyPythonTask> {
+        let filter = log_filter(min_level, ranks, pattern)?;
+        let forwarder_mesh_opt = self
+            .forwarder_mesh
+            .as_ref()
+            .map(|mesh| mesh.deref().clone());
+        let instance = instance.clone();
+
+        PyPythonTask::new(async move {
+            let Some(forwarder_mesh) = forwarder_mesh_opt else {
+                return Ok((Vec::new(), Vec::new()));
+            };
+
+            let (records, missing) =
+                Self::search_internal(instance.deref(), forwarder_mesh, filter, limit)
+                    .await
+                    .map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())
+                    })?;
+            let records = records
+                .into_iter()
+                .map(|record| {
+                    let timestamp = record
+                        .timestamp
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs_f64();
+                    (
+                        timestamp,
+                        record.effective_level().map(|level| level.to_string()),
+                        record.rank,
+                        record.actor_id,
+                        record.line,
+                    )
+                })
+                .collect::<Vec<_>>();
+            Ok((records, missing))
+        })
+    }
 }
 
 // NOTE ON LIFECYCLE / CLEANUP
@@ -619,11 +619,19 @@ impl ProcLauncher for ActorProcLauncher {
     ///   rather than direct OS signals.
     /// - If decoding the exit payload fails, the returned `exit_rx`
     ///   resolves to `ProcExitKind::Failed` with a decode error reason.
+    /// - Resource limits are not supported: the spawner protocol has
+    ///   no way to apply them, so a launch requesting them fails
+    ///   rather than running the proc unconstrained.
     async fn launch(
         &self,
         proc_id: &reference::ProcId,
         opts: LaunchOptions,
     ) -> Result<LaunchResult, ProcLauncherError> {
+        if let Some(resources) = &opts.resources {
+            return Err(ProcLauncherError::Other(format!(
+                "resource limits {resources:?} are not supported by the actor proc launcher"
+            )));
+        }
         let (exit_port, exit_port_rx) = self.mailbox.open_once_port::<PythonMessage>();
 
         let pickled_args = Python::attach(|py| -> Result<Vec<u8>, ProcLauncherError> {
@@ -146,6 +146,41 @@ fn counts_from_loaded(loaded: &[(&str, usize)]) -> NodeCounts {
     }
 }
 
+/// Write `batch` as a single-batch Arrow IPC file (BN-2).
+pub(crate) fn write_table_file(path: &Path, batch: &RecordBatch) -> anyhow::Result<()> {
+    let file = fs::File::create(path).with_context(|| format!("failed to create {:?}", path))?;
+    let writer = BufWriter::new(file);
+    let mut ipc_writer = FileWriter::try_new(writer, &batch.schema())
+        .with_context(|| format!("failed to create IPC writer for {:?}", path))?;
+    ipc_writer
+        .write(batch)
+        .with_context(|| format!("failed to write batch to {:?}", path))?;
+    ipc_writer
+        .finish()
+        .with_context(|| format!("failed to finish IPC file {:?}", path))?;
+    Ok(())
+}
+
+/// Read a single-batch Arrow IPC file, erroring unless it holds
+/// exactly one batch (BN-2).
+pub(crate) fn read_table_file(path: &Path) -> anyhow::Result<RecordBatch> {
+    let file = fs::File::open(path).with_context(|| format!("failed to open {:?}", path))?;
+    let reader = FileReader::try_new(BufReader::new(file), None)
+        .with_context(|| format!("failed to create IPC reader for {:?}", path))?;
+    let mut batches = Vec::new();
+    for batch_result in reader {
+        batches
+            .push(batch_result.with_context(|| format!("failed to read batch from {:?}", path))?);
+    }
+    anyhow::ensure!(
+        batches.len() == 1,
+        "expected exactly 1 batch in {:?}, found {}",
+        path,
+        batches.len(),
+    );
+    Ok(batches.pop().expect("checked above"))
+}
+
 /// Write a snapshot bundle to `dir`.
 ///
 /// All manifest metadata is derived from the batches themselves —
@@ -185,18 +220,7 @@ pub fn write_bundle(dir: &Path, batches: &[NamedBatch]) -> anyhow::Result<()> {
 
     // Write table files before manifest (BN-5).
     for (name, batch) in batches {
-        let path = dir.join(format!("{}.arrow", name));
-        let file =
-            fs::File::create(&path).with_context(|| format!("failed to create {:?}", path))?;
-        let writer = BufWriter::new(file);
-        let mut ipc_writer = FileWriter::try_new(writer, &batch.schema())
-            .with_context(|| format!("failed to create IPC writer for {:?}", path))?;
-        ipc_writer
-            .write(batch)
-            .with_context(|| format!("failed to write batch to {:?}", path))?;
-        ipc_writer
-            .finish()
-            .with_context(|| format!("failed to finish IPC file {:?}", path))?;
+        write_table_file(&dir.join(format!("{}.arrow", name)), batch)?;
     }
 
     // BN-5: manifest written last, derived from batch data.
@@ -216,11 +240,11 @@ pub fn write_bundle(dir: &Path, batches: &[NamedBatch]) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Import a snapshot bundle from `dir`.
+/// Read and validate a snapshot bundle from `dir`.
 ///
-/// Validates the manifest (BN-6), reads one `RecordBatch` per table
-/// file (BN-2), and ingests into a fresh [`TableStore`].
-pub async fn import_snapshot_bundle(dir: &Path) -> anyhow::Result<(TableStore, BundleManifest)> {
+/// Validates the manifest (BN-6) and returns one `RecordBatch` per
+/// table file (BN-2), in [`SNAPSHOT_TABLE_NAMES`] order.
+pub fn read_bundle(dir: &Path) -> anyhow::Result<(Vec<NamedBatch>, BundleManifest)> {
     // Read and validate manifest.
     let manifest_path = dir.join("manifest.json");
     let manifest_file = fs::File::open(&manifest_path)
@@ -245,55 +269,37 @@ pub async fn import_snapshot_bundle(dir: &Path) -> anyhow::Result<(TableStore, B
         expected,
     );
 
-    // Load each table file and ingest into a fresh store.
-    // Track row counts per table for node_counts cross-check.
-    let store = TableStore::new_empty();
+    // Load each table file. Track row counts per table for
+    // node_counts cross-check.
+    let mut batches: Vec<NamedBatch> = Vec::new();
     let mut loaded_batches: Vec<(&str, usize)> = Vec::new();
-    for table_name in &manifest.tables {
-        let path = dir.join(format!("{}.arrow", table_name));
-        let file = fs::File::open(&path).with_context(|| format!("failed to open {:?}", path))?;
-        let reader = FileReader::try_new(BufReader::new(file), None)
-            .with_context(|| format!("failed to create IPC reader for {:?}", path))?;
-
+    for table_name in SNAPSHOT_TABLE_NAMES {
         // BN-2: read exactly one batch.
-        let mut batch_count = 0;
-        let mut row_count = 0;
-        for batch_result in reader {
-            let batch =
-                batch_result.with_context(|| format!("failed to read batch from {:?}", path))?;
-
-            // BN-1: verify snapshots table has one row and matches
-            // the manifest.
-            if table_name == "snapshots" {
-                let (row_id, row_ts) = extract_snapshot_row(&batch)
-                    .context("snapshots.arrow failed BN-1 validation")?;
-                anyhow::ensure!(
-                    row_id == manifest.snapshot_id,
-                    "snapshots.arrow snapshot_id {:?} does not match \
-                     manifest {:?}",
-                    row_id,
-                    manifest.snapshot_id,
-                );
-                anyhow::ensure!(
-                    row_ts == manifest.snapshot_ts,
-                    "snapshots.arrow snapshot_ts {} does not match \
-                     manifest {}",
-                    row_ts,
-                    manifest.snapshot_ts,
-                );
-            }
-
-            row_count += batch.num_rows();
-            store.ingest_batch(table_name, batch).await?;
-            batch_count += 1;
+        let batch = read_table_file(&dir.join(format!("{}.arrow", table_name)))?;
+
+        // BN-1: verify snapshots table has one row and matches the
+        // manifest.
+        if *table_name == "snapshots" {
+            let (row_id, row_ts) =
+                extract_snapshot_row(&batch).context("snapshots.arrow failed BN-1 validation")?;
+            anyhow::ensure!(
+                row_id == manifest.snapshot_id,
+                "snapshots.arrow snapshot_id {:?} does not match \
+                 manifest {:?}",
+                row_id,
+                manifest.snapshot_id,
+            );
+            anyhow::ensure!(
+                row_ts == manifest.snapshot_ts,
+                "snapshots.arrow snapshot_ts {} does not match \
+                 manifest {}",
+                row_ts,
+                manifest.snapshot_ts,
+            );
         }
-        anyhow::ensure!(
-            batch_count == 1,
-            "expected exactly 1 batch in {:?}, found {}",
-            path,
-            batch_count,
-        );
-        loaded_batches.push((table_name, row_count));
+
+        loaded_batches.push((*table_name, batch.num_rows()));
+        batches.push((*table_name, batch));
     }
 
     // Cross-check manifest.node_counts against actual file row
@@ -308,6 +314,19 @@ pub async fn import_snapshot_bundle(dir: &Path) -> anyhow::Result<(TableStore, B
         actual_counts,
     );
 
+    Ok((batches, manifest))
+}
+
+/// Import a snapshot bundle from `dir`.
+///
+/// Validates the bundle via [`read_bundle`] and ingests every table
+/// into a fresh [`TableStore`].
+pub async fn import_snapshot_bundle(dir: &Path) -> anyhow::Result<(TableStore, BundleManifest)> {
+    let (batches, manifest) = read_bundle(dir)?;
+    let store = TableStore::new_empty();
+    for (table_name, batch) in batches {
+        store.ingest_batch(table_name, batch).await?;
+    }
     Ok((store, manifest))
 }
 
@@ -446,6 +465,7 @@ mod tests {
                 stopped_retention_cap: 100,
                 is_poisoned: false,
                 failed_actor_count: 0,
+                introspect_generation: None,
             }],
             actor_nodes: vec![ActorNodeRow {
                 snapshot_id: id.to_owned(),
@@ -65,7 +65,7 @@ use crate::schema::RootNodeRow;
 use crate::schema::SnapshotRow;
 
 /// All row vectors produced by a single snapshot capture.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SnapshotData {
     /// Capture metadata (CS-1: exactly one per successful capture).
     pub snapshot: SnapshotRow,
@@ -338,6 +338,7 @@ mod tests {
                         is_poisoned: false,
                         failed_actor_count: 0,
                         debug: Default::default(),
+                        generation: None,
                     },
                     vec![actor_a.clone(), actor_b.clone()],
                 ),
@@ -447,6 +448,7 @@ mod tests {
                         is_poisoned: false,
                         failed_actor_count: 0,
                         debug: Default::default(),
+                        generation: None,
                     },
                     vec![a0.clone(), a1.clone(), a2.clone()],
                 ),
@@ -576,6 +578,7 @@ mod tests {
                         is_poisoned: false,
                         failed_actor_count: 0,
                         debug: Default::default(),
+                        generation: None,
                     },
                     vec![actor_a.clone(), actor_b.clone()],
                 ),
@@ -708,6 +711,7 @@ mod tests {
                         stopped_retention_cap: 0,
                         is_poisoned: false,
                         failed_actor_count: 0,
+                        introspect_generation: None,
                     }),
                     actor_failure: None,
                     children: vec![],
@@ -869,6 +873,7 @@ mod tests {
                         is_poisoned: false,
                         failed_actor_count: 0,
                         debug: Default::default(),
+                        generation: None,
                     },
                     vec![error_ref.clone()],
                 ),
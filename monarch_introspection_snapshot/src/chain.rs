@@ -0,0 +1,509 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Delta bundles and snapshot chains.
+//!
+//! A *delta bundle* is the on-disk form of a
+//! [`SnapshotDelta`](crate::delta::SnapshotDelta): the 9 snapshot
+//! tables holding only the upserted nodes' rows (`snapshots.arrow`
+//! holds the delta's own snapshot row), a `removed_nodes.arrow`
+//! tombstone table, and a `delta.json` manifest.
+//!
+//! A *chain* is a full base bundle plus an ordered sequence of delta
+//! bundles, each applying to the snapshot materialized by its
+//! predecessor. It is the storage format for high-frequency capture
+//! and the input for timeline replay.
+//!
+//! ```text
+//! {chain_dir}/
+//!   chain.json
+//!   base/                              full bundle (see bundle.rs)
+//!   deltas/000001-{snapshot_id}/       delta bundle
+//!   deltas/000002-{snapshot_id}/       delta bundle
+//! ```
+//!
+//! # Chain invariants (CH-*)
+//!
+//! - **CH-1 (base is a bundle):** `base/` is a full snapshot bundle
+//!   and satisfies BN-1..BN-8.
+//! - **CH-2 (manifests last):** `delta.json` is written after the
+//!   delta's table files, and `chain.json` is replaced (write to a
+//!   temporary file, then rename) only after the delta bundle is
+//!   complete. A delta directory not listed in `chain.json` is
+//!   ignored by readers.
+//! - **CH-3 (linked):** Each delta's `base_snapshot_id` is the
+//!   `snapshot_id` of the preceding chain entry. The writer checks
+//!   this on append and [`replay_chain`] checks it on read (DL-5).
+//! - **CH-4 (replay fidelity):** [`replay_chain`] materializes exactly
+//!   the indexes the writer held after each append.
+//! - **CH-5 (portable manifest):** `chain.json` stores directories
+//!   relative to the chain root, so a chain can be moved or copied.
+
+use std::fs;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use datafusion::arrow::array::AsArray;
+use monarch_record_batch::RecordBatchBuffer;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::bundle::read_bundle;
+use crate::bundle::read_table_file;
+use crate::bundle::write_bundle;
+use crate::bundle::write_table_file;
+use crate::delta::SnapshotDelta;
+use crate::delta::SnapshotIndex;
+use crate::push::NamedBatch;
+use crate::push::SNAPSHOT_TABLE_NAMES;
+use crate::push::batches_to_data;
+use crate::push::drain_to_batches;
+use crate::schema::RemovedNodeRow;
+use crate::schema::RemovedNodeRowBuffer;
+use crate::service::NodeCounts;
+
+/// Current delta bundle and chain format version.
+const CHAIN_VERSION: u32 = 1;
+
+/// Tombstone table present only in delta bundles.
+const REMOVED_NODES_TABLE: &str = "removed_nodes";
+
+/// Portable metadata for a delta bundle, persisted as `delta.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaManifest {
+    /// Format version. Currently always `1`.
+    pub version: u32,
+    /// The snapshot this delta produces.
+    pub snapshot_id: String,
+    /// Capture timestamp of that snapshot, microseconds since epoch.
+    pub snapshot_ts: i64,
+    /// The snapshot this delta applies to.
+    pub base_snapshot_id: String,
+    /// Row counts of the upserted nodes.
+    pub node_counts: NodeCounts,
+    /// Number of removed nodes.
+    pub removed_nodes: usize,
+}
+
+/// One snapshot in a chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainEntry {
+    /// Snapshot ID.
+    pub snapshot_id: String,
+    /// Capture timestamp, microseconds since epoch.
+    pub snapshot_ts: i64,
+    /// Bundle directory relative to the chain root (CH-5).
+    pub dir: String,
+}
+
+/// Portable metadata for a chain, persisted as `chain.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainManifest {
+    /// Format version. Currently always `1`.
+    pub version: u32,
+    /// The full base snapshot.
+    pub base: ChainEntry,
+    /// Deltas in application order.
+    pub deltas: Vec<ChainEntry>,
+}
+
+impl ChainManifest {
+    /// All entries in timeline order, base first.
+    pub fn entries(&self) -> impl Iterator<Item = &ChainEntry> {
+        std::iter::once(&self.base).chain(self.deltas.iter())
+    }
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> anyhow::Result<()> {
+    let file = fs::File::create(path).with_context(|| format!("failed to create {:?}", path))?;
+    serde_json::to_writer_pretty(BufWriter::new(file), value)
+        .with_context(|| format!("failed to write {:?}", path))
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> anyhow::Result<T> {
+    let file = fs::File::open(path).with_context(|| format!("failed to open {:?}", path))?;
+    serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("failed to parse {:?}", path))
+}
+
+/// Write a delta bundle to `dir`, which must not exist.
+///
+/// Table files are written first and `delta.json` last (CH-2).
+pub fn write_delta_bundle(dir: &Path, delta: &SnapshotDelta) -> anyhow::Result<DeltaManifest> {
+    let data = delta.upsert_data();
+    let node_counts = NodeCounts::from_data(&data);
+    let batches = drain_to_batches(data)?;
+
+    let mut removed = RemovedNodeRowBuffer::default();
+    for node_id in &delta.removed {
+        removed.insert(RemovedNodeRow {
+            snapshot_id: delta.snapshot.snapshot_id.clone(),
+            node_id: node_id.clone(),
+        });
+    }
+
+    fs::create_dir(dir).with_context(|| format!("failed to create delta directory {:?}", dir))?;
+    for (name, batch) in &batches {
+        write_table_file(&dir.join(format!("{}.arrow", name)), batch)?;
+    }
+    write_table_file(
+        &dir.join(format!("{}.arrow", REMOVED_NODES_TABLE)),
+        &removed.drain_to_record_batch()?,
+    )?;
+
+    let manifest = DeltaManifest {
+        version: CHAIN_VERSION,
+        snapshot_id: delta.snapshot.snapshot_id.clone(),
+        snapshot_ts: delta.snapshot.snapshot_ts,
+        base_snapshot_id: delta.base_snapshot_id.clone(),
+        node_counts,
+        removed_nodes: delta.removed.len(),
+    };
+    write_json(&dir.join("delta.json"), &manifest)?;
+    Ok(manifest)
+}
+
+/// Read and validate a delta bundle from `dir`.
+pub fn read_delta_bundle(dir: &Path) -> anyhow::Result<(SnapshotDelta, DeltaManifest)> {
+    let manifest: DeltaManifest = read_json(&dir.join("delta.json"))?;
+    anyhow::ensure!(
+        manifest.version == CHAIN_VERSION,
+        "unsupported delta bundle version {} (expected {})",
+        manifest.version,
+        CHAIN_VERSION,
+    );
+
+    let mut batches: Vec<NamedBatch> = Vec::new();
+    for table_name in SNAPSHOT_TABLE_NAMES {
+        let batch = read_table_file(&dir.join(format!("{}.arrow", table_name)))?;
+        batches.push((*table_name, batch));
+    }
+    let data = batches_to_data(&batches)?;
+    anyhow::ensure!(
+        data.snapshot.snapshot_id == manifest.snapshot_id
+            && data.snapshot.snapshot_ts == manifest.snapshot_ts,
+        "delta snapshot row {:?} does not match manifest {:?}",
+        data.snapshot,
+        manifest,
+    );
+    let counts = NodeCounts::from_data(&data);
+    anyhow::ensure!(
+        counts == manifest.node_counts,
+        "manifest node_counts {:?} do not match actual file row counts {:?}",
+        manifest.node_counts,
+        counts,
+    );
+
+    let removed_batch = read_table_file(&dir.join(format!("{}.arrow", REMOVED_NODES_TABLE)))?;
+    let removed: Vec<String> = removed_batch
+        .column_by_name("node_id")
+        .and_then(|c| c.as_string_opt::<i32>())
+        .context("removed_nodes.node_id missing or not Utf8")?
+        .iter()
+        .map(|id| id.map(str::to_owned).context("null removed node_id"))
+        .collect::<anyhow::Result<_>>()?;
+    anyhow::ensure!(
+        removed.len() == manifest.removed_nodes,
+        "manifest removed_nodes {} does not match actual {}",
+        manifest.removed_nodes,
+        removed.len(),
+    );
+
+    let index = SnapshotIndex::from_data(data)?;
+    let delta = SnapshotDelta {
+        base_snapshot_id: manifest.base_snapshot_id.clone(),
+        snapshot: index.snapshot,
+        upserts: index.nodes.into_values().collect(),
+        removed,
+    };
+    Ok((delta, manifest))
+}
+
+/// Appends deltas to a chain directory.
+pub struct ChainWriter {
+    dir: PathBuf,
+    manifest: ChainManifest,
+    /// The snapshot materialized by the last entry.
+    head: SnapshotIndex,
+}
+
+impl ChainWriter {
+    /// Create a chain at `dir` (which must not exist) with `base` as
+    /// its full base snapshot (CH-1).
+    pub fn create(dir: &Path, base: SnapshotIndex) -> anyhow::Result<Self> {
+        fs::create_dir(dir)
+            .with_context(|| format!("failed to create chain directory {:?}", dir))?;
+        write_bundle(
+            &dir.join("base"),
+            &drain_to_batches(base.clone().into_data())?,
+        )?;
+        fs::create_dir(dir.join("deltas"))
+            .with_context(|| format!("failed to create {:?}", dir.join("deltas")))?;
+
+        let writer = Self {
+            dir: dir.to_owned(),
+            manifest: ChainManifest {
+                version: CHAIN_VERSION,
+                base: ChainEntry {
+                    snapshot_id: base.snapshot.snapshot_id.clone(),
+                    snapshot_ts: base.snapshot.snapshot_ts,
+                    dir: "base".to_owned(),
+                },
+                deltas: Vec::new(),
+            },
+            head: base,
+        };
+        writer.write_manifest()?;
+        Ok(writer)
+    }
+
+    /// Append a delta against the current head (CH-2, CH-3).
+    pub fn append(&mut self, delta: &SnapshotDelta) -> anyhow::Result<&ChainEntry> {
+        let next = delta.apply(&self.head)?;
+        let rel = format!(
+            "deltas/{:06}-{}",
+            self.manifest.deltas.len() + 1,
+            delta.snapshot.snapshot_id
+        );
+        write_delta_bundle(&self.dir.join(&rel), delta)?;
+        self.manifest.deltas.push(ChainEntry {
+            snapshot_id: delta.snapshot.snapshot_id.clone(),
+            snapshot_ts: delta.snapshot.snapshot_ts,
+            dir: rel,
+        });
+        self.write_manifest()?;
+        self.head = next;
+        Ok(self.manifest.deltas.last().expect("just pushed"))
+    }
+
+    /// The chain root directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The chain manifest as last written.
+    pub fn manifest(&self) -> &ChainManifest {
+        &self.manifest
+    }
+
+    /// The snapshot materialized by the last entry.
+    pub fn head(&self) -> &SnapshotIndex {
+        &self.head
+    }
+
+    /// CH-2: replace `chain.json` atomically.
+    fn write_manifest(&self) -> anyhow::Result<()> {
+        let tmp = self.dir.join("chain.json.tmp");
+        write_json(&tmp, &self.manifest)?;
+        fs::rename(&tmp, self.dir.join("chain.json"))
+            .with_context(|| format!("failed to replace chain.json in {:?}", self.dir))
+    }
+}
+
+/// Read a chain's manifest without materializing any snapshot.
+pub fn read_chain_manifest(dir: &Path) -> anyhow::Result<ChainManifest> {
+    let manifest: ChainManifest = read_json(&dir.join("chain.json"))?;
+    anyhow::ensure!(
+        manifest.version == CHAIN_VERSION,
+        "unsupported chain version {} (expected {})",
+        manifest.version,
+        CHAIN_VERSION,
+    );
+    Ok(manifest)
+}
+
+/// Materialize every snapshot of a chain, base first (CH-3, CH-4).
+pub fn replay_chain(dir: &Path) -> anyhow::Result<(ChainManifest, Vec<SnapshotIndex>)> {
+    let manifest = read_chain_manifest(dir)?;
+    let (batches, _) = read_bundle(&dir.join(&manifest.base.dir))?;
+    let base = SnapshotIndex::from_data(batches_to_data(&batches)?)?;
+    anyhow::ensure!(
+        base.snapshot_id() == manifest.base.snapshot_id,
+        "chain base {} does not match manifest {}",
+        base.snapshot_id(),
+        manifest.base.snapshot_id,
+    );
+
+    let mut snapshots = vec![base];
+    for entry in &manifest.deltas {
+        let (delta, _) = read_delta_bundle(&dir.join(&entry.dir))?;
+        anyhow::ensure!(
+            delta.snapshot.snapshot_id == entry.snapshot_id,
+            "delta {:?} holds snapshot {}, expected {}",
+            entry.dir,
+            delta.snapshot.snapshot_id,
+            entry.snapshot_id,
+        );
+        let next = delta
+            .apply(snapshots.last().expect("base pushed"))
+            .with_context(|| format!("failed to apply delta {:?}", entry.dir))?;
+        snapshots.push(next);
+    }
+    Ok((manifest, snapshots))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::convert::ConvertedNode;
+    use crate::convert::NodeKindRow;
+    use crate::schema::ActorNodeRow;
+    use crate::schema::ChildRow;
+    use crate::schema::NodeRow;
+    use crate::schema::RootNodeRow;
+    use crate::schema::SnapshotRow;
+
+    // Fixtures: an index with root and a set of actors, built
+    // directly from rows.
+
+    fn actor(snapshot_id: &str, name: &str, messages: i64) -> ConvertedNode {
+        ConvertedNode {
+            node: NodeRow {
+                snapshot_id: snapshot_id.to_owned(),
+                node_id: name.to_owned(),
+                node_kind: "actor".to_owned(),
+                as_of: 1,
+            },
+            kind_row: NodeKindRow::Actor(ActorNodeRow {
+                snapshot_id: snapshot_id.to_owned(),
+                node_id: name.to_owned(),
+                actor_status: "running".to_owned(),
+                actor_type: "test".to_owned(),
+                messages_processed: messages,
+                created_at: None,
+                last_message_handler: Some("handle".to_owned()),
+                total_processing_time_us: 0,
+                is_system: false,
+            }),
+            actor_failure: None,
+            children: vec![],
+        }
+    }
+
+    fn index(snapshot_id: &str, ts: i64, actors: &[(&str, i64)]) -> SnapshotIndex {
+        let root = ConvertedNode {
+            node: NodeRow {
+                snapshot_id: snapshot_id.to_owned(),
+                node_id: "root".to_owned(),
+                node_kind: "root".to_owned(),
+                as_of: 1,
+            },
+            kind_row: NodeKindRow::Root(RootNodeRow {
+                snapshot_id: snapshot_id.to_owned(),
+                node_id: "root".to_owned(),
+                num_hosts: 0,
+                started_at: 0,
+                started_by: "test".to_owned(),
+            }),
+            actor_failure: None,
+            children: actors
+                .iter()
+                .enumerate()
+                .map(|(i, (name, _))| ChildRow {
+                    snapshot_id: snapshot_id.to_owned(),
+                    parent_id: "root".to_owned(),
+                    child_id: name.to_string(),
+                    child_sort_key: i as i64,
+                    is_system: false,
+                    is_stopped: false,
+                })
+                .collect(),
+        };
+        let mut nodes = BTreeMap::new();
+        nodes.insert("root".to_owned(), root);
+        for (name, messages) in actors {
+            nodes.insert(name.to_string(), actor(snapshot_id, name, *messages));
+        }
+        SnapshotIndex {
+            snapshot: SnapshotRow {
+                snapshot_id: snapshot_id.to_owned(),
+                snapshot_ts: ts,
+            },
+            nodes,
+        }
+    }
+
+    #[test]
+    fn test_delta_bundle_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = index("s1", 10, &[("a", 1), ("b", 1)]);
+        let next = index("s2", 20, &[("a", 2), ("c", 0)]);
+        let delta = SnapshotDelta::diff(&base, &next);
+
+        let manifest = write_delta_bundle(&dir.path().join("d"), &delta).unwrap();
+        assert_eq!(manifest.base_snapshot_id, "s1");
+        assert_eq!(manifest.removed_nodes, 1);
+        assert_eq!(manifest.node_counts.actor_nodes, 2);
+
+        let (read, read_manifest) = read_delta_bundle(&dir.path().join("d")).unwrap();
+        assert_eq!(read, delta);
+        assert_eq!(read_manifest, manifest);
+    }
+
+    // CH-1, CH-3, CH-4: a replayed chain reproduces every snapshot the
+    // writer materialized.
+    #[test]
+    fn test_chain_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let chain_dir = dir.path().join("chain");
+        let steps = [
+            index("s1", 10, &[("a", 1)]),
+            index("s2", 20, &[("a", 2), ("b", 0)]),
+            index("s3", 30, &[("b", 5)]),
+            index("s4", 40, &[("b", 5)]),
+        ];
+
+        let mut writer = ChainWriter::create(&chain_dir, steps[0].clone()).unwrap();
+        let mut expected = vec![writer.head().clone()];
+        for pair in steps.windows(2) {
+            writer
+                .append(&SnapshotDelta::diff(&pair[0], &pair[1]))
+                .unwrap();
+            expected.push(writer.head().clone());
+        }
+        assert_eq!(expected.last().unwrap(), &steps[3]);
+
+        let (manifest, snapshots) = replay_chain(&chain_dir).unwrap();
+        assert_eq!(snapshots, expected);
+        let ids: Vec<&str> = manifest.entries().map(|e| e.snapshot_id.as_str()).collect();
+        assert_eq!(ids, vec!["s1", "s2", "s3", "s4"]);
+        assert_eq!(manifest.deltas[0].dir, "deltas/000001-s2");
+    }
+
+    // CH-3: the writer refuses a delta that does not continue the
+    // head.
+    #[test]
+    fn test_chain_append_rejects_unlinked_delta() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = index("s1", 10, &[("a", 1)]);
+        let mut writer = ChainWriter::create(&dir.path().join("chain"), base).unwrap();
+        let other = index("x", 10, &[]);
+        let delta = SnapshotDelta::diff(&other, &index("s2", 20, &[]));
+        assert!(writer.append(&delta).is_err());
+        assert!(writer.manifest().deltas.is_empty());
+    }
+
+    // CH-2: a delta directory not listed in chain.json is ignored.
+    #[test]
+    fn test_unlisted_delta_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let chain_dir = dir.path().join("chain");
+        let base = index("s1", 10, &[("a", 1)]);
+        ChainWriter::create(&chain_dir, base.clone()).unwrap();
+        let delta = SnapshotDelta::diff(&base, &index("s2", 20, &[]));
+        write_delta_bundle(&chain_dir.join("deltas/000001-s2"), &delta).unwrap();
+
+        let (_, snapshots) = replay_chain(&chain_dir).unwrap();
+        assert_eq!(snapshots, vec![base]);
+    }
+}
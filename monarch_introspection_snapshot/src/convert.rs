@@ -12,7 +12,8 @@
 //! The entry point is [`convert_node`], which takes a single
 //! [`NodePayload`] and produces a [`ConvertedNode`] — a typed
 //! per-node projection that the BFS capture layer can fold into a
-//! full snapshot.
+//! full snapshot. [`restore_node`] goes the other way, for readers
+//! that replay stored snapshots.
 //!
 //! # Conversion invariants (CV-*)
 //!
@@ -35,12 +36,20 @@
 //! - **CV-7 (parent not materialized):** `convert_node` does not read
 //!   or persist `NodePayload.parent`. Parenthood in the snapshot
 //!   schema is represented only through [`ChildRow`] edges.
+//! - **CV-8 (restore is a left inverse up to unstored fields):**
+//!   [`restore_node`] rebuilds a [`NodePayload`] whose conversion
+//!   equals the input `ConvertedNode`. Fields the schema does not
+//!   store come back empty: `parent` is `None`, host memory and proc
+//!   debug stats are `Default`, and `flight_recorder` is `None`.
 
 use std::collections::HashSet;
+use std::str::FromStr;
+use std::time::Duration;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
 use anyhow::Context;
+use hyperactor::reference::ActorId;
 use hyperactor_mesh::introspect::FailureInfo;
 use hyperactor_mesh::introspect::NodePayload;
 use hyperactor_mesh::introspect::NodeProperties;
@@ -123,6 +132,21 @@ fn to_opt_micros(t: Option<SystemTime>) -> anyhow::Result<Option<i64>> {
     t.map(to_micros).transpose()
 }
 
+/// Inverse of [`to_micros`]. Negative values are rejected, matching
+/// the write side.
+fn from_micros(micros: i64) -> anyhow::Result<SystemTime> {
+    let micros = u64::try_from(micros).context("negative microseconds since epoch")?;
+    Ok(UNIX_EPOCH + Duration::from_micros(micros))
+}
+
+fn to_usize(value: i64, field: &str) -> anyhow::Result<usize> {
+    usize::try_from(value).with_context(|| format!("{field} out of range: {value}"))
+}
+
+fn to_u64(value: i64, field: &str) -> anyhow::Result<u64> {
+    u64::try_from(value).with_context(|| format!("{field} out of range: {value}"))
+}
+
 // Child classification (CV-5)
 
 /// Link-level classification sets extracted from a parent's
@@ -234,6 +258,7 @@ pub fn convert_node(snapshot_id: &str, payload: &NodePayload) -> anyhow::Result<
             stopped_retention_cap,
             is_poisoned,
             failed_actor_count,
+            generation,
             ..
         } => {
             let row = ProcNodeRow {
@@ -246,6 +271,10 @@ pub fn convert_node(snapshot_id: &str, payload: &NodePayload) -> anyhow::Result<
                 is_poisoned: *is_poisoned,
                 failed_actor_count: i64::try_from(*failed_actor_count)
                     .context("failed_actor_count overflow i64")?,
+                introspect_generation: generation
+                    .map(i64::try_from)
+                    .transpose()
+                    .context("introspect_generation overflow i64")?,
             };
             (NodeKindRow::Proc(row), None)
         }
@@ -325,6 +354,110 @@ fn convert_failure(
     })
 }
 
+// Restore (CV-8)
+
+/// Rebuild a [`NodePayload`] from its relational projection.
+///
+/// The inverse of [`convert_node`] for every stored field (CV-8).
+/// Children come back in `child_sort_key` order; the parent's
+/// `system_children` / `stopped_children` sets are rebuilt from the
+/// [`ChildRow`] link flags (CV-5).
+pub fn restore_node(converted: &ConvertedNode) -> anyhow::Result<NodePayload> {
+    let identity = NodeRef::from_str(&converted.node.node_id)
+        .with_context(|| format!("invalid node_id {:?}", converted.node.node_id))?;
+
+    let mut edges: Vec<&ChildRow> = converted.children.iter().collect();
+    edges.sort_by_key(|c| c.child_sort_key);
+    let mut children = Vec::with_capacity(edges.len());
+    let mut system_children = Vec::new();
+    let mut stopped_children = Vec::new();
+    for edge in edges {
+        let child = NodeRef::from_str(&edge.child_id)
+            .with_context(|| format!("invalid child_id {:?}", edge.child_id))?;
+        if edge.is_system {
+            system_children.push(child.clone());
+        }
+        if edge.is_stopped {
+            stopped_children.push(child.clone());
+        }
+        children.push(child);
+    }
+
+    let properties = match &converted.kind_row {
+        NodeKindRow::Root(row) => NodeProperties::Root {
+            num_hosts: to_usize(row.num_hosts, "num_hosts")?,
+            started_at: from_micros(row.started_at)?,
+            started_by: row.started_by.clone(),
+            system_children,
+        },
+        NodeKindRow::Host(row) => NodeProperties::Host {
+            addr: row.addr.clone(),
+            num_procs: to_usize(row.host_num_procs, "host_num_procs")?,
+            system_children,
+            memory: Default::default(),
+        },
+        NodeKindRow::Proc(row) => NodeProperties::Proc {
+            proc_name: row.proc_name.clone(),
+            num_actors: to_usize(row.num_actors, "num_actors")?,
+            system_children,
+            stopped_children,
+            stopped_retention_cap: to_usize(row.stopped_retention_cap, "stopped_retention_cap")?,
+            is_poisoned: row.is_poisoned,
+            failed_actor_count: to_usize(row.failed_actor_count, "failed_actor_count")?,
+            debug: Default::default(),
+            generation: row
+                .introspect_generation
+                .map(|g| to_u64(g, "introspect_generation"))
+                .transpose()?,
+        },
+        NodeKindRow::Actor(row) => NodeProperties::Actor {
+            actor_status: row.actor_status.clone(),
+            actor_type: row.actor_type.clone(),
+            messages_processed: to_u64(row.messages_processed, "messages_processed")?,
+            created_at: row.created_at.map(from_micros).transpose()?,
+            last_message_handler: row.last_message_handler.clone(),
+            total_processing_time_us: to_u64(
+                row.total_processing_time_us,
+                "total_processing_time_us",
+            )?,
+            flight_recorder: None,
+            is_system: row.is_system,
+            failure_info: converted
+                .actor_failure
+                .as_ref()
+                .map(restore_failure)
+                .transpose()?,
+        },
+        NodeKindRow::ResolutionError(row) => NodeProperties::Error {
+            code: row.error_code.clone(),
+            message: row.error_message.clone(),
+        },
+    };
+
+    Ok(NodePayload {
+        identity,
+        properties,
+        children,
+        parent: None,
+        as_of: from_micros(converted.node.as_of)?,
+    })
+}
+
+fn restore_failure(row: &ActorFailureRow) -> anyhow::Result<FailureInfo> {
+    Ok(FailureInfo {
+        error_message: row.failure_error_message.clone(),
+        root_cause_actor: ActorId::from_str(&row.failure_root_cause_actor).with_context(|| {
+            format!(
+                "invalid failure_root_cause_actor {:?}",
+                row.failure_root_cause_actor
+            )
+        })?,
+        root_cause_name: row.failure_root_cause_name.clone(),
+        occurred_at: from_micros(row.failure_occurred_at)?,
+        is_propagated: row.failure_is_propagated,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -444,6 +577,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 1,
                 debug: Default::default(),
+                generation: None,
             },
             children: vec![NodeRef::Actor(test_actor_id())],
             parent: Some(NodeRef::Host(test_host_actor_id())),
@@ -605,6 +739,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: None,
             },
             children,
             parent: Some(NodeRef::Host(test_host_actor_id())),
@@ -649,6 +784,7 @@ mod tests {
                 is_poisoned: false,
                 failed_actor_count: 0,
                 debug: Default::default(),
+                generation: None,
             },
             children: vec![NodeRef::Actor(a0), NodeRef::Actor(a1), NodeRef::Actor(a2)],
             parent: None,
@@ -712,6 +848,7 @@ mod tests {
                     is_poisoned: false,
                     failed_actor_count: 0,
                     debug: Default::default(),
+                    generation: None,
                 },
             ),
             (
@@ -822,4 +959,91 @@ mod tests {
         assert!(!host_result.children[0].is_system);
         assert!(!host_result.children[0].is_stopped);
     }
+
+    // CV-8: restore inverts conversion for every stored field,
+    // including child classification and failure detail.
+    #[test]
+    fn test_restore_round_trip() {
+        let sys = NodeRef::Actor(test_proc_id().actor_id("sys_actor", 0));
+        let stopped = NodeRef::Actor(test_proc_id().actor_id("stopped_actor", 0));
+        let proc_payload = NodePayload {
+            identity: NodeRef::Proc(test_proc_id()),
+            properties: NodeProperties::Proc {
+                proc_name: "worker".to_owned(),
+                num_actors: 2,
+                system_children: vec![sys.clone()],
+                stopped_children: vec![stopped.clone()],
+                stopped_retention_cap: 10,
+                is_poisoned: true,
+                failed_actor_count: 1,
+                debug: Default::default(),
+                generation: None,
+            },
+            children: vec![sys, stopped],
+            parent: None,
+            as_of: test_time(),
+        };
+        let actor_payload = NodePayload {
+            identity: NodeRef::Actor(test_actor_id()),
+            properties: NodeProperties::Actor {
+                actor_status: "failed: boom".to_owned(),
+                actor_type: "A".to_owned(),
+                messages_processed: 7,
+                created_at: Some(test_time()),
+                last_message_handler: Some("handle".to_owned()),
+                total_processing_time_us: 42,
+                flight_recorder: None,
+                is_system: false,
+                failure_info: Some(FailureInfo {
+                    error_message: "boom".to_owned(),
+                    root_cause_actor: test_actor_id(),
+                    root_cause_name: None,
+                    occurred_at: test_time_2(),
+                    is_propagated: false,
+                }),
+            },
+            children: vec![],
+            parent: None,
+            as_of: test_time_2(),
+        };
+
+        for payload in [proc_payload, actor_payload] {
+            let converted = convert_node("s", &payload).unwrap();
+            let restored = restore_node(&converted).unwrap();
+            assert_eq!(restored, payload);
+            assert_eq!(convert_node("s", &restored).unwrap(), converted);
+        }
+    }
+
+    // CV-8: fields the schema does not store come back empty.
+    #[test]
+    fn test_restore_drops_unstored_fields() {
+        let payload = NodePayload {
+            identity: NodeRef::Actor(test_actor_id()),
+            properties: NodeProperties::Actor {
+                actor_status: "running".to_owned(),
+                actor_type: "A".to_owned(),
+                messages_processed: 0,
+                created_at: None,
+                last_message_handler: None,
+                total_processing_time_us: 0,
+                flight_recorder: Some("recent events".to_owned()),
+                is_system: false,
+                failure_info: None,
+            },
+            children: vec![],
+            parent: Some(NodeRef::Proc(test_proc_id())),
+            as_of: test_time(),
+        };
+
+        let restored = restore_node(&convert_node("s", &payload).unwrap()).unwrap();
+        assert_eq!(restored.parent, None);
+        let NodeProperties::Actor {
+            flight_recorder, ..
+        } = restored.properties
+        else {
+            panic!("expected Actor properties");
+        };
+        assert_eq!(flight_recorder, None);
+    }
 }
@@ -0,0 +1,829 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Incremental (delta) snapshot capture.
+//!
+//! A full capture resolves every node of the mesh. For large meshes
+//! captured every second most of that work is wasted: the bulk of
+//! the topology does not change between ticks. This module captures
+//! and stores snapshots as differences against a base.
+//!
+//! - [`SnapshotIndex`] is a snapshot keyed by `node_id`: one
+//!   [`ConvertedNode`] per node, the unit of diffing.
+//! - [`SnapshotDelta`] is the difference between two indexes: the
+//!   nodes whose projection changed or that appeared (`upserts`) and
+//!   the nodes that disappeared (`removed`).
+//! - [`capture_delta`] walks the mesh like
+//!   [`capture_snapshot`](crate::capture::capture_snapshot) but
+//!   carries unchanged subtrees forward from the base without
+//!   resolving them.
+//!
+//! Proc payloads resolved from live state carry the proc's
+//! introspection generation (`ProcNodeRow::introspect_generation`),
+//! which is bumped after every change to any of the proc's actors.
+//! When a proc reports the same generation as in the base, its whole
+//! actor subtree is carried forward (DL-7). This is where the bulk of
+//! the nodes of a large mesh live. Procs without a generation (e.g.
+//! synthesized by the admin) are walked node by node, and the
+//! `reuse` predicate decides per node: [`reuse_terminal_actors`] (the
+//! default used by the service) skips actors that are stopped or
+//! failed, whose projection cannot change any more.
+//!
+//! # Delta invariants (DL-*)
+//!
+//! - **DL-1 (materialization):** For any `base` and `next`,
+//!   `SnapshotDelta::diff(base, next).apply(base)` equals `next` up
+//!   to the `as_of` of unchanged nodes (DL-2). For indexes produced
+//!   by [`capture_delta`] the equality is exact.
+//! - **DL-2 (content equality):** A node is unchanged iff its
+//!   projection equals the base projection ignoring `snapshot_id` and
+//!   `as_of`. An unchanged node keeps the base `as_of` when
+//!   materialized, so `as_of` is the time the node's current content
+//!   was first observed.
+//! - **DL-3 (reuse skips resolve):** During [`capture_delta`], a node
+//!   present in the base for which `reuse` returns `true` is not
+//!   resolved. Its base projection is carried forward and its base
+//!   children are traversed.
+//! - **DL-4 (removal):** A base node not reached by the traversal is
+//!   listed in `removed`; it is never also in `upserts`.
+//! - **DL-5 (chain consistency):** [`SnapshotDelta::apply`] errors
+//!   unless the base's `snapshot_id` equals `base_snapshot_id` and
+//!   every removed node exists in the base.
+//! - **DL-6 (restamp):** Every row of a materialized index carries the
+//!   index's own `snapshot_id`, including rows carried forward from
+//!   the base.
+//! - **DL-7 (subtree skip):** During [`capture_delta`], if a resolved
+//!   proc reports an introspection generation equal to the one
+//!   recorded for it in the base, every descendant present in the
+//!   base is carried forward as under DL-3, regardless of `reuse`.
+//!   The proc node itself is always resolved.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use anyhow::Context;
+use hyperactor_mesh::introspect::NodePayload;
+use hyperactor_mesh::introspect::NodeRef;
+
+use crate::capture::SnapshotData;
+use crate::convert::ConvertedNode;
+use crate::convert::NodeKindRow;
+use crate::convert::convert_node;
+use crate::convert::to_micros;
+use crate::schema::SnapshotRow;
+
+/// A snapshot keyed by node: one [`ConvertedNode`] per `node_id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotIndex {
+    /// Capture metadata.
+    pub snapshot: SnapshotRow,
+    /// Node projections keyed by `node_id`.
+    pub nodes: BTreeMap<String, ConvertedNode>,
+}
+
+impl SnapshotIndex {
+    /// Regroup the row families of `data` per node.
+    ///
+    /// Errors if a node has no kind-specific row, or if a subtype,
+    /// failure or child row refers to a node without a [`NodeRow`].
+    pub fn from_data(data: SnapshotData) -> anyhow::Result<Self> {
+        let mut kinds: BTreeMap<String, NodeKindRow> = BTreeMap::new();
+        let kind_rows = data
+            .root_nodes
+            .into_iter()
+            .map(|r| (r.node_id.clone(), NodeKindRow::Root(r)))
+            .chain(
+                data.host_nodes
+                    .into_iter()
+                    .map(|r| (r.node_id.clone(), NodeKindRow::Host(r))),
+            )
+            .chain(
+                data.proc_nodes
+                    .into_iter()
+                    .map(|r| (r.node_id.clone(), NodeKindRow::Proc(r))),
+            )
+            .chain(
+                data.actor_nodes
+                    .into_iter()
+                    .map(|r| (r.node_id.clone(), NodeKindRow::Actor(r))),
+            )
+            .chain(
+                data.resolution_errors
+                    .into_iter()
+                    .map(|r| (r.node_id.clone(), NodeKindRow::ResolutionError(r))),
+            );
+        for (node_id, kind_row) in kind_rows {
+            anyhow::ensure!(
+                kinds.insert(node_id.clone(), kind_row).is_none(),
+                "node {} has more than one subtype row",
+                node_id,
+            );
+        }
+
+        let mut failures: BTreeMap<String, _> = data
+            .actor_failures
+            .into_iter()
+            .map(|f| (f.node_id.clone(), f))
+            .collect();
+        let mut children: BTreeMap<String, Vec<_>> = BTreeMap::new();
+        for child in data.children {
+            children
+                .entry(child.parent_id.clone())
+                .or_default()
+                .push(child);
+        }
+
+        let mut nodes = BTreeMap::new();
+        for node in data.nodes {
+            let node_id = node.node_id.clone();
+            let kind_row = kinds
+                .remove(&node_id)
+                .with_context(|| format!("node {} has no subtype row", node_id))?;
+            let mut node_children = children.remove(&node_id).unwrap_or_default();
+            node_children.sort_by_key(|c| c.child_sort_key);
+            let converted = ConvertedNode {
+                node,
+                kind_row,
+                actor_failure: failures.remove(&node_id),
+                children: node_children,
+            };
+            anyhow::ensure!(
+                nodes.insert(node_id.clone(), converted).is_none(),
+                "duplicate node {}",
+                node_id,
+            );
+        }
+
+        let dangling = kinds
+            .keys()
+            .chain(failures.keys())
+            .chain(children.keys())
+            .next();
+        if let Some(node_id) = dangling {
+            anyhow::bail!("rows refer to node {} which has no node row", node_id);
+        }
+
+        Ok(Self {
+            snapshot: data.snapshot,
+            nodes,
+        })
+    }
+
+    /// Flatten back into row families, in `node_id` order.
+    pub fn into_data(self) -> SnapshotData {
+        let mut data = SnapshotData {
+            snapshot: self.snapshot,
+            nodes: Vec::new(),
+            children: Vec::new(),
+            root_nodes: Vec::new(),
+            host_nodes: Vec::new(),
+            proc_nodes: Vec::new(),
+            actor_nodes: Vec::new(),
+            actor_failures: Vec::new(),
+            resolution_errors: Vec::new(),
+        };
+        for converted in self.nodes.into_values() {
+            data.push_converted(converted);
+        }
+        data
+    }
+
+    /// The snapshot ID of this index.
+    pub fn snapshot_id(&self) -> &str {
+        &self.snapshot.snapshot_id
+    }
+}
+
+/// Overwrite the `snapshot_id` of every row of `node`, and its
+/// `as_of` when given (DL-2, DL-6).
+fn restamp(node: &mut ConvertedNode, snapshot_id: &str, as_of: Option<i64>) {
+    node.node.snapshot_id = snapshot_id.to_owned();
+    if let Some(as_of) = as_of {
+        node.node.as_of = as_of;
+    }
+    match &mut node.kind_row {
+        NodeKindRow::Root(r) => r.snapshot_id = snapshot_id.to_owned(),
+        NodeKindRow::Host(h) => h.snapshot_id = snapshot_id.to_owned(),
+        NodeKindRow::Proc(p) => p.snapshot_id = snapshot_id.to_owned(),
+        NodeKindRow::Actor(a) => a.snapshot_id = snapshot_id.to_owned(),
+        NodeKindRow::ResolutionError(e) => e.snapshot_id = snapshot_id.to_owned(),
+    }
+    if let Some(f) = &mut node.actor_failure {
+        f.snapshot_id = snapshot_id.to_owned();
+    }
+    for child in &mut node.children {
+        child.snapshot_id = snapshot_id.to_owned();
+    }
+}
+
+/// Whether `next` has the same content as `base` (DL-2).
+fn same_content(base: &ConvertedNode, next: &ConvertedNode) -> bool {
+    let mut restamped = base.clone();
+    restamp(
+        &mut restamped,
+        &next.node.snapshot_id,
+        Some(next.node.as_of),
+    );
+    restamped == *next
+}
+
+/// Difference between a snapshot and its base.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotDelta {
+    /// The snapshot this delta applies to.
+    pub base_snapshot_id: String,
+    /// Capture metadata of the snapshot this delta produces.
+    pub snapshot: SnapshotRow,
+    /// New or changed nodes, in `node_id` order.
+    pub upserts: Vec<ConvertedNode>,
+    /// IDs of base nodes absent from the new snapshot, in order.
+    pub removed: Vec<String>,
+}
+
+impl SnapshotDelta {
+    /// Compute the delta that turns `base` into `next` (DL-1, DL-2,
+    /// DL-4).
+    pub fn diff(base: &SnapshotIndex, next: &SnapshotIndex) -> Self {
+        let upserts = next
+            .nodes
+            .iter()
+            .filter(|(node_id, node)| {
+                base.nodes
+                    .get(*node_id)
+                    .is_none_or(|b| !same_content(b, node))
+            })
+            .map(|(_, node)| node.clone())
+            .collect();
+        let removed = base
+            .nodes
+            .keys()
+            .filter(|node_id| !next.nodes.contains_key(*node_id))
+            .cloned()
+            .collect();
+        Self {
+            base_snapshot_id: base.snapshot_id().to_owned(),
+            snapshot: next.snapshot.clone(),
+            upserts,
+            removed,
+        }
+    }
+
+    /// Materialize the snapshot this delta produces from its base
+    /// (DL-1, DL-5, DL-6).
+    pub fn apply(&self, base: &SnapshotIndex) -> anyhow::Result<SnapshotIndex> {
+        anyhow::ensure!(
+            base.snapshot_id() == self.base_snapshot_id,
+            "delta {} applies to base {}, not {}",
+            self.snapshot.snapshot_id,
+            self.base_snapshot_id,
+            base.snapshot_id(),
+        );
+        let snapshot_id = &self.snapshot.snapshot_id;
+        let mut nodes = base.nodes.clone();
+        for node_id in &self.removed {
+            anyhow::ensure!(
+                nodes.remove(node_id).is_some(),
+                "delta {} removes {} which is not in base {}",
+                snapshot_id,
+                node_id,
+                self.base_snapshot_id,
+            );
+        }
+        for node in nodes.values_mut() {
+            restamp(node, snapshot_id, None);
+        }
+        for node in &self.upserts {
+            let mut node = node.clone();
+            restamp(&mut node, snapshot_id, None);
+            nodes.insert(node.node.node_id.clone(), node);
+        }
+        Ok(SnapshotIndex {
+            snapshot: self.snapshot.clone(),
+            nodes,
+        })
+    }
+
+    /// Whether the snapshot is identical to its base.
+    pub fn is_empty(&self) -> bool {
+        self.upserts.is_empty() && self.removed.is_empty()
+    }
+
+    /// The upserted rows as a [`SnapshotData`] whose snapshot row is
+    /// the delta's own.
+    pub fn upsert_data(&self) -> SnapshotData {
+        SnapshotIndex {
+            snapshot: self.snapshot.clone(),
+            nodes: self
+                .upserts
+                .iter()
+                .map(|n| (n.node.node_id.clone(), n.clone()))
+                .collect(),
+        }
+        .into_data()
+    }
+}
+
+/// Default reuse policy for [`capture_delta`]: actors that have
+/// stopped or failed are frozen, so their base projection is reused.
+/// Only consulted for nodes outside an unchanged proc (DL-7).
+pub fn reuse_terminal_actors(node: &ConvertedNode) -> bool {
+    match &node.kind_row {
+        NodeKindRow::Actor(a) => {
+            a.actor_status.starts_with("stopped") || a.actor_status.starts_with("failed")
+        }
+        _ => false,
+    }
+}
+
+/// Whether `next` is a proc reporting the same introspection
+/// generation as `base` (DL-7).
+fn same_generation(base: &ConvertedNode, next: &ConvertedNode) -> bool {
+    match (&base.kind_row, &next.kind_row) {
+        (NodeKindRow::Proc(b), NodeKindRow::Proc(n)) => {
+            n.introspect_generation.is_some() && b.introspect_generation == n.introspect_generation
+        }
+        _ => false,
+    }
+}
+
+/// Result of [`capture_delta`].
+#[derive(Debug)]
+pub struct DeltaCapture {
+    /// The new snapshot, materialized.
+    pub index: SnapshotIndex,
+    /// The difference from the base.
+    pub delta: SnapshotDelta,
+    /// Number of nodes resolved (the rest were reused, DL-3, DL-7).
+    pub resolved: usize,
+}
+
+/// Capture a snapshot incrementally against `base`.
+///
+/// Traverses the mesh by BFS from root like
+/// [`capture_snapshot`](crate::capture::capture_snapshot) (CS-2,
+/// CS-6, CS-7 hold), except that base nodes accepted by `reuse`, and
+/// the descendants of procs whose generation is unchanged, are
+/// carried forward without resolving (DL-3, DL-7).
+pub async fn capture_delta<F, Fut, R>(
+    snapshot_id: &str,
+    base: &SnapshotIndex,
+    resolve: F,
+    reuse: R,
+) -> anyhow::Result<DeltaCapture>
+where
+    F: Fn(&NodeRef) -> Fut,
+    Fut: Future<Output = anyhow::Result<NodePayload>>,
+    R: Fn(&ConvertedNode) -> bool,
+{
+    let snapshot = SnapshotRow {
+        snapshot_id: snapshot_id.to_owned(),
+        snapshot_ts: to_micros(SystemTime::now())
+            .context("failed to compute snapshot timestamp")?,
+    };
+
+    let mut nodes = BTreeMap::new();
+    let mut resolved = 0;
+    let mut visited: HashSet<NodeRef> = HashSet::new();
+    // Each entry records whether the node lies below a proc whose
+    // generation is unchanged (DL-7).
+    let mut queue: VecDeque<(NodeRef, bool)> = VecDeque::new();
+    queue.push_back((NodeRef::Root, false));
+
+    while let Some((node_ref, frozen)) = queue.pop_front() {
+        if !visited.insert(node_ref.clone()) {
+            continue;
+        }
+
+        let node_id = node_ref.to_string();
+        let base_node = base.nodes.get(&node_id);
+        let mut converted = match base_node.filter(|n| frozen || reuse(*n)) {
+            // DL-3: carry forward, traverse base children.
+            Some(base_node) => {
+                for child in &base_node.children {
+                    let child_ref = NodeRef::from_str(&child.child_id).with_context(|| {
+                        format!("failed to parse base child id {}", child.child_id)
+                    })?;
+                    queue.push_back((child_ref, frozen));
+                }
+                base_node.clone()
+            }
+            None => {
+                let payload = resolve(&node_ref)
+                    .await
+                    .with_context(|| format!("failed to resolve {}", node_ref))?;
+                resolved += 1;
+                let converted = convert_node(snapshot_id, &payload)
+                    .with_context(|| format!("failed to convert {}", node_ref))?;
+                let unchanged = base_node.is_some_and(|b| same_generation(b, &converted));
+                for child_ref in &payload.children {
+                    queue.push_back((child_ref.clone(), unchanged));
+                }
+                converted
+            }
+        };
+        // DL-2: an unchanged node keeps its base as_of.
+        if let Some(base_node) = base.nodes.get(&node_id)
+            && same_content(base_node, &converted)
+        {
+            converted.node.as_of = base_node.node.as_of;
+        }
+        restamp(&mut converted, snapshot_id, None);
+        nodes.insert(node_id, converted);
+    }
+
+    let index = SnapshotIndex { snapshot, nodes };
+    let delta = SnapshotDelta::diff(base, &index);
+    Ok(DeltaCapture {
+        index,
+        delta,
+        resolved,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use std::time::UNIX_EPOCH;
+
+    use hyperactor::channel::ChannelAddr;
+    use hyperactor::reference::ActorId;
+    use hyperactor::reference::ProcId;
+    use hyperactor_mesh::introspect::NodeProperties;
+
+    use super::*;
+    use crate::capture::capture_snapshot;
+
+    // Fixtures: root → host → proc → {actors}. Same shapes as the
+    // capture.rs tests.
+
+    fn test_proc_id() -> ProcId {
+        ProcId::with_name(ChannelAddr::Local(0), "worker")
+    }
+
+    fn actor_id(name: &str) -> ActorId {
+        test_proc_id().actor_id(name, 0)
+    }
+
+    fn host_ref() -> NodeRef {
+        NodeRef::Host(test_proc_id().actor_id("host_agent", 0))
+    }
+
+    fn time(micros: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_micros(1_700_000_000_000_000 + micros)
+    }
+
+    fn actor_payload(name: &str, status: &str, messages: u64, at: SystemTime) -> NodePayload {
+        NodePayload {
+            identity: NodeRef::Actor(actor_id(name)),
+            properties: NodeProperties::Actor {
+                actor_status: status.to_owned(),
+                actor_type: "test".to_owned(),
+                messages_processed: messages,
+                created_at: Some(time(0)),
+                last_message_handler: None,
+                total_processing_time_us: 0,
+                flight_recorder: None,
+                is_system: false,
+                failure_info: None,
+            },
+            children: vec![],
+            parent: None,
+            as_of: at,
+        }
+    }
+
+    /// A mesh with the given `(name, status, messages)` actors on one
+    /// proc, every payload stamped `as_of = at`.
+    fn mesh(actors: &[(&str, &str, u64)], at: SystemTime) -> HashMap<NodeRef, NodePayload> {
+        let proc_ref = NodeRef::Proc(test_proc_id());
+        let actor_refs: Vec<NodeRef> = actors
+            .iter()
+            .map(|(name, _, _)| NodeRef::Actor(actor_id(name)))
+            .collect();
+        let mut map = HashMap::new();
+        map.insert(
+            NodeRef::Root,
+            NodePayload {
+                identity: NodeRef::Root,
+                properties: NodeProperties::Root {
+                    num_hosts: 1,
+                    started_at: time(0),
+                    started_by: "test".to_owned(),
+                    system_children: vec![],
+                },
+                children: vec![host_ref()],
+                parent: None,
+                as_of: at,
+            },
+        );
+        map.insert(
+            host_ref(),
+            NodePayload {
+                identity: host_ref(),
+                properties: NodeProperties::Host {
+                    addr: "10.0.0.1".to_owned(),
+                    num_procs: 1,
+                    system_children: vec![],
+                    memory: Default::default(),
+                },
+                children: vec![proc_ref.clone()],
+                parent: Some(NodeRef::Root),
+                as_of: at,
+            },
+        );
+        map.insert(
+            proc_ref.clone(),
+            NodePayload {
+                identity: proc_ref,
+                properties: NodeProperties::Proc {
+                    proc_name: "worker".to_owned(),
+                    num_actors: actors.len(),
+                    system_children: vec![],
+                    stopped_children: vec![],
+                    stopped_retention_cap: 0,
+                    is_poisoned: false,
+                    failed_actor_count: 0,
+                    debug: Default::default(),
+                    generation: None,
+                },
+                children: actor_refs,
+                parent: Some(host_ref()),
+                as_of: at,
+            },
+        );
+        for (name, status, messages) in actors {
+            map.insert(
+                NodeRef::Actor(actor_id(name)),
+                actor_payload(name, status, *messages, at),
+            );
+        }
+        map
+    }
+
+    /// Stamp the proc of a [`mesh`] with an introspection generation.
+    fn with_generation(
+        mut map: HashMap<NodeRef, NodePayload>,
+        generation: u64,
+    ) -> HashMap<NodeRef, NodePayload> {
+        let proc = map.get_mut(&NodeRef::Proc(test_proc_id())).unwrap();
+        if let NodeProperties::Proc {
+            generation: proc_generation,
+            ..
+        } = &mut proc.properties
+        {
+            *proc_generation = Some(generation);
+        }
+        map
+    }
+
+    fn recording_resolver(
+        map: HashMap<NodeRef, NodePayload>,
+        log: Arc<Mutex<Vec<NodeRef>>>,
+    ) -> impl Fn(&NodeRef) -> std::future::Ready<anyhow::Result<NodePayload>> {
+        move |node_ref: &NodeRef| {
+            log.lock().unwrap().push(node_ref.clone());
+            std::future::ready(
+                map.get(node_ref)
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("unknown ref: {}", node_ref)),
+            )
+        }
+    }
+
+    async fn full_index(id: &str, map: HashMap<NodeRef, NodePayload>) -> SnapshotIndex {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let data = capture_snapshot(id, recording_resolver(map, log))
+            .await
+            .unwrap();
+        SnapshotIndex::from_data(data).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_index_round_trip() {
+        let map = mesh(&[("a", "running", 1), ("b", "running", 2)], time(1));
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let data = capture_snapshot("s1", recording_resolver(map, log))
+            .await
+            .unwrap();
+        let index = SnapshotIndex::from_data(data.clone()).unwrap();
+        assert_eq!(index.nodes.len(), 5);
+        // Row families survive regrouping; only the order of nodes
+        // changes.
+        let back = index.clone().into_data();
+        assert_eq!(SnapshotIndex::from_data(back.clone()).unwrap(), index);
+        assert_eq!(back.children.len(), data.children.len());
+        assert_eq!(back.actor_nodes.len(), data.actor_nodes.len());
+    }
+
+    // DL-1, DL-2, DL-4: diff keeps only changed, added and removed
+    // nodes, and applying it reproduces the target.
+    #[tokio::test]
+    async fn test_diff_apply() {
+        let base = full_index(
+            "s1",
+            mesh(&[("a", "running", 1), ("b", "running", 2)], time(1)),
+        )
+        .await;
+        let next = full_index(
+            "s2",
+            mesh(&[("a", "running", 5), ("c", "running", 0)], time(2)),
+        )
+        .await;
+
+        let delta = SnapshotDelta::diff(&base, &next);
+        let upserted: Vec<&str> = delta
+            .upserts
+            .iter()
+            .map(|n| n.node.node_id.as_str())
+            .collect();
+        // a changed, c added, proc's children changed. Root and host
+        // differ only in as_of (DL-2).
+        let mut expected = vec![
+            NodeRef::Proc(test_proc_id()).to_string(),
+            NodeRef::Actor(actor_id("a")).to_string(),
+            NodeRef::Actor(actor_id("c")).to_string(),
+        ];
+        expected.sort();
+        assert_eq!(upserted, expected);
+        assert_eq!(
+            delta.removed,
+            vec![NodeRef::Actor(actor_id("b")).to_string()]
+        );
+
+        let applied = delta.apply(&base).unwrap();
+        assert_eq!(
+            applied.nodes.keys().collect::<Vec<_>>(),
+            next.nodes.keys().collect::<Vec<_>>()
+        );
+        // Unchanged nodes keep the base as_of; everything else is
+        // identical to the target.
+        for (node_id, node) in &applied.nodes {
+            assert!(same_content(node, &next.nodes[node_id]), "{}", node_id);
+            assert_eq!(node.node.snapshot_id, "s2");
+        }
+        assert_eq!(
+            applied.nodes["root"].node.as_of,
+            base.nodes["root"].node.as_of
+        );
+    }
+
+    #[tokio::test]
+    async fn test_identical_snapshots_have_empty_delta() {
+        let actors = [("a", "running", 1)];
+        let base = full_index("s1", mesh(&actors, time(1))).await;
+        let next = full_index("s2", mesh(&actors, time(2))).await;
+        let delta = SnapshotDelta::diff(&base, &next);
+        assert!(delta.is_empty());
+        assert_eq!(delta.base_snapshot_id, "s1");
+        assert_eq!(delta.snapshot.snapshot_id, "s2");
+    }
+
+    // DL-5: a delta only applies to its own base.
+    #[tokio::test]
+    async fn test_apply_rejects_wrong_base() {
+        let base = full_index("s1", mesh(&[("a", "running", 1)], time(1))).await;
+        let next = full_index("s2", mesh(&[], time(2))).await;
+        let delta = SnapshotDelta::diff(&base, &next);
+        assert!(delta.apply(&next).is_err());
+
+        let mut bad = delta.clone();
+        bad.removed.push("nope".to_owned());
+        assert!(bad.apply(&base).is_err());
+    }
+
+    // DL-3, DL-6: terminal actors are not re-resolved; the captured
+    // index is restamped and equals a full capture up to as_of.
+    #[tokio::test]
+    async fn test_capture_delta_reuses_terminal_actors() {
+        let base = full_index(
+            "s1",
+            mesh(
+                &[("live", "running", 1), ("dead", "stopped:done", 3)],
+                time(1),
+            ),
+        )
+        .await;
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let map = mesh(
+            &[("live", "running", 2), ("dead", "stopped:done", 3)],
+            time(2),
+        );
+        let capture = capture_delta(
+            "s2",
+            &base,
+            recording_resolver(map.clone(), log.clone()),
+            reuse_terminal_actors,
+        )
+        .await
+        .unwrap();
+
+        let dead_ref = NodeRef::Actor(actor_id("dead"));
+        assert!(!log.lock().unwrap().contains(&dead_ref));
+        assert_eq!(capture.resolved, 4);
+
+        // Only the live actor changed.
+        assert_eq!(capture.delta.upserts.len(), 1);
+        assert_eq!(
+            capture.delta.upserts[0].node.node_id,
+            NodeRef::Actor(actor_id("live")).to_string()
+        );
+        assert!(capture.delta.removed.is_empty());
+        assert_eq!(capture.delta.apply(&base).unwrap(), capture.index);
+
+        let dead = &capture.index.nodes[&dead_ref.to_string()];
+        assert_eq!(dead.node.snapshot_id, "s2");
+
+        let full = full_index("s2", map).await;
+        for (node_id, node) in &capture.index.nodes {
+            assert!(same_content(node, &full.nodes[node_id]), "{}", node_id);
+        }
+    }
+
+    // DL-4: a reused node whose parent no longer lists it is removed.
+    #[tokio::test]
+    async fn test_capture_delta_removes_unreachable() {
+        let base = full_index("s1", mesh(&[("dead", "failed:boom", 1)], time(1))).await;
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let capture = capture_delta(
+            "s2",
+            &base,
+            recording_resolver(mesh(&[], time(2)), log),
+            reuse_terminal_actors,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            capture.delta.removed,
+            vec![NodeRef::Actor(actor_id("dead")).to_string()]
+        );
+    }
+
+    // DL-7: a proc with an unchanged generation is resolved, but its
+    // actors are carried forward even though they are live.
+    #[tokio::test]
+    async fn test_capture_delta_skips_unchanged_procs() {
+        let actors = [("a", "running", 1), ("b", "running", 2)];
+        let base = full_index("s1", with_generation(mesh(&actors, time(1)), 7)).await;
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let map = with_generation(mesh(&actors, time(2)), 7);
+        let capture = capture_delta(
+            "s2",
+            &base,
+            recording_resolver(map, log.clone()),
+            reuse_terminal_actors,
+        )
+        .await
+        .unwrap();
+
+        let resolved = log.lock().unwrap().clone();
+        assert_eq!(
+            resolved,
+            vec![NodeRef::Root, host_ref(), NodeRef::Proc(test_proc_id())]
+        );
+        assert_eq!(capture.resolved, 3);
+        assert!(capture.delta.is_empty());
+        assert_eq!(capture.delta.apply(&base).unwrap(), capture.index);
+    }
+
+    // DL-7: a bumped generation walks the proc's actors again.
+    #[tokio::test]
+    async fn test_capture_delta_resolves_changed_procs() {
+        let base = full_index(
+            "s1",
+            with_generation(mesh(&[("a", "running", 1)], time(1)), 7),
+        )
+        .await;
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let map = with_generation(mesh(&[("a", "running", 2)], time(2)), 8);
+        let capture = capture_delta(
+            "s2",
+            &base,
+            recording_resolver(map.clone(), log.clone()),
+            reuse_terminal_actors,
+        )
+        .await
+        .unwrap();
+
+        assert!(log.lock().unwrap().contains(&NodeRef::Actor(actor_id("a"))));
+        assert_eq!(capture.resolved, 4);
+        let full = full_index("s2", map).await;
+        for (node_id, node) in &capture.index.nodes {
+            assert!(same_content(node, &full.nodes[node_id]), "{}", node_id);
+        }
+    }
+}
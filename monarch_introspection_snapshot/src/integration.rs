@@ -61,6 +61,7 @@ use crate::schema::ProcNodeRowBuffer;
 use crate::schema::ResolutionErrorRowBuffer;
 use crate::schema::RootNodeRowBuffer;
 use crate::schema::SnapshotRowBuffer;
+use crate::service::CaptureMode;
 use crate::service::CaptureSnapshot;
 use crate::service::SnapshotCaptureActor;
 
@@ -125,19 +126,27 @@ pub async fn register_snapshot_schemas(table_store: &TableStore) -> anyhow::Resu
 /// actor via `DrainAndStop`. Fire-and-forget — returns `()`.
 ///
 /// `cx` is any actor context for sending the initial
-/// `CaptureSnapshot` message to the spawned actor.
+/// `CaptureSnapshot` message to the spawned actor. `mode` selects
+/// full or incremental capture per tick.
 pub fn start_periodic_snapshots(
     cx: &impl hyperactor::context::Actor,
     table_store: TableStore,
     admin_ref: hyperactor::reference::ActorRef<MeshAdminAgent>,
     interval: Duration,
+    mode: CaptureMode,
 ) -> anyhow::Result<()> {
     anyhow::ensure!(
         !interval.is_zero(),
         "periodic capture interval must be non-zero"
     );
+    if let CaptureMode::Incremental { rebase_every, .. } = mode {
+        anyhow::ensure!(
+            rebase_every > 0,
+            "incremental capture rebase_every must be non-zero"
+        );
+    }
     let proc = cx.instance().proc();
-    let actor = SnapshotCaptureActor::new(table_store, admin_ref, interval);
+    let actor = SnapshotCaptureActor::new(table_store, admin_ref, interval, mode);
     let handle = proc.spawn("snapshot_capture", actor)?;
     // PT-3: first capture fires at spawn time.
     handle.send(cx, CaptureSnapshot)?;
@@ -18,11 +18,15 @@
 //! - [`push`] — drain `SnapshotData` into `TableStore` tables
 //! - [`service`] — `SnapshotService` capture pipeline
 //! - [`bundle`] — durable snapshot bundle export/import
+//! - [`delta`] — incremental capture and snapshot diffs
+//! - [`chain`] — delta bundles and base-plus-deltas chains
 //! - [`integration`] — wiring into live telemetry
 
 pub mod bundle;
 pub mod capture;
+pub mod chain;
 pub mod convert;
+pub mod delta;
 pub mod integration;
 pub mod push;
 pub mod schema;
@@ -11,7 +11,9 @@
 //! The shared infrastructure is [`drain_to_batches`], which converts
 //! a [`SnapshotData`] into 9 named `RecordBatch` pairs. The public
 //! entry point [`push_snapshot`] uses this to ingest all tables into
-//! a [`TableStore`].
+//! a [`TableStore`]. [`batches_to_data`] goes the other way, for
+//! consumers (delta diffing, replay) that need typed rows back from
+//! a bundle.
 //!
 //! # Push-snapshot invariants (PS-*)
 //!
@@ -32,20 +34,39 @@
 //!   adaptation.
 //! - **PS-7 (error propagation):** Drain/ingest failure returns `Err`
 //!   and does not silently skip tables.
-
+//! - **PS-8 (drain inverse):** [`batches_to_data`] is the inverse of
+//!   [`drain_to_batches`]: reading the drained batches back yields
+//!   the original rows, in the original order.
+
+use anyhow::Context;
+use datafusion::arrow::array::Array;
+use datafusion::arrow::array::AsArray;
+use datafusion::arrow::array::BooleanArray;
+use datafusion::arrow::array::Int64Array;
+use datafusion::arrow::array::StringArray;
+use datafusion::arrow::datatypes::Int64Type;
 use datafusion::arrow::record_batch::RecordBatch;
 use monarch_distributed_telemetry::database_scanner::TableStore;
 use monarch_record_batch::RecordBatchBuffer;
 
 use crate::capture::SnapshotData;
+use crate::schema::ActorFailureRow;
 use crate::schema::ActorFailureRowBuffer;
+use crate::schema::ActorNodeRow;
 use crate::schema::ActorNodeRowBuffer;
+use crate::schema::ChildRow;
 use crate::schema::ChildRowBuffer;
+use crate::schema::HostNodeRow;
 use crate::schema::HostNodeRowBuffer;
+use crate::schema::NodeRow;
 use crate::schema::NodeRowBuffer;
+use crate::schema::ProcNodeRow;
 use crate::schema::ProcNodeRowBuffer;
+use crate::schema::ResolutionErrorRow;
 use crate::schema::ResolutionErrorRowBuffer;
+use crate::schema::RootNodeRow;
 use crate::schema::RootNodeRowBuffer;
+use crate::schema::SnapshotRow;
 use crate::schema::SnapshotRowBuffer;
 
 /// Canonical table names in sorted order.
@@ -136,6 +157,273 @@ pub fn drain_to_batches(data: SnapshotData) -> anyhow::Result<Vec<NamedBatch>> {
     ])
 }
 
+/// Typed column access for one named batch, with errors that name
+/// the table and column.
+struct Columns<'a> {
+    table: &'a str,
+    batch: &'a RecordBatch,
+}
+
+impl<'a> Columns<'a> {
+    /// Find the batch for `table` in `batches`.
+    fn find(batches: &'a [NamedBatch], table: &'a str) -> anyhow::Result<Self> {
+        let batch = batches
+            .iter()
+            .find(|(n, _)| *n == table)
+            .map(|(_, b)| b)
+            .with_context(|| format!("missing {} batch", table))?;
+        Ok(Self { table, batch })
+    }
+
+    fn num_rows(&self) -> usize {
+        self.batch.num_rows()
+    }
+
+    fn utf8(&self, name: &str) -> anyhow::Result<&'a StringArray> {
+        self.batch
+            .column_by_name(name)
+            .and_then(|c| c.as_string_opt::<i32>())
+            .with_context(|| format!("{}.{} missing or not Utf8", self.table, name))
+    }
+
+    fn int64(&self, name: &str) -> anyhow::Result<&'a Int64Array> {
+        self.batch
+            .column_by_name(name)
+            .and_then(|c| c.as_primitive_opt::<Int64Type>())
+            .with_context(|| format!("{}.{} missing or not Int64", self.table, name))
+    }
+
+    fn boolean(&self, name: &str) -> anyhow::Result<&'a BooleanArray> {
+        self.batch
+            .column_by_name(name)
+            .and_then(|c| c.as_boolean_opt())
+            .with_context(|| format!("{}.{} missing or not Boolean", self.table, name))
+    }
+}
+
+/// Read a nullable column value.
+fn nullable<A: Array, T>(array: &A, i: usize, value: impl Fn(&A, usize) -> T) -> Option<T> {
+    (!array.is_null(i)).then(|| value(array, i))
+}
+
+/// Read named `RecordBatch` pairs back into a [`SnapshotData`]
+/// (PS-8).
+///
+/// `batches` must contain one batch per [`SNAPSHOT_TABLE_NAMES`]
+/// entry, and `snapshots` must hold exactly one row. Extra batches
+/// are ignored.
+pub fn batches_to_data(batches: &[NamedBatch]) -> anyhow::Result<SnapshotData> {
+    let b = Columns::find(batches, "snapshots")?;
+    anyhow::ensure!(
+        b.num_rows() == 1,
+        "snapshots batch must have exactly 1 row, found {}",
+        b.num_rows(),
+    );
+    let snapshot = SnapshotRow {
+        snapshot_id: b.utf8("snapshot_id")?.value(0).to_owned(),
+        snapshot_ts: b.int64("snapshot_ts")?.value(0),
+    };
+
+    let b = Columns::find(batches, "nodes")?;
+    let (snapshot_id, node_id, node_kind, as_of) = (
+        b.utf8("snapshot_id")?,
+        b.utf8("node_id")?,
+        b.utf8("node_kind")?,
+        b.int64("as_of")?,
+    );
+    let nodes = (0..b.num_rows())
+        .map(|i| NodeRow {
+            snapshot_id: snapshot_id.value(i).to_owned(),
+            node_id: node_id.value(i).to_owned(),
+            node_kind: node_kind.value(i).to_owned(),
+            as_of: as_of.value(i),
+        })
+        .collect();
+
+    let b = Columns::find(batches, "children")?;
+    let (snapshot_id, parent_id, child_id, child_sort_key, is_system, is_stopped) = (
+        b.utf8("snapshot_id")?,
+        b.utf8("parent_id")?,
+        b.utf8("child_id")?,
+        b.int64("child_sort_key")?,
+        b.boolean("is_system")?,
+        b.boolean("is_stopped")?,
+    );
+    let children = (0..b.num_rows())
+        .map(|i| ChildRow {
+            snapshot_id: snapshot_id.value(i).to_owned(),
+            parent_id: parent_id.value(i).to_owned(),
+            child_id: child_id.value(i).to_owned(),
+            child_sort_key: child_sort_key.value(i),
+            is_system: is_system.value(i),
+            is_stopped: is_stopped.value(i),
+        })
+        .collect();
+
+    let b = Columns::find(batches, "root_nodes")?;
+    let (snapshot_id, node_id, num_hosts, started_at, started_by) = (
+        b.utf8("snapshot_id")?,
+        b.utf8("node_id")?,
+        b.int64("num_hosts")?,
+        b.int64("started_at")?,
+        b.utf8("started_by")?,
+    );
+    let root_nodes = (0..b.num_rows())
+        .map(|i| RootNodeRow {
+            snapshot_id: snapshot_id.value(i).to_owned(),
+            node_id: node_id.value(i).to_owned(),
+            num_hosts: num_hosts.value(i),
+            started_at: started_at.value(i),
+            started_by: started_by.value(i).to_owned(),
+        })
+        .collect();
+
+    let b = Columns::find(batches, "host_nodes")?;
+    let (snapshot_id, node_id, addr, host_num_procs) = (
+        b.utf8("snapshot_id")?,
+        b.utf8("node_id")?,
+        b.utf8("addr")?,
+        b.int64("host_num_procs")?,
+    );
+    let host_nodes = (0..b.num_rows())
+        .map(|i| HostNodeRow {
+            snapshot_id: snapshot_id.value(i).to_owned(),
+            node_id: node_id.value(i).to_owned(),
+            addr: addr.value(i).to_owned(),
+            host_num_procs: host_num_procs.value(i),
+        })
+        .collect();
+
+    let b = Columns::find(batches, "proc_nodes")?;
+    let (
+        snapshot_id,
+        node_id,
+        proc_name,
+        num_actors,
+        stopped_retention_cap,
+        is_poisoned,
+        failed_actor_count,
+        introspect_generation,
+    ) = (
+        b.utf8("snapshot_id")?,
+        b.utf8("node_id")?,
+        b.utf8("proc_name")?,
+        b.int64("num_actors")?,
+        b.int64("stopped_retention_cap")?,
+        b.boolean("is_poisoned")?,
+        b.int64("failed_actor_count")?,
+        b.int64("introspect_generation")?,
+    );
+    let proc_nodes = (0..b.num_rows())
+        .map(|i| ProcNodeRow {
+            snapshot_id: snapshot_id.value(i).to_owned(),
+            node_id: node_id.value(i).to_owned(),
+            proc_name: proc_name.value(i).to_owned(),
+            num_actors: num_actors.value(i),
+            stopped_retention_cap: stopped_retention_cap.value(i),
+            is_poisoned: is_poisoned.value(i),
+            failed_actor_count: failed_actor_count.value(i),
+            introspect_generation: nullable(introspect_generation, i, |a, i| a.value(i)),
+        })
+        .collect();
+
+    let b = Columns::find(batches, "actor_nodes")?;
+    let (
+        snapshot_id,
+        node_id,
+        actor_status,
+        actor_type,
+        messages_processed,
+        created_at,
+        last_message_handler,
+        total_processing_time_us,
+        is_system,
+    ) = (
+        b.utf8("snapshot_id")?,
+        b.utf8("node_id")?,
+        b.utf8("actor_status")?,
+        b.utf8("actor_type")?,
+        b.int64("messages_processed")?,
+        b.int64("created_at")?,
+        b.utf8("last_message_handler")?,
+        b.int64("total_processing_time_us")?,
+        b.boolean("is_system")?,
+    );
+    let actor_nodes = (0..b.num_rows())
+        .map(|i| ActorNodeRow {
+            snapshot_id: snapshot_id.value(i).to_owned(),
+            node_id: node_id.value(i).to_owned(),
+            actor_status: actor_status.value(i).to_owned(),
+            actor_type: actor_type.value(i).to_owned(),
+            messages_processed: messages_processed.value(i),
+            created_at: nullable(created_at, i, |a, i| a.value(i)),
+            last_message_handler: nullable(last_message_handler, i, |a, i| a.value(i).to_owned()),
+            total_processing_time_us: total_processing_time_us.value(i),
+            is_system: is_system.value(i),
+        })
+        .collect();
+
+    let b = Columns::find(batches, "actor_failures")?;
+    let (
+        snapshot_id,
+        node_id,
+        failure_error_message,
+        failure_root_cause_actor,
+        failure_root_cause_name,
+        failure_occurred_at,
+        failure_is_propagated,
+    ) = (
+        b.utf8("snapshot_id")?,
+        b.utf8("node_id")?,
+        b.utf8("failure_error_message")?,
+        b.utf8("failure_root_cause_actor")?,
+        b.utf8("failure_root_cause_name")?,
+        b.int64("failure_occurred_at")?,
+        b.boolean("failure_is_propagated")?,
+    );
+    let actor_failures = (0..b.num_rows())
+        .map(|i| ActorFailureRow {
+            snapshot_id: snapshot_id.value(i).to_owned(),
+            node_id: node_id.value(i).to_owned(),
+            failure_error_message: failure_error_message.value(i).to_owned(),
+            failure_root_cause_actor: failure_root_cause_actor.value(i).to_owned(),
+            failure_root_cause_name: nullable(failure_root_cause_name, i, |a, i| {
+                a.value(i).to_owned()
+            }),
+            failure_occurred_at: failure_occurred_at.value(i),
+            failure_is_propagated: failure_is_propagated.value(i),
+        })
+        .collect();
+
+    let b = Columns::find(batches, "resolution_errors")?;
+    let (snapshot_id, node_id, error_code, error_message) = (
+        b.utf8("snapshot_id")?,
+        b.utf8("node_id")?,
+        b.utf8("error_code")?,
+        b.utf8("error_message")?,
+    );
+    let resolution_errors = (0..b.num_rows())
+        .map(|i| ResolutionErrorRow {
+            snapshot_id: snapshot_id.value(i).to_owned(),
+            node_id: node_id.value(i).to_owned(),
+            error_code: error_code.value(i).to_owned(),
+            error_message: error_message.value(i).to_owned(),
+        })
+        .collect();
+
+    Ok(SnapshotData {
+        snapshot,
+        nodes,
+        children,
+        root_nodes,
+        host_nodes,
+        proc_nodes,
+        actor_nodes,
+        actor_failures,
+        resolution_errors,
+    })
+}
+
 /// Drain a captured snapshot into table storage.
 ///
 /// Calls [`drain_to_batches`] and ingests each batch into its
@@ -352,6 +640,7 @@ mod tests {
                 stopped_retention_cap: 100,
                 is_poisoned: false,
                 failed_actor_count: 1,
+                introspect_generation: None,
             }],
             actor_nodes: vec![
                 ActorNodeRow {
@@ -653,4 +942,23 @@ mod tests {
         assert_eq!(counts["actor_failures"], 1);
         assert_eq!(counts["resolution_errors"], 1);
     }
+
+    // PS-8: reading drained batches back yields the original rows,
+    // including nullable columns.
+    #[test]
+    fn test_batches_to_data_round_trip() {
+        for data in [minimal_snapshot("r1"), populated_snapshot("r2")] {
+            let batches = drain_to_batches(data.clone()).unwrap();
+            assert_eq!(batches_to_data(&batches).unwrap(), data);
+        }
+    }
+
+    // PS-8: a missing table is an error, not an empty family.
+    #[test]
+    fn test_batches_to_data_missing_table() {
+        let mut batches = drain_to_batches(minimal_snapshot("r3")).unwrap();
+        batches.retain(|(n, _)| *n != "children");
+        let err = batches_to_data(&batches).unwrap_err();
+        assert!(err.to_string().contains("children"), "{}", err);
+    }
 }
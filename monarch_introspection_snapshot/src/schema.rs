@@ -21,6 +21,12 @@
 //! Timestamps are `i64` microseconds since epoch. Queries should
 //! treat ID columns as opaque join keys — do not parse them in SQL.
 //!
+//! The rows deliberately spell times as `i64` and kinds and statuses
+//! as `String`, rather than using the `SystemTime`, fieldless-enum and
+//! `Vec` columns that `RecordBatchRow` also supports: stored snapshot
+//! chains and existing queries depend on these column types (SR-2,
+//! SR-3), so changing them is a schema migration.
+//!
 //! # How to read this schema
 //!
 //! This schema stores one mesh-admin snapshot as a small relational
@@ -93,12 +99,14 @@ use monarch_record_batch::RecordBatchRow;
 // SR-3: Time and count columns (`snapshot_ts`, `as_of`, `started_at`,
 //       `created_at`, `failure_occurred_at`, `num_hosts`,
 //       `host_num_procs`, `num_actors`, `stopped_retention_cap`,
-//       `failed_actor_count`, `messages_processed`,
-//       `total_processing_time_us`, `child_sort_key`) are Arrow `Int64`.
+//       `failed_actor_count`, `introspect_generation`,
+//       `messages_processed`, `total_processing_time_us`,
+//       `child_sort_key`) are Arrow `Int64`.
 // SR-4: Flag columns (`is_system`, `is_stopped`, `is_poisoned`,
 //       `failure_is_propagated`) are Arrow `Boolean`.
 // SR-5: Optional source fields map to nullable Arrow columns; required
 //       fields are non-nullable. The optional fields are:
+//       `ProcNodeRow.introspect_generation`,
 //       `ActorNodeRow.created_at`, `ActorNodeRow.last_message_handler`,
 //       `ActorFailureRow.failure_root_cause_name`.
 // SR-6: `drain_to_record_batch()` preserves row count and empties the
@@ -203,6 +211,10 @@ pub struct ProcNodeRow {
     pub is_poisoned: bool,
     /// Number of failed actor children.
     pub failed_actor_count: i64,
+    /// Introspection generation of the proc when it was resolved.
+    /// Nullable — absent when the payload came from published attrs.
+    /// Used by incremental capture to skip unchanged subtrees.
+    pub introspect_generation: Option<i64>,
 }
 
 /// Subtype table for actor nodes. PK: `(snapshot_id, node_id)`.
@@ -275,6 +287,18 @@ pub struct ResolutionErrorRow {
     pub error_message: String,
 }
 
+/// Node removed between a delta snapshot and its base. Only present
+/// in delta bundles (see [`crate::delta`]); a full snapshot never
+/// contains tombstones. PK: `(snapshot_id, node_id)`.
+#[derive(Debug, Clone, PartialEq, RecordBatchRow)]
+pub struct RemovedNodeRow {
+    /// PK component. FK → [`SnapshotRow::snapshot_id`] of the delta.
+    pub snapshot_id: String,
+    /// PK component. Opaque key of a node present in the base but
+    /// absent from the delta's snapshot.
+    pub node_id: String,
+}
+
 #[cfg(test)]
 mod tests {
     use datafusion::arrow::array::Array;
@@ -372,7 +396,7 @@ mod tests {
     #[test]
     fn test_proc_node_row_schema() {
         let schema = ProcNodeRowBuffer::schema();
-        assert_eq!(schema.fields().len(), 7);
+        assert_eq!(schema.fields().len(), 8);
         assert_field(&schema, 0, "snapshot_id", DataType::Utf8, false);
         assert_field(&schema, 1, "node_id", DataType::Utf8, false);
         assert_field(&schema, 2, "proc_name", DataType::Utf8, false);
@@ -380,6 +404,7 @@ mod tests {
         assert_field(&schema, 4, "stopped_retention_cap", DataType::Int64, false);
         assert_field(&schema, 5, "is_poisoned", DataType::Boolean, false);
         assert_field(&schema, 6, "failed_actor_count", DataType::Int64, false);
+        assert_field(&schema, 7, "introspect_generation", DataType::Int64, true);
     }
 
     // Verifies SR-1, SR-2, SR-3, SR-4, SR-5.
@@ -443,6 +468,15 @@ mod tests {
         assert_field(&schema, 3, "error_message", DataType::Utf8, false);
     }
 
+    // Verifies SR-1, SR-2, SR-5.
+    #[test]
+    fn test_removed_node_row_schema() {
+        let schema = RemovedNodeRowBuffer::schema();
+        assert_eq!(schema.fields().len(), 2);
+        assert_field(&schema, 0, "snapshot_id", DataType::Utf8, false);
+        assert_field(&schema, 1, "node_id", DataType::Utf8, false);
+    }
+
     // Drain round-trip tests (SR-5, SR-6)
 
     // Verifies SR-6.
@@ -594,6 +628,7 @@ mod tests {
             stopped_retention_cap: 100,
             is_poisoned: false,
             failed_actor_count: 1,
+            introspect_generation: Some(7),
         });
         let batch = buf.drain_to_record_batch().unwrap();
         assert_eq!(batch.num_rows(), 1);
@@ -55,15 +55,23 @@
 //!     table_store,
 //!     admin_ref,
 //!     Duration::from_secs(30),
+//!     CaptureMode::Full,
 //! );
 //! proc.spawn("snapshot_capture", actor)?;
 //! // Actor is stopped by framework lifecycle on proc teardown.
 //! ```
 //!
-//! The actor reuses the same capture pipeline but is live-ingest only
+//! The actor reuses the same capture pipeline with live ingest
 //! (`export_root` is always `None`). Overlapping ticks are skipped,
 //! not queued. Capture errors are logged and do not stop the timer.
 //!
+//! **Incremental capture** — [`SnapshotService::capture_incremental`]
+//! captures a delta against the previous incremental capture (see
+//! [`crate::delta`]) and, given a `chain_root`, appends it to a
+//! snapshot chain (see [`crate::chain`]). The periodic actor uses it
+//! when constructed with [`CaptureMode::Incremental`], writing
+//! base-plus-delta chains when the mode has a `chain_root`.
+//!
 //! # Service invariants (SV-*)
 //!
 //! - **SV-1 (sink required):** `capture` returns `Err` when both
@@ -90,6 +98,27 @@
 //!   succeeds and bundle writing fails (or vice versa), you have
 //!   partial success. The sinks are independent and the service
 //!   reports the error.
+//! - **SV-8 (incremental materialization):** `capture_incremental`
+//!   ingests the full materialized snapshot into `table_store`, so
+//!   SQL consumers see the same tables as for a full capture (up to
+//!   the `as_of` of unchanged nodes, DL-2).
+//! - **SV-9 (full base):** Without a previous incremental capture (the
+//!   first call, or after `reset_incremental`) `capture_incremental`
+//!   performs a full capture, which becomes the base of later deltas.
+//! - **SV-10 (chain continuity):** With `chain_root`, each delta is
+//!   appended to the chain opened under that root. A new chain
+//!   `{chain_root}/chain-{snapshot_id}/` is started from the full
+//!   materialized snapshot when no chain is open under that root. A
+//!   capture without `chain_root` closes the open chain, so chains
+//!   never have gaps.
+//! - **SV-11 (chain before ingest):** `capture_incremental` appends
+//!   to the chain and advances the head together, before ingesting
+//!   into `table_store`. A capture that fails before that point
+//!   (traversal or chain write) leaves the previous head and chain
+//!   untouched and ingests nothing. An ingest failure afterwards is
+//!   reported, but the chain and head keep the snapshot, so later
+//!   deltas stay consistent with the chain; the store misses only
+//!   that snapshot (later ones are ingested in full, SV-8).
 //!
 //! # Periodic-trigger invariants (PT-*)
 //!
@@ -112,12 +141,20 @@
 //!   `test_pt5_drain_halts_future_captures`.
 //! - **PT-6 (failure resilience):** Capture `Err` logged, loop
 //!   continues.
-//! - **PT-7 (live-ingest only):** Always `export_root = None`.
+//! - **PT-7 (live ingest):** Always `export_root = None`. In
+//!   incremental mode `chain_root` is the one configured on the
+//!   [`CaptureMode`], so each rebase starts a new chain (SV-9,
+//!   SV-10).
+//! - **PT-8 (bounded drift):** In [`CaptureMode::Incremental`], every
+//!   `rebase_every`-th tick after a full capture is a full capture
+//!   again, bounding how long reused (unresolved) projections are
+//!   carried forward.
 
 use std::future::Future;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
@@ -142,6 +179,11 @@ use uuid::Uuid;
 use crate::bundle::write_bundle;
 use crate::capture::SnapshotData;
 use crate::capture::capture_snapshot;
+use crate::chain::ChainWriter;
+use crate::delta::SnapshotDelta;
+use crate::delta::SnapshotIndex;
+use crate::delta::capture_delta;
+use crate::delta::reuse_terminal_actors;
 use crate::push::drain_to_batches;
 
 /// Snapshot capture service.
@@ -156,6 +198,19 @@ pub struct SnapshotService {
     /// Overlap guard for the periodic trigger. CAS to acquire, reset
     /// on completion.
     in_flight: Arc<AtomicBool>,
+    /// Head and open chain of incremental capture.
+    incremental: Arc<Mutex<IncrementalState>>,
+}
+
+/// State carried between incremental captures.
+#[derive(Default)]
+struct IncrementalState {
+    /// The last materialized snapshot; deltas are taken against it.
+    head: Option<SnapshotIndex>,
+    /// Deltas captured since the last full capture.
+    deltas_since_base: usize,
+    /// The chain deltas are appended to (SV-10).
+    chain: Option<ChainWriter>,
 }
 
 impl SnapshotService {
@@ -168,6 +223,7 @@ impl SnapshotService {
         Self {
             table_store,
             in_flight: Arc::new(AtomicBool::new(false)),
+            incremental: Arc::new(Mutex::new(IncrementalState::default())),
         }
     }
 
@@ -232,8 +288,170 @@ impl SnapshotService {
             node_counts,
             capture_duration_ms: t0.elapsed().as_secs_f64() * 1000.0,
             bundle_path,
+            delta: None,
         })
     }
+
+    /// Capture a mesh snapshot incrementally and publish to configured
+    /// sinks.
+    ///
+    /// Captures a delta against the previous incremental capture,
+    /// reusing the projections of stopped and failed actors without
+    /// resolving them (DL-3). The first call, and the first call after
+    /// [`reset_incremental`](Self::reset_incremental), is a full
+    /// capture (SV-9).
+    ///
+    /// - If `self.table_store` is `Some`, the full materialized
+    ///   snapshot is ingested into live storage (SV-8).
+    /// - If `chain_root` is `Some`, the delta is appended to a chain
+    ///   under `chain_root` (SV-10); `bundle_path` in the result is
+    ///   the chain directory.
+    ///
+    /// At least one sink must be active (SV-1).
+    pub async fn capture_incremental<F, Fut>(
+        &self,
+        resolve: F,
+        chain_root: Option<&Path>,
+    ) -> anyhow::Result<CaptureResult>
+    where
+        F: Fn(&NodeRef) -> Fut,
+        Fut: Future<Output = anyhow::Result<NodePayload>>,
+    {
+        if self.table_store.is_none() && chain_root.is_none() {
+            anyhow::bail!(
+                "snapshot capture requires at least one active sink \
+                 (table_store or chain_root)"
+            );
+        }
+
+        let snapshot_id = Uuid::new_v4().to_string();
+        let t0 = Instant::now();
+        // Clone the head out; the lock is not held across the
+        // traversal.
+        let head = self.lock_incremental()?.head.clone();
+        let (index, delta) = match head {
+            Some(base) => {
+                let capture =
+                    capture_delta(&snapshot_id, &base, &resolve, reuse_terminal_actors).await?;
+                let summary = DeltaSummary {
+                    base_snapshot_id: capture.delta.base_snapshot_id.clone(),
+                    upserted_nodes: capture.delta.upserts.len(),
+                    removed_nodes: capture.delta.removed.len(),
+                    resolved_nodes: capture.resolved,
+                };
+                (capture.index, Some((capture.delta, summary)))
+            }
+            None => (
+                SnapshotIndex::from_data(capture_snapshot(&snapshot_id, &resolve).await?)?,
+                None,
+            ),
+        };
+        let data = index.clone().into_data();
+        let node_counts = NodeCounts::from_data(&data);
+        let snapshot_ts = data.snapshot.snapshot_ts;
+        let batches = drain_to_batches(data)?;
+
+        // SV-11: write the chain and advance the head before
+        // ingesting, so a chain failure leaves no trace in the store.
+        let bundle_path = self.advance_incremental(&snapshot_id, index, &delta, chain_root)?;
+
+        // SV-8: live storage always sees the full snapshot.
+        if let Some(ref store) = self.table_store {
+            for (name, batch) in batches {
+                store.ingest_batch(name, batch).await?;
+            }
+        }
+
+        Ok(CaptureResult {
+            snapshot_id,
+            snapshot_ts,
+            node_counts,
+            capture_duration_ms: t0.elapsed().as_secs_f64() * 1000.0,
+            bundle_path,
+            delta: delta.map(|(_, summary)| summary),
+        })
+    }
+
+    /// Append `index` to the chain under `chain_root` and make it the
+    /// head. Returns the chain directory, if any (SV-10, SV-11).
+    fn advance_incremental(
+        &self,
+        snapshot_id: &str,
+        index: SnapshotIndex,
+        delta: &Option<(SnapshotDelta, DeltaSummary)>,
+        chain_root: Option<&Path>,
+    ) -> anyhow::Result<Option<PathBuf>> {
+        let mut state = self.lock_incremental()?;
+        // SV-10: append to the open chain under chain_root, or start
+        // a new one.
+        let bundle_path = match chain_root {
+            Some(root) => {
+                let open = state
+                    .chain
+                    .as_mut()
+                    .filter(|chain| chain.dir().parent() == Some(root));
+                let appended = match (open, delta) {
+                    (Some(chain), Some((delta, _))) => {
+                        chain.append(delta)?;
+                        true
+                    }
+                    _ => false,
+                };
+                if !appended {
+                    let dir = root.join(format!("chain-{}", snapshot_id));
+                    state.chain = Some(ChainWriter::create(&dir, index.clone())?);
+                }
+                state.chain.as_ref().map(|chain| chain.dir().to_owned())
+            }
+            None => {
+                state.chain = None;
+                None
+            }
+        };
+        state.deltas_since_base = match delta {
+            Some(_) => state.deltas_since_base + 1,
+            None => 0,
+        };
+        state.head = Some(index);
+        Ok(bundle_path)
+    }
+
+    /// Drop the incremental head and close the open chain. The next
+    /// [`capture_incremental`](Self::capture_incremental) is a full
+    /// capture (SV-9).
+    pub fn reset_incremental(&self) -> anyhow::Result<()> {
+        *self.lock_incremental()? = IncrementalState::default();
+        Ok(())
+    }
+
+    /// Number of deltas captured since the last full incremental
+    /// capture.
+    pub fn deltas_since_base(&self) -> anyhow::Result<usize> {
+        Ok(self.lock_incremental()?.deltas_since_base)
+    }
+
+    fn lock_incremental(&self) -> anyhow::Result<std::sync::MutexGuard<'_, IncrementalState>> {
+        self.incremental
+            .lock()
+            .map_err(|_| anyhow::anyhow!("incremental capture state poisoned"))
+    }
+}
+
+/// How the periodic trigger captures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureMode {
+    /// Every tick is a full BFS capture.
+    Full,
+    /// Ticks capture deltas against the previous tick. Every
+    /// `rebase_every`-th tick after a full capture is full again
+    /// (PT-8).
+    Incremental {
+        /// Deltas between full captures. Must be non-zero.
+        rebase_every: usize,
+        /// When set, ticks also write base-plus-delta chains under
+        /// this directory, one chain per rebase (PT-7).
+        chain_root: Option<PathBuf>,
+    },
 }
 
 /// Private drop guard that resets `in_flight` to `false` on all exit
@@ -249,13 +467,18 @@ impl Drop for InFlightGuard<'_> {
 /// Execute one periodic capture tick.
 ///
 /// Owns the CAS overlap check (PT-4), drop guard, capture call
-/// (PT-7: always `export_root = None`), and error logging (PT-6).
+/// (PT-7: always `export_root = None`), rebase scheduling (PT-8), and
+/// error logging (PT-6).
 /// Returns `true` if a capture was attempted (CAS succeeded),
 /// `false` if skipped due to overlap.
 ///
 /// This is the unit of per-tick behavior, factored out of the
 /// spawned timer loop so it can be tested deterministically.
-async fn run_periodic_tick<F, Fut>(service: &SnapshotService, resolve: F) -> bool
+async fn run_periodic_tick<F, Fut>(
+    service: &SnapshotService,
+    resolve: F,
+    mode: &CaptureMode,
+) -> bool
 where
     F: Fn(&NodeRef) -> Fut,
     Fut: Future<Output = anyhow::Result<NodePayload>>,
@@ -271,8 +494,28 @@ where
     }
     let _guard = InFlightGuard(&service.in_flight);
 
-    // PT-7: always None for export_root.
-    match service.capture(resolve, None).await {
+    // PT-7: export_root is always None; chain_root comes from the
+    // mode.
+    let result = match mode {
+        CaptureMode::Full => service.capture(resolve, None).await,
+        CaptureMode::Incremental {
+            rebase_every,
+            chain_root,
+        } => {
+            // PT-8: periodically start over from a full capture.
+            if service
+                .deltas_since_base()
+                .is_ok_and(|n| n >= *rebase_every)
+                && let Err(e) = service.reset_incremental()
+            {
+                tracing::warn!("periodic capture rebase failed: {:#}", e);
+            }
+            service
+                .capture_incremental(resolve, chain_root.as_deref())
+                .await
+        }
+    };
+    match result {
         Ok(result) => {
             let c = &result.node_counts;
             let short_id = &result.snapshot_id[..6];
@@ -285,6 +528,16 @@ where
                     result.capture_duration_ms,
                     short_id,
                 );
+            } else if let Some(d) = &result.delta {
+                tracing::info!(
+                    "capture ok: {} nodes, delta +{} -{} ({} resolved) in {:.0}ms [snap_id={}]",
+                    c.nodes,
+                    d.upserted_nodes,
+                    d.removed_nodes,
+                    d.resolved_nodes,
+                    result.capture_duration_ms,
+                    short_id,
+                );
             } else {
                 tracing::info!(
                     "capture ok: {} nodes ({} hosts, {} procs, {} actors) in {:.0}ms [snap_id={}]",
@@ -325,6 +578,8 @@ pub struct SnapshotCaptureActor {
     /// Delay between periodic capture ticks after the initial
     /// immediate fire.
     interval: Duration,
+    /// Full or incremental capture per tick.
+    mode: CaptureMode,
 }
 
 #[async_trait]
@@ -351,7 +606,7 @@ impl Handler<CaptureSnapshot> for SnapshotCaptureActor {
                 resp.0.map_err(|e| anyhow::anyhow!("{}", e))
             }
         };
-        run_periodic_tick(&self.service, resolve).await;
+        run_periodic_tick(&self.service, resolve, &self.mode).await;
 
         // Reschedule. If the actor is stopping, this spawns a
         // detached task whose eventual port.send() fails harmlessly.
@@ -369,11 +624,13 @@ impl SnapshotCaptureActor {
         table_store: TableStore,
         admin_ref: hyperactor_reference::ActorRef<MeshAdminAgent>,
         interval: Duration,
+        mode: CaptureMode,
     ) -> Self {
         Self {
             service: SnapshotService::new(Some(table_store)),
             admin_ref,
             interval,
+            mode,
         }
     }
 }
@@ -390,8 +647,25 @@ pub struct CaptureResult {
     /// Wall-clock capture duration in milliseconds.
     pub capture_duration_ms: f64,
     /// Filesystem path to the bundle directory, if a bundle was
-    /// exported. `None` when `export_root` was not provided.
+    /// exported. `None` when `export_root` was not provided. For
+    /// incremental captures, the chain directory.
     pub bundle_path: Option<PathBuf>,
+    /// Delta statistics for incremental captures taken against a
+    /// previous snapshot. `None` for full captures.
+    pub delta: Option<DeltaSummary>,
+}
+
+/// Summary of an incremental capture.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DeltaSummary {
+    /// The snapshot the delta was taken against.
+    pub base_snapshot_id: String,
+    /// Nodes added or changed.
+    pub upserted_nodes: usize,
+    /// Nodes removed.
+    pub removed_nodes: usize,
+    /// Nodes resolved during the capture; the rest were reused.
+    pub resolved_nodes: usize,
 }
 
 /// Summary counts of entities in a captured snapshot.
@@ -523,6 +797,7 @@ mod tests {
                     is_poisoned: false,
                     failed_actor_count: 0,
                     debug: Default::default(),
+                    generation: None,
                 },
                 children: vec![actor_ref.clone()],
                 parent: Some(host_ref),
@@ -698,6 +973,7 @@ mod tests {
             },
             capture_duration_ms: 42.5,
             bundle_path: None,
+            delta: None,
         };
         let json = serde_json::to_string(&result).unwrap();
         assert!(json.contains("\"snapshot_id\":\"test-snap\""));
@@ -832,7 +1108,7 @@ mod tests {
         // Pre-set in_flight to simulate an ongoing capture.
         service.in_flight.store(true, Ordering::Release);
 
-        let attempted = run_periodic_tick(&service, resolve).await;
+        let attempted = run_periodic_tick(&service, resolve, &CaptureMode::Full).await;
         assert!(!attempted, "PT-4: tick should be skipped when in_flight");
 
         // Store should be empty — no capture ran.
@@ -850,7 +1126,7 @@ mod tests {
         let payloads = minimal_mesh_payloads();
         let resolve = stub_resolver(payloads);
 
-        let attempted = run_periodic_tick(&service, resolve).await;
+        let attempted = run_periodic_tick(&service, resolve, &CaptureMode::Full).await;
         assert!(attempted, "PT-4: tick should attempt capture");
 
         // Store should be populated.
@@ -869,7 +1145,7 @@ mod tests {
 
         let resolve = |_: &NodeRef| std::future::ready(Err(anyhow::anyhow!("simulated failure")));
 
-        let attempted = run_periodic_tick(&service, resolve).await;
+        let attempted = run_periodic_tick(&service, resolve, &CaptureMode::Full).await;
         assert!(
             attempted,
             "PT-6: tick should attempt capture even if it fails",
@@ -885,7 +1161,7 @@ mod tests {
         // after an earlier failure.
         let payloads = minimal_mesh_payloads();
         let resolve = stub_resolver(payloads);
-        let attempted = run_periodic_tick(&service, resolve).await;
+        let attempted = run_periodic_tick(&service, resolve, &CaptureMode::Full).await;
         assert!(attempted, "PT-6: second tick should succeed");
         assert_eq!(store.table_names().unwrap().len(), 9);
     }
@@ -898,7 +1174,7 @@ mod tests {
         let payloads = minimal_mesh_payloads();
         let resolve = stub_resolver(payloads);
 
-        run_periodic_tick(&service, resolve).await;
+        run_periodic_tick(&service, resolve, &CaptureMode::Full).await;
 
         // Verify store was populated (live ingest happened).
         assert_eq!(store.table_names().unwrap().len(), 9);
@@ -907,4 +1183,166 @@ mod tests {
         // service.capture(resolve, None). No bundle directory
         // was created.
     }
+
+    // --- Incremental capture (SV-8..SV-11, PT-8) ---
+
+    // SV-8, SV-9: the first incremental capture is full; the second
+    // is a delta against it, and both are fully materialized in the
+    // store.
+    #[tokio::test]
+    async fn test_capture_incremental_full_then_delta() {
+        let store = TableStore::new_empty();
+        let service = SnapshotService::new(Some(store.clone()));
+
+        let first = service
+            .capture_incremental(stub_resolver(minimal_mesh_payloads()), None)
+            .await
+            .unwrap();
+        assert!(first.delta.is_none(), "SV-9: first capture is full");
+
+        let second = service
+            .capture_incremental(stub_resolver(minimal_mesh_payloads()), None)
+            .await
+            .unwrap();
+        let delta = second.delta.as_ref().expect("second capture is a delta");
+        assert_eq!(delta.base_snapshot_id, first.snapshot_id);
+        assert_eq!(delta.removed_nodes, 0);
+        assert_eq!(second.node_counts, first.node_counts);
+        assert_eq!(service.deltas_since_base().unwrap(), 1);
+
+        // SV-8: the store holds both snapshots in full.
+        let ctx = datafusion::prelude::SessionContext::new();
+        ctx.register_table("nodes", store.table_provider("nodes").unwrap().unwrap())
+            .unwrap();
+        let batches = ctx
+            .sql(&format!(
+                "SELECT node_id FROM nodes WHERE snapshot_id = '{}'",
+                second.snapshot_id
+            ))
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(rows, 4);
+
+        service.reset_incremental().unwrap();
+        let third = service
+            .capture_incremental(stub_resolver(minimal_mesh_payloads()), None)
+            .await
+            .unwrap();
+        assert!(third.delta.is_none(), "SV-9: capture after reset is full");
+    }
+
+    // SV-10: captures with the same chain_root append to one chain;
+    // a capture without it closes the chain.
+    #[tokio::test]
+    async fn test_capture_incremental_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = SnapshotService::new(None);
+
+        let first = service
+            .capture_incremental(stub_resolver(minimal_mesh_payloads()), Some(dir.path()))
+            .await
+            .unwrap();
+        let chain_dir = first.bundle_path.clone().unwrap();
+        assert_eq!(
+            chain_dir.file_name().unwrap().to_str().unwrap(),
+            format!("chain-{}", first.snapshot_id),
+        );
+        let second = service
+            .capture_incremental(stub_resolver(minimal_mesh_payloads()), Some(dir.path()))
+            .await
+            .unwrap();
+        assert_eq!(second.bundle_path.as_ref(), Some(&chain_dir));
+
+        let (manifest, snapshots) = crate::chain::replay_chain(&chain_dir).unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(manifest.deltas[0].snapshot_id, second.snapshot_id);
+
+        // SV-1 applies: no store and no chain_root is an error, and
+        // SV-11: the failure leaves the open chain in place.
+        assert!(
+            service
+                .capture_incremental(stub_resolver(minimal_mesh_payloads()), None)
+                .await
+                .is_err()
+        );
+        let third = service
+            .capture_incremental(stub_resolver(minimal_mesh_payloads()), Some(dir.path()))
+            .await
+            .unwrap();
+        assert_eq!(third.bundle_path.as_ref(), Some(&chain_dir));
+    }
+
+    // PT-8: incremental ticks rebase after rebase_every deltas.
+    #[tokio::test]
+    async fn test_periodic_tick_incremental_rebase() {
+        let store = TableStore::new_empty();
+        let service = SnapshotService::new(Some(store.clone()));
+        let mode = CaptureMode::Incremental {
+            rebase_every: 2,
+            chain_root: None,
+        };
+
+        let mut deltas = Vec::new();
+        for _ in 0..5 {
+            run_periodic_tick(&service, stub_resolver(minimal_mesh_payloads()), &mode).await;
+            deltas.push(service.deltas_since_base().unwrap());
+        }
+        assert_eq!(deltas, vec![0, 1, 2, 0, 1]);
+    }
+
+    // PT-7, SV-10: incremental ticks with a chain_root write one
+    // base-plus-delta chain per rebase.
+    #[tokio::test]
+    async fn test_periodic_tick_incremental_chains() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = SnapshotService::new(Some(TableStore::new_empty()));
+        let mode = CaptureMode::Incremental {
+            rebase_every: 2,
+            chain_root: Some(dir.path().to_owned()),
+        };
+
+        for _ in 0..5 {
+            run_periodic_tick(&service, stub_resolver(minimal_mesh_payloads()), &mode).await;
+        }
+
+        let mut lengths: Vec<usize> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| {
+                let (_, snapshots) = crate::chain::replay_chain(&entry.unwrap().path()).unwrap();
+                snapshots.len()
+            })
+            .collect();
+        lengths.sort();
+        assert_eq!(lengths, vec![2, 3]);
+    }
+
+    // SV-11: a chain write failure ingests nothing and keeps the head.
+    #[tokio::test]
+    async fn test_capture_incremental_chain_failure_ingests_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        // A file where the chain directory's parent should be.
+        let not_a_dir = dir.path().join("file");
+        std::fs::write(&not_a_dir, b"").unwrap();
+        let store = TableStore::new_empty();
+        let service = SnapshotService::new(Some(store.clone()));
+
+        assert!(
+            service
+                .capture_incremental(stub_resolver(minimal_mesh_payloads()), Some(&not_a_dir))
+                .await
+                .is_err()
+        );
+        assert!(store.table_names().unwrap().is_empty());
+
+        let next = service
+            .capture_incremental(stub_resolver(minimal_mesh_payloads()), None)
+            .await
+            .unwrap();
+        assert!(next.delta.is_none(), "SV-11: the head was not advanced");
+        assert_eq!(store.table_names().unwrap().len(), 9);
+    }
 }
@@ -39,6 +39,7 @@ use monarch_introspection_snapshot::capture::capture_snapshot;
 use monarch_introspection_snapshot::integration::register_snapshot_schemas;
 use monarch_introspection_snapshot::integration::start_periodic_snapshots;
 use monarch_introspection_snapshot::push::push_snapshot;
+use monarch_introspection_snapshot::service::CaptureMode;
 use ndslice::extent;
 use ndslice::view::Ranked;
 
@@ -371,7 +372,13 @@ async fn test_pt1_rejects_zero_interval() -> Result<()> {
     let admin_ref = spawn_admin([&host_mesh], &instance, Some("[::]:0".parse()?), None).await?;
     let table_store = TableStore::new_empty();
 
-    let err = start_periodic_snapshots(&instance, table_store, admin_ref.clone(), Duration::ZERO);
+    let err = start_periodic_snapshots(
+        &instance,
+        table_store,
+        admin_ref.clone(),
+        Duration::ZERO,
+        CaptureMode::Full,
+    );
     assert!(err.is_err(), "PT-1: zero interval must be rejected");
     assert!(
         err.unwrap_err().to_string().contains("non-zero"),
@@ -400,6 +407,7 @@ async fn test_pt3_immediate_first_capture() -> Result<()> {
         table_store.clone(),
         admin_ref.clone(),
         Duration::from_secs(600),
+        CaptureMode::Full,
     )?;
 
     // Give the immediate capture time to complete.
@@ -445,6 +453,7 @@ async fn test_pt5_drain_halts_future_captures() -> Result<()> {
         table_store.clone(),
         admin_ref.clone(),
         Duration::from_millis(200),
+        CaptureMode::Full,
     )?;
 
     // Let a few captures run.
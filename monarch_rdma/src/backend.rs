@@ -11,10 +11,12 @@
 #[cfg(test)]
 pub(crate) mod cuda_test_utils;
 pub mod ibverbs;
+pub mod shm;
 pub mod tcp;
 
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::sync::OnceLock;
 use std::time::Duration;
 
 use anyhow::Result;
@@ -32,8 +34,9 @@ use crate::RdmaTransportLevel;
 /// Each variant holds the information needed to perform RDMA operations
 /// using that backend on a particular buffer.
 ///
-/// The [`OnceCell`] is lazily populated at runtime and excluded from
-/// serialization; deserializing produces an empty cell.
+/// The [`OnceCell`] and [`OnceLock`] are lazily populated at runtime
+/// and excluded from serialization; deserializing produces empty
+/// cells.
 #[derive(Debug, Clone)]
 pub enum RdmaRemoteBackendContext {
     Ibverbs(
@@ -41,6 +44,9 @@ pub enum RdmaRemoteBackendContext {
         Arc<OnceCell<ibverbs::IbvBuffer>>,
     ),
     Tcp(reference::ActorRef<tcp::manager_actor::TcpManagerActor>),
+    /// The cell caches whether the buffer is reachable from this
+    /// process, as decided by [`shm::ShmBackend::connect`].
+    SharedMemory(shm::ShmBuffer, Arc<OnceLock<Option<shm::ShmBackend>>>),
 }
 
 impl Serialize for RdmaRemoteBackendContext {
@@ -54,6 +60,8 @@ impl Serialize for RdmaRemoteBackendContext {
                 "Tcp",
                 actor_ref,
             ),
+            RdmaRemoteBackendContext::SharedMemory(buffer, _) => serializer
+                .serialize_newtype_variant("RdmaRemoteBackendContext", 2, "SharedMemory", buffer),
         }
     }
 }
@@ -65,6 +73,7 @@ impl<'de> Deserialize<'de> for RdmaRemoteBackendContext {
         enum Repr {
             Ibverbs(reference::ActorRef<ibverbs::manager_actor::IbvManagerActor>),
             Tcp(reference::ActorRef<tcp::manager_actor::TcpManagerActor>),
+            SharedMemory(shm::ShmBuffer),
         }
 
         match Repr::deserialize(deserializer)? {
@@ -73,6 +82,10 @@ impl<'de> Deserialize<'de> for RdmaRemoteBackendContext {
                 Arc::new(OnceCell::new()),
             )),
             Repr::Tcp(actor_ref) => Ok(RdmaRemoteBackendContext::Tcp(actor_ref)),
+            Repr::SharedMemory(buffer) => Ok(RdmaRemoteBackendContext::SharedMemory(
+                buffer,
+                Arc::new(OnceLock::new()),
+            )),
         }
     }
 }
@@ -32,6 +32,7 @@ mod mlx5dv_tests;
 #[cfg(test)]
 mod test_utils;
 
+use crate::RdmaOpLayout;
 use crate::RdmaOpType;
 use crate::local_memory::RdmaLocalMemory;
 
@@ -57,4 +58,5 @@ pub struct IbvOp {
     pub local_memory: Arc<dyn RdmaLocalMemory>,
     pub remote_buffer: IbvBuffer,
     pub remote_manager: reference::ActorRef<IbvManagerActor>,
+    pub layout: RdmaOpLayout,
 }
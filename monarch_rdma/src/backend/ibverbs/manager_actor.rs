@@ -63,6 +63,11 @@ use crate::rdma_manager_actor::RdmaManagerMessageClient;
 use crate::rdma_manager_actor::get_rdmaxcel_error_message;
 use crate::validate_execution_context;
 
+/// Maximum number of segments posted to a queue pair before waiting for
+/// their completions, keeping scatter-gather ops within the default
+/// send queue depth.
+const MAX_POSTED_SEGMENTS: usize = 256;
+
 /// Messages handled by [`IbvManagerActor`].
 #[derive(Handler, HandleClient, RefClient, Debug, Serialize, Deserialize, Named)]
 pub enum IbvManagerMessage {
@@ -1134,13 +1139,43 @@ impl IbvBackend {
                 .await?
                 .map_err(|e| anyhow::anyhow!(e))?;
 
-            let wr_id = match op.op_type {
-                RdmaOpType::WriteFromLocal => qp.put(local_buffer.clone(), op.remote_buffer)?,
-                RdmaOpType::ReadIntoLocal => qp.get(local_buffer.clone(), op.remote_buffer)?,
-            };
+            // All segments share the local MR and the queue pair; each
+            // becomes one work request (or several, for segments over
+            // the maximum message size).
+            let segments = op
+                .layout
+                .resolve(op.local_memory.size(), op.remote_buffer.size)?;
+            let deadline = Instant::now() + timeout;
+            for window in segments.chunks(MAX_POSTED_SEGMENTS) {
+                let mut wr_ids = Vec::new();
+                for segment in window {
+                    let local = IbvBuffer {
+                        addr: local_buffer.addr + segment.local_offset,
+                        size: segment.len,
+                        ..local_buffer.clone()
+                    };
+                    let remote = IbvBuffer {
+                        addr: op.remote_buffer.addr + segment.remote_offset,
+                        size: segment.len,
+                        ..op.remote_buffer.clone()
+                    };
+                    wr_ids.extend(match op.op_type {
+                        RdmaOpType::WriteFromLocal => qp.put(local, remote)?,
+                        RdmaOpType::ReadIntoLocal => qp.get(local, remote)?,
+                    });
+                }
 
-            Self::wait_for_completion(&local_buffer, &mut qp, PollTarget::Send, &wr_id, timeout)
-                .await
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                Self::wait_for_completion(
+                    &local_buffer,
+                    &mut qp,
+                    PollTarget::Send,
+                    &wr_ids,
+                    remaining,
+                )
+                .await?;
+            }
+            Ok::<_, anyhow::Error>(())
         }
         .await;
 
@@ -1189,6 +1224,7 @@ impl RdmaBackend for IbvBackend {
                 local_memory: op.local.clone(),
                 remote_buffer: remote_ibv_buffer,
                 remote_manager: remote_ibv_mgr,
+                layout: op.layout,
             });
         }
 
@@ -0,0 +1,654 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Same-host shared-memory backend for RDMA operations.
+//!
+//! When the owner of a buffer runs on the same host (and in the same
+//! PID namespace) as the caller, data is copied directly between the
+//! two address spaces with cross-memory attach (`process_vm_readv` /
+//! `process_vm_writev`), bypassing sockets and NICs entirely.
+//!
+//! Invariants:
+//! - **SHM-1 (host memory only):** A [`ShmBuffer`] is advertised only
+//!   for host (non-CUDA) memory, since cross-memory attach cannot reach
+//!   device memory. Local device memory is staged through a host
+//!   bounce buffer.
+//! - **SHM-2 (co-location):** A [`ShmBuffer`] is used only if its
+//!   [`host_key`](ShmBuffer::host_key) equals the caller's, i.e. both
+//!   processes share a kernel boot and a PID namespace, so the
+//!   recorded pid names the owner.
+//! - **SHM-3 (permission probe):** Cross-memory attach is subject to
+//!   ptrace access checks (e.g. Yama's `ptrace_scope`). Before the
+//!   backend is chosen, the buffer's generation word (SHM-4) is read
+//!   from the owner; if that fails, callers fall back to the next
+//!   backend instead of failing the operation.
+//! - **SHM-4 (liveness):** A [`ShmBackend`] holds a pidfd for the
+//!   owner, opened once the owner's start time has been checked
+//!   against the one it advertised, so a recycled pid is never taken
+//!   for the owner. Before every cross-memory attach call, the backend
+//!   checks that the owner is still running and that the buffer's
+//!   [`ShmRegistration`] has not been dropped, by comparing the
+//!   generation word in the owner's memory with the advertised one.
+//!
+//! The checks in SHM-4 narrow, but cannot close, the window in which
+//! an owner releases a buffer while a copy is in flight, so owners
+//! must still not release buffers with operations outstanding.
+
+use std::os::fd::AsRawFd;
+use std::os::fd::FromRawFd;
+use std::os::fd::OwnedFd;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use hyperactor::context;
+use serde::Deserialize;
+use serde::Serialize;
+use typeuri::Named;
+
+use crate::RdmaOp;
+use crate::RdmaOpLayout;
+use crate::RdmaOpType;
+use crate::RdmaTransportLevel;
+use crate::backend::RdmaBackend;
+use crate::local_memory::RdmaLocalMemory;
+use crate::local_memory::is_device_ptr;
+
+/// Identifies the kernel boot and PID namespace of this process. Two
+/// processes with equal keys can address each other by pid.
+///
+/// Returns `None` if either can't be determined, in which case the
+/// shared-memory backend is unavailable.
+pub fn local_host_key() -> Option<&'static str> {
+    static HOST_KEY: OnceLock<Option<String>> = OnceLock::new();
+    HOST_KEY
+        .get_or_init(|| {
+            let boot_id = std::fs::read_to_string("/proc/sys/kernel/random/boot_id").ok()?;
+            let pid_ns = std::fs::read_link("/proc/self/ns/pid").ok()?;
+            Some(format!("{}/{}", boot_id.trim(), pid_ns.display()))
+        })
+        .as_deref()
+}
+
+/// The start time of process `pid`, in clock ticks since boot, as
+/// reported by `/proc/<pid>/stat`.
+fn process_start_time(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // The command name may contain spaces and parentheses; the fields
+    // after it start at the last ')', with the state (field 3).
+    let (_, fields) = stat.rsplit_once(')')?;
+    fields.split_whitespace().nth(19)?.parse().ok()
+}
+
+/// Source of registration generations; zero marks a free slot.
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// Generation words of dropped registrations, for reuse.
+static FREE_SLOTS: Mutex<Vec<&'static AtomicU64>> = Mutex::new(Vec::new());
+
+/// Keeps a buffer advertised to same-host peers. Peers check the
+/// registration's generation word before every copy; dropping the
+/// registration changes it, so they fail instead of copying to or
+/// from memory that may have been reused (SHM-4).
+#[derive(Debug)]
+pub struct ShmRegistration {
+    slot: &'static AtomicU64,
+    generation: u64,
+}
+
+impl ShmRegistration {
+    fn new() -> Self {
+        let slot = FREE_SLOTS
+            .lock()
+            .unwrap()
+            .pop()
+            .unwrap_or_else(|| Box::leak(Box::new(AtomicU64::new(0))));
+        let generation = NEXT_GENERATION.fetch_add(1, Ordering::Relaxed);
+        slot.store(generation, Ordering::SeqCst);
+        Self { slot, generation }
+    }
+}
+
+impl Drop for ShmRegistration {
+    fn drop(&mut self) {
+        self.slot.store(0, Ordering::SeqCst);
+        FREE_SLOTS.lock().unwrap().push(self.slot);
+    }
+}
+
+/// Shared-memory transport details for a registered buffer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Named)]
+pub struct ShmBuffer {
+    /// The owner's [`local_host_key`].
+    pub host_key: String,
+    /// The owner's pid.
+    pub pid: u32,
+    /// Virtual address of the buffer in the owner's address space.
+    pub addr: usize,
+    pub size: usize,
+    /// The owner's start time, which tells it apart from a later
+    /// process reusing its pid.
+    pub start_time: u64,
+    /// Address of the registration's generation word in the owner's
+    /// address space.
+    pub generation_addr: usize,
+    /// The registration's generation when the buffer was advertised.
+    pub generation: u64,
+}
+
+impl ShmBuffer {
+    /// Describe `local` for same-host peers, or `None` if it can't be
+    /// reached with cross-memory attach (SHM-1). The buffer remains
+    /// usable by peers until the returned registration is dropped.
+    pub fn for_local(local: &dyn RdmaLocalMemory) -> Option<(Self, ShmRegistration)> {
+        if is_device_ptr(local.addr()) {
+            return None;
+        }
+        let pid = std::process::id();
+        let host_key = local_host_key()?.to_string();
+        let start_time = process_start_time(pid)?;
+        let registration = ShmRegistration::new();
+        let buffer = Self {
+            host_key,
+            pid,
+            addr: local.addr(),
+            size: local.size(),
+            start_time,
+            generation_addr: registration.slot.as_ptr() as usize,
+            generation: registration.generation,
+        };
+        Some((buffer, registration))
+    }
+
+    /// Whether the owner is co-located with this process (SHM-2).
+    pub fn is_local(&self) -> bool {
+        local_host_key() == Some(self.host_key.as_str())
+    }
+}
+
+/// Open a pidfd for `pid`.
+fn pidfd_open(pid: u32) -> std::io::Result<OwnedFd> {
+    // SAFETY: `pidfd_open` takes no pointers.
+    let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid as libc::pid_t, 0) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: on success, `pidfd_open` returns a new descriptor owned
+    // by the caller.
+    Ok(unsafe { OwnedFd::from_raw_fd(fd as libc::c_int) })
+}
+
+/// Issue one cross-memory attach call copying up to `len` bytes
+/// between local `local_addr` and `remote_addr` in process `pid`,
+/// and return the number of bytes copied.
+fn copy_once(
+    op_type: RdmaOpType,
+    pid: u32,
+    local_addr: usize,
+    remote_addr: usize,
+    len: usize,
+) -> Result<usize> {
+    let local_iov = libc::iovec {
+        iov_base: local_addr as *mut libc::c_void,
+        iov_len: len,
+    };
+    let remote_iov = libc::iovec {
+        iov_base: remote_addr as *mut libc::c_void,
+        iov_len: len,
+    };
+    let pid = pid as libc::pid_t;
+    // SAFETY: `local_iov` covers memory owned by the caller; the
+    // kernel validates the remote range and fails with EFAULT rather
+    // than touching unmapped memory.
+    let n = unsafe {
+        match op_type {
+            RdmaOpType::ReadIntoLocal => {
+                libc::process_vm_readv(pid, &local_iov, 1, &remote_iov, 1, 0)
+            }
+            RdmaOpType::WriteFromLocal => {
+                libc::process_vm_writev(pid, &local_iov, 1, &remote_iov, 1, 0)
+            }
+        }
+    };
+    if n < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    anyhow::ensure!(n > 0, "cross-memory attach made no progress");
+    Ok(n as usize)
+}
+
+/// Like [`copy_once`], but copies all `len` bytes.
+fn copy(
+    op_type: RdmaOpType,
+    pid: u32,
+    local_addr: usize,
+    remote_addr: usize,
+    len: usize,
+) -> Result<()> {
+    let mut done = 0;
+    while done < len {
+        done += copy_once(
+            op_type,
+            pid,
+            local_addr + done,
+            remote_addr + done,
+            len - done,
+        )?;
+    }
+    Ok(())
+}
+
+/// Handle for executing operations against co-located buffers.
+///
+/// Every operation is a sequence of cross-memory attach system calls
+/// issued from a blocking task, each preceded by the checks of SHM-4.
+#[derive(Debug, Clone)]
+pub struct ShmBackend {
+    /// The owner's pid.
+    pid: u32,
+    /// Becomes readable once the owner exits.
+    pidfd: Arc<OwnedFd>,
+}
+
+impl ShmBackend {
+    /// Returns a backend for `buffer` if it is co-located and this
+    /// process is permitted to access it (SHM-2, SHM-3, SHM-4).
+    pub fn connect(buffer: &ShmBuffer) -> Option<Self> {
+        if !hyperactor_config::global::get(crate::config::RDMA_ENABLE_SHM) || !buffer.is_local() {
+            return None;
+        }
+        let pidfd = match pidfd_open(buffer.pid) {
+            Ok(pidfd) => pidfd,
+            Err(e) => {
+                tracing::debug!(
+                    "cannot open pidfd for pid {}, not using shm backend: {}",
+                    buffer.pid,
+                    e
+                );
+                return None;
+            }
+        };
+        // Checked after opening the pidfd, so that the pidfd refers
+        // to the owner if the start times match.
+        if process_start_time(buffer.pid) != Some(buffer.start_time) {
+            tracing::debug!(
+                "pid {} no longer names the buffer's owner, not using shm backend",
+                buffer.pid
+            );
+            return None;
+        }
+        let backend = Self {
+            pid: buffer.pid,
+            pidfd: Arc::new(pidfd),
+        };
+        if let Err(e) = backend.check_registration(buffer) {
+            tracing::debug!(
+                "shared-memory access to pid {} denied, not using shm backend: {}",
+                buffer.pid,
+                e
+            );
+            return None;
+        }
+        Some(backend)
+    }
+
+    /// Fail unless the owner is still running and `remote` is still
+    /// registered (SHM-4).
+    fn check_registration(&self, remote: &ShmBuffer) -> Result<()> {
+        anyhow::ensure!(
+            remote.pid == self.pid,
+            "buffer is owned by pid {}, not {}",
+            remote.pid,
+            self.pid
+        );
+        let mut pollfd = libc::pollfd {
+            fd: self.pidfd.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: `pollfd` is a single valid entry, and a zero timeout
+        // makes the call non-blocking.
+        let ready = unsafe { libc::poll(&mut pollfd, 1, 0) };
+        if ready < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        anyhow::ensure!(ready == 0, "owner pid {} has exited", self.pid);
+
+        let mut generation = [0u8; 8];
+        copy(
+            RdmaOpType::ReadIntoLocal,
+            self.pid,
+            generation.as_mut_ptr() as usize,
+            remote.generation_addr,
+            generation.len(),
+        )?;
+        anyhow::ensure!(
+            u64::from_ne_bytes(generation) == remote.generation,
+            "shared-memory buffer of pid {} has been released",
+            self.pid
+        );
+        Ok(())
+    }
+
+    /// Copy `len` bytes between local host memory at `local_addr` and
+    /// `remote` at `offset`, in the direction given by `op_type`.
+    fn transfer(
+        &self,
+        op_type: RdmaOpType,
+        remote: &ShmBuffer,
+        local_addr: usize,
+        offset: usize,
+        len: usize,
+    ) -> Result<()> {
+        let mut done = 0;
+        while done < len {
+            self.check_registration(remote)?;
+            // Partial transfers happen at page boundaries; continue
+            // from where the kernel stopped.
+            done += copy_once(
+                op_type,
+                remote.pid,
+                local_addr + done,
+                remote.addr + offset + done,
+                len - done,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Execute a single operation. Host memory is copied directly, one
+    /// system call per segment; device memory is staged through a bounce
+    /// buffer of `chunk_size` bytes (SHM-1).
+    fn execute(
+        &self,
+        op_type: RdmaOpType,
+        local: &dyn RdmaLocalMemory,
+        remote: &ShmBuffer,
+        layout: &RdmaOpLayout,
+        chunk_size: usize,
+    ) -> Result<()> {
+        let segments = layout.resolve(local.size(), remote.size)?;
+
+        if !is_device_ptr(local.addr()) {
+            for segment in segments {
+                self.transfer(
+                    op_type,
+                    remote,
+                    local.addr() + segment.local_offset,
+                    segment.remote_offset,
+                    segment.len,
+                )?;
+            }
+            return Ok(());
+        }
+
+        let max_len = segments.iter().map(|s| s.len).max().unwrap_or(0);
+        let mut bounce = vec![0u8; chunk_size.min(max_len)];
+        for segment in segments {
+            let mut done = 0;
+            while done < segment.len {
+                let len = std::cmp::min(bounce.len(), segment.len - done);
+                let chunk = &mut bounce[..len];
+                let local_offset = segment.local_offset + done;
+                let remote_offset = segment.remote_offset + done;
+                match op_type {
+                    RdmaOpType::ReadIntoLocal => {
+                        self.transfer(
+                            op_type,
+                            remote,
+                            chunk.as_mut_ptr() as usize,
+                            remote_offset,
+                            len,
+                        )?;
+                        local.write_at(local_offset, chunk)?;
+                    }
+                    RdmaOpType::WriteFromLocal => {
+                        local.read_at(local_offset, chunk)?;
+                        self.transfer(
+                            op_type,
+                            remote,
+                            chunk.as_mut_ptr() as usize,
+                            remote_offset,
+                            len,
+                        )?;
+                    }
+                }
+                done += len;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RdmaBackend for ShmBackend {
+    type TransportInfo = ();
+
+    /// Submit a batch of operations against co-located buffers.
+    ///
+    /// Copies run on a blocking thread, one operation after another.
+    async fn submit(
+        &mut self,
+        _cx: &(impl context::Actor + Send + Sync),
+        ops: Vec<RdmaOp>,
+        timeout: Duration,
+    ) -> Result<()> {
+        let chunk_size =
+            hyperactor_config::global::get(crate::config::RDMA_MAX_CHUNK_SIZE_MB) * 1024 * 1024;
+        let ops = ops
+            .into_iter()
+            .map(|op| Ok((op.op_type, op.local, op.remote.resolve_shm()?, op.layout)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let backend = self.clone();
+        let copy = tokio::task::spawn_blocking(move || {
+            for (op_type, local, remote, layout) in ops {
+                backend.execute(op_type, local.as_ref(), &remote, &layout, chunk_size)?;
+            }
+            Ok::<_, anyhow::Error>(())
+        });
+        tokio::time::timeout(timeout, copy)
+            .await
+            .map_err(|_| anyhow::anyhow!("shm submit timed out"))??
+    }
+
+    fn transport_level(&self) -> RdmaTransportLevel {
+        RdmaTransportLevel::SharedMemory
+    }
+
+    fn transport_info(&self) -> Option<Self::TransportInfo> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local_memory::KeepaliveLocalMemory;
+
+    fn host_mem(data: Vec<u8>) -> Arc<dyn RdmaLocalMemory> {
+        let addr = data.as_ptr() as usize;
+        let size = data.len();
+        Arc::new(KeepaliveLocalMemory::new(addr, size, Arc::new(data)))
+    }
+
+    fn contents(mem: &Arc<dyn RdmaLocalMemory>) -> Vec<u8> {
+        let mut buf = vec![0u8; mem.size()];
+        mem.read_at(0, &mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_shm_buffer_is_local() {
+        let mem = host_mem(vec![0; 8]);
+        let (buffer, _registration) = ShmBuffer::for_local(mem.as_ref()).unwrap();
+        assert_eq!(buffer.pid, std::process::id());
+        assert_eq!(buffer.size, 8);
+        assert!(buffer.is_local());
+
+        let elsewhere = ShmBuffer {
+            host_key: "another-boot/pid:[1]".to_string(),
+            ..buffer
+        };
+        assert!(!elsewhere.is_local());
+        assert!(ShmBackend::connect(&elsewhere).is_none());
+    }
+
+    // Cross-memory attach on our own pid is always permitted, so these
+    // exercise the full copy path within one process.
+    #[test]
+    fn test_shm_read_and_write() {
+        let remote_mem = host_mem((0..=255).collect());
+        let (remote, _registration) = ShmBuffer::for_local(remote_mem.as_ref()).unwrap();
+        let backend = ShmBackend::connect(&remote).unwrap();
+
+        let local = host_mem(vec![0; 256]);
+        backend
+            .execute(
+                RdmaOpType::ReadIntoLocal,
+                local.as_ref(),
+                &remote,
+                &RdmaOpLayout::Contiguous,
+                1024,
+            )
+            .unwrap();
+        assert_eq!(contents(&local), contents(&remote_mem));
+
+        let local = host_mem(vec![7; 100]);
+        backend
+            .execute(
+                RdmaOpType::WriteFromLocal,
+                local.as_ref(),
+                &remote,
+                &RdmaOpLayout::Contiguous,
+                1024,
+            )
+            .unwrap();
+        let written = contents(&remote_mem);
+        assert!(written[..100].iter().all(|b| *b == 7));
+        assert_eq!(written[100], 100);
+
+        let too_big = host_mem(vec![0; 512]);
+        assert!(
+            backend
+                .execute(
+                    RdmaOpType::ReadIntoLocal,
+                    too_big.as_ref(),
+                    &remote,
+                    &RdmaOpLayout::Contiguous,
+                    1024
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_shm_strided_read() {
+        // A 4x8 row-major remote matrix; gather columns [2..5).
+        let remote_mem = host_mem((0..32).collect());
+        let (remote, _registration) = ShmBuffer::for_local(remote_mem.as_ref()).unwrap();
+        let backend = ShmBackend::connect(&remote).unwrap();
+        let local = host_mem(vec![0; 12]);
+        let layout = RdmaOpLayout::Strided(crate::RdmaStride {
+            local_offset: 0,
+            remote_offset: 2,
+            row_len: 3,
+            rows: 4,
+            local_stride: 3,
+            remote_stride: 8,
+        });
+        backend
+            .execute(
+                RdmaOpType::ReadIntoLocal,
+                local.as_ref(),
+                &remote,
+                &layout,
+                1024,
+            )
+            .unwrap();
+        assert_eq!(
+            contents(&local),
+            vec![2, 3, 4, 10, 11, 12, 18, 19, 20, 26, 27, 28]
+        );
+    }
+
+    #[test]
+    fn test_transfer_offset_and_fault() {
+        let remote_mem = host_mem((0..64).collect());
+        let (remote, _registration) = ShmBuffer::for_local(remote_mem.as_ref()).unwrap();
+        let backend = ShmBackend::connect(&remote).unwrap();
+        let mut dst = [0u8; 4];
+        backend
+            .transfer(
+                RdmaOpType::ReadIntoLocal,
+                &remote,
+                dst.as_mut_ptr() as usize,
+                10,
+                4,
+            )
+            .unwrap();
+        assert_eq!(dst, [10, 11, 12, 13]);
+
+        // An unmapped remote address fails instead of faulting.
+        let unmapped = ShmBuffer {
+            addr: 8,
+            ..remote.clone()
+        };
+        assert!(
+            backend
+                .transfer(
+                    RdmaOpType::ReadIntoLocal,
+                    &unmapped,
+                    dst.as_mut_ptr() as usize,
+                    0,
+                    4
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_released_and_stale_buffers() {
+        let remote_mem = host_mem((0..64).collect());
+        let (remote, registration) = ShmBuffer::for_local(remote_mem.as_ref()).unwrap();
+        let backend = ShmBackend::connect(&remote).unwrap();
+        let mut dst = [0u8; 4];
+        let read = |backend: &ShmBackend, remote: &ShmBuffer, dst: &mut [u8; 4]| {
+            backend.transfer(
+                RdmaOpType::ReadIntoLocal,
+                remote,
+                dst.as_mut_ptr() as usize,
+                0,
+                4,
+            )
+        };
+        read(&backend, &remote, &mut dst).unwrap();
+
+        // Once released, the buffer can no longer be copied, even if
+        // its generation word is reused by a later registration.
+        drop(registration);
+        assert!(read(&backend, &remote, &mut dst).is_err());
+        assert!(ShmBackend::connect(&remote).is_none());
+        let (reused, _registration) = ShmBuffer::for_local(remote_mem.as_ref()).unwrap();
+        assert!(read(&backend, &remote, &mut dst).is_err());
+        read(&backend, &reused, &mut dst).unwrap();
+
+        // A buffer advertised by an earlier process with our pid is not
+        // taken for ours.
+        let stale = ShmBuffer {
+            start_time: reused.start_time + 1,
+            ..reused.clone()
+        };
+        assert!(ShmBackend::connect(&stale).is_none());
+    }
+}
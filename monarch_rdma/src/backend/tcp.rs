@@ -17,9 +17,12 @@ pub mod manager_actor;
 use std::sync::Arc;
 
 use hyperactor::ActorRef;
+use hyperactor::reference::PortRef;
 use manager_actor::TcpManagerActor;
+use manager_actor::TcpTransferProgress;
 
 use crate::RdmaLocalMemory;
+use crate::RdmaOpLayout;
 use crate::RdmaOpType;
 
 /// A single operation for the [`TcpBackend`](manager_actor::TcpBackend).
@@ -29,4 +32,7 @@ pub struct TcpOp {
     pub local_memory: Arc<dyn RdmaLocalMemory>,
     pub remote_tcp_manager: ActorRef<TcpManagerActor>,
     pub remote_buf_id: usize,
+    pub layout: RdmaOpLayout,
+    /// Where to report progress, if anywhere.
+    pub progress: Option<PortRef<TcpTransferProgress>>,
 }
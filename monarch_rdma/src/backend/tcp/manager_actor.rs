@@ -11,8 +11,18 @@
 //! Transfers buffer data over the default hyperactor channel transport
 //! in chunks controlled by
 //! [`RDMA_MAX_CHUNK_SIZE_MB`](crate::config::RDMA_MAX_CHUNK_SIZE_MB).
+//!
+//! Every chunk carries a CRC32 of its payload, verified by the receiver
+//! before the chunk is used. A chunk that times out, fails to send, or
+//! arrives corrupted is resent on its own, up to
+//! [`RDMA_TCP_CHUNK_RETRIES`](crate::config::RDMA_TCP_CHUNK_RETRIES)
+//! times, so one bad chunk does not restart the whole transfer.
+//! Parallel transfers proceed in rounds: after each round the receiver
+//! reports which chunks are still missing, and only those are resent.
 
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::future::Future;
 use std::sync::Arc;
 use std::sync::OnceLock;
 use std::time::Duration;
@@ -42,6 +52,7 @@ use hyperactor::context;
 use hyperactor::context::Actor as _;
 use hyperactor::reference;
 use hyperactor::reference::OncePortRef;
+use hyperactor::reference::PortRef;
 use hyperactor_mesh::transport::default_transport;
 use serde::Deserialize;
 use serde::Serialize;
@@ -54,31 +65,79 @@ use super::TcpOp;
 use crate::RdmaLocalMemory;
 use crate::RdmaOp;
 use crate::RdmaOpType;
+use crate::RdmaSegment;
 use crate::RdmaTransportLevel;
 use crate::backend::RdmaBackend;
+use crate::layout::batch_segments;
 use crate::rdma_manager_actor::GetTcpActorRefClient;
 use crate::rdma_manager_actor::RdmaManagerActor;
 use crate::rdma_manager_actor::RdmaManagerMessageClient;
 
-/// [`Named`] wrapper around [`Part`] for use as a reply type.
+/// [`Named`] wrapper around [`Part`] for use as a reply type, together
+/// with the CRC32 of the payload.
 ///
 /// [`Part`] itself does not implement [`Named`], which is required by
-/// [`OncePortRef`]. This newtype adds the missing trait.
+/// [`OncePortRef`]. This type adds the missing trait.
 #[derive(Debug, Clone, Serialize, Deserialize, Named)]
-pub struct TcpChunk(Part);
+pub struct TcpChunk {
+    data: Part,
+    checksum: u32,
+}
 wirevalue::register_type!(TcpChunk);
 
+impl TcpChunk {
+    fn new(data: Bytes) -> Self {
+        Self {
+            checksum: crc32fast::hash(&data),
+            data: Part::from(data),
+        }
+    }
+
+    /// Return the payload, or an error if it does not match its checksum.
+    fn into_verified_bytes(self) -> Result<Bytes> {
+        let data = self.data.into_bytes();
+        verify_checksum(&data, self.checksum)?;
+        Ok(data)
+    }
+}
+
+fn verify_checksum(data: &[u8], expected: u32) -> Result<()> {
+    let actual = crc32fast::hash(data);
+    anyhow::ensure!(
+        actual == expected,
+        "chunk checksum mismatch: expected {expected:#010x}, got {actual:#010x}"
+    );
+    Ok(())
+}
+
 /// Data chunk sent over direct parallel channels.
 #[derive(Debug, Clone, Serialize, Deserialize, Named)]
 struct TcpDataChunk {
     // Which specific transfer this chunk is associated with.
     transfer_id: usize,
+    // Index of this chunk within the transfer.
+    index: usize,
     // Offset into the buffer for this chunk.
     offset: usize,
+    // CRC32 of `data`.
+    checksum: u32,
     data: Part,
 }
 wirevalue::register_type!(TcpDataChunk);
 
+/// Progress of a TCP fallback operation, reported each time a chunk has
+/// been verified and written. See [`TcpBackend::submit_with_progress`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Named)]
+pub struct TcpTransferProgress {
+    /// Id of the remote buffer the operation targets.
+    pub buf_id: usize,
+    /// Bytes transferred so far.
+    pub bytes_done: usize,
+    /// Total bytes moved by the operation.
+    pub bytes_total: usize,
+}
+wirevalue::register_type!(TcpTransferProgress);
+
 /// Tracks the progress of a single parallel transfer (read or write).
 ///
 /// Shared between channel receive loops and actor message handlers
@@ -88,45 +147,71 @@ struct TransferState {
     /// Buffer backing this transfer, provided at construction.
     local_memory: Arc<dyn RdmaLocalMemory>,
 
-    /// Number of chunks received so far.
-    chunks_received: usize,
+    /// Chunks not yet received in the current round.
+    pending: HashSet<usize>,
 
-    /// Total chunks expected for this transfer.
-    total_chunks: usize,
+    /// Chunks that arrived with a bad checksum in the current round.
+    failed: Vec<usize>,
+
+    /// Remote buffer id and total size, for progress reports.
+    buf_id: usize,
+    size: usize,
 
-    /// Completion reply port. Fired when all chunks arrive
-    /// or an error occurs.
-    done: OncePortRef<Result<(), String>>,
+    /// Bytes verified and written so far.
+    bytes_done: usize,
+
+    /// Round results: fired whenever `pending` drains, with the chunks
+    /// that must be resent (empty once the transfer is complete), or
+    /// with an error.
+    done: PortRef<Result<Vec<usize>, String>>,
+
+    /// Optional progress port.
+    progress: Option<PortRef<TcpTransferProgress>>,
 }
 
 impl TransferState {
     fn new(
-        total_chunks: usize,
         local_memory: Arc<dyn RdmaLocalMemory>,
-        done: OncePortRef<Result<(), String>>,
+        total_chunks: usize,
+        buf_id: usize,
+        size: usize,
+        done: PortRef<Result<Vec<usize>, String>>,
+        progress: Option<PortRef<TcpTransferProgress>>,
     ) -> Self {
         Self {
             local_memory,
-            chunks_received: 0,
-            total_chunks,
+            pending: (0..total_chunks).collect(),
+            failed: Vec::new(),
+            buf_id,
+            size,
+            bytes_done: 0,
             done,
+            progress,
         }
     }
 }
 
-/// Sends the result of a completed transfer to the caller's reply port.
+/// Sends the result of a transfer round to the caller's port.
 ///
 /// Sending an actor message from the spawned receiver task requires the
 /// loop to own a dummy [`context::Actor`] impl. If the task sent directly
-/// to a remote [`OncePortRef`], the message would appear to come from
+/// to a remote [`PortRef`], the message would appear to come from
 /// this dummy context, and undeliverable messages wouldn't be handled
 /// properly. This intermediate message lets us use a [`PortHandle`]
 /// whose message cannot be undeliverable; the handler then forwards the
 /// result using the real actor's context.
 #[derive(Debug, Serialize, Deserialize, Named)]
 struct SendTransferResult {
-    done: OncePortRef<Result<(), String>>,
-    result: Result<(), String>,
+    done: PortRef<Result<Vec<usize>, String>>,
+    result: Result<Vec<usize>, String>,
+}
+
+/// Forwards a progress report from the receiver task, for the same
+/// reason as [`SendTransferResult`].
+#[derive(Debug, Serialize, Deserialize, Named)]
+struct SendTransferProgress {
+    port: PortRef<TcpTransferProgress>,
+    progress: TcpTransferProgress,
 }
 
 /// Fatal error from the receive loop.
@@ -144,18 +229,22 @@ struct TransferError {
 struct RegisterTransferLocal {
     local_memory: Arc<dyn RdmaLocalMemory>,
     total_chunks: usize,
-    done: OncePortRef<Result<(), String>>,
+    buf_id: usize,
+    size: usize,
+    done: PortRef<Result<Vec<usize>, String>>,
+    progress: Option<PortRef<TcpTransferProgress>>,
     // The transfer ID
     reply: OncePortHandle<usize>,
 }
 
 /// Tell the local TcpManagerActor to read local memory and push
-/// chunks to `dest_addr`.
+/// the given chunks to `dest_addr`.
 #[derive(Debug)]
 struct ExecuteTransferLocal {
     transfer_id: usize,
     local_memory: Arc<dyn RdmaLocalMemory>,
     chunk_size: usize,
+    chunks: Vec<usize>,
     dest_addr: ChannelAddr,
 }
 
@@ -166,10 +255,12 @@ struct ExecuteTransferLocal {
 #[derive(Handler, HandleClient, RefClient, Debug, Serialize, Deserialize, Named)]
 enum TcpManagerMessage {
     /// Write a chunk of data into a registered buffer at the given offset.
+    /// The chunk is rejected if `data` does not match `checksum`.
     WriteChunk {
         buf_id: usize,
         offset: usize,
         data: Part,
+        checksum: u32,
         #[reply]
         reply: OncePortRef<Result<(), String>>,
     },
@@ -181,31 +272,61 @@ enum TcpManagerMessage {
         #[reply]
         reply: OncePortRef<Result<TcpChunk, String>>,
     },
+    /// Write a batch of segments into a registered buffer. `segments`
+    /// holds `(offset, len)` pairs; `data` holds their bytes back to
+    /// back, in order, and is rejected if it does not match `checksum`.
+    WriteSegments {
+        buf_id: usize,
+        segments: Vec<(usize, usize)>,
+        data: Part,
+        checksum: u32,
+        #[reply]
+        reply: OncePortRef<Result<(), String>>,
+    },
+    /// Read a batch of `(offset, len)` segments from a registered buffer,
+    /// returning their bytes back to back, in order.
+    ReadSegments {
+        buf_id: usize,
+        segments: Vec<(usize, usize)>,
+        #[reply]
+        reply: OncePortRef<Result<TcpChunk, String>>,
+    },
     /// Return the channel address served by this actor for parallel transfers.
     /// `None` when parallelism is 1.
     GetChannelAddress {
         #[reply]
         reply: OncePortRef<Option<ChannelAddr>>,
     },
-    /// Set up a remote TcpManagerActor to receive a parallel transfer from
-    /// the sender.
+    /// Set up a remote TcpManagerActor to receive a parallel transfer of
+    /// `size` bytes from the sender.
     RegisterTransferRemote {
         buf_id: usize,
         total_chunks: usize,
-        done: OncePortRef<Result<(), String>>,
+        size: usize,
+        done: PortRef<Result<Vec<usize>, String>>,
+        progress: Option<PortRef<TcpTransferProgress>>,
         #[reply]
         reply: OncePortRef<Result<usize, String>>,
     },
     /// Tell the remote TcpManagerActor to read its local memory and push
-    /// chunks to the dest_addr provided by the sender.
+    /// the given chunks to the dest_addr provided by the sender.
     ExecuteTransferRemote {
         transfer_id: usize,
         buf_id: usize,
         chunk_size: usize,
+        chunks: Vec<usize>,
         dest_addr: ChannelAddr,
         #[reply]
         reply: OncePortRef<Result<(), String>>,
     },
+    /// Start a new round of a parallel transfer this actor is receiving,
+    /// returning the chunks that must be resent. Used when a round's
+    /// result did not arrive in time. An unknown transfer has completed.
+    QueryTransfer {
+        transfer_id: usize,
+        #[reply]
+        reply: OncePortRef<Vec<usize>>,
+    },
 }
 wirevalue::register_type!(TcpManagerMessage);
 
@@ -245,18 +366,10 @@ impl TcpManagerActor {
         }
     }
 
-    fn register_transfer(
-        &mut self,
-        local_memory: Arc<dyn RdmaLocalMemory>,
-        total_chunks: usize,
-        done: OncePortRef<Result<(), String>>,
-    ) -> usize {
+    fn register_transfer(&mut self, state: TransferState) -> usize {
         let transfer_id = self.next_transfer_id;
         self.next_transfer_id += 1;
-        self.transfers.insert(
-            transfer_id,
-            TransferState::new(total_chunks, local_memory, done),
-        );
+        self.transfers.insert(transfer_id, state);
         transfer_id
     }
 
@@ -266,6 +379,7 @@ impl TcpManagerActor {
         transfer_id: usize,
         local_memory: Arc<dyn RdmaLocalMemory>,
         chunk_size: usize,
+        chunks: Vec<usize>,
         dest_addr: ChannelAddr,
     ) -> Result<()> {
         let parallelism =
@@ -284,9 +398,9 @@ impl TcpManagerActor {
         let conns = self.outbound.get(&dest_addr).unwrap();
 
         let size = local_memory.size();
-        let total_chunks = size.div_ceil(chunk_size);
+        let chunks = Arc::new(chunks);
 
-        let chunk_index = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let next_chunk = Arc::new(std::sync::atomic::AtomicUsize::new(0));
         let error_port: PortHandle<TransferError> = cx.port();
         let proc = cx.instance().proc().clone();
         let cancel = self.cancel.clone();
@@ -294,7 +408,8 @@ impl TcpManagerActor {
         for conn in conns.clone() {
             let mem = local_memory.clone();
             let transfer_id = transfer_id;
-            let chunk_index = chunk_index.clone();
+            let chunks = chunks.clone();
+            let next_chunk = next_chunk.clone();
             let error_port = error_port.clone();
             let proc = proc.clone();
             let cancel = cancel.clone();
@@ -313,12 +428,12 @@ impl TcpManagerActor {
                         return;
                     }
 
-                    let idx = chunk_index.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                    if idx >= total_chunks {
+                    let next = next_chunk.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    let Some(&index) = chunks.get(next) else {
                         break;
-                    }
+                    };
 
-                    let offset = idx * chunk_size;
+                    let offset = index * chunk_size;
                     let len = std::cmp::min(chunk_size, size - offset);
                     let mut buf = BytesMut::zeroed(len);
                     if let Err(e) = mem.read_at(offset, &mut buf) {
@@ -335,21 +450,18 @@ impl TcpManagerActor {
 
                     let chunk = TcpDataChunk {
                         transfer_id,
+                        index,
                         offset,
+                        checksum: crc32fast::hash(&buf),
                         data: Part::from(buf.freeze()),
                     };
 
+                    // A chunk that fails to send is reported missing by
+                    // the receiver and resent in the next round.
                     if let Err(e) = conn.send(chunk).await {
-                        error_port
-                            .send(
-                                &instance,
-                                TransferError {
-                                    message: format!(
-                                        "failed to send chunk at offset {offset}: {e}"
-                                    ),
-                                },
-                            )
-                            .unwrap();
+                        tracing::warn!(
+                            "failed to send chunk {index} of transfer {transfer_id}: {e}"
+                        );
                         return;
                     }
                 }
@@ -392,6 +504,7 @@ impl Actor for TcpManagerActor {
             let transfers = self.transfers.clone();
             let proc = this.proc().clone();
             let result_port: PortHandle<SendTransferResult> = this.port();
+            let progress_port: PortHandle<SendTransferProgress> = this.port();
             let error_port: PortHandle<TransferError> = this.port();
             let cancel = self.cancel.clone();
             let receiver_name = reference::name::Name::generate(
@@ -439,40 +552,81 @@ impl Actor for TcpManagerActor {
                         }
                     };
 
-                    let mut write_offset = chunk.offset;
-                    let fragments = chunk.data.into_inner();
-                    let write_err = fragments.iter().find_map(|fragment| {
-                        let result = entry.local_memory.write_at(write_offset, fragment);
-                        write_offset += fragment.len();
-                        result.err()
-                    });
-                    if let Some(e) = write_err {
-                        let transfer_id = chunk.transfer_id;
-                        drop(entry);
-                        let (_, state) = transfers.remove(&transfer_id).unwrap();
-                        result_port
-                            .send(
-                                &instance,
-                                SendTransferResult {
-                                    done: state.done,
-                                    result: Err(e.to_string()),
-                                },
-                            )
-                            .unwrap();
+                    // Late duplicates of chunks that were already
+                    // written (e.g. after a resend) are dropped.
+                    if !entry.pending.remove(&chunk.index) {
                         continue;
                     }
 
-                    entry.chunks_received += 1;
-                    if entry.chunks_received == entry.total_chunks {
+                    let fragments = chunk.data.into_inner();
+                    let mut hasher = crc32fast::Hasher::new();
+                    fragments
+                        .iter()
+                        .for_each(|fragment| hasher.update(fragment));
+                    if hasher.finalize() != chunk.checksum {
+                        tracing::warn!(
+                            "checksum mismatch for chunk {} of transfer {}",
+                            chunk.index,
+                            chunk.transfer_id,
+                        );
+                        entry.failed.push(chunk.index);
+                    } else {
+                        let mut write_offset = chunk.offset;
+                        let write_err = fragments.iter().find_map(|fragment| {
+                            let result = entry.local_memory.write_at(write_offset, fragment);
+                            write_offset += fragment.len();
+                            result.err()
+                        });
+                        if let Some(e) = write_err {
+                            let transfer_id = chunk.transfer_id;
+                            drop(entry);
+                            let (_, state) = transfers.remove(&transfer_id).unwrap();
+                            result_port
+                                .send(
+                                    &instance,
+                                    SendTransferResult {
+                                        done: state.done,
+                                        result: Err(e.to_string()),
+                                    },
+                                )
+                                .unwrap();
+                            continue;
+                        }
+
+                        entry.bytes_done += write_offset - chunk.offset;
+                        if let Some(port) = &entry.progress {
+                            progress_port
+                                .send(
+                                    &instance,
+                                    SendTransferProgress {
+                                        port: port.clone(),
+                                        progress: TcpTransferProgress {
+                                            buf_id: entry.buf_id,
+                                            bytes_done: entry.bytes_done,
+                                            bytes_total: entry.size,
+                                        },
+                                    },
+                                )
+                                .unwrap();
+                        }
+                    }
+
+                    if entry.pending.is_empty() {
                         let transfer_id = chunk.transfer_id;
-                        drop(entry);
-                        let (_, state) = transfers.remove(&transfer_id).unwrap();
+                        let resend = std::mem::take(&mut entry.failed);
+                        let done = if resend.is_empty() {
+                            drop(entry);
+                            transfers.remove(&transfer_id).unwrap().1.done
+                        } else {
+                            entry.pending.extend(resend.iter().copied());
+                            entry.done.clone()
+                        };
                         result_port
                             .send(
                                 &instance,
                                 SendTransferResult {
-                                    done: state.done,
-                                    result: Ok(()),
+                                    done,
+                                    result: Ok(resend),
                                 },
                             )
                             .unwrap();
@@ -508,6 +662,7 @@ impl TcpManagerMessageHandler for TcpManagerActor {
         buf_id: usize,
         offset: usize,
         data: Part,
+        checksum: u32,
     ) -> Result<Result<(), String>, anyhow::Error> {
         let owner = self.owner.get().expect("TcpManagerActor owner not set");
         let mem = match owner.request_local_memory(cx, buf_id).await {
@@ -517,6 +672,9 @@ impl TcpManagerMessageHandler for TcpManagerActor {
         };
 
         let bytes = data.into_bytes();
+        if let Err(e) = verify_checksum(&bytes, checksum) {
+            return Ok(Err(e.to_string()));
+        }
         if let Err(e) = mem.write_at(offset, &bytes) {
             return Ok(Err(e.to_string()));
         }
@@ -542,7 +700,69 @@ impl TcpManagerMessageHandler for TcpManagerActor {
         if let Err(e) = mem.read_at(offset, &mut buf) {
             return Ok(Err(e.to_string()));
         }
-        Ok(Ok(TcpChunk(Part::from(buf.freeze()))))
+        Ok(Ok(TcpChunk::new(buf.freeze())))
+    }
+
+    async fn write_segments(
+        &mut self,
+        cx: &Context<Self>,
+        buf_id: usize,
+        segments: Vec<(usize, usize)>,
+        data: Part,
+        checksum: u32,
+    ) -> Result<Result<(), String>, anyhow::Error> {
+        let owner = self.owner.get().expect("TcpManagerActor owner not set");
+        let mem = match owner.request_local_memory(cx, buf_id).await {
+            Ok(Some(mem)) => mem,
+            Ok(None) => return Ok(Err(format!("buffer {buf_id} not found"))),
+            Err(e) => return Ok(Err(e.to_string())),
+        };
+
+        let bytes = data.into_bytes();
+        if let Err(e) = verify_checksum(&bytes, checksum) {
+            return Ok(Err(e.to_string()));
+        }
+        let total: usize = segments.iter().map(|(_, len)| len).sum();
+        if bytes.len() != total {
+            return Ok(Err(format!(
+                "segment data size mismatch: expected {total}, got {}",
+                bytes.len()
+            )));
+        }
+        let mut pos = 0;
+        for (offset, len) in segments {
+            if let Err(e) = mem.write_at(offset, &bytes[pos..pos + len]) {
+                return Ok(Err(e.to_string()));
+            }
+            pos += len;
+        }
+
+        Ok(Ok(()))
+    }
+
+    async fn read_segments(
+        &mut self,
+        cx: &Context<Self>,
+        buf_id: usize,
+        segments: Vec<(usize, usize)>,
+    ) -> Result<Result<TcpChunk, String>, anyhow::Error> {
+        let owner = self.owner.get().expect("TcpManagerActor owner not set");
+        let mem = match owner.request_local_memory(cx, buf_id).await {
+            Ok(Some(mem)) => mem,
+            Ok(None) => return Ok(Err(format!("buffer {buf_id} not found"))),
+            Err(e) => return Ok(Err(e.to_string())),
+        };
+
+        let total: usize = segments.iter().map(|(_, len)| len).sum();
+        let mut buf = BytesMut::zeroed(total);
+        let mut pos = 0;
+        for (offset, len) in segments {
+            if let Err(e) = mem.read_at(offset, &mut buf[pos..pos + len]) {
+                return Ok(Err(e.to_string()));
+            }
+            pos += len;
+        }
+        Ok(Ok(TcpChunk::new(buf.freeze())))
     }
 
     async fn get_channel_address(
@@ -557,7 +777,9 @@ impl TcpManagerMessageHandler for TcpManagerActor {
         cx: &Context<Self>,
         buf_id: usize,
         total_chunks: usize,
-        done: OncePortRef<Result<(), String>>,
+        size: usize,
+        done: PortRef<Result<Vec<usize>, String>>,
+        progress: Option<PortRef<TcpTransferProgress>>,
     ) -> Result<Result<usize, String>, anyhow::Error> {
         let owner = self.owner.get().expect("TcpManagerActor owner not set");
         let mem = match owner.request_local_memory(cx, buf_id).await {
@@ -565,7 +787,14 @@ impl TcpManagerMessageHandler for TcpManagerActor {
             Ok(None) => return Ok(Err(format!("buffer {buf_id} not found"))),
             Err(e) => return Ok(Err(e.to_string())),
         };
-        let transfer_id = self.register_transfer(mem, total_chunks, done);
+        let transfer_id = self.register_transfer(TransferState::new(
+            mem,
+            total_chunks,
+            buf_id,
+            size,
+            done,
+            progress,
+        ));
         Ok(Ok(transfer_id))
     }
 
@@ -575,6 +804,7 @@ impl TcpManagerMessageHandler for TcpManagerActor {
         transfer_id: usize,
         buf_id: usize,
         chunk_size: usize,
+        chunks: Vec<usize>,
         dest_addr: ChannelAddr,
     ) -> Result<Result<(), String>, anyhow::Error> {
         let owner = self.owner.get().expect("TcpManagerActor owner not set");
@@ -583,9 +813,24 @@ impl TcpManagerMessageHandler for TcpManagerActor {
             Ok(None) => return Ok(Err(format!("buffer {buf_id} not found"))),
             Err(e) => return Ok(Err(e.to_string())),
         };
-        self.execute_transfer(cx, transfer_id, mem, chunk_size, dest_addr)?;
+        self.execute_transfer(cx, transfer_id, mem, chunk_size, chunks, dest_addr)?;
         Ok(Ok(()))
     }
+
+    async fn query_transfer(
+        &mut self,
+        _cx: &Context<Self>,
+        transfer_id: usize,
+    ) -> Result<Vec<usize>, anyhow::Error> {
+        let Some(mut entry) = self.transfers.get_mut(&transfer_id) else {
+            return Ok(Vec::new());
+        };
+        let failed = std::mem::take(&mut entry.failed);
+        entry.pending.extend(failed);
+        let mut missing: Vec<usize> = entry.pending.iter().copied().collect();
+        missing.sort_unstable();
+        Ok(missing)
+    }
 }
 
 #[async_trait]
@@ -595,8 +840,14 @@ impl Handler<RegisterTransferLocal> for TcpManagerActor {
         cx: &Context<Self>,
         message: RegisterTransferLocal,
     ) -> Result<(), anyhow::Error> {
-        let transfer_id =
-            self.register_transfer(message.local_memory, message.total_chunks, message.done);
+        let transfer_id = self.register_transfer(TransferState::new(
+            message.local_memory,
+            message.total_chunks,
+            message.buf_id,
+            message.size,
+            message.done,
+            message.progress,
+        ));
         message.reply.send(cx, transfer_id)?;
         Ok(())
     }
@@ -614,6 +865,7 @@ impl Handler<ExecuteTransferLocal> for TcpManagerActor {
             message.transfer_id,
             message.local_memory,
             message.chunk_size,
+            message.chunks,
             message.dest_addr,
         )
     }
@@ -630,6 +882,21 @@ impl Handler<SendTransferResult> for TcpManagerActor {
     }
 }
 
+#[async_trait]
+impl Handler<SendTransferProgress> for TcpManagerActor {
+    async fn handle(
+        &mut self,
+        cx: &Context<Self>,
+        message: SendTransferProgress,
+    ) -> Result<(), anyhow::Error> {
+        // Progress is best-effort; a vanished listener is not an error.
+        if let Err(e) = message.port.send(cx, message.progress) {
+            tracing::debug!("failed to report transfer progress: {e}");
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl Handler<TransferError> for TcpManagerActor {
     async fn handle(
@@ -642,6 +909,58 @@ impl Handler<TransferError> for TcpManagerActor {
     }
 }
 
+/// Run `attempt` until it succeeds, giving each try at most
+/// [`RDMA_TCP_CHUNK_TIMEOUT`](crate::config::RDMA_TCP_CHUNK_TIMEOUT)
+/// and retrying failures up to
+/// [`RDMA_TCP_CHUNK_RETRIES`](crate::config::RDMA_TCP_CHUNK_RETRIES)
+/// times before `deadline`.
+async fn with_retries<T, Fut>(
+    what: &str,
+    deadline: Instant,
+    mut attempt: impl FnMut() -> Fut,
+) -> Result<T>
+where
+    Fut: Future<Output = Result<T>>,
+{
+    let retries = hyperactor_config::global::get(crate::config::RDMA_TCP_CHUNK_RETRIES);
+    let chunk_timeout = hyperactor_config::global::get(crate::config::RDMA_TCP_CHUNK_TIMEOUT);
+    let mut failures = 0;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let result = match tokio_timeout(remaining.min(chunk_timeout), attempt()).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow::anyhow!("{what} timed out")),
+        };
+        match result {
+            Ok(value) => return Ok(value),
+            Err(e) if failures < retries && Instant::now() < deadline => {
+                failures += 1;
+                tracing::warn!("{what} failed (retry {failures} of {retries}): {e}");
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Report progress of `op` to its progress port, if any. Best-effort.
+fn report_progress(
+    cx: &(impl context::Actor + Send + Sync),
+    op: &TcpOp,
+    bytes_done: usize,
+    bytes_total: usize,
+) {
+    if let Some(port) = &op.progress {
+        let progress = TcpTransferProgress {
+            buf_id: op.remote_buf_id,
+            bytes_done,
+            bytes_total,
+        };
+        if let Err(e) = port.send(cx, progress) {
+            tracing::debug!("failed to report transfer progress: {e}");
+        }
+    }
+}
+
 /// Wrapper around [`ActorHandle<TcpManagerActor>`] that moves the TCP
 /// data-plane (chunked reads/writes) off the actor loop while keeping
 /// buffer resolution serialized through actor messages.
@@ -660,9 +979,15 @@ impl std::ops::Deref for TcpBackend {
 }
 
 impl TcpBackend {
-    /// Execute a parallel write: register the transfer on the remote
-    /// side, then execute locally to push chunks over direct channels.
-    async fn execute_parallel_write(
+    /// Execute a parallel transfer in rounds.
+    ///
+    /// The receiving side (remote for writes, local for reads) registers
+    /// the transfer; the sending side pushes chunks over direct channels.
+    /// Whenever a round drains, the receiver reports which chunks arrived
+    /// corrupted; if a round's report does not arrive in time, the
+    /// receiver is asked which chunks are still missing. Only those chunks
+    /// are sent in the next round.
+    async fn execute_parallel(
         &self,
         cx: &(impl context::Actor + Send + Sync),
         op: &TcpOp,
@@ -671,53 +996,173 @@ impl TcpBackend {
     ) -> Result<()> {
         let size = op.local_memory.size();
         let total_chunks = size.div_ceil(chunk_size);
+        if total_chunks == 0 {
+            return Ok(());
+        }
+        let what = match op.op_type {
+            RdmaOpType::WriteFromLocal => "parallel write",
+            RdmaOpType::ReadIntoLocal => "parallel read",
+        };
 
-        let (done_handle, done_rx) = hyperactor::mailbox::open_once_port::<Result<(), String>>(cx);
-        let done_ref = done_handle.bind();
+        let (done_handle, mut done_rx) =
+            hyperactor::mailbox::open_port::<Result<Vec<usize>, String>>(cx);
+        let done = done_handle.bind();
+
+        // Register the transfer with the receiver and find out where the
+        // sender should push chunks.
+        let (transfer_id, dest_addr) = match op.op_type {
+            RdmaOpType::WriteFromLocal => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                let transfer_id = tokio_timeout(
+                    remaining,
+                    op.remote_tcp_manager.register_transfer_remote(
+                        cx,
+                        op.remote_buf_id,
+                        total_chunks,
+                        size,
+                        done,
+                        op.progress.clone(),
+                    ),
+                )
+                .await
+                .map_err(|_| anyhow::anyhow!("register_transfer_remote timed out"))??
+                .map_err(|e| anyhow::anyhow!(e))?;
+
+                let dest_addr = tokio_timeout(
+                    deadline.saturating_duration_since(Instant::now()),
+                    op.remote_tcp_manager.get_channel_address(cx),
+                )
+                .await
+                .map_err(|_| anyhow::anyhow!("get_channel_address timed out"))??
+                .ok_or_else(|| anyhow::anyhow!("remote does not have parallel channels enabled"))?;
+                (transfer_id, dest_addr)
+            }
+            RdmaOpType::ReadIntoLocal => {
+                let (id_handle, id_rx) = hyperactor::mailbox::open_once_port::<usize>(cx);
+                self.0.send(
+                    cx,
+                    RegisterTransferLocal {
+                        local_memory: op.local_memory.clone(),
+                        total_chunks,
+                        buf_id: op.remote_buf_id,
+                        size,
+                        done,
+                        progress: op.progress.clone(),
+                        reply: id_handle,
+                    },
+                )?;
+                let transfer_id = id_rx
+                    .recv()
+                    .await
+                    .map_err(|e| anyhow::anyhow!("failed to receive transfer id: {e}"))?;
+
+                let my_channel_addr = self
+                    .0
+                    .get_channel_address(cx)
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("local parallel channels not enabled"))?;
+                (transfer_id, my_channel_addr)
+            }
+        };
 
-        let remaining = deadline.saturating_duration_since(Instant::now());
-        let transfer_id = tokio_timeout(
-            remaining,
-            op.remote_tcp_manager.register_transfer_remote(
-                cx,
-                op.remote_buf_id,
-                total_chunks,
-                done_ref,
-            ),
-        )
-        .await
-        .map_err(|_| anyhow::anyhow!("register_transfer_remote timed out"))??
-        .map_err(|e| anyhow::anyhow!(e))?;
+        let parallelism =
+            hyperactor_config::global::get(crate::config::RDMA_TCP_FALLBACK_PARALLELISM);
+        let retries = hyperactor_config::global::get(crate::config::RDMA_TCP_CHUNK_RETRIES);
+        let chunk_timeout = hyperactor_config::global::get(crate::config::RDMA_TCP_CHUNK_TIMEOUT);
+
+        let mut chunks: Vec<usize> = (0..total_chunks).collect();
+        let mut rounds = 0;
+        while !chunks.is_empty() {
+            let round_size = chunks.len();
+            match op.op_type {
+                RdmaOpType::WriteFromLocal => {
+                    self.0.send(
+                        cx,
+                        ExecuteTransferLocal {
+                            transfer_id,
+                            local_memory: op.local_memory.clone(),
+                            chunk_size,
+                            chunks,
+                            dest_addr: dest_addr.clone(),
+                        },
+                    )?;
+                }
+                RdmaOpType::ReadIntoLocal => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    tokio_timeout(
+                        remaining,
+                        op.remote_tcp_manager.execute_transfer_remote(
+                            cx,
+                            transfer_id,
+                            op.remote_buf_id,
+                            chunk_size,
+                            chunks,
+                            dest_addr.clone(),
+                        ),
+                    )
+                    .await
+                    .map_err(|_| anyhow::anyhow!("execute_transfer_remote timed out"))??
+                    .map_err(|e| anyhow::anyhow!(e))?;
+                }
+            }
 
-        let dest_addr = tokio_timeout(
-            deadline.saturating_duration_since(Instant::now()),
-            op.remote_tcp_manager.get_channel_address(cx),
-        )
-        .await
-        .map_err(|_| anyhow::anyhow!("get_channel_address timed out"))??
-        .ok_or_else(|| anyhow::anyhow!("remote does not have parallel channels enabled"))?;
+            // Each channel carries its share of the round one chunk at a
+            // time.
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let round_timeout = chunk_timeout
+                .saturating_mul(
+                    round_size
+                        .div_ceil(parallelism)
+                        .try_into()
+                        .unwrap_or(u32::MAX),
+                )
+                .min(remaining);
+            chunks = match tokio_timeout(round_timeout, done_rx.recv()).await {
+                Ok(result) => result?.map_err(|e| anyhow::anyhow!(e))?,
+                Err(_) => {
+                    if Instant::now() >= deadline {
+                        anyhow::bail!("{what} timed out");
+                    }
+                    let query_timeout = deadline
+                        .saturating_duration_since(Instant::now())
+                        .min(chunk_timeout);
+                    let missing = match op.op_type {
+                        RdmaOpType::WriteFromLocal => {
+                            tokio_timeout(
+                                query_timeout,
+                                op.remote_tcp_manager.query_transfer(cx, transfer_id),
+                            )
+                            .await
+                        }
+                        RdmaOpType::ReadIntoLocal => {
+                            tokio_timeout(query_timeout, self.0.query_transfer(cx, transfer_id))
+                                .await
+                        }
+                    };
+                    missing.map_err(|_| anyhow::anyhow!("query_transfer timed out"))??
+                }
+            };
 
-        self.0.send(
-            cx,
-            ExecuteTransferLocal {
-                transfer_id,
-                local_memory: op.local_memory.clone(),
-                chunk_size,
-                dest_addr,
-            },
-        )?;
+            if !chunks.is_empty() {
+                rounds += 1;
+                anyhow::ensure!(
+                    rounds <= retries,
+                    "{what} failed: {} chunks still missing after {retries} retries",
+                    chunks.len()
+                );
+                tracing::warn!(
+                    "{what} transfer {transfer_id}: resending {} chunks (retry {rounds})",
+                    chunks.len()
+                );
+            }
+        }
 
-        let remaining = deadline.saturating_duration_since(Instant::now());
-        let result = tokio_timeout(remaining, done_rx.recv())
-            .await
-            .map_err(|_| anyhow::anyhow!("parallel write timed out"))?
-            .map_err(|e| anyhow::anyhow!(e))?;
-        result.map_err(|e| anyhow::anyhow!(e))
+        Ok(())
     }
 
-    /// Execute a parallel read: register the transfer locally, then
-    /// ask the remote side to push chunks to our channel.
-    async fn execute_parallel_read(
+    /// Execute a write operation: read local memory in chunks and write
+    /// them into the remote buffer via actor messages.
+    async fn execute_write(
         &self,
         cx: &(impl context::Actor + Send + Sync),
         op: &TcpOp,
@@ -725,91 +1170,133 @@ impl TcpBackend {
         deadline: Instant,
     ) -> Result<()> {
         let size = op.local_memory.size();
-        let total_chunks = size.div_ceil(chunk_size);
+        let mut offset = 0;
 
-        let (done_handle, done_rx) = hyperactor::mailbox::open_once_port::<Result<(), String>>(cx);
-        let done_ref = done_handle.bind();
+        while offset < size {
+            if Instant::now() >= deadline {
+                anyhow::bail!("tcp write timed out");
+            }
 
-        let (id_handle, id_rx) = hyperactor::mailbox::open_once_port::<usize>(cx);
+            let len = std::cmp::min(chunk_size, size - offset);
 
-        self.0.send(
-            cx,
-            RegisterTransferLocal {
-                local_memory: op.local_memory.clone(),
-                total_chunks,
-                done: done_ref,
-                reply: id_handle,
-            },
-        )?;
-
-        let transfer_id = id_rx
-            .recv()
-            .await
-            .map_err(|e| anyhow::anyhow!("failed to receive transfer id: {e}"))?;
-
-        let my_channel_addr = self
-            .0
-            .get_channel_address(cx)
-            .await?
-            .ok_or_else(|| anyhow::anyhow!("local parallel channels not enabled"))?;
+            let mut buf = vec![0u8; len];
+            op.local_memory.read_at(offset, &mut buf)?;
+            let checksum = crc32fast::hash(&buf);
+            let data = Part::from(Bytes::from(buf));
 
-        let remaining = deadline.saturating_duration_since(Instant::now());
-        tokio_timeout(
-            remaining,
-            op.remote_tcp_manager.execute_transfer_remote(
-                cx,
-                transfer_id,
-                op.remote_buf_id,
-                chunk_size,
-                my_channel_addr,
-            ),
-        )
-        .await
-        .map_err(|_| anyhow::anyhow!("execute_transfer_remote timed out"))??
-        .map_err(|e| anyhow::anyhow!(e))?;
+            with_retries("tcp write chunk", deadline, || {
+                let data = data.clone();
+                async move {
+                    op.remote_tcp_manager
+                        .write_chunk(cx, op.remote_buf_id, offset, data, checksum)
+                        .await?
+                        .map_err(|e| anyhow::anyhow!(e))
+                }
+            })
+            .await?;
 
-        let remaining = deadline.saturating_duration_since(Instant::now());
-        let result = tokio_timeout(remaining, done_rx.recv())
-            .await
-            .map_err(|_| anyhow::anyhow!("parallel read timed out"))?
-            .map_err(|e| anyhow::anyhow!(e))?;
-        result.map_err(|e| anyhow::anyhow!(e))
+            offset += len;
+            report_progress(cx, op, offset, size);
+        }
+
+        Ok(())
     }
 
-    /// Execute a write operation: read local memory in chunks and write
-    /// them into the remote buffer via actor messages.
-    async fn execute_write(
+    /// Execute a scatter-gather write: pack the op's segments into
+    /// batches of at most `chunk_size` bytes and send one message per
+    /// batch.
+    async fn execute_segmented_write(
         &self,
         cx: &(impl context::Actor + Send + Sync),
         op: &TcpOp,
+        segments: &[RdmaSegment],
         chunk_size: usize,
         deadline: Instant,
     ) -> Result<()> {
-        let size = op.local_memory.size();
-        let mut offset = 0;
-
-        while offset < size {
-            let remaining = deadline.saturating_duration_since(Instant::now());
-            if remaining.is_zero() {
+        let size = segments.iter().map(|s| s.len).sum();
+        let mut bytes_done = 0;
+        for batch in batch_segments(segments, chunk_size) {
+            if Instant::now() >= deadline {
                 anyhow::bail!("tcp write timed out");
             }
 
-            let len = std::cmp::min(chunk_size, size - offset);
+            let total = batch.iter().map(|s| s.len).sum();
+            let mut buf = BytesMut::zeroed(total);
+            let mut pos = 0;
+            for segment in &batch {
+                op.local_memory
+                    .read_at(segment.local_offset, &mut buf[pos..pos + segment.len])?;
+                pos += segment.len;
+            }
+            let checksum = crc32fast::hash(&buf);
+            let data = Part::from(buf.freeze());
+            let remote_segments: Vec<_> = batch.iter().map(|s| (s.remote_offset, s.len)).collect();
+
+            with_retries("tcp write segments", deadline, || {
+                let (remote_segments, data) = (remote_segments.clone(), data.clone());
+                async move {
+                    op.remote_tcp_manager
+                        .write_segments(cx, op.remote_buf_id, remote_segments, data, checksum)
+                        .await?
+                        .map_err(|e| anyhow::anyhow!(e))
+                }
+            })
+            .await?;
 
-            let mut buf = vec![0u8; len];
-            op.local_memory.read_at(offset, &mut buf)?;
-            let data = Part::from(Bytes::from(buf));
+            bytes_done += total;
+            report_progress(cx, op, bytes_done, size);
+        }
 
-            tokio_timeout(
-                remaining,
-                op.remote_tcp_manager
-                    .write_chunk(cx, op.remote_buf_id, offset, data),
-            )
-            .await
-            .map_err(|_| anyhow::anyhow!("tcp write chunk timed out"))??
-            .map_err(|e| anyhow::anyhow!(e))?;
+        Ok(())
+    }
 
-            offset += len;
+    /// Execute a scatter-gather read: request batches of at most
+    /// `chunk_size` bytes and scatter each reply into local memory.
+    async fn execute_segmented_read(
+        &self,
+        cx: &(impl context::Actor + Send + Sync),
+        op: &TcpOp,
+        segments: &[RdmaSegment],
+        chunk_size: usize,
+        deadline: Instant,
+    ) -> Result<()> {
+        let size = segments.iter().map(|s| s.len).sum();
+        let mut bytes_done = 0;
+        for batch in batch_segments(segments, chunk_size) {
+            if Instant::now() >= deadline {
+                anyhow::bail!("tcp read timed out");
+            }
+
+            let total: usize = batch.iter().map(|s| s.len).sum();
+            let remote_segments: Vec<_> = batch.iter().map(|s| (s.remote_offset, s.len)).collect();
+            let data = with_retries("tcp read segments", deadline, || {
+                let remote_segments = remote_segments.clone();
+                async move {
+                    let data = op
+                        .remote_tcp_manager
+                        .read_segments(cx, op.remote_buf_id, remote_segments)
+                        .await?
+                        .map_err(|e| anyhow::anyhow!(e))?
+                        .into_verified_bytes()?;
+                    anyhow::ensure!(
+                        data.len() == total,
+                        "tcp read segments size mismatch: expected {total}, got {}",
+                        data.len()
+                    );
+                    Ok(data)
+                }
+            })
+            .await?;
+
+            let mut pos = 0;
+            for segment in &batch {
+                op.local_memory
+                    .write_at(segment.local_offset, &data[pos..pos + segment.len])?;
+                pos += segment.len;
+            }
+
+            bytes_done += total;
+            report_progress(cx, op, bytes_done, size);
         }
 
         Ok(())
@@ -828,52 +1315,51 @@ impl TcpBackend {
         let mut offset = 0;
 
         while offset < size {
-            let remaining = deadline.saturating_duration_since(Instant::now());
-            if remaining.is_zero() {
+            if Instant::now() >= deadline {
                 anyhow::bail!("tcp read timed out");
             }
 
             let len = std::cmp::min(chunk_size, size - offset);
 
-            let chunk = tokio_timeout(
-                remaining,
-                op.remote_tcp_manager
-                    .read_chunk(cx, op.remote_buf_id, offset, len),
-            )
-            .await
-            .map_err(|_| anyhow::anyhow!("tcp read chunk timed out"))??
-            .map_err(|e| anyhow::anyhow!(e))?;
-            let data = chunk.0.into_bytes();
-
-            anyhow::ensure!(
-                data.len() == len,
-                "tcp read chunk size mismatch: expected {len}, got {}",
-                data.len()
-            );
+            let data = with_retries("tcp read chunk", deadline, || async move {
+                let data = op
+                    .remote_tcp_manager
+                    .read_chunk(cx, op.remote_buf_id, offset, len)
+                    .await?
+                    .map_err(|e| anyhow::anyhow!(e))?
+                    .into_verified_bytes()?;
+                anyhow::ensure!(
+                    data.len() == len,
+                    "tcp read chunk size mismatch: expected {len}, got {}",
+                    data.len()
+                );
+                Ok(data)
+            })
+            .await?;
 
             op.local_memory.write_at(offset, &data)?;
 
             offset += len;
+            report_progress(cx, op, offset, size);
         }
 
         Ok(())
     }
-}
-
-#[async_trait]
-impl RdmaBackend for TcpBackend {
-    type TransportInfo = ();
 
-    /// Submit a batch of RDMA operations over TCP.
+    /// Submit a batch of RDMA operations over TCP, reporting each
+    /// operation's progress to `progress` as chunks complete.
     ///
     /// Each operation's remote buffer is resolved to its TCP backend
     /// context, then executed directly — sending chunked write/read
-    /// messages to the remote [`TcpManagerActor`].
-    async fn submit(
+    /// messages to the remote [`TcpManagerActor`]. Scatter-gather and
+    /// strided ops pack many segments into each message and always use
+    /// the actor path, regardless of parallelism.
+    pub async fn submit_with_progress(
         &mut self,
         cx: &(impl context::Actor + Send + Sync),
         ops: Vec<RdmaOp>,
         timeout: Duration,
+        progress: Option<PortRef<TcpTransferProgress>>,
     ) -> Result<()> {
         let chunk_size =
             hyperactor_config::global::get(crate::config::RDMA_MAX_CHUNK_SIZE_MB) * 1024 * 1024;
@@ -893,19 +1379,27 @@ impl RdmaBackend for TcpBackend {
                 local_memory: op.local,
                 remote_tcp_manager: remote_tcp_mgr,
                 remote_buf_id,
+                layout: op.layout,
+                progress: progress.clone(),
             };
 
-            if parallelism > 1 {
+            if !tcp_op.layout.is_contiguous() {
+                let segments = tcp_op
+                    .layout
+                    .resolve(tcp_op.local_memory.size(), op.remote.size)?;
                 match tcp_op.op_type {
                     RdmaOpType::WriteFromLocal => {
-                        self.execute_parallel_write(cx, &tcp_op, chunk_size, deadline)
+                        self.execute_segmented_write(cx, &tcp_op, &segments, chunk_size, deadline)
                             .await?;
                     }
                     RdmaOpType::ReadIntoLocal => {
-                        self.execute_parallel_read(cx, &tcp_op, chunk_size, deadline)
+                        self.execute_segmented_read(cx, &tcp_op, &segments, chunk_size, deadline)
                             .await?;
                     }
                 }
+            } else if parallelism > 1 {
+                self.execute_parallel(cx, &tcp_op, chunk_size, deadline)
+                    .await?;
             } else {
                 match tcp_op.op_type {
                     RdmaOpType::WriteFromLocal => {
@@ -921,6 +1415,23 @@ impl RdmaBackend for TcpBackend {
 
         Ok(())
     }
+}
+
+#[async_trait]
+impl RdmaBackend for TcpBackend {
+    type TransportInfo = ();
+
+    /// Submit a batch of RDMA operations over TCP.
+    ///
+    /// See [`TcpBackend::submit_with_progress`].
+    async fn submit(
+        &mut self,
+        cx: &(impl context::Actor + Send + Sync),
+        ops: Vec<RdmaOp>,
+        timeout: Duration,
+    ) -> Result<()> {
+        self.submit_with_progress(cx, ops, timeout, None).await
+    }
 
     fn transport_level(&self) -> RdmaTransportLevel {
         RdmaTransportLevel::Tcp
@@ -938,17 +1449,24 @@ mod tests {
     use std::sync::atomic::Ordering;
     use std::time::Duration;
 
+    use bytes::Bytes;
     use hyperactor::ActorHandle;
     use hyperactor::Proc;
     use hyperactor::RemoteSpawn;
     use hyperactor::channel::ChannelAddr;
     use hyperactor_config::Flattrs;
+    use serde_multipart::Part;
 
     use super::TcpBackend;
+    use super::TcpChunk;
     use super::TcpManagerActor;
+    use super::TcpTransferProgress;
     use crate::RdmaManagerMessageClient;
     use crate::RdmaOp;
+    use crate::RdmaOpLayout;
     use crate::RdmaOpType;
+    use crate::RdmaSegment;
+    use crate::RdmaStride;
     use crate::backend::RdmaBackend;
     use crate::local_memory::Keepalive;
     use crate::local_memory::KeepaliveLocalMemory;
@@ -1119,6 +1637,7 @@ mod tests {
                     op_type: RdmaOpType::WriteFromLocal,
                     local: env.local_memory.clone(),
                     remote,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 timeout,
             )
@@ -1153,6 +1672,7 @@ mod tests {
                     op_type: RdmaOpType::ReadIntoLocal,
                     local: env.local_memory.clone(),
                     remote,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 timeout,
             )
@@ -1191,6 +1711,7 @@ mod tests {
                     op_type: RdmaOpType::WriteFromLocal,
                     local: env.local_memory.clone(),
                     remote: remote.clone(),
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 timeout,
             )
@@ -1206,6 +1727,7 @@ mod tests {
                     op_type: RdmaOpType::ReadIntoLocal,
                     local: env.local_memory.clone(),
                     remote,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 timeout,
             )
@@ -1280,6 +1802,7 @@ mod tests {
                     op_type: RdmaOpType::WriteFromLocal,
                     local: env.local_memory.clone(),
                     remote,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(30),
             )
@@ -1319,6 +1842,7 @@ mod tests {
                     op_type: RdmaOpType::ReadIntoLocal,
                     local: env.local_memory.clone(),
                     remote,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(30),
             )
@@ -1333,6 +1857,92 @@ mod tests {
         Ok(())
     }
 
+    /// Strided read of a 2-D block, then a scatter write back, with
+    /// segments split across 1 MiB batches.
+    #[timed_test::async_timed_test(timeout_secs = 30)]
+    async fn test_tcp_strided_read_and_segmented_write() -> anyhow::Result<()> {
+        let config = hyperactor_config::global::lock();
+        let _guard = config.override_key(crate::config::RDMA_ALLOW_TCP_FALLBACK, true);
+        let _chunk_guard = config.override_key(crate::config::RDMA_MAX_CHUNK_SIZE_MB, 1);
+
+        let buf_size = 3 * 1024 * 512;
+        let mut envs = setup_tcp_env(buf_size).await?;
+
+        let mut src = vec![0u8; buf_size];
+        for (i, byte) in src.iter_mut().enumerate() {
+            *byte = ((i * 7 + 3) % 256) as u8;
+        }
+        envs[1].local_memory.write_at(0, &src)?;
+
+        // 1000 rows of 1200 bytes, 1536 bytes apart remotely, packed
+        // densely locally: 1.2 MB in total, so one row spans two batches.
+        let stride = RdmaStride {
+            local_offset: 0,
+            remote_offset: 0,
+            row_len: 1200,
+            rows: 1000,
+            local_stride: 1200,
+            remote_stride: 1536,
+        };
+        let remote = envs[1].rdma_remote_buf.clone();
+        let env = &mut envs[0];
+        env.tcp_backend
+            .submit(
+                &env.instance,
+                vec![RdmaOp {
+                    op_type: RdmaOpType::ReadIntoLocal,
+                    local: env.local_memory.clone(),
+                    remote: remote.clone(),
+                    layout: RdmaOpLayout::Strided(stride),
+                }],
+                Duration::from_secs(30),
+            )
+            .await?;
+
+        let mut dst = vec![0u8; buf_size];
+        env.local_memory.read_at(0, &mut dst)?;
+        for row in 0..stride.rows {
+            assert_eq!(
+                dst[row * 1200..(row + 1) * 1200],
+                src[row * 1536..row * 1536 + 1200],
+                "mismatch in row {row}"
+            );
+        }
+
+        // Scatter the first two local rows into the remote buffer
+        // swapped.
+        env.tcp_backend
+            .submit(
+                &env.instance,
+                vec![RdmaOp {
+                    op_type: RdmaOpType::WriteFromLocal,
+                    local: env.local_memory.clone(),
+                    remote,
+                    layout: RdmaOpLayout::Segments(vec![
+                        RdmaSegment {
+                            local_offset: 0,
+                            remote_offset: 1536,
+                            len: 1200,
+                        },
+                        RdmaSegment {
+                            local_offset: 1200,
+                            remote_offset: 0,
+                            len: 1200,
+                        },
+                    ]),
+                }],
+                Duration::from_secs(30),
+            )
+            .await?;
+
+        envs[1].local_memory.read_at(0, &mut dst)?;
+        assert_eq!(dst[..1200], src[1536..1536 + 1200]);
+        assert_eq!(dst[1536..1536 + 1200], src[..1200]);
+        assert_eq!(dst[1200..1536], src[1200..1536]);
+
+        Ok(())
+    }
+
     /// Multi-chunk write-then-read round-trip.
     #[timed_test::async_timed_test(timeout_secs = 30)]
     async fn test_tcp_multi_chunk_round_trip() -> anyhow::Result<()> {
@@ -1358,6 +1968,7 @@ mod tests {
                     op_type: RdmaOpType::WriteFromLocal,
                     local: env.local_memory.clone(),
                     remote: remote.clone(),
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(30),
             )
@@ -1373,6 +1984,7 @@ mod tests {
                     op_type: RdmaOpType::ReadIntoLocal,
                     local: env.local_memory.clone(),
                     remote,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(30),
             )
@@ -1434,6 +2046,7 @@ mod tests {
                     op_type: RdmaOpType::WriteFromLocal,
                     local: env.local_memory.clone(),
                     remote: remote.clone(),
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(10),
             )
@@ -1456,6 +2069,7 @@ mod tests {
                     op_type: RdmaOpType::WriteFromLocal,
                     local: env.local_memory.clone(),
                     remote: remote.clone(),
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(10),
             )
@@ -1492,6 +2106,7 @@ mod tests {
                     op_type: RdmaOpType::ReadIntoLocal,
                     local: env.local_memory.clone(),
                     remote,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(10),
             )
@@ -1683,6 +2298,7 @@ mod tests {
                     op_type: RdmaOpType::WriteFromLocal,
                     local: env.local_memory.clone(),
                     remote: remote.clone(),
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(30),
             )
@@ -1740,6 +2356,65 @@ mod tests {
         do_round_trip_test(&mut envs, buf_size, Duration::from_secs(30)).await
     }
 
+    /// Both the sequential and the parallel paths report progress up to
+    /// the full operation size.
+    #[timed_test::async_timed_test(timeout_secs = 30)]
+    async fn test_tcp_transfer_progress() -> anyhow::Result<()> {
+        let config = hyperactor_config::global::lock();
+        let _guard = config.override_key(crate::config::RDMA_ALLOW_TCP_FALLBACK, true);
+        let _chunk_guard = config.override_key(crate::config::RDMA_MAX_CHUNK_SIZE_MB, 1);
+
+        let buf_size = 3 * 1024 * 1024;
+        for parallelism in [1, 2] {
+            let _par_guard =
+                config.override_key(crate::config::RDMA_TCP_FALLBACK_PARALLELISM, parallelism);
+            let mut envs = setup_tcp_env(buf_size).await?;
+            let remote = envs[1].rdma_remote_buf.clone();
+            let env = &mut envs[0];
+
+            let (handle, mut rx) =
+                hyperactor::mailbox::open_port::<TcpTransferProgress>(&env.instance);
+            env.tcp_backend
+                .submit_with_progress(
+                    &env.instance,
+                    vec![RdmaOp {
+                        op_type: RdmaOpType::WriteFromLocal,
+                        local: env.local_memory.clone(),
+                        remote: remote.clone(),
+                        layout: RdmaOpLayout::Contiguous,
+                    }],
+                    Duration::from_secs(30),
+                    Some(handle.bind()),
+                )
+                .await?;
+
+            let mut reports = 0;
+            loop {
+                let progress = rx.recv().await?;
+                assert_eq!(progress.buf_id, remote.id);
+                assert_eq!(progress.bytes_total, buf_size);
+                reports += 1;
+                if progress.bytes_done == buf_size {
+                    break;
+                }
+            }
+            assert_eq!(reports, 3, "parallelism {parallelism}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_tcp_chunk_checksum() {
+        let chunk = TcpChunk::new(Bytes::from_static(b"hello"));
+        assert_eq!(chunk.clone().into_verified_bytes().unwrap(), "hello");
+
+        let corrupted = TcpChunk {
+            data: Part::from(Bytes::from_static(b"hellp")),
+            ..chunk
+        };
+        assert!(corrupted.into_verified_bytes().is_err());
+    }
+
     /// Same-process parallel write.
     #[timed_test::async_timed_test(timeout_secs = 30)]
     async fn test_tcp_parallel_same_process_write() -> anyhow::Result<()> {
@@ -1807,6 +2482,7 @@ mod tests {
                     op_type: RdmaOpType::WriteFromLocal,
                     local: mem_0,
                     remote: remote_1,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(30),
             ),
@@ -1816,6 +2492,7 @@ mod tests {
                     op_type: RdmaOpType::WriteFromLocal,
                     local: mem_2,
                     remote: remote_3,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(30),
             ),
@@ -1880,6 +2557,7 @@ mod tests {
                     op_type: RdmaOpType::ReadIntoLocal,
                     local: mem_0,
                     remote: remote_1,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(30),
             ),
@@ -1889,6 +2567,7 @@ mod tests {
                     op_type: RdmaOpType::ReadIntoLocal,
                     local: mem_2,
                     remote: remote_3,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(30),
             ),
@@ -1957,6 +2636,7 @@ mod tests {
                     op_type: RdmaOpType::WriteFromLocal,
                     local: mem_0,
                     remote: remote_1,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(30),
             ),
@@ -1966,6 +2646,7 @@ mod tests {
                     op_type: RdmaOpType::ReadIntoLocal,
                     local: mem_2,
                     remote: remote_3,
+                    layout: RdmaOpLayout::Contiguous,
                 }],
                 Duration::from_secs(30),
             ),
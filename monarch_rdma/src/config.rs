@@ -8,6 +8,8 @@
 
 //! RDMA configuration attributes.
 
+use std::time::Duration;
+
 use hyperactor_config::CONFIG;
 use hyperactor_config::ConfigAttr;
 use hyperactor_config::attrs::declare_attrs;
@@ -53,4 +55,35 @@ declare_attrs! {
         Some("rdma_tcp_fallback_parallelism".to_string()),
     ))
     pub attr RDMA_TCP_FALLBACK_PARALLELISM: usize = 1;
+
+    /// Number of times a failed TCP fallback chunk is retried.
+    ///
+    /// A chunk fails if it times out, fails to send, or arrives with a
+    /// bad checksum. Only the failed chunks are resent; an operation
+    /// fails once a chunk has exhausted its retries.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("MONARCH_RDMA_TCP_CHUNK_RETRIES".to_string()),
+        Some("rdma_tcp_chunk_retries".to_string()),
+    ))
+    pub attr RDMA_TCP_CHUNK_RETRIES: usize = 3;
+
+    /// How long to wait for a single TCP fallback chunk before retrying
+    /// it. Parallel transfers wait this long per chunk per channel.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("MONARCH_RDMA_TCP_CHUNK_TIMEOUT".to_string()),
+        Some("rdma_tcp_chunk_timeout".to_string()),
+    ))
+    pub attr RDMA_TCP_CHUNK_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Use the same-host shared-memory backend for co-located buffers.
+    ///
+    /// When true (the default), operations on a buffer owned by a
+    /// process on the same host copy memory directly with cross-memory
+    /// attach, falling back to the other backends if the kernel denies
+    /// access.
+    @meta(CONFIG = ConfigAttr::new(
+        Some("MONARCH_RDMA_ENABLE_SHM".to_string()),
+        Some("rdma_enable_shm".to_string()),
+    ))
+    pub attr RDMA_ENABLE_SHM: bool = true;
 }
@@ -0,0 +1,247 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Scatter-gather and strided layouts for RDMA operations.
+//!
+//! An [`RdmaOp`](crate::RdmaOp) moves bytes between one local region
+//! and one remote buffer. Its [`RdmaOpLayout`] says which bytes: the
+//! whole local region against the start of the remote buffer, an
+//! explicit list of [`RdmaSegment`]s, or a strided 2-D copy
+//! ([`RdmaStride`]), e.g. a shard of a tensor that is non-contiguous in
+//! the remote buffer.
+//!
+//! Backends consume layouts through [`RdmaOpLayout::resolve`], which
+//! validates bounds and coalesces adjacent segments, so a strided copy
+//! whose rows are back to back on both sides degrades to a single
+//! contiguous transfer.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// One contiguous piece of a vectored operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RdmaSegment {
+    /// Byte offset into the local region.
+    pub local_offset: usize,
+    /// Byte offset into the remote buffer.
+    pub remote_offset: usize,
+    /// Number of bytes to move.
+    pub len: usize,
+}
+
+/// A strided 2-D copy: `rows` runs of `row_len` bytes, where row `i`
+/// starts at `local_offset + i * local_stride` locally and
+/// `remote_offset + i * remote_stride` remotely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RdmaStride {
+    pub local_offset: usize,
+    pub remote_offset: usize,
+    pub row_len: usize,
+    pub rows: usize,
+    pub local_stride: usize,
+    pub remote_stride: usize,
+}
+
+/// Which bytes an [`RdmaOp`](crate::RdmaOp) moves.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RdmaOpLayout {
+    /// The whole local region against the start of the remote buffer.
+    #[default]
+    Contiguous,
+    /// An explicit list of segments, moved in order.
+    Segments(Vec<RdmaSegment>),
+    /// A strided 2-D copy.
+    Strided(RdmaStride),
+}
+
+impl RdmaOpLayout {
+    /// Whether this is the [`Contiguous`](Self::Contiguous) layout.
+    pub fn is_contiguous(&self) -> bool {
+        matches!(self, Self::Contiguous)
+    }
+
+    /// Expand the layout into segments for a local region of
+    /// `local_size` bytes and a remote buffer of `remote_size` bytes.
+    ///
+    /// Fails if any segment falls outside either region. Empty
+    /// segments are dropped and segments that continue the previous one
+    /// on both sides are merged.
+    pub fn resolve(
+        &self,
+        local_size: usize,
+        remote_size: usize,
+    ) -> anyhow::Result<Vec<RdmaSegment>> {
+        let segments = match self {
+            Self::Contiguous => vec![RdmaSegment {
+                local_offset: 0,
+                remote_offset: 0,
+                len: local_size,
+            }],
+            Self::Segments(segments) => segments.clone(),
+            Self::Strided(stride) => (0..stride.rows)
+                .map(|row| RdmaSegment {
+                    // Saturate so that overflowing rows fail the bounds
+                    // check below instead of wrapping.
+                    local_offset: stride
+                        .local_offset
+                        .saturating_add(row.saturating_mul(stride.local_stride)),
+                    remote_offset: stride
+                        .remote_offset
+                        .saturating_add(row.saturating_mul(stride.remote_stride)),
+                    len: stride.row_len,
+                })
+                .collect(),
+        };
+
+        let mut resolved: Vec<RdmaSegment> = Vec::with_capacity(segments.len());
+        for segment in segments {
+            let local_end = segment.local_offset.checked_add(segment.len);
+            let remote_end = segment.remote_offset.checked_add(segment.len);
+            anyhow::ensure!(
+                local_end.is_some_and(|end| end <= local_size),
+                "segment {:?} exceeds local region ({local_size} bytes)",
+                segment
+            );
+            anyhow::ensure!(
+                remote_end.is_some_and(|end| end <= remote_size),
+                "segment {:?} exceeds remote buffer ({remote_size} bytes)",
+                segment
+            );
+            if segment.len == 0 {
+                continue;
+            }
+            match resolved.last_mut() {
+                Some(last)
+                    if last.local_offset + last.len == segment.local_offset
+                        && last.remote_offset + last.len == segment.remote_offset =>
+                {
+                    last.len += segment.len;
+                }
+                _ => resolved.push(segment),
+            }
+        }
+        Ok(resolved)
+    }
+}
+
+/// Group `segments` into batches of at most `max_bytes` bytes each,
+/// splitting segments longer than `max_bytes`. Order is preserved.
+pub(crate) fn batch_segments(segments: &[RdmaSegment], max_bytes: usize) -> Vec<Vec<RdmaSegment>> {
+    let mut batches = Vec::new();
+    let mut batch = Vec::new();
+    let mut batch_bytes = 0;
+    for segment in segments {
+        let mut done = 0;
+        while done < segment.len {
+            if batch_bytes == max_bytes {
+                batches.push(std::mem::take(&mut batch));
+                batch_bytes = 0;
+            }
+            let len = std::cmp::min(segment.len - done, max_bytes - batch_bytes);
+            batch.push(RdmaSegment {
+                local_offset: segment.local_offset + done,
+                remote_offset: segment.remote_offset + done,
+                len,
+            });
+            batch_bytes += len;
+            done += len;
+        }
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(local_offset: usize, remote_offset: usize, len: usize) -> RdmaSegment {
+        RdmaSegment {
+            local_offset,
+            remote_offset,
+            len,
+        }
+    }
+
+    #[test]
+    fn test_resolve_contiguous_and_segments() {
+        assert_eq!(
+            RdmaOpLayout::Contiguous.resolve(16, 32).unwrap(),
+            vec![seg(0, 0, 16)]
+        );
+        assert!(RdmaOpLayout::Contiguous.resolve(64, 32).is_err());
+
+        let layout = RdmaOpLayout::Segments(vec![
+            seg(0, 8, 4),
+            seg(4, 12, 4),
+            seg(8, 0, 0),
+            seg(8, 0, 2),
+        ]);
+        assert_eq!(
+            layout.resolve(16, 16).unwrap(),
+            vec![seg(0, 8, 8), seg(8, 0, 2)]
+        );
+
+        assert!(
+            RdmaOpLayout::Segments(vec![seg(0, 14, 4)])
+                .resolve(16, 16)
+                .is_err()
+        );
+        assert!(
+            RdmaOpLayout::Segments(vec![seg(usize::MAX, 0, 1)])
+                .resolve(16, 16)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_resolve_strided() {
+        // Gather column block [2..4) of a 3x8 row-major remote matrix
+        // into a dense 3x2 local buffer.
+        let layout = RdmaOpLayout::Strided(RdmaStride {
+            local_offset: 0,
+            remote_offset: 2,
+            row_len: 2,
+            rows: 3,
+            local_stride: 2,
+            remote_stride: 8,
+        });
+        assert_eq!(
+            layout.resolve(6, 24).unwrap(),
+            vec![seg(0, 2, 2), seg(2, 10, 2), seg(4, 18, 2)]
+        );
+        assert!(layout.resolve(6, 19).is_err());
+
+        // Rows that are back to back on both sides coalesce.
+        let dense = RdmaOpLayout::Strided(RdmaStride {
+            local_offset: 0,
+            remote_offset: 4,
+            row_len: 4,
+            rows: 4,
+            local_stride: 4,
+            remote_stride: 4,
+        });
+        assert_eq!(dense.resolve(16, 20).unwrap(), vec![seg(0, 4, 16)]);
+    }
+
+    #[test]
+    fn test_batch_segments() {
+        let batches = batch_segments(&[seg(0, 0, 3), seg(10, 20, 6), seg(30, 40, 1)], 4);
+        assert_eq!(
+            batches,
+            vec![
+                vec![seg(0, 0, 3), seg(10, 20, 1)],
+                vec![seg(11, 21, 4)],
+                vec![seg(15, 25, 1), seg(30, 40, 1)],
+            ]
+        );
+        assert!(batch_segments(&[], 4).is_empty());
+    }
+}
@@ -22,11 +22,15 @@ pub mod backend;
 pub mod config;
 pub mod device_selection;
 pub mod efa;
+mod layout;
 pub mod local_memory;
 mod rdma_components;
 mod rdma_manager_actor;
 
 pub use backend::ibverbs::primitives::*;
+pub use layout::RdmaOpLayout;
+pub use layout::RdmaSegment;
+pub use layout::RdmaStride;
 
 /// Whether any RDMA backend is available on this system.
 ///
@@ -53,11 +57,15 @@ pub enum RdmaOpType {
 }
 
 /// A single RDMA operation to be submitted to a backend.
+///
+/// `layout` selects which bytes of `local` and `remote` are moved; see
+/// [`RdmaOpLayout`] for scatter-gather and strided copies.
 #[derive(Debug)]
 pub struct RdmaOp {
     pub op_type: RdmaOpType,
     pub local: Arc<dyn RdmaLocalMemory>,
     pub remote: RdmaRemoteBuffer,
+    pub layout: RdmaOpLayout,
 }
 
 /// Transport level, ordered slowest to fastest.
@@ -68,9 +76,12 @@ pub struct RdmaOp {
 pub enum RdmaTransportLevel {
     /// TCP/IP sockets (fallback transport).
     Tcp,
+    /// Cross-memory copies between processes on the same host. Only
+    /// reaches co-located buffers.
+    SharedMemory,
     /// RDMA NIC (RoCE, InfiniBand, EFA).
     Nic,
-    /// Direct memory access (NVLink, shared memory).
+    /// Direct memory access (NVLink).
     Memory,
 }
 
@@ -55,6 +55,7 @@ use typeuri::Named;
 
 use crate::RdmaManagerActor;
 use crate::RdmaOp;
+use crate::RdmaOpLayout;
 use crate::RdmaOpType;
 use crate::ReleaseBufferClient;
 use crate::backend::RdmaBackend;
@@ -63,6 +64,8 @@ use crate::backend::ibverbs::IbvBuffer;
 use crate::backend::ibverbs::manager_actor::IbvBackend;
 use crate::backend::ibverbs::manager_actor::IbvManagerActor;
 use crate::backend::ibverbs::manager_actor::IbvManagerMessageClient;
+use crate::backend::shm::ShmBackend;
+use crate::backend::shm::ShmBuffer;
 use crate::backend::tcp::manager_actor::TcpBackend;
 use crate::backend::tcp::manager_actor::TcpManagerActor;
 use crate::local_memory::RdmaLocalMemory;
@@ -88,6 +91,7 @@ wirevalue::register_type!(RdmaRemoteBuffer);
 #[derive(Debug)]
 pub enum RdmaLocalBackend {
     Ibv(IbvBackend),
+    Shm(ShmBackend),
     Tcp(TcpBackend),
 }
 
@@ -100,6 +104,7 @@ impl RdmaLocalBackend {
     ) -> Result<(), anyhow::Error> {
         match self {
             RdmaLocalBackend::Ibv(h) => h.submit(cx, ops, timeout).await,
+            RdmaLocalBackend::Shm(h) => h.submit(cx, ops, timeout).await,
             RdmaLocalBackend::Tcp(h) => h.submit(cx, ops, timeout).await,
         }
     }
@@ -108,7 +113,9 @@ impl RdmaLocalBackend {
 impl RdmaRemoteBuffer {
     /// Choose the best available backend for this buffer.
     ///
-    /// Prefers ibverbs when both the local and remote sides support it.
+    /// Prefers the shared-memory backend when the owner is on the same
+    /// host and the kernel permits cross-memory access, then ibverbs
+    /// when both the local and remote sides support it.
     /// Falls back to TCP when ibverbs is unavailable and
     /// [`RDMA_ALLOW_TCP_FALLBACK`](crate::config::RDMA_ALLOW_TCP_FALLBACK)
     /// is enabled.
@@ -116,6 +123,10 @@ impl RdmaRemoteBuffer {
         &self,
         client: &(impl context::Actor + Send + Sync),
     ) -> Result<RdmaLocalBackend, anyhow::Error> {
+        if let Some(shm) = self.connect_shm() {
+            return Ok(RdmaLocalBackend::Shm(shm));
+        }
+
         if self.has_ibverbs_backend() {
             if let Ok(ibv_handle) = IbvManagerActor::local_handle(client).await {
                 return Ok(RdmaLocalBackend::Ibv(IbvBackend(ibv_handle)));
@@ -143,19 +154,8 @@ impl RdmaRemoteBuffer {
         local: Arc<dyn RdmaLocalMemory>,
         timeout: u64,
     ) -> Result<bool, anyhow::Error> {
-        let mut backend = self.choose_backend(client).await?;
-        backend
-            .submit(
-                client,
-                vec![RdmaOp {
-                    op_type: RdmaOpType::WriteFromLocal,
-                    local,
-                    remote: self.clone(),
-                }],
-                Duration::from_secs(timeout),
-            )
-            .await?;
-        Ok(true)
+        self.write_from_local_with_layout(client, local, RdmaOpLayout::Contiguous, timeout)
+            .await
     }
 
     /// Pull data from this remote buffer into local memory (remote->local).
@@ -164,15 +164,54 @@ impl RdmaRemoteBuffer {
         client: &(impl context::Actor + Send + Sync),
         local: Arc<dyn RdmaLocalMemory>,
         timeout: u64,
+    ) -> Result<bool, anyhow::Error> {
+        self.read_into_local_with_layout(client, local, RdmaOpLayout::Contiguous, timeout)
+            .await
+    }
+
+    /// Push the segments of local memory selected by `layout` into this
+    /// remote buffer as a single operation.
+    pub async fn write_from_local_with_layout(
+        &self,
+        client: &(impl context::Actor + Send + Sync),
+        local: Arc<dyn RdmaLocalMemory>,
+        layout: RdmaOpLayout,
+        timeout: u64,
+    ) -> Result<bool, anyhow::Error> {
+        self.submit_op(client, RdmaOpType::WriteFromLocal, local, layout, timeout)
+            .await
+    }
+
+    /// Pull the segments of this remote buffer selected by `layout` into
+    /// local memory as a single operation.
+    pub async fn read_into_local_with_layout(
+        &self,
+        client: &(impl context::Actor + Send + Sync),
+        local: Arc<dyn RdmaLocalMemory>,
+        layout: RdmaOpLayout,
+        timeout: u64,
+    ) -> Result<bool, anyhow::Error> {
+        self.submit_op(client, RdmaOpType::ReadIntoLocal, local, layout, timeout)
+            .await
+    }
+
+    async fn submit_op(
+        &self,
+        client: &(impl context::Actor + Send + Sync),
+        op_type: RdmaOpType,
+        local: Arc<dyn RdmaLocalMemory>,
+        layout: RdmaOpLayout,
+        timeout: u64,
     ) -> Result<bool, anyhow::Error> {
         let mut backend = self.choose_backend(client).await?;
         backend
             .submit(
                 client,
                 vec![RdmaOp {
-                    op_type: RdmaOpType::ReadIntoLocal,
+                    op_type,
                     local,
                     remote: self.clone(),
+                    layout,
                 }],
                 Duration::from_secs(timeout),
             )
@@ -255,6 +294,35 @@ impl RdmaRemoteBuffer {
             })
             .ok_or_else(|| anyhow::anyhow!("tcp backend not found for buffer: {:?}", self))
     }
+
+    /// The shared-memory backend for this buffer, if it is usable from
+    /// this process. [`ShmBackend::connect`] runs once per buffer; its
+    /// result is cached alongside the buffer's context.
+    fn connect_shm(&self) -> Option<ShmBackend> {
+        if !hyperactor_config::global::get(crate::config::RDMA_ENABLE_SHM) {
+            return None;
+        }
+        self.backends.iter().find_map(|b| match b {
+            RdmaRemoteBackendContext::SharedMemory(buffer, backend) => {
+                backend.get_or_init(|| ShmBackend::connect(buffer)).clone()
+            }
+            _ => None,
+        })
+    }
+
+    /// Extract the shared-memory backend context from this buffer.
+    ///
+    /// Present only if the owner registered host memory; whether it is
+    /// usable from this process is decided by [`ShmBackend::connect`].
+    pub fn resolve_shm(&self) -> Result<ShmBuffer, anyhow::Error> {
+        self.backends
+            .iter()
+            .find_map(|b| match b {
+                RdmaRemoteBackendContext::SharedMemory(buffer, _) => Some(buffer.clone()),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow::anyhow!("shm backend not found for buffer: {:?}", self))
+    }
 }
 
 /// Utility to validate execution context.
@@ -21,10 +21,13 @@
 //! - Delegates MR registration, QP management, and data movement to the
 //!   ibverbs backend ([`IbvManagerActor`]) when available, or falls back to
 //!   the TCP backend ([`TcpManagerActor`]).
+//! - Advertises host buffers to same-host peers through the shared-memory
+//!   backend ([`ShmBuffer`]), which needs no actor.
 //! - Handles remote [`ReleaseBuffer`] requests to clean up registrations.
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::OnceLock;
 
 use async_trait::async_trait;
 use hyperactor::Actor;
@@ -49,6 +52,8 @@ use crate::backend::RdmaRemoteBackendContext;
 use crate::backend::ibverbs::manager_actor::IbvManagerActor;
 use crate::backend::ibverbs::manager_actor::IbvManagerMessageClient;
 use crate::backend::ibverbs::primitives::IbvConfig;
+use crate::backend::shm::ShmBuffer;
+use crate::backend::shm::ShmRegistration;
 use crate::backend::tcp::manager_actor::TcpManagerActor;
 use crate::local_memory::RdmaLocalMemory;
 use crate::rdma_components::RdmaRemoteBuffer;
@@ -154,6 +159,8 @@ impl<A: Actor> RdmaBackendActor<A> {
 pub struct RdmaManagerActor {
     next_remote_buf_id: usize,
     buffers: HashMap<usize, Arc<dyn RdmaLocalMemory>>,
+    /// Same-host advertisements of `buffers`, dropped on release.
+    shm_registrations: HashMap<usize, ShmRegistration>,
     ibverbs: Option<RdmaBackendActor<IbvManagerActor>>,
     tcp: RdmaBackendActor<TcpManagerActor>,
 }
@@ -208,6 +215,7 @@ impl RemoteSpawn for RdmaManagerActor {
         Ok(Self {
             next_remote_buf_id: 0,
             buffers: HashMap::new(),
+            shm_registrations: HashMap::new(),
             ibverbs: ibv,
             tcp,
         })
@@ -265,6 +273,9 @@ impl GetTcpActorRefHandler for RdmaManagerActor {
 #[hyperactor::handle(ReleaseBuffer)]
 impl ReleaseBufferHandler for RdmaManagerActor {
     async fn release_buffer(&mut self, cx: &Context<Self>, id: usize) -> Result<(), anyhow::Error> {
+        // Invalidate same-host peers' access before the memory can be
+        // freed.
+        self.shm_registrations.remove(&id);
         self.buffers.remove(&id);
         if let Some(ibv) = &self.ibverbs {
             ibv.handle().release_buffer(cx, id).await?;
@@ -285,10 +296,20 @@ impl RdmaManagerMessageHandler for RdmaManagerActor {
         self.next_remote_buf_id += 1;
         let size = local.size();
 
-        self.buffers.insert(remote_buf_id, local);
-
         let mut backends = Vec::new();
 
+        if hyperactor_config::global::get(crate::config::RDMA_ENABLE_SHM)
+            && let Some((buffer, registration)) = ShmBuffer::for_local(local.as_ref())
+        {
+            backends.push(RdmaRemoteBackendContext::SharedMemory(
+                buffer,
+                Arc::new(OnceLock::new()),
+            ));
+            self.shm_registrations.insert(remote_buf_id, registration);
+        }
+
+        self.buffers.insert(remote_buf_id, local);
+
         if let Some(ibv) = &self.ibverbs {
             backends.push(RdmaRemoteBackendContext::Ibverbs(
                 ibv.handle().bind(),
@@ -0,0 +1,197 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Mapping from Rust field types to Arrow columns.
+//!
+//! `#[derive(RecordBatchRow)]` emits one column per field and
+//! delegates the Arrow type and array construction to the field
+//! type's [`RecordBatchColumn`] impl. Implementations exist for the
+//! scalar types, `String`, `SystemTime`, `Duration` and `Vec<T>`;
+//! the derive itself implements the trait for row structs (as struct
+//! arrays) and fieldless enums (as dictionary-encoded strings), so
+//! those can be nested in other rows.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use datafusion::arrow::array::ArrayRef;
+use datafusion::arrow::array::BooleanArray;
+use datafusion::arrow::array::DurationMicrosecondArray;
+use datafusion::arrow::array::Float32Array;
+use datafusion::arrow::array::Float64Array;
+use datafusion::arrow::array::Int32Array;
+use datafusion::arrow::array::Int64Array;
+use datafusion::arrow::array::ListArray;
+use datafusion::arrow::array::StringArray;
+use datafusion::arrow::array::TimestampMicrosecondArray;
+use datafusion::arrow::array::UInt32Array;
+use datafusion::arrow::array::UInt64Array;
+use datafusion::arrow::buffer::NullBuffer;
+use datafusion::arrow::buffer::OffsetBuffer;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::arrow::datatypes::Field;
+use datafusion::arrow::datatypes::TimeUnit;
+use datafusion::arrow::error::ArrowError;
+
+/// A Rust type that can be stored as one Arrow column.
+///
+/// Nullability is a property of the field rather than of the type:
+/// a field of type `Option<T>` produces a nullable column of `T`'s
+/// data type, built with [`to_array`](Self::to_array).
+pub trait RecordBatchColumn: Sized {
+    /// The Arrow data type of the column.
+    fn data_type() -> DataType;
+
+    /// Build a column from nullable values.
+    fn to_array(values: Vec<Option<Self>>) -> Result<ArrayRef, ArrowError>;
+
+    /// Build a column from non-null values.
+    ///
+    /// The default wraps every value in `Some`; implementations
+    /// override it when Arrow offers a cheaper non-null constructor.
+    fn to_required_array(values: Vec<Self>) -> Result<ArrayRef, ArrowError> {
+        Self::to_array(values.into_iter().map(Some).collect())
+    }
+}
+
+/// Build a validity buffer from per-row flags, or `None` if every row
+/// is valid.
+pub fn null_buffer(validity: Vec<bool>) -> Option<NullBuffer> {
+    if validity.iter().all(|valid| *valid) {
+        None
+    } else {
+        Some(NullBuffer::from(validity))
+    }
+}
+
+macro_rules! impl_from_vec_column {
+    ($ty:ty, $data_type:expr, $array:ty) => {
+        impl RecordBatchColumn for $ty {
+            fn data_type() -> DataType {
+                $data_type
+            }
+
+            fn to_array(values: Vec<Option<Self>>) -> Result<ArrayRef, ArrowError> {
+                Ok(Arc::new(<$array>::from(values)))
+            }
+
+            fn to_required_array(values: Vec<Self>) -> Result<ArrayRef, ArrowError> {
+                Ok(Arc::new(<$array>::from(values)))
+            }
+        }
+    };
+}
+
+impl_from_vec_column!(u64, DataType::UInt64, UInt64Array);
+impl_from_vec_column!(u32, DataType::UInt32, UInt32Array);
+impl_from_vec_column!(i64, DataType::Int64, Int64Array);
+impl_from_vec_column!(i32, DataType::Int32, Int32Array);
+impl_from_vec_column!(String, DataType::Utf8, StringArray);
+impl_from_vec_column!(bool, DataType::Boolean, BooleanArray);
+impl_from_vec_column!(f64, DataType::Float64, Float64Array);
+impl_from_vec_column!(f32, DataType::Float32, Float32Array);
+
+/// Whole microseconds in `duration`, or an error if they do not fit
+/// in an `i64` (about 292,000 years).
+fn duration_to_micros(duration: Duration) -> Result<i64, ArrowError> {
+    i64::try_from(duration.as_micros()).map_err(|_| {
+        ArrowError::InvalidArgumentError(format!(
+            "{:?} overflows a 64-bit microsecond column",
+            duration
+        ))
+    })
+}
+
+/// Microseconds since the Unix epoch; times before the epoch are
+/// negative.
+fn system_time_to_micros(time: SystemTime) -> Result<i64, ArrowError> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => duration_to_micros(after),
+        Err(before) => duration_to_micros(before.duration()).map(|micros| -micros),
+    }
+}
+
+/// Stored as `Timestamp(Microsecond, None)`: microseconds since the
+/// Unix epoch, without a time zone.
+impl RecordBatchColumn for SystemTime {
+    fn data_type() -> DataType {
+        DataType::Timestamp(TimeUnit::Microsecond, None)
+    }
+
+    fn to_array(values: Vec<Option<Self>>) -> Result<ArrayRef, ArrowError> {
+        Ok(Arc::new(TimestampMicrosecondArray::from(
+            values
+                .into_iter()
+                .map(|v| v.map(system_time_to_micros).transpose())
+                .collect::<Result<Vec<_>, _>>()?,
+        )))
+    }
+
+    fn to_required_array(values: Vec<Self>) -> Result<ArrayRef, ArrowError> {
+        Ok(Arc::new(TimestampMicrosecondArray::from(
+            values
+                .into_iter()
+                .map(system_time_to_micros)
+                .collect::<Result<Vec<_>, _>>()?,
+        )))
+    }
+}
+
+/// Stored as `Duration(Microsecond)`.
+impl RecordBatchColumn for Duration {
+    fn data_type() -> DataType {
+        DataType::Duration(TimeUnit::Microsecond)
+    }
+
+    fn to_array(values: Vec<Option<Self>>) -> Result<ArrayRef, ArrowError> {
+        Ok(Arc::new(DurationMicrosecondArray::from(
+            values
+                .into_iter()
+                .map(|v| v.map(duration_to_micros).transpose())
+                .collect::<Result<Vec<_>, _>>()?,
+        )))
+    }
+
+    fn to_required_array(values: Vec<Self>) -> Result<ArrayRef, ArrowError> {
+        Ok(Arc::new(DurationMicrosecondArray::from(
+            values
+                .into_iter()
+                .map(duration_to_micros)
+                .collect::<Result<Vec<_>, _>>()?,
+        )))
+    }
+}
+
+/// Stored as a `List` of non-null items. A null `Option<Vec<T>>` is a
+/// null list; an empty vector is an empty list.
+impl<T: RecordBatchColumn> RecordBatchColumn for Vec<T> {
+    fn data_type() -> DataType {
+        DataType::List(Arc::new(Field::new_list_field(T::data_type(), false)))
+    }
+
+    fn to_array(values: Vec<Option<Self>>) -> Result<ArrayRef, ArrowError> {
+        let mut validity = Vec::with_capacity(values.len());
+        let mut lengths = Vec::with_capacity(values.len());
+        let mut items = Vec::new();
+        for value in values {
+            validity.push(value.is_some());
+            let value = value.unwrap_or_default();
+            lengths.push(value.len());
+            items.extend(value);
+        }
+        let field = Arc::new(Field::new_list_field(T::data_type(), false));
+        Ok(Arc::new(ListArray::try_new(
+            field,
+            OffsetBuffer::from_lengths(lengths),
+            T::to_required_array(items)?,
+            null_buffer(validity),
+        )?))
+    }
+}
@@ -12,7 +12,14 @@
 //! `#[derive(RecordBatchRow)]` (from `monarch_record_batch_macros`).
 //! It is extracted here so the derive macro can be used from any
 //! Monarch crate, not only `monarch_distributed_telemetry`.
+//!
+//! [`RecordBatchColumn`] maps individual field types to Arrow columns;
+//! the derive delegates to it for every field.
+
+mod column;
 
+pub use column::RecordBatchColumn;
+pub use column::null_buffer;
 use datafusion::arrow::error::ArrowError;
 use datafusion::arrow::record_batch::RecordBatch;
 // Re-export the derive macro so consumers only need to depend on this
@@ -0,0 +1,210 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ * All rights reserved.
+ *
+ * This source code is licensed under the BSD-style license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Tests for `#[derive(RecordBatchRow)]` column mappings.
+
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use datafusion::arrow::array::Array;
+use datafusion::arrow::array::DictionaryArray;
+use datafusion::arrow::array::DurationMicrosecondArray;
+use datafusion::arrow::array::ListArray;
+use datafusion::arrow::array::StringArray;
+use datafusion::arrow::array::StructArray;
+use datafusion::arrow::array::TimestampMicrosecondArray;
+use datafusion::arrow::array::UInt64Array;
+use datafusion::arrow::datatypes::DataType;
+use datafusion::arrow::datatypes::Int32Type;
+use datafusion::arrow::datatypes::TimeUnit;
+use monarch_record_batch::RecordBatchBuffer;
+use monarch_record_batch::RecordBatchRow;
+
+#[derive(RecordBatchRow)]
+enum Status {
+    Running,
+    #[record_batch(rename = "stopped")]
+    Stopped,
+}
+
+#[derive(RecordBatchRow)]
+struct Location {
+    host: String,
+    gpu: Option<u32>,
+}
+
+#[derive(RecordBatchRow)]
+struct Row {
+    #[record_batch(rename = "row_id")]
+    id: u64,
+    started_at: SystemTime,
+    uptime: Option<Duration>,
+    status: Status,
+    tags: Vec<String>,
+    location: Option<Location>,
+    #[record_batch(skip)]
+    #[allow(dead_code)]
+    scratch: Vec<u8>,
+}
+
+fn row(id: u64, status: Status, location: Option<Location>) -> Row {
+    Row {
+        id,
+        started_at: UNIX_EPOCH + Duration::from_micros(id * 10),
+        uptime: (id % 2 == 0).then(|| Duration::from_micros(id)),
+        status,
+        tags: (0..id).map(|i| format!("t{}", i)).collect(),
+        location,
+        scratch: vec![0; 4],
+    }
+}
+
+#[test]
+fn test_schema() {
+    let schema = RowBuffer::schema();
+    let names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "row_id",
+            "started_at",
+            "uptime",
+            "status",
+            "tags",
+            "location"
+        ]
+    );
+
+    assert_eq!(
+        schema.field(1).data_type(),
+        &DataType::Timestamp(TimeUnit::Microsecond, None)
+    );
+    assert!(!schema.field(1).is_nullable());
+    assert_eq!(
+        schema.field(2).data_type(),
+        &DataType::Duration(TimeUnit::Microsecond)
+    );
+    assert!(schema.field(2).is_nullable());
+    assert_eq!(
+        schema.field(3).data_type(),
+        &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+    );
+    assert!(matches!(schema.field(4).data_type(), DataType::List(_)));
+    match schema.field(5).data_type() {
+        DataType::Struct(fields) => {
+            assert_eq!(fields.len(), 2);
+            assert_eq!(fields[0].name(), "host");
+            assert!(fields[1].is_nullable());
+        }
+        other => panic!("expected struct, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_drain_to_record_batch() {
+    let mut buffer = RowBuffer::default();
+    buffer.insert(row(
+        1,
+        Status::Running,
+        Some(Location {
+            host: "a".into(),
+            gpu: Some(3),
+        }),
+    ));
+    buffer.insert(row(2, Status::Stopped, None));
+    assert_eq!(buffer.len(), 2);
+
+    let batch = buffer.drain_to_record_batch().unwrap();
+    assert!(buffer.is_empty());
+    assert_eq!(batch.num_rows(), 2);
+
+    let ids = batch
+        .column_by_name("row_id")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<UInt64Array>()
+        .unwrap();
+    assert_eq!(ids.values(), &[1, 2]);
+
+    let started = batch
+        .column_by_name("started_at")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<TimestampMicrosecondArray>()
+        .unwrap();
+    assert_eq!(started.value(1), 20);
+
+    let uptime = batch
+        .column_by_name("uptime")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<DurationMicrosecondArray>()
+        .unwrap();
+    assert!(uptime.is_null(0));
+    assert_eq!(uptime.value(1), 2);
+
+    let status = batch
+        .column_by_name("status")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<DictionaryArray<Int32Type>>()
+        .unwrap();
+    let status_values = status
+        .values()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    let decoded: Vec<&str> = status
+        .keys()
+        .iter()
+        .map(|k| status_values.value(k.unwrap() as usize))
+        .collect();
+    assert_eq!(decoded, vec!["Running", "stopped"]);
+
+    let tags = batch
+        .column_by_name("tags")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<ListArray>()
+        .unwrap();
+    assert_eq!(tags.value_length(0), 1);
+    assert_eq!(tags.value_length(1), 2);
+
+    let location = batch
+        .column_by_name("location")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<StructArray>()
+        .unwrap();
+    assert!(location.is_valid(0));
+    assert!(location.is_null(1));
+    let hosts = location
+        .column_by_name("host")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(hosts.value(0), "a");
+}
+
+#[test]
+fn test_time_overflow_is_an_error() {
+    let mut buffer = RowBuffer::default();
+    let mut overflowing = row(2, Status::Running, None);
+    overflowing.uptime = Some(Duration::MAX);
+    buffer.insert(overflowing);
+    assert!(buffer.drain_to_record_batch().is_err());
+
+    let mut buffer = RowBuffer::default();
+    let mut overflowing = row(1, Status::Running, None);
+    // About 35 million years: representable, but not in microseconds.
+    overflowing.started_at = UNIX_EPOCH + Duration::from_secs(1 << 50);
+    buffer.insert(overflowing);
+    assert!(buffer.drain_to_record_batch().is_err());
+}
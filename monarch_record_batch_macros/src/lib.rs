@@ -15,46 +15,86 @@
 //! struct Span {
 //!     id: u64,
 //!     name: String,
-//!     timestamp: i64,
+//!     timestamp: SystemTime,
 //!     parent_id: Option<u64>,
+//!     #[record_batch(rename = "span_kind")]
+//!     kind: SpanKind,
+//!     tags: Vec<String>,
+//!     #[record_batch(skip)]
+//!     scratch: Vec<u8>,
+//! }
+//!
+//! #[derive(RecordBatchRow)]
+//! enum SpanKind {
+//!     Client,
+//!     Server,
 //! }
 //! ```
 //!
-//! This generates:
+//! For a struct this generates:
 //! - `SpanBuffer` struct with `Vec<T>` for each field
 //! - `insert(&mut self, row: Span)` method
-//! - `schema() -> SchemaRef` method
+//! - `fields() -> Vec<Field>` and `schema() -> SchemaRef` methods
 //! - `impl monarch_record_batch::RecordBatchBuffer` with `len()` and
 //!   `drain_to_record_batch()` methods
+//! - `impl monarch_record_batch::RecordBatchColumn for Span`, so the
+//!   row can be nested in another row as an Arrow struct column
+//!
+//! For a fieldless enum it generates only the `RecordBatchColumn`
+//! impl, storing the variant name as a dictionary-encoded string.
+//!
+//! Each field's Arrow type comes from its type's `RecordBatchColumn`
+//! impl; `Option<T>` fields are nullable columns of `T`. Supported
+//! field types are the integer and float scalars, `bool`, `String`,
+//! `SystemTime` (timestamp), `Duration`, `Vec<T>` (list), and other
+//! derived rows and enums.
 //!
-//! The consumer crate must depend on `monarch_record_batch` (for the trait)
+//! Field and variant attributes:
+//! - `#[record_batch(rename = "name")]` sets the column (or enum
+//!   value) name.
+//! - `#[record_batch(skip)]` omits a struct field from the table.
+//!
+//! The consumer crate must depend on `monarch_record_batch` (for the traits)
 //! and `datafusion` (for Arrow types used in the generated code).
 
 use proc_macro::TokenStream;
 use quote::format_ident;
 use quote::quote;
+use syn::Attribute;
 use syn::DeriveInput;
 use syn::Field;
 use syn::Fields;
+use syn::LitStr;
 use syn::Type;
 use syn::parse_macro_input;
 
 /// Derive macro for generating Arrow RecordBatch buffer types.
-#[proc_macro_derive(RecordBatchRow)]
+#[proc_macro_derive(RecordBatchRow, attributes(record_batch))]
 pub fn derive_record_batch_row(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let name = &input.ident;
-    let buffer_name = format_ident!("{}Buffer", name);
 
-    let fields = match &input.data {
+    match &input.data {
         syn::Data::Struct(data) => match &data.fields {
-            Fields::Named(fields) => &fields.named,
+            Fields::Named(fields) => derive_struct(&input, fields),
             _ => panic!("RecordBatchRow only supports named fields"),
         },
-        _ => panic!("RecordBatchRow only supports structs"),
-    };
+        syn::Data::Enum(data) => derive_enum(&input, data),
+        _ => panic!("RecordBatchRow only supports structs and fieldless enums"),
+    }
+}
+
+fn derive_struct(input: &DeriveInput, fields: &syn::FieldsNamed) -> TokenStream {
+    let name = &input.ident;
+    let buffer_name = format_ident!("{}Buffer", name);
 
-    let field_info: Vec<FieldInfo> = fields.iter().map(FieldInfo::from_field).collect();
+    let field_info: Vec<FieldInfo> = fields
+        .named
+        .iter()
+        .filter_map(FieldInfo::from_field)
+        .collect();
+    if field_info.is_empty() {
+        panic!("RecordBatchRow requires at least one non-skipped field");
+    }
 
     let buffer_fields = field_info.iter().map(|f| {
         let name = &f.name;
@@ -68,21 +108,51 @@ pub fn derive_record_batch_row(input: TokenStream) -> TokenStream {
     });
 
     let schema_fields = field_info.iter().map(|f| {
-        let field_name_str = f.name.to_string();
+        let column_name = &f.column_name;
         let nullable = f.nullable;
-        let data_type = &f.arrow_data_type;
+        let inner_ty = &f.inner_type;
         quote! {
-            datafusion::arrow::datatypes::Field::new(#field_name_str, #data_type, #nullable)
+            datafusion::arrow::datatypes::Field::new(
+                #column_name,
+                <#inner_ty as monarch_record_batch::RecordBatchColumn>::data_type(),
+                #nullable,
+            )
         }
     });
 
     let column_conversions = field_info.iter().map(|f| {
         let name = &f.name;
-        let array_conversion = &f.array_conversion;
+        let conversion = f.conversion();
+        quote! { #conversion(std::mem::take(&mut self.#name))? }
+    });
+
+    // Nested (struct-array) conversion: split each optional row into
+    // per-field optional values, then build the children.
+    let nested_vecs = field_info.iter().map(|f| {
+        let column = f.column_ident();
+        let inner_ty = &f.inner_type;
         quote! {
-            std::sync::Arc::new(#array_conversion(std::mem::take(&mut self.#name)))
+            let mut #column: Vec<Option<#inner_ty>> = Vec::with_capacity(values.len());
         }
     });
+    let nested_some_pushes = field_info.iter().map(|f| {
+        let name = &f.name;
+        let column = f.column_ident();
+        if f.nullable {
+            quote! { #column.push(row.#name); }
+        } else {
+            quote! { #column.push(Some(row.#name)); }
+        }
+    });
+    let nested_none_pushes = field_info.iter().map(|f| {
+        let column = f.column_ident();
+        quote! { #column.push(None); }
+    });
+    let nested_children = field_info.iter().map(|f| {
+        let column = f.column_ident();
+        let inner_ty = &f.inner_type;
+        quote! { <#inner_ty as monarch_record_batch::RecordBatchColumn>::to_array(#column)? }
+    });
 
     let first_field = &field_info[0].name;
 
@@ -97,10 +167,16 @@ pub fn derive_record_batch_row(input: TokenStream) -> TokenStream {
                 #(#insert_pushes)*
             }
 
-            pub fn schema() -> datafusion::arrow::datatypes::SchemaRef {
-                std::sync::Arc::new(datafusion::arrow::datatypes::Schema::new(vec![
+            pub fn fields() -> Vec<datafusion::arrow::datatypes::Field> {
+                vec![
                     #(#schema_fields,)*
-                ]))
+                ]
+            }
+
+            pub fn schema() -> datafusion::arrow::datatypes::SchemaRef {
+                std::sync::Arc::new(datafusion::arrow::datatypes::Schema::new(
+                    #buffer_name::fields(),
+                ))
             }
         }
 
@@ -117,40 +193,167 @@ pub fn derive_record_batch_row(input: TokenStream) -> TokenStream {
                 datafusion::arrow::record_batch::RecordBatch::try_new(schema, columns)
             }
         }
+
+        impl monarch_record_batch::RecordBatchColumn for #name {
+            fn data_type() -> datafusion::arrow::datatypes::DataType {
+                datafusion::arrow::datatypes::DataType::Struct(#buffer_name::fields().into())
+            }
+
+            fn to_array(
+                values: Vec<Option<Self>>,
+            ) -> Result<datafusion::arrow::array::ArrayRef, datafusion::arrow::error::ArrowError> {
+                let mut validity = Vec::with_capacity(values.len());
+                #(#nested_vecs)*
+                for value in values {
+                    validity.push(value.is_some());
+                    match value {
+                        Some(row) => {
+                            #(#nested_some_pushes)*
+                        }
+                        None => {
+                            #(#nested_none_pushes)*
+                        }
+                    }
+                }
+                let array = datafusion::arrow::array::StructArray::try_new(
+                    #buffer_name::fields().into(),
+                    vec![#(#nested_children,)*],
+                    monarch_record_batch::null_buffer(validity),
+                )?;
+                Ok(std::sync::Arc::new(array))
+            }
+        }
     };
 
     TokenStream::from(expanded)
 }
 
+fn derive_enum(input: &DeriveInput, data: &syn::DataEnum) -> TokenStream {
+    let name = &input.ident;
+
+    let arms = data.variants.iter().map(|variant| {
+        if !matches!(variant.fields, Fields::Unit) {
+            panic!("RecordBatchRow only supports fieldless enums");
+        }
+        let ident = &variant.ident;
+        let attrs = ColumnAttrs::parse(&variant.attrs);
+        if attrs.skip {
+            panic!("#[record_batch(skip)] is not supported on enum variants");
+        }
+        let value = attrs.rename.unwrap_or_else(|| ident.to_string());
+        quote! { #name::#ident => #value }
+    });
+
+    let expanded = quote! {
+        impl monarch_record_batch::RecordBatchColumn for #name {
+            fn data_type() -> datafusion::arrow::datatypes::DataType {
+                datafusion::arrow::datatypes::DataType::Dictionary(
+                    Box::new(datafusion::arrow::datatypes::DataType::Int32),
+                    Box::new(datafusion::arrow::datatypes::DataType::Utf8),
+                )
+            }
+
+            fn to_array(
+                values: Vec<Option<Self>>,
+            ) -> Result<datafusion::arrow::array::ArrayRef, datafusion::arrow::error::ArrowError> {
+                let array: datafusion::arrow::array::DictionaryArray<
+                    datafusion::arrow::datatypes::Int32Type,
+                > = values
+                    .iter()
+                    .map(|value| value.as_ref().map(|value| match value {
+                        #(#arms,)*
+                    }))
+                    .collect();
+                Ok(std::sync::Arc::new(array))
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Parsed `#[record_batch(...)]` attributes on a field or variant.
+#[derive(Default)]
+struct ColumnAttrs {
+    rename: Option<String>,
+    skip: bool,
+}
+
+impl ColumnAttrs {
+    fn parse(attrs: &[Attribute]) -> Self {
+        let mut parsed = ColumnAttrs::default();
+        for attr in attrs {
+            if !attr.path().is_ident("record_batch") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    parsed.skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    parsed.rename = Some(value.value());
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename = \"...\"` or `skip`"))
+                }
+            })
+            .unwrap_or_else(|e| panic!("invalid #[record_batch] attribute: {}", e));
+        }
+        parsed
+    }
+}
+
 struct FieldInfo {
     name: syn::Ident,
+    column_name: String,
     vec_type: proc_macro2::TokenStream,
+    inner_type: Type,
     nullable: bool,
-    arrow_data_type: proc_macro2::TokenStream,
-    array_conversion: proc_macro2::TokenStream,
 }
 
 impl FieldInfo {
-    fn from_field(field: &Field) -> Self {
+    /// Returns `None` for fields marked `#[record_batch(skip)]`.
+    fn from_field(field: &Field) -> Option<Self> {
+        let attrs = ColumnAttrs::parse(&field.attrs);
+        if attrs.skip {
+            return None;
+        }
+
         let name = field.ident.clone().expect("field must have name");
+        let column_name = attrs.rename.unwrap_or_else(|| name.to_string());
         let (inner_ty, nullable) = extract_option_inner(&field.ty);
 
-        let (vec_type, arrow_data_type, array_conversion) = if nullable {
-            let vec_ty = quote! { Vec<Option<#inner_ty>> };
-            let (data_type, array_conv) = get_arrow_type_and_conversion(inner_ty);
-            (vec_ty, data_type, array_conv)
+        let vec_type = if nullable {
+            quote! { Vec<Option<#inner_ty>> }
         } else {
-            let vec_ty = quote! { Vec<#inner_ty> };
-            let (data_type, array_conv) = get_arrow_type_and_conversion(inner_ty);
-            (vec_ty, data_type, array_conv)
+            quote! { Vec<#inner_ty> }
         };
 
-        FieldInfo {
+        Some(FieldInfo {
             name,
+            column_name,
             vec_type,
+            inner_type: inner_ty.clone(),
             nullable,
-            arrow_data_type,
-            array_conversion,
+        })
+    }
+
+    /// Local variable holding this field's values while building a
+    /// nested struct array; suffixed so it cannot shadow the
+    /// generated code's own locals.
+    fn column_ident(&self) -> syn::Ident {
+        format_ident!("{}_column", self.name)
+    }
+
+    /// The function converting this field's buffered `Vec` into an
+    /// Arrow array.
+    fn conversion(&self) -> proc_macro2::TokenStream {
+        let inner_ty = &self.inner_type;
+        if self.nullable {
+            quote! { <#inner_ty as monarch_record_batch::RecordBatchColumn>::to_array }
+        } else {
+            quote! { <#inner_ty as monarch_record_batch::RecordBatchColumn>::to_required_array }
         }
     }
 }
@@ -169,45 +372,3 @@ fn extract_option_inner(ty: &Type) -> (&Type, bool) {
     }
     (ty, false)
 }
-
-fn get_arrow_type_and_conversion(
-    ty: &Type,
-) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
-    let type_str = quote!(#ty).to_string().replace(' ', "");
-
-    match type_str.as_str() {
-        "u64" => (
-            quote! { datafusion::arrow::datatypes::DataType::UInt64 },
-            quote! { datafusion::arrow::array::UInt64Array::from },
-        ),
-        "u32" => (
-            quote! { datafusion::arrow::datatypes::DataType::UInt32 },
-            quote! { datafusion::arrow::array::UInt32Array::from },
-        ),
-        "i64" => (
-            quote! { datafusion::arrow::datatypes::DataType::Int64 },
-            quote! { datafusion::arrow::array::Int64Array::from },
-        ),
-        "i32" => (
-            quote! { datafusion::arrow::datatypes::DataType::Int32 },
-            quote! { datafusion::arrow::array::Int32Array::from },
-        ),
-        "String" => (
-            quote! { datafusion::arrow::datatypes::DataType::Utf8 },
-            quote! { datafusion::arrow::array::StringArray::from },
-        ),
-        "bool" => (
-            quote! { datafusion::arrow::datatypes::DataType::Boolean },
-            quote! { datafusion::arrow::array::BooleanArray::from },
-        ),
-        "f64" => (
-            quote! { datafusion::arrow::datatypes::DataType::Float64 },
-            quote! { datafusion::arrow::array::Float64Array::from },
-        ),
-        "f32" => (
-            quote! { datafusion::arrow::datatypes::DataType::Float32 },
-            quote! { datafusion::arrow::array::Float32Array::from },
-        ),
-        _ => panic!("unsupported type: {}", type_str),
-    }
-}
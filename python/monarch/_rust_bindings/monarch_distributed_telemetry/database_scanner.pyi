@@ -42,3 +42,23 @@ class DatabaseScanner:
     ) -> int:
         """Perform a scan, sending results directly to the dest port."""
         ...
+    def register_standing_query(
+        self,
+        dest: object,
+        query_id: str,
+        table_name: str,
+        where_clause: Optional[str] = None,
+        columns: Optional[List[str]] = None,
+    ) -> None:
+        """Register a standing query whose deltas are sent to the dest port.
+
+        Raises if the query does not plan against the table's schema,
+        e.g. because the WHERE clause uses an aggregate.
+        """
+        ...
+    def unregister_standing_query(self, query_id: str) -> bool:
+        """Unregister a standing query. Returns whether it was registered."""
+        ...
+    def standing_query_ids(self) -> List[str]:
+        """Get the ids of the registered standing queries."""
+        ...
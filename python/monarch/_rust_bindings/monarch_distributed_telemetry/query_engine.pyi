@@ -4,6 +4,8 @@
 # This source code is licensed under the BSD-style license found in the
 # LICENSE file in the root directory of this source tree.
 
+from typing import List, Optional, Tuple
+
 class QueryEngine:
     """DataFusion query execution, creates ports, collects results."""
 
@@ -12,3 +14,24 @@ class QueryEngine:
     def query(self, sql: str) -> bytes:
         """Execute a SQL query and return results as Arrow IPC bytes."""
         ...
+
+class StandingQuerySubscription:
+    """Client side of a standing query registered across the telemetry actors."""
+
+    def __new__(
+        cls,
+        actor: object,
+        query_id: str,
+        table_name: str,
+        where_clause: Optional[str] = None,
+        columns: Optional[List[str]] = None,
+    ) -> "StandingQuerySubscription": ...
+    def __repr__(self) -> str: ...
+    @property
+    def query_id(self) -> str: ...
+    def next(self, timeout: Optional[float] = None) -> Optional[Tuple[int, bytes]]:
+        """Wait for the next delta as (rank, Arrow IPC bytes), or None on timeout."""
+        ...
+    def cancel(self) -> None:
+        """Unregister the query on every scanner."""
+        ...
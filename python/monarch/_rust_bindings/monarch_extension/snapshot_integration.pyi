@@ -18,4 +18,6 @@ def _start_periodic_snapshots(
     admin_ref: PyMeshAdminRef,
     instance: Instance,
     interval_secs: float,
+    rebase_every: int | None = None,
+    chain_root: str | None = None,
 ) -> None: ...
@@ -7,7 +7,7 @@
 # pyre-strict
 
 from pathlib import Path
-from typing import List, Literal, Optional, Union
+from typing import Callable, List, Literal, Optional, Union
 
 PrivateKey = Union[bytes, Path, None]
 CA = Union[bytes, Path, Literal["trust_all_connections"]]
@@ -27,3 +27,8 @@ def attach_to_workers(
     workers: List[PythonTask[str]],
     name: Optional[str] = None,
 ) -> PythonTask[HostMesh]: ...
+def set_health_probe(probe: Callable[[], Optional[bool]]) -> None:
+    """Install the health probe run by this proc on every health check.
+    The proc is unhealthy while the probe returns False, raises, or cannot
+    acquire the GIL in time."""
+    ...
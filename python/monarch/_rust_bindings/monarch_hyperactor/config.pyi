@@ -21,6 +21,8 @@ class Encoding(Enum):
     Bincode: int
     Json: int
     Multipart: int
+    Cbor: int
+    MessagePack: int
 
 def reload_config_from_env() -> None:
     """
@@ -131,7 +133,8 @@ def configure(
         remote_allocator_heartbeat_interval: Heartbeat interval for
             remote allocator (humantime)
         default_encoding: Default message encoding (Encoding.Bincode,
-            Encoding.Json, or Encoding.Multipart)
+            Encoding.Json, Encoding.Multipart, Encoding.Cbor, or
+            Encoding.MessagePack)
         channel_net_rx_buffer_full_check_interval: Network receive buffer
             check interval (humantime)
         message_latency_sampling_rate: Sampling rate for message latency
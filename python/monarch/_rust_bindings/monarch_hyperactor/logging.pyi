@@ -32,5 +32,23 @@ class LoggingMeshClient:
         level: int,
     ) -> None: ...
     def flush(self, instance: Instance) -> PythonTask[None]: ...
+    def set_rank_files(self, instance: Instance, dir: str | None = None) -> None: ...
+    def set_filter(
+        self,
+        instance: Instance,
+        min_level: str | None = None,
+        ranks: list[int] | None = None,
+        pattern: str | None = None,
+    ) -> None: ...
+    def search(
+        self,
+        instance: Instance,
+        min_level: str | None = None,
+        ranks: list[int] | None = None,
+        pattern: str | None = None,
+        limit: int = 100,
+    ) -> PythonTask[
+        tuple[list[tuple[float, str | None, int | None, str | None, str]], list[int]]
+    ]: ...
 
 def log_endpoint_exception(e: Exception, endpoint: str, actor_id: ActorId) -> None: ...
@@ -8,11 +8,12 @@
 
 
 from pathlib import Path
-from typing import List, Literal, Optional, Union
+from typing import Callable, List, Literal, Optional, Union
 
 from monarch._rust_bindings.monarch_hyperactor.bootstrap import (
     attach_to_workers as _attach_to_workers,
     run_worker_loop_forever as _run_worker_loop_forever,
+    set_health_probe as _set_health_probe,
 )
 from monarch._rust_bindings.monarch_hyperactor.host_mesh import HostMesh as HyHostMesh
 from monarch._rust_bindings.monarch_hyperactor.pytokio import PythonTask
@@ -129,3 +130,18 @@ def attach_to_workers(
     )
     hm._code_sync_proc_mesh = _Lazy(lambda: hm.spawn_procs())
     return hm
+
+
+def set_health_probe(probe: Callable[[], Optional[bool]]) -> None:
+    """
+    Install a health probe for the current proc, replacing any previous one.
+
+    When health probing is enabled (`MESH_PROC_HEALTH_PROBE_INTERVAL`), the
+    host agent checks every proc periodically and calls `probe` on each check.
+    The check fails if `probe` returns False, raises, or does not finish within
+    `MESH_PROC_HEALTH_PROBE_TIMEOUT`, e.g. because the GIL is held by a wedged
+    thread. Procs that fail enough consecutive checks are reported unhealthy.
+
+    `probe` runs while holding the GIL, so it should be cheap and non-blocking.
+    """
+    _set_health_probe(probe)
@@ -312,6 +312,15 @@ class TelemetryConfig:
             into the telemetry query surface. 0 disables periodic capture
             (default). Snapshot table schemas are always pre-registered
             regardless of this setting.
+        snapshot_rebase_every: When set, periodic snapshots are captured
+            incrementally: each tick resolves only nodes that may have
+            changed since the previous one, and every
+            ``snapshot_rebase_every``-th tick is a full capture. None
+            (default) captures the full mesh on every tick.
+        snapshot_chain_dir: Directory under which incremental periodic
+            snapshots are also written as base-plus-delta chains, one
+            chain per full capture. Requires ``snapshot_rebase_every``.
+            None (default) keeps snapshots in the query surface only.
     """
 
     batch_size: int = 1000
@@ -319,6 +328,8 @@ class TelemetryConfig:
     include_dashboard: bool = False
     dashboard_port: int = 8265
     snapshot_interval_secs: float = 0  # 0 = disabled
+    snapshot_rebase_every: Optional[int] = None
+    snapshot_chain_dir: Optional[str] = None
 
 
 @dataclass
@@ -503,6 +514,8 @@ class JobTrait(ABC):
             admin_ref=admin_ref,
             instance=context().actor_instance._as_rust(),
             interval_secs=telemetry.snapshot_interval_secs,
+            rebase_every=telemetry.snapshot_rebase_every,
+            chain_root=telemetry.snapshot_chain_dir,
         )
         self._snapshot_started = True
 
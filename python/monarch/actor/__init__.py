@@ -33,7 +33,11 @@ from monarch._src.actor.actor_mesh import (
     shutdown_context,
     ValueMesh,
 )
-from monarch._src.actor.bootstrap import attach_to_workers, run_worker_loop_forever
+from monarch._src.actor.bootstrap import (
+    attach_to_workers,
+    run_worker_loop_forever,
+    set_health_probe,
+)
 from monarch._src.actor.debugger.debug_controller import debug_controller
 from monarch._src.actor.endpoint import endpoint
 from monarch._src.actor.future import Future
@@ -75,6 +79,7 @@ __all__ = [
     "Extent",
     "run_worker_loop_forever",
     "attach_to_workers",
+    "set_health_probe",
     "enable_transport",
     "Context",
     "ChannelTransport",
@@ -127,7 +127,7 @@ def configure(**kwargs: "ConfigureKwargsType") -> None:
             stop_actor_timeout: Timeout for stopping actors (humantime).
             cleanup_timeout: Timeout for cleanup operations (humantime).
             remote_allocator_heartbeat_interval: Heartbeat interval for remote allocator (humantime).
-            default_encoding: Default message encoding (Encoding.Bincode, Encoding.Json, or Encoding.Multipart).
+            default_encoding: Default message encoding (Encoding.Bincode, Encoding.Json, Encoding.Multipart, Encoding.Cbor, or Encoding.MessagePack).
             channel_net_rx_buffer_full_check_interval: Network receive buffer check interval (humantime).
             message_latency_sampling_rate: Sampling rate for message latency tracking (0.0 to 1.0).
             enable_dest_actor_reordering_buffer: Enable reordering buffer in dest actor.
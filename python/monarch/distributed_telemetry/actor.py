@@ -21,7 +21,7 @@ variable and used by the DistributedTelemetryActor when it initializes.
 
 import functools
 import logging
-from typing import Any, Callable, Dict, List, Optional
+from typing import Any, Callable, Dict, List, Optional, Tuple
 
 from monarch._rust_bindings.monarch_distributed_telemetry.database_scanner import (
     DatabaseScanner,
@@ -111,6 +111,10 @@ class DistributedTelemetryActor(Actor):
 
         self._children: Dict[str, Any] = {}
         self._num_procs_processed: int = 0
+        # Standing query registrations, replayed onto newly spawned children.
+        self._standing_queries: Dict[
+            str, Tuple[PortId, str, Optional[str], Optional[List[str]]]
+        ] = {}
         self._proc_id: str = context().actor_instance.proc_id
 
     def __supervise__(self, failure: MeshFailure) -> bool:
@@ -145,6 +149,16 @@ class DistributedTelemetryActor(Actor):
                 # pyre-ignore[16]: actor_mesh is an ActorMesh with _name
                 mesh_name: str = actor_mesh._name.get()
                 self._children[mesh_name] = actor_mesh
+                for query_id, (
+                    dest,
+                    table_name,
+                    where_clause,
+                    columns,
+                ) in self._standing_queries.items():
+                    # pyre-ignore[16]: actor_mesh is an ActorMesh
+                    actor_mesh.register_standing_query.call(
+                        dest, query_id, table_name, where_clause, columns
+                    ).get()
             except Exception:
                 logger.warning("failed to spawn telemetry on proc mesh, skipping")
 
@@ -232,6 +246,46 @@ class DistributedTelemetryActor(Actor):
 
         return total_count
 
+    @endpoint
+    def register_standing_query(
+        self,
+        dest: PortId,
+        query_id: str,
+        table_name: str,
+        where_clause: Optional[str],
+        columns: Optional[List[str]],
+    ) -> None:
+        """Register a standing query locally and on all children.
+
+        Children spawned later receive the registration when they are
+        spawned, so the query covers ProcMeshes created after it.
+        """
+        self._spawn_missing_children()
+        self._scanner.register_standing_query(
+            dest, query_id, table_name, where_clause, columns
+        )
+        self._standing_queries[query_id] = (dest, table_name, where_clause, columns)
+        for child_mesh in self._children.values():
+            try:
+                # pyre-ignore[29]: child_mesh is an ActorMesh
+                child_mesh.register_standing_query.call(
+                    dest, query_id, table_name, where_clause, columns
+                ).get()
+            except Exception:
+                logger.info("child register_standing_query failed, skipping")
+
+    @endpoint
+    def unregister_standing_query(self, query_id: str) -> None:
+        """Unregister a standing query locally and on all children."""
+        self._scanner.unregister_standing_query(query_id)
+        self._standing_queries.pop(query_id, None)
+        for child_mesh in self._children.values():
+            try:
+                # pyre-ignore[29]: child_mesh is an ActorMesh
+                child_mesh.unregister_standing_query.call(query_id).get()
+            except Exception:
+                logger.info("child unregister_standing_query failed, skipping")
+
 
 def start_telemetry(
     batch_size: int = 1000,
@@ -12,14 +12,62 @@ QueryEngine - Wrapper for the Rust QueryEngine.
 Provides SQL query execution over distributed telemetry actors.
 """
 
-from typing import Any, Optional, Tuple, Type
+import uuid
+from typing import Any, Iterator, List, Optional, Tuple, Type
 
 import pyarrow as pa
 from monarch._rust_bindings.monarch_distributed_telemetry.query_engine import (
     QueryEngine as _QueryEngine,
+    StandingQuerySubscription,
 )
 
 
+class StandingQuery:
+    """
+    A standing query registered on every DatabaseScanner.
+
+    Each scanner evaluates the query against newly ingested rows only and
+    streams the matching rows back as deltas. Iterate over the object (or
+    call ``next_delta``) to receive them; call ``cancel`` to unregister.
+    """
+
+    def __init__(self, subscription: StandingQuerySubscription) -> None:
+        self._subscription = subscription
+
+    @property
+    def query_id(self) -> str:
+        return self._subscription.query_id
+
+    def next_delta(
+        self, timeout: Optional[float] = None
+    ) -> Optional[Tuple[int, pa.Table]]:
+        """
+        Wait for the next delta.
+
+        Args:
+            timeout: Seconds to wait; None waits indefinitely.
+
+        Returns:
+            ``(rank, table)`` of the scanner that ingested the rows, or
+            None if the timeout elapsed.
+        """
+        delta = self._subscription.next(timeout)
+        if delta is None:
+            return None
+        rank, data = delta
+        return rank, pa.ipc.open_stream(data).read_all()
+
+    def __iter__(self) -> Iterator[Tuple[int, pa.Table]]:
+        while True:
+            delta = self.next_delta()
+            if delta is not None:
+                yield delta
+
+    def cancel(self) -> None:
+        """Unregister the query on every scanner."""
+        self._subscription.cancel()
+
+
 class QueryEngine:
     """
     SQL query engine for distributed telemetry data.
@@ -79,3 +127,37 @@ class QueryEngine:
             Arrow IPC serialized stream containing all record batches
         """
         return self._ensure_engine().query(sql)
+
+    def watch(
+        self,
+        table_name: str,
+        where: Optional[str] = None,
+        columns: Optional[List[str]] = None,
+        query_id: Optional[str] = None,
+    ) -> StandingQuery:
+        """
+        Register a standing query over a table.
+
+        Rather than polling with full scans, each scanner evaluates the
+        predicate against rows as they are ingested and streams matches
+        back. Rows already in the table are not delivered; combine with
+        ``query`` for a snapshot-then-follow pattern.
+
+        Args:
+            table_name: Table to watch (e.g. ``"events"``).
+            where: Optional SQL WHERE clause (e.g. ``"level = 'ERROR'"``).
+            columns: Optional list of columns to deliver.
+            query_id: Optional identifier; generated when omitted.
+
+        Returns:
+            A StandingQuery yielding ``(rank, pa.Table)`` deltas.
+        """
+        return StandingQuery(
+            StandingQuerySubscription(
+                self._actor,
+                query_id or f"sq-{uuid.uuid4().hex}",
+                table_name,
+                where,
+                columns,
+            )
+        )
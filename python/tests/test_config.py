@@ -350,7 +350,13 @@ def test_encoding_param():
     assert config["default_encoding"] == Encoding.Multipart
 
     # Test all valid encodings
-    valid_encodings = [Encoding.Bincode, Encoding.Json, Encoding.Multipart]
+    valid_encodings = [
+        Encoding.Bincode,
+        Encoding.Json,
+        Encoding.Multipart,
+        Encoding.Cbor,
+        Encoding.MessagePack,
+    ]
     for encoding in valid_encodings:
         with configured(default_encoding=encoding) as config:
             assert config["default_encoding"] == encoding
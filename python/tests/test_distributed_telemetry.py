@@ -1578,6 +1578,36 @@ def test_per_table_row_retention(cleanup_callbacks) -> None:
         )
 
 
+@pytest.mark.timeout(60)
+@isolate_in_subprocess
+def test_standing_query_streams_new_rows(cleanup_callbacks) -> None:
+    """A standing query delivers rows ingested after registration only."""
+    engine, _, _scanner = start_telemetry(include_dashboard=False)
+
+    dump = json.dumps(
+        {"Ok": {"pid": 1, "binary": "python3", "stack_traces": [], "warnings": []}}
+    )
+
+    engine._actor.store_pyspy_dump.call("before", "proc[0]", dump).get()
+
+    watch = engine.watch(
+        "pyspy_dumps", where="dump_id LIKE 'after%'", columns=["dump_id"]
+    )
+    engine._actor.store_pyspy_dump.call("other", "proc[0]", dump).get()
+    engine._actor.store_pyspy_dump.call("after-1", "proc[0]", dump).get()
+
+    delta = watch.next_delta(timeout=30)
+    assert delta is not None
+    rank, table = delta
+    assert rank == 0
+    assert table.column_names == ["dump_id"]
+    assert table.to_pydict()["dump_id"] == ["after-1"]
+
+    watch.cancel()
+    engine._actor.store_pyspy_dump.call("after-2", "proc[0]", dump).get()
+    assert watch.next_delta(timeout=1) is None
+
+
 # --- Snapshot integration tests ---
 #
 # These tests verify that introspection snapshot tables are
@@ -17,6 +17,8 @@ use std::collections::hash_map;
 use std::fmt::Debug;
 use std::hash::Hash;
 
+use serde::Deserialize;
+use serde::Serialize;
 pub use struct_diff_patch_macros::Diff;
 pub use struct_diff_patch_macros::Patch;
 
@@ -47,6 +49,10 @@ pub trait Patch<T> {
 
 /// Implements the "diff" operation, which produces a patch given
 /// two instances of the same type.
+///
+/// The patch types defined by this crate are serializable whenever
+/// the values they carry are, so patches can be sent to other
+/// processes.
 pub trait Diff: Sized {
     /// The type of patch produced by this diff operation.
     type Patch: Patch<Self>;
@@ -70,7 +76,11 @@ impl<T> Patch<T> for Option<T> {
 }
 
 /// A patch of an option.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "T: Serialize, T::Patch: Serialize",
+    deserialize = "T: Deserialize<'de>, T::Patch: Deserialize<'de>"
+))]
 pub enum OptionPatch<T: Diff> {
     /// Set a new value.
     Set(T),
@@ -110,7 +120,11 @@ impl<T: Diff + Clone> Diff for Option<T> {
 }
 
 /// Vector patches.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "T: Serialize, T::Patch: Serialize",
+    deserialize = "T: Deserialize<'de>, T::Patch: Deserialize<'de>"
+))]
 pub struct VecPatch<T: Diff> {
     /// Truncate the vector to this length.
     len: usize,
@@ -170,7 +184,11 @@ impl<T: Diff + Clone> Diff for Vec<T> {
 }
 
 /// HashMap patches.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "K: Serialize, V: Serialize, V::Patch: Serialize",
+    deserialize = "K: Deserialize<'de>, V: Deserialize<'de>, V::Patch: Deserialize<'de>"
+))]
 pub struct HashMapPatch<K, V: Diff> {
     /// Remove the following keys.
     remove: Vec<K>,
@@ -460,6 +478,56 @@ mod tests {
         unit_patch.apply(&mut unit).unwrap();
     }
 
+    fn round_trip<P>(patch: &P) -> P
+    where
+        P: Serialize + serde::de::DeserializeOwned,
+    {
+        serde_json::from_str(&serde_json::to_string(patch).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn option_patch_serde_round_trip() {
+        for patch in [
+            OptionPatch::Set(Some(1_u32)),
+            OptionPatch::Patch(OptionPatch::Patch(Some(2))),
+            OptionPatch::Clear,
+        ] {
+            assert_eq!(round_trip(&patch), patch);
+        }
+    }
+
+    #[test]
+    fn vec_patch_serde_round_trip() {
+        let patch = vec!["a".to_string(), "b".to_string()].diff(&vec![
+            "a".to_string(),
+            "c".to_string(),
+            "d".to_string(),
+        ]);
+        assert_eq!(round_trip(&patch), patch);
+
+        let mut value = vec!["a".to_string(), "b".to_string()];
+        round_trip(&patch).apply(&mut value).unwrap();
+        assert_eq!(value, vec!["a", "c", "d"]);
+    }
+
+    #[test]
+    fn hashmap_patch_serde_round_trip() {
+        let original = HashMap::from([
+            ("keep".to_string(), vec![1_u32]),
+            ("remove".to_string(), vec![2]),
+        ]);
+        let target = HashMap::from([
+            ("keep".to_string(), vec![1, 10]),
+            ("insert".to_string(), vec![3]),
+        ]);
+        let patch = original.diff(&target);
+        assert_eq!(round_trip(&patch), patch);
+
+        let mut value = original;
+        round_trip(&patch).apply(&mut value).unwrap();
+        assert_eq!(value, target);
+    }
+
     #[test]
     fn option_patch_error_when_patching_none() {
         let mut value: Option<u32> = None;
@@ -35,6 +35,17 @@ pub trait Named: Sized + 'static {
         cityhasher::hash(Self::typename())
     }
 
+    /// A fingerprint of the type's serialized shape, for types that opt in
+    /// with `#[named(schema)]`. Peers built from different versions of a
+    /// fingerprinted type can detect the mismatch instead of decoding
+    /// values with the wrong layout. Derived fingerprints include those of
+    /// the type's fields; types without a fingerprint of their own, such
+    /// as primitives and foreign types, contribute nothing. Containers
+    /// forward the fingerprints of their parameters.
+    fn schema_fingerprint() -> Option<u64> {
+        None
+    }
+
     /// The TypeId for this type. TypeIds are unique only within a binary,
     /// and should not be used for global identification.
     fn typeid() -> TypeId {
@@ -141,6 +152,10 @@ macro_rules! impl_tuple {
             fn typename() -> &'static str {
                 intern_typename!(Self, concat!("(", tuple_format_string!($($name,)+), ")"), $($name),+)
             }
+
+            fn schema_fingerprint() -> Option<u64> {
+                schema::combine([$($name::schema_fingerprint()),+])
+            }
         }
         impl_tuple_peel! { $($name,)+ }
     )
@@ -152,30 +167,155 @@ impl<T: Named + 'static> Named for Option<T> {
     fn typename() -> &'static str {
         intern_typename!(Self, "Option<{}>", T)
     }
+
+    fn schema_fingerprint() -> Option<u64> {
+        T::schema_fingerprint()
+    }
 }
 
 impl<T: Named + 'static> Named for Vec<T> {
     fn typename() -> &'static str {
         intern_typename!(Self, "Vec<{}>", T)
     }
+
+    fn schema_fingerprint() -> Option<u64> {
+        T::schema_fingerprint()
+    }
 }
 
 impl<K: Named + 'static, V: Named + 'static> Named for HashMap<K, V> {
     fn typename() -> &'static str {
         intern_typename!(Self, "HashMap<{}, {}>", K, V)
     }
+
+    fn schema_fingerprint() -> Option<u64> {
+        schema::combine([K::schema_fingerprint(), V::schema_fingerprint()])
+    }
 }
 
 impl<T: Named + 'static, E: Named + 'static> Named for Result<T, E> {
     fn typename() -> &'static str {
         intern_typename!(Self, "Result<{}, {}>", T, E)
     }
+
+    fn schema_fingerprint() -> Option<u64> {
+        schema::combine([T::schema_fingerprint(), E::schema_fingerprint()])
+    }
 }
 
 impl<T: Named + 'static> Named for std::ops::Range<T> {
     fn typename() -> &'static str {
         intern_typename!(Self, "std::ops::Range<{}>", T)
     }
+
+    fn schema_fingerprint() -> Option<u64> {
+        T::schema_fingerprint()
+    }
+}
+
+/// Support for schema fingerprints derived with `#[named(schema)]`.
+#[doc(hidden)] // not part of the public API
+pub mod schema {
+    use std::any::TypeId;
+    use std::cell::RefCell;
+    use std::marker::PhantomData;
+    use std::sync::OnceLock;
+
+    use crate::Named;
+
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+
+    thread_local! {
+        /// The types whose fingerprints are being computed on this thread.
+        static VISITING: RefCell<Vec<TypeId>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Fold `fingerprint` into `hash` with 64-bit FNV-1a, the hash the
+    /// derive macro uses for shapes. Missing fingerprints fold in as 0 so
+    /// that each keeps its position.
+    pub fn mix(hash: u64, fingerprint: Option<u64>) -> u64 {
+        fingerprint
+            .unwrap_or(0)
+            .to_le_bytes()
+            .into_iter()
+            .fold(hash, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+            })
+    }
+
+    /// Combine the fingerprints of a container's parameters: `None`
+    /// unless at least one of them has a fingerprint.
+    pub fn combine<const N: usize>(fingerprints: [Option<u64>; N]) -> Option<u64> {
+        fingerprints
+            .iter()
+            .any(Option::is_some)
+            .then(|| fingerprints.into_iter().fold(FNV_OFFSET_BASIS, mix))
+    }
+
+    /// The fingerprint of the type `id`, whose own shape hashes to `shape`
+    /// and whose fields' fingerprints are returned by `fields`.
+    ///
+    /// A type reached again while its fingerprint is being computed (a
+    /// recursive type) contributes only its shape. Since a type's
+    /// fingerprint then depends on where the computation started, only
+    /// fingerprints computed from the top are memoized in `cache`.
+    pub fn derived(
+        id: TypeId,
+        shape: u64,
+        cache: Option<&OnceLock<u64>>,
+        fields: impl FnOnce() -> Vec<Option<u64>>,
+    ) -> u64 {
+        let (recursive, top) = VISITING.with(|visiting| {
+            let visiting = visiting.borrow();
+            (visiting.contains(&id), visiting.is_empty())
+        });
+        if recursive {
+            return shape;
+        }
+        let cache = cache.filter(|_| top);
+        if let Some(fingerprint) = cache.and_then(OnceLock::get) {
+            return *fingerprint;
+        }
+        VISITING.with(|visiting| visiting.borrow_mut().push(id));
+        let fields = fields();
+        VISITING.with(|visiting| visiting.borrow_mut().pop());
+        let fingerprint = fields.into_iter().fold(shape, mix);
+        if let Some(cache) = cache {
+            let _ = cache.set(fingerprint);
+        }
+        fingerprint
+    }
+
+    /// A field type, whose fingerprint resolves through [`NamedField`]
+    /// if it implements [`Named`], and to `None` through
+    /// [`ForeignField`] otherwise. Call `schema_fingerprint` on a
+    /// reference to a `Field` with both traits in scope.
+    pub struct Field<T: ?Sized>(pub PhantomData<T>);
+
+    /// Fingerprints of [`Named`] field types.
+    pub trait NamedField {
+        /// The field type's fingerprint.
+        fn schema_fingerprint(&self) -> Option<u64>;
+    }
+
+    impl<T: Named> NamedField for Field<T> {
+        fn schema_fingerprint(&self) -> Option<u64> {
+            T::schema_fingerprint()
+        }
+    }
+
+    /// Fingerprints of other field types, selected by autoref when
+    /// [`NamedField`] does not apply.
+    pub trait ForeignField {
+        /// Always `None`.
+        fn schema_fingerprint(&self) -> Option<u64>;
+    }
+
+    impl<T: ?Sized> ForeignField for &Field<T> {
+        fn schema_fingerprint(&self) -> Option<u64> {
+            None
+        }
+    }
 }
 
 #[cfg(test)]
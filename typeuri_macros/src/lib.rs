@@ -10,6 +10,7 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
+use syn::Attribute;
 use syn::Data;
 use syn::DataEnum;
 use syn::DeriveInput;
@@ -36,6 +37,29 @@ use syn::parse_macro_input;
 /// #[named(name = "custom::path::MyEnum")]
 /// enum MyEnum { A, B }
 /// ```
+///
+/// The `schema` attribute additionally derives
+/// [`typeuri::Named::schema_fingerprint`] from the type's serde shape:
+/// its field and variant names, their order, the field types as
+/// written, and any `#[serde(...)]` attributes. The type's own name
+/// is not part of the fingerprint, so a type declared to mirror an
+/// older shape fingerprints the same as that shape did.
+///
+/// Each field type also folds in its own
+/// [`typeuri::Named::schema_fingerprint`], so changing the definition of
+/// a fingerprinted field type (say, adding a field to a struct used as a
+/// field) changes the fingerprint of every type that contains it.
+/// Containers such as `Option` and `Vec` forward the fingerprints of
+/// their parameters. Field types that do not implement `Named`, or that
+/// do not opt in, contribute only their paths as written, so a change to
+/// what such a type, a type alias or an imported name resolves to goes
+/// unnoticed.
+///
+/// ```ignore
+/// #[derive(Named, Serialize, Deserialize)]
+/// #[named(schema)]
+/// struct MyMessage { id: u64, payload: String }
+/// ```
 #[proc_macro_derive(Named, attributes(named))]
 pub fn derive_named(input: TokenStream) -> TokenStream {
     // Parse the input struct or enum
@@ -48,6 +72,7 @@ pub fn derive_named(input: TokenStream) -> TokenStream {
 
     let type_params: Vec<_> = input.generics.type_params().collect();
     let has_generics = !type_params.is_empty();
+    let mut schema = false;
 
     for attr in &input.attrs {
         if attr.path().is_ident("named") {
@@ -55,6 +80,19 @@ pub fn derive_named(input: TokenStream) -> TokenStream {
                 syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated,
             ) {
                 for item in meta {
+                    if let Meta::Path(path) = &item {
+                        if path.is_ident("schema") {
+                            schema = true;
+                            continue;
+                        }
+                        return TokenStream::from(
+                            syn::Error::new_spanned(
+                                path,
+                                "unsupported attribute (expected `name` or `schema`)",
+                            )
+                            .to_compile_error(),
+                        );
+                    }
                     if let Meta::NameValue(MetaNameValue {
                         path,
                         value: Expr::Lit(expr_lit),
@@ -74,7 +112,7 @@ pub fn derive_named(input: TokenStream) -> TokenStream {
                             return TokenStream::from(
                                 syn::Error::new_spanned(
                                     path,
-                                    "unsupported attribute (only `name` is supported)",
+                                    "unsupported attribute (expected `name` or `schema`)",
                                 )
                                 .to_compile_error(),
                             );
@@ -145,6 +183,43 @@ pub fn derive_named(input: TokenStream) -> TokenStream {
         _ => quote! {},
     };
 
+    let schema_impl = if schema {
+        let fingerprint = fingerprint(&schema_shape(&input));
+        let field_types = field_types(&input);
+        // Generic types share the function-local static across
+        // instantiations, so only non-generic types memoize.
+        let (cache_decl, cache) = if has_generics {
+            (quote! {}, quote! { None })
+        } else {
+            (
+                quote! {
+                    static CACHE: std::sync::OnceLock<u64> = std::sync::OnceLock::new();
+                },
+                quote! { Some(&CACHE) },
+            )
+        };
+        quote! {
+            fn schema_fingerprint() -> Option<u64> {
+                #cache_decl
+                Some(typeuri::schema::derived(
+                    std::any::TypeId::of::<Self>(),
+                    #fingerprint,
+                    #cache,
+                    || {
+                        use typeuri::schema::ForeignField as _;
+                        use typeuri::schema::NamedField as _;
+                        vec![#(
+                            (&typeuri::schema::Field::<#field_types>(std::marker::PhantomData))
+                                .schema_fingerprint()
+                        ),*]
+                    },
+                ))
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     let (_, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let expanded = quote! {
@@ -152,8 +227,87 @@ pub fn derive_named(input: TokenStream) -> TokenStream {
             fn typename() -> &'static str { #typename_impl }
             fn typehash() -> u64 { #typehash_impl }
             #arm_impl
+            #schema_impl
         }
     };
 
     TokenStream::from(expanded)
 }
+
+/// Render the serde-relevant shape of a type as a canonical string:
+/// `#[serde]` attributes, field and variant names, and field types as
+/// written, with all whitespace removed. Field types are not expanded
+/// here; their fingerprints are folded in at run time (see the `schema`
+/// attribute on [`derive_named`]).
+fn schema_shape(input: &DeriveInput) -> String {
+    fn serde_attrs(attrs: &[Attribute]) -> String {
+        attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("serde"))
+            .map(|attr| quote!(#attr).to_string())
+            .collect()
+    }
+
+    fn fields_shape(fields: &Fields) -> String {
+        let shapes: Vec<_> = fields
+            .iter()
+            .map(|field| {
+                let ty = &field.ty;
+                let name = field
+                    .ident
+                    .as_ref()
+                    .map_or_else(String::new, |ident| format!("{ident}:"));
+                format!("{}{}{}", serde_attrs(&field.attrs), name, quote!(#ty))
+            })
+            .collect();
+        match fields {
+            Fields::Named(_) => format!("{{{}}}", shapes.join(",")),
+            Fields::Unnamed(_) => format!("({})", shapes.join(",")),
+            Fields::Unit => String::new(),
+        }
+    }
+
+    let body = match &input.data {
+        Data::Struct(data) => format!("struct{}", fields_shape(&data.fields)),
+        Data::Enum(data) => {
+            let variants: Vec<_> = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    format!(
+                        "{}{}{}",
+                        serde_attrs(&variant.attrs),
+                        variant.ident,
+                        fields_shape(&variant.fields)
+                    )
+                })
+                .collect();
+            format!("enum{{{}}}", variants.join(","))
+        }
+        Data::Union(_) => "union".to_string(),
+    };
+    let shape = format!("{}{}", serde_attrs(&input.attrs), body);
+    shape.split_whitespace().collect()
+}
+
+/// The types of all of the fields of a struct or enum, in declaration
+/// order.
+fn field_types(input: &DeriveInput) -> Vec<&syn::Type> {
+    match &input.data {
+        Data::Struct(data) => data.fields.iter().map(|field| &field.ty).collect(),
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter().map(|field| &field.ty))
+            .collect(),
+        Data::Union(_) => Vec::new(),
+    }
+}
+
+/// 64-bit FNV-1a, computed at expansion time so that fingerprints are
+/// plain literals in the generated code.
+fn fingerprint(shape: &str) -> u64 {
+    shape.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
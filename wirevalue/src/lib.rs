@@ -11,7 +11,18 @@
 //!
 //! Wirevalues also provide encoding polymorphism, allowing the same representation
 //! to carry multiple serialization formats, and to transcode between them for
-//! types that are registered through [`register_type!`].
+//! types that are registered through [`register_type!`]. Besides the
+//! Rust-specific bincode and multipart encodings, values may be encoded as
+//! JSON, CBOR, or MessagePack, which are self-describing and can be produced
+//! and consumed by clients written in other languages.
+//!
+//! Values of types that opt in to a schema fingerprint (`#[named(schema)]`)
+//! carry the sender's fingerprint. Deserializing such a value into a type
+//! whose fingerprint differs fails with [`Error::SchemaMismatch`], unless
+//! a migration from the sender's shape was registered through
+//! [`register_migration!`]. Values of other types keep the wire layout
+//! they had before fingerprints were introduced, so peers built on either
+//! side of that change interoperate.
 
 use std::any::TypeId;
 use std::collections::HashMap;
@@ -39,6 +50,9 @@ pub const BROKEN_TYPEHASH: u64 = 0;
 pub trait NamedDumpable: Named + Serialize + for<'de> Deserialize<'de> {
     /// Dump the data in Any to a JSON value.
     fn dump(data: Any) -> Result<serde_json::Value>;
+
+    /// Re-encode the data in Any with the provided encoding.
+    fn transcode(data: Any, encoding: Encoding) -> Result<Any>;
 }
 
 impl<T: Named + Serialize + for<'de> Deserialize<'de>> NamedDumpable for T {
@@ -46,6 +60,11 @@ impl<T: Named + Serialize + for<'de> Deserialize<'de>> NamedDumpable for T {
         let value = data.deserialized::<Self>()?;
         Ok(serde_json::to_value(value)?)
     }
+
+    fn transcode(data: Any, encoding: Encoding) -> Result<Any> {
+        let value = data.deserialized::<Self>()?;
+        Any::serialize_with_encoding(encoding, &value)
+    }
 }
 
 #[doc(hidden)]
@@ -61,6 +80,8 @@ pub struct TypeInfo {
     pub port: fn() -> u64,
     /// A function that can transcode a serialized value to JSON.
     pub dump: Option<fn(Any) -> Result<serde_json::Value>>,
+    /// A function that can re-encode a serialized value with another encoding.
+    pub transcode: Option<fn(Any, Encoding) -> Result<Any>>,
     /// Return the arm for this type, if available.
     pub arm_unchecked: unsafe fn(*const ()) -> Option<&'static str>,
     /// Return the endpoint name for this message, if available.
@@ -117,6 +138,19 @@ impl TypeInfo {
         }
     }
 
+    /// Re-encode the serialized data with the provided encoding.
+    pub fn transcode(&self, data: Any, encoding: Encoding) -> Result<Any> {
+        if let Some(transcode) = self.transcode {
+            (transcode)(data, encoding)
+        } else {
+            Err(Error::MissingTranscoder {
+                typename: self.typename(),
+                from: data.encoding(),
+                to: encoding,
+            })
+        }
+    }
+
     /// Get the arm name for an enum value.
     ///
     /// # Safety
@@ -169,6 +203,7 @@ macro_rules! register_type {
                 typeid: <$type as $crate::Named>::typeid,
                 port: <$type as $crate::Named>::port,
                 dump: Some(<$type as $crate::NamedDumpable>::dump),
+                transcode: Some(<$type as $crate::NamedDumpable>::transcode),
                 arm_unchecked: <$type as $crate::Named>::arm_unchecked,
                 endpoint_name: |ptr| {
                     // SAFETY: ptr points to a value of type $type, as guaranteed by the caller.
@@ -179,6 +214,63 @@ macro_rules! register_type {
     };
 }
 
+/// A registered migration from an older shape of a schema-fingerprinted type.
+/// This is a utility used by [`register_migration!`], and is not intended
+/// for direct use.
+#[doc(hidden)]
+pub struct Migration {
+    /// Named::typehash() of the current type.
+    pub typehash: fn() -> u64,
+    /// Named::schema_fingerprint() of the type mirroring the old shape.
+    pub from: fn() -> Option<u64>,
+    /// Decode a value with the old shape and convert it to the current type.
+    pub migrate: fn(&Any) -> Result<Box<dyn std::any::Any>>,
+}
+
+inventory::collect!(Migration);
+
+/// Migrations linked into the binary, keyed by (typehash, old fingerprint).
+static MIGRATIONS: LazyLock<HashMap<(u64, u64), &'static Migration>> = LazyLock::new(|| {
+    inventory::iter::<Migration>()
+        .filter_map(|entry| Some((((entry.typehash)(), (entry.from)()?), entry)))
+        .collect()
+});
+
+/// Register a migration that lets this binary decode values serialized
+/// with an older shape of a schema-fingerprinted type.
+///
+/// `$old` mirrors the old shape: it derives [`typeuri::Named`] with
+/// `#[named(schema)]` (so that its fingerprint is the old one) and
+/// [`serde::Deserialize`]. `$migrate` converts an `$old` value into the
+/// current type:
+///
+/// ```ignore
+/// #[derive(Named, Deserialize)]
+/// #[named(schema)]
+/// struct RequestV1 { id: u32 }
+///
+/// wirevalue::register_migration!(Request, RequestV1, |old| Request {
+///     id: old.id.into(),
+///     deadline: None,
+/// });
+/// ```
+#[macro_export]
+macro_rules! register_migration {
+    ($type:ty, $old:ty, $migrate:expr) => {
+        $crate::submit! {
+            $crate::Migration {
+                typehash: <$type as $crate::Named>::typehash,
+                from: <$old as $crate::Named>::schema_fingerprint,
+                migrate: |any| {
+                    let migrate: fn($old) -> $type = $migrate;
+                    let old: $old = any.deserialized_as()?;
+                    Ok(Box::new(migrate(old)))
+                },
+            }
+        }
+    };
+}
+
 // Re-export inventory::submit for the register_type! macro
 #[doc(hidden)]
 pub use inventory::submit;
@@ -208,6 +300,12 @@ pub enum Encoding {
     /// Serde multipart encoding.
     #[strum(to_string = "serde_multipart")]
     Multipart,
+    /// CBOR (RFC 8949) encoding.
+    #[strum(to_string = "cbor")]
+    Cbor,
+    /// MessagePack encoding. Structs are encoded as maps keyed by field name.
+    #[strum(to_string = "msgpack")]
+    MessagePack,
 }
 
 /// The encoding used for a serialized value.
@@ -216,6 +314,8 @@ enum Encoded {
     Bincode(bytes::Bytes),
     Json(bytes::Bytes),
     Multipart(serde_multipart::Message),
+    Cbor(bytes::Bytes),
+    MessagePack(bytes::Bytes),
 }
 
 impl Encoded {
@@ -225,6 +325,8 @@ impl Encoded {
             Encoded::Bincode(data) => data.len(),
             Encoded::Json(data) => data.len(),
             Encoded::Multipart(message) => message.len(),
+            Encoded::Cbor(data) => data.len(),
+            Encoded::MessagePack(data) => data.len(),
         }
     }
 
@@ -234,6 +336,8 @@ impl Encoded {
             Encoded::Bincode(data) => data.is_empty(),
             Encoded::Json(data) => data.is_empty(),
             Encoded::Multipart(message) => message.is_empty(),
+            Encoded::Cbor(data) => data.is_empty(),
+            Encoded::MessagePack(data) => data.is_empty(),
         }
     }
 
@@ -243,6 +347,8 @@ impl Encoded {
             Encoded::Bincode(_) => Encoding::Bincode,
             Encoded::Json(_) => Encoding::Json,
             Encoded::Multipart(_) => Encoding::Multipart,
+            Encoded::Cbor(_) => Encoding::Cbor,
+            Encoded::MessagePack(_) => Encoding::MessagePack,
         }
     }
 
@@ -251,6 +357,8 @@ impl Encoded {
         match &self {
             Encoded::Bincode(data) => crc32fast::hash(data),
             Encoded::Json(data) => crc32fast::hash(data),
+            Encoded::Cbor(data) => crc32fast::hash(data),
+            Encoded::MessagePack(data) => crc32fast::hash(data),
             Encoded::Multipart(message) => {
                 let mut hasher = crc32fast::Hasher::new();
                 for fragment in message.body().iter() {
@@ -272,6 +380,8 @@ impl std::fmt::Debug for Encoded {
         match self {
             Encoded::Bincode(data) => write!(f, "Encoded::Bincode({})", HexFmt(data)),
             Encoded::Json(data) => write!(f, "Encoded::Json({})", HexFmt(data)),
+            Encoded::Cbor(data) => write!(f, "Encoded::Cbor({})", HexFmt(data)),
+            Encoded::MessagePack(data) => write!(f, "Encoded::MessagePack({})", HexFmt(data)),
             Encoded::Multipart(message) => {
                 write!(
                     f,
@@ -298,6 +408,22 @@ pub enum Error {
     #[error(transparent)]
     Json(#[from] serde_json::Error),
 
+    /// Errors returned when encoding CBOR.
+    #[error(transparent)]
+    CborEncode(#[from] ciborium::ser::Error<std::io::Error>),
+
+    /// Errors returned when decoding CBOR.
+    #[error(transparent)]
+    CborDecode(#[from] ciborium::de::Error<std::io::Error>),
+
+    /// Errors returned when encoding MessagePack.
+    #[error(transparent)]
+    MessagePackEncode(#[from] rmp_serde::encode::Error),
+
+    /// Errors returned when decoding MessagePack.
+    #[error(transparent)]
+    MessagePackDecode(#[from] rmp_serde::decode::Error),
+
     /// The encoding was not recognized.
     #[error("unknown encoding: {0}")]
     InvalidEncoding(String),
@@ -313,6 +439,18 @@ pub enum Error {
         actual: String,
     },
 
+    /// The value was serialized with a different shape of the type, and no
+    /// migration from that shape is registered.
+    #[error(
+        "schema mismatch for {typename}: expected fingerprint {expected:#018x}, \
+         found {actual:#018x} (no migration registered)"
+    )]
+    SchemaMismatch {
+        typename: &'static str,
+        expected: u64,
+        actual: u64,
+    },
+
     /// Type info not available for the given typehash.
     #[error("binary does not have typeinfo for typehash {0}")]
     MissingTypeInfo(u64),
@@ -324,6 +462,14 @@ pub enum Error {
     /// Operation requires bincode encoding.
     #[error("only bincode encoding supports prefix operations")]
     PrefixNotSupported,
+
+    /// Transcoder not available for the given type.
+    #[error("binary does not have a transcoder for {typename} (from {from} to {to})")]
+    MissingTranscoder {
+        typename: &'static str,
+        from: Encoding,
+        to: Encoding,
+    },
 }
 
 /// A specialized Result type for wirevalue operations.
@@ -336,12 +482,75 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// Currently, Any passes through to bincode, but in the future we may include
 /// content-encoding information to allow for other codecs as well.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(into = "WireAny", from = "WireAny")]
 pub struct Any {
     /// The encoded data
     encoded: Encoded,
     /// The typehash of the serialized value. This is used to provide
     /// typed introspection of the value.
     typehash: u64,
+    /// The schema fingerprint of the serialized type, if it has one.
+    schema: Option<u64>,
+}
+
+/// The wire representation of [`Any`]. Its layout is that of an `Any`
+/// without schema fingerprints: values of types without a fingerprint
+/// serialize exactly as before, so that peers built before fingerprints
+/// were introduced can still exchange them. A fingerprint is carried by
+/// wrapping the encoded data in [`WireEncoded::Fingerprinted`].
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Any")]
+struct WireAny {
+    encoded: WireEncoded,
+    typehash: u64,
+}
+
+/// The wire representation of [`Encoded`]. Variants are only ever
+/// appended, so that the tags of existing variants are unchanged.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Encoded")]
+enum WireEncoded {
+    Bincode(bytes::Bytes),
+    Json(bytes::Bytes),
+    Multipart(serde_multipart::Message),
+    Fingerprinted { schema: u64, encoded: Encoded },
+    Cbor(bytes::Bytes),
+    MessagePack(bytes::Bytes),
+}
+
+impl From<Any> for WireAny {
+    fn from(any: Any) -> Self {
+        let encoded = match (any.schema, any.encoded) {
+            (Some(schema), encoded) => WireEncoded::Fingerprinted { schema, encoded },
+            (None, Encoded::Bincode(data)) => WireEncoded::Bincode(data),
+            (None, Encoded::Json(data)) => WireEncoded::Json(data),
+            (None, Encoded::Multipart(message)) => WireEncoded::Multipart(message),
+            (None, Encoded::Cbor(data)) => WireEncoded::Cbor(data),
+            (None, Encoded::MessagePack(data)) => WireEncoded::MessagePack(data),
+        };
+        Self {
+            encoded,
+            typehash: any.typehash,
+        }
+    }
+}
+
+impl From<WireAny> for Any {
+    fn from(wire: WireAny) -> Self {
+        let (encoded, schema) = match wire.encoded {
+            WireEncoded::Bincode(data) => (Encoded::Bincode(data), None),
+            WireEncoded::Json(data) => (Encoded::Json(data), None),
+            WireEncoded::Multipart(message) => (Encoded::Multipart(message), None),
+            WireEncoded::Cbor(data) => (Encoded::Cbor(data), None),
+            WireEncoded::MessagePack(data) => (Encoded::MessagePack(data), None),
+            WireEncoded::Fingerprinted { schema, encoded } => (encoded, Some(schema)),
+        };
+        Self {
+            encoded,
+            typehash: wire.typehash,
+            schema,
+        }
+    }
 }
 
 impl std::fmt::Display for Any {
@@ -403,8 +612,17 @@ impl Any {
                 Encoding::Multipart => {
                     Encoded::Multipart(serde_multipart::serialize_bincode(value)?)
                 }
+                Encoding::Cbor => {
+                    let mut data = Vec::new();
+                    ciborium::into_writer(value, &mut data)?;
+                    Encoded::Cbor(data.into())
+                }
+                Encoding::MessagePack => {
+                    Encoded::MessagePack(rmp_serde::to_vec_named(value)?.into())
+                }
             },
             typehash: T::typehash(),
+            schema: T::schema_fingerprint(),
         })
     }
 
@@ -414,6 +632,7 @@ impl Any {
         Self {
             encoded: Encoded::Bincode(bytes::Bytes::new()),
             typehash: BROKEN_TYPEHASH,
+            schema: None,
         }
     }
 
@@ -439,13 +658,44 @@ impl Any {
     /// Deserialize a value to the provided type T, without checking for type conformance.
     /// This should be used carefully, only when you know that the dynamic type check is
     /// not needed.
-    pub fn deserialized_unchecked<T: DeserializeOwned>(&self) -> Result<T> {
+    ///
+    /// If the value is T-typed, its schema fingerprint is still checked, and a
+    /// registered migration is applied if the value has an older shape.
+    pub fn deserialized_unchecked<T: DeserializeOwned + Named>(&self) -> Result<T> {
+        if self.is::<T>()
+            && let (Some(expected), Some(actual)) = (T::schema_fingerprint(), self.schema)
+            && expected != actual
+        {
+            let Some(migration) = MIGRATIONS.get(&(self.typehash, actual)) else {
+                return Err(Error::SchemaMismatch {
+                    typename: T::typename(),
+                    expected,
+                    actual,
+                });
+            };
+            return (migration.migrate)(self)?
+                .downcast::<T>()
+                .map(|value| *value)
+                .map_err(|_| Error::TypeMismatch {
+                    expected: T::typename(),
+                    actual: "migrated value".to_string(),
+                });
+        }
+        self.deserialized_as()
+    }
+
+    /// Deserialize the payload as a U-typed value, without checking for type or
+    /// schema conformance. This should be used with care (typically only in
+    /// migrations), as the value's representation may be illegally coerced.
+    pub fn deserialized_as<U: DeserializeOwned>(&self) -> Result<U> {
         match &self.encoded {
             Encoded::Bincode(data) => Ok(bincode::deserialize(data)?),
             Encoded::Json(data) => Ok(serde_json::from_slice(data)?),
             Encoded::Multipart(message) => {
                 Ok(serde_multipart::deserialize_bincode(message.clone())?)
             }
+            Encoded::Cbor(data) => Ok(ciborium::from_reader(data.as_ref())?),
+            Encoded::MessagePack(data) => Ok(rmp_serde::from_slice(data)?),
         }
     }
 
@@ -453,7 +703,10 @@ impl Any {
     /// is embedded in the value, and the corresponding type is available in this binary.
     pub fn transcode_to_json(self) -> std::result::Result<Self, Self> {
         match self.encoded {
-            Encoded::Bincode(_) | Encoded::Multipart(_) => {
+            Encoded::Bincode(_)
+            | Encoded::Multipart(_)
+            | Encoded::Cbor(_)
+            | Encoded::MessagePack(_) => {
                 let json_value = match self.dump() {
                     Ok(json_value) => json_value,
                     Err(_) => return Err(self),
@@ -465,17 +718,35 @@ impl Any {
                 Ok(Self {
                     encoded: Encoded::Json(json_data.into()),
                     typehash: self.typehash,
+                    schema: self.schema,
                 })
             }
             Encoded::Json(_) => Ok(self),
         }
     }
 
+    /// Transcode the serialized value to the provided encoding. Like
+    /// [`Any::transcode_to_json`], this operation will succeed if the type hash
+    /// is embedded in the value, and the corresponding type is available in this
+    /// binary. The value is returned unchanged if it already has the encoding.
+    pub fn transcode(self, encoding: Encoding) -> std::result::Result<Self, Self> {
+        if self.encoding() == encoding {
+            return Ok(self);
+        }
+        let Some(typeinfo) = TYPE_INFO.get(&self.typehash) else {
+            return Err(self);
+        };
+        typeinfo.transcode(self.clone(), encoding).map_err(|_| self)
+    }
+
     /// Dump the Any message into a JSON value. This will succeed if: 1) the typehash is embedded
     /// in the serialized value; 2) the named type is linked into the binary.
     pub fn dump(&self) -> Result<serde_json::Value> {
         match &self.encoded {
-            Encoded::Bincode(_) | Encoded::Multipart(_) => {
+            Encoded::Bincode(_)
+            | Encoded::Multipart(_)
+            | Encoded::Cbor(_)
+            | Encoded::MessagePack(_) => {
                 let Some(typeinfo) = TYPE_INFO.get(&self.typehash) else {
                     return Err(Error::MissingTypeInfo(self.typehash));
                 };
@@ -495,6 +766,11 @@ impl Any {
         self.typehash
     }
 
+    /// The schema fingerprint of the serialized value, if its type has one.
+    pub fn schema(&self) -> Option<u64> {
+        self.schema
+    }
+
     /// The typename of the serialized value, if available.
     pub fn typename(&self) -> Option<&'static str> {
         TYPE_INFO
@@ -744,6 +1020,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_transcode() {
+        let data = TestDumpStruct {
+            a: "hello".to_string(),
+            b: 1234,
+            c: None,
+            d: Some(Part::from("part")),
+        };
+        for from in Encoding::iter() {
+            let serialized = Any::serialize_with_encoding(from, &data).unwrap();
+            for to in Encoding::iter() {
+                let transcoded = serialized.clone().transcode(to).unwrap();
+                assert_eq!(transcoded.encoding(), to);
+                assert_eq!(transcoded.schema(), serialized.schema());
+                assert_eq!(transcoded.deserialized::<TestDumpStruct>().unwrap(), data);
+            }
+        }
+
+        // Self-describing encodings carry field names, so that other
+        // languages can decode them without the Rust type.
+        let cbor = Any::serialize_with_encoding(Encoding::Cbor, &data).unwrap();
+        let value: ciborium::Value =
+            ciborium::from_reader(cbor.encoded.as_cbor().unwrap().as_ref()).unwrap();
+        let fields: Vec<_> = value
+            .as_map()
+            .unwrap()
+            .iter()
+            .map(|(key, _)| key.as_text().unwrap().to_string())
+            .collect();
+        assert_eq!(fields, ["a", "b", "c", "d"]);
+
+        let data = TestDumpStruct { d: None, ..data };
+        let msgpack = Any::serialize_with_encoding(Encoding::MessagePack, &data).unwrap();
+        let value: serde_json::Value =
+            rmp_serde::from_slice(msgpack.encoded.as_message_pack().unwrap()).unwrap();
+        assert_eq!(value["a"], "hello");
+        assert_eq!(value["b"], 1234);
+
+        // Unregistered types cannot be transcoded.
+        let unregistered =
+            Any::serialize_with_encoding(Encoding::Bincode, &v1::Reply { ok: true }).unwrap();
+        assert!(unregistered.transcode(Encoding::Cbor).is_err());
+
+        // Registered types without a transcoder report what was asked.
+        let typeinfo = TypeInfo {
+            typename: <TestDumpStruct as Named>::typename,
+            typehash: <TestDumpStruct as Named>::typehash,
+            typeid: <TestDumpStruct as Named>::typeid,
+            port: <TestDumpStruct as Named>::port,
+            dump: None,
+            transcode: None,
+            arm_unchecked: <TestDumpStruct as Named>::arm_unchecked,
+            endpoint_name: |_| None,
+        };
+        let serialized = Any::serialize_with_encoding(Encoding::Bincode, &data).unwrap();
+        let err = typeinfo.transcode(serialized, Encoding::Json).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::MissingTranscoder {
+                    from: Encoding::Bincode,
+                    to: Encoding::Json,
+                    ..
+                }
+            ),
+            "{err}"
+        );
+    }
+
     #[test]
     fn test_emplace_prefix() {
         let config = hyperactor_config::global::lock();
@@ -911,6 +1256,204 @@ mod tests {
         }
     }
 
+    mod v1 {
+        use serde::Deserialize;
+        use serde::Serialize;
+
+        #[derive(typeuri::Named, Serialize, Deserialize, Debug, PartialEq)]
+        #[named(schema, name = "wirevalue::tests::Request")]
+        pub struct Request {
+            pub id: u32,
+        }
+
+        #[derive(typeuri::Named, Serialize, Deserialize, Debug, PartialEq)]
+        #[named(schema, name = "wirevalue::tests::Reply")]
+        pub struct Reply {
+            pub ok: bool,
+        }
+    }
+
+    mod v2 {
+        use serde::Deserialize;
+        use serde::Serialize;
+
+        #[derive(typeuri::Named, Serialize, Deserialize, Debug, PartialEq)]
+        #[named(schema, name = "wirevalue::tests::Request")]
+        pub struct Request {
+            pub id: u64,
+            pub deadline: Option<u64>,
+        }
+
+        #[derive(typeuri::Named, Serialize, Deserialize, Debug, PartialEq)]
+        #[named(schema, name = "wirevalue::tests::Reply")]
+        pub struct Reply {
+            pub ok: bool,
+            pub message: String,
+        }
+    }
+
+    /// Mirrors the shape of `v1::Request`.
+    #[derive(typeuri::Named, Deserialize)]
+    #[named(schema)]
+    struct RequestV1 {
+        id: u32,
+    }
+
+    crate::register_migration!(v2::Request, RequestV1, |old| v2::Request {
+        id: old.id.into(),
+        deadline: None,
+    });
+
+    #[test]
+    fn test_schema_fingerprint() {
+        assert_eq!(TestDumpStruct::schema_fingerprint(), None);
+        assert_eq!(String::schema_fingerprint(), None);
+
+        let v1 = v1::Request::schema_fingerprint().unwrap();
+        assert_ne!(v1, v2::Request::schema_fingerprint().unwrap());
+        // The type name is not part of the shape.
+        assert_eq!(RequestV1::schema_fingerprint(), Some(v1));
+    }
+
+    #[test]
+    fn test_schema_fingerprint_nested() {
+        #[allow(dead_code)]
+        mod before {
+            #[derive(typeuri::Named)]
+            #[named(schema)]
+            pub struct Inner {
+                pub a: u32,
+            }
+
+            #[derive(typeuri::Named)]
+            #[named(schema)]
+            pub struct Outer {
+                pub inner: Option<Inner>,
+                pub path: std::path::PathBuf,
+            }
+        }
+
+        #[allow(dead_code)]
+        mod after {
+            #[derive(typeuri::Named)]
+            #[named(schema)]
+            pub struct Inner {
+                pub a: u32,
+                pub b: u32,
+            }
+
+            #[derive(typeuri::Named)]
+            #[named(schema)]
+            pub struct Outer {
+                pub inner: Option<Inner>,
+                pub path: std::path::PathBuf,
+            }
+        }
+
+        // Outer is written identically in both; only Inner changed.
+        assert_ne!(
+            before::Outer::schema_fingerprint(),
+            after::Outer::schema_fingerprint()
+        );
+
+        #[allow(dead_code)]
+        #[derive(typeuri::Named)]
+        #[named(schema)]
+        struct Tree {
+            children: Vec<Tree>,
+        }
+
+        // Recursive types terminate, and are stable.
+        let tree = Tree::schema_fingerprint();
+        assert!(tree.is_some());
+        assert_eq!(Tree::schema_fingerprint(), tree);
+    }
+
+    #[test]
+    fn test_schema_mismatch() {
+        for enc in Encoding::iter() {
+            let ser = Any::serialize_with_encoding(enc, &v1::Reply { ok: true }).unwrap();
+            assert_eq!(ser.schema(), v1::Reply::schema_fingerprint());
+            assert_eq!(
+                ser.deserialized::<v1::Reply>().unwrap(),
+                v1::Reply { ok: true }
+            );
+
+            let err = ser.deserialized::<v2::Reply>().unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    Error::SchemaMismatch {
+                        typename: "wirevalue::tests::Reply",
+                        expected,
+                        actual,
+                    } if Some(expected) == v2::Reply::schema_fingerprint()
+                        && Some(actual) == v1::Reply::schema_fingerprint()
+                ),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_schema_migration() {
+        for enc in Encoding::iter() {
+            let ser = Any::serialize_with_encoding(enc, &v1::Request { id: 7 }).unwrap();
+            assert_eq!(
+                ser.deserialized::<v2::Request>().unwrap(),
+                v2::Request {
+                    id: 7,
+                    deadline: None
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_pre_schema_any_layout() {
+        // The layout of Any before schema fingerprints were introduced.
+        #[derive(Serialize, Deserialize)]
+        enum LegacyEncoded {
+            Bincode(bytes::Bytes),
+            Json(bytes::Bytes),
+            Multipart(serde_multipart::Message),
+        }
+        #[derive(Serialize, Deserialize)]
+        struct LegacyAny {
+            encoded: LegacyEncoded,
+            typehash: u64,
+        }
+
+        let value = TestDumpStruct {
+            a: "hello".to_string(),
+            b: 1,
+            c: Some(2),
+            d: None,
+        };
+        let legacy = LegacyAny {
+            encoded: LegacyEncoded::Bincode(bincode::serialize(&value).unwrap().into()),
+            typehash: TestDumpStruct::typehash(),
+        };
+        let legacy_bytes = bincode::serialize(&legacy).unwrap();
+
+        // New peers decode values sent by old peers.
+        let decoded: Any = bincode::deserialize(&legacy_bytes).unwrap();
+        assert_eq!(decoded.schema(), None);
+        assert_eq!(decoded.deserialized::<TestDumpStruct>().unwrap(), value);
+
+        // Old peers decode unfingerprinted values sent by new peers.
+        let any = Any::serialize_with_encoding(Encoding::Bincode, &value).unwrap();
+        assert_eq!(bincode::serialize(&any).unwrap(), legacy_bytes);
+        let legacy: LegacyAny = bincode::deserialize(&legacy_bytes).unwrap();
+        assert!(matches!(legacy.encoded, LegacyEncoded::Bincode(_)));
+
+        // Fingerprinted values round trip with their fingerprint.
+        let any = Any::serialize_with_encoding(Encoding::Bincode, &v1::Request { id: 1 }).unwrap();
+        let decoded: Any = bincode::deserialize(&bincode::serialize(&any).unwrap()).unwrap();
+        assert_eq!(decoded, any);
+        assert_eq!(decoded.schema(), v1::Request::schema_fingerprint());
+    }
+
     #[test]
     fn test_broken_any() {
         let broken = Any::new_broken();